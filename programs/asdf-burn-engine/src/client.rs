@@ -0,0 +1,327 @@
+//! Off-chain Rust client (feature "no-entrypoint", implied by "cpi")
+//!
+//! PDA derivation mirrors the seeds in constants.rs so services never re-derive
+//! them by hand. Instructions are built from the Anchor-generated
+//! `crate::accounts::*` / `crate::instruction::*` types, which keeps account
+//! order and discriminators in lockstep with the program.
+//!
+//! ```ignore
+//! use asdf_burn_engine::{accounts, instruction, client};
+//!
+//! let ix = client::build_instruction(
+//!     accounts::AdminControl {
+//!         dat_state: client::find_dat_state().0,
+//!         admin,
+//!         admin_log: Some(client::find_admin_log().0),
+//!     },
+//!     instruction::EmergencyPause {},
+//! );
+//! ```
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use crate::constants::*;
+
+fn find(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(seeds, &crate::ID)
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// CORE PDAS
+// ══════════════════════════════════════════════════════════════════════════════
+
+pub fn find_dat_state() -> (Pubkey, u8) {
+    find(&[DAT_STATE_SEED])
+}
+
+pub fn find_dat_authority() -> (Pubkey, u8) {
+    find(&[DAT_AUTHORITY_SEED])
+}
+
+pub fn find_token_stats(mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[TOKEN_STATS_SEED, mint.as_ref()])
+}
+
+pub fn find_token_config(mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[TOKEN_CONFIG_SEED, mint.as_ref()])
+}
+
+pub fn find_root_treasury(root_mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[ROOT_TREASURY_SEED, root_mint.as_ref()])
+}
+
+pub fn find_admin_log() -> (Pubkey, u8) {
+    find(&[ADMIN_LOG_SEED])
+}
+
+pub fn find_change_queue() -> (Pubkey, u8) {
+    find(&[CHANGE_QUEUE_SEED])
+}
+
+pub fn find_governance() -> (Pubkey, u8) {
+    find(&[GOVERNANCE_SEED])
+}
+
+pub fn find_fee_split_proposal(id: u64) -> (Pubkey, u8) {
+    find(&[FEE_SPLIT_PROPOSAL_SEED, &id.to_le_bytes()])
+}
+
+pub fn find_vote_record(proposal: &Pubkey, voter: &Pubkey) -> (Pubkey, u8) {
+    find(&[VOTE_RECORD_SEED, proposal.as_ref(), voter.as_ref()])
+}
+
+pub fn find_vote_vault(proposal: &Pubkey) -> (Pubkey, u8) {
+    find(&[VOTE_VAULT_SEED, proposal.as_ref()])
+}
+
+pub fn find_stake_account(owner: &Pubkey) -> (Pubkey, u8) {
+    find(&[STAKE_SEED, owner.as_ref()])
+}
+
+pub fn find_stake_vault(stake_account: &Pubkey) -> (Pubkey, u8) {
+    find(&[STAKE_VAULT_SEED, stake_account.as_ref()])
+}
+
+pub fn find_token_registry() -> (Pubkey, u8) {
+    find(&[TOKEN_REGISTRY_SEED])
+}
+
+pub fn find_token_registry_page(page: u16) -> (Pubkey, u8) {
+    find(&[TOKEN_REGISTRY_PAGE_SEED, &page.to_le_bytes()])
+}
+
+pub fn find_root_set() -> (Pubkey, u8) {
+    find(&[ROOT_SET_SEED])
+}
+
+pub fn find_root_stream(mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[ROOT_STREAM_SEED, mint.as_ref()])
+}
+
+pub fn find_community_treasury(mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[COMMUNITY_TREASURY_SEED, mint.as_ref()])
+}
+
+pub fn find_rewards_vault(mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[REWARDS_VAULT_SEED, mint.as_ref()])
+}
+
+pub fn find_rewards_token_account(mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[REWARDS_TOKEN_SEED, mint.as_ref()])
+}
+
+pub fn find_reflection_claim(rewards_vault: &Pubkey, epoch: u64, holder: &Pubkey) -> (Pubkey, u8) {
+    find(&[REFLECTION_CLAIM_SEED, rewards_vault.as_ref(), &epoch.to_le_bytes(), holder.as_ref()])
+}
+
+pub fn find_cycle_state(mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[CYCLE_STATE_SEED, mint.as_ref()])
+}
+
+pub fn find_cycle_window(mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[CYCLE_WINDOW_SEED, mint.as_ref()])
+}
+
+pub fn find_cycle_history(mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[CYCLE_HISTORY_SEED, mint.as_ref()])
+}
+
+pub fn find_dca_state(mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[DCA_STATE_SEED, mint.as_ref()])
+}
+
+pub fn find_buy_commitment(mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[BUY_COMMIT_SEED, mint.as_ref()])
+}
+
+pub fn find_shadow_comparison(mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[SHADOW_COMPARISON_SEED, mint.as_ref()])
+}
+
+pub fn find_dat_token_account(mint: &Pubkey, nonce: u8) -> (Pubkey, u8) {
+    find(&[DAT_TOKEN_ACCOUNT_SEED, mint.as_ref(), &[nonce]])
+}
+
+pub fn find_stats_snapshot(day: i64) -> (Pubkey, u8) {
+    find(&[STATS_SNAPSHOT_SEED, &day.to_le_bytes()])
+}
+
+pub fn find_daily_stats(mint: &Pubkey, day: i64) -> (Pubkey, u8) {
+    find(&[DAILY_STATS_SEED, mint.as_ref(), &day.to_le_bytes()])
+}
+
+pub fn find_global_stats() -> (Pubkey, u8) {
+    find(&[GLOBAL_STATS_SEED])
+}
+
+pub fn find_partner_stats(partner: &Pubkey) -> (Pubkey, u8) {
+    find(&[PARTNER_STATS_SEED, partner.as_ref()])
+}
+
+pub fn find_alert_config() -> (Pubkey, u8) {
+    find(&[ALERT_CONFIG_SEED])
+}
+
+pub fn find_monitor_key(monitor: &Pubkey) -> (Pubkey, u8) {
+    find(&[MONITOR_KEY_SEED, monitor.as_ref()])
+}
+
+pub fn find_keeper_info(keeper: &Pubkey) -> (Pubkey, u8) {
+    find(&[KEEPER_SEED, keeper.as_ref()])
+}
+
+/// Cycle lookup table created by create_cycle_lut at `recent_slot` (owned by the ALT program)
+pub fn find_cycle_lut(recent_slot: u64) -> (Pubkey, u8) {
+    crate::helpers::derive_lookup_table_address(&find_dat_authority().0, recent_slot)
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// VALIDATOR PDAS
+// ══════════════════════════════════════════════════════════════════════════════
+
+pub fn find_validator_state(mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[VALIDATOR_STATE_SEED, mint.as_ref()])
+}
+
+pub fn find_validator_set() -> (Pubkey, u8) {
+    find(&[VALIDATOR_SET_SEED])
+}
+
+pub fn find_validator_bond(operator: &Pubkey) -> (Pubkey, u8) {
+    find(&[VALIDATOR_BOND_SEED, operator.as_ref()])
+}
+
+pub fn find_fee_attestation(mint: &Pubkey, end_slot: u64) -> (Pubkey, u8) {
+    find(&[FEE_ATTESTATION_SEED, mint.as_ref(), &end_slot.to_le_bytes()])
+}
+
+pub fn find_bounty_vault() -> (Pubkey, u8) {
+    find(&[BOUNTY_VAULT_SEED])
+}
+
+pub fn find_emergency_withdrawal() -> (Pubkey, u8) {
+    find(&[EMERGENCY_WITHDRAW_SEED])
+}
+
+pub fn find_reserve_vault() -> (Pubkey, u8) {
+    find(&[RESERVE_VAULT_SEED])
+}
+
+pub fn find_insurance_fund() -> (Pubkey, u8) {
+    find(&[INSURANCE_FUND_SEED])
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// EXTERNAL APP PDAS
+// ══════════════════════════════════════════════════════════════════════════════
+
+pub fn find_rebate_pool() -> (Pubkey, u8) {
+    find(&[REBATE_POOL_SEED])
+}
+
+pub fn find_rebate_epoch(epoch: u64) -> (Pubkey, u8) {
+    find(&[REBATE_EPOCH_SEED, &epoch.to_le_bytes()])
+}
+
+pub fn find_user_stats(user: &Pubkey) -> (Pubkey, u8) {
+    find(&[USER_STATS_SEED, user.as_ref()])
+}
+
+pub fn find_token_deposit(user: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[TOKEN_DEPOSIT_SEED, user.as_ref(), mint.as_ref()])
+}
+
+pub fn find_token_deposit_vault(token_deposit: &Pubkey) -> (Pubkey, u8) {
+    find(&[TOKEN_DEPOSIT_VAULT_SEED, token_deposit.as_ref()])
+}
+
+pub fn find_app_registry() -> (Pubkey, u8) {
+    find(&[APP_REGISTRY_SEED])
+}
+
+pub fn find_app_stats(app_id: u32) -> (Pubkey, u8) {
+    find(&[APP_STATS_SEED, &app_id.to_le_bytes()])
+}
+
+pub fn find_referral_stats(referrer: &Pubkey) -> (Pubkey, u8) {
+    find(&[REFERRAL_STATS_SEED, referrer.as_ref()])
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// INSTRUCTION BUILDERS
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// Build any program instruction from its `crate::accounts::*` struct and
+/// `crate::instruction::*` arguments (remaining accounts appended in order)
+pub fn build_instruction_with_remaining(
+    accounts: impl ToAccountMetas,
+    args: impl InstructionData,
+    remaining: Vec<AccountMeta>,
+) -> Instruction {
+    let mut metas = accounts.to_account_metas(None);
+    metas.extend(remaining);
+    Instruction {
+        program_id: crate::ID,
+        accounts: metas,
+        data: args.data(),
+    }
+}
+
+/// Build any program instruction from its `crate::accounts::*` struct and
+/// `crate::instruction::*` arguments
+pub fn build_instruction(accounts: impl ToAccountMetas, args: impl InstructionData) -> Instruction {
+    build_instruction_with_remaining(accounts, args, Vec::new())
+}
+
+/// initialize_token_stats: `payer` funds TokenStats rent for `mint`, listed on `registry_page`
+pub fn initialize_token_stats(mint: Pubkey, payer: Pubkey, registry_page: u16) -> Instruction {
+    build_instruction(
+        crate::accounts::InitializeTokenStats {
+            token_stats: find_token_stats(&mint).0,
+            token_registry: find_token_registry().0,
+            token_registry_page: find_token_registry_page(registry_page).0,
+            mint,
+            payer,
+            system_program: anchor_lang::system_program::ID,
+        },
+        crate::instruction::InitializeTokenStats {},
+    )
+}
+
+/// update_pending_fees: admin attributes `amount_lamports` of fees to `mint`
+pub fn update_pending_fees(admin: Pubkey, mint: Pubkey, amount_lamports: u64) -> Instruction {
+    build_instruction(
+        crate::accounts::UpdatePendingFees {
+            dat_state: find_dat_state().0,
+            token_stats: find_token_stats(&mint).0,
+            mint,
+            admin,
+        },
+        crate::instruction::UpdatePendingFees { amount_lamports },
+    )
+}
+
+/// Accounts for the AdminControl instructions (recorded in the AdminLog)
+fn admin_control(admin: Pubkey) -> crate::accounts::AdminControl {
+    crate::accounts::AdminControl {
+        dat_state: find_dat_state().0,
+        admin,
+        admin_log: Some(find_admin_log().0),
+    }
+}
+
+/// emergency_pause: admin halts all cycles
+pub fn emergency_pause(admin: Pubkey) -> Instruction {
+    build_instruction(
+        admin_control(admin),
+        crate::instruction::EmergencyPause {},
+    )
+}
+
+/// resume: admin lifts the emergency pause
+pub fn resume(admin: Pubkey) -> Instruction {
+    build_instruction(
+        admin_control(admin),
+        crate::instruction::Resume {},
+    )
+}
@@ -10,10 +10,10 @@ pub const ASDF_MINT: Pubkey = Pubkey::new_from_array([
     209, 16, 36, 3, 140, 127, 58, 109, 149, 250, 73, 0, 212, 5, 39, 95
 ]);
 
-/// Wrapped SOL mint
+/// Wrapped SOL mint: So11111111111111111111111111111111111111112
 pub const WSOL_MINT: Pubkey = Pubkey::new_from_array([
-    6, 221, 246, 225, 215, 101, 161, 147, 217, 203, 225, 70, 206, 235, 121, 172,
-    28, 180, 133, 237, 95, 91, 55, 145, 58, 140, 245, 133, 126, 255, 0, 169
+    6, 155, 136, 87, 254, 171, 129, 132, 251, 104, 127, 99, 70, 24, 192, 53,
+    218, 196, 57, 220, 26, 235, 59, 85, 152, 160, 240, 0, 0, 0, 0, 1
 ]);
 
 /// PumpSwap pool for ASDF (mainnet): DuhRX5JTPtsWU5n44t8tcFEfmzy2Eu27p4y6z8Rhf2bb
@@ -44,6 +44,13 @@ pub const TOKEN_2022_PROGRAM: Pubkey = Pubkey::new_from_array([
     190, 192, 170, 33, 225, 195, 158, 240, 26, 96, 235, 152, 242, 210, 242, 92
 ]);
 
+/// Pyth Solana Receiver program: rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ
+/// Owner of PriceUpdateV2 accounts used for USD burn accounting
+pub const PYTH_RECEIVER_PROGRAM: Pubkey = Pubkey::new_from_array([
+    12, 183, 250, 187, 82, 247, 166, 72, 187, 91, 49, 125, 154, 1, 139, 144,
+    87, 203, 2, 71, 116, 250, 254, 1, 230, 196, 223, 152, 204, 56, 88, 129
+]);
+
 /// Fee Program: pfeeUxB6jkeY1Hxd7CsFCAjcbHA9rWtchMGdZ6VojVZ
 pub const PUMP_FEE_PROGRAM: Pubkey = Pubkey::new_from_array([
     12, 53, 255, 169, 5, 90, 142, 86, 141, 168, 247, 188, 7, 86, 21, 39,
@@ -56,6 +63,12 @@ pub const MAYHEM_PROGRAM: Pubkey = Pubkey::new_from_array([
     90, 212, 160, 103, 22, 96, 103, 76, 78, 3, 69, 89, 128, 61, 101, 163
 ]);
 
+/// Metaplex Token Metadata program: metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s
+pub const MPL_TOKEN_METADATA_PROGRAM: Pubkey = Pubkey::new_from_array([
+    11, 112, 101, 177, 227, 209, 124, 69, 56, 157, 82, 127, 107, 4, 195, 205,
+    88, 184, 108, 115, 26, 160, 253, 181, 73, 182, 209, 188, 3, 248, 41, 70
+]);
+
 // ══════════════════════════════════════════════════════════════════════════════
 // PUMPSWAP CONFIG ACCOUNTS
 // ══════════════════════════════════════════════════════════════════════════════
@@ -66,14 +79,34 @@ pub const PUMPSWAP_GLOBAL_CONFIG: Pubkey = Pubkey::new_from_array([
     24, 213, 141, 69, 193, 234, 116, 137, 251, 55, 35, 217, 121, 60, 114, 166
 ]);
 
-/// PumpSwap Event Authority PDA: Ce6TQqeHC9p8KetsN6JsjHK7UTZk7nasjjnr7XxXp9F1
+/// PumpSwap Event Authority PDA: GS4CU59F31iL7aR2Q8zVS8DRrcRnXX1yjQ66TqNVQnaR
+/// seeds: ["__event_authority"] under PUMP_SWAP_PROGRAM
 pub const PUMPSWAP_EVENT_AUTHORITY: Pubkey = Pubkey::new_from_array([
+    229, 74, 112, 149, 40, 131, 159, 97, 192, 185, 184, 96, 121, 137, 28, 19,
+    146, 22, 228, 122, 113, 182, 47, 183, 59, 236, 114, 22, 148, 88, 116, 94
+]);
+
+/// PumpSwap Global Volume Accumulator PDA: C2aFPdENg4A2HQsmrd5rTw5TaYBX5Ku887cWjbFKtZpw
+/// seeds: ["global_volume_accumulator"] under PUMP_SWAP_PROGRAM
+pub const PUMPSWAP_GLOBAL_VOLUME_ACCUMULATOR: Pubkey = Pubkey::new_from_array([
+    163, 215, 187, 18, 126, 88, 173, 193, 44, 166, 143, 131, 67, 126, 194, 225,
+    195, 249, 130, 13, 233, 62, 88, 249, 23, 138, 41, 24, 221, 170, 247, 180
+]);
+
+// ══════════════════════════════════════════════════════════════════════════════
+// PUMP.FUN CONFIG ACCOUNTS
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// Pump.fun Event Authority PDA: Ce6TQqeHC9p8KetsN6JsjHK7UTZk7nasjjnr7XxXp9F1
+/// seeds: ["__event_authority"] under PUMP_PROGRAM
+pub const PUMP_EVENT_AUTHORITY: Pubkey = Pubkey::new_from_array([
     172, 241, 54, 235, 1, 252, 28, 78, 136, 61, 35, 200, 181, 132, 74, 181,
     154, 55, 246, 106, 221, 87, 197, 233, 172, 59, 83, 224, 89, 211, 92, 100
 ]);
 
-/// Global Volume Accumulator PDA: Hq2wp8uJ9jCPsYgNHex8RtqdvMPfVGoYwjvF1ATiwn2Y
-pub const PUMPSWAP_GLOBAL_VOLUME_ACCUMULATOR: Pubkey = Pubkey::new_from_array([
+/// Pump.fun Global Volume Accumulator PDA: Hq2wp8uJ9jCPsYgNHex8RtqdvMPfVGoYwjvF1ATiwn2Y
+/// seeds: ["global_volume_accumulator"] under PUMP_PROGRAM
+pub const PUMP_GLOBAL_VOLUME_ACCUMULATOR: Pubkey = Pubkey::new_from_array([
     250, 9, 17, 165, 72, 99, 65, 45, 99, 31, 78, 7, 135, 3, 41, 108,
     3, 95, 13, 19, 51, 160, 217, 200, 131, 141, 115, 183, 16, 254, 110, 45
 ]);
@@ -90,13 +123,8 @@ pub const PUMPSWAP_PROTOCOL_FEE_RECIPIENTS: [Pubkey; 1] = [
     ])
 ];
 
-/// SPL Protocol fee recipient: 6QgPshH1egekJ2TURfakiiApDdv98qfRuRe7RectX8xs
-pub const PROTOCOL_FEE_RECIPIENTS: [Pubkey; 1] = [
-    Pubkey::new_from_array([
-        80, 91, 86, 43, 240, 254, 69, 217, 123, 109, 178, 11, 165, 24, 224, 160,
-        197, 204, 48, 77, 217, 105, 172, 23, 142, 107, 116, 145, 130, 79, 179, 164
-    ]),
-];
+/// Maximum entries in the FeeRecipients rotation
+pub const MAX_FEE_RECIPIENTS: usize = 8;
 
 /// Mayhem Fee Recipient (Token2022): GesfTA3X2arioaHp8bbKdjG9vJtskViWACZoYvxp4twS
 pub const MAYHEM_FEE_RECIPIENT: Pubkey = Pubkey::new_from_array([
@@ -114,17 +142,22 @@ pub const MAYHEM_AGENT_WALLET: Pubkey = Pubkey::new_from_array([
 // DEV SUSTAINABILITY
 // ══════════════════════════════════════════════════════════════════════════════
 
-/// Dev sustainability wallet: dcW5uy7wKdKFxkhyBfPv3MyvrCkDcv1rWucoat13KH4
+/// Initial dev sustainability wallet: dcW5uy7wKdKFxkhyBfPv3MyvrCkDcv1rWucoat13KH4
 /// Receives 1% of secondary burns - keeps infrastructure running
 /// 1% today = 99% burns forever
+/// Live value: DATState.dev_wallet (rotated via propose_dev_wallet / accept_dev_wallet)
 pub const DEV_WALLET: Pubkey = Pubkey::new_from_array([
     9, 97, 12, 254, 90, 14, 23, 86, 57, 91, 82, 93, 3, 190, 97, 174,
     236, 104, 14, 8, 135, 85, 242, 4, 180, 76, 160, 246, 199, 117, 11, 155
 ]);
 
-/// Dev fee in basis points (100 = 1%)
+/// Initial dev fee in basis points (100 = 1%)
+/// Live value: DATState.dev_fee_bps (queued by propose_dev_fee, applied by execute_change)
 pub const DEV_FEE_BPS: u16 = 100;
 
+/// Absolute cap on the dev fee (2%)
+pub const MAX_DEV_FEE_BPS: u16 = 200;
+
 // ══════════════════════════════════════════════════════════════════════════════
 // PDA SEEDS
 // ══════════════════════════════════════════════════════════════════════════════
@@ -141,12 +174,180 @@ pub const TOKEN_STATS_SEED: &[u8] = b"token_stats_v1";
 /// Root Treasury PDA seed (receives 44.8% from secondaries)
 pub const ROOT_TREASURY_SEED: &[u8] = b"root_treasury";
 
+/// Root set PDA seed (additional roots beyond DATState.root_token_mint)
+pub const ROOT_SET_SEED: &[u8] = b"root_set_v1";
+
+/// Maximum number of additional roots in the RootSet
+pub const MAX_ROOTS: usize = 8;
+
+/// Token registry PDA seed: ["token_registry_v1"] (page count of the ecosystem token list)
+pub const TOKEN_REGISTRY_SEED: &[u8] = b"token_registry_v1";
+
+/// Token registry page PDA seed: ["token_registry_page_v1", page LE]
+pub const TOKEN_REGISTRY_PAGE_SEED: &[u8] = b"token_registry_page_v1";
+
+/// Mints per TokenRegistryPage
+pub const TOKEN_REGISTRY_PAGE_SIZE: usize = 64;
+
+/// Root stream PDA seed: ["root_stream_v1", mint] (accrued root share of a secondary)
+pub const ROOT_STREAM_SEED: &[u8] = b"root_stream_v1";
+
+/// Longest flush schedule a RootStream may use (cycles between flushes)
+pub const MAX_ROOT_FLUSH_EVERY_CYCLES: u16 = 100;
+
 /// Validator State PDA seed (trustless fee tracking)
 pub const VALIDATOR_STATE_SEED: &[u8] = b"validator_v1";
 
+/// PumpFun bonding curve seed (derived under PUMP_PROGRAM)
+pub const PUMP_BONDING_CURVE_SEED: &[u8] = b"bonding-curve";
+
+/// PumpFun Creator Vault seed (derived under PUMP_PROGRAM; note: hyphen)
+pub const PUMP_CREATOR_VAULT_SEED: &[u8] = b"creator-vault";
+
 /// PumpSwap Creator Vault seed (note: underscore, not hyphen)
 pub const PUMPSWAP_CREATOR_VAULT_SEED: &[u8] = b"creator_vault";
 
+/// Metaplex metadata seed: ["metadata", MPL_TOKEN_METADATA_PROGRAM, mint]
+pub const MPL_METADATA_SEED: &[u8] = b"metadata";
+
+/// Metaplex UpdateMetadataAccountV2 instruction index
+pub const MPL_UPDATE_METADATA_ACCOUNT_V2_DISCRIMINATOR: u8 = 15;
+
+/// Metaplex DataV2 field limits (bytes)
+pub const MPL_MAX_NAME_LEN: usize = 32;
+pub const MPL_MAX_SYMBOL_LEN: usize = 10;
+pub const MPL_MAX_URI_LEN: usize = 200;
+
+/// Community Treasury token PDA seed (receives the non-burned remainder per token)
+pub const COMMUNITY_TREASURY_SEED: &[u8] = b"community_treasury";
+
+/// Rewards vault PDA seed: ["rewards_vault_v1", mint] (holder reflection accounting)
+pub const REWARDS_VAULT_SEED: &[u8] = b"rewards_vault_v1";
+
+/// Rewards token PDA seed: ["rewards_tokens_v1", mint] (dat_authority-owned reflected tokens)
+pub const REWARDS_TOKEN_SEED: &[u8] = b"rewards_tokens_v1";
+
+/// Reflection claim PDA seed: ["reflection_claim_v1", rewards_vault, epoch LE, holder]
+pub const REFLECTION_CLAIM_SEED: &[u8] = b"reflection_claim_v1";
+
+/// Validator set PDA seed (M-of-N fee attestors)
+pub const VALIDATOR_SET_SEED: &[u8] = b"validator_set";
+
+/// Bug bounty escrow PDA seed (timelocked security payouts)
+pub const BOUNTY_VAULT_SEED: &[u8] = b"bounty_vault";
+
+/// Emergency withdrawal PDA seed (timelocked dat_authority recovery)
+pub const EMERGENCY_WITHDRAW_SEED: &[u8] = b"emergency_withdraw";
+
+/// Treasury SOL reserve PDA seed (rainy-day fund kept back from buybacks)
+pub const RESERVE_VAULT_SEED: &[u8] = b"reserve_vault_v1";
+
+/// Insurance fund PDA seed (reimbursements for users affected by program bugs)
+pub const INSURANCE_FUND_SEED: &[u8] = b"insurance_fund_v1";
+
+/// Validator bond PDA seed: ["validator_bond", operator]
+pub const VALIDATOR_BOND_SEED: &[u8] = b"validator_bond";
+
+/// Fee attestation PDA seed: ["fee_attestation", mint, end_slot LE]
+pub const FEE_ATTESTATION_SEED: &[u8] = b"fee_attestation";
+
+/// DCA State PDA seed (TWAP-style tranche buybacks per mint)
+pub const DCA_STATE_SEED: &[u8] = b"dca_v1";
+
+/// Buy commitment PDA seed (commit-reveal buy sizing per mint)
+pub const BUY_COMMIT_SEED: &[u8] = b"buy_commit_v1";
+
+/// Cycle window PDA seed (VRF-settled collect window per mint)
+pub const CYCLE_WINDOW_SEED: &[u8] = b"cycle_window_v1";
+
+/// Cycle State PDA seed (per-mint collect → buy → burn state machine)
+pub const CYCLE_STATE_SEED: &[u8] = b"cycle_v1";
+
+/// Cycle history PDA seed (per-mint ring buffer of recent cycles)
+pub const CYCLE_HISTORY_SEED: &[u8] = b"cycle_history_v1";
+
+/// Number of cycles kept in a CycleHistory ring buffer
+pub const CYCLE_HISTORY_LEN: usize = 32;
+
+/// Admin log PDA seed (ring buffer of recent admin operations)
+pub const ADMIN_LOG_SEED: &[u8] = b"admin_log_v1";
+
+/// Number of admin operations kept in the AdminLog ring buffer
+pub const ADMIN_LOG_LEN: usize = 64;
+
+/// Change queue PDA seed (timelocked parameter changes)
+pub const CHANGE_QUEUE_SEED: &[u8] = b"change_queue_v1";
+
+/// Number of parameter changes the ChangeQueue can hold at once
+pub const MAX_PENDING_CHANGES: usize = 16;
+
+/// Longest delay a queued parameter change may request (30 days)
+pub const MAX_CHANGE_DELAY: i64 = 30 * 24 * 3600;
+
+/// Governance PDA seed (community fee split voting settings)
+pub const GOVERNANCE_SEED: &[u8] = b"governance_v1";
+
+/// Fee split proposal PDA seed: ["fee_split_proposal_v1", id LE]
+pub const FEE_SPLIT_PROPOSAL_SEED: &[u8] = b"fee_split_proposal_v1";
+
+/// Vote record PDA seed: ["vote_v1", proposal, voter]
+pub const VOTE_RECORD_SEED: &[u8] = b"vote_v1";
+
+/// Vote vault seed: ["vote_vault_v1", proposal] (dat_authority-owned $ASDF escrow)
+pub const VOTE_VAULT_SEED: &[u8] = b"vote_vault_v1";
+
+/// Shortest governance voting period (1 day)
+pub const MIN_VOTING_PERIOD: i64 = 24 * 3600;
+
+/// Longest governance voting period (14 days)
+pub const MAX_VOTING_PERIOD: i64 = 14 * 24 * 3600;
+
+/// Stake account PDA seed: ["stake_v1", owner]
+pub const STAKE_SEED: &[u8] = b"stake_v1";
+
+/// Stake vault seed: ["stake_vault_v1", stake_account] (dat_authority-owned $ASDF escrow)
+pub const STAKE_VAULT_SEED: &[u8] = b"stake_vault_v1";
+
+/// Shortest stake lock (7 days)
+pub const MIN_STAKE_LOCK: i64 = 7 * 24 * 3600;
+
+/// Longest stake lock (365 days), earning the full boost
+pub const MAX_STAKE_LOCK: i64 = 365 * 24 * 3600;
+
+/// Rebate boost at MAX_STAKE_LOCK in bps of the base rebate (10000 = 2x on staked contributions)
+pub const MAX_STAKE_BOOST_BPS: u64 = 10_000;
+
+/// Shadow comparison PDA seed (old vs candidate buy math per mint)
+pub const SHADOW_COMPARISON_SEED: &[u8] = b"shadow_v1";
+
+/// Stats snapshot PDA seed: ["stats_snapshot_v1", day LE] (daily TokenStats Merkle root)
+pub const STATS_SNAPSHOT_SEED: &[u8] = b"stats_snapshot_v1";
+
+/// Daily stats PDA seed: ["daily_stats_v1", mint, day LE] (per-mint daily chart buckets)
+pub const DAILY_STATS_SEED: &[u8] = b"daily_stats_v1";
+
+/// Global stats roll-up PDA seed (aggregates recomputed from every TokenStats)
+pub const GLOBAL_STATS_SEED: &[u8] = b"global_stats_v1";
+
+/// Partner stats PDA seed: ["partner_stats_v1", partner] (listing partner revenue share)
+pub const PARTNER_STATS_SEED: &[u8] = b"partner_stats_v1";
+
+/// Alert config PDA seed (operational alert thresholds)
+pub const ALERT_CONFIG_SEED: &[u8] = b"alert_config_v1";
+
+/// Token config PDA seed (ecosystem directory metadata per mint)
+pub const TOKEN_CONFIG_SEED: &[u8] = b"token_config_v1";
+
+/// Monitor key PDA seed (per-key fee attribution quotas)
+pub const MONITOR_KEY_SEED: &[u8] = b"monitor_v1";
+
+/// Keeper PDA seed (registered crankers and their performance)
+pub const KEEPER_SEED: &[u8] = b"keeper_v1";
+
+/// Rotated DAT token account PDA seed: ["dat_token_account", mint, nonce]
+/// Replaces the dat_authority ATA after a compromise (non-ATA, nonce in seeds)
+pub const DAT_TOKEN_ACCOUNT_SEED: &[u8] = b"dat_token_account";
+
 // ══════════════════════════════════════════════════════════════════════════════
 // EXTERNAL APP INTEGRATION (Phase 2 Ready)
 // ══════════════════════════════════════════════════════════════════════════════
@@ -157,11 +358,32 @@ pub const USER_STATS_SEED: &[u8] = b"user_stats_v1";
 /// RebatePool PDA seed (self-sustaining rebate fund)
 pub const REBATE_POOL_SEED: &[u8] = b"rebate_pool";
 
-/// Burn share (99.448% → burn via DAT ATA)
+/// RebateEpoch PDA seed: ["rebate_epoch_v1", epoch LE] (pro-rata rebate epochs)
+pub const REBATE_EPOCH_SEED: &[u8] = b"rebate_epoch_v1";
+
+/// TokenDeposit PDA seed: ["token_deposit_v1", user, mint] (non-$ASDF fee escrow)
+pub const TOKEN_DEPOSIT_SEED: &[u8] = b"token_deposit_v1";
+
+/// TokenDeposit vault seed: ["token_deposit_vault", token_deposit] (dat_authority-owned)
+pub const TOKEN_DEPOSIT_VAULT_SEED: &[u8] = b"token_deposit_vault";
+
+/// AppRegistry PDA seed (registered integrating apps)
+pub const APP_REGISTRY_SEED: &[u8] = b"app_registry_v1";
+
+/// AppStats PDA seed: ["app_stats_v1", app_id LE] (per-app deposit attribution)
+pub const APP_STATS_SEED: &[u8] = b"app_stats_v1";
+
+/// FeeRecipients PDA seed: ["fee_recipients_v1"] (PumpFun protocol fee recipient rotation)
+pub const FEE_RECIPIENTS_SEED: &[u8] = b"fee_recipients_v1";
+
+/// ReferralStats PDA seed: ["referral_stats_v1", referrer]
+pub const REFERRAL_STATS_SEED: &[u8] = b"referral_stats_v1";
+
+/// Default burn share (99.448% → burn via DAT ATA)
 /// Using ÷100000 for exact precision
 pub const BURN_SHARE: u32 = 99448; // 99.448% exact
 
-/// Rebate share (0.552% → rebate pool)
+/// Default rebate share (0.552% → rebate pool), tunable in RebatePool
 /// Self-sustaining: always funded by deposits
 pub const REBATE_SHARE: u32 = 552; // 0.552% exact
 
@@ -172,10 +394,415 @@ pub const SHARE_DENOMINATOR: u64 = 100000;
 /// Market-regulated: TX_COST × 19 = efficiency threshold
 pub const MIN_DEPOSIT_SOL_EQUIV: u64 = 100_000_000; // 0.1 SOL
 
-/// Rebate eligibility threshold in lamports (~0.1 SOL in rebate pool)
+/// Default rebate eligibility threshold in lamports (~0.1 SOL in rebate pool)
 /// Market-regulated: TX_COST × 19 = efficiency threshold
 pub const REBATE_THRESHOLD_SOL_EQUIV: u64 = 100_000_000; // 0.1 SOL
 
+/// Maximum onboarding bonus rate on a first deposit (10%)
+pub const MAX_ONBOARDING_BONUS_BPS: u16 = 1000;
+
+/// Maximum per-user onboarding bonus (10,000 $ASDF, 6 decimals)
+pub const MAX_ONBOARDING_BONUS_CAP: u64 = 10_000_000_000;
+
+/// Maximum global onboarding bonus budget (10M $ASDF, 1% of supply)
+pub const MAX_ONBOARDING_BONUS_BUDGET: u64 = 10_000_000_000_000;
+
+/// Maximum tunable rebate share over SHARE_DENOMINATOR (5%)
+pub const MAX_REBATE_SHARE: u32 = 5_000;
+
+/// Rebate threshold bounds for update_rebate_params (0.01 SOL - 10 SOL)
+pub const MIN_REBATE_THRESHOLD: u64 = 10_000_000;
+pub const MAX_REBATE_THRESHOLD: u64 = 10_000_000_000;
+
+/// Slots between request_rebate_draw and the slot whose hash seeds it
+pub const DRAW_DELAY_SLOTS: u64 = 4;
+
+/// SlotHashes sysvar depth - a draw expires once its target slot hash is evicted
+pub const SLOT_HASHES_WINDOW: u64 = 512;
+
+/// Maximum candidate UserStats per rebate draw
+pub const MAX_DRAW_CANDIDATES: usize = 20;
+
+/// Minimum rebate epoch length before close_rebate_epoch (7 days)
+pub const REBATE_EPOCH_DURATION: i64 = 7 * SECONDS_PER_DAY;
+
+/// Maximum referrer share of a referred deposit's rebate portion (50%)
+pub const MAX_REFERRAL_SHARE_BPS: u16 = 5000;
+
+/// Delay after the latest token deposit before an unconverted escrow can be refunded (7 days)
+pub const TOKEN_DEPOSIT_REFUND_DELAY: i64 = 7 * SECONDS_PER_DAY;
+
+/// Delay between request_offboard and offboard_token (7 days, in-flight cycles settle)
+pub const OFFBOARD_TIMELOCK: i64 = 7 * SECONDS_PER_DAY;
+
+/// Hard cap on a listing partner's share of collected fees (5%)
+pub const MAX_PARTNER_SHARE_BPS: u16 = 500;
+
+// ══════════════════════════════════════════════════════════════════════════════
+// ALERT KINDS (AlertRaised.kind)
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// dat_authority balance below AlertConfig.min_authority_balance
+pub const ALERT_LOW_AUTHORITY_BALANCE: u8 = 0;
+
+/// DATState.consecutive_failures above AlertConfig.max_consecutive_failures
+pub const ALERT_CONSECUTIVE_FAILURES: u8 = 1;
+
+/// Rebate pool balance below AlertConfig.min_rebate_pool_balance
+pub const ALERT_LOW_REBATE_POOL: u8 = 2;
+
+// ══════════════════════════════════════════════════════════════════════════════
+// ADMIN OPERATIONS (AdminLogEntry.op / PendingChange.param, 0 = unwritten slot)
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// set_root_token (subject = new root mint)
+pub const ADMIN_OP_SET_ROOT_TOKEN: u8 = 1;
+
+/// fee_split_bps (queued by update_fee_split; direct changes in older log entries)
+pub const ADMIN_OP_FEE_SPLIT: u8 = 2;
+
+/// emergency_pause
+pub const ADMIN_OP_PAUSE: u8 = 3;
+
+/// resume
+pub const ADMIN_OP_RESUME: u8 = 4;
+
+/// min_fees_threshold (queued)
+pub const ADMIN_OP_MIN_FEES: u8 = 5;
+
+/// max_fees_per_cycle (queued)
+pub const ADMIN_OP_MAX_FEES: u8 = 6;
+
+/// slippage_bps (queued)
+pub const ADMIN_OP_SLIPPAGE: u8 = 7;
+
+/// min_cycle_interval (queued)
+pub const ADMIN_OP_MIN_INTERVAL: u8 = 8;
+
+/// auto_resume_after (queued)
+pub const ADMIN_OP_AUTO_RESUME_AFTER: u8 = 9;
+
+/// auto_pause_failure_threshold (queued)
+pub const ADMIN_OP_FAILURE_THRESHOLD: u8 = 10;
+
+/// force_unlock (subject = released mint)
+pub const ADMIN_OP_FORCE_UNLOCK: u8 = 11;
+
+/// import_config (queued by propose_import_config, value = blob version)
+pub const ADMIN_OP_IMPORT_CONFIG: u8 = 12;
+
+/// propose_dev_fee before it was queued (kept for existing log entries)
+pub const ADMIN_OP_DEV_FEE_PROPOSED: u8 = 13;
+
+/// dev_fee_bps (queued by propose_dev_fee)
+pub const ADMIN_OP_DEV_FEE: u8 = 14;
+
+/// reserve_bps (queued)
+pub const ADMIN_OP_RESERVE_BPS: u8 = 15;
+
+/// insurance_bps (queued)
+pub const ADMIN_OP_INSURANCE_BPS: u8 = 16;
+
+/// randomized_timing (queued, 0 = off / 1 = on)
+pub const ADMIN_OP_RANDOMIZED_TIMING: u8 = 17;
+
+/// max_jito_tip_lamports (queued)
+pub const ADMIN_OP_MAX_JITO_TIP: u8 = 18;
+
+/// max_price_deviation_bps (queued by update_price_guard)
+pub const ADMIN_OP_PRICE_GUARD: u8 = 19;
+
+/// last_known_price cleared (queued by update_price_guard, value = 1)
+pub const ADMIN_OP_PRICE_OBSERVATION_RESET: u8 = 20;
+
+/// TokenStats.burn_bps (queued by set_burn_ratio, subject = mint)
+pub const ADMIN_OP_BURN_RATIO: u8 = 21;
+
+/// TokenStats.dust_threshold (queued by set_dust_threshold, subject = mint)
+pub const ADMIN_OP_DUST_THRESHOLD: u8 = 22;
+
+/// Largest dust_threshold set_dust_threshold accepts (1,000 tokens at 6 decimals)
+pub const MAX_DUST_THRESHOLD: u64 = 1_000_000_000;
+
+/// ValidatorState.max_fee_per_slot (queued by update_validator_limits, subject = mint)
+pub const ADMIN_OP_VALIDATOR_MAX_FEE: u8 = 23;
+
+/// ValidatorState.max_tx_per_slot (queued by update_validator_limits, subject = mint)
+pub const ADMIN_OP_VALIDATOR_MAX_TX: u8 = 24;
+
+/// RebatePool.referral_share_bps (queued by set_referral_share)
+pub const ADMIN_OP_REFERRAL_SHARE: u8 = 25;
+
+/// RebatePool.bonus_bps (queued by configure_onboarding_bonus)
+pub const ADMIN_OP_ONBOARDING_BONUS_BPS: u8 = 26;
+
+/// RebatePool.bonus_cap (queued by configure_onboarding_bonus)
+pub const ADMIN_OP_ONBOARDING_BONUS_CAP: u8 = 27;
+
+/// RebatePool.bonus_budget (queued by configure_onboarding_bonus)
+pub const ADMIN_OP_ONBOARDING_BONUS_BUDGET: u8 = 28;
+
+/// AlertConfig.min_authority_balance (queued by configure_alerts)
+pub const ADMIN_OP_ALERT_MIN_AUTHORITY_BALANCE: u8 = 29;
+
+/// AlertConfig.max_consecutive_failures (queued by configure_alerts)
+pub const ADMIN_OP_ALERT_MAX_CONSECUTIVE_FAILURES: u8 = 30;
+
+/// AlertConfig.min_rebate_pool_balance (queued by configure_alerts)
+pub const ADMIN_OP_ALERT_MIN_REBATE_POOL_BALANCE: u8 = 31;
+
+/// TokenStats.reflection_bps (queued by set_reflection_ratio, subject = mint)
+pub const ADMIN_OP_REFLECTION_RATIO: u8 = 32;
+
+/// TokenStats.liquidity_bps (queued by set_liquidity_ratio, subject = mint)
+pub const ADMIN_OP_LIQUIDITY_RATIO: u8 = 33;
+
+// ══════════════════════════════════════════════════════════════════════════════
+// MINT RISK FLAGS (Token2022 extension screening)
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// Mint has a freeze authority (can freeze the DAT token account)
+pub const MINT_RISK_FREEZE_AUTHORITY: u8 = 1 << 0;
+
+/// Token2022 PermanentDelegate - delegate can move or burn any holder's tokens
+pub const MINT_RISK_PERMANENT_DELEGATE: u8 = 1 << 1;
+
+/// Token2022 TransferHook - arbitrary program invoked on every transfer
+pub const MINT_RISK_TRANSFER_HOOK: u8 = 1 << 2;
+
+/// Token2022 DefaultAccountState = Frozen - new accounts start frozen
+pub const MINT_RISK_DEFAULT_FROZEN: u8 = 1 << 3;
+
+/// Token2022 NonTransferable - tokens cannot be bought into the DAT account
+pub const MINT_RISK_NON_TRANSFERABLE: u8 = 1 << 4;
+
+/// Token2022 Pausable - authority can halt all transfers
+pub const MINT_RISK_PAUSABLE: u8 = 1 << 5;
+
+/// Token2022 TransferFeeConfig - transfers deliver less than the sent amount
+pub const MINT_RISK_TRANSFER_FEE: u8 = 1 << 6;
+
+/// Flags that make a mint unacceptable (treasury can be confiscated or never funded)
+pub const MINT_RISK_REJECT_MASK: u8 = MINT_RISK_PERMANENT_DELEGATE | MINT_RISK_NON_TRANSFERABLE;
+
+// ══════════════════════════════════════════════════════════════════════════════
+// FEE ATTESTATION (optimistic Merkle commitments)
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// Maximum attestors in the validator set
+pub const MAX_ATTESTORS: usize = 8;
+
+/// Domain prefix of the message attestors sign (see fee_attestation_message)
+pub const FEE_ATTESTATION_DOMAIN: &[u8] = b"asdf_fee_attestation_v1";
+
+/// Challenge window after an attestation is posted (~5 minutes)
+pub const ATTESTATION_CHALLENGE_WINDOW_SLOTS: u64 = 750;
+
+/// Default attested fee cap per slot (0.01 SOL)
+pub const DEFAULT_MAX_FEE_PER_SLOT: u64 = 10_000_000;
+
+/// Default attested transaction cap per slot
+pub const DEFAULT_MAX_TX_PER_SLOT: u32 = 100;
+
+/// Protocol ceiling for update_validator_limits fee cap (1 SOL per slot)
+pub const MAX_FEE_PER_SLOT_LIMIT: u64 = 1_000_000_000;
+
+/// Protocol ceiling for update_validator_limits transaction cap
+pub const MAX_TX_PER_SLOT_LIMIT: u32 = 1_000;
+
+/// Maximum entries per register_validated_fees_batch (3 accounts each)
+pub const MAX_FEE_BATCH_SIZE: usize = 10;
+
+/// Maximum tokens per collect_fees_batch
+pub const MAX_COLLECT_BATCH_SIZE: usize = 20;
+
+/// Maximum secondaries per run_ecosystem_cycle (6 remaining accounts each)
+pub const MAX_ECOSYSTEM_SECONDARIES: usize = 4;
+
+/// Maximum Merkle proof depth (2^20 leaves, well above 100 TX/slot * 1000 slots)
+pub const MAX_MERKLE_PROOF_DEPTH: usize = 20;
+
+/// Maximum creator fee a single transaction can plausibly generate (1 SOL)
+pub const MAX_FEE_PER_TX: u64 = 1_000_000_000;
+
+/// Fraud reasons reported by challenge_attestation
+pub const FRAUD_SLOT_OUT_OF_RANGE: u8 = 1;
+pub const FRAUD_INDEX_BEYOND_COUNT: u8 = 2;
+pub const FRAUD_FEE_TOO_HIGH: u8 = 3;
+pub const FRAUD_DUPLICATE_TX: u8 = 4;
+
+/// Minimum stake for a bonded (permissionless) attestation poster (1 SOL)
+pub const MIN_VALIDATOR_BOND: u64 = 1_000_000_000;
+
+/// Share of a bond burned per proven fraudulent attestation (50%)
+pub const SLASH_BPS: u16 = 5000;
+
+/// Incinerator: 1nc1nerator11111111111111111111111111111111
+/// Lamports credited here are destroyed (slashed bonds are burned, not redistributed)
+pub const INCINERATOR: Pubkey = Pubkey::new_from_array([
+    0, 51, 144, 114, 141, 52, 17, 96, 121, 189, 201, 17, 191, 255, 0, 219,
+    212, 77, 46, 205, 204, 247, 156, 166, 225, 0, 56, 225, 0, 0, 0, 0
+]);
+
+// ══════════════════════════════════════════════════════════════════════════════
+// VENUE SELECTION (best execution)
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// PumpFun bonding curve (execute_buy / execute_buy_secondary)
+pub const VENUE_BONDING_CURVE: u8 = 0;
+
+/// PumpSwap AMM pool (execute_buy_amm)
+pub const VENUE_PUMPSWAP: u8 = 1;
+
+/// Maximum AMM pools quoted by select_best_venue (3 accounts each)
+pub const MAX_VENUE_POOLS: usize = 4;
+
+// ══════════════════════════════════════════════════════════════════════════════
+// PRICE ORACLE (USD burn accounting)
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// Pyth SOL/USD price feed id
+/// 0xef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d
+pub const SOL_USD_FEED_ID: [u8; 32] = [
+    239, 13, 139, 111, 218, 44, 235, 164, 29, 161, 93, 64, 149, 209, 218, 57,
+    42, 13, 47, 142, 208, 198, 199, 188, 15, 76, 250, 200, 194, 128, 181, 109
+];
+
+/// PriceUpdateV2 account discriminator: sha256("account:PriceUpdateV2")[..8]
+pub const PYTH_PRICE_UPDATE_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+
+/// Maximum oracle price age at burn time (60 seconds)
+pub const ORACLE_MAX_AGE_SECONDS: i64 = 60;
+
+/// Decimals of USD accumulators (micro-USD)
+pub const USD_DECIMALS: u8 = 6;
+
+// ══════════════════════════════════════════════════════════════════════════════
+// RANDOMIZED CYCLE TIMING (Switchboard On-Demand randomness)
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// Switchboard On-Demand program: SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv
+/// Owner of the RandomnessAccountData accounts backing cycle windows
+#[cfg(not(feature = "testing"))]
+pub const SWITCHBOARD_ON_DEMAND_PROGRAM: Pubkey = Pubkey::new_from_array([
+    6, 115, 189, 70, 242, 228, 126, 4, 241, 43, 217, 47, 183, 49, 150, 142,
+    205, 157, 151, 87, 194, 116, 218, 135, 71, 111, 70, 92, 4, 12, 101, 115
+]);
+
+/// Switchboard On-Demand devnet program: Aio4gaXjXzJNVLtzwtNVmSqGKpANtXhybbkhtAC94ji2
+#[cfg(feature = "testing")]
+pub const SWITCHBOARD_ON_DEMAND_PROGRAM: Pubkey = Pubkey::new_from_array([
+    144, 110, 20, 100, 197, 248, 183, 99, 60, 192, 90, 66, 76, 221, 179, 174,
+    205, 109, 171, 184, 174, 199, 71, 188, 79, 62, 17, 48, 30, 64, 99, 203
+]);
+
+/// RandomnessAccountData discriminator: sha256("account:RandomnessAccountData")[..8]
+pub const SWITCHBOARD_RANDOMNESS_DISCRIMINATOR: [u8; 8] = [10, 66, 229, 135, 220, 239, 217, 114];
+
+/// Fixed delay between request_cycle_randomness and the earliest possible window (1 hour)
+pub const CYCLE_WINDOW_MIN_DELAY: i64 = 3600;
+
+/// Range the VRF value spreads the window start over, after the fixed delay (12 hours)
+pub const CYCLE_WINDOW_SPREAD: u64 = 12 * 3600;
+
+/// How long a settled window stays open for collect_fees (30 minutes)
+pub const CYCLE_WINDOW_DURATION: i64 = 1800;
+
+/// Time after which an unrevealed randomness request may be replaced (1 hour)
+pub const CYCLE_RANDOMNESS_TIMEOUT: i64 = 3600;
+
+// ══════════════════════════════════════════════════════════════════════════════
+// JITO TIPS
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// Jito block engine tip accounts (mainnet); cycle tips may only be paid to one of these
+pub const JITO_TIP_ACCOUNTS: [Pubkey; 8] = [
+    // 96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5
+    Pubkey::new_from_array([
+        120, 82, 28, 177, 121, 206, 187, 133, 137, 181, 86, 162, 213, 236, 148, 210,
+        73, 134, 130, 253, 249, 187, 42, 245, 173, 100, 228, 145, 204, 65, 83, 218
+    ]),
+    // HFqU5x63VTqvQss8hp11i4wVV8bD44PvwucfZ2bU7gRe
+    Pubkey::new_from_array([
+        241, 135, 236, 135, 209, 247, 69, 203, 58, 3, 56, 74, 38, 166, 158, 218,
+        12, 162, 209, 170, 15, 65, 228, 36, 22, 55, 126, 145, 255, 91, 93, 49
+    ]),
+    // Cw8CFyM9FkoMi7K7Crf6HNQqf4uEMzpKw6QNghXLvLkY
+    Pubkey::new_from_array([
+        177, 78, 13, 229, 94, 159, 186, 134, 57, 110, 191, 213, 72, 207, 248, 201,
+        32, 17, 234, 199, 183, 91, 170, 155, 45, 156, 106, 134, 245, 161, 113, 65
+    ]),
+    // ADaUMid9yfUytqMBgopwjb2DTLSokTSzL1zt6iGPaS49
+    Pubkey::new_from_array([
+        136, 241, 255, 163, 162, 223, 230, 23, 189, 196, 227, 87, 50, 81, 163, 34,
+        227, 252, 174, 129, 229, 164, 87, 57, 14, 100, 117, 28, 0, 164, 101, 226
+    ]),
+    // DfXygSm4jCyNCybVYYK6DwvWqjKee8pbDmJGcLWNDXjh
+    Pubkey::new_from_array([
+        188, 43, 87, 6, 94, 241, 221, 102, 84, 48, 190, 96, 107, 166, 89, 108,
+        2, 149, 48, 27, 173, 239, 139, 90, 252, 65, 1, 65, 80, 244, 18, 116
+    ]),
+    // ADuUkR4vqLUMWXxW9gh6D6L8pMSawimctcNZ5pGwDcEt
+    Pubkey::new_from_array([
+        137, 7, 125, 85, 165, 187, 19, 48, 118, 62, 183, 103, 245, 94, 192, 119,
+        180, 26, 13, 7, 95, 125, 225, 215, 63, 186, 202, 60, 99, 213, 84, 113
+    ]),
+    // DttWaMuVvTiduZRnguLF7jNxTgiMBZ1hyAumKUiL2KRL
+    Pubkey::new_from_array([
+        191, 151, 27, 89, 16, 139, 91, 133, 160, 79, 176, 147, 241, 226, 27, 78,
+        63, 212, 196, 200, 244, 135, 221, 9, 185, 87, 82, 118, 159, 13, 216, 195
+    ]),
+    // 3AVi9Tg9Uo68tJfuvoKvqKNWKkC5wPdSSdeBnizKZ6jT
+    Pubkey::new_from_array([
+        32, 38, 16, 30, 194, 3, 40, 150, 74, 50, 171, 171, 19, 108, 84, 5,
+        185, 31, 58, 227, 142, 228, 246, 76, 182, 189, 232, 121, 184, 104, 56, 210
+    ]),
+];
+
+/// Hard ceiling on the configurable per-instruction tip (0.01 SOL)
+pub const MAX_JITO_TIP_LAMPORTS: u64 = 10_000_000;
+
+// ══════════════════════════════════════════════════════════════════════════════
+// ADDRESS LOOKUP TABLES
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// Address Lookup Table program: AddressLookupTab1e1111111111111111111111111
+pub const ADDRESS_LOOKUP_TABLE_PROGRAM: Pubkey = Pubkey::new_from_array([
+    2, 119, 166, 175, 151, 51, 155, 122, 200, 141, 24, 146, 201, 4, 70, 245,
+    0, 2, 48, 146, 102, 246, 46, 83, 193, 24, 36, 73, 130, 0, 0, 0
+]);
+
+/// ProgramInstruction::CreateLookupTable tag (bincode u32)
+pub const ALT_CREATE_LOOKUP_TABLE_TAG: u32 = 0;
+
+/// ProgramInstruction::ExtendLookupTable tag (bincode u32)
+pub const ALT_EXTEND_LOOKUP_TABLE_TAG: u32 = 2;
+
+/// Maximum addresses extend_cycle_lut adds per instruction (keeps the transaction under 1232 bytes)
+pub const MAX_LUT_EXTEND_ADDRESSES: usize = 20;
+
+// ══════════════════════════════════════════════════════════════════════════════
+// ACCOUNT LAYOUT VERSIONS (see migrations::MIGRATIONS)
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// Current DATState layout (V1 382 bytes ... V17 with version, V19 zero-copy)
+pub const DAT_STATE_VERSION: u8 = 19;
+
+/// Current TokenStats layout (V1 114 bytes ... V12 with partner_paid_pending, V13 zero-copy)
+pub const TOKEN_STATS_VERSION: u8 = 13;
+
+/// Current TokenConfig layout (V1 145 bytes ... V5 with offboarding request)
+pub const TOKEN_CONFIG_VERSION: u8 = 5;
+
+/// Current RebatePool layout (V1 89 bytes ... V6 with version)
+pub const REBATE_POOL_VERSION: u8 = 6;
+
+/// Current UserStats layout (V1 81 bytes ... V6 with version)
+pub const USER_STATS_VERSION: u8 = 6;
+
+/// Current ChangeQueue layout (V1 577 bytes, V2 with version and change subjects)
+pub const CHANGE_QUEUE_VERSION: u8 = 2;
+
 // ══════════════════════════════════════════════════════════════════════════════
 // INSTRUCTION DISCRIMINATORS (8-byte hashes)
 // ══════════════════════════════════════════════════════════════════════════════
@@ -196,6 +823,12 @@ pub const PUMPSWAP_BUY_DISCRIMINATOR: [u8; 8] = [102, 6, 61, 18, 1, 218, 235, 23
 /// PumpSwap collect_coin_creator_fee instruction discriminator
 pub const PUMPSWAP_COLLECT_CREATOR_FEE_DISCRIMINATOR: [u8; 8] = [160, 57, 89, 42, 181, 139, 43, 66];
 
+/// PumpSwap set_coin_creator instruction discriminator
+pub const PUMPSWAP_SET_COIN_CREATOR_DISCRIMINATOR: [u8; 8] = [210, 149, 128, 45, 188, 58, 78, 175];
+
+/// PumpSwap AMM deposit (add liquidity) instruction discriminator
+pub const PUMPSWAP_DEPOSIT_DISCRIMINATOR: [u8; 8] = [242, 35, 198, 137, 82, 225, 242, 182];
+
 // ══════════════════════════════════════════════════════════════════════════════
 // FLUSH THRESHOLDS
 // ══════════════════════════════════════════════════════════════════════════════
@@ -214,15 +847,50 @@ pub const MAX_FEES_PER_CYCLE: u64 = 69_420_000_000_000;
 /// Slippage protection (5%) - prevents unfavorable execution
 pub const INITIAL_SLIPPAGE_BPS: u16 = 500;
 
+/// Price circuit breaker: max deviation from last observed root price (30%)
+/// 0 disables the check
+pub const DEFAULT_MAX_PRICE_DEVIATION_BPS: u16 = 3000;
+
+/// Fixed-point scale for observed prices (lamports per token base unit * 1e9)
+pub const PRICE_SCALE: u128 = 1_000_000_000;
+
 /// Minimum interval between flushes (60 seconds)
 /// Prevents spam while allowing responsive execution
 pub const MIN_CYCLE_INTERVAL: i64 = 60;
 
+/// Quota window for monitor key fee attribution (1 day)
+pub const SECONDS_PER_DAY: i64 = 86_400;
+
 /// Maximum pending fees per token (69 SOL)
 /// ~6900 trades at 0.01 SOL each - well beyond typical daemon sync interval
 /// Prevents accumulation overflow and ensures fair distribution
 pub const MAX_PENDING_FEES: u64 = 69_000_000_000;
 
+/// Maximum bytes of the portable config blob per ConfigExported event
+pub const CONFIG_EXPORT_CHUNK_SIZE: usize = 512;
+
+// ══════════════════════════════════════════════════════════════════════════════
+// COMPUTE BUDGET GUARDS
+// ══════════════════════════════════════════════════════════════════════════════
+// Minimum compute units that must remain before each phase starts, so a
+// handler aborts with InsufficientComputeRemaining instead of running out
+// mid-CPI. Sized from devnet traces with ~25% headroom.
+
+/// Creator fee collection, partner payout and root treasury forwarding
+pub const CU_PHASE_COLLECT: u64 = 60_000;
+
+/// Secondary fee split transfer to the root treasury
+pub const CU_PHASE_FEE_SPLIT: u64 = 10_000;
+
+/// PumpFun bonding curve buy CPI plus balance reload
+pub const CU_PHASE_PUMPFUN_BUY: u64 = 100_000;
+
+/// PumpSwap AMM buy CPI plus balance reload
+pub const CU_PHASE_AMM_BUY: u64 = 140_000;
+
+/// Burn, optional community treasury transfer and stats payer refund
+pub const CU_PHASE_BURN: u64 = 40_000;
+
 // ══════════════════════════════════════════════════════════════════════════════
 // BURN CYCLE RESERVES
 // ══════════════════════════════════════════════════════════════════════════════
@@ -233,16 +901,96 @@ pub const RENT_EXEMPT_MINIMUM: u64 = 890_880;
 /// Safety buffer for transactions (~0.00005 SOL)
 pub const SAFETY_BUFFER: u64 = 50_000;
 
+/// Minimum dat_authority balance kept after the TokenStats payer refund
+pub const PAYER_REFUND_RESERVE: u64 = RENT_EXEMPT_MINIMUM + SAFETY_BUFFER;
+
+/// Headroom above dat_authority's rent floor that top_up_authority may restore
+pub const AUTHORITY_TOP_UP_HEADROOM: u64 = 5_000;
+
+/// Delay between propose_emergency_withdraw and execute_emergency_withdraw (72h)
+pub const EMERGENCY_WITHDRAW_TIMELOCK: i64 = 72 * 3600;
+
+/// Delay between propose_reserve_deployment and deploy_reserve (48h)
+pub const RESERVE_DEPLOY_TIMELOCK: i64 = 48 * 3600;
+
+/// Delay between propose_insurance_payout and execute_insurance_payout (72h, guardian veto window)
+pub const INSURANCE_PAYOUT_TIMELOCK: i64 = 72 * 3600;
+
 /// ATA rent reserve (~0.0021 SOL)
 pub const ATA_RENT_RESERVE: u64 = 2_100_000;
 
+/// Account size used for the dynamic ATA reserve (Token2022 ATA with ImmutableOwner, >= SPL 165)
+pub const ATA_RESERVE_ACCOUNT_LEN: usize = 170;
+
 /// Minimum fees before split is worthwhile (~0.1 SOL)
 /// Market-regulated: aligned with FLUSH_THRESHOLD for consistency
 pub const MIN_FEES_FOR_SPLIT: u64 = 100_000_000;
 
+/// Minimum SOL reserves (0.01 SOL) a pool needs to be bought from or to pass try_resume's probe
+pub const MIN_POOL_LIQUIDITY: u64 = 10_000_000;
+
+/// PumpFun token supply (6 decimals): 1B on standard curves
+pub const PUMP_TOKEN_TOTAL_SUPPLY: u64 = 1_000_000_000_000_000;
+
+/// Mayhem Mode curves mint 2B (1B + 1B for the Mayhem agent)
+pub const PUMP_MAYHEM_TOKEN_TOTAL_SUPPLY: u64 = 2_000_000_000_000_000;
+
+/// Default consecutive record_failure calls that trip the automatic emergency pause
+pub const DEFAULT_AUTO_PAUSE_FAILURE_THRESHOLD: u8 = 5;
+
+/// Bounds for update_failure_threshold
+pub const MIN_AUTO_PAUSE_FAILURE_THRESHOLD: u8 = 2;
+pub const MAX_AUTO_PAUSE_FAILURE_THRESHOLD: u8 = 20;
+
+/// Consecutive failures of one token that pause only that token
+pub const TOKEN_PAUSE_FAILURE_THRESHOLD: u8 = 3;
+
+/// Keeper reward tiers: (minimum successful cycles, reward bps of the cycle's SOL)
+pub const KEEPER_REWARD_TIERS: [(u64, u16); 3] = [(0, 10), (100, 20), (1_000, 30)];
+
+/// Window (1 hour) in which failures of distinct tokens count toward the global pause
+pub const FAILURE_ESCALATION_WINDOW: i64 = 3600;
+
+/// Distinct tokens failing within FAILURE_ESCALATION_WINDOW that trip the global pause
+pub const FAILURE_ESCALATION_MIN_TOKENS: u8 = 2;
+
 /// Minimum buy amount (~0.0001 SOL)
 pub const MINIMUM_BUY_AMOUNT: u64 = 100_000;
 
+/// Maximum number of tranches in a DCA plan
+pub const MAX_DCA_TRANCHES: u8 = 24;
+
+/// Minimum slot spacing between DCA tranches (~4 seconds)
+pub const MIN_DCA_SLOT_SPACING: u64 = 10;
+
+/// Slots after commit_buy before reveal_and_buy may land (never in the commit's own slot)
+pub const BUY_REVEAL_MIN_DELAY_SLOTS: u64 = 1;
+
+/// Slots after commit_buy in which reveal_and_buy must land (~60 seconds)
+pub const BUY_REVEAL_WINDOW_SLOTS: u64 = 150;
+
+/// Time a cycle may sit in Collected/Bought before abort_cycle can reset it (1 hour)
+pub const CYCLE_ABORT_TIMEOUT: i64 = 3600;
+
+/// Token category for untagged tokens (cannot be mass-paused)
+pub const UNCATEGORIZED: u8 = 0;
+
+/// Default burn ratio in basis points (10000 = 100% burn, nothing to treasury)
+pub const DEFAULT_BURN_BPS: u16 = 10000;
+
+/// Minimum burn ratio in basis points (50% burn floor)
+/// Creation > Extraction: at least half of every buyback is always burned
+pub const MIN_BURN_BPS: u16 = 5000;
+
+/// Maximum share of a cycle provide_liquidity may deposit into the PumpSwap pool (25%)
+pub const MAX_LIQUIDITY_BPS: u16 = 2500;
+
+/// Maximum share of collected SOL kept in the ReserveVault instead of bought back (20%)
+pub const MAX_RESERVE_BPS: u16 = 2000;
+
+/// Maximum share of collected SOL deposited into the InsuranceFund (5%)
+pub const MAX_INSURANCE_BPS: u16 = 500;
+
 // ══════════════════════════════════════════════════════════════════════════════
 // TESTING MODE CONFIGURATION
 // ══════════════════════════════════════════════════════════════════════════════
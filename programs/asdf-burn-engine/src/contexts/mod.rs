@@ -1,11 +1,12 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     token,
-    token_interface::{self as token_interface, TokenInterface, TokenAccount, Mint},
+    token_interface::{TokenInterface, TokenAccount, Mint},
     associated_token::AssociatedToken,
 };
 use crate::constants::*;
 use crate::errors::ErrorCode;
+use crate::helpers::pump_creator_vault;
 use crate::state::*;
 
 // ACCOUNTS - Instruction account validation structs
@@ -13,7 +14,7 @@ use crate::state::*;
 #[derive(Accounts)]
 pub struct Initialize<'info> {
     #[account(init, payer = admin, space = 8 + DATState::LEN, seeds = [DAT_STATE_SEED], bump)]
-    pub dat_state: Account<'info, DATState>,
+    pub dat_state: AccountLoader<'info, DATState>,
     /// CHECK: PDA
     #[account(seeds = [DAT_AUTHORITY_SEED], bump)]
     pub dat_authority: AccountInfo<'info>,
@@ -31,26 +32,242 @@ pub struct InitializeTokenStats<'info> {
         seeds = [TOKEN_STATS_SEED, mint.key().as_ref()],
         bump
     )]
-    pub token_stats: Account<'info, TokenStats>,
-    /// CHECK: Token mint
+    pub token_stats: AccountLoader<'info, TokenStats>,
+    /// Token registry header
+    #[account(mut, seeds = [TOKEN_REGISTRY_SEED], bump = token_registry.bump)]
+    pub token_registry: Account<'info, TokenRegistry>,
+    /// Any token registry page with room (receives the mint)
+    #[account(
+        mut,
+        seeds = [TOKEN_REGISTRY_PAGE_SEED, &token_registry_page.page.to_le_bytes()],
+        bump = token_registry_page.bump
+    )]
+    pub token_registry_page: Box<Account<'info, TokenRegistryPage>>,
+    /// CHECK: Token mint - owner validated, raw data screened for risky extensions
+    #[account(
+        constraint = mint.owner == &token::ID || mint.owner == &TOKEN_2022_PROGRAM @ ErrorCode::InvalidAccountOwner
+    )]
     pub mint: AccountInfo<'info>,
     #[account(mut)]
     pub payer: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
+/// CreateTokenRegistryPage - Append the next TokenRegistry page (permissionless, payer funds rent)
+#[derive(Accounts)]
+#[instruction(page: u16)]
+pub struct CreateTokenRegistryPage<'info> {
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + TokenRegistry::LEN,
+        seeds = [TOKEN_REGISTRY_SEED],
+        bump
+    )]
+    pub token_registry: Account<'info, TokenRegistry>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + TokenRegistryPage::LEN,
+        seeds = [TOKEN_REGISTRY_PAGE_SEED, &page.to_le_bytes()],
+        bump
+    )]
+    pub token_registry_page: Box<Account<'info, TokenRegistryPage>>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// RegisterToken - List a TokenStats created before the TokenRegistry existed (permissionless)
+#[derive(Accounts)]
+pub struct RegisterToken<'info> {
+    #[account(
+        mut,
+        seeds = [TOKEN_STATS_SEED, token_stats.load()?.mint.as_ref()],
+        bump = token_stats.load()?.bump
+    )]
+    pub token_stats: AccountLoader<'info, TokenStats>,
+    /// Token registry header
+    #[account(mut, seeds = [TOKEN_REGISTRY_SEED], bump = token_registry.bump)]
+    pub token_registry: Account<'info, TokenRegistry>,
+    /// Any token registry page with room (receives the mint)
+    #[account(
+        mut,
+        seeds = [TOKEN_REGISTRY_PAGE_SEED, &token_registry_page.page.to_le_bytes()],
+        bump = token_registry_page.bump
+    )]
+    pub token_registry_page: Box<Account<'info, TokenRegistryPage>>,
+}
+
 #[derive(Accounts)]
 pub struct SetRootToken<'info> {
     #[account(mut, seeds = [DAT_STATE_SEED], bump)]
-    pub dat_state: Account<'info, DATState>,
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(
+        mut,
+        seeds = [TOKEN_STATS_SEED, root_token_stats.load()?.mint.as_ref()],
+        bump = root_token_stats.load()?.bump
+    )]
+    pub root_token_stats: AccountLoader<'info, TokenStats>,
+    #[account(constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub admin: Signer<'info>,
+    /// Stats of the root being replaced - REQUIRED when a different root is already set
+    #[account(
+        mut,
+        seeds = [TOKEN_STATS_SEED, old_root_token_stats.load()?.mint.as_ref()],
+        bump = old_root_token_stats.load()?.bump
+    )]
+    pub old_root_token_stats: Option<AccountLoader<'info, TokenStats>>,
+    /// Admin audit log - appended when passed (skipped until initialize_admin_log)
+    #[account(mut, seeds = [ADMIN_LOG_SEED], bump = admin_log.bump)]
+    pub admin_log: Option<Box<Account<'info, AdminLog>>>,
+}
+
+/// InitializeAdminLog - Admin creates the ring buffer of recent admin operations
+#[derive(Accounts)]
+pub struct InitializeAdminLog<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + AdminLog::LEN,
+        seeds = [ADMIN_LOG_SEED],
+        bump
+    )]
+    pub admin_log: Box<Account<'info, AdminLog>>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// InitializeRootSet - Admin creates the set of additional roots
+#[derive(Accounts)]
+pub struct InitializeRootSet<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + RootSet::LEN,
+        seeds = [ROOT_SET_SEED],
+        bump
+    )]
+    pub root_set: Account<'info, RootSet>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// ManageRootSet - Admin lists or unlists an additional root
+#[derive(Accounts)]
+pub struct ManageRootSet<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(mut, seeds = [ROOT_SET_SEED], bump = root_set.bump)]
+    pub root_set: Account<'info, RootSet>,
+    #[account(
+        mut,
+        seeds = [TOKEN_STATS_SEED, root_token_stats.load()?.mint.as_ref()],
+        bump = root_token_stats.load()?.bump
+    )]
+    pub root_token_stats: AccountLoader<'info, TokenStats>,
+    pub admin: Signer<'info>,
+}
+
+/// AssignRoot - Admin routes a secondary's fee split to a listed root
+#[derive(Accounts)]
+pub struct AssignRoot<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(seeds = [TOKEN_STATS_SEED, token_stats.load()?.mint.as_ref()], bump = token_stats.load()?.bump)]
+    pub token_stats: AccountLoader<'info, TokenStats>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + TokenConfig::LEN,
+        seeds = [TOKEN_CONFIG_SEED, token_stats.load()?.mint.as_ref()],
+        bump
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    /// REQUIRED unless assigning back to the primary root
+    #[account(seeds = [ROOT_SET_SEED], bump = root_set.bump)]
+    pub root_set: Option<Account<'info, RootSet>>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// ConfigureRootStream - Admin sets a secondary's root flush schedule
+#[derive(Accounts)]
+pub struct ConfigureRootStream<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(seeds = [TOKEN_STATS_SEED, token_stats.load()?.mint.as_ref()], bump = token_stats.load()?.bump)]
+    pub token_stats: AccountLoader<'info, TokenStats>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + RootStream::LEN,
+        seeds = [ROOT_STREAM_SEED, token_stats.load()?.mint.as_ref()],
+        bump
+    )]
+    pub root_stream: Account<'info, RootStream>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// FlushToRoot - Anyone moves a secondary's accrued root share to its root treasury once due
+#[derive(Accounts)]
+pub struct FlushToRoot<'info> {
+    #[account(mut, seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    /// CHECK: DAT authority PDA - holds the accrued root share
+    #[account(mut, seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
+    pub dat_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [ROOT_STREAM_SEED, root_stream.mint.as_ref()],
+        bump = root_stream.bump
+    )]
+    pub root_stream: Account<'info, RootStream>,
+    #[account(
+        mut,
+        seeds = [TOKEN_STATS_SEED, root_stream.mint.as_ref()],
+        bump = token_stats.load()?.bump
+    )]
+    pub token_stats: AccountLoader<'info, TokenStats>,
+    /// CHECK: TokenConfig PDA of the secondary (may be uninitialized) - read for its assigned root
+    #[account(seeds = [TOKEN_CONFIG_SEED, root_stream.mint.as_ref()], bump)]
+    pub token_config: AccountInfo<'info>,
+    /// Root set - REQUIRED when token_config assigns a root other than the primary
+    #[account(seeds = [ROOT_SET_SEED], bump = root_set.bump)]
+    pub root_set: Option<Account<'info, RootSet>>,
+    /// CHECK: Root treasury PDA - validated in flush_to_root against the assigned root
+    #[account(mut)]
+    pub root_treasury: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// MigrateTokenConfig - Admin grows a TokenConfig created before assigned_root existed
+#[derive(Accounts)]
+pub struct MigrateTokenConfig<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    /// CHECK: TokenConfig PDA in its old layout - resized in migrate_token_config
     #[account(
         mut,
-        seeds = [TOKEN_STATS_SEED, root_token_stats.mint.as_ref()],
-        bump = root_token_stats.bump
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump,
+        constraint = token_config.owner == &crate::ID @ ErrorCode::InvalidAccountOwner
     )]
-    pub root_token_stats: Account<'info, TokenStats>,
-    #[account(constraint = admin.key() == dat_state.admin @ ErrorCode::UnauthorizedAccess)]
+    pub token_config: AccountInfo<'info>,
+    /// CHECK: Mint address for PDA derivation
+    pub mint: AccountInfo<'info>,
+    #[account(mut)]
     pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
 }
 
 /// CollectFees - Collect creator fees from PumpFun bonding curve vault
@@ -61,767 +278,3823 @@ pub struct SetRootToken<'info> {
 ///   ["creator-vault", dat_authority] verified by PUMP_PROGRAM.
 /// - root_treasury: Validated at runtime in collect_fees() via PDA derivation check.
 ///   The function verifies the provided account matches the expected PDA derived from
-///   ["root_treasury", root_token_mint].
+///   ["root_treasury", token_mint] of the collecting root (primary or RootSet).
 #[derive(Accounts)]
 pub struct CollectFees<'info> {
     #[account(mut, seeds = [DAT_STATE_SEED], bump)]
-    pub dat_state: Account<'info, DATState>,
+    pub dat_state: AccountLoader<'info, DATState>,
     #[account(
         mut,
         seeds = [TOKEN_STATS_SEED, token_mint.key().as_ref()],
-        bump = token_stats.bump
+        bump = token_stats.load()?.bump
     )]
-    pub token_stats: Account<'info, TokenStats>,
+    pub token_stats: AccountLoader<'info, TokenStats>,
     pub token_mint: InterfaceAccount<'info, Mint>,
     /// CHECK: DAT authority PDA - receives SOL from creator vault
-    #[account(mut, seeds = [DAT_AUTHORITY_SEED], bump = dat_state.dat_authority_bump)]
+    #[account(mut, seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
     pub dat_authority: AccountInfo<'info>,
-    /// CHECK: Creator vault - validated by PumpFun program during CPI.
-    /// Seeds: ["creator-vault", creator_pubkey] where creator=dat_authority.
-    /// The CPI to collect_creator_fee will fail if this is not a valid vault.
+    /// CHECK: Creator vault - PDA ["creator-vault", dat_authority] under PUMP_PROGRAM,
+    /// derived on-chain so a wrong vault fails with InvalidCreatorVault before the CPI.
     /// NOTE: Vault is a native SOL account (System Program owner), NOT owned by PUMP_PROGRAM.
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = creator_vault.key() == pump_creator_vault(&dat_authority.key()) @ ErrorCode::InvalidCreatorVault
+    )]
     pub creator_vault: AccountInfo<'info>,
     /// CHECK: Event authority for PumpFun program
     pub pump_event_authority: AccountInfo<'info>,
     /// CHECK: PumpFun program (hardcoded address verified in CPI)
     pub pump_swap_program: AccountInfo<'info>,
     /// CHECK: Root treasury PDA (optional) - validated at runtime in collect_fees()
-    /// via PDA derivation: ["root_treasury", root_token_mint]
+    /// via PDA derivation: ["root_treasury", token_mint] (token must be a root)
     #[account(mut)]
     pub root_treasury: Option<AccountInfo<'info>>,
     pub system_program: Program<'info, System>,
-}
-
-/// CollectFeesAMM - Collect creator fees from PumpSwap AMM
-/// Used for tokens that have migrated from bonding curve to AMM
-#[derive(Accounts)]
-pub struct CollectFeesAMM<'info> {
-    #[account(seeds = [DAT_STATE_SEED], bump)]
-    pub dat_state: Account<'info, DATState>,
+    /// Cycle state machine for this mint (collect → buy → burn ordering)
     #[account(
         mut,
-        seeds = [TOKEN_STATS_SEED, token_mint.key().as_ref()],
-        bump = token_stats.bump
+        seeds = [CYCLE_STATE_SEED, token_mint.key().as_ref()],
+        bump = cycle_state.bump
     )]
-    pub token_stats: Account<'info, TokenStats>,
-    pub token_mint: InterfaceAccount<'info, Mint>,
-    /// CHECK: DAT authority PDA - must be registered as coin_creator in PumpSwap
-    #[account(mut, seeds = [DAT_AUTHORITY_SEED], bump = dat_state.dat_authority_bump)]
-    pub dat_authority: AccountInfo<'info>,
-    /// WSOL mint (So11111111111111111111111111111111111111112)
-    pub wsol_mint: InterfaceAccount<'info, Mint>,
-    /// DAT's WSOL token account (destination for collected fees)
+    pub cycle_state: Box<Account<'info, CycleState>>,
+    /// CHECK: Listing partner wallet - REQUIRED when token_stats names a partner
+    #[account(mut, constraint = partner.key() == token_stats.load()?.partner @ ErrorCode::InvalidParameter)]
+    pub partner: Option<AccountInfo<'info>>,
+    /// Listing partner totals - REQUIRED when token_stats names a partner
     #[account(
         mut,
-        constraint = dat_wsol_account.mint == wsol_mint.key() @ ErrorCode::InvalidParameter,
-        constraint = dat_wsol_account.owner == dat_authority.key() @ ErrorCode::InvalidParameter
+        seeds = [PARTNER_STATS_SEED, token_stats.load()?.partner.as_ref()],
+        bump = partner_stats.bump
     )]
-    pub dat_wsol_account: InterfaceAccount<'info, TokenAccount>,
-    /// CHECK: PumpSwap creator vault authority PDA - seeds: ["creator_vault", dat_authority]
-    pub creator_vault_authority: AccountInfo<'info>,
-    /// CHECK: Creator vault ATA (source of WSOL fees)
-    #[account(mut)]
-    pub creator_vault_ata: AccountInfo<'info>,
-    /// CHECK: PumpSwap program
-    pub pump_swap_program: AccountInfo<'info>,
-    pub token_program: Interface<'info, TokenInterface>,
+    pub partner_stats: Option<Account<'info, PartnerStats>>,
+    /// Optional current-day activity bucket (recorded when provided)
+    #[account(
+        mut,
+        seeds = [DAILY_STATS_SEED, token_stats.load()?.mint.as_ref(), &daily_stats.day.to_le_bytes()],
+        bump = daily_stats.bump
+    )]
+    pub daily_stats: Option<Account<'info, DailyStats>>,
+    /// Treasury SOL reserve - REQUIRED when dat_state.reserve_bps > 0
+    #[account(mut, seeds = [RESERVE_VAULT_SEED], bump = reserve_vault.bump)]
+    pub reserve_vault: Option<Box<Account<'info, ReserveVault>>>,
+    /// Insurance fund - REQUIRED when dat_state.insurance_bps > 0
+    #[account(mut, seeds = [INSURANCE_FUND_SEED], bump = insurance_fund.bump)]
+    pub insurance_fund: Option<Box<Account<'info, InsuranceFund>>>,
+    /// VRF-settled collect window - REQUIRED when dat_state.randomized_timing is on
+    #[account(seeds = [CYCLE_WINDOW_SEED, token_mint.key().as_ref()], bump = cycle_window.bump)]
+    pub cycle_window: Option<Box<Account<'info, CycleWindow>>>,
 }
 
-/// UnwrapWsol - Convert WSOL back to native SOL
-/// Call after collect_fees_amm to enable buyback with native SOL
+/// CollectFeesBatch - Collect the shared creator vault once for several tokens
+/// remaining_accounts: [token_stats (mut)] per token
 #[derive(Accounts)]
-pub struct UnwrapWsol<'info> {
+pub struct CollectFeesBatch<'info> {
     #[account(seeds = [DAT_STATE_SEED], bump)]
-    pub dat_state: Account<'info, DATState>,
-    /// CHECK: DAT authority PDA (receives unwrapped SOL)
-    #[account(mut, seeds = [DAT_AUTHORITY_SEED], bump = dat_state.dat_authority_bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    /// CHECK: DAT authority PDA - receives SOL from the creator vault
+    #[account(mut, seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
     pub dat_authority: AccountInfo<'info>,
-    /// DAT's WSOL token account (will be closed)
+    /// CHECK: PumpFun creator vault of dat_authority (native SOL account, shared by every token)
     #[account(
         mut,
-        constraint = dat_wsol_account.owner == dat_authority.key() @ ErrorCode::InvalidParameter
+        constraint = creator_vault.key() == pump_creator_vault(&dat_authority.key()) @ ErrorCode::InvalidCreatorVault
     )]
-    pub dat_wsol_account: InterfaceAccount<'info, TokenAccount>,
-    pub token_program: Interface<'info, TokenInterface>,
+    pub creator_vault: AccountInfo<'info>,
+    /// CHECK: Event authority for PumpFun program
+    pub pump_event_authority: AccountInfo<'info>,
+    /// CHECK: PumpFun program (hardcoded address verified in CPI)
+    pub pump_swap_program: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+    /// Treasury SOL reserve - REQUIRED when dat_state.reserve_bps > 0
+    #[account(mut, seeds = [RESERVE_VAULT_SEED], bump = reserve_vault.bump)]
+    pub reserve_vault: Option<Box<Account<'info, ReserveVault>>>,
+    /// Insurance fund - REQUIRED when dat_state.insurance_bps > 0
+    #[account(mut, seeds = [INSURANCE_FUND_SEED], bump = insurance_fund.bump)]
+    pub insurance_fund: Option<Box<Account<'info, InsuranceFund>>>,
 }
 
-/// WrapWsol - Convert native SOL to WSOL for AMM buyback
-/// Call before execute_buy_amm when root token is on PumpSwap AMM
+/// CreateCycleLut - Admin creates the cycle Address Lookup Table (authority: dat_authority)
 #[derive(Accounts)]
-pub struct WrapWsol<'info> {
+pub struct CreateCycleLut<'info> {
+    #[account(mut, seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    /// CHECK: PDA (lookup table authority)
+    #[account(seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
+    pub dat_authority: AccountInfo<'info>,
+    /// CHECK: New lookup table - derivation from dat_authority and recent_slot checked in the instruction
+    #[account(mut)]
+    pub lookup_table: AccountInfo<'info>,
+    #[account(mut, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub admin: Signer<'info>,
+    /// CHECK: Address Lookup Table program - validated address
+    #[account(address = ADDRESS_LOOKUP_TABLE_PROGRAM @ ErrorCode::InvalidParameter)]
+    pub address_lookup_table_program: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// ExtendCycleLut - Admin appends addresses to the recorded cycle Address Lookup Table
+#[derive(Accounts)]
+pub struct ExtendCycleLut<'info> {
     #[account(seeds = [DAT_STATE_SEED], bump)]
-    pub dat_state: Account<'info, DATState>,
-    /// CHECK: DAT authority PDA (source of native SOL)
-    #[account(mut, seeds = [DAT_AUTHORITY_SEED], bump = dat_state.dat_authority_bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    /// CHECK: PDA (lookup table authority)
+    #[account(seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
     pub dat_authority: AccountInfo<'info>,
-    /// DAT's WSOL token account (destination for wrapped SOL)
-    /// Must be owned by dat_authority and have WSOL mint
+    /// CHECK: The cycle LUT recorded in DATState
     #[account(
         mut,
-        token::mint = wsol_mint,
-        token::authority = dat_authority
+        constraint = dat_state.load()?.cycle_lut != Pubkey::default()
+            && lookup_table.key() == dat_state.load()?.cycle_lut @ ErrorCode::CycleLutMismatch
     )]
-    pub dat_wsol_account: InterfaceAccount<'info, TokenAccount>,
-    /// WSOL mint (So11111111111111111111111111111111111111112)
-    pub wsol_mint: InterfaceAccount<'info, Mint>,
-    pub token_program: Program<'info, token::Token>,
+    pub lookup_table: AccountInfo<'info>,
+    #[account(mut, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub admin: Signer<'info>,
+    /// CHECK: Address Lookup Table program - validated address
+    #[account(address = ADDRESS_LOOKUP_TABLE_PROGRAM @ ErrorCode::InvalidParameter)]
+    pub address_lookup_table_program: AccountInfo<'info>,
     pub system_program: Program<'info, System>,
 }
 
-/// ExecuteBuy - Simplified to reduce stack usage (removed unused accounts)
+/// RunEcosystemCycle - Root plus up to MAX_ECOSYSTEM_SECONDARIES secondaries in one transaction
+/// remaining_accounts: [token_stats (mut), mint (mut), bonding_curve (mut),
+/// curve_token_account (mut), dat_token_account (mut), token_config] per secondary
 #[derive(Accounts)]
-pub struct ExecuteBuy<'info> {
+pub struct RunEcosystemCycle<'info> {
     #[account(mut, seeds = [DAT_STATE_SEED], bump)]
-    pub dat_state: Account<'info, DATState>,
-    /// CHECK: PDA (holds native SOL for buying)
-    #[account(mut, seeds = [DAT_AUTHORITY_SEED], bump = dat_state.dat_authority_bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    /// CHECK: PDA (receives the vault fees, pays every buy)
+    #[account(mut, seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
     pub dat_authority: AccountInfo<'info>,
-    /// DAT's token account for receiving bought tokens - validated mint and authority
+    /// CHECK: Creator vault - PDA ["creator-vault", dat_authority] under PUMP_PROGRAM
     #[account(
         mut,
-        constraint = dat_asdf_account.mint == asdf_mint.key() @ ErrorCode::InvalidParameter,
-        constraint = dat_asdf_account.owner == dat_authority.key() @ ErrorCode::InvalidParameter
+        constraint = creator_vault.key() == pump_creator_vault(&dat_authority.key()) @ ErrorCode::InvalidCreatorVault
     )]
-    pub dat_asdf_account: InterfaceAccount<'info, TokenAccount>,
-    /// CHECK: Pool (bonding curve) - validated by PumpFun program
-    #[account(mut, constraint = pool.owner == &PUMP_PROGRAM @ ErrorCode::InvalidBondingCurve)]
-    pub pool: AccountInfo<'info>,
-    /// CHECK: Token mint (validation done by PumpFun)
-    #[account(mut)]
-    pub asdf_mint: AccountInfo<'info>,
-    #[account(mut)]
-    pub pool_asdf_account: InterfaceAccount<'info, TokenAccount>,
-    /// CHECK: Config
-    pub pump_global_config: AccountInfo<'info>,
-    /// CHECK: Recipient
-    #[account(mut)]
-    pub protocol_fee_recipient: AccountInfo<'info>,
-    /// CHECK: Creator vault (PDA from token creator)
-    #[account(mut)]
     pub creator_vault: AccountInfo<'info>,
-    /// CHECK: Event auth
+    /// CHECK: PumpFun event authority - validated address
+    #[account(address = PUMP_EVENT_AUTHORITY @ ErrorCode::InvalidParameter)]
     pub pump_event_authority: AccountInfo<'info>,
-    /// CHECK: Pump program
+    /// CHECK: PumpFun program - validated program ID via constraint
+    #[account(constraint = pump_swap_program.key() == PUMP_PROGRAM @ ErrorCode::InvalidParameter)]
     pub pump_swap_program: AccountInfo<'info>,
-    /// CHECK: Global volume accumulator (PDA) - required by Pump.fun buy instruction
+    /// CHECK: PumpFun Global - fee recipients parsed on-chain (discriminator checked)
+    #[account(constraint = pump_global_config.owner == &PUMP_PROGRAM @ ErrorCode::InvalidPumpGlobalConfig)]
+    pub pump_global_config: AccountInfo<'info>,
+    /// CHECK: Protocol fee recipient - checked against the rotation entry (shared by every buy)
+    #[account(mut)]
+    pub protocol_fee_recipient: AccountInfo<'info>,
+    /// CHECK: Global volume accumulator (PDA) - validated address
+    #[account(address = PUMP_GLOBAL_VOLUME_ACCUMULATOR @ ErrorCode::InvalidParameter)]
     pub global_volume_accumulator: AccountInfo<'info>,
-    /// CHECK: User volume accumulator (PDA) - seeds: ["user_volume_accumulator", user]
+    /// CHECK: User volume accumulator (PDA) - seeds: ["user_volume_accumulator", dat_authority]
     #[account(mut)]
     pub user_volume_accumulator: AccountInfo<'info>,
     /// CHECK: Fee config (PDA)
     pub fee_config: AccountInfo<'info>,
-    /// CHECK: Fee program
+    /// CHECK: Fee program - validated program ID via constraint
+    #[account(constraint = fee_program.key() == PUMP_FEE_PROGRAM @ ErrorCode::InvalidParameter)]
     pub fee_program: AccountInfo<'info>,
+    /// Token program shared by the root and every secondary
     pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
-}
-
-#[derive(Accounts)]
-pub struct ExecuteBuySecondary<'info> {
-    #[account(mut, seeds = [DAT_STATE_SEED], bump)]
-    pub dat_state: Account<'info, DATState>,
-    /// CHECK: PDA (holds native SOL for buying)
-    #[account(mut, seeds = [DAT_AUTHORITY_SEED], bump = dat_state.dat_authority_bump)]
-    pub dat_authority: AccountInfo<'info>,
-    /// DAT's token account - validated mint and authority
+    /// Protocol fee recipient rotation (PumpFun global fee recipients if omitted)
+    #[account(seeds = [FEE_RECIPIENTS_SEED], bump = fee_recipients.bump)]
+    pub fee_recipients: Option<Box<Account<'info, FeeRecipients>>>,
     #[account(
         mut,
-        constraint = dat_asdf_account.mint == asdf_mint.key() @ ErrorCode::InvalidParameter,
-        constraint = dat_asdf_account.owner == dat_authority.key() @ ErrorCode::InvalidParameter
+        seeds = [TOKEN_STATS_SEED, root_mint.key().as_ref()],
+        bump = root_token_stats.load()?.bump
     )]
-    pub dat_asdf_account: InterfaceAccount<'info, TokenAccount>,
-    /// CHECK: Pool (bonding curve) - validated owner
-    #[account(mut, constraint = pool.owner == &PUMP_PROGRAM @ ErrorCode::InvalidBondingCurve)]
-    pub pool: AccountInfo<'info>,
+    pub root_token_stats: AccountLoader<'info, TokenStats>,
+    /// CHECK: Primary root mint (validation done by PumpFun)
+    #[account(mut, constraint = dat_state.load()?.root_token_mint() == Some(root_mint.key()) @ ErrorCode::InvalidRootToken)]
+    pub root_mint: AccountInfo<'info>,
+    /// CHECK: Root bonding curve - validated by PumpFun program
+    #[account(mut, constraint = root_pool.owner == &PUMP_PROGRAM @ ErrorCode::InvalidBondingCurve)]
+    pub root_pool: AccountInfo<'info>,
     #[account(mut)]
-    pub asdf_mint: InterfaceAccount<'info, Mint>,
-    /// Pool's token account - validated mint matches
+    pub root_pool_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// Root DAT token account - must be the rotated account once rotate_token_account was used
     #[account(
         mut,
-        constraint = pool_asdf_account.mint == asdf_mint.key() @ ErrorCode::InvalidParameter
+        constraint = root_dat_token_account.mint == root_mint.key() @ ErrorCode::InvalidParameter,
+        constraint = root_dat_token_account.owner == dat_authority.key() @ ErrorCode::InvalidParameter,
+        constraint = root_token_stats.load()?.accepts_token_account(&root_dat_token_account.key()) @ ErrorCode::InvalidTokenAccount
     )]
-    pub pool_asdf_account: InterfaceAccount<'info, TokenAccount>,
-    /// CHECK: Config
-    pub pump_global_config: AccountInfo<'info>,
-    /// CHECK: Recipient
-    #[account(mut)]
-    pub protocol_fee_recipient: AccountInfo<'info>,
-    /// CHECK: Creator vault (PDA from token creator)
-    #[account(mut)]
-    pub creator_vault: AccountInfo<'info>,
-    /// CHECK: Event auth
-    pub pump_event_authority: AccountInfo<'info>,
-    /// CHECK: Pump program - validated program ID via constraint
-    #[account(constraint = pump_swap_program.key() == PUMP_PROGRAM @ ErrorCode::InvalidParameter)]
-    pub pump_swap_program: AccountInfo<'info>,
-    /// CHECK: Global volume accumulator (PDA) - required by Pump.fun buy instruction
-    pub global_volume_accumulator: AccountInfo<'info>,
-    /// CHECK: User volume accumulator (PDA) - seeds: ["user_volume_accumulator", user]
-    #[account(mut)]
-    pub user_volume_accumulator: AccountInfo<'info>,
-    /// CHECK: Fee config (PDA)
-    pub fee_config: AccountInfo<'info>,
-    /// CHECK: Fee program
-    pub fee_program: AccountInfo<'info>,
-    /// CHECK: Root treasury PDA (REQUIRED for secondary tokens)
-    #[account(mut)]
-    pub root_treasury: Option<AccountInfo<'info>>,
-    pub token_program: Interface<'info, TokenInterface>,
-    pub system_program: Program<'info, System>,
+    pub root_dat_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// Root cycle state - the cycle ends in Bought; burn_and_update completes it
+    #[account(
+        mut,
+        seeds = [CYCLE_STATE_SEED, root_mint.key().as_ref()],
+        bump = root_cycle_state.bump
+    )]
+    pub root_cycle_state: Box<Account<'info, CycleState>>,
+    /// CHECK: Root treasury PDA - drained into the root buy
+    #[account(mut, seeds = [ROOT_TREASURY_SEED, root_mint.key().as_ref()], bump)]
+    pub root_treasury: AccountInfo<'info>,
+    /// Treasury SOL reserve - REQUIRED when dat_state.reserve_bps > 0
+    #[account(mut, seeds = [RESERVE_VAULT_SEED], bump = reserve_vault.bump)]
+    pub reserve_vault: Option<Box<Account<'info, ReserveVault>>>,
+    /// Insurance fund - REQUIRED when dat_state.insurance_bps > 0
+    #[account(mut, seeds = [INSURANCE_FUND_SEED], bump = insurance_fund.bump)]
+    pub insurance_fund: Option<Box<Account<'info, InsuranceFund>>>,
 }
 
-/// ExecuteBuyAMM - For PumpSwap AMM pools (migrated tokens)
-/// Requires 23+ accounts as per PumpSwap AMM specification
+/// CollectFeesAMM - Collect creator fees from PumpSwap AMM
+/// Used for tokens that have migrated from bonding curve to AMM
 #[derive(Accounts)]
-pub struct ExecuteBuyAMM<'info> {
-    // DAT State accounts
+pub struct CollectFeesAMM<'info> {
     #[account(mut, seeds = [DAT_STATE_SEED], bump)]
-    pub dat_state: Account<'info, DATState>,
-    /// CHECK: PDA authority (holds WSOL, acts as "user" in AMM)
-    #[account(mut, seeds = [DAT_AUTHORITY_SEED], bump = dat_state.dat_authority_bump)]
-    pub dat_authority: AccountInfo<'info>,
-    /// DAT's token account for receiving bought tokens - validated mint and authority
+    pub dat_state: AccountLoader<'info, DATState>,
     #[account(
         mut,
-        constraint = dat_token_account.mint == base_mint.key() @ ErrorCode::InvalidParameter,
-        constraint = dat_token_account.owner == dat_authority.key() @ ErrorCode::InvalidParameter
+        seeds = [TOKEN_STATS_SEED, token_mint.key().as_ref()],
+        bump = token_stats.load()?.bump
     )]
-    pub dat_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub token_stats: AccountLoader<'info, TokenStats>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    /// CHECK: DAT authority PDA - must be registered as coin_creator in PumpSwap
+    #[account(mut, seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
+    pub dat_authority: AccountInfo<'info>,
+    /// WSOL mint (So11111111111111111111111111111111111111112)
+    pub wsol_mint: InterfaceAccount<'info, Mint>,
+    /// DAT's WSOL token account (destination for collected fees)
+    #[account(
+        mut,
+        constraint = dat_wsol_account.mint == wsol_mint.key() @ ErrorCode::InvalidParameter,
+        constraint = dat_wsol_account.owner == dat_authority.key() @ ErrorCode::InvalidParameter
+    )]
+    pub dat_wsol_account: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: PumpSwap creator vault authority PDA - seeds: ["creator_vault", dat_authority]
+    pub creator_vault_authority: AccountInfo<'info>,
+    /// CHECK: Creator vault ATA (source of WSOL fees)
+    #[account(mut)]
+    pub creator_vault_ata: AccountInfo<'info>,
+    /// CHECK: PumpSwap program
+    pub pump_swap_program: AccountInfo<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    /// Cycle state machine for this mint (collect → buy → burn ordering)
+    #[account(
+        mut,
+        seeds = [CYCLE_STATE_SEED, token_mint.key().as_ref()],
+        bump = cycle_state.bump
+    )]
+    pub cycle_state: Box<Account<'info, CycleState>>,
+    /// Optional current-day activity bucket (recorded when provided)
+    #[account(
+        mut,
+        seeds = [DAILY_STATS_SEED, token_stats.load()?.mint.as_ref(), &daily_stats.day.to_le_bytes()],
+        bump = daily_stats.bump
+    )]
+    pub daily_stats: Option<Account<'info, DailyStats>>,
+    /// VRF-settled collect window - REQUIRED when dat_state.randomized_timing is on
+    #[account(seeds = [CYCLE_WINDOW_SEED, token_mint.key().as_ref()], bump = cycle_window.bump)]
+    pub cycle_window: Option<Box<Account<'info, CycleWindow>>>,
+}
+
+/// OnboardExternalToken - An external PumpFun creator points its creator fees at the DAT
+/// The creator signs after handing the bonding curve to dat_authority off-chain;
+/// TokenStats + TokenConfig created
+#[derive(Accounts)]
+pub struct OnboardExternalToken<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    /// CHECK: DAT authority PDA - must already be the bonding curve creator
+    #[account(seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
+    pub dat_authority: AccountInfo<'info>,
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + TokenStats::LEN,
+        seeds = [TOKEN_STATS_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub token_stats: AccountLoader<'info, TokenStats>,
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + TokenConfig::LEN,
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub token_config: Box<Account<'info, TokenConfig>>,
+    /// Token registry header
+    #[account(mut, seeds = [TOKEN_REGISTRY_SEED], bump = token_registry.bump)]
+    pub token_registry: Account<'info, TokenRegistry>,
+    /// Any token registry page with room (receives the mint)
+    #[account(
+        mut,
+        seeds = [TOKEN_REGISTRY_PAGE_SEED, &token_registry_page.page.to_le_bytes()],
+        bump = token_registry_page.bump
+    )]
+    pub token_registry_page: Box<Account<'info, TokenRegistryPage>>,
+    /// CHECK: Token mint - owner validated, raw data screened for risky extensions
+    #[account(
+        constraint = mint.owner == &token::ID || mint.owner == &TOKEN_2022_PROGRAM @ ErrorCode::InvalidAccountOwner
+    )]
+    pub mint: AccountInfo<'info>,
+    /// CHECK: PumpFun bonding curve PDA ["bonding-curve", mint] - live, creator parsed in onboard_external_token
+    #[account(
+        seeds = [PUMP_BONDING_CURVE_SEED, mint.key().as_ref()],
+        bump,
+        seeds::program = PUMP_PROGRAM
+    )]
+    pub bonding_curve: AccountInfo<'info>,
+    /// Original creator (pays rent for TokenStats and TokenConfig)
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// RequestOffboard - The original creator of an onboarded token starts the OFFBOARD_TIMELOCK
+#[derive(Accounts)]
+pub struct RequestOffboard<'info> {
+    #[account(
+        mut,
+        seeds = [TOKEN_CONFIG_SEED, token_config.mint.as_ref()],
+        bump = token_config.bump,
+        constraint = token_config.is_onboarded() @ ErrorCode::NotOriginalCreator,
+        constraint = token_config.original_creator == creator.key() @ ErrorCode::NotOriginalCreator
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    pub creator: Signer<'info>,
+}
+
+/// OffboardToken - Remove an onboarded token from the DAT after OFFBOARD_TIMELOCK
+/// Settles pending fees to the root and closes TokenStats + TokenConfig (rent to the
+/// original creator)
+#[derive(Accounts)]
+pub struct OffboardToken<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    /// CHECK: DAT authority PDA - pays the pending fees to the root
+    #[account(mut, seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
+    pub dat_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [TOKEN_STATS_SEED, mint.key().as_ref()],
+        bump = token_stats.load()?.bump,
+        close = creator
+    )]
+    pub token_stats: AccountLoader<'info, TokenStats>,
+    #[account(
+        mut,
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = token_config.is_onboarded() @ ErrorCode::NotOriginalCreator,
+        constraint = token_config.original_creator == creator.key() @ ErrorCode::NotOriginalCreator,
+        close = creator
+    )]
+    pub token_config: Box<Account<'info, TokenConfig>>,
+    /// Token registry header - REQUIRED when token_stats.in_registry
+    #[account(mut, seeds = [TOKEN_REGISTRY_SEED], bump = token_registry.bump)]
+    pub token_registry: Option<Account<'info, TokenRegistry>>,
+    /// Page listing the mint (token_stats.registry_page) - REQUIRED when token_stats.in_registry
+    #[account(
+        mut,
+        seeds = [TOKEN_REGISTRY_PAGE_SEED, &token_stats.load()?.registry_page.to_le_bytes()],
+        bump = token_registry_page.bump
+    )]
+    pub token_registry_page: Option<Box<Account<'info, TokenRegistryPage>>>,
+    /// CHECK: Token mint - PDA seeds above tie it to token_stats / token_config
+    pub mint: AccountInfo<'info>,
+    /// Active DAT token account for the mint - must be empty (nothing awaiting burn)
+    #[account(
+        constraint = dat_token_account.key()
+            == token_stats.load()?.active_token_account(&dat_authority.key(), &token_program.key())
+            @ ErrorCode::InvalidTokenAccount
+    )]
+    pub dat_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// Root set - REQUIRED when token_config assigns a root other than the primary
+    #[account(seeds = [ROOT_SET_SEED], bump = root_set.bump)]
+    pub root_set: Option<Box<Account<'info, RootSet>>>,
+    /// CHECK: Root treasury PDA - validated in offboard_token against the assigned root
+    #[account(mut)]
+    pub root_treasury: AccountInfo<'info>,
+    /// Original creator (receives the closed accounts' rent)
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// ClaimAmmCreator - Register dat_authority as coin_creator of a migrated token's PumpSwap pool
+/// Permissionless: PumpSwap only copies the bonding curve creator, checked to be dat_authority
+#[derive(Accounts)]
+pub struct ClaimAmmCreator<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    /// CHECK: DAT authority PDA - creator of the bonding curve, becomes the pool's coin_creator
+    #[account(seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
+    pub dat_authority: AccountInfo<'info>,
+    #[account(
+        seeds = [TOKEN_STATS_SEED, token_mint.key().as_ref()],
+        bump = token_stats.load()?.bump
+    )]
+    pub token_stats: AccountLoader<'info, TokenStats>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    /// CHECK: PumpSwap pool - owner checked, base_mint and coin_creator parsed in claim_amm_creator
+    #[account(mut, constraint = pool.owner == &PUMP_SWAP_PROGRAM @ ErrorCode::InvalidPool)]
+    pub pool: AccountInfo<'info>,
+    /// CHECK: Metaplex metadata PDA ["metadata", MPL_TOKEN_METADATA_PROGRAM, mint] (may be empty)
+    #[account(
+        seeds = [MPL_METADATA_SEED, MPL_TOKEN_METADATA_PROGRAM.as_ref(), token_mint.key().as_ref()],
+        bump,
+        seeds::program = MPL_TOKEN_METADATA_PROGRAM
+    )]
+    pub metadata: AccountInfo<'info>,
+    /// CHECK: PumpFun bonding curve PDA ["bonding-curve", mint] - must be complete, creator parsed
+    #[account(
+        seeds = [PUMP_BONDING_CURVE_SEED, token_mint.key().as_ref()],
+        bump,
+        seeds::program = PUMP_PROGRAM
+    )]
+    pub bonding_curve: AccountInfo<'info>,
+    /// CHECK: PumpSwap event authority (PDA) - validated address
+    #[account(address = PUMPSWAP_EVENT_AUTHORITY @ ErrorCode::InvalidParameter)]
+    pub event_authority: AccountInfo<'info>,
+    /// CHECK: PumpSwap AMM program - validated address
+    #[account(address = PUMP_SWAP_PROGRAM @ ErrorCode::InvalidParameter)]
+    pub pump_swap_program: AccountInfo<'info>,
+}
+
+/// UnwrapWsol - Convert WSOL back to native SOL
+/// Call after collect_fees_amm to enable buyback with native SOL
+#[derive(Accounts)]
+pub struct UnwrapWsol<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    /// CHECK: DAT authority PDA (receives unwrapped SOL)
+    #[account(mut, seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
+    pub dat_authority: AccountInfo<'info>,
+    /// DAT's WSOL token account (will be closed)
+    #[account(
+        mut,
+        constraint = dat_wsol_account.owner == dat_authority.key() @ ErrorCode::InvalidParameter
+    )]
+    pub dat_wsol_account: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// RecoverWsol - Unwrap WSOL orphaned by a failed AMM buy (permissionless once cycles are stale)
+#[derive(Accounts)]
+pub struct RecoverWsol<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    /// CHECK: DAT authority PDA (receives the recovered SOL)
+    #[account(mut, seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
+    pub dat_authority: AccountInfo<'info>,
+    /// DAT's WSOL token account (will be closed)
+    #[account(
+        mut,
+        constraint = dat_wsol_account.owner == dat_authority.key() @ ErrorCode::InvalidParameter,
+        constraint = dat_wsol_account.mint == WSOL_MINT @ ErrorCode::InvalidParameter
+    )]
+    pub dat_wsol_account: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Program<'info, token::Token>,
+    pub caller: Signer<'info>,
+}
+
+/// WrapWsol - Convert native SOL to WSOL for AMM buyback
+/// Call before execute_buy_amm when root token is on PumpSwap AMM
+/// Creates dat_authority's WSOL ATA on first use (rent paid by payer)
+#[derive(Accounts)]
+pub struct WrapWsol<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    /// CHECK: DAT authority PDA (source of native SOL)
+    #[account(mut, seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
+    pub dat_authority: AccountInfo<'info>,
+    /// DAT's WSOL ATA (destination for wrapped SOL)
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = wsol_mint,
+        associated_token::authority = dat_authority,
+        associated_token::token_program = token_program
+    )]
+    pub dat_wsol_account: InterfaceAccount<'info, TokenAccount>,
+    /// WSOL mint (So11111111111111111111111111111111111111112)
+    #[account(address = WSOL_MINT @ ErrorCode::InvalidParameter)]
+    pub wsol_mint: InterfaceAccount<'info, Mint>,
+    /// Pays the ATA rent when the WSOL account does not exist yet
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub token_program: Program<'info, token::Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// ExecuteBuy - Simplified to reduce stack usage (removed unused accounts)
+#[derive(Accounts)]
+pub struct ExecuteBuy<'info> {
+    #[account(mut, seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    /// CHECK: PDA (holds native SOL for buying)
+    #[account(mut, seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
+    pub dat_authority: AccountInfo<'info>,
+    /// Token stats of asdf_mint - pins the active (possibly rotated) DAT token account
+    #[account(seeds = [TOKEN_STATS_SEED, asdf_mint.key().as_ref()], bump = token_stats.load()?.bump)]
+    pub token_stats: AccountLoader<'info, TokenStats>,
+    /// DAT's token account for receiving bought tokens - validated mint, authority and rotation
+    #[account(
+        mut,
+        constraint = dat_asdf_account.mint == asdf_mint.key() @ ErrorCode::InvalidParameter,
+        constraint = dat_asdf_account.owner == dat_authority.key() @ ErrorCode::InvalidParameter,
+        constraint = token_stats.load()?.accepts_token_account(&dat_asdf_account.key()) @ ErrorCode::InvalidTokenAccount
+    )]
+    pub dat_asdf_account: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: Pool (bonding curve) - validated by PumpFun program
+    #[account(mut, constraint = pool.owner == &PUMP_PROGRAM @ ErrorCode::InvalidBondingCurve)]
+    pub pool: AccountInfo<'info>,
+    /// CHECK: Token mint (validation done by PumpFun)
+    #[account(mut)]
+    pub asdf_mint: AccountInfo<'info>,
+    #[account(mut)]
+    pub pool_asdf_account: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: PumpFun Global - fee recipients parsed on-chain (discriminator checked)
+    #[account(constraint = pump_global_config.owner == &PUMP_PROGRAM @ ErrorCode::InvalidPumpGlobalConfig)]
+    pub pump_global_config: AccountInfo<'info>,
+    /// CHECK: Recipient
+    #[account(mut)]
+    pub protocol_fee_recipient: AccountInfo<'info>,
+    /// CHECK: Creator vault (PDA from token creator)
+    #[account(mut)]
+    pub creator_vault: AccountInfo<'info>,
+    /// CHECK: Pump.fun event authority - validated address
+    #[account(address = PUMP_EVENT_AUTHORITY @ ErrorCode::InvalidParameter)]
+    pub pump_event_authority: AccountInfo<'info>,
+    /// CHECK: Pump program - validated program ID via constraint
+    #[account(constraint = pump_swap_program.key() == PUMP_PROGRAM @ ErrorCode::InvalidParameter)]
+    pub pump_swap_program: AccountInfo<'info>,
+    /// CHECK: Global volume accumulator (PDA) - required by Pump.fun buy instruction, validated address
+    #[account(address = PUMP_GLOBAL_VOLUME_ACCUMULATOR @ ErrorCode::InvalidParameter)]
+    pub global_volume_accumulator: AccountInfo<'info>,
+    /// CHECK: User volume accumulator (PDA) - seeds: ["user_volume_accumulator", user]
+    #[account(mut)]
+    pub user_volume_accumulator: AccountInfo<'info>,
+    /// CHECK: Fee config (PDA)
+    pub fee_config: AccountInfo<'info>,
+    /// CHECK: Fee program - validated program ID via constraint
+    #[account(constraint = fee_program.key() == PUMP_FEE_PROGRAM @ ErrorCode::InvalidParameter)]
+    pub fee_program: AccountInfo<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    /// Optional shadow-mode comparison for this mint (recorded only when shadow_math is on)
+    #[account(
+        mut,
+        seeds = [SHADOW_COMPARISON_SEED, asdf_mint.key().as_ref()],
+        bump = shadow_comparison.bump
+    )]
+    pub shadow_comparison: Option<Account<'info, ShadowComparison>>,
+    /// Cycle state machine for this mint (collect → buy → burn ordering)
+    #[account(
+        mut,
+        seeds = [CYCLE_STATE_SEED, asdf_mint.key().as_ref()],
+        bump = cycle_state.bump
+    )]
+    pub cycle_state: Box<Account<'info, CycleState>>,
+    /// Protocol fee recipient rotation (PumpFun global fee recipients if omitted)
+    #[account(seeds = [FEE_RECIPIENTS_SEED], bump = fee_recipients.bump)]
+    pub fee_recipients: Option<Box<Account<'info, FeeRecipients>>>,
+    /// CHECK: Jito tip account - REQUIRED when a tip is paid, checked against JITO_TIP_ACCOUNTS
+    #[account(mut)]
+    pub jito_tip_account: Option<AccountInfo<'info>>,
+}
+
+/// ConfigureShadowMath - Admin toggles shadow-mode math comparison for a mint
+#[derive(Accounts)]
+pub struct ConfigureShadowMath<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + ShadowComparison::LEN,
+        seeds = [SHADOW_COMPARISON_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub shadow_comparison: Account<'info, ShadowComparison>,
+    /// CHECK: Token mint being compared
+    pub mint: AccountInfo<'info>,
+    #[account(mut, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// StartDca - Admin schedules a TWAP-style tranche plan for a mint
+#[derive(Accounts)]
+pub struct StartDca<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + DcaState::LEN,
+        seeds = [DCA_STATE_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub dca_state: Account<'info, DcaState>,
+    /// CHECK: Token mint the plan buys
+    pub mint: AccountInfo<'info>,
+    #[account(mut, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// CancelDca - Admin abandons the remaining tranches of a plan
+#[derive(Accounts)]
+pub struct CancelDca<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(
+        mut,
+        seeds = [DCA_STATE_SEED, dca_state.mint.as_ref()],
+        bump = dca_state.bump
+    )]
+    pub dca_state: Account<'info, DcaState>,
+    pub admin: Signer<'info>,
+}
+
+/// ExecuteBuyTranche - One DCA tranche on the bonding curve (same accounts as ExecuteBuy)
+#[derive(Accounts)]
+pub struct ExecuteBuyTranche<'info> {
+    pub buy: ExecuteBuy<'info>,
+    #[account(
+        mut,
+        seeds = [DCA_STATE_SEED, buy.asdf_mint.key().as_ref()],
+        bump = dca_state.bump
+    )]
+    pub dca_state: Account<'info, DcaState>,
+}
+
+/// CommitBuy - Admin seals the size of the next bonding-curve buy for a mint
+#[derive(Accounts)]
+pub struct CommitBuy<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + BuyCommitment::LEN,
+        seeds = [BUY_COMMIT_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub buy_commitment: Account<'info, BuyCommitment>,
+    /// CHECK: Token mint the committed buy targets
+    pub mint: AccountInfo<'info>,
+    #[account(mut, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// RequestCycleRandomness - Admin binds a freshly committed Switchboard randomness account
+/// to a mint's next cycle window
+#[derive(Accounts)]
+pub struct RequestCycleRandomness<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + CycleWindow::LEN,
+        seeds = [CYCLE_WINDOW_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub cycle_window: Account<'info, CycleWindow>,
+    /// CHECK: Token mint the window gates
+    pub mint: AccountInfo<'info>,
+    /// CHECK: Switchboard RandomnessAccountData - owner checked here, layout parsed in the instruction
+    #[account(constraint = randomness_account.owner == &SWITCHBOARD_ON_DEMAND_PROGRAM @ ErrorCode::InvalidRandomnessAccount)]
+    pub randomness_account: AccountInfo<'info>,
+    #[account(mut, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// SettleCycleWindow - Anyone fixes the window once the oracle revealed the randomness
+#[derive(Accounts)]
+pub struct SettleCycleWindow<'info> {
+    #[account(
+        mut,
+        seeds = [CYCLE_WINDOW_SEED, cycle_window.mint.as_ref()],
+        bump = cycle_window.bump
+    )]
+    pub cycle_window: Account<'info, CycleWindow>,
+    /// CHECK: Switchboard RandomnessAccountData bound by the pending request
+    #[account(
+        constraint = randomness_account.key() == cycle_window.randomness_account @ ErrorCode::RandomnessMismatch,
+        constraint = randomness_account.owner == &SWITCHBOARD_ON_DEMAND_PROGRAM @ ErrorCode::InvalidRandomnessAccount
+    )]
+    pub randomness_account: AccountInfo<'info>,
+}
+
+/// RevealAndBuy - Execute a committed buy on the bonding curve (same accounts as ExecuteBuy)
+#[derive(Accounts)]
+pub struct RevealAndBuy<'info> {
+    pub buy: ExecuteBuy<'info>,
+    #[account(
+        mut,
+        seeds = [BUY_COMMIT_SEED, buy.asdf_mint.key().as_ref()],
+        bump = buy_commitment.bump
+    )]
+    pub buy_commitment: Account<'info, BuyCommitment>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteBuySecondary<'info> {
+    #[account(mut, seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    /// CHECK: PDA (holds native SOL for buying)
+    #[account(mut, seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
+    pub dat_authority: AccountInfo<'info>,
+    /// Token stats of asdf_mint - pins the active (possibly rotated) DAT token account
+    #[account(seeds = [TOKEN_STATS_SEED, asdf_mint.key().as_ref()], bump = token_stats.load()?.bump)]
+    pub token_stats: AccountLoader<'info, TokenStats>,
+    /// DAT's token account - validated mint, authority and rotation
+    #[account(
+        mut,
+        constraint = dat_asdf_account.mint == asdf_mint.key() @ ErrorCode::InvalidParameter,
+        constraint = dat_asdf_account.owner == dat_authority.key() @ ErrorCode::InvalidParameter,
+        constraint = token_stats.load()?.accepts_token_account(&dat_asdf_account.key()) @ ErrorCode::InvalidTokenAccount
+    )]
+    pub dat_asdf_account: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: Pool (bonding curve) - validated owner
+    #[account(mut, constraint = pool.owner == &PUMP_PROGRAM @ ErrorCode::InvalidBondingCurve)]
+    pub pool: AccountInfo<'info>,
+    #[account(mut)]
+    pub asdf_mint: InterfaceAccount<'info, Mint>,
+    /// Pool's token account - validated mint matches
+    #[account(
+        mut,
+        constraint = pool_asdf_account.mint == asdf_mint.key() @ ErrorCode::InvalidParameter
+    )]
+    pub pool_asdf_account: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: PumpFun Global - fee recipients parsed on-chain (discriminator checked)
+    #[account(constraint = pump_global_config.owner == &PUMP_PROGRAM @ ErrorCode::InvalidPumpGlobalConfig)]
+    pub pump_global_config: AccountInfo<'info>,
+    /// CHECK: Recipient
+    #[account(mut)]
+    pub protocol_fee_recipient: AccountInfo<'info>,
+    /// CHECK: Creator vault (PDA from token creator)
+    #[account(mut)]
+    pub creator_vault: AccountInfo<'info>,
+    /// CHECK: Pump.fun event authority - validated address
+    #[account(address = PUMP_EVENT_AUTHORITY @ ErrorCode::InvalidParameter)]
+    pub pump_event_authority: AccountInfo<'info>,
+    /// CHECK: Pump program - validated program ID via constraint
+    #[account(constraint = pump_swap_program.key() == PUMP_PROGRAM @ ErrorCode::InvalidParameter)]
+    pub pump_swap_program: AccountInfo<'info>,
+    /// CHECK: Global volume accumulator (PDA) - required by Pump.fun buy instruction, validated address
+    #[account(address = PUMP_GLOBAL_VOLUME_ACCUMULATOR @ ErrorCode::InvalidParameter)]
+    pub global_volume_accumulator: AccountInfo<'info>,
+    /// CHECK: User volume accumulator (PDA) - seeds: ["user_volume_accumulator", user]
+    #[account(mut)]
+    pub user_volume_accumulator: AccountInfo<'info>,
+    /// CHECK: Fee config (PDA)
+    pub fee_config: AccountInfo<'info>,
+    /// CHECK: Fee program - validated program ID via constraint
+    #[account(constraint = fee_program.key() == PUMP_FEE_PROGRAM @ ErrorCode::InvalidParameter)]
+    pub fee_program: AccountInfo<'info>,
+    /// CHECK: Root treasury PDA (REQUIRED for secondary tokens) - of the assigned root
+    #[account(mut)]
+    pub root_treasury: Option<AccountInfo<'info>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    /// Cycle state machine for this mint (collect → buy → burn ordering)
+    #[account(
+        mut,
+        seeds = [CYCLE_STATE_SEED, asdf_mint.key().as_ref()],
+        bump = cycle_state.bump
+    )]
+    pub cycle_state: Box<Account<'info, CycleState>>,
+    /// Protocol fee recipient rotation (PumpFun global fee recipients if omitted)
+    #[account(seeds = [FEE_RECIPIENTS_SEED], bump = fee_recipients.bump)]
+    pub fee_recipients: Option<Box<Account<'info, FeeRecipients>>>,
+    /// CHECK: TokenConfig PDA of asdf_mint (may be uninitialized) - read for its assigned root
+    #[account(seeds = [TOKEN_CONFIG_SEED, asdf_mint.key().as_ref()], bump)]
+    pub token_config: AccountInfo<'info>,
+    /// Root set - REQUIRED when token_config assigns a root other than the primary
+    #[account(seeds = [ROOT_SET_SEED], bump = root_set.bump)]
+    pub root_set: Option<Box<Account<'info, RootSet>>>,
+    /// Root share stream - when streaming, the root share accrues instead of being transferred
+    #[account(
+        mut,
+        seeds = [ROOT_STREAM_SEED, asdf_mint.key().as_ref()],
+        bump = root_stream.bump
+    )]
+    pub root_stream: Option<Box<Account<'info, RootStream>>>,
+    /// CHECK: Jito tip account - REQUIRED when a tip is paid, checked against JITO_TIP_ACCOUNTS
+    #[account(mut)]
+    pub jito_tip_account: Option<AccountInfo<'info>>,
+}
+
+/// ProvideLiquidity - Deposit a share of the cycle's WSOL and bought tokens into the
+/// PumpSwap pool between buy and burn (admin only); LP tokens stay with dat_authority
+#[derive(Accounts)]
+pub struct ProvideLiquidity<'info> {
+    #[account(mut, seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    /// CHECK: PDA authority (provides both sides and holds the LP tokens)
+    #[account(seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
+    pub dat_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [TOKEN_STATS_SEED, base_mint.key().as_ref()],
+        bump = token_stats.load()?.bump
+    )]
+    pub token_stats: AccountLoader<'info, TokenStats>,
+    #[account(
+        mut,
+        seeds = [CYCLE_STATE_SEED, base_mint.key().as_ref()],
+        bump = cycle_state.bump
+    )]
+    pub cycle_state: Box<Account<'info, CycleState>>,
+    /// CHECK: AMM Pool account - owned by PumpSwap program
+    #[account(mut, constraint = pool.owner == &PUMP_SWAP_PROGRAM @ ErrorCode::InvalidPool)]
+    pub pool: AccountInfo<'info>,
+    /// CHECK: PumpSwap global config - validated address
+    #[account(address = PUMPSWAP_GLOBAL_CONFIG @ ErrorCode::InvalidParameter)]
+    pub global_config: AccountInfo<'info>,
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// CHECK: Quote token mint (WSOL) - validated address
+    #[account(address = WSOL_MINT @ ErrorCode::PoolQuoteMintMismatch)]
+    pub quote_mint: AccountInfo<'info>,
+    /// CHECK: Pool LP mint - validated by PumpSwap against the pool
+    #[account(mut)]
+    pub lp_mint: AccountInfo<'info>,
+    /// DAT token account holding the cycle's bought tokens
+    #[account(
+        mut,
+        constraint = dat_token_account.mint == base_mint.key() @ ErrorCode::MintMismatch,
+        constraint = dat_token_account.owner == dat_authority.key() @ ErrorCode::InvalidParameter,
+        constraint = token_stats.load()?.accepts_token_account(&dat_token_account.key()) @ ErrorCode::InvalidTokenAccount
+    )]
+    pub dat_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// DAT WSOL account holding the cycle's liquidity SOL
+    #[account(
+        mut,
+        constraint = dat_wsol_account.mint == WSOL_MINT @ ErrorCode::MintMismatch,
+        constraint = dat_wsol_account.owner == dat_authority.key() @ ErrorCode::InvalidParameter
+    )]
+    pub dat_wsol_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// DAT LP token account (receives the LP tokens)
+    #[account(
+        mut,
+        constraint = dat_lp_token_account.mint == lp_mint.key() @ ErrorCode::MintMismatch,
+        constraint = dat_lp_token_account.owner == dat_authority.key() @ ErrorCode::InvalidParameter
+    )]
+    pub dat_lp_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: Pool's base token account
+    #[account(mut)]
+    pub pool_base_token_account: AccountInfo<'info>,
+    /// CHECK: Pool's quote token account (WSOL)
+    #[account(mut)]
+    pub pool_quote_token_account: AccountInfo<'info>,
+    /// CHECK: SPL Token program - validated address
+    #[account(address = anchor_spl::token::ID @ ErrorCode::InvalidParameter)]
+    pub token_program: AccountInfo<'info>,
+    /// CHECK: Token2022 program (LP mint) - validated address
+    #[account(address = TOKEN_2022_PROGRAM @ ErrorCode::InvalidParameter)]
+    pub token_2022_program: AccountInfo<'info>,
+    /// CHECK: PumpSwap event authority (PDA) - validated address
+    #[account(address = PUMPSWAP_EVENT_AUTHORITY @ ErrorCode::InvalidParameter)]
+    pub event_authority: AccountInfo<'info>,
+    /// CHECK: PumpSwap AMM program - validated address
+    #[account(address = PUMP_SWAP_PROGRAM @ ErrorCode::InvalidParameter)]
+    pub pump_swap_program: AccountInfo<'info>,
+    pub admin: Signer<'info>,
+}
+
+/// ExecuteBuyAMM - For PumpSwap AMM pools (migrated tokens)
+/// Requires 23+ accounts as per PumpSwap AMM specification
+#[derive(Accounts)]
+pub struct ExecuteBuyAMM<'info> {
+    // DAT State accounts
+    #[account(mut, seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    /// CHECK: PDA authority (holds WSOL, acts as "user" in AMM)
+    #[account(mut, seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
+    pub dat_authority: AccountInfo<'info>,
+    /// DAT's token account for receiving bought tokens - validated mint and authority
+    #[account(
+        mut,
+        constraint = dat_token_account.mint == base_mint.key() @ ErrorCode::InvalidParameter,
+        constraint = dat_token_account.owner == dat_authority.key() @ ErrorCode::InvalidParameter
+    )]
+    pub dat_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    // PumpSwap AMM Core accounts (1-9)
+    /// CHECK: AMM Pool account - owned by PumpSwap program
+    #[account(mut, constraint = pool.owner == &PUMP_SWAP_PROGRAM @ ErrorCode::InvalidBondingCurve)]
+    pub pool: AccountInfo<'info>,
+    /// CHECK: PumpSwap global config
+    pub global_config: AccountInfo<'info>,
+    /// Base token mint (the token being bought)
+    pub base_mint: InterfaceAccount<'info, Mint>,
+    /// CHECK: Quote token mint (WSOL)
+    pub quote_mint: AccountInfo<'info>,
+    /// CHECK: DAT's WSOL account (user_quote_token_account)
+    #[account(mut)]
+    pub dat_wsol_account: AccountInfo<'info>,
+    /// CHECK: Pool's base token account
+    #[account(mut)]
+    pub pool_base_token_account: AccountInfo<'info>,
+    /// CHECK: Pool's quote token account (WSOL)
+    #[account(mut)]
+    pub pool_quote_token_account: AccountInfo<'info>,
+
+    // Protocol fee accounts (10-11)
+    /// CHECK: Protocol fee recipient
+    pub protocol_fee_recipient: AccountInfo<'info>,
+    /// CHECK: Protocol fee recipient's token account (PDA)
+    #[account(mut)]
+    pub protocol_fee_recipient_ata: AccountInfo<'info>,
+
+    // Program accounts (12-17)
+    /// Base token program (SPL Token or Token2022)
+    pub base_token_program: Interface<'info, TokenInterface>,
+    /// CHECK: Quote token program (always SPL Token for WSOL) - validated via constraint
+    #[account(constraint = quote_token_program.key() == anchor_spl::token::ID @ ErrorCode::InvalidParameter)]
+    pub quote_token_program: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: Associated token program - validated via constraint (creates the WSOL ATA in execute_buy_amm_wrapped)
+    #[account(constraint = associated_token_program.key() == anchor_spl::associated_token::ID @ ErrorCode::InvalidParameter)]
+    pub associated_token_program: AccountInfo<'info>,
+    /// CHECK: PumpSwap event authority (PDA) - validated address
+    #[account(address = PUMPSWAP_EVENT_AUTHORITY @ ErrorCode::InvalidParameter)]
+    pub event_authority: AccountInfo<'info>,
+    /// CHECK: PumpSwap AMM program - validated via constraint
+    #[account(constraint = pump_swap_program.key() == PUMP_SWAP_PROGRAM @ ErrorCode::InvalidParameter)]
+    pub pump_swap_program: AccountInfo<'info>,
+
+    // Creator fee accounts (18-19)
+    /// CHECK: Coin creator vault ATA (receives creator fees)
+    #[account(mut)]
+    pub coin_creator_vault_ata: AccountInfo<'info>,
+    /// CHECK: Coin creator vault authority (PDA)
+    pub coin_creator_vault_authority: AccountInfo<'info>,
+
+    // Volume tracking accounts (20-23)
+    /// CHECK: Global volume accumulator (PDA) - validated address
+    #[account(address = PUMPSWAP_GLOBAL_VOLUME_ACCUMULATOR @ ErrorCode::InvalidParameter)]
+    pub global_volume_accumulator: AccountInfo<'info>,
+    /// CHECK: User volume accumulator (PDA)
+    #[account(mut)]
+    pub user_volume_accumulator: AccountInfo<'info>,
+    /// CHECK: Fee config (PDA)
+    pub fee_config: AccountInfo<'info>,
+    /// CHECK: Fee program - validated program ID via constraint
+    #[account(constraint = fee_program.key() == PUMP_FEE_PROGRAM @ ErrorCode::InvalidParameter)]
+    pub fee_program: AccountInfo<'info>,
+    /// Cycle state machine for this mint (collect → buy → burn ordering)
+    #[account(
+        mut,
+        seeds = [CYCLE_STATE_SEED, base_mint.key().as_ref()],
+        bump = cycle_state.bump
+    )]
+    pub cycle_state: Box<Account<'info, CycleState>>,
+}
+
+/// SettlePendingFees - Deduct an ecosystem allocation from a token's pending fees (admin only)
+#[derive(Accounts)]
+#[instruction(mint: Pubkey)]
+pub struct SettlePendingFees<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+
+    #[account(
+        mut,
+        seeds = [TOKEN_STATS_SEED, mint.as_ref()],
+        bump = token_stats.load()?.bump
+    )]
+    pub token_stats: AccountLoader<'info, TokenStats>,
+
+    /// Admin signer required - only admin can settle allocations
+    #[account(constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeAllocatedCycle<'info> {
+    #[account(mut, seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+
+    #[account(
+        mut,
+        seeds = [TOKEN_STATS_SEED, token_stats.load()?.mint.as_ref()],
+        bump = token_stats.load()?.bump
+    )]
+    pub token_stats: AccountLoader<'info, TokenStats>,
+
+    /// Admin signer required - only admin can finalize allocated cycles
+    #[account(constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct BurnAndUpdate<'info> {
+    #[account(mut, seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(
+        mut,
+        seeds = [TOKEN_STATS_SEED, asdf_mint.key().as_ref()],
+        bump = token_stats.load()?.bump
+    )]
+    pub token_stats: AccountLoader<'info, TokenStats>,
+    /// CHECK: PDA (mut: funds the one-time stats_payer refund)
+    #[account(mut, seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
+    pub dat_authority: AccountInfo<'info>,
+    /// DAT token account - must be the rotated account once rotate_token_account was used
+    #[account(
+        mut,
+        constraint = token_stats.load()?.accepts_token_account(&dat_asdf_account.key()) @ ErrorCode::InvalidTokenAccount
+    )]
+    pub dat_asdf_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = asdf_mint.to_account_info().owner == token_program.key @ ErrorCode::InvalidAccountOwner
+    )]
+    pub asdf_mint: InterfaceAccount<'info, Mint>,
+    /// Community treasury token PDA (REQUIRED when token_stats.burn_bps < 10000)
+    /// Receives the non-burned remainder of the cycle
+    #[account(
+        mut,
+        seeds = [COMMUNITY_TREASURY_SEED, asdf_mint.key().as_ref()],
+        bump,
+        token::mint = asdf_mint,
+        token::authority = dat_authority,
+        token::token_program = token_program
+    )]
+    pub community_treasury: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// Holder reflection vault (REQUIRED when token_stats.reflection_bps > 0)
+    #[account(
+        mut,
+        seeds = [REWARDS_VAULT_SEED, asdf_mint.key().as_ref()],
+        bump = rewards_vault.bump
+    )]
+    pub rewards_vault: Option<Box<Account<'info, RewardsVault>>>,
+    /// Rewards token PDA receiving the reflected share (REQUIRED with rewards_vault)
+    #[account(
+        mut,
+        seeds = [REWARDS_TOKEN_SEED, asdf_mint.key().as_ref()],
+        bump,
+        token::mint = asdf_mint,
+        token::authority = dat_authority,
+        token::token_program = token_program
+    )]
+    pub rewards_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    /// CHECK: Optional Pyth SOL/USD PriceUpdateV2 - owner checked here, layout/feed/staleness in parse_sol_usd_price
+    /// When provided, the burn is valued in USD and added to token_stats.usd_value_burned
+    #[account(constraint = price_oracle.owner == &PYTH_RECEIVER_PROGRAM @ ErrorCode::InvalidOracle)]
+    pub price_oracle: Option<AccountInfo<'info>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    /// CHECK: Optional - account that initialized token_stats, refunded its rent once at the first burn
+    /// Refund is deferred to a later burn when omitted
+    #[account(
+        mut,
+        constraint = stats_payer.key() == token_stats.load()?.stats_payer @ ErrorCode::InvalidParameter
+    )]
+    pub stats_payer: Option<AccountInfo<'info>>,
+    /// Required alongside stats_payer (dat_authority -> stats_payer transfer)
+    pub system_program: Option<Program<'info, System>>,
+    /// Cycle state machine for this mint (collect → buy → burn ordering)
+    #[account(
+        mut,
+        seeds = [CYCLE_STATE_SEED, asdf_mint.key().as_ref()],
+        bump = cycle_state.bump
+    )]
+    pub cycle_state: Box<Account<'info, CycleState>>,
+    /// Optional ring buffer of recent cycles (recorded when provided)
+    #[account(
+        mut,
+        seeds = [CYCLE_HISTORY_SEED, asdf_mint.key().as_ref()],
+        bump = cycle_history.bump
+    )]
+    pub cycle_history: Option<Box<Account<'info, CycleHistory>>>,
+    /// Optional current-day activity bucket (recorded when provided)
+    #[account(
+        mut,
+        seeds = [DAILY_STATS_SEED, token_stats.load()?.mint.as_ref(), &daily_stats.day.to_le_bytes()],
+        bump = daily_stats.bump
+    )]
+    pub daily_stats: Option<Account<'info, DailyStats>>,
+    /// Optional cranker - earns the keeper reward when it signs with its keeper_info
+    #[account(mut)]
+    pub keeper: Option<Signer<'info>>,
+    /// Optional registered keeper record of `keeper` (REQUIRED with keeper to be rewarded)
+    #[account(
+        mut,
+        seeds = [KEEPER_SEED, keeper_info.keeper.as_ref()],
+        bump = keeper_info.bump
+    )]
+    pub keeper_info: Option<Box<Account<'info, KeeperInfo>>>,
+}
+
+/// RegisterKeeper - A cranker registers for cycle rewards (keeper funds rent)
+#[derive(Accounts)]
+pub struct RegisterKeeper<'info> {
+    #[account(
+        init,
+        payer = keeper,
+        space = 8 + KeeperInfo::LEN,
+        seeds = [KEEPER_SEED, keeper.key().as_ref()],
+        bump
+    )]
+    pub keeper_info: Account<'info, KeeperInfo>,
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// DeactivateKeeper - Admin or the keeper itself stops a keeper's rewards
+#[derive(Accounts)]
+pub struct DeactivateKeeper<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(
+        mut,
+        seeds = [KEEPER_SEED, keeper_info.keeper.as_ref()],
+        bump = keeper_info.bump
+    )]
+    pub keeper_info: Account<'info, KeeperInfo>,
+    #[account(
+        constraint = authority.key() == dat_state.load()?.admin
+            || authority.key() == keeper_info.keeper @ ErrorCode::UnauthorizedAccess
+    )]
+    pub authority: Signer<'info>,
+}
+
+/// InitializeCycleState - Create a mint's cycle state machine (permissionless, payer funds rent)
+#[derive(Accounts)]
+pub struct InitializeCycleState<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + CycleState::LEN,
+        seeds = [CYCLE_STATE_SEED, token_stats.load()?.mint.as_ref()],
+        bump
+    )]
+    pub cycle_state: Account<'info, CycleState>,
+    #[account(seeds = [TOKEN_STATS_SEED, token_stats.load()?.mint.as_ref()], bump = token_stats.load()?.bump)]
+    pub token_stats: AccountLoader<'info, TokenStats>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// InitializeCycleHistory - Create a mint's recent-cycle ring buffer (permissionless, payer funds rent)
+#[derive(Accounts)]
+pub struct InitializeCycleHistory<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + CycleHistory::LEN,
+        seeds = [CYCLE_HISTORY_SEED, token_stats.load()?.mint.as_ref()],
+        bump
+    )]
+    pub cycle_history: Box<Account<'info, CycleHistory>>,
+    #[account(seeds = [TOKEN_STATS_SEED, token_stats.load()?.mint.as_ref()], bump = token_stats.load()?.bump)]
+    pub token_stats: AccountLoader<'info, TokenStats>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// InitializeDailyStats - Create a mint's bucket for today or tomorrow (permissionless, payer funds rent)
+#[derive(Accounts)]
+#[instruction(day: i64)]
+pub struct InitializeDailyStats<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + DailyStats::LEN,
+        seeds = [DAILY_STATS_SEED, token_stats.load()?.mint.as_ref(), &day.to_le_bytes()],
+        bump
+    )]
+    pub daily_stats: Account<'info, DailyStats>,
+    #[account(seeds = [TOKEN_STATS_SEED, token_stats.load()?.mint.as_ref()], bump = token_stats.load()?.bump)]
+    pub token_stats: AccountLoader<'info, TokenStats>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// AbortCycle - Reset a cycle stuck in Collected/Bought (permissionless after CYCLE_ABORT_TIMEOUT)
+/// Also releases the cycle lock if this mint holds it
+#[derive(Accounts)]
+pub struct AbortCycle<'info> {
+    #[account(mut, seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(
+        mut,
+        seeds = [CYCLE_STATE_SEED, cycle_state.mint.as_ref()],
+        bump = cycle_state.bump
+    )]
+    pub cycle_state: Account<'info, CycleState>,
+    pub caller: Signer<'info>,
+}
+
+/// ViewCirculatingSupply - Permissionless read of burn-adjusted circulating supply
+/// Optional holdings default to 0 when omitted; each one is validated when provided
+#[derive(Accounts)]
+pub struct ViewCirculatingSupply<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(
+        seeds = [TOKEN_STATS_SEED, mint.key().as_ref()],
+        bump = token_stats.load()?.bump
+    )]
+    pub token_stats: AccountLoader<'info, TokenStats>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    /// CHECK: DAT authority PDA - owner of protocol token accounts
+    #[account(seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
+    pub dat_authority: AccountInfo<'info>,
+    /// Active DAT token account (tokens bought, awaiting burn)
+    #[account(
+        constraint = dat_token_account.mint == mint.key() @ ErrorCode::MintMismatch,
+        constraint = dat_token_account.owner == dat_authority.key() @ ErrorCode::InvalidParameter,
+        constraint = token_stats.load()?.accepts_token_account(&dat_token_account.key()) @ ErrorCode::InvalidTokenAccount
+    )]
+    pub dat_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        seeds = [COMMUNITY_TREASURY_SEED, mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = dat_authority
+    )]
+    pub community_treasury: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(seeds = [REBATE_POOL_SEED], bump = rebate_pool.bump)]
+    pub rebate_pool: Option<Account<'info, RebatePool>>,
+    /// Rebate pool token account (owned by the rebate pool PDA)
+    #[account(
+        constraint = rebate_pool_ata.mint == mint.key() @ ErrorCode::MintMismatch,
+        constraint = rebate_pool.as_ref().is_some_and(|p| rebate_pool_ata.owner == p.key()) @ ErrorCode::InvalidParameter
+    )]
+    pub rebate_pool_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+/// SelectBestVenue - Permissionless pre-instruction quoting every venue of a mint
+/// remaining_accounts: [pool, pool_base_token_account, pool_quote_token_account] per AMM pool
+#[derive(Accounts)]
+pub struct SelectBestVenue<'info> {
+    pub mint: InterfaceAccount<'info, Mint>,
+    /// CHECK: Optional PumpFun bonding curve - owner checked here, PDA and layout in select_best_venue
+    #[account(constraint = bonding_curve.owner == &PUMP_PROGRAM @ ErrorCode::InvalidBondingCurve)]
+    pub bonding_curve: Option<AccountInfo<'info>>,
+}
+
+/// QuoteBuy - Permissionless bonding curve buy quote using the on-chain buy math
+#[derive(Accounts)]
+pub struct QuoteBuy<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    /// CHECK: PumpFun bonding curve PDA ["bonding-curve", mint] - layout parsed by the buy math
+    #[account(
+        seeds = [PUMP_BONDING_CURVE_SEED, mint.key().as_ref()],
+        bump,
+        seeds::program = PUMP_PROGRAM
+    )]
+    pub bonding_curve: AccountInfo<'info>,
+}
+
+/// InitializeCommunityTreasury - Create the community treasury token PDA for a mint
+/// Token account owned by dat_authority, seeds: ["community_treasury", mint]
+#[derive(Accounts)]
+pub struct InitializeCommunityTreasury<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    /// CHECK: DAT authority PDA - owner of the treasury token account
+    #[account(seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
+    pub dat_authority: AccountInfo<'info>,
+    #[account(
+        init,
+        payer = admin,
+        seeds = [COMMUNITY_TREASURY_SEED, mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = dat_authority,
+        token::token_program = token_program
+    )]
+    pub community_treasury: InterfaceAccount<'info, TokenAccount>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(mut, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub admin: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// InitializeRewardsVault - Create a mint's holder reflection vault and its token PDA (admin only)
+/// Token account owned by dat_authority, seeds: ["rewards_tokens_v1", mint]
+#[derive(Accounts)]
+pub struct InitializeRewardsVault<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    /// CHECK: DAT authority PDA - owner of the rewards token account
+    #[account(seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
+    pub dat_authority: AccountInfo<'info>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + RewardsVault::LEN,
+        seeds = [REWARDS_VAULT_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub rewards_vault: Account<'info, RewardsVault>,
+    #[account(
+        init,
+        payer = admin,
+        seeds = [REWARDS_TOKEN_SEED, mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = dat_authority,
+        token::token_program = token_program
+    )]
+    pub rewards_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(mut, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub admin: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// PostReflectionSnapshot - Admin posts a holder Merkle snapshot starting a new reflection epoch
+#[derive(Accounts)]
+pub struct PostReflectionSnapshot<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(
+        mut,
+        seeds = [REWARDS_VAULT_SEED, rewards_vault.mint.as_ref()],
+        bump = rewards_vault.bump
+    )]
+    pub rewards_vault: Account<'info, RewardsVault>,
+    pub admin: Signer<'info>,
+}
+
+/// ClaimReflection - Holder claims their leaf of the current reflection snapshot (holder funds rent)
+#[derive(Accounts)]
+pub struct ClaimReflection<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    /// CHECK: DAT authority PDA - owner of the rewards token account
+    #[account(seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
+    pub dat_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [REWARDS_VAULT_SEED, mint.key().as_ref()],
+        bump = rewards_vault.bump
+    )]
+    pub rewards_vault: Account<'info, RewardsVault>,
+    #[account(
+        init,
+        payer = holder,
+        space = 8 + ReflectionClaim::LEN,
+        seeds = [
+            REFLECTION_CLAIM_SEED,
+            rewards_vault.key().as_ref(),
+            &rewards_vault.epoch.to_le_bytes(),
+            holder.key().as_ref()
+        ],
+        bump
+    )]
+    pub claim_record: Account<'info, ReflectionClaim>,
+    #[account(mut, seeds = [REWARDS_TOKEN_SEED, mint.key().as_ref()], bump)]
+    pub rewards_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        constraint = holder_token_account.mint == mint.key() @ ErrorCode::MintMismatch,
+        constraint = holder_token_account.owner == holder.key() @ ErrorCode::InvalidParameter
+    )]
+    pub holder_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub holder: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// RotateTokenAccount - Move a mint's DAT balance to a fresh PDA token account
+/// Recovery path when the current DAT account was delegated or otherwise compromised
+/// New account seeds: ["dat_token_account", mint, nonce] where nonce = previous + 1
+#[derive(Accounts)]
+#[instruction(nonce: u8)]
+pub struct RotateTokenAccount<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    /// CHECK: DAT authority PDA - owner of both token accounts
+    #[account(seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
+    pub dat_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [TOKEN_STATS_SEED, mint.key().as_ref()],
+        bump = token_stats.load()?.bump,
+        constraint = nonce == token_stats.load()?.token_account_nonce.saturating_add(1) @ ErrorCode::InvalidParameter
+    )]
+    pub token_stats: AccountLoader<'info, TokenStats>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    /// Current (compromised) DAT token account
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = dat_authority,
+        token::token_program = token_program,
+        constraint = token_stats.load()?.accepts_token_account(&old_token_account.key()) @ ErrorCode::InvalidTokenAccount
+    )]
+    pub old_token_account: InterfaceAccount<'info, TokenAccount>,
+    /// Fresh DAT token account (PDA, not an ATA)
+    #[account(
+        init,
+        payer = admin,
+        seeds = [DAT_TOKEN_ACCOUNT_SEED, mint.key().as_ref(), &[nonce]],
+        bump,
+        token::mint = mint,
+        token::authority = dat_authority,
+        token::token_program = token_program
+    )]
+    pub new_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub admin: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// SetBurnRatio - Admin queues the per-token burn ratio (remainder to community treasury)
+#[derive(Accounts)]
+pub struct SetBurnRatio<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(
+        seeds = [TOKEN_STATS_SEED, token_stats.load()?.mint.as_ref()],
+        bump = token_stats.load()?.bump
+    )]
+    pub token_stats: AccountLoader<'info, TokenStats>,
+    #[account(mut, seeds = [CHANGE_QUEUE_SEED], bump = change_queue.bump)]
+    pub change_queue: Box<Account<'info, ChangeQueue>>,
+    pub admin: Signer<'info>,
+    /// Community treasury token PDA (REQUIRED when set_burn_ratio sets burn_bps < 10000)
+    /// burn_and_update cannot route the non-burned remainder without it
+    #[account(
+        seeds = [COMMUNITY_TREASURY_SEED, token_stats.load()?.mint.as_ref()],
+        bump,
+        constraint = community_treasury.mint == token_stats.load()?.mint @ ErrorCode::InvalidCommunityTreasury
+    )]
+    pub community_treasury: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+/// SetReflectionRatio - Admin queues the share of a token's burn reflected to holders
+#[derive(Accounts)]
+pub struct SetReflectionRatio<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(
+        seeds = [TOKEN_STATS_SEED, token_stats.load()?.mint.as_ref()],
+        bump = token_stats.load()?.bump
+    )]
+    pub token_stats: AccountLoader<'info, TokenStats>,
+    #[account(mut, seeds = [CHANGE_QUEUE_SEED], bump = change_queue.bump)]
+    pub change_queue: Box<Account<'info, ChangeQueue>>,
+    pub admin: Signer<'info>,
+}
+
+/// SetLiquidityRatio - Admin queues the share of each cycle seeded into the PumpSwap pool
+#[derive(Accounts)]
+pub struct SetLiquidityRatio<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(
+        seeds = [TOKEN_STATS_SEED, token_stats.load()?.mint.as_ref()],
+        bump = token_stats.load()?.bump
+    )]
+    pub token_stats: AccountLoader<'info, TokenStats>,
+    #[account(mut, seeds = [CHANGE_QUEUE_SEED], bump = change_queue.bump)]
+    pub change_queue: Box<Account<'info, ChangeQueue>>,
+    pub admin: Signer<'info>,
+}
+
+/// SetDustThreshold - Admin queues the largest balance sweep_dust may burn for a token
+#[derive(Accounts)]
+pub struct SetDustThreshold<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(
+        seeds = [TOKEN_STATS_SEED, token_stats.load()?.mint.as_ref()],
+        bump = token_stats.load()?.bump
+    )]
+    pub token_stats: AccountLoader<'info, TokenStats>,
+    #[account(mut, seeds = [CHANGE_QUEUE_SEED], bump = change_queue.bump)]
+    pub change_queue: Box<Account<'info, ChangeQueue>>,
+    pub admin: Signer<'info>,
+}
+
+/// SweepDust - Burn a residual DAT token balance below the dust threshold (permissionless, between cycles)
+#[derive(Accounts)]
+#[instruction(mint: Pubkey)]
+pub struct SweepDust<'info> {
+    #[account(mut, seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(
+        mut,
+        seeds = [TOKEN_STATS_SEED, mint.as_ref()],
+        bump = token_stats.load()?.bump
+    )]
+    pub token_stats: AccountLoader<'info, TokenStats>,
+    #[account(
+        seeds = [CYCLE_STATE_SEED, mint.as_ref()],
+        bump = cycle_state.bump
+    )]
+    pub cycle_state: Box<Account<'info, CycleState>>,
+    /// CHECK: PDA - owner of the DAT token account, signs the burn
+    #[account(seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
+    pub dat_authority: AccountInfo<'info>,
+    /// Active DAT token account for this mint (rotated account or dat_authority ATA)
+    #[account(
+        mut,
+        constraint = dat_token_account.key()
+            == token_stats.load()?.active_token_account(&dat_authority.key(), &token_program.key()) @ ErrorCode::InvalidTokenAccount
+    )]
+    pub dat_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        address = mint @ ErrorCode::InvalidParameter,
+        constraint = token_mint.to_account_info().owner == token_program.key @ ErrorCode::InvalidAccountOwner
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub caller: Signer<'info>,
+}
+
+/// CommitDailySnapshot - Operator commits the day's TokenStats Merkle root (once per day)
+#[derive(Accounts)]
+#[instruction(day: u64)]
+pub struct CommitDailySnapshot<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + StatsSnapshot::LEN,
+        seeds = [STATS_SNAPSHOT_SEED, &day.to_le_bytes()],
+        bump
+    )]
+    pub stats_snapshot: Account<'info, StatsSnapshot>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// RollupGlobalStats - Admin recomputes ecosystem aggregates from TokenStats batches
+/// remaining_accounts: TokenStats accounts, strictly ascending by mint across batches
+#[derive(Accounts)]
+pub struct RollupGlobalStats<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + GlobalStats::LEN,
+        seeds = [GLOBAL_STATS_SEED],
+        bump
+    )]
+    pub global_stats: Box<Account<'info, GlobalStats>>,
+    #[account(mut, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// VerifyInvariants - Permissionless on-chain accounting tripwire
+/// remaining_accounts: TokenStats accounts, strictly ascending by mint (pass every token
+/// for the ecosystem-wide checks to be meaningful)
+#[derive(Accounts)]
+pub struct VerifyInvariants<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    /// CHECK: DAT authority PDA - holds collected SOL until it is spent
+    #[account(seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
+    pub dat_authority: AccountInfo<'info>,
+    /// CHECK: Creator vault - PDA ["creator-vault", dat_authority] under PUMP_PROGRAM (uncollected fees)
+    #[account(constraint = creator_vault.key() == pump_creator_vault(&dat_authority.key()) @ ErrorCode::InvalidCreatorVault)]
+    pub creator_vault: AccountInfo<'info>,
+}
+
+/// VerifySnapshotInclusion - Permissionless proof check against a daily snapshot
+#[derive(Accounts)]
+pub struct VerifySnapshotInclusion<'info> {
+    #[account(seeds = [STATS_SNAPSHOT_SEED, &stats_snapshot.day.to_le_bytes()], bump = stats_snapshot.bump)]
+    pub stats_snapshot: Account<'info, StatsSnapshot>,
+}
+
+/// ProposeTokenPartner - Admin proposes a token's listing partner revenue share (timelocked)
+#[derive(Accounts)]
+pub struct ProposeTokenPartner<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(mut, seeds = [TOKEN_STATS_SEED, token_stats.load()?.mint.as_ref()], bump = token_stats.load()?.bump)]
+    pub token_stats: AccountLoader<'info, TokenStats>,
+    pub admin: Signer<'info>,
+}
+
+/// ApplyTokenPartner - Admin applies a proposed partner after the cooldown
+/// Creates the partner's PartnerStats on first use (admin pays rent)
+#[derive(Accounts)]
+pub struct ApplyTokenPartner<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(mut, seeds = [TOKEN_STATS_SEED, token_stats.load()?.mint.as_ref()], bump = token_stats.load()?.bump)]
+    pub token_stats: AccountLoader<'info, TokenStats>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + PartnerStats::LEN,
+        seeds = [PARTNER_STATS_SEED, token_stats.load()?.pending_partner.as_ref()],
+        bump
+    )]
+    pub partner_stats: Account<'info, PartnerStats>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// ConfigureAlerts - Admin queues operational alert thresholds
+#[derive(Accounts)]
+pub struct ConfigureAlerts<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + AlertConfig::LEN,
+        seeds = [ALERT_CONFIG_SEED],
+        bump
+    )]
+    pub alert_config: Account<'info, AlertConfig>,
+    #[account(mut, seeds = [CHANGE_QUEUE_SEED], bump = change_queue.bump)]
+    pub change_queue: Box<Account<'info, ChangeQueue>>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// CheckAlerts - Permissionless threshold check (emits AlertRaised per breach)
+#[derive(Accounts)]
+pub struct CheckAlerts<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(seeds = [ALERT_CONFIG_SEED], bump = alert_config.bump)]
+    pub alert_config: Account<'info, AlertConfig>,
+    /// CHECK: DAT authority PDA (balance only)
+    #[account(seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
+    pub dat_authority: AccountInfo<'info>,
+    /// CHECK: Rebate pool PDA (owner check for rebate_pool_ata)
+    #[account(seeds = [REBATE_POOL_SEED], bump)]
+    pub rebate_pool: Option<AccountInfo<'info>>,
+    /// Rebate pool's $ASDF ATA - omit to skip the rebate pool alert
+    #[account(
+        constraint = rebate_pool_ata.mint == dat_state.load()?.asdf_mint @ ErrorCode::MintMismatch,
+        constraint = rebate_pool.as_ref().map(|p| p.key()) == Some(rebate_pool_ata.owner) @ ErrorCode::InvalidRebatePool
+    )]
+    pub rebate_pool_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+/// SetTokenInfoUri - Admin sets a token's ecosystem directory URI
+#[derive(Accounts)]
+pub struct SetTokenInfoUri<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(seeds = [TOKEN_STATS_SEED, token_stats.load()?.mint.as_ref()], bump = token_stats.load()?.bump)]
+    pub token_stats: AccountLoader<'info, TokenStats>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + TokenConfig::LEN,
+        seeds = [TOKEN_CONFIG_SEED, token_stats.load()?.mint.as_ref()],
+        bump
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// ViewTokenInfo - Permissionless ecosystem directory entry for a token
+#[derive(Accounts)]
+pub struct ViewTokenInfo<'info> {
+    #[account(seeds = [TOKEN_STATS_SEED, token_stats.load()?.mint.as_ref()], bump = token_stats.load()?.bump)]
+    pub token_stats: AccountLoader<'info, TokenStats>,
+    #[account(seeds = [TOKEN_CONFIG_SEED, token_stats.load()?.mint.as_ref()], bump = token_config.bump)]
+    pub token_config: Option<Account<'info, TokenConfig>>,
+}
+
+/// GetTokenStats - Permissionless TokenStats view in a migration-stable layout
+#[derive(Accounts)]
+pub struct GetTokenStats<'info> {
+    #[account(seeds = [TOKEN_STATS_SEED, token_stats.load()?.mint.as_ref()], bump = token_stats.load()?.bump)]
+    pub token_stats: AccountLoader<'info, TokenStats>,
+}
+
+/// GetCyclePreview - Permissionless preview of a mint's next cycle step
+#[derive(Accounts)]
+pub struct GetCyclePreview<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(seeds = [TOKEN_STATS_SEED, token_stats.load()?.mint.as_ref()], bump = token_stats.load()?.bump)]
+    pub token_stats: AccountLoader<'info, TokenStats>,
+    #[account(seeds = [CYCLE_STATE_SEED, token_stats.load()?.mint.as_ref()], bump = cycle_state.bump)]
+    pub cycle_state: Account<'info, CycleState>,
+    /// CHECK: DAT authority PDA - funds buys
+    #[account(seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
+    pub dat_authority: AccountInfo<'info>,
+    /// CHECK: Creator vault - PDA ["creator-vault", dat_authority] under PUMP_PROGRAM
+    #[account(constraint = creator_vault.key() == pump_creator_vault(&dat_authority.key()) @ ErrorCode::InvalidCreatorVault)]
+    pub creator_vault: AccountInfo<'info>,
+}
+
+/// GetPendingRebate - Permissionless view of a user's claimable rebate
+#[derive(Accounts)]
+pub struct GetPendingRebate<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(seeds = [REBATE_POOL_SEED], bump = rebate_pool.bump)]
+    pub rebate_pool: Account<'info, RebatePool>,
+    #[account(
+        constraint = rebate_pool_ata.mint == dat_state.load()?.asdf_mint @ ErrorCode::MintMismatch,
+        constraint = rebate_pool_ata.owner == rebate_pool.key() @ ErrorCode::InvalidParameter
+    )]
+    pub rebate_pool_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(seeds = [USER_STATS_SEED, user_stats.user.as_ref()], bump = user_stats.bump)]
+    pub user_stats: Account<'info, UserStats>,
+}
+
+/// SetTokenCategory - Admin tags a token with an operational category
+#[derive(Accounts)]
+pub struct SetTokenCategory<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(
+        mut,
+        seeds = [TOKEN_STATS_SEED, token_stats.load()?.mint.as_ref()],
+        bump = token_stats.load()?.bump
+    )]
+    pub token_stats: AccountLoader<'info, TokenStats>,
+    pub admin: Signer<'info>,
+}
+
+/// CategoryControl - Admin pauses/resumes a category
+/// remaining_accounts: writable TokenStats accounts of every token in the category
+#[derive(Accounts)]
+pub struct CategoryControl<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RecordFailure<'info> {
+    #[account(mut, seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    /// Admin signer required to prevent DoS attacks
+    pub admin: Signer<'info>,
+    /// Optional cycle state of the failing mint (attributes CycleFailed to it)
+    #[account(seeds = [CYCLE_STATE_SEED, cycle_state.mint.as_ref()], bump = cycle_state.bump)]
+    pub cycle_state: Option<Account<'info, CycleState>>,
+    /// Optional stats of the failing mint (isolates the failure to that token)
+    #[account(
+        mut,
+        seeds = [TOKEN_STATS_SEED, token_stats.load()?.mint.as_ref()],
+        bump = token_stats.load()?.bump
+    )]
+    pub token_stats: Option<AccountLoader<'info, TokenStats>>,
+}
+
+/// ResumeToken - Admin lifts a single token's pause
+#[derive(Accounts)]
+pub struct ResumeToken<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(
+        mut,
+        seeds = [TOKEN_STATS_SEED, token_stats.load()?.mint.as_ref()],
+        bump = token_stats.load()?.bump
+    )]
+    pub token_stats: AccountLoader<'info, TokenStats>,
+    pub admin: Signer<'info>,
+}
+
+/// AcknowledgeUpgrade - Admin opens a new governance epoch after a program upgrade
+#[derive(Accounts)]
+pub struct AcknowledgeUpgrade<'info> {
+    #[account(mut, seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    /// This program's ProgramData (its deployment slot proves a new upgrade)
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = anchor_lang::solana_program::bpf_loader_upgradeable::ID
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    pub admin: Signer<'info>,
+}
+
+/// TopUpAuthority - Anyone donates the missing rent delta to dat_authority (permissionless)
+#[derive(Accounts)]
+pub struct TopUpAuthority<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    /// CHECK: PDA signer, receives the donation
+    #[account(mut, seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
+    pub dat_authority: AccountInfo<'info>,
+    #[account(mut)]
+    pub donor: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AdminControl<'info> {
+    #[account(mut, seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    pub admin: Signer<'info>,
+    /// Admin audit log - appended when passed (skipped until initialize_admin_log)
+    #[account(mut, seeds = [ADMIN_LOG_SEED], bump = admin_log.bump)]
+    pub admin_log: Option<Box<Account<'info, AdminLog>>>,
+}
+
+/// InitializeChangeQueue - Admin creates the parameter change queue and names the guardian
+#[derive(Accounts)]
+pub struct InitializeChangeQueue<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + ChangeQueue::LEN,
+        seeds = [CHANGE_QUEUE_SEED],
+        bump
+    )]
+    pub change_queue: Box<Account<'info, ChangeQueue>>,
+    /// CHECK: Guardian allowed to veto queued changes
+    pub guardian: AccountInfo<'info>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// ChangeQueueAdmin - Admin proposes, executes or cancels timelocked parameter changes
+#[derive(Accounts)]
+pub struct ChangeQueueAdmin<'info> {
+    #[account(mut, seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(mut, seeds = [CHANGE_QUEUE_SEED], bump = change_queue.bump)]
+    pub change_queue: Box<Account<'info, ChangeQueue>>,
+    pub admin: Signer<'info>,
+    /// Admin audit log - appended when passed (skipped until initialize_admin_log)
+    #[account(mut, seeds = [ADMIN_LOG_SEED], bump = admin_log.bump)]
+    pub admin_log: Option<Box<Account<'info, AdminLog>>>,
+    /// Token stats of the change's subject mint (REQUIRED when executing a per-token change)
+    #[account(mut, seeds = [TOKEN_STATS_SEED, token_stats.load()?.mint.as_ref()], bump = token_stats.load()?.bump)]
+    pub token_stats: Option<AccountLoader<'info, TokenStats>>,
+    /// Validator state of the change's subject mint (REQUIRED when executing an update_validator_limits change)
+    #[account(mut, seeds = [VALIDATOR_STATE_SEED, validator_state.mint.as_ref()], bump = validator_state.bump)]
+    pub validator_state: Option<Box<Account<'info, ValidatorState>>>,
+    /// Rebate pool (REQUIRED when executing a referral share or onboarding bonus change)
+    #[account(mut, seeds = [REBATE_POOL_SEED], bump = rebate_pool.bump)]
+    pub rebate_pool: Option<Box<Account<'info, RebatePool>>>,
+    /// Alert config (REQUIRED when executing a configure_alerts change)
+    #[account(mut, seeds = [ALERT_CONFIG_SEED], bump = alert_config.bump)]
+    pub alert_config: Option<Box<Account<'info, AlertConfig>>>,
+}
+
+/// GuardianControl - Guardian vetoes a queued change or hands over the role
+#[derive(Accounts)]
+pub struct GuardianControl<'info> {
+    #[account(
+        mut,
+        seeds = [CHANGE_QUEUE_SEED],
+        bump = change_queue.bump,
+        constraint = guardian.key() == change_queue.guardian @ ErrorCode::UnauthorizedAccess
+    )]
+    pub change_queue: Box<Account<'info, ChangeQueue>>,
+    pub guardian: Signer<'info>,
+}
+
+/// InitializeGovernance - Admin configures community fee split voting
+#[derive(Accounts)]
+pub struct InitializeGovernance<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Governance::LEN,
+        seeds = [GOVERNANCE_SEED],
+        bump
+    )]
+    pub governance: Account<'info, Governance>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// CreateProposal - Anyone proposes a fee split and funds its proposal and vote vault rent
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(mut, seeds = [GOVERNANCE_SEED], bump = governance.bump)]
+    pub governance: Account<'info, Governance>,
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + FeeSplitProposal::LEN,
+        seeds = [FEE_SPLIT_PROPOSAL_SEED, &governance.next_proposal_id.to_le_bytes()],
+        bump
+    )]
+    pub proposal: Account<'info, FeeSplitProposal>,
+    /// CHECK: DAT authority PDA - owner of the vote vault
+    #[account(seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
+    pub dat_authority: AccountInfo<'info>,
+    /// $ASDF escrow for this proposal's votes
+    #[account(
+        init,
+        payer = proposer,
+        seeds = [VOTE_VAULT_SEED, proposal.key().as_ref()],
+        bump,
+        token::mint = asdf_mint,
+        token::authority = dat_authority,
+        token::token_program = token_program
+    )]
+    pub vote_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = dat_state.load()?.asdf_mint @ ErrorCode::MintMismatch)]
+    pub asdf_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// CastVote - Voter escrows $ASDF for or against a proposal (one ballot per voter)
+#[derive(Accounts)]
+pub struct CastVote<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(mut, seeds = [FEE_SPLIT_PROPOSAL_SEED, &proposal.id.to_le_bytes()], bump = proposal.bump)]
+    pub proposal: Account<'info, FeeSplitProposal>,
+    #[account(
+        init,
+        payer = voter,
+        space = 8 + VoteRecord::LEN,
+        seeds = [VOTE_RECORD_SEED, proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+    #[account(mut, seeds = [VOTE_VAULT_SEED, proposal.key().as_ref()], bump)]
+    pub vote_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = dat_state.load()?.asdf_mint @ ErrorCode::MintMismatch)]
+    pub asdf_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        constraint = voter_token_account.mint == asdf_mint.key() @ ErrorCode::MintMismatch
+    )]
+    pub voter_token_account: InterfaceAccount<'info, TokenAccount>,
+    /// Voter's stake - adds its amount to the vote weight if locked through the vote
+    #[account(seeds = [STAKE_SEED, voter.key().as_ref()], bump = stake_account.bump)]
+    pub stake_account: Option<Account<'info, StakeAccount>>,
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// ReclaimVote - Voter recovers escrowed $ASDF once voting ended (closes the ballot)
+#[derive(Accounts)]
+pub struct ReclaimVote<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    /// CHECK: DAT authority PDA - owner of the vote vault
+    #[account(seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
+    pub dat_authority: AccountInfo<'info>,
+    #[account(seeds = [FEE_SPLIT_PROPOSAL_SEED, &proposal.id.to_le_bytes()], bump = proposal.bump)]
+    pub proposal: Account<'info, FeeSplitProposal>,
+    #[account(
+        mut,
+        close = voter,
+        seeds = [VOTE_RECORD_SEED, proposal.key().as_ref(), voter.key().as_ref()],
+        bump = vote_record.bump,
+        has_one = voter @ ErrorCode::UnauthorizedAccess
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+    #[account(mut, seeds = [VOTE_VAULT_SEED, proposal.key().as_ref()], bump)]
+    pub vote_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = dat_state.load()?.asdf_mint @ ErrorCode::MintMismatch)]
+    pub asdf_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        constraint = voter_token_account.mint == asdf_mint.key() @ ErrorCode::MintMismatch,
+        constraint = voter_token_account.owner == voter.key() @ ErrorCode::InvalidParameter
+    )]
+    pub voter_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Stake - User locks $ASDF for a rebate boost and vote weight (initializes the stake if needed)
+#[derive(Accounts)]
+pub struct Stake<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    /// CHECK: DAT authority PDA - owner of the stake vault
+    #[account(seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
+    pub dat_authority: AccountInfo<'info>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + StakeAccount::LEN,
+        seeds = [STAKE_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+    /// $ASDF escrow for this stake
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [STAKE_VAULT_SEED, stake_account.key().as_ref()],
+        bump,
+        token::mint = asdf_mint,
+        token::authority = dat_authority,
+        token::token_program = token_program
+    )]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = dat_state.load()?.asdf_mint @ ErrorCode::MintMismatch)]
+    pub asdf_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        constraint = owner_token_account.mint == asdf_mint.key() @ ErrorCode::MintMismatch
+    )]
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// ExtendStake - Owner re-locks their stake for a new duration
+#[derive(Accounts)]
+pub struct ExtendStake<'info> {
+    #[account(
+        mut,
+        seeds = [STAKE_SEED, owner.key().as_ref()],
+        bump = stake_account.bump,
+        has_one = owner @ ErrorCode::UnauthorizedAccess
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+    pub owner: Signer<'info>,
+}
+
+/// Unstake - Owner withdraws their whole stake once the lock expired
+#[derive(Accounts)]
+pub struct Unstake<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    /// CHECK: DAT authority PDA - owner of the stake vault
+    #[account(seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
+    pub dat_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [STAKE_SEED, owner.key().as_ref()],
+        bump = stake_account.bump,
+        has_one = owner @ ErrorCode::UnauthorizedAccess
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+    #[account(mut, seeds = [STAKE_VAULT_SEED, stake_account.key().as_ref()], bump)]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = dat_state.load()?.asdf_mint @ ErrorCode::MintMismatch)]
+    pub asdf_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        constraint = owner_token_account.mint == asdf_mint.key() @ ErrorCode::MintMismatch,
+        constraint = owner_token_account.owner == owner.key() @ ErrorCode::InvalidParameter
+    )]
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub owner: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// ExecuteProposal - Apply a passed fee split proposal after the timelock (permissionless)
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    #[account(mut, seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(seeds = [GOVERNANCE_SEED], bump = governance.bump)]
+    pub governance: Account<'info, Governance>,
+    #[account(mut, seeds = [FEE_SPLIT_PROPOSAL_SEED, &proposal.id.to_le_bytes()], bump = proposal.bump)]
+    pub proposal: Account<'info, FeeSplitProposal>,
+}
+
+/// TryResume - Permissionless resume after an automatic pause's cool-off
+/// Health probe: dat_state.pool_address must still hold MIN_POOL_LIQUIDITY of SOL
+#[derive(Accounts)]
+pub struct TryResume<'info> {
+    #[account(mut, seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    /// CHECK: Root pool (PumpFun bonding curve or PumpSwap pool) - owner and layout checked in try_resume
+    #[account(constraint = pool.key() == dat_state.load()?.pool_address @ ErrorCode::InvalidPool)]
+    pub pool: AccountInfo<'info>,
+    /// PumpSwap quote (WSOL) vault - REQUIRED when pool is a PumpSwap pool
+    pub pool_quote_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub caller: Signer<'info>,
+}
+
+/// ExportConfig - Emit the portable DATState configuration (permissionless)
+#[derive(Accounts)]
+pub struct ExportConfig<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+}
+
+#[derive(Accounts)]
+pub struct UpdatePendingFees<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(
+        mut,
+        seeds = [TOKEN_STATS_SEED, mint.key().as_ref()],
+        bump,
+        constraint = token_stats.load()?.mint == mint.key() @ ErrorCode::MintMismatch
+    )]
+    pub token_stats: AccountLoader<'info, TokenStats>,
+    /// CHECK: Token mint being tracked
+    pub mint: AccountInfo<'info>,
+    pub admin: Signer<'info>,
+}
+
+/// UpdatePendingFeesMonitor - Registered monitor key attributes fees under its daily quota
+#[derive(Accounts)]
+pub struct UpdatePendingFeesMonitor<'info> {
+    #[account(
+        mut,
+        seeds = [MONITOR_KEY_SEED, monitor.key().as_ref()],
+        bump = monitor_key.bump,
+        constraint = monitor_key.authority == monitor.key() @ ErrorCode::UnauthorizedAccess
+    )]
+    pub monitor_key: Account<'info, MonitorKey>,
+    #[account(
+        mut,
+        seeds = [TOKEN_STATS_SEED, mint.key().as_ref()],
+        bump,
+        constraint = token_stats.load()?.mint == mint.key() @ ErrorCode::MintMismatch
+    )]
+    pub token_stats: AccountLoader<'info, TokenStats>,
+    /// CHECK: Token mint being tracked
+    pub mint: AccountInfo<'info>,
+    pub monitor: Signer<'info>,
+}
+
+/// RegisterMonitorKey - Admin registers a monitor key with a daily quota
+#[derive(Accounts)]
+pub struct RegisterMonitorKey<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + MonitorKey::LEN,
+        seeds = [MONITOR_KEY_SEED, monitor.key().as_ref()],
+        bump
+    )]
+    pub monitor_key: Account<'info, MonitorKey>,
+    /// CHECK: Monitor signing key being registered
+    pub monitor: AccountInfo<'info>,
+    #[account(mut, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// UpdateMonitorKey - Admin changes quota and/or reinstates a suspended key
+#[derive(Accounts)]
+pub struct UpdateMonitorKey<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(
+        mut,
+        seeds = [MONITOR_KEY_SEED, monitor_key.authority.as_ref()],
+        bump = monitor_key.bump
+    )]
+    pub monitor_key: Account<'info, MonitorKey>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeValidator<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ValidatorState::LEN,
+        seeds = [VALIDATOR_STATE_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub validator_state: Account<'info, ValidatorState>,
+
+    /// CHECK: Bonding curve account - verified by owner constraint
+    #[account(constraint = bonding_curve.owner == &PUMP_PROGRAM @ ErrorCode::InvalidBondingCurve)]
+    pub bonding_curve: AccountInfo<'info>,
+
+    /// CHECK: Token mint
+    pub mint: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(fee_amount: u64, end_slot: u64)]
+pub struct RegisterValidatedFees<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+
+    /// Poster - the admin (M-of-N attestor signatures required) or a bonded validator
+    /// Pays rent for the attestation (refunded on finalize)
+    #[account(mut)]
+    pub poster: Signer<'info>,
+
+    /// Poster's bond - required unless the poster is the admin
+    #[account(
+        mut,
+        seeds = [VALIDATOR_BOND_SEED, poster.key().as_ref()],
+        bump = validator_bond.bump
+    )]
+    pub validator_bond: Option<Account<'info, ValidatorBond>>,
+
+    #[account(
+        mut,
+        seeds = [VALIDATOR_STATE_SEED, validator_state.mint.as_ref()],
+        bump = validator_state.bump,
+    )]
+    pub validator_state: Account<'info, ValidatorState>,
+
+    #[account(
+        seeds = [TOKEN_STATS_SEED, validator_state.mint.as_ref()],
+        bump = token_stats.load()?.bump,
+        constraint = token_stats.load()?.mint == validator_state.mint @ ErrorCode::MintMismatch
+    )]
+    pub token_stats: AccountLoader<'info, TokenStats>,
+
+    #[account(
+        init,
+        payer = poster,
+        space = 8 + FeeAttestation::LEN,
+        seeds = [FEE_ATTESTATION_SEED, validator_state.mint.as_ref(), &end_slot.to_le_bytes()],
+        bump
+    )]
+    pub fee_attestation: Account<'info, FeeAttestation>,
+
+    /// M-of-N attestors whose ed25519 signatures must precede this instruction
+    #[account(seeds = [VALIDATOR_SET_SEED], bump = validator_set.bump)]
+    pub validator_set: Account<'info, ValidatorSet>,
+
+    /// CHECK: Instructions sysvar (ed25519 signature introspection)
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// RegisterValidatedFeesBatch - Fee attestations for several tokens in one transaction
+/// remaining_accounts: [validator_state, token_stats, fee_attestation] per entry
+#[derive(Accounts)]
+pub struct RegisterValidatedFeesBatch<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+
+    /// Poster - the admin (M-of-N attestor signatures required) or a bonded validator
+    /// Pays rent for every attestation (refunded on finalize)
+    #[account(mut)]
+    pub poster: Signer<'info>,
+
+    /// Poster's bond - required unless the poster is the admin
+    #[account(
+        mut,
+        seeds = [VALIDATOR_BOND_SEED, poster.key().as_ref()],
+        bump = validator_bond.bump
+    )]
+    pub validator_bond: Option<Account<'info, ValidatorBond>>,
+
+    /// M-of-N attestors whose ed25519 signatures must precede this instruction
+    #[account(seeds = [VALIDATOR_SET_SEED], bump = validator_set.bump)]
+    pub validator_set: Account<'info, ValidatorSet>,
+
+    /// CHECK: Instructions sysvar (ed25519 signature introspection)
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// ConfigureValidatorSet - Admin sets fee attestors and threshold
+#[derive(Accounts)]
+pub struct ConfigureValidatorSet<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + ValidatorSet::LEN,
+        seeds = [VALIDATOR_SET_SEED],
+        bump
+    )]
+    pub validator_set: Account<'info, ValidatorSet>,
+    #[account(mut, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// ConfigureFeeRecipients - Admin sets the PumpFun protocol fee recipient rotation
+#[derive(Accounts)]
+pub struct ConfigureFeeRecipients<'info> {
+    #[account(mut, seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + FeeRecipients::LEN,
+        seeds = [FEE_RECIPIENTS_SEED],
+        bump
+    )]
+    pub fee_recipients: Account<'info, FeeRecipients>,
+    #[account(mut, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// ChallengeAttestation - Permissionless fraud proof against a pending attestation
+/// Rent of the discarded attestation goes to the challenger as a bounty
+#[derive(Accounts)]
+pub struct ChallengeAttestation<'info> {
+    #[account(
+        mut,
+        seeds = [FEE_ATTESTATION_SEED, fee_attestation.mint.as_ref(), &fee_attestation.end_slot.to_le_bytes()],
+        bump = fee_attestation.bump,
+        close = challenger
+    )]
+    pub fee_attestation: Account<'info, FeeAttestation>,
+    #[account(mut)]
+    pub challenger: Signer<'info>,
+}
+
+/// BondValidator - Operator deposits SOL into its validator bond (creates it on first deposit)
+#[derive(Accounts)]
+pub struct BondValidator<'info> {
+    #[account(
+        init_if_needed,
+        payer = operator,
+        space = 8 + ValidatorBond::LEN,
+        seeds = [VALIDATOR_BOND_SEED, operator.key().as_ref()],
+        bump
+    )]
+    pub validator_bond: Account<'info, ValidatorBond>,
+    #[account(mut)]
+    pub operator: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// WithdrawBond - Operator withdraws stake once no attestation is outstanding
+#[derive(Accounts)]
+pub struct WithdrawBond<'info> {
+    #[account(
+        mut,
+        seeds = [VALIDATOR_BOND_SEED, operator.key().as_ref()],
+        bump = validator_bond.bump,
+        has_one = operator @ ErrorCode::UnauthorizedAccess
+    )]
+    pub validator_bond: Account<'info, ValidatorBond>,
+    #[account(mut)]
+    pub operator: Signer<'info>,
+}
+
+/// SlashValidator - Anyone disputes a bonded attestation; proven fraud burns part of the bond
+#[derive(Accounts)]
+pub struct SlashValidator<'info> {
+    #[account(
+        mut,
+        seeds = [FEE_ATTESTATION_SEED, fee_attestation.mint.as_ref(), &fee_attestation.end_slot.to_le_bytes()],
+        bump = fee_attestation.bump,
+        close = challenger
+    )]
+    pub fee_attestation: Account<'info, FeeAttestation>,
+    #[account(
+        mut,
+        constraint = validator_bond.key() == fee_attestation.bond @ ErrorCode::InvalidBondAccount
+    )]
+    pub validator_bond: Account<'info, ValidatorBond>,
+    /// CHECK: Incinerator - slashed lamports are destroyed
+    #[account(mut, address = INCINERATOR)]
+    pub incinerator: AccountInfo<'info>,
+    #[account(mut)]
+    pub challenger: Signer<'info>,
+}
+
+/// FinalizeAttestation - Permissionless: credit an unchallenged attestation after the window
+#[derive(Accounts)]
+pub struct FinalizeAttestation<'info> {
+    #[account(
+        mut,
+        seeds = [FEE_ATTESTATION_SEED, fee_attestation.mint.as_ref(), &fee_attestation.end_slot.to_le_bytes()],
+        bump = fee_attestation.bump,
+        close = poster
+    )]
+    pub fee_attestation: Account<'info, FeeAttestation>,
+    #[account(
+        mut,
+        seeds = [VALIDATOR_STATE_SEED, fee_attestation.mint.as_ref()],
+        bump = validator_state.bump,
+    )]
+    pub validator_state: Account<'info, ValidatorState>,
+    #[account(
+        mut,
+        seeds = [TOKEN_STATS_SEED, fee_attestation.mint.as_ref()],
+        bump = token_stats.load()?.bump,
+        constraint = token_stats.load()?.mint == fee_attestation.mint @ ErrorCode::MintMismatch
+    )]
+    pub token_stats: AccountLoader<'info, TokenStats>,
+    /// CHECK: Original poster - receives the attestation rent
+    #[account(mut, constraint = poster.key() == fee_attestation.poster @ ErrorCode::UnauthorizedAccess)]
+    pub poster: AccountInfo<'info>,
+    /// Bond backing the attestation (required when fee_attestation.bond is set)
+    #[account(
+        mut,
+        constraint = validator_bond.key() == fee_attestation.bond @ ErrorCode::InvalidBondAccount
+    )]
+    pub validator_bond: Option<Account<'info, ValidatorBond>>,
+}
+
+/// Accounts for sync_validator_slot instruction
+/// HIGH-02 FIX: Now requires admin authorization to prevent DoS attacks
+#[derive(Accounts)]
+pub struct SyncValidatorSlot<'info> {
+    // HIGH-02 FIX: Added DATState and admin signer for authorization
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+
+    #[account(
+        mut,
+        seeds = [VALIDATOR_STATE_SEED, validator_state.mint.as_ref()],
+        bump = validator_state.bump,
+    )]
+    pub validator_state: Account<'info, ValidatorState>,
+
+    /// Admin authority - HIGH-02 FIX: Required to prevent DoS
+    #[account(
+        constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess
+    )]
+    pub admin: Signer<'info>,
+}
+
+/// CloseValidatorState - Admin reclaims a validator's rent (token must have no pending fees)
+/// Close before TokenStats: the pending-fee check reads the token's stats
+#[derive(Accounts)]
+pub struct CloseValidatorState<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+
+    #[account(
+        mut,
+        seeds = [VALIDATOR_STATE_SEED, validator_state.mint.as_ref()],
+        bump = validator_state.bump,
+        close = rent_destination
+    )]
+    pub validator_state: Account<'info, ValidatorState>,
+
+    #[account(
+        seeds = [TOKEN_STATS_SEED, validator_state.mint.as_ref()],
+        bump = token_stats.load()?.bump,
+        constraint = token_stats.load()?.mint == validator_state.mint @ ErrorCode::MintMismatch
+    )]
+    pub token_stats: AccountLoader<'info, TokenStats>,
+
+    /// CHECK: Rent recovery destination chosen by the admin
+    #[account(mut)]
+    pub rent_destination: AccountInfo<'info>,
+
+    pub admin: Signer<'info>,
+}
+
+/// CloseTokenStats - Admin reclaims a dead token's stats rent
+/// Requires no pending fees and an empty active DAT token account (nothing awaiting burn)
+#[derive(Accounts)]
+pub struct CloseTokenStats<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+
+    #[account(
+        mut,
+        seeds = [TOKEN_STATS_SEED, token_stats.load()?.mint.as_ref()],
+        bump = token_stats.load()?.bump,
+        close = rent_destination
+    )]
+    pub token_stats: AccountLoader<'info, TokenStats>,
+
+    /// Token registry header - REQUIRED when token_stats.in_registry
+    #[account(mut, seeds = [TOKEN_REGISTRY_SEED], bump = token_registry.bump)]
+    pub token_registry: Option<Account<'info, TokenRegistry>>,
+    /// Page listing the mint (token_stats.registry_page) - REQUIRED when token_stats.in_registry
+    #[account(
+        mut,
+        seeds = [TOKEN_REGISTRY_PAGE_SEED, &token_stats.load()?.registry_page.to_le_bytes()],
+        bump = token_registry_page.bump
+    )]
+    pub token_registry_page: Option<Box<Account<'info, TokenRegistryPage>>>,
+
+    /// CHECK: DAT authority PDA (owner of the DAT token account)
+    #[account(seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
+    pub dat_authority: AccountInfo<'info>,
+
+    /// Active DAT token account for the mint - must be empty
+    #[account(
+        constraint = dat_token_account.key()
+            == token_stats.load()?.active_token_account(&dat_authority.key(), &token_program.key())
+            @ ErrorCode::InvalidTokenAccount
+    )]
+    pub dat_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Rent recovery destination chosen by the admin
+    #[account(mut)]
+    pub rent_destination: AccountInfo<'info>,
+
+    pub admin: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// UpdateValidatorLimits - Admin queues a validator's per-slot fee/TX caps
+#[derive(Accounts)]
+pub struct UpdateValidatorLimits<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+
+    #[account(
+        seeds = [VALIDATOR_STATE_SEED, validator_state.mint.as_ref()],
+        bump = validator_state.bump,
+    )]
+    pub validator_state: Account<'info, ValidatorState>,
+
+    #[account(mut, seeds = [CHANGE_QUEUE_SEED], bump = change_queue.bump)]
+    pub change_queue: Box<Account<'info, ChangeQueue>>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ResetValidatorSlot<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+
+    #[account(
+        mut,
+        seeds = [VALIDATOR_STATE_SEED, validator_state.mint.as_ref()],
+        bump = validator_state.bump,
+    )]
+    pub validator_state: Account<'info, ValidatorState>,
+
+    #[account(constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateTokenStats<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(mut, constraint = token_stats.owner == &crate::ID @ ErrorCode::InvalidAccountOwner)]
+    /// CHECK: Manual PDA verification and deserialization for migration
+    pub token_stats: AccountInfo<'info>,
+    /// CHECK: Mint address for PDA derivation
+    pub mint: AccountInfo<'info>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// MigrateDatState - Migrate DAT state to add new fields (one-time migration)
+#[derive(Accounts)]
+pub struct MigrateDatState<'info> {
+    #[account(mut, seeds = [DAT_STATE_SEED], bump)]
+    /// CHECK: Manual verification - using AccountInfo for raw data access during migration
+    pub dat_state: AccountInfo<'info>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// MigrateAccount - Upgrade any registered account kind to its current layout (see migrations::MIGRATIONS)
+/// Admin only (payer = admin) except UserStats, which anyone may migrate
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    /// CHECK: Manual verification - DAT state may itself be in an old layout (admin read from raw data)
+    pub dat_state: AccountInfo<'info>,
+    /// CHECK: Owner, discriminator and layout checked in migrations::migrate
+    #[account(mut)]
+    pub account: AccountInfo<'info>,
+    /// CHECK: Mint tracked by a TokenStats account - REQUIRED when its layout predates risk_flags
+    pub mint: Option<AccountInfo<'info>>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// ProposeAdminTransfer - Current admin proposes a new admin (two-step transfer)
+#[derive(Accounts)]
+pub struct ProposeAdminTransfer<'info> {
+    #[account(mut, seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    pub admin: Signer<'info>,
+    /// CHECK: Proposed new admin (will need to accept)
+    pub new_admin: AccountInfo<'info>,
+}
+
+/// FundBountyVault - Admin moves SOL from the root treasury into the bounty vault
+/// root_treasury validated at runtime via PDA derivation: ["root_treasury", root_token_mint]
+#[derive(Accounts)]
+pub struct FundBountyVault<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + BountyVault::LEN,
+        seeds = [BOUNTY_VAULT_SEED],
+        bump
+    )]
+    pub bounty_vault: Account<'info, BountyVault>,
+    /// CHECK: Root treasury PDA - validated in fund_bounty_vault
+    #[account(mut)]
+    pub root_treasury: AccountInfo<'info>,
+    #[account(mut, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// BountyControl - Admin proposes or cancels a bounty payout
+#[derive(Accounts)]
+pub struct BountyControl<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(mut, seeds = [BOUNTY_VAULT_SEED], bump = bounty_vault.bump)]
+    pub bounty_vault: Account<'info, BountyVault>,
+    pub admin: Signer<'info>,
+}
+
+/// PayBounty - Admin executes a proposed payout after the timelock
+#[derive(Accounts)]
+pub struct PayBounty<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(mut, seeds = [BOUNTY_VAULT_SEED], bump = bounty_vault.bump)]
+    pub bounty_vault: Account<'info, BountyVault>,
+    /// CHECK: Bounty recipient - must equal the pending proposal (checked in pay_bounty)
+    #[account(mut)]
+    pub recipient: AccountInfo<'info>,
+    pub admin: Signer<'info>,
+}
+
+/// SetEmergencyGuardian - Admin sets the key allowed to veto emergency withdrawals
+/// Creates the emergency withdrawal account on first use
+#[derive(Accounts)]
+pub struct SetEmergencyGuardian<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + EmergencyWithdrawal::LEN,
+        seeds = [EMERGENCY_WITHDRAW_SEED],
+        bump
+    )]
+    pub emergency_withdrawal: Account<'info, EmergencyWithdrawal>,
+    #[account(mut, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// InitializeReserveVault - Admin creates the treasury SOL reserve
+#[derive(Accounts)]
+pub struct InitializeReserveVault<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + ReserveVault::LEN,
+        seeds = [RESERVE_VAULT_SEED],
+        bump
+    )]
+    pub reserve_vault: Account<'info, ReserveVault>,
+    #[account(mut, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// ReserveControl - Admin proposes or cancels a reserve deployment
+#[derive(Accounts)]
+pub struct ReserveControl<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(mut, seeds = [RESERVE_VAULT_SEED], bump = reserve_vault.bump)]
+    pub reserve_vault: Account<'info, ReserveVault>,
+    pub admin: Signer<'info>,
+}
+
+/// DeployReserve - Admin executes a proposed reserve deployment after the timelock
+#[derive(Accounts)]
+pub struct DeployReserve<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(mut, seeds = [RESERVE_VAULT_SEED], bump = reserve_vault.bump)]
+    pub reserve_vault: Account<'info, ReserveVault>,
+    /// CHECK: Deployment recipient - must equal the pending proposal
+    #[account(mut, constraint = recipient.key() == reserve_vault.pending_recipient @ ErrorCode::ReserveDeploymentMismatch)]
+    pub recipient: AccountInfo<'info>,
+    pub admin: Signer<'info>,
+}
+
+/// InitializeInsuranceFund - Admin creates the insurance fund and names its veto guardian
+#[derive(Accounts)]
+pub struct InitializeInsuranceFund<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + InsuranceFund::LEN,
+        seeds = [INSURANCE_FUND_SEED],
+        bump
+    )]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+    /// CHECK: Veto guardian - any key other than the admin
+    pub guardian: AccountInfo<'info>,
+    #[account(mut, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// ProposeInsurancePayout - Admin proposes a timelocked reimbursement from the insurance fund
+#[derive(Accounts)]
+pub struct ProposeInsurancePayout<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(mut, seeds = [INSURANCE_FUND_SEED], bump = insurance_fund.bump)]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+    pub admin: Signer<'info>,
+}
+
+/// VetoInsurancePayout - Guardian (or admin) cancels a pending insurance payout
+#[derive(Accounts)]
+pub struct VetoInsurancePayout<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(
+        mut,
+        seeds = [INSURANCE_FUND_SEED],
+        bump = insurance_fund.bump,
+        constraint = authority.key() == insurance_fund.guardian
+            || authority.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess
+    )]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+    pub authority: Signer<'info>,
+}
+
+/// ExecuteInsurancePayout - Admin pays a proposed reimbursement after the timelock
+#[derive(Accounts)]
+pub struct ExecuteInsurancePayout<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(mut, seeds = [INSURANCE_FUND_SEED], bump = insurance_fund.bump)]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+    /// CHECK: Payout recipient - must equal the pending proposal
+    #[account(mut, constraint = recipient.key() == insurance_fund.pending_recipient @ ErrorCode::InsurancePayoutMismatch)]
+    pub recipient: AccountInfo<'info>,
+    pub admin: Signer<'info>,
+}
+
+/// ProposeEmergencyWithdraw - Admin proposes a timelocked withdrawal from dat_authority
+#[derive(Accounts)]
+pub struct ProposeEmergencyWithdraw<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(mut, seeds = [EMERGENCY_WITHDRAW_SEED], bump = emergency_withdrawal.bump)]
+    pub emergency_withdrawal: Account<'info, EmergencyWithdrawal>,
+    /// CHECK: PDA holding the SOL to recover (balance checked in propose_emergency_withdraw)
+    #[account(seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
+    pub dat_authority: AccountInfo<'info>,
+    pub admin: Signer<'info>,
+}
+
+/// VetoEmergencyWithdraw - Guardian (or admin) cancels a pending emergency withdrawal
+#[derive(Accounts)]
+pub struct VetoEmergencyWithdraw<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(
+        mut,
+        seeds = [EMERGENCY_WITHDRAW_SEED],
+        bump = emergency_withdrawal.bump,
+        constraint = authority.key() == emergency_withdrawal.guardian
+            || authority.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess
+    )]
+    pub emergency_withdrawal: Account<'info, EmergencyWithdrawal>,
+    pub authority: Signer<'info>,
+}
+
+/// ExecuteEmergencyWithdraw - Admin executes a proposed withdrawal after the timelock
+#[derive(Accounts)]
+pub struct ExecuteEmergencyWithdraw<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(mut, seeds = [EMERGENCY_WITHDRAW_SEED], bump = emergency_withdrawal.bump)]
+    pub emergency_withdrawal: Account<'info, EmergencyWithdrawal>,
+    /// CHECK: PDA signer, source of the withdrawal
+    #[account(mut, seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
+    pub dat_authority: AccountInfo<'info>,
+    /// CHECK: Destination - must equal the pending proposal
+    #[account(mut, constraint = destination.key() == emergency_withdrawal.pending_destination @ ErrorCode::InvalidParameter)]
+    pub destination: AccountInfo<'info>,
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// CancelAdminTransfer - Current admin cancels a pending transfer
+#[derive(Accounts)]
+pub struct CancelAdminTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [DAT_STATE_SEED],
+        bump,
+        constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess,
+        constraint = dat_state.load()?.pending_admin().is_some() @ ErrorCode::InvalidParameter
+    )]
+    pub dat_state: AccountLoader<'info, DATState>,
+    pub admin: Signer<'info>,
+}
+
+/// AcceptAdminTransfer - Proposed admin accepts the transfer (two-step transfer)
+#[derive(Accounts)]
+pub struct AcceptAdminTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [DAT_STATE_SEED],
+        bump,
+        constraint = dat_state.load()?.pending_admin() == Some(new_admin.key()) @ ErrorCode::UnauthorizedAccess
+    )]
+    pub dat_state: AccountLoader<'info, DATState>,
+    /// The proposed admin who is accepting the transfer
+    pub new_admin: Signer<'info>,
+}
+
+/// ProposeDevWallet - Admin proposes a new dev wallet (two-step rotation)
+#[derive(Accounts)]
+pub struct ProposeDevWallet<'info> {
+    #[account(mut, seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    pub admin: Signer<'info>,
+    /// CHECK: Proposed dev wallet (will need to accept)
+    pub new_dev_wallet: AccountInfo<'info>,
+}
+
+/// AcceptDevWallet - Proposed dev wallet accepts the rotation
+#[derive(Accounts)]
+pub struct AcceptDevWallet<'info> {
+    #[account(
+        mut,
+        seeds = [DAT_STATE_SEED],
+        bump,
+        constraint = dat_state.load()?.pending_dev_wallet != Pubkey::default()
+            && dat_state.load()?.pending_dev_wallet == new_dev_wallet.key() @ ErrorCode::UnauthorizedAccess
+    )]
+    pub dat_state: AccountLoader<'info, DATState>,
+    /// The proposed dev wallet accepting the rotation
+    pub new_dev_wallet: Signer<'info>,
+}
+
+/// DEPRECATED: Use ProposeAdminTransfer + AcceptAdminTransfer instead
+/// Kept for backwards compatibility but now just calls propose_admin_transfer
+#[derive(Accounts)]
+pub struct TransferAdmin<'info> {
+    #[account(mut, seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    pub admin: Signer<'info>,
+    /// CHECK: New admin
+    pub new_admin: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CreatePumpfunTokenMayhem<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+
+    /// CHECK: PDA - DAT Authority acts as token creator
+    #[account(mut, seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
+    pub dat_authority: AccountInfo<'info>,
+
+    #[account(mut, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub mint: Signer<'info>,
+
+    /// CHECK: PDA from pump program (mint-authority seed)
+    pub mint_authority: AccountInfo<'info>,
+
+    /// CHECK: Bonding curve PDA (82 bytes for Mayhem Mode - 81 + 1 for is_mayhem_mode flag)
+    #[account(mut)]
+    pub bonding_curve: AccountInfo<'info>,
+
+    /// CHECK: Associated bonding curve token account (Token2022 ATA)
+    #[account(mut)]
+    pub associated_bonding_curve: AccountInfo<'info>,
 
-    // PumpSwap AMM Core accounts (1-9)
-    /// CHECK: AMM Pool account - owned by PumpSwap program
-    #[account(mut, constraint = pool.owner == &PUMP_SWAP_PROGRAM @ ErrorCode::InvalidBondingCurve)]
-    pub pool: AccountInfo<'info>,
-    /// CHECK: PumpSwap global config
-    pub global_config: AccountInfo<'info>,
-    /// Base token mint (the token being bought)
-    pub base_mint: InterfaceAccount<'info, Mint>,
-    /// CHECK: Quote token mint (WSOL)
-    pub quote_mint: AccountInfo<'info>,
-    /// CHECK: DAT's WSOL account (user_quote_token_account)
+    /// CHECK: Global config PDA from pump program
+    pub global: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Token2022 program (not legacy Token program!)
+    pub token_2022_program: AccountInfo<'info>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// CHECK: Mayhem program - handles AI agent trading
     #[account(mut)]
-    pub dat_wsol_account: AccountInfo<'info>,
-    /// CHECK: Pool's base token account
+    pub mayhem_program: AccountInfo<'info>,
+
+    /// CHECK: Global params PDA from mayhem program
+    pub global_params: AccountInfo<'info>,
+
+    /// CHECK: SOL vault PDA from mayhem program
     #[account(mut)]
-    pub pool_base_token_account: AccountInfo<'info>,
-    /// CHECK: Pool's quote token account (WSOL)
+    pub sol_vault: AccountInfo<'info>,
+
+    /// CHECK: Mayhem state PDA (derived from mint)
     #[account(mut)]
-    pub pool_quote_token_account: AccountInfo<'info>,
+    pub mayhem_state: AccountInfo<'info>,
 
-    // Protocol fee accounts (10-11)
-    /// CHECK: Protocol fee recipient
-    pub protocol_fee_recipient: AccountInfo<'info>,
-    /// CHECK: Protocol fee recipient's token account (PDA)
+    /// CHECK: Mayhem token vault (Token2022 ATA)
     #[account(mut)]
-    pub protocol_fee_recipient_ata: AccountInfo<'info>,
+    pub mayhem_token_vault: AccountInfo<'info>,
+
+    /// CHECK: Event authority PDA
+    pub event_authority: AccountInfo<'info>,
+
+    /// CHECK: Main pump program (6EF8r...)
+    pub pump_program: AccountInfo<'info>,
+
+    // Initial dev-buy accounts - REQUIRED when initial_buy_lamports is set
+    /// CHECK: PumpFun protocol fee recipient - checked in initial_dev_buy
+    #[account(mut)]
+    pub protocol_fee_recipient: Option<AccountInfo<'info>>,
+
+    /// CHECK: dat_authority's Token2022 ATA for mint - created and checked in initial_dev_buy
+    #[account(mut)]
+    pub dat_token_account: Option<AccountInfo<'info>>,
+
+    /// CHECK: PumpFun creator vault of dat_authority - checked in initial_dev_buy
+    #[account(mut)]
+    pub creator_vault: Option<AccountInfo<'info>>,
+
+    /// CHECK: Global volume accumulator - validated address
+    #[account(address = PUMP_GLOBAL_VOLUME_ACCUMULATOR @ ErrorCode::InvalidParameter)]
+    pub global_volume_accumulator: Option<AccountInfo<'info>>,
+
+    /// CHECK: User volume accumulator (PDA) - seeds: ["user_volume_accumulator", dat_authority]
+    #[account(mut)]
+    pub user_volume_accumulator: Option<AccountInfo<'info>>,
+
+    /// CHECK: Fee config PDA - validated by the fee program
+    pub fee_config: Option<AccountInfo<'info>>,
+
+    /// CHECK: Fee program - validated address
+    #[account(address = PUMP_FEE_PROGRAM @ ErrorCode::InvalidParameter)]
+    pub fee_program: Option<AccountInfo<'info>>,
+}
+
+/// CreatePumpfunTokenV2 - Create token using create_v2 (Token2022) without Mayhem Mode
+/// Standard Token2022 token with 1B supply
+/// NOTE: Even without Mayhem Mode, PumpFun's create_v2 requires all Mayhem accounts
+#[derive(Accounts)]
+pub struct CreatePumpfunTokenV2<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+
+    /// CHECK: PDA - DAT Authority acts as token creator
+    #[account(mut, seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
+    pub dat_authority: AccountInfo<'info>,
+
+    #[account(mut, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub mint: Signer<'info>,
+
+    /// CHECK: PDA from pump program (mint-authority seed)
+    pub mint_authority: AccountInfo<'info>,
+
+    /// CHECK: Bonding curve PDA
+    #[account(mut)]
+    pub bonding_curve: AccountInfo<'info>,
+
+    /// CHECK: Associated bonding curve token account (Token2022 ATA)
+    #[account(mut)]
+    pub associated_bonding_curve: AccountInfo<'info>,
+
+    /// CHECK: Global config PDA from pump program
+    pub global: AccountInfo<'info>,
 
-    // Program accounts (12-17)
-    /// Base token program (SPL Token or Token2022)
-    pub base_token_program: Interface<'info, TokenInterface>,
-    /// CHECK: Quote token program (always SPL Token for WSOL) - validated via constraint
-    #[account(constraint = quote_token_program.key() == anchor_spl::token::ID @ ErrorCode::InvalidParameter)]
-    pub quote_token_program: AccountInfo<'info>,
     pub system_program: Program<'info, System>,
-    /// CHECK: Associated token program
-    pub associated_token_program: AccountInfo<'info>,
-    /// CHECK: PumpSwap event authority (PDA) - derived from program
+
+    /// CHECK: Token2022 program (not legacy Token program!)
+    pub token_2022_program: AccountInfo<'info>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    // Mayhem accounts - required by create_v2 even when is_mayhem_mode = false
+    /// CHECK: Mayhem program (MAyhSmz...) - must be passed even without Mayhem Mode
+    #[account(mut)]
+    pub mayhem_program: AccountInfo<'info>,
+
+    /// CHECK: Global params PDA from mayhem program
+    pub global_params: AccountInfo<'info>,
+
+    /// CHECK: SOL vault PDA from mayhem program
+    #[account(mut)]
+    pub sol_vault: AccountInfo<'info>,
+
+    /// CHECK: Mayhem state PDA (derived from mint)
+    #[account(mut)]
+    pub mayhem_state: AccountInfo<'info>,
+
+    /// CHECK: Mayhem token vault (Token2022 ATA)
+    #[account(mut)]
+    pub mayhem_token_vault: AccountInfo<'info>,
+
+    /// CHECK: Event authority PDA
     pub event_authority: AccountInfo<'info>,
-    /// CHECK: PumpSwap AMM program - validated via constraint
-    #[account(constraint = pump_swap_program.key() == PUMP_SWAP_PROGRAM @ ErrorCode::InvalidParameter)]
-    pub pump_swap_program: AccountInfo<'info>,
 
-    // Creator fee accounts (18-19)
-    /// CHECK: Coin creator vault ATA (receives creator fees)
-    #[account(mut)]
-    pub coin_creator_vault_ata: AccountInfo<'info>,
-    /// CHECK: Coin creator vault authority (PDA)
-    pub coin_creator_vault_authority: AccountInfo<'info>,
+    /// CHECK: Main pump program (6EF8r...)
+    pub pump_program: AccountInfo<'info>,
+
+    // Initial dev-buy accounts - REQUIRED when initial_buy_lamports is set
+    /// CHECK: PumpFun protocol fee recipient - checked in initial_dev_buy
+    #[account(mut)]
+    pub protocol_fee_recipient: Option<AccountInfo<'info>>,
+
+    /// CHECK: dat_authority's Token2022 ATA for mint - created and checked in initial_dev_buy
+    #[account(mut)]
+    pub dat_token_account: Option<AccountInfo<'info>>,
+
+    /// CHECK: PumpFun creator vault of dat_authority - checked in initial_dev_buy
+    #[account(mut)]
+    pub creator_vault: Option<AccountInfo<'info>>,
+
+    /// CHECK: Global volume accumulator - validated address
+    #[account(address = PUMP_GLOBAL_VOLUME_ACCUMULATOR @ ErrorCode::InvalidParameter)]
+    pub global_volume_accumulator: Option<AccountInfo<'info>>,
+
+    /// CHECK: User volume accumulator (PDA) - seeds: ["user_volume_accumulator", dat_authority]
+    #[account(mut)]
+    pub user_volume_accumulator: Option<AccountInfo<'info>>,
+
+    /// CHECK: Fee config PDA - validated by the fee program
+    pub fee_config: Option<AccountInfo<'info>>,
+
+    /// CHECK: Fee program - validated address
+    #[account(address = PUMP_FEE_PROGRAM @ ErrorCode::InvalidParameter)]
+    pub fee_program: Option<AccountInfo<'info>>,
+}
+
+/// UpdateTokenMetadata - Admin rewrites name / symbol / uri of Metaplex metadata
+/// whose update authority is dat_authority
+#[derive(Accounts)]
+pub struct UpdateTokenMetadata<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+
+    /// CHECK: DAT authority PDA - metadata update authority, signs the CPI
+    #[account(seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
+    pub dat_authority: AccountInfo<'info>,
+
+    /// CHECK: Token mint (metadata PDA seed)
+    pub mint: AccountInfo<'info>,
+
+    /// CHECK: Metaplex metadata PDA ["metadata", MPL_TOKEN_METADATA_PROGRAM, mint] - parsed in update_token_metadata
+    #[account(
+        mut,
+        seeds = [MPL_METADATA_SEED, MPL_TOKEN_METADATA_PROGRAM.as_ref(), mint.key().as_ref()],
+        bump,
+        seeds::program = MPL_TOKEN_METADATA_PROGRAM,
+        constraint = metadata.owner == &MPL_TOKEN_METADATA_PROGRAM @ ErrorCode::InvalidMetadata
+    )]
+    pub metadata: AccountInfo<'info>,
+
+    /// CHECK: Metaplex Token Metadata program - validated address
+    #[account(address = MPL_TOKEN_METADATA_PROGRAM @ ErrorCode::InvalidParameter)]
+    pub token_metadata_program: AccountInfo<'info>,
+
+    pub admin: Signer<'info>,
+}
+
+/// TransferDevFee - Transfer 1% dev sustainability fee at end of batch
+/// Called after burn to ensure cycle completed successfully before taking fee
+#[derive(Accounts)]
+pub struct TransferDevFee<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+
+    /// Cycle state of the mint whose cycle pays the fee (records the paid sequence)
+    #[account(
+        mut,
+        seeds = [CYCLE_STATE_SEED, cycle_state.mint.as_ref()],
+        bump = cycle_state.bump
+    )]
+    pub cycle_state: Box<Account<'info, CycleState>>,
+
+    /// CHECK: DAT authority PDA - source of SOL for dev fee
+    #[account(mut, seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
+    pub dat_authority: AccountInfo<'info>,
+
+    /// CHECK: Dev wallet - validated against DATState.dev_wallet
+    /// 1% today = 99% burns forever
+    #[account(
+        mut,
+        address = dat_state.load()?.dev_wallet @ ErrorCode::InvalidDevWallet
+    )]
+    pub dev_wallet: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// EXTERNAL APP INTEGRATION CONTEXTS
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// InitializeRebatePool - Initialize the self-sustaining rebate pool
+/// Called once during protocol setup
+#[derive(Accounts)]
+pub struct InitializeRebatePool<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + RebatePool::LEN,
+        seeds = [REBATE_POOL_SEED],
+        bump
+    )]
+    pub rebate_pool: Account<'info, RebatePool>,
+
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+
+    /// Admin must authorize initialization
+    #[account(
+        mut,
+        constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess
+    )]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// DepositFeeAsdf - External app deposits $ASDF fees with automatic split
+/// Split: 99.448% → DAT ATA (burn), 0.552% → Rebate Pool ATA (rebates)
+/// With target_mint, the deposit is in that ecosystem token and feeds its own burn
+#[derive(Accounts)]
+#[instruction(amount: u64, target_mint: Option<Pubkey>)]
+pub struct DepositFeeAsdf<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+
+    /// CHECK: DAT authority PDA
+    #[account(seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
+    pub dat_authority: AccountInfo<'info>,
+
+    /// Rebate pool state (for tracking deposits)
+    #[account(
+        mut,
+        seeds = [REBATE_POOL_SEED],
+        bump = rebate_pool.bump
+    )]
+    pub rebate_pool: Account<'info, RebatePool>,
+
+    /// User stats - initialized if needed
+    /// Protocol pays rent via dat_authority
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + UserStats::LEN,
+        seeds = [USER_STATS_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    /// The user whose contribution is being tracked
+    /// CHECK: Any valid pubkey (user being credited)
+    pub user: AccountInfo<'info>,
+
+    /// Payer's token account (source of deposit) - $ASDF, or target_mint when set
+    #[account(
+        mut,
+        constraint = payer_token_account.mint == target_mint.unwrap_or(dat_state.load()?.asdf_mint) @ ErrorCode::MintMismatch
+    )]
+    pub payer_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// DAT's token account for the deposit mint (receives 99.448% for burn)
+    /// For a target_mint this must be the target's active DAT token account
+    #[account(
+        mut,
+        constraint = dat_asdf_account.mint == target_mint.unwrap_or(dat_state.load()?.asdf_mint) @ ErrorCode::MintMismatch,
+        constraint = dat_asdf_account.owner == dat_authority.key() @ ErrorCode::InvalidParameter
+    )]
+    pub dat_asdf_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Rebate pool's token account for the deposit mint (receives 0.552%)
+    #[account(
+        mut,
+        constraint = rebate_pool_ata.mint == target_mint.unwrap_or(dat_state.load()?.asdf_mint) @ ErrorCode::MintMismatch
+    )]
+    pub rebate_pool_ata: InterfaceAccount<'info, TokenAccount>,
+
+    /// Deposit mint ($ASDF, or target_mint when set) - transfer_checked and transfer fee
+    #[account(address = target_mint.unwrap_or(dat_state.load()?.asdf_mint) @ ErrorCode::MintMismatch)]
+    pub asdf_mint: InterfaceAccount<'info, Mint>,
 
-    // Volume tracking accounts (20-23)
-    /// CHECK: Global volume accumulator (PDA)
-    pub global_volume_accumulator: AccountInfo<'info>,
-    /// CHECK: User volume accumulator (PDA)
+    /// Transaction payer (can be builder or protocol)
     #[account(mut)]
-    pub user_volume_accumulator: AccountInfo<'info>,
-    /// CHECK: Fee config (PDA)
-    pub fee_config: AccountInfo<'info>,
-    /// CHECK: Fee program
-    pub fee_program: AccountInfo<'info>,
-}
+    pub payer: Signer<'info>,
 
-#[derive(Accounts)]
-pub struct FinalizeAllocatedCycle<'info> {
-    #[account(seeds = [DAT_STATE_SEED], bump)]
-    pub dat_state: Account<'info, DATState>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
 
+    /// Optional user $ASDF account - receives the onboarding bonus on a first deposit
+    /// Omit to skip the bonus (eligibility is kept for a later deposit)
     #[account(
         mut,
-        seeds = [TOKEN_STATS_SEED, token_stats.mint.as_ref()],
-        bump = token_stats.bump
+        constraint = user_ata.mint == dat_state.load()?.asdf_mint @ ErrorCode::MintMismatch,
+        constraint = user_ata.owner == user.key() @ ErrorCode::InvalidBonusAccount
     )]
-    pub token_stats: Account<'info, TokenStats>,
-
-    /// Admin signer required - only admin can finalize allocated cycles
-    #[account(constraint = admin.key() == dat_state.admin @ ErrorCode::UnauthorizedAccess)]
-    pub admin: Signer<'info>,
-}
+    pub user_ata: Option<InterfaceAccount<'info, TokenAccount>>,
 
-#[derive(Accounts)]
-pub struct BurnAndUpdate<'info> {
-    #[account(mut, seeds = [DAT_STATE_SEED], bump)]
-    pub dat_state: Account<'info, DATState>,
+    /// Target token stats (REQUIRED when target_mint is set to a non-root token)
+    /// Credited with the burn slice as pending fees
     #[account(
         mut,
-        seeds = [TOKEN_STATS_SEED, asdf_mint.key().as_ref()],
-        bump = token_stats.bump
+        seeds = [TOKEN_STATS_SEED, target_mint.unwrap_or_default().as_ref()],
+        bump = target_token_stats.load()?.bump
     )]
-    pub token_stats: Account<'info, TokenStats>,
-    /// CHECK: PDA
-    #[account(seeds = [DAT_AUTHORITY_SEED], bump = dat_state.dat_authority_bump)]
-    pub dat_authority: AccountInfo<'info>,
-    #[account(mut)]
-    pub dat_asdf_account: InterfaceAccount<'info, TokenAccount>,
+    pub target_token_stats: Option<AccountLoader<'info, TokenStats>>,
+
+    /// Open rebate epoch - credits the deposit pro-rata instead of pending_contribution
     #[account(
         mut,
-        constraint = asdf_mint.to_account_info().owner == token_program.key @ ErrorCode::InvalidAccountOwner
+        seeds = [REBATE_EPOCH_SEED, &rebate_pool.current_epoch.to_le_bytes()],
+        bump = rebate_epoch.bump
     )]
-    pub asdf_mint: InterfaceAccount<'info, Mint>,
-    pub token_program: Interface<'info, TokenInterface>,
-}
+    pub rebate_epoch: Option<Account<'info, RebateEpoch>>,
 
-#[derive(Accounts)]
-pub struct RecordFailure<'info> {
-    #[account(mut, seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.admin @ ErrorCode::UnauthorizedAccess)]
-    pub dat_state: Account<'info, DATState>,
-    /// Admin signer required to prevent DoS attacks
-    pub admin: Signer<'info>,
-}
+    /// User's closed contribution epoch - REQUIRED (with user_ata) when it is unclaimed
+    #[account(
+        mut,
+        seeds = [REBATE_EPOCH_SEED, &user_stats.contribution_epoch.to_le_bytes()],
+        bump = previous_rebate_epoch.bump
+    )]
+    pub previous_rebate_epoch: Option<Account<'info, RebateEpoch>>,
 
-#[derive(Accounts)]
-pub struct AdminControl<'info> {
-    #[account(mut, seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.admin @ ErrorCode::UnauthorizedAccess)]
-    pub dat_state: Account<'info, DATState>,
-    pub admin: Signer<'info>,
-}
+    /// Optional registered app the deposit is attributed to ($ASDF deposits only)
+    #[account(
+        mut,
+        seeds = [APP_STATS_SEED, &app_stats.app_id.to_le_bytes()],
+        bump = app_stats.bump
+    )]
+    pub app_stats: Option<Box<Account<'info, AppStats>>>,
 
-#[derive(Accounts)]
-pub struct UpdatePendingFees<'info> {
-    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.admin @ ErrorCode::UnauthorizedAccess)]
-    pub dat_state: Account<'info, DATState>,
+    /// Referrer's stats (REQUIRED when the user has a referrer)
     #[account(
         mut,
-        seeds = [TOKEN_STATS_SEED, mint.key().as_ref()],
-        bump,
-        constraint = token_stats.mint == mint.key() @ ErrorCode::MintMismatch
+        seeds = [REFERRAL_STATS_SEED, referral_stats.referrer.as_ref()],
+        bump = referral_stats.bump
     )]
-    pub token_stats: Account<'info, TokenStats>,
-    /// CHECK: Token mint being tracked
-    pub mint: AccountInfo<'info>,
-    pub admin: Signer<'info>,
+    pub referral_stats: Option<Box<Account<'info, ReferralStats>>>,
 }
 
+/// SetReferrer - User names the referrer credited on their deposits (once)
 #[derive(Accounts)]
-pub struct InitializeValidator<'info> {
+pub struct SetReferrer<'info> {
+    /// User stats - initialized if needed
     #[account(
-        init,
-        payer = payer,
-        space = 8 + ValidatorState::LEN,
-        seeds = [VALIDATOR_STATE_SEED, mint.key().as_ref()],
+        init_if_needed,
+        payer = user,
+        space = 8 + UserStats::LEN,
+        seeds = [USER_STATS_SEED, user.key().as_ref()],
         bump
     )]
-    pub validator_state: Account<'info, ValidatorState>,
+    pub user_stats: Account<'info, UserStats>,
 
-    /// CHECK: Bonding curve account - verified by owner constraint
-    #[account(constraint = bonding_curve.owner == &PUMP_PROGRAM @ ErrorCode::InvalidBondingCurve)]
-    pub bonding_curve: AccountInfo<'info>,
+    /// Referrer stats - initialized for the first referred user
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + ReferralStats::LEN,
+        seeds = [REFERRAL_STATS_SEED, referrer.key().as_ref()],
+        bump
+    )]
+    pub referral_stats: Account<'info, ReferralStats>,
 
-    /// CHECK: Token mint
-    pub mint: AccountInfo<'info>,
+    /// CHECK: Any wallet other than the user
+    pub referrer: AccountInfo<'info>,
 
     #[account(mut)]
-    pub payer: Signer<'info>,
+    pub user: Signer<'info>,
+
     pub system_program: Program<'info, System>,
 }
 
+/// ClaimReferralRewards - Referrer withdraws credited rewards from the rebate pool
 #[derive(Accounts)]
-pub struct RegisterValidatedFees<'info> {
+pub struct ClaimReferralRewards<'info> {
     #[account(seeds = [DAT_STATE_SEED], bump)]
-    pub dat_state: Account<'info, DATState>,
+    pub dat_state: AccountLoader<'info, DATState>,
 
-    /// Admin signer - only admin can register fees (CRITICAL security fix)
-    #[account(constraint = admin.key() == dat_state.admin @ ErrorCode::UnauthorizedAccess)]
-    pub admin: Signer<'info>,
+    /// Rebate pool authority PDA
+    #[account(mut, seeds = [REBATE_POOL_SEED], bump = rebate_pool.bump)]
+    pub rebate_pool: Account<'info, RebatePool>,
 
+    /// Rebate pool's $ASDF ATA (holds the reserved rewards)
     #[account(
         mut,
-        seeds = [VALIDATOR_STATE_SEED, validator_state.mint.as_ref()],
-        bump = validator_state.bump,
+        constraint = rebate_pool_ata.mint == dat_state.load()?.asdf_mint @ ErrorCode::MintMismatch,
+        constraint = rebate_pool_ata.owner == rebate_pool.key() @ ErrorCode::InvalidRebatePool
     )]
-    pub validator_state: Account<'info, ValidatorState>,
+    pub rebate_pool_ata: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
-        seeds = [TOKEN_STATS_SEED, validator_state.mint.as_ref()],
-        bump = token_stats.bump,
-        constraint = token_stats.mint == validator_state.mint @ ErrorCode::MintMismatch
+        seeds = [REFERRAL_STATS_SEED, referrer.key().as_ref()],
+        bump = referral_stats.bump,
+        has_one = referrer @ ErrorCode::UnauthorizedAccess
     )]
-    pub token_stats: Account<'info, TokenStats>,
-}
+    pub referral_stats: Account<'info, ReferralStats>,
 
-/// Accounts for sync_validator_slot instruction
-/// HIGH-02 FIX: Now requires admin authorization to prevent DoS attacks
-#[derive(Accounts)]
-pub struct SyncValidatorSlot<'info> {
-    // HIGH-02 FIX: Added DATState and admin signer for authorization
-    #[account(seeds = [DAT_STATE_SEED], bump)]
-    pub dat_state: Account<'info, DATState>,
+    pub referrer: Signer<'info>,
 
+    /// Referrer's $ASDF account (destination)
     #[account(
         mut,
-        seeds = [VALIDATOR_STATE_SEED, validator_state.mint.as_ref()],
-        bump = validator_state.bump,
+        constraint = referrer_ata.mint == dat_state.load()?.asdf_mint @ ErrorCode::MintMismatch,
+        constraint = referrer_ata.owner == referrer.key() @ ErrorCode::InvalidParameter
     )]
-    pub validator_state: Account<'info, ValidatorState>,
+    pub referrer_ata: InterfaceAccount<'info, TokenAccount>,
 
-    /// Admin authority - HIGH-02 FIX: Required to prevent DoS
-    #[account(
-        constraint = admin.key() == dat_state.admin @ ErrorCode::UnauthorizedAccess
-    )]
-    pub admin: Signer<'info>,
+    /// $ASDF mint (decimals for transfer_checked)
+    #[account(address = dat_state.load()?.asdf_mint @ ErrorCode::MintMismatch)]
+    pub asdf_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
+/// RegisterApp - Register an integrating app and create its AppStats (admin only)
 #[derive(Accounts)]
-pub struct ResetValidatorSlot<'info> {
-    #[account(seeds = [DAT_STATE_SEED], bump)]
-    pub dat_state: Account<'info, DATState>,
-
+#[instruction(app_id: u32)]
+pub struct RegisterApp<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
     #[account(
-        mut,
-        seeds = [VALIDATOR_STATE_SEED, validator_state.mint.as_ref()],
-        bump = validator_state.bump,
+        init_if_needed,
+        payer = admin,
+        space = 8 + AppRegistry::LEN,
+        seeds = [APP_REGISTRY_SEED],
+        bump
     )]
-    pub validator_state: Account<'info, ValidatorState>,
-
-    #[account(constraint = admin.key() == dat_state.admin @ ErrorCode::UnauthorizedAccess)]
-    pub admin: Signer<'info>,
-}
-
-#[derive(Accounts)]
-pub struct MigrateTokenStats<'info> {
-    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.admin @ ErrorCode::UnauthorizedAccess)]
-    pub dat_state: Account<'info, DATState>,
-    #[account(mut, constraint = token_stats.owner == &crate::ID @ ErrorCode::InvalidAccountOwner)]
-    /// CHECK: Manual PDA verification and deserialization for migration
-    pub token_stats: AccountInfo<'info>,
-    /// CHECK: Mint address for PDA derivation
-    pub mint: AccountInfo<'info>,
-    pub admin: Signer<'info>,
-    pub system_program: Program<'info, System>,
-}
-
-/// MigrateDatState - Migrate DAT state to add new fields (one-time migration)
-/// This handles the account reallocation from 382 to 390 bytes
-#[derive(Accounts)]
-pub struct MigrateDatState<'info> {
-    #[account(mut, seeds = [DAT_STATE_SEED], bump)]
-    /// CHECK: Manual verification - using AccountInfo for raw data access during migration
-    pub dat_state: AccountInfo<'info>,
+    pub app_registry: Account<'info, AppRegistry>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + AppStats::LEN,
+        seeds = [APP_STATS_SEED, &app_id.to_le_bytes()],
+        bump
+    )]
+    pub app_stats: Account<'info, AppStats>,
     #[account(mut)]
     pub admin: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
-/// ProposeAdminTransfer - Current admin proposes a new admin (two-step transfer)
+/// DepositFeeSol - External app deposits SOL fees
+/// Split: 99.448% → root treasury (next ROOT cycle buyback), 0.552% → rebate pool PDA
 #[derive(Accounts)]
-pub struct ProposeAdminTransfer<'info> {
-    #[account(mut, seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.admin @ ErrorCode::UnauthorizedAccess)]
-    pub dat_state: Account<'info, DATState>,
-    pub admin: Signer<'info>,
-    /// CHECK: Proposed new admin (will need to accept)
-    pub new_admin: AccountInfo<'info>,
-}
+pub struct DepositFeeSol<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
 
-/// CancelAdminTransfer - Current admin cancels a pending transfer
-#[derive(Accounts)]
-pub struct CancelAdminTransfer<'info> {
+    /// Rebate pool state (receives the rebate share in lamports)
     #[account(
         mut,
-        seeds = [DAT_STATE_SEED],
-        bump,
-        constraint = admin.key() == dat_state.admin @ ErrorCode::UnauthorizedAccess,
-        constraint = dat_state.pending_admin.is_some() @ ErrorCode::InvalidParameter
+        seeds = [REBATE_POOL_SEED],
+        bump = rebate_pool.bump
     )]
-    pub dat_state: Account<'info, DATState>,
-    pub admin: Signer<'info>,
-}
+    pub rebate_pool: Account<'info, RebatePool>,
 
-/// AcceptAdminTransfer - Proposed admin accepts the transfer (two-step transfer)
-#[derive(Accounts)]
-pub struct AcceptAdminTransfer<'info> {
+    /// User stats - initialized if needed
     #[account(
-        mut,
-        seeds = [DAT_STATE_SEED],
-        bump,
-        constraint = dat_state.pending_admin == Some(new_admin.key()) @ ErrorCode::UnauthorizedAccess
+        init_if_needed,
+        payer = payer,
+        space = 8 + UserStats::LEN,
+        seeds = [USER_STATS_SEED, user.key().as_ref()],
+        bump
     )]
-    pub dat_state: Account<'info, DATState>,
-    /// The proposed admin who is accepting the transfer
-    pub new_admin: Signer<'info>,
-}
-
-/// DEPRECATED: Use ProposeAdminTransfer + AcceptAdminTransfer instead
-/// Kept for backwards compatibility but now just calls propose_admin_transfer
-#[derive(Accounts)]
-pub struct TransferAdmin<'info> {
-    #[account(mut, seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.admin @ ErrorCode::UnauthorizedAccess)]
-    pub dat_state: Account<'info, DATState>,
-    pub admin: Signer<'info>,
-    /// CHECK: New admin
-    pub new_admin: AccountInfo<'info>,
-}
-
-#[derive(Accounts)]
-pub struct CreatePumpfunTokenMayhem<'info> {
-    #[account(seeds = [DAT_STATE_SEED], bump)]
-    pub dat_state: Account<'info, DATState>,
-
-    /// CHECK: PDA - DAT Authority acts as token creator
-    #[account(mut, seeds = [DAT_AUTHORITY_SEED], bump = dat_state.dat_authority_bump)]
-    pub dat_authority: AccountInfo<'info>,
-
-    #[account(mut, constraint = admin.key() == dat_state.admin @ ErrorCode::UnauthorizedAccess)]
-    pub admin: Signer<'info>,
-
-    #[account(mut)]
-    pub mint: Signer<'info>,
+    pub user_stats: Account<'info, UserStats>,
 
-    /// CHECK: PDA from pump program (mint-authority seed)
-    pub mint_authority: AccountInfo<'info>,
+    /// The user whose contribution is being tracked
+    /// CHECK: Any valid pubkey (user being credited)
+    pub user: AccountInfo<'info>,
 
-    /// CHECK: Bonding curve PDA (82 bytes for Mayhem Mode - 81 + 1 for is_mayhem_mode flag)
+    /// CHECK: Root treasury PDA - validated at runtime via PDA derivation:
+    /// ["root_treasury", root_token_mint]
     #[account(mut)]
-    pub bonding_curve: AccountInfo<'info>,
+    pub root_treasury: AccountInfo<'info>,
 
-    /// CHECK: Associated bonding curve token account (Token2022 ATA)
+    /// Transaction payer (source of the SOL deposit)
     #[account(mut)]
-    pub associated_bonding_curve: AccountInfo<'info>,
-
-    /// CHECK: Global config PDA from pump program
-    pub global: AccountInfo<'info>,
+    pub payer: Signer<'info>,
 
     pub system_program: Program<'info, System>,
+}
 
-    /// CHECK: Token2022 program (not legacy Token program!)
-    pub token_2022_program: AccountInfo<'info>,
-
-    pub associated_token_program: Program<'info, AssociatedToken>,
-
-    /// CHECK: Mayhem program - handles AI agent trading
-    #[account(mut)]
-    pub mayhem_program: AccountInfo<'info>,
-
-    /// CHECK: Global params PDA from mayhem program
-    pub global_params: AccountInfo<'info>,
-
-    /// CHECK: SOL vault PDA from mayhem program
-    #[account(mut)]
-    pub sol_vault: AccountInfo<'info>,
-
-    /// CHECK: Mayhem state PDA (derived from mint)
-    #[account(mut)]
-    pub mayhem_state: AccountInfo<'info>,
+/// DepositFeeToken - External app deposits fees in any non-$ASDF SPL token
+/// Tokens are escrowed in a dat_authority-owned vault until convert_token_deposit
+#[derive(Accounts)]
+pub struct DepositFeeToken<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
 
-    /// CHECK: Mayhem token vault (Token2022 ATA)
-    #[account(mut)]
-    pub mayhem_token_vault: AccountInfo<'info>,
+    /// CHECK: DAT authority PDA - owner of the escrow vault
+    #[account(seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
+    pub dat_authority: AccountInfo<'info>,
 
-    /// CHECK: Event authority PDA
-    pub event_authority: AccountInfo<'info>,
+    /// Deposited token mint ($ASDF goes through deposit_fee_asdf)
+    #[account(constraint = mint.key() != dat_state.load()?.asdf_mint @ ErrorCode::UnsupportedDepositMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
 
-    /// CHECK: Main pump program (6EF8r...)
-    pub pump_program: AccountInfo<'info>,
-}
+    /// The user credited once the deposit is converted
+    /// CHECK: Any valid pubkey (user being credited)
+    pub user: AccountInfo<'info>,
 
-/// CreatePumpfunTokenV2 - Create token using create_v2 (Token2022) without Mayhem Mode
-/// Standard Token2022 token with 1B supply
-/// NOTE: Even without Mayhem Mode, PumpFun's create_v2 requires all Mayhem accounts
-#[derive(Accounts)]
-pub struct CreatePumpfunTokenV2<'info> {
-    #[account(seeds = [DAT_STATE_SEED], bump)]
-    pub dat_state: Account<'info, DATState>,
+    /// Escrow record for (user, mint) - initialized if needed
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + TokenDeposit::LEN,
+        seeds = [TOKEN_DEPOSIT_SEED, user.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub token_deposit: Account<'info, TokenDeposit>,
 
-    /// CHECK: PDA - DAT Authority acts as token creator
-    #[account(mut, seeds = [DAT_AUTHORITY_SEED], bump = dat_state.dat_authority_bump)]
-    pub dat_authority: AccountInfo<'info>,
+    /// Escrow vault holding the deposited tokens
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [TOKEN_DEPOSIT_VAULT_SEED, token_deposit.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = dat_authority,
+        token::token_program = token_program
+    )]
+    pub deposit_vault: InterfaceAccount<'info, TokenAccount>,
 
-    #[account(mut, constraint = admin.key() == dat_state.admin @ ErrorCode::UnauthorizedAccess)]
-    pub admin: Signer<'info>,
+    /// Payer's token account (source of deposit)
+    #[account(
+        mut,
+        constraint = payer_token_account.mint == mint.key() @ ErrorCode::MintMismatch
+    )]
+    pub payer_token_account: InterfaceAccount<'info, TokenAccount>,
 
+    /// Transaction payer (can be builder or protocol)
     #[account(mut)]
-    pub mint: Signer<'info>,
+    pub payer: Signer<'info>,
 
-    /// CHECK: PDA from pump program (mint-authority seed)
-    pub mint_authority: AccountInfo<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
 
-    /// CHECK: Bonding curve PDA
-    #[account(mut)]
-    pub bonding_curve: AccountInfo<'info>,
+/// ConvertTokenDeposit - Orchestrator swaps an escrowed deposit to $ASDF (admin only)
+/// Escrowed tokens go to the admin; the admin's $ASDF takes the standard burn/rebate split
+#[derive(Accounts)]
+pub struct ConvertTokenDeposit<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
 
-    /// CHECK: Associated bonding curve token account (Token2022 ATA)
-    #[account(mut)]
-    pub associated_bonding_curve: AccountInfo<'info>,
+    /// CHECK: DAT authority PDA - owner of the escrow vault and DAT ATA
+    #[account(seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
+    pub dat_authority: AccountInfo<'info>,
 
-    /// CHECK: Global config PDA from pump program
-    pub global: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [TOKEN_DEPOSIT_SEED, token_deposit.user.as_ref(), token_deposit.mint.as_ref()],
+        bump = token_deposit.bump
+    )]
+    pub token_deposit: Box<Account<'info, TokenDeposit>>,
 
-    pub system_program: Program<'info, System>,
+    #[account(
+        mut,
+        seeds = [TOKEN_DEPOSIT_VAULT_SEED, token_deposit.key().as_ref()],
+        bump
+    )]
+    pub deposit_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    /// CHECK: Token2022 program (not legacy Token program!)
-    pub token_2022_program: AccountInfo<'info>,
+    #[account(constraint = mint.key() == token_deposit.mint @ ErrorCode::MintMismatch)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
 
-    pub associated_token_program: Program<'info, AssociatedToken>,
+    /// Admin account receiving the escrowed tokens for the swap
+    #[account(
+        mut,
+        constraint = admin_token_account.mint == token_deposit.mint @ ErrorCode::MintMismatch
+    )]
+    pub admin_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    // Mayhem accounts - required by create_v2 even when is_mayhem_mode = false
-    /// CHECK: Mayhem program (MAyhSmz...) - must be passed even without Mayhem Mode
-    #[account(mut)]
-    pub mayhem_program: AccountInfo<'info>,
+    /// Admin $ASDF account funding the conversion
+    #[account(
+        mut,
+        constraint = admin_asdf_account.mint == dat_state.load()?.asdf_mint @ ErrorCode::MintMismatch
+    )]
+    pub admin_asdf_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    /// CHECK: Global params PDA from mayhem program
-    pub global_params: AccountInfo<'info>,
+    /// DAT's $ASDF account (receives 99.448% for burn)
+    #[account(
+        mut,
+        constraint = dat_asdf_account.mint == dat_state.load()?.asdf_mint @ ErrorCode::MintMismatch,
+        constraint = dat_asdf_account.owner == dat_authority.key() @ ErrorCode::InvalidParameter
+    )]
+    pub dat_asdf_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    /// CHECK: SOL vault PDA from mayhem program
-    #[account(mut)]
-    pub sol_vault: AccountInfo<'info>,
+    #[account(seeds = [REBATE_POOL_SEED], bump = rebate_pool.bump)]
+    pub rebate_pool: Box<Account<'info, RebatePool>>,
 
-    /// CHECK: Mayhem state PDA (derived from mint)
-    #[account(mut)]
-    pub mayhem_state: AccountInfo<'info>,
+    /// Rebate pool's $ASDF account (receives 0.552%)
+    #[account(
+        mut,
+        constraint = rebate_pool_ata.mint == dat_state.load()?.asdf_mint @ ErrorCode::MintMismatch,
+        constraint = rebate_pool_ata.owner == rebate_pool.key() @ ErrorCode::InvalidRebatePool
+    )]
+    pub rebate_pool_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// User stats - initialized if needed
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + UserStats::LEN,
+        seeds = [USER_STATS_SEED, token_deposit.user.as_ref()],
+        bump
+    )]
+    pub user_stats: Box<Account<'info, UserStats>>,
 
-    /// CHECK: Mayhem token vault (Token2022 ATA)
     #[account(mut)]
-    pub mayhem_token_vault: AccountInfo<'info>,
+    pub admin: Signer<'info>,
 
-    /// CHECK: Event authority PDA
-    pub event_authority: AccountInfo<'info>,
+    /// $ASDF mint (decimals for transfer_checked)
+    #[account(address = dat_state.load()?.asdf_mint @ ErrorCode::MintMismatch)]
+    pub asdf_mint: Box<InterfaceAccount<'info, Mint>>,
 
-    /// CHECK: Main pump program (6EF8r...)
-    pub pump_program: AccountInfo<'info>,
+    /// Token program of the deposited mint
+    pub token_program: Interface<'info, TokenInterface>,
+    /// Token program of $ASDF
+    pub asdf_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
 }
 
-/// TransferDevFee - Transfer 1% dev sustainability fee at end of batch
-/// Called after burn to ensure cycle completed successfully before taking fee
+/// RefundTokenDeposit - User reclaims an unconverted deposit after TOKEN_DEPOSIT_REFUND_DELAY
 #[derive(Accounts)]
-pub struct TransferDevFee<'info> {
+pub struct RefundTokenDeposit<'info> {
     #[account(seeds = [DAT_STATE_SEED], bump)]
-    pub dat_state: Account<'info, DATState>,
+    pub dat_state: AccountLoader<'info, DATState>,
 
-    /// CHECK: DAT authority PDA - source of SOL for dev fee
-    #[account(mut, seeds = [DAT_AUTHORITY_SEED], bump = dat_state.dat_authority_bump)]
+    /// CHECK: DAT authority PDA - owner of the escrow vault
+    #[account(seeds = [DAT_AUTHORITY_SEED], bump = dat_state.load()?.dat_authority_bump)]
     pub dat_authority: AccountInfo<'info>,
 
-    /// CHECK: Dev wallet - validated against hardcoded constant
-    /// 1% today = 99% burns forever
     #[account(
         mut,
-        address = DEV_WALLET @ ErrorCode::InvalidDevWallet
+        seeds = [TOKEN_DEPOSIT_SEED, user.key().as_ref(), token_deposit.mint.as_ref()],
+        bump = token_deposit.bump,
+        has_one = user @ ErrorCode::UnauthorizedAccess
     )]
-    pub dev_wallet: AccountInfo<'info>,
+    pub token_deposit: Account<'info, TokenDeposit>,
 
-    pub system_program: Program<'info, System>,
+    #[account(
+        mut,
+        seeds = [TOKEN_DEPOSIT_VAULT_SEED, token_deposit.key().as_ref()],
+        bump
+    )]
+    pub deposit_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(constraint = mint.key() == token_deposit.mint @ ErrorCode::MintMismatch)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == token_deposit.mint @ ErrorCode::MintMismatch,
+        constraint = user_token_account.owner == user.key() @ ErrorCode::InvalidParameter
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub user: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
-// ══════════════════════════════════════════════════════════════════════════════
-// EXTERNAL APP INTEGRATION CONTEXTS
-// ══════════════════════════════════════════════════════════════════════════════
+/// WithdrawRebateSol - Admin withdraws SOL accrued by the rebate pool for conversion to $ASDF
+#[derive(Accounts)]
+pub struct WithdrawRebateSol<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(mut, seeds = [REBATE_POOL_SEED], bump = rebate_pool.bump)]
+    pub rebate_pool: Account<'info, RebatePool>,
+    /// CHECK: Any account receiving the lamports (admin's swap wallet)
+    #[account(mut)]
+    pub destination: AccountInfo<'info>,
+    pub admin: Signer<'info>,
+}
 
-/// InitializeRebatePool - Initialize the self-sustaining rebate pool
-/// Called once during protocol setup
+/// ConfigureOnboardingBonus - Admin queues the first-deposit bonus rate, cap and budget
 #[derive(Accounts)]
-pub struct InitializeRebatePool<'info> {
+pub struct ConfigureOnboardingBonus<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(seeds = [REBATE_POOL_SEED], bump = rebate_pool.bump)]
+    pub rebate_pool: Account<'info, RebatePool>,
+    #[account(mut, seeds = [CHANGE_QUEUE_SEED], bump = change_queue.bump)]
+    pub change_queue: Box<Account<'info, ChangeQueue>>,
+    pub admin: Signer<'info>,
+}
+
+/// SetReferralShare - Admin queues the referrer share of referred deposits
+#[derive(Accounts)]
+pub struct SetReferralShare<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(seeds = [REBATE_POOL_SEED], bump = rebate_pool.bump)]
+    pub rebate_pool: Account<'info, RebatePool>,
+    #[account(mut, seeds = [CHANGE_QUEUE_SEED], bump = change_queue.bump)]
+    pub change_queue: Box<Account<'info, ChangeQueue>>,
+    pub admin: Signer<'info>,
+}
+
+/// UpdateRebateParams - Admin proposes / applies rebate economics (timelocked)
+#[derive(Accounts)]
+pub struct UpdateRebateParams<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(mut, seeds = [REBATE_POOL_SEED], bump = rebate_pool.bump)]
+    pub rebate_pool: Account<'info, RebatePool>,
+    pub admin: Signer<'info>,
+}
+
+/// RequestRebateDraw - Admin commits a candidate list for a weighted rebate draw
+#[derive(Accounts)]
+pub struct RequestRebateDraw<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(mut, seeds = [REBATE_POOL_SEED], bump = rebate_pool.bump)]
+    pub rebate_pool: Account<'info, RebatePool>,
+    pub admin: Signer<'info>,
+}
+
+/// SelectRebateWinner - Resolve a pending rebate draw (permissionless)
+/// remaining_accounts: the committed candidate UserStats, in committed order
+#[derive(Accounts)]
+pub struct SelectRebateWinner<'info> {
+    #[account(mut, seeds = [REBATE_POOL_SEED], bump = rebate_pool.bump)]
+    pub rebate_pool: Account<'info, RebatePool>,
+    /// CHECK: SlotHashes sysvar (draw entropy), read raw - too large to deserialize
+    #[account(address = anchor_lang::solana_program::sysvar::slot_hashes::ID)]
+    pub slot_hashes: AccountInfo<'info>,
+}
+
+/// InitializeRebateEpoch - Admin opens the first pro-rata rebate epoch
+#[derive(Accounts)]
+pub struct InitializeRebateEpoch<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(mut, seeds = [REBATE_POOL_SEED], bump = rebate_pool.bump)]
+    pub rebate_pool: Account<'info, RebatePool>,
     #[account(
         init,
         payer = admin,
-        space = 8 + RebatePool::LEN,
-        seeds = [REBATE_POOL_SEED],
+        space = 8 + RebateEpoch::LEN,
+        seeds = [REBATE_EPOCH_SEED, &rebate_pool.current_epoch.to_le_bytes()],
         bump
     )]
-    pub rebate_pool: Account<'info, RebatePool>,
+    pub rebate_epoch: Account<'info, RebateEpoch>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
 
+/// CloseRebateEpoch - Close the open epoch and open the next (permissionless, payer covers rent)
+#[derive(Accounts)]
+pub struct CloseRebateEpoch<'info> {
     #[account(seeds = [DAT_STATE_SEED], bump)]
-    pub dat_state: Account<'info, DATState>,
-
-    /// Admin must authorize initialization
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(mut, seeds = [REBATE_POOL_SEED], bump = rebate_pool.bump)]
+    pub rebate_pool: Account<'info, RebatePool>,
     #[account(
         mut,
-        constraint = admin.key() == dat_state.admin @ ErrorCode::UnauthorizedAccess
+        seeds = [REBATE_EPOCH_SEED, &rebate_pool.current_epoch.to_le_bytes()],
+        bump = rebate_epoch.bump
     )]
-    pub admin: Signer<'info>,
-
+    pub rebate_epoch: Account<'info, RebateEpoch>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + RebateEpoch::LEN,
+        seeds = [REBATE_EPOCH_SEED, &rebate_pool.current_epoch.saturating_add(1).to_le_bytes()],
+        bump
+    )]
+    pub next_rebate_epoch: Account<'info, RebateEpoch>,
+    /// Rebate pool's $ASDF ATA (balance bounds the reservation)
+    #[account(
+        constraint = rebate_pool_ata.mint == dat_state.load()?.asdf_mint @ ErrorCode::MintMismatch,
+        constraint = rebate_pool_ata.owner == rebate_pool.key() @ ErrorCode::InvalidRebatePool
+    )]
+    pub rebate_pool_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
-/// DepositFeeAsdf - External app deposits $ASDF fees with automatic split
-/// Split: 99.448% → DAT ATA (burn), 0.552% → Rebate Pool ATA (rebates)
+/// ClaimEpochRebate - User claims their pro-rata share of a closed epoch
 #[derive(Accounts)]
-pub struct DepositFeeAsdf<'info> {
+pub struct ClaimEpochRebate<'info> {
     #[account(seeds = [DAT_STATE_SEED], bump)]
-    pub dat_state: Account<'info, DATState>,
-
-    /// CHECK: DAT authority PDA
-    #[account(seeds = [DAT_AUTHORITY_SEED], bump = dat_state.dat_authority_bump)]
-    pub dat_authority: AccountInfo<'info>,
-
-    /// Rebate pool state (for tracking deposits)
+    pub dat_state: AccountLoader<'info, DATState>,
+    #[account(mut, seeds = [REBATE_POOL_SEED], bump = rebate_pool.bump)]
+    pub rebate_pool: Account<'info, RebatePool>,
     #[account(
         mut,
-        seeds = [REBATE_POOL_SEED],
-        bump = rebate_pool.bump
+        seeds = [REBATE_EPOCH_SEED, &user_stats.contribution_epoch.to_le_bytes()],
+        bump = rebate_epoch.bump
     )]
-    pub rebate_pool: Account<'info, RebatePool>,
-
-    /// User stats - initialized if needed
-    /// Protocol pays rent via dat_authority
+    pub rebate_epoch: Account<'info, RebateEpoch>,
     #[account(
-        init_if_needed,
-        payer = payer,
-        space = 8 + UserStats::LEN,
+        mut,
+        constraint = rebate_pool_ata.mint == dat_state.load()?.asdf_mint @ ErrorCode::MintMismatch
+    )]
+    pub rebate_pool_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
         seeds = [USER_STATS_SEED, user.key().as_ref()],
-        bump
+        bump = user_stats.bump,
+        constraint = user_stats.user == user.key() @ ErrorCode::InvalidParameter
     )]
     pub user_stats: Account<'info, UserStats>,
-
-    /// The user whose contribution is being tracked
-    /// CHECK: Any valid pubkey (user being credited)
-    pub user: AccountInfo<'info>,
-
-    /// Payer's $ASDF token account (source of deposit)
+    pub user: Signer<'info>,
     #[account(
         mut,
-        constraint = payer_token_account.mint == dat_state.asdf_mint @ ErrorCode::MintMismatch
+        constraint = user_ata.mint == dat_state.load()?.asdf_mint @ ErrorCode::MintMismatch,
+        constraint = user_ata.owner == user.key() @ ErrorCode::InvalidParameter
     )]
-    pub payer_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub user_ata: InterfaceAccount<'info, TokenAccount>,
+    /// $ASDF mint (decimals for transfer_checked)
+    #[account(address = dat_state.load()?.asdf_mint @ ErrorCode::MintMismatch)]
+    pub asdf_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
 
-    /// DAT's $ASDF token account (receives 99.448% for burn)
+/// MigrateRebatePool - Realloc the rebate pool to add tunable params (admin pays rent)
+#[derive(Accounts)]
+pub struct MigrateRebatePool<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump, constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
+    pub dat_state: AccountLoader<'info, DATState>,
+    /// CHECK: Manual verification - legacy layout cannot deserialize as RebatePool
     #[account(
         mut,
-        constraint = dat_asdf_account.mint == dat_state.asdf_mint @ ErrorCode::MintMismatch,
-        constraint = dat_asdf_account.owner == dat_authority.key() @ ErrorCode::InvalidParameter
+        seeds = [REBATE_POOL_SEED],
+        bump,
+        constraint = rebate_pool.owner == &crate::ID @ ErrorCode::InvalidAccountOwner
     )]
-    pub dat_asdf_account: InterfaceAccount<'info, TokenAccount>,
+    pub rebate_pool: AccountInfo<'info>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
 
-    /// Rebate pool's $ASDF ATA (receives 0.552% for rebates)
+/// MigrateUserStats - Realloc a legacy UserStats account (permissionless, payer covers rent)
+#[derive(Accounts)]
+pub struct MigrateUserStats<'info> {
+    /// CHECK: Manual PDA verification - legacy layout cannot deserialize as UserStats
     #[account(
         mut,
-        constraint = rebate_pool_ata.mint == dat_state.asdf_mint @ ErrorCode::MintMismatch
+        seeds = [USER_STATS_SEED, user.key().as_ref()],
+        bump,
+        constraint = user_stats.owner == &crate::ID @ ErrorCode::InvalidAccountOwner
     )]
-    pub rebate_pool_ata: InterfaceAccount<'info, TokenAccount>,
-
-    /// Transaction payer (can be builder or protocol)
+    pub user_stats: AccountInfo<'info>,
+    /// CHECK: User whose stats are migrated (PDA derivation only)
+    pub user: AccountInfo<'info>,
     #[account(mut)]
     pub payer: Signer<'info>,
-
-    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
@@ -831,7 +4104,7 @@ pub struct DepositFeeAsdf<'info> {
 #[derive(Accounts)]
 pub struct ProcessUserRebate<'info> {
     #[account(seeds = [DAT_STATE_SEED], bump)]
-    pub dat_state: Account<'info, DATState>,
+    pub dat_state: AccountLoader<'info, DATState>,
 
     /// Rebate pool authority PDA
     #[account(
@@ -844,7 +4117,7 @@ pub struct ProcessUserRebate<'info> {
     /// Rebate pool's $ASDF ATA (source of rebate funds)
     #[account(
         mut,
-        constraint = rebate_pool_ata.mint == dat_state.asdf_mint @ ErrorCode::MintMismatch
+        constraint = rebate_pool_ata.mint == dat_state.load()?.asdf_mint @ ErrorCode::MintMismatch
     )]
     pub rebate_pool_ata: InterfaceAccount<'info, TokenAccount>,
 
@@ -860,17 +4133,75 @@ pub struct ProcessUserRebate<'info> {
     /// CHECK: User receiving rebate
     pub user: AccountInfo<'info>,
 
+    /// Selected user's stake - boosts the rebate while locked
+    #[account(seeds = [STAKE_SEED, user.key().as_ref()], bump = stake_account.bump)]
+    pub stake_account: Option<Account<'info, StakeAccount>>,
+
     /// User's $ASDF ATA (destination for rebate)
     #[account(
         mut,
-        constraint = user_ata.mint == dat_state.asdf_mint @ ErrorCode::MintMismatch,
+        constraint = user_ata.mint == dat_state.load()?.asdf_mint @ ErrorCode::MintMismatch,
         constraint = user_ata.owner == user.key() @ ErrorCode::InvalidParameter
     )]
     pub user_ata: InterfaceAccount<'info, TokenAccount>,
 
     /// Admin authorization for rebate processing
-    #[account(constraint = admin.key() == dat_state.admin @ ErrorCode::UnauthorizedAccess)]
+    #[account(constraint = admin.key() == dat_state.load()?.admin @ ErrorCode::UnauthorizedAccess)]
     pub admin: Signer<'info>,
 
+    /// $ASDF mint (decimals for transfer_checked)
+    #[account(address = dat_state.load()?.asdf_mint @ ErrorCode::MintMismatch)]
+    pub asdf_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// ClaimRebate - User pulls their own rebate from the pool (permissionless)
+#[derive(Accounts)]
+pub struct ClaimRebate<'info> {
+    #[account(seeds = [DAT_STATE_SEED], bump)]
+    pub dat_state: AccountLoader<'info, DATState>,
+
+    /// Rebate pool authority PDA
+    #[account(
+        mut,
+        seeds = [REBATE_POOL_SEED],
+        bump = rebate_pool.bump
+    )]
+    pub rebate_pool: Account<'info, RebatePool>,
+
+    /// Rebate pool's $ASDF ATA (source of rebate funds)
+    #[account(
+        mut,
+        constraint = rebate_pool_ata.mint == dat_state.load()?.asdf_mint @ ErrorCode::MintMismatch
+    )]
+    pub rebate_pool_ata: InterfaceAccount<'info, TokenAccount>,
+
+    /// Claiming user's stats
+    #[account(
+        mut,
+        seeds = [USER_STATS_SEED, user.key().as_ref()],
+        bump = user_stats.bump,
+        constraint = user_stats.user == user.key() @ ErrorCode::InvalidParameter
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    /// User claiming the rebate
+    pub user: Signer<'info>,
+
+    /// Claiming user's stake - boosts the rebate while locked
+    #[account(seeds = [STAKE_SEED, user.key().as_ref()], bump = stake_account.bump)]
+    pub stake_account: Option<Account<'info, StakeAccount>>,
+
+    /// User's $ASDF ATA (destination for rebate)
+    #[account(
+        mut,
+        constraint = user_ata.mint == dat_state.load()?.asdf_mint @ ErrorCode::MintMismatch,
+        constraint = user_ata.owner == user.key() @ ErrorCode::InvalidParameter
+    )]
+    pub user_ata: InterfaceAccount<'info, TokenAccount>,
+
+    /// $ASDF mint (decimals for transfer_checked)
+    #[account(address = dat_state.load()?.asdf_mint @ ErrorCode::MintMismatch)]
+    pub asdf_mint: InterfaceAccount<'info, Mint>,
     pub token_program: Interface<'info, TokenInterface>,
 }
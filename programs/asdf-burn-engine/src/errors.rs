@@ -37,6 +37,9 @@ pub enum ErrorCode {
     #[msg("No tokens pending burn")]
     NoPendingBurn,
 
+    #[msg("dat_authority is already above its rent floor buffer")]
+    AuthorityAboveFloor,
+
     #[msg("Invalid pool state")]
     InvalidPool,
 
@@ -47,6 +50,30 @@ pub enum ErrorCode {
     #[msg("Invalid root treasury")]
     InvalidRootTreasury,
 
+    #[msg("Root set is full")]
+    RootSetFull,
+
+    #[msg("Root is already listed")]
+    RootAlreadyInSet,
+
+    #[msg("Root is not in the root set")]
+    RootNotInSet,
+
+    #[msg("Token registry page is full")]
+    TokenRegistryPageFull,
+
+    #[msg("Every token registry page must be full before a new page is created")]
+    TokenRegistryNotFull,
+
+    #[msg("Token is already in the token registry")]
+    TokenAlreadyRegistered,
+
+    #[msg("Token is not in this token registry page")]
+    TokenNotInRegistry,
+
+    #[msg("Nothing accrued or flush schedule not reached")]
+    RootFlushNotDue,
+
     // Fee split errors
     #[msg("Fee split must be 0-10000 bps")]
     InvalidFeeSplit,
@@ -58,6 +85,9 @@ pub enum ErrorCode {
     #[msg("Insufficient pool liquidity")]
     InsufficientPoolLiquidity,
 
+    #[msg("No tradable venue to quote")]
+    NoVenueAvailable,
+
     // Validator errors
     #[msg("Slot already processed")]
     StaleValidation,
@@ -100,9 +130,477 @@ pub enum ErrorCode {
     #[msg("Account size mismatch")]
     AccountSizeMismatch,
 
+    #[msg("Account discriminator does not match the migration kind")]
+    MigrationKindMismatch,
+
+    #[msg("Migrating this TokenStats layout needs its mint account")]
+    MigrationMintMissing,
+
+    #[msg("Migration target version does not match this program build")]
+    MigrationVersionMismatch,
+
     #[msg("Invalid dev wallet address")]
     InvalidDevWallet,
 
+    #[msg("Dev fee exceeds the 200 bps cap")]
+    DevFeeAboveCap,
+
+    #[msg("No pending dev fee change")]
+    NoPendingDevFee,
+
+    #[msg("Dev fee already paid for this cycle")]
+    DevFeeAlreadyPaid,
+
+    // Parameter change queue errors
+    #[msg("Parameter change queue is full")]
+    ChangeQueueFull,
+
+    #[msg("No queued parameter change with this id")]
+    PendingChangeNotFound,
+
+    // Governance errors
+    #[msg("Voting period has ended")]
+    VotingClosed,
+
+    #[msg("Voting period has not ended")]
+    VotingNotEnded,
+
+    #[msg("Proposal did not reach quorum and majority")]
+    ProposalNotPassed,
+
+    #[msg("Proposal already executed")]
+    ProposalAlreadyExecuted,
+
+    // Staking errors
+    #[msg("Stake lock must be MIN_STAKE_LOCK-MAX_STAKE_LOCK and not shorten the current lock")]
+    InvalidLockDuration,
+
+    #[msg("Stake is still locked")]
+    StakeLocked,
+
+    #[msg("No staked $ASDF")]
+    NoStake,
+
+    // Burn ratio errors
+    #[msg("Burn ratio must be 5000-10000 bps")]
+    InvalidBurnRatio,
+
+    #[msg("Community treasury required for partial burns")]
+    InvalidCommunityTreasury,
+
+    // Holder reflection errors
+    #[msg("Reflection share cannot exceed the burn share")]
+    InvalidReflectionRatio,
+
+    #[msg("Rewards vault required for reflection")]
+    InvalidRewardsVault,
+
+    #[msg("Snapshot allocates more than the accrued reflection")]
+    ReflectionSnapshotExceedsAccrued,
+
+    #[msg("Reflection snapshot fully claimed or not posted")]
+    ReflectionSnapshotExhausted,
+
+    // Liquidity seeding errors
+    #[msg("Liquidity ratio must be 0-2500 bps")]
+    InvalidLiquidityRatio,
+
+    #[msg("Liquidity seeding disabled for this token")]
+    LiquidityModeDisabled,
+
+    #[msg("Deposit exceeds the token's liquidity share of the cycle")]
+    LiquidityExceedsShare,
+
+    #[msg("Liquidity already provided this cycle")]
+    LiquidityAlreadyProvided,
+
+    // Dust sweeping errors
+    #[msg("No token balance to sweep")]
+    NoDustToSweep,
+
+    #[msg("Token balance exceeds the dust threshold")]
+    DustAboveThreshold,
+
+    // Mint screening errors
+    #[msg("Invalid mint account")]
+    InvalidMint,
+
+    #[msg("Mint has dangerous Token2022 extensions")]
+    DangerousMintExtension,
+
+    #[msg("Token account is not the active DAT account for this mint")]
+    InvalidTokenAccount,
+
+    // DCA errors
+    #[msg("No active DCA plan")]
+    DcaNotActive,
+
+    #[msg("DCA plan already in progress")]
+    DcaAlreadyActive,
+
+    #[msg("DCA tranche spacing not elapsed")]
+    DcaTrancheTooSoon,
+
+    // Commit-reveal buy errors
+    #[msg("No open buy commitment")]
+    NoBuyCommitment,
+
+    #[msg("Buy reveal must land after the commit slot")]
+    BuyRevealTooEarly,
+
+    #[msg("Buy reveal window has passed")]
+    BuyRevealExpired,
+
+    #[msg("Revealed amount and salt do not match the commitment")]
+    BuyCommitmentMismatch,
+
+    // Cycle state machine errors
+    #[msg("Operation not allowed in the current cycle phase")]
+    InvalidCyclePhase,
+
+    #[msg("Cycle has not been stuck long enough to abort")]
+    CycleNotStuck,
+
+    #[msg("Another token's cycle holds the cycle lock")]
+    CycleLocked,
+
+    #[msg("Cycle lock is not held by this token")]
+    CycleLockNotHeld,
+
+    #[msg("Insufficient compute units remaining for the next phase")]
+    InsufficientComputeRemaining,
+
+    // Fee attestation errors
+    #[msg("Invalid Merkle proof")]
+    InvalidMerkleProof,
+
+    #[msg("Challenge window has closed")]
+    ChallengeWindowClosed,
+
+    #[msg("Challenge window still open")]
+    ChallengeWindowOpen,
+
+    #[msg("Proof does not demonstrate fraud")]
+    NoFraudProven,
+
+    #[msg("Invalid validator set configuration")]
+    InvalidValidatorSet,
+
+    #[msg("Proposal predates the current governance epoch")]
+    StaleGovernanceProposal,
+
+    #[msg("Program upgrade already acknowledged")]
+    UpgradeAlreadyAcknowledged,
+
+    #[msg("Invalid fee recipient list")]
+    InvalidFeeRecipients,
+
+    #[msg("protocol_fee_recipient is not this cycle's rotation entry")]
+    InvalidFeeRecipient,
+
+    #[msg("Invalid PumpFun global config account")]
+    InvalidPumpGlobalConfig,
+
+    #[msg("creator_vault is not the PumpFun creator vault of dat_authority")]
+    InvalidCreatorVault,
+
+    #[msg("Bonding curve has not migrated to PumpSwap")]
+    CurveNotMigrated,
+
+    #[msg("dat_authority is not the bonding curve creator")]
+    NotCurveCreator,
+
+    #[msg("PumpSwap pool coin_creator is not dat_authority")]
+    CoinCreatorMismatch,
+
+    #[msg("Bonding curve already migrated to PumpSwap")]
+    CurveAlreadyMigrated,
+
+    #[msg("Bonding curve creator was not handed to dat_authority")]
+    CreatorHandoffFailed,
+
+    #[msg("Invalid Metaplex metadata account")]
+    InvalidMetadata,
+
+    #[msg("Metadata is immutable or its update authority is not dat_authority")]
+    MetadataNotUpdatable,
+
+    #[msg("initial_buy_lamports is set - pass the dev-buy accounts")]
+    DevBuyAccountsMissing,
+
+    #[msg("Only the original creator of an onboarded token can offboard it")]
+    NotOriginalCreator,
+
+    #[msg("Offboarding already requested")]
+    OffboardAlreadyRequested,
+
+    #[msg("Offboarding not requested")]
+    OffboardNotRequested,
+
+    #[msg("Offboarding timelock not elapsed")]
+    OffboardTimelockActive,
+
+    #[msg("Pool base mint does not match base_mint")]
+    PoolBaseMintMismatch,
+
+    #[msg("Pool quote mint does not match quote_mint")]
+    PoolQuoteMintMismatch,
+
+    #[msg("Pool base vault does not match pool_base_token_account")]
+    PoolBaseVaultMismatch,
+
+    #[msg("Pool quote vault does not match pool_quote_token_account")]
+    PoolQuoteVaultMismatch,
+
+    #[msg("Malformed ed25519 attestation instruction")]
+    InvalidAttestation,
+
+    #[msg("Not enough attestor signatures")]
+    InsufficientAttestations,
+
+    #[msg("Validator bond below minimum or smaller than the claimed fees")]
+    InsufficientBond,
+
+    #[msg("Validator bond has unresolved attestations")]
+    BondLocked,
+
+    #[msg("Bond account does not match the attestation")]
+    InvalidBondAccount,
+
+    #[msg("Attestation is bonded - dispute it with slash_validator")]
+    BondedAttestation,
+
+    #[msg("Attestation is not bonded - dispute it with challenge_attestation")]
+    UnbondedAttestation,
+
+    // Bug bounty errors
+    #[msg("No pending bounty payout")]
+    NoPendingBounty,
+
+    #[msg("Payout does not match the pending bounty proposal")]
+    BountyMismatch,
+
+    #[msg("Insufficient funds in bounty vault")]
+    InsufficientBountyFunds,
+
+    // Emergency withdrawal errors
+    #[msg("Emergency withdrawal guardian is not set")]
+    GuardianNotSet,
+
+    #[msg("No pending emergency withdrawal")]
+    NoPendingEmergencyWithdraw,
+
+    #[msg("An emergency withdrawal is already pending")]
+    EmergencyWithdrawPending,
+
+    #[msg("Emergency withdrawal timelock has not elapsed")]
+    EmergencyWithdrawTimelocked,
+
+    #[msg("Emergency withdrawals require the emergency pause")]
+    EmergencyWithdrawRequiresPause,
+
+    // Treasury reserve errors
+    #[msg("Reserve ratio exceeds MAX_RESERVE_BPS")]
+    InvalidReserveRatio,
+
+    #[msg("reserve_bps is set - pass the reserve vault")]
+    ReserveVaultMissing,
+
+    #[msg("No pending reserve deployment")]
+    NoPendingReserveDeployment,
+
+    #[msg("A reserve deployment is already pending")]
+    ReserveDeploymentPending,
+
+    #[msg("Deployment does not match the pending reserve proposal")]
+    ReserveDeploymentMismatch,
+
+    #[msg("Reserve deployment timelock has not elapsed")]
+    ReserveDeploymentTimelocked,
+
+    #[msg("Insufficient funds in reserve vault")]
+    InsufficientReserveFunds,
+
+    // Insurance fund errors
+    #[msg("Insurance ratio exceeds MAX_INSURANCE_BPS")]
+    InvalidInsuranceRatio,
+
+    #[msg("insurance_bps is set - pass the insurance fund")]
+    InsuranceFundMissing,
+
+    #[msg("No pending insurance payout")]
+    NoPendingInsurancePayout,
+
+    #[msg("An insurance payout is already pending")]
+    InsurancePayoutPending,
+
+    #[msg("Payout does not match the pending insurance proposal")]
+    InsurancePayoutMismatch,
+
+    #[msg("Insurance payout timelock has not elapsed")]
+    InsurancePayoutTimelocked,
+
+    #[msg("Insufficient funds in insurance fund")]
+    InsufficientInsuranceFunds,
+
+    // Automatic resume errors
+    #[msg("DAT is not auto-paused or auto-resume is disabled")]
+    AutoResumeUnavailable,
+
+    #[msg("Auto-resume cool-off has not elapsed")]
+    AutoResumeCoolOff,
+
+    // Account closing errors
+    #[msg("Token still has pending fees")]
+    PendingFeesNotZero,
+
+    #[msg("Settled amount exceeds the token's pending fees")]
+    SettlementExceedsPending,
+
+    // Config portability errors
+    #[msg("Config blob could not be decoded")]
+    InvalidConfigBlob,
+
+    #[msg("Unsupported config blob version")]
+    ConfigVersionMismatch,
+
+    #[msg("Config import requires the DAT to be emergency paused")]
+    ImportRequiresPause,
+
+    #[msg("Config import requires an otherwise empty change queue")]
+    ImportQueueNotEmpty,
+
+    #[msg("Config blob does not match the queued import")]
+    ImportHashMismatch,
+
+    #[msg("Token still has bought tokens awaiting burn")]
+    PendingBurnNotZero,
+
+    #[msg("Root token accounts cannot be closed")]
+    CannotCloseRootToken,
+
+    // Stats snapshot errors
+    #[msg("Snapshot day is in the future")]
+    InvalidSnapshotDay,
+
+    // Global stats roll-up errors
+    #[msg("No global stats roll-up in progress")]
+    RollupNotStarted,
+
+    #[msg("TokenStats must be rolled up in strictly ascending mint order")]
+    RollupOutOfOrder,
+
+    // Daily stats errors
+    #[msg("DailyStats bucket is not for the current day")]
+    DailyStatsDayMismatch,
+
+    // Accounting invariant errors (verify_invariants)
+    #[msg("TokenStats must be passed in strictly ascending mint order")]
+    InvariantTokensOutOfOrder,
+
+    #[msg("Invariant broken: dat_authority + creator vault cannot cover pending fees")]
+    InvariantPendingFeesUnbacked,
+
+    #[msg("Invariant broken: total SOL used exceeds total SOL collected")]
+    InvariantSolUsedExceedsCollected,
+
+    #[msg("Invariant broken: partner payouts exceed SOL collected")]
+    InvariantPartnerPaidExceedsCollected,
+
+    #[msg("Invariant broken: dust burned exceeds total burned")]
+    InvariantDustExceedsBurned,
+
+    #[msg("Invariant broken: last cycle figures exceed lifetime totals")]
+    InvariantLastCycleExceedsTotals,
+
+    // Partner revenue share errors
+    #[msg("Partner share exceeds MAX_PARTNER_SHARE_BPS")]
+    PartnerShareTooHigh,
+
+    #[msg("No pending partner change")]
+    NoPendingPartnerChange,
+
+    #[msg("Partner and partner_stats accounts required for this token")]
+    PartnerAccountsMissing,
+
+    // Token metadata errors
+    #[msg("Info URI exceeds 96 bytes")]
+    InfoUriTooLong,
+
+    // Category pause errors
+    #[msg("Token is paused")]
+    TokenPaused,
+
+    #[msg("Token does not belong to the requested category")]
+    CategoryMismatch,
+
+    // Oracle errors
+    #[msg("Invalid price oracle account")]
+    InvalidOracle,
+
+    #[msg("Oracle price is stale")]
+    OraclePriceStale,
+
+    // Randomized cycle timing errors
+    #[msg("Invalid Switchboard randomness account")]
+    InvalidRandomnessAccount,
+
+    #[msg("Randomness must be committed in the previous slot and not yet revealed")]
+    RandomnessNotFresh,
+
+    #[msg("Randomness not revealed yet")]
+    RandomnessNotRevealed,
+
+    #[msg("Randomness account does not match the pending request")]
+    RandomnessMismatch,
+
+    #[msg("Cycle window request pending or window not closed yet")]
+    CycleWindowActive,
+
+    #[msg("No pending cycle randomness request")]
+    NoCycleRandomnessRequest,
+
+    #[msg("randomized_timing is on - pass the cycle window")]
+    CycleWindowMissing,
+
+    #[msg("Outside the settled cycle window")]
+    OutsideCycleWindow,
+
+    // Batch collection errors
+    #[msg("Batch collection unavailable: randomized_timing is on or the token has a listing partner")]
+    BatchCollectUnsupported,
+
+    #[msg("run_ecosystem_cycle needs randomized_timing off and secondaries with 100% burn, no reflection and no partner")]
+    EcosystemCycleUnsupported,
+
+    // Keeper errors
+    #[msg("Keeper signer does not match keeper_info")]
+    KeeperMismatch,
+
+    #[msg("Keeper already deactivated")]
+    KeeperInactive,
+
+    // Jito tip errors
+    #[msg("Jito tip exceeds max_jito_tip_lamports")]
+    JitoTipAboveMax,
+
+    #[msg("Tip account is not a Jito tip account")]
+    InvalidJitoTipAccount,
+
+    #[msg("A Jito tip was requested - pass the tip account")]
+    JitoTipAccountMissing,
+
+    // Address Lookup Table errors
+    #[msg("Lookup table is not derived from dat_authority and the given slot")]
+    InvalidLookupTable,
+
+    #[msg("Lookup table is not the cycle LUT recorded in DATState")]
+    CycleLutMismatch,
+
+    // Monitor key errors
+    #[msg("Monitor key suspended - quota exceeded")]
+    MonitorKeySuspended,
+
     // External App Integration errors
     #[msg("Deposit below minimum threshold")]
     DepositBelowMinimum,
@@ -116,6 +614,87 @@ pub enum ErrorCode {
     #[msg("Rebate pool insufficient funds")]
     RebatePoolInsufficient,
 
+    #[msg("Rebate share or threshold out of bounds")]
+    InvalidRebateParams,
+
+    #[msg("No root token price observed yet - SOL deposits cannot be valued")]
+    NoPriceObservation,
+
+    #[msg("No pending rebate params change")]
+    NoPendingRebateParams,
+
+    #[msg("A rebate draw is already pending")]
+    DrawPending,
+
+    #[msg("No rebate draw pending")]
+    NoDrawPending,
+
+    #[msg("Draw target slot not yet produced")]
+    DrawNotReady,
+
+    #[msg("Draw target slot hash no longer available - request a new draw")]
+    DrawExpired,
+
+    #[msg("Draw candidates do not match the committed set")]
+    DrawCandidatesMismatch,
+
+    #[msg("Draw candidate stats changed after the draw was requested")]
+    DrawCandidateChanged,
+
+    #[msg("No draw candidate meets the rebate threshold")]
+    NoEligibleCandidates,
+
+    #[msg("Rebate epoch is closed")]
+    RebateEpochClosed,
+
+    #[msg("Rebate epoch is still open")]
+    RebateEpochNotClosed,
+
+    #[msg("Rebate epoch duration not elapsed")]
+    RebateEpochActive,
+
+    #[msg("No contribution to claim for this epoch")]
+    NoEpochContribution,
+
+    #[msg("Unclaimed rebate from a closed epoch - pass previous_rebate_epoch and user_ata")]
+    UnclaimedEpochRebate,
+
     #[msg("User stats not found")]
     UserStatsNotFound,
+
+    #[msg("Onboarding bonus recipient must be the user's $ASDF account")]
+    InvalidBonusAccount,
+
+    #[msg("Deposit target is not an initialized ecosystem token")]
+    InvalidDepositTarget,
+
+    #[msg("Referrer already set")]
+    ReferrerAlreadySet,
+
+    #[msg("Invalid referrer")]
+    InvalidReferrer,
+
+    #[msg("Referred user's deposit requires the referrer's ReferralStats")]
+    ReferralAccountMissing,
+
+    #[msg("No referral rewards to claim")]
+    NoReferralRewards,
+
+    #[msg("Use deposit_fee_asdf for $ASDF deposits")]
+    UnsupportedDepositMint,
+
+    #[msg("No escrowed token deposit")]
+    NoTokenDeposit,
+
+    #[msg("Token deposit refund delay not elapsed")]
+    TokenDepositLocked,
+
+    #[msg("Queued change delay has not elapsed")]
+    ChangeTimelockActive,
+
+    #[msg("Account the queued change applies to was not supplied")]
+    ChangeTargetMissing,
+
+    #[msg("Supplied account is not the queued change's subject")]
+    ChangeTargetMismatch,
 }
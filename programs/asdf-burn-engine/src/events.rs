@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use crate::migrations::AccountKind;
+use crate::state::CyclePhase;
 
 // ══════════════════════════════════════════════════════════════════════════════
 // INITIALIZATION EVENTS
@@ -19,6 +21,59 @@ pub struct TokenStatsInitialized {
     pub timestamp: i64,
 }
 
+/// Emitted when a token's mint carries risky authorities or Token2022 extensions
+#[event]
+pub struct MintRiskFlagged {
+    pub mint: Pubkey,
+    pub risk_flags: u8,
+    pub timestamp: i64,
+}
+
+/// Emitted when the account that initialized a token's stats is refunded at its first burn
+#[event]
+pub struct StatsPayerRefunded {
+    pub mint: Pubkey,
+    pub payer: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a page is appended to the token registry
+#[event]
+pub struct TokenRegistryPageCreated {
+    pub page: u16,
+    pub payer: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a mint is listed in or removed from the token registry
+#[event]
+pub struct TokenRegistryUpdated {
+    pub mint: Pubkey,
+    pub page: u16,
+    pub registered: bool,
+    pub token_count: u32,
+    pub timestamp: i64,
+}
+
+/// Emitted when a dead or mistaken token's stats account is closed
+#[event]
+pub struct TokenStatsClosed {
+    pub mint: Pubkey,
+    pub rent_destination: Pubkey,
+    pub lamports_recovered: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a token's validator state is closed
+#[event]
+pub struct ValidatorStateClosed {
+    pub mint: Pubkey,
+    pub rent_destination: Pubkey,
+    pub lamports_recovered: u64,
+    pub timestamp: i64,
+}
+
 /// Emitted when a validator is initialized for trustless fee tracking
 #[event]
 pub struct ValidatorInitialized {
@@ -33,8 +88,12 @@ pub struct ValidatorInitialized {
 // ══════════════════════════════════════════════════════════════════════════════
 
 /// Emitted when a buyback cycle completes successfully
+/// cycle_seq is the mint's CycleState.sequence; venue is where the cycle's buy ran
 #[event]
 pub struct CycleCompleted {
+    pub mint: Pubkey,
+    pub venue: u8,
+    pub cycle_seq: u64,
     pub cycle_number: u32,
     pub tokens_burned: u64,
     pub sol_used: u64,
@@ -43,216 +102,1640 @@ pub struct CycleCompleted {
     pub timestamp: i64,
 }
 
+/// Standardized cross-protocol burn event (community burn-aggregator schema)
+/// Field names and order are part of the schema; do not change them
+/// Emitted by burn_and_update only with the `standard-burn-events` feature
+#[event]
+pub struct TokenBurn {
+    /// Mint whose supply was reduced
+    pub mint: Pubkey,
+    /// Raw token units burned
+    pub amount: u64,
+    /// Burn authority (dat_authority PDA)
+    pub authority: Pubkey,
+    /// Protocol account the burn is attributed to (TokenStats PDA)
+    pub reference: Pubkey,
+}
+
 /// Emitted when a cycle fails
+/// mint, venue and cycle_seq come from the failing mint's CycleState (default / 0 when not given)
 #[event]
 pub struct CycleFailed {
+    pub mint: Pubkey,
+    pub venue: u8,
+    pub cycle_seq: u64,
     pub failed_count: u32,
     pub consecutive_failures: u8,
     pub error_code: u32,
     pub timestamp: i64,
 }
 
-/// Emitted when a buy is executed
+/// Emitted by every buy path (bonding curve, secondary, DCA tranche, PumpSwap AMM)
+/// sequence is DATState.event_sequence, strictly increasing across buys
 #[event]
 pub struct BuyExecuted {
-    pub tokens_bought: u64,
+    pub mint: Pubkey,
+    pub venue: u8,
     pub sol_spent: u64,
+    pub tokens_received: u64,
+    pub sequence: u64,
     pub timestamp: i64,
 }
 
-// ══════════════════════════════════════════════════════════════════════════════
-// STATUS EVENTS
-// ══════════════════════════════════════════════════════════════════════════════
+impl BuyExecuted {
+    /// Event from the payer's SOL / WSOL and the DAT token balances around the buy CPI
+    /// sol_spent is what actually left the payer, not the max_sol_cost slippage cap
+    pub fn observed(mint: Pubkey, venue: u8, sol: (u64, u64), tokens: (u64, u64), sequence: u64, timestamp: i64) -> Self {
+        Self {
+            mint,
+            venue,
+            sol_spent: sol.0.saturating_sub(sol.1),
+            tokens_received: tokens.1.saturating_sub(tokens.0),
+            sequence,
+            timestamp,
+        }
+    }
+}
 
-/// Emitted when DAT status changes (active/paused)
+/// Emitted when select_best_venue routes a buy to the best-quoting venue
 #[event]
-pub struct StatusChanged {
-    pub is_active: bool,
-    pub emergency_pause: bool,
+pub struct VenueSelected {
+    pub mint: Pubkey,
+    pub sol_in: u64,
+    pub venue: u8,
+    pub venue_account: Pubkey,
+    pub tokens_out: u64,
+    pub venues_quoted: u8,
     pub timestamp: i64,
 }
 
-/// Emitted for emergency actions (pause/resume)
+/// Emitted when execute_buy records a shadow-mode math comparison
 #[event]
-pub struct EmergencyAction {
-    pub action: String,
-    pub admin: Pubkey,
+pub struct ShadowMathCompared {
+    pub mint: Pubkey,
+    pub old_sol_cost: u64,
+    pub old_tokens: u64,
+    pub new_sol_cost: u64,
+    pub new_tokens: u64,
+    pub actual_tokens: u64,
+    pub token_delta: i64,
+    pub sol_delta: i64,
+    pub samples: u64,
     pub timestamp: i64,
 }
 
-// ══════════════════════════════════════════════════════════════════════════════
-// ADMIN EVENTS
-// ══════════════════════════════════════════════════════════════════════════════
-
-/// Emitted when admin transfer is proposed (two-step transfer)
+/// Emitted when shadow-mode math is enabled or disabled for a mint
 #[event]
-pub struct AdminTransferProposed {
-    pub current_admin: Pubkey,
-    pub proposed_admin: Pubkey,
+pub struct ShadowMathConfigured {
+    pub mint: Pubkey,
+    pub enabled: bool,
+    pub reset: bool,
     pub timestamp: i64,
 }
 
-/// Emitted when admin transfer is completed
+/// Emitted when a DCA plan is started
 #[event]
-pub struct AdminTransferred {
-    pub old_admin: Pubkey,
-    pub new_admin: Pubkey,
+pub struct DcaStarted {
+    pub mint: Pubkey,
+    pub total_lamports: u64,
+    pub tranche_count: u8,
+    pub tranche_lamports: u64,
+    pub min_slot_spacing: u64,
     pub timestamp: i64,
 }
 
-/// Emitted when admin transfer is cancelled
+/// Emitted when a DCA tranche is bought
 #[event]
-pub struct AdminTransferCancelled {
-    pub admin: Pubkey,
-    pub cancelled_new_admin: Pubkey,
+pub struct DcaTrancheExecuted {
+    pub mint: Pubkey,
+    pub tranche_index: u8,
+    pub tranche_lamports: u64,
+    pub remaining_lamports: u64,
+    pub slot: u64,
     pub timestamp: i64,
 }
 
-// ══════════════════════════════════════════════════════════════════════════════
-// TOKEN EVENTS
-// ══════════════════════════════════════════════════════════════════════════════
+/// Emitted when a DCA plan is cancelled before completion
+#[event]
+pub struct DcaCancelled {
+    pub mint: Pubkey,
+    pub tranches_executed: u8,
+    pub remaining_lamports: u64,
+    pub timestamp: i64,
+}
 
-/// Emitted when a new token is created via PumpFun
+/// Emitted when a sealed buy size is committed
 #[event]
-pub struct TokenCreated {
+pub struct BuyCommitted {
     pub mint: Pubkey,
-    pub bonding_curve: Pubkey,
-    pub creator: Pubkey,
-    pub name: String,
-    pub symbol: String,
+    pub commitment: [u8; 32],
+    pub committed_slot: u64,
+    pub reveal_deadline: u64,
     pub timestamp: i64,
 }
 
-/// Emitted when root token is set/changed
+/// Emitted when Switchboard randomness is bound to a mint's next cycle window
 #[event]
-pub struct RootTokenSet {
-    pub root_mint: Pubkey,
-    pub fee_split_bps: u16,
+pub struct CycleRandomnessRequested {
+    pub mint: Pubkey,
+    pub randomness_account: Pubkey,
+    pub seed_slot: u64,
     pub timestamp: i64,
 }
 
-/// Emitted when ASDF mint is updated (TESTING mode only)
+/// Emitted when a mint's cycle window is fixed from revealed randomness
 #[event]
-pub struct AsdfMintUpdated {
-    pub old_mint: Pubkey,
-    pub new_mint: Pubkey,
+pub struct CycleWindowSettled {
+    pub mint: Pubkey,
+    pub randomness: [u8; 32],
+    pub window_start: i64,
+    pub window_end: i64,
     pub timestamp: i64,
 }
 
-// ══════════════════════════════════════════════════════════════════════════════
-// FEE EVENTS
-// ══════════════════════════════════════════════════════════════════════════════
+/// Emitted when a cranker registers as a keeper
+#[event]
+pub struct KeeperRegistered {
+    pub keeper: Pubkey,
+    pub timestamp: i64,
+}
 
-/// Emitted when fee split ratio is updated
+/// Emitted when a keeper stops earning rewards
 #[event]
-pub struct FeeSplitUpdated {
-    pub old_bps: u16,
-    pub new_bps: u16,
+pub struct KeeperDeactivated {
+    pub keeper: Pubkey,
+    pub deactivated_by: Pubkey,
+    pub successful_cycles: u64,
+    pub total_rewards: u64,
     pub timestamp: i64,
 }
 
-/// Emitted when fees are redirected from secondary to root token
+/// Emitted when a registered keeper completes a cycle
 #[event]
-pub struct FeesRedirectedToRoot {
-    pub from_token: Pubkey,
-    pub to_root: Pubkey,
+pub struct KeeperRewarded {
+    pub keeper: Pubkey,
+    pub mint: Pubkey,
+    pub successful_cycles: u64,
+    pub reward_bps: u16,
+    /// Lamports paid (0 when skipped: dat_authority short or system_program omitted)
     pub amount: u64,
     pub timestamp: i64,
 }
 
-/// Emitted when root treasury collects accumulated fees
+/// Emitted when a buy instruction pays a Jito tip from dat_authority
 #[event]
-pub struct RootTreasuryCollected {
-    pub root_mint: Pubkey,
+pub struct JitoTipPaid {
+    pub mint: Pubkey,
+    pub tip_account: Pubkey,
     pub amount: u64,
+    pub cycle_seq: u64,
     pub timestamp: i64,
 }
 
-/// Emitted when pending fees are updated by daemon
+/// Emitted when create_cycle_lut creates the cycle Address Lookup Table
 #[event]
-pub struct PendingFeesUpdated {
+pub struct CycleLutCreated {
+    pub lookup_table: Pubkey,
+    pub previous: Pubkey,
+    pub recent_slot: u64,
+    pub addresses: u8,
+    pub timestamp: i64,
+}
+
+/// Emitted when extend_cycle_lut appends addresses to the cycle Address Lookup Table
+#[event]
+pub struct CycleLutExtended {
+    pub lookup_table: Pubkey,
+    pub added: u8,
+    pub timestamp: i64,
+}
+
+/// Emitted when migrate_account upgrades an account to its current layout
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub kind: AccountKind,
+    /// Size before the migration (equal to the current size when nothing was done)
+    pub previous_size: u32,
+    pub version: u8,
+    pub timestamp: i64,
+}
+
+/// Emitted when a committed buy is revealed and executed
+#[event]
+pub struct BuyRevealed {
     pub mint: Pubkey,
     pub amount: u64,
-    pub total_pending: u64,
+    pub committed_slot: u64,
+    pub slot: u64,
     pub timestamp: i64,
 }
 
-/// Emitted when AMM fees are collected (post-migration tokens)
+/// Emitted when a mint's cycle state machine is created
 #[event]
-pub struct AmmFeesCollected {
+pub struct CycleStateInitialized {
+    pub mint: Pubkey,
+    pub cycle_state: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin audit log is created
+#[event]
+pub struct AdminLogInitialized {
+    pub admin_log: Pubkey,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a mint's cycle history ring buffer is created
+#[event]
+pub struct CycleHistoryInitialized {
+    pub mint: Pubkey,
+    pub cycle_history: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a mint's daily stats bucket is created
+#[event]
+pub struct DailyStatsInitialized {
+    pub mint: Pubkey,
+    pub day: i64,
+    pub daily_stats: Pubkey,
+}
+
+/// Emitted when abort_cycle resets a stuck cycle to Idle
+#[event]
+pub struct CycleAborted {
+    pub mint: Pubkey,
+    pub sequence: u64,
+    pub aborted_phase: CyclePhase,
+    pub stuck_seconds: i64,
+    pub aborted_by: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin force-releases a stuck cycle lock
+#[event]
+pub struct CycleLockForceReleased {
     pub mint: Pubkey,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when recover_wsol unwraps orphaned WSOL back into dat_authority
+#[event]
+pub struct WsolRecovered {
+    pub wsol_account: Pubkey,
     pub wsol_amount: u64,
+    pub lamports_recovered: u64,
+    pub recovered_by: Pubkey,
     pub timestamp: i64,
 }
 
-// ══════════════════════════════════════════════════════════════════════════════
-// VALIDATOR EVENTS
-// ══════════════════════════════════════════════════════════════════════════════
+/// Emitted when a burn is valued in USD via the SOL/USD oracle
+#[event]
+pub struct BurnValued {
+    pub mint: Pubkey,
+    pub tokens_burned: u64,
+    pub sol_value: u64,
+    pub usd_value: u64,
+    pub total_usd_value_burned: u64,
+    pub oracle_price: i64,
+    pub oracle_exponent: i32,
+    pub timestamp: i64,
+}
 
-/// Emitted when validator slot is reset (admin only)
+/// Emitted when the non-burned share of a cycle is sent to the community treasury
 #[event]
-pub struct ValidatorSlotReset {
+pub struct CommunityTreasuryFunded {
     pub mint: Pubkey,
-    pub old_slot: u64,
-    pub new_slot: u64,
+    pub amount: u64,
+    pub burn_bps: u16,
+    pub total_sent: u64,
     pub timestamp: i64,
 }
 
-/// Emitted when validator slot is synced (permissionless, stale validators only)
+/// Emitted when the reflected share of a cycle is sent to the rewards vault
 #[event]
-pub struct ValidatorSlotSynced {
+pub struct ReflectionFunded {
     pub mint: Pubkey,
-    pub old_slot: u64,
-    pub new_slot: u64,
-    pub slot_delta: u64,
+    pub amount: u64,
+    pub reflection_bps: u16,
+    pub total_reflected: u64,
     pub timestamp: i64,
 }
 
-/// Emitted when validated fees are registered
+/// Emitted when a holder reflection snapshot starts a new epoch
 #[event]
-pub struct ValidatedFeesRegistered {
+pub struct ReflectionSnapshotPosted {
     pub mint: Pubkey,
-    pub fee_amount: u64,
-    pub end_slot: u64,
-    pub tx_count: u32,
-    pub total_pending: u64,
+    pub epoch: u64,
+    pub merkle_root: [u8; 32],
+    pub amount: u64,
+    pub unallocated: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a holder claims their reflection for an epoch
+#[event]
+pub struct ReflectionClaimed {
+    pub mint: Pubkey,
+    pub epoch: u64,
+    pub holder: Pubkey,
+    pub amount: u64,
     pub timestamp: i64,
 }
 
 // ══════════════════════════════════════════════════════════════════════════════
-// EXTERNAL APP INTEGRATION EVENTS
+// STATUS EVENTS
 // ══════════════════════════════════════════════════════════════════════════════
 
-/// Emitted when rebate pool is initialized
+/// Emitted when DAT status changes (active/paused)
 #[event]
-pub struct RebatePoolInitialized {
-    pub rebate_pool: Pubkey,
-    pub rebate_pool_ata: Pubkey,
+pub struct StatusChanged {
+    pub is_active: bool,
+    pub emergency_pause: bool,
     pub timestamp: i64,
 }
 
-/// Emitted when user stats are initialized
+/// Emitted when record_failure pauses a single token after repeated failures
 #[event]
-pub struct UserStatsInitialized {
-    pub user: Pubkey,
-    pub user_stats: Pubkey,
+pub struct TokenAutoPaused {
+    pub mint: Pubkey,
+    pub consecutive_failures: u8,
     pub timestamp: i64,
 }
 
-/// Emitted when $ASDF fee is deposited via external app
+/// Emitted when failures of several tokens in one window trip the global pause
 #[event]
-pub struct FeeAsdfDeposited {
-    pub user: Pubkey,
-    pub amount: u64,
-    pub burn_amount: u64,
-    pub rebate_pool_amount: u64,
-    pub pending_contribution: u64,
+pub struct FailureEscalated {
+    pub failing_tokens: u8,
+    pub window_start: i64,
     pub timestamp: i64,
 }
 
-/// Emitted when user rebate is processed
+/// Emitted when the admin lifts a single token's pause
 #[event]
-pub struct UserRebateProcessed {
+pub struct TokenResumed {
+    pub mint: Pubkey,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when try_resume lifts an automatic pause after its cool-off
+#[event]
+pub struct AutoResumed {
+    pub paused_at: i64,
+    pub pool_sol_reserves: u64,
+    pub caller: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin changes the consecutive-failure auto-pause threshold
+#[event]
+pub struct FailureThresholdUpdated {
+    pub old_threshold: u8,
+    pub new_threshold: u8,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin sets the automatic-pause cool-off (0 = disabled)
+#[event]
+pub struct AutoResumeAfterUpdated {
+    pub old_auto_resume_after: i64,
+    pub new_auto_resume_after: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the parameter change queue is created
+#[event]
+pub struct ChangeQueueInitialized {
+    pub change_queue: Pubkey,
+    pub guardian: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a parameter change is queued (param = ADMIN_OP_* tag)
+/// subject = mint for per-token changes, default otherwise
+#[event]
+pub struct ParameterChangeProposed {
+    pub id: u64,
+    pub param: u8,
+    pub subject: Pubkey,
+    pub value: u64,
+    pub executable_at: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a queued parameter change is applied
+#[event]
+pub struct ParameterChangeExecuted {
+    pub id: u64,
+    pub param: u8,
+    pub subject: Pubkey,
+    pub old_value: u64,
+    pub new_value: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a queued parameter change is cancelled by the admin or vetoed by the guardian
+#[event]
+pub struct ParameterChangeCancelled {
+    pub id: u64,
+    pub param: u8,
+    pub cancelled_by: Pubkey,
+    pub vetoed: bool,
+    pub timestamp: i64,
+}
+
+/// Emitted when community governance is configured
+#[event]
+pub struct GovernanceInitialized {
+    pub quorum: u64,
+    pub voting_period: i64,
+    pub execution_delay: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a community fee split proposal is created
+#[event]
+pub struct FeeSplitProposalCreated {
+    pub id: u64,
+    pub proposal: Pubkey,
+    pub proposer: Pubkey,
+    pub new_fee_split_bps: u16,
+    pub voting_ends_at: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a voter escrows $ASDF on a proposal
+#[event]
+pub struct VoteCast {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub support: bool,
+    pub weight: u64,
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a voter's escrowed $ASDF is returned after voting
+#[event]
+pub struct VoteReclaimed {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a user stakes $ASDF (stake)
+#[event]
+pub struct Staked {
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub total_staked: u64,
+    pub locked_until: i64,
+    pub lock_duration: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a user re-locks their stake (extend_stake)
+#[event]
+pub struct StakeExtended {
+    pub owner: Pubkey,
+    pub locked_until: i64,
+    pub lock_duration: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a user withdraws an unlocked stake (unstake)
+#[event]
+pub struct Unstaked {
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a passed fee split proposal is applied
+#[event]
+pub struct FeeSplitProposalExecuted {
+    pub id: u64,
+    pub proposal: Pubkey,
+    pub old_bps: u16,
+    pub new_bps: u16,
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the guardian hands the veto role to a new account
+#[event]
+pub struct GuardianTransferred {
+    pub old_guardian: Pubkey,
+    pub new_guardian: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a donor restores dat_authority to its rent floor buffer
+#[event]
+pub struct AuthorityToppedUp {
+    pub donor: Pubkey,
+    pub amount: u64,
+    pub balance: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when acknowledge_upgrade opens a new governance epoch
+#[event]
+pub struct GovernanceEpochAdvanced {
+    pub epoch: u32,
+    pub program_slot: u64,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted for emergency actions (pause/resume)
+#[event]
+pub struct EmergencyAction {
+    pub action: String,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// ADMIN EVENTS
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// Emitted when admin transfer is proposed (two-step transfer)
+#[event]
+pub struct AdminTransferProposed {
+    pub current_admin: Pubkey,
+    pub proposed_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when admin transfer is completed
+#[event]
+pub struct AdminTransferred {
+    pub old_admin: Pubkey,
+    pub new_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when admin transfer is cancelled
+#[event]
+pub struct AdminTransferCancelled {
+    pub admin: Pubkey,
+    pub cancelled_new_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the root treasury funds the bug bounty vault
+#[event]
+pub struct BountyVaultFunded {
+    pub amount: u64,
+    pub total_funded: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a bounty payout is proposed (timelock starts)
+#[event]
+pub struct BountyProposed {
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub report_hash: [u8; 32],
+    pub executable_at: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a bounty is paid out of the vault
+#[event]
+pub struct BountyPaid {
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub report_hash: [u8; 32],
+    pub payout_count: u32,
+    pub total_paid: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a pending bounty proposal is withdrawn
+#[event]
+pub struct BountyCancelled {
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub report_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+/// Emitted when the emergency withdrawal guardian is set
+#[event]
+pub struct EmergencyGuardianSet {
+    pub old_guardian: Pubkey,
+    pub new_guardian: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when an emergency withdrawal from dat_authority is proposed (timelock starts)
+#[event]
+pub struct EmergencyWithdrawProposed {
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub executable_at: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a pending emergency withdrawal is vetoed
+#[event]
+pub struct EmergencyWithdrawVetoed {
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub vetoed_by: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when an emergency withdrawal moves SOL out of dat_authority
+#[event]
+pub struct EmergencyWithdrawExecuted {
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub total_withdrawn: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when collect_fees keeps a slice of collected SOL in the reserve vault
+#[event]
+pub struct ReserveFunded {
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub total_reserved: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a reserve deployment is proposed (timelock starts)
+#[event]
+pub struct ReserveDeploymentProposed {
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub executable_at: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a pending reserve deployment is withdrawn
+#[event]
+pub struct ReserveDeploymentCancelled {
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when reserve SOL is deployed to its recipient
+#[event]
+pub struct ReserveDeployed {
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub total_deployed: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the insurance fund is created
+#[event]
+pub struct InsuranceFundInitialized {
+    pub insurance_fund: Pubkey,
+    pub guardian: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when collect_fees deposits a slice of collected SOL into the insurance fund
+#[event]
+pub struct InsuranceDeposited {
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub total_deposited: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when an insurance payout is proposed (timelock starts)
+#[event]
+pub struct InsurancePayoutProposed {
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub claim_hash: [u8; 32],
+    pub executable_at: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a pending insurance payout is vetoed
+#[event]
+pub struct InsurancePayoutVetoed {
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub claim_hash: [u8; 32],
+    pub vetoed_by: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when an insurance payout is made
+#[event]
+pub struct InsurancePayoutExecuted {
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub claim_hash: [u8; 32],
+    pub payout_count: u32,
+    pub total_paid: u64,
+    pub timestamp: i64,
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// TOKEN EVENTS
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// Emitted when a new token is created via PumpFun
+#[event]
+pub struct TokenCreated {
+    pub mint: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub creator: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub timestamp: i64,
+}
+
+/// Emitted when a token created by the DAT is bought in the same instruction
+/// Tokens stay in dat_authority's ATA and are burned by the next cycle
+#[event]
+pub struct InitialBuyExecuted {
+    pub mint: Pubkey,
+    pub sol_spent: u64,
+    pub tokens_received: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin rewrites a token's Metaplex name / symbol / uri
+#[event]
+pub struct TokenMetadataUpdated {
+    pub mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub timestamp: i64,
+}
+
+/// Emitted when root token is set/changed
+#[event]
+pub struct RootTokenSet {
+    pub root_mint: Pubkey,
+    pub fee_split_bps: u16,
+    pub timestamp: i64,
+}
+
+/// Emitted when set_root_token replaces a root (its TokenStats is no longer flagged root)
+#[event]
+pub struct RootTokenUnset {
+    pub root_mint: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin sets a secondary's root flush schedule (0 = lump transfers)
+#[event]
+pub struct RootStreamConfigured {
+    pub mint: Pubkey,
+    pub flush_every_cycles: u16,
+    pub timestamp: i64,
+}
+
+/// Emitted when a streaming secondary accrues its root share instead of transferring it
+#[event]
+pub struct RootShareAccrued {
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub accrued_lamports: u64,
+    pub cycles_accrued: u16,
+    pub cycle_seq: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when flush_to_root moves a secondary's accrued root share to the root treasury
+#[event]
+pub struct RootFlushed {
+    pub mint: Pubkey,
+    pub root_mint: Pubkey,
+    pub amount: u64,
+    pub cycles: u16,
+    pub total_flushed: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a root is added to or removed from the RootSet
+#[event]
+pub struct RootSetUpdated {
+    pub root_mint: Pubkey,
+    pub added: bool,
+    pub root_count: u8,
+    pub timestamp: i64,
+}
+
+/// Emitted when a secondary is assigned a root (default = primary root)
+#[event]
+pub struct RootAssigned {
+    pub mint: Pubkey,
+    pub root_mint: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when ASDF mint is updated (TESTING mode only)
+#[event]
+pub struct AsdfMintUpdated {
+    pub old_mint: Pubkey,
+    pub new_mint: Pubkey,
+    pub timestamp: i64,
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// FEE EVENTS
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// Emitted when fee split ratio is updated
+#[event]
+pub struct FeeSplitUpdated {
+    pub old_bps: u16,
+    pub new_bps: u16,
+    pub timestamp: i64,
+}
+
+/// Emitted when a dev fee change is proposed (timelocked)
+#[event]
+pub struct DevFeeProposed {
+    pub current_bps: u16,
+    pub proposed_bps: u16,
+    pub executable_at: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a pending dev fee change is executed
+#[event]
+pub struct DevFeeUpdated {
+    pub old_bps: u16,
+    pub new_bps: u16,
+    pub timestamp: i64,
+}
+
+/// Emitted when a dev wallet rotation is proposed (two-step rotation)
+#[event]
+pub struct DevWalletProposed {
+    pub current_wallet: Pubkey,
+    pub proposed_wallet: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the proposed dev wallet accepts the rotation
+#[event]
+pub struct DevWalletUpdated {
+    pub old_wallet: Pubkey,
+    pub new_wallet: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a mint's DAT token account is rotated after a compromise
+#[event]
+pub struct TokenCategorySet {
+    pub mint: Pubkey,
+    pub old_category: u8,
+    pub new_category: u8,
+    pub timestamp: i64,
+}
+
+/// Emitted when the daily TokenStats Merkle snapshot is committed
+#[event]
+pub struct DailySnapshotCommitted {
+    pub day: u64,
+    pub merkle_root: [u8; 32],
+    pub token_count: u32,
+    pub committer: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a global stats roll-up is finalized
+/// checksum chains stats_leaf_hash() of every TokenStats in ascending mint order
+#[event]
+pub struct GlobalStatsRolledUp {
+    pub token_count: u32,
+    pub total_burned: u64,
+    pub total_sol_collected: u64,
+    pub total_sol_used: u64,
+    pub total_buybacks: u64,
+    pub usd_value_burned: u64,
+    pub checksum: [u8; 32],
+    pub timestamp: i64,
+}
+
+/// Emitted when verify_invariants passes for a batch of TokenStats
+/// backing_lamports = dat_authority above its rent floor + creator vault balance
+#[event]
+pub struct InvariantsVerified {
+    pub tokens_checked: u32,
+    pub pending_fees_lamports: u64,
+    pub backing_lamports: u64,
+    pub total_sol_collected: u64,
+    pub total_sol_used: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a token's partner revenue share change is proposed (timelocked)
+#[event]
+pub struct PartnerShareProposed {
+    pub mint: Pubkey,
+    pub partner: Pubkey,
+    pub partner_share_bps: u16,
+    pub executable_at: i64,
+}
+
+/// Emitted when a token's partner revenue share takes effect
+#[event]
+pub struct PartnerShareUpdated {
+    pub mint: Pubkey,
+    pub old_partner: Pubkey,
+    pub new_partner: Pubkey,
+    pub old_share_bps: u16,
+    pub new_share_bps: u16,
+    pub timestamp: i64,
+}
+
+/// Emitted when collect_fees pays a listing partner
+#[event]
+pub struct PartnerPaid {
+    pub mint: Pubkey,
+    pub partner: Pubkey,
+    pub amount: u64,
+    pub total_partner_paid: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when alert thresholds are configured
+#[event]
+pub struct AlertsConfigured {
+    pub min_authority_balance: u64,
+    pub max_consecutive_failures: u8,
+    pub min_rebate_pool_balance: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted by check_alerts for each breached threshold (kind = ALERT_*)
+#[event]
+pub struct AlertRaised {
+    pub kind: u8,
+    pub value: u64,
+    pub threshold: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a token's ecosystem info URI is set
+#[event]
+pub struct TokenInfoUriSet {
+    pub mint: Pubkey,
+    pub info_uri: String,
+    pub timestamp: i64,
+}
+
+/// Emitted when every token of a category is paused or resumed
+#[event]
+pub struct CategoryPauseChanged {
+    pub category: u8,
+    pub paused: bool,
+    pub tokens_updated: u32,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PriceGuardUpdated {
+    pub old_bps: u16,
+    pub new_bps: u16,
+    pub observation_reset: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokenAccountRotated {
+    pub mint: Pubkey,
+    pub old_account: Pubkey,
+    pub new_account: Pubkey,
+    pub amount_moved: u64,
+    pub nonce: u8,
+    pub timestamp: i64,
+}
+
+/// Emitted when a token's burn ratio is updated
+#[event]
+pub struct BurnRatioUpdated {
+    pub mint: Pubkey,
+    pub old_bps: u16,
+    pub new_bps: u16,
+    pub timestamp: i64,
+}
+
+/// Emitted when a token's liquidity seeding ratio is updated
+#[event]
+pub struct LiquidityRatioUpdated {
+    pub mint: Pubkey,
+    pub old_bps: u16,
+    pub new_bps: u16,
+    pub timestamp: i64,
+}
+
+/// Emitted when a cycle share is deposited into the token's PumpSwap pool
+#[event]
+pub struct LiquidityProvided {
+    pub mint: Pubkey,
+    pub pool: Pubkey,
+    pub base_amount: u64,
+    pub quote_amount: u64,
+    pub lp_tokens: u64,
+    pub total_liquidity_sol: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a token's reflection ratio is updated
+#[event]
+pub struct ReflectionRatioUpdated {
+    pub mint: Pubkey,
+    pub old_bps: u16,
+    pub new_bps: u16,
+    pub timestamp: i64,
+}
+
+/// Emitted when a token's dust threshold is updated
+#[event]
+pub struct DustThresholdUpdated {
+    pub mint: Pubkey,
+    pub old_threshold: u64,
+    pub new_threshold: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when sweep_dust burns a residual DAT token balance
+#[event]
+pub struct DustSwept {
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub dust_burned: u64,
+    pub swept_by: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when fees are redirected from secondary to root token
+/// from_token is the secondary mint; cycle_seq is its CycleState.sequence
+#[event]
+pub struct FeesRedirectedToRoot {
+    pub from_token: Pubkey,
+    pub to_root: Pubkey,
+    pub amount: u64,
+    pub venue: u8,
+    pub cycle_seq: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when collect_fees_batch drains the creator vault for several tokens
+/// unattributed: lamports no token in the batch had pending fees for
+#[event]
+pub struct FeesBatchCollected {
+    pub tokens: u8,
+    pub total_collected: u64,
+    pub unattributed: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when run_ecosystem_cycle completes a secondary (buy and burn in the same instruction)
+#[event]
+pub struct EcosystemSecondaryCompleted {
+    pub mint: Pubkey,
+    pub allocation: u64,
+    pub sol_to_root: u64,
+    pub sol_used: u64,
+    pub tokens_burned: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when run_ecosystem_cycle has bought the root token and queued its burn
+#[event]
+pub struct EcosystemCycleExecuted {
+    pub root_mint: Pubkey,
+    pub secondaries: u8,
+    pub sol_from_vault: u64,
+    pub sol_to_root: u64,
+    pub root_buy_lamports: u64,
+    pub pending_burn_amount: u64,
+    pub cycle_seq: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when root treasury collects accumulated fees
+#[event]
+pub struct RootTreasuryCollected {
+    pub root_mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when pending fees are updated by daemon
+#[event]
+pub struct MonitorKeyRegistered {
+    pub authority: Pubkey,
+    pub daily_quota_lamports: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MonitorKeyUpdated {
+    pub authority: Pubkey,
+    pub daily_quota_lamports: u64,
+    pub is_suspended: bool,
+    pub timestamp: i64,
+}
+
+/// Emitted when a monitor key exceeds its daily quota and is suspended
+#[event]
+pub struct MonitorKeySuspended {
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub attempted_lamports: u64,
+    pub attributed_today: u64,
+    pub daily_quota_lamports: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PendingFeesUpdated {
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub total_pending: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when an ecosystem allocation is settled against a token's pending fees
+#[event]
+pub struct PendingFeesSettled {
+    pub mint: Pubkey,
+    pub settled_amount: u64,
+    pub remaining_pending: u64,
+    pub cycles_participated: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when AMM fees are collected (post-migration tokens)
+#[event]
+pub struct AmmFeesCollected {
+    pub mint: Pubkey,
+    pub wsol_amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when an externally launched token hands its bonding curve creator to dat_authority
+#[event]
+pub struct ExternalTokenOnboarded {
+    pub mint: Pubkey,
+    pub original_creator: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the original creator of an onboarded token starts the offboarding timelock
+#[event]
+pub struct OffboardRequested {
+    pub mint: Pubkey,
+    pub original_creator: Pubkey,
+    pub executable_at: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when an onboarded token leaves the DAT (per-token accounts closed)
+#[event]
+pub struct TokenOffboarded {
+    pub mint: Pubkey,
+    pub original_creator: Pubkey,
+    pub root_mint: Pubkey,
+    pub fees_settled: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when dat_authority is registered as coin_creator of a migrated PumpSwap pool
+#[event]
+pub struct AmmCreatorClaimed {
+    pub mint: Pubkey,
+    pub pool: Pubkey,
+    pub previous_creator: Pubkey,
+    pub timestamp: i64,
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// VALIDATOR EVENTS
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// Emitted when a validator's per-slot sanity caps are changed (admin only)
+#[event]
+pub struct ValidatorLimitsUpdated {
+    pub mint: Pubkey,
+    pub old_max_fee_per_slot: u64,
+    pub new_max_fee_per_slot: u64,
+    pub old_max_tx_per_slot: u32,
+    pub new_max_tx_per_slot: u32,
+    pub timestamp: i64,
+}
+
+/// Emitted when validator slot is reset (admin only)
+#[event]
+pub struct ValidatorSlotReset {
+    pub mint: Pubkey,
+    pub old_slot: u64,
+    pub new_slot: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when validator slot is synced (permissionless, stale validators only)
+#[event]
+pub struct ValidatorSlotSynced {
+    pub mint: Pubkey,
+    pub old_slot: u64,
+    pub new_slot: u64,
+    pub slot_delta: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the daemon commits a fee attestation (not yet credited)
+#[event]
+pub struct FeeAttestationPosted {
+    pub mint: Pubkey,
+    pub merkle_root: [u8; 32],
+    pub fee_amount: u64,
+    pub tx_count: u32,
+    pub start_slot: u64,
+    pub end_slot: u64,
+    pub challenge_deadline_slot: u64,
+    pub attestations: u8,
+    pub timestamp: i64,
+}
+
+/// Emitted when the M-of-N attestor set changes
+#[event]
+pub struct ValidatorSetUpdated {
+    pub attestors: Vec<Pubkey>,
+    pub threshold: u8,
+    pub timestamp: i64,
+}
+
+/// Emitted when the PumpFun protocol fee recipient rotation changes
+#[event]
+pub struct FeeRecipientsUpdated {
+    pub recipients: Vec<Pubkey>,
+    pub timestamp: i64,
+}
+
+/// Emitted when an attestation is disproven and discarded
+#[event]
+pub struct FeeAttestationChallenged {
+    pub mint: Pubkey,
+    pub end_slot: u64,
+    pub challenger: Pubkey,
+    pub fraud_reason: u8,
+    pub fee_amount_rejected: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when an operator deposits into or withdraws from its validator bond
+#[event]
+pub struct ValidatorBondChanged {
+    pub operator: Pubkey,
+    pub deposited: u64,
+    pub withdrawn: u64,
+    pub bonded_lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a bonded validator is slashed for a fraudulent attestation
+#[event]
+pub struct ValidatorSlashed {
+    pub operator: Pubkey,
+    pub mint: Pubkey,
+    pub end_slot: u64,
+    pub challenger: Pubkey,
+    pub fraud_reason: u8,
+    pub slashed_lamports: u64,
+    pub remaining_bond: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when validated fees are registered (attestation finalized)
+#[event]
+pub struct ValidatedFeesRegistered {
+    pub mint: Pubkey,
+    pub fee_amount: u64,
+    pub end_slot: u64,
+    pub tx_count: u32,
+    pub total_pending: u64,
+    pub timestamp: i64,
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// EXTERNAL APP INTEGRATION EVENTS
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// Emitted when rebate pool is initialized
+#[event]
+pub struct RebatePoolInitialized {
+    pub rebate_pool: Pubkey,
+    pub rebate_pool_ata: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when user stats are initialized
+#[event]
+pub struct UserStatsInitialized {
+    pub user: Pubkey,
+    pub user_stats: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when $ASDF fee is deposited via external app
+#[event]
+pub struct FeeAsdfDeposited {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub burn_amount: u64,
+    pub rebate_pool_amount: u64,
+    pub pending_contribution: u64,
+    /// Registered app the deposit came through (0 = none)
+    pub app_id: u32,
+    pub timestamp: i64,
+}
+
+/// Display-ready deposit receipt (v2) - emitted alongside FeeAsdfDeposited
+/// Effective bps reflect integer rounding of the actual transferred amounts
+#[event]
+pub struct FeeAsdfDepositedV2 {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub burn_amount: u64,
+    pub rebate_pool_amount: u64,
+    /// burn_amount / amount in basis points
+    pub burn_bps_effective: u16,
+    /// rebate_pool_amount / amount in basis points
+    pub rebate_bps_effective: u16,
+    /// User's pending contribution after this deposit
+    pub pending_contribution: u64,
+    /// Rebate the user would receive if processed now
+    pub projected_rebate: u64,
+    /// This deposit's share of tokens pending burn in the next cycle (bps)
+    pub projected_cycle_share_bps: u16,
+    pub timestamp: i64,
+}
+
+/// Emitted when a deposit is directed to an ecosystem token other than $ASDF
+/// Targeted deposits are burned in that token's next cycle and earn no $ASDF rebate
+#[event]
+pub struct FeeDepositTargeted {
+    pub user: Pubkey,
+    pub target_mint: Pubkey,
+    pub amount: u64,
+    pub burn_amount: u64,
+    pub rebate_pool_amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a first deposit receives the onboarding bonus
+#[event]
+pub struct OnboardingBonusPaid {
+    pub user: Pubkey,
+    pub deposit_amount: u64,
+    pub bonus_amount: u64,
+    pub remaining_budget: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a rebate params change is proposed (timelocked)
+#[event]
+pub struct RebateParamsProposed {
+    pub rebate_share: u32,
+    pub rebate_threshold: u64,
+    pub executable_at: i64,
+}
+
+/// Emitted when pending rebate params take effect
+#[event]
+pub struct RebateParamsUpdated {
+    pub old_rebate_share: u32,
+    pub new_rebate_share: u32,
+    pub old_rebate_threshold: u64,
+    pub new_rebate_threshold: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a weighted rebate draw is requested
+#[event]
+pub struct RebateDrawRequested {
+    pub draw: u64,
+    pub candidates: u32,
+    pub candidates_hash: [u8; 32],
+    pub target_slot: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a rebate draw selects its winner
+#[event]
+pub struct RebateWinnerSelected {
+    pub draw: u64,
+    pub winner: Pubkey,
+    pub winner_weight: u64,
+    pub total_weight: u64,
+    pub target_slot: u64,
+    pub randomness: [u8; 32],
+    pub timestamp: i64,
+}
+
+/// Emitted when a rebate epoch opens
+#[event]
+pub struct RebateEpochOpened {
+    pub epoch: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a rebate epoch closes and its pro-rata pool is reserved
+#[event]
+pub struct RebateEpochClosed {
+    pub epoch: u64,
+    pub total_contributions: u64,
+    pub pool_amount: u64,
+    pub epoch_reserved: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a user receives their pro-rata share of a closed epoch
+#[event]
+pub struct EpochRebateClaimed {
+    pub user: Pubkey,
+    pub epoch: u64,
+    pub contribution: u64,
+    pub rebate_amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the onboarding bonus program is configured
+#[event]
+pub struct OnboardingBonusConfigured {
+    pub bonus_bps: u16,
+    pub bonus_cap: u64,
+    pub bonus_budget: u64,
+    pub timestamp: i64,
+}
+
+/// One chunk of the portable config blob (export_config)
+/// Concatenate chunks 0..chunk_count and check sha256 against config_hash
+#[event]
+pub struct ConfigExported {
+    pub version: u8,
+    pub chunk_index: u8,
+    pub chunk_count: u8,
+    pub config_hash: [u8; 32],
+    pub data: Vec<u8>,
+    pub timestamp: i64,
+}
+
+/// Emitted when a portable config blob is queued for import (propose_import_config)
+/// The guardian compares config_hash with the exported blob before the delay elapses
+#[event]
+pub struct ConfigImportProposed {
+    pub id: u64,
+    pub admin: Pubkey,
+    pub version: u8,
+    pub config_hash: [u8; 32],
+    pub executable_at: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a portable config blob is restored (import_config)
+#[event]
+pub struct ConfigImported {
+    pub admin: Pubkey,
+    pub version: u8,
+    pub config_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+/// Emitted when a user names their referrer (set_referrer)
+#[event]
+pub struct ReferrerSet {
+    pub user: Pubkey,
+    pub referrer: Pubkey,
+    pub referred_users: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a referred deposit credits the referrer
+#[event]
+pub struct ReferralRewardCredited {
+    pub referrer: Pubkey,
+    pub user: Pubkey,
+    pub deposit_amount: u64,
+    pub reward: u64,
+    pub pending_rewards: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a referrer claims accumulated rewards
+#[event]
+pub struct ReferralRewardsClaimed {
+    pub referrer: Pubkey,
+    pub amount: u64,
+    pub total_claimed: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the referral share is updated
+#[event]
+pub struct ReferralShareUpdated {
+    pub old_bps: u16,
+    pub new_bps: u16,
+    pub timestamp: i64,
+}
+
+/// Emitted when an integrating app is registered
+#[event]
+pub struct AppRegistered {
+    pub app_id: u32,
+    pub authority: Pubkey,
+    pub fee_share_bps: u16,
+    pub app_stats: Pubkey,
+    pub app_count: u32,
+    pub timestamp: i64,
+}
+
+/// Emitted when an external app deposits SOL fees (deposit_fee_sol)
+#[event]
+pub struct FeeSolDeposited {
+    pub user: Pubkey,
+    pub amount_lamports: u64,
+    pub burn_lamports: u64,
+    pub rebate_lamports: u64,
+    pub asdf_equivalent: u64,
+    pub pending_contribution: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a non-$ASDF fee deposit is escrowed (deposit_fee_token)
+#[event]
+pub struct TokenFeeEscrowed {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub min_asdf_out: u64,
+    pub escrowed_total: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when an escrowed token deposit is converted to $ASDF and split
+#[event]
+pub struct TokenDepositConverted {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub token_amount: u64,
+    pub asdf_amount: u64,
+    pub burn_amount: u64,
+    pub rebate_pool_amount: u64,
+    pub pending_contribution: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when an unconverted token deposit is returned to the user
+#[event]
+pub struct TokenDepositRefunded {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when SOL accrued by the rebate pool is withdrawn for conversion to $ASDF
+#[event]
+pub struct RebateSolWithdrawn {
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when user rebate is processed
+#[event]
+pub struct UserRebateProcessed {
+    pub user: Pubkey,
+    pub pending_burned: u64,
+    pub rebate_amount: u64,
+    pub total_contributed: u64,
+    pub total_rebate: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a user claims their own rebate (claim_rebate)
+#[event]
+pub struct UserRebateClaimed {
     pub user: Pubkey,
     pub pending_burned: u64,
     pub rebate_amount: u64,
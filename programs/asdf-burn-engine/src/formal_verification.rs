@@ -15,7 +15,6 @@
 mod formal_tests {
     use crate::constants::*;
     use crate::helpers::math::*;
-    use crate::ErrorCode;
 
     // ========================================================================
     // SECTION 3: CORE INVARIANTS
@@ -173,10 +172,10 @@ mod formal_tests {
                 let new_down = current_bps.saturating_sub(delta);
 
                 if new_up <= FEE_SPLIT_BPS_MAX {
-                    assert!(new_up >= FEE_SPLIT_BPS_MIN && new_up <= FEE_SPLIT_BPS_MAX);
+                    assert!((FEE_SPLIT_BPS_MIN..=FEE_SPLIT_BPS_MAX).contains(&new_up));
                 }
                 if new_down >= FEE_SPLIT_BPS_MIN {
-                    assert!(new_down >= FEE_SPLIT_BPS_MIN && new_down <= FEE_SPLIT_BPS_MAX);
+                    assert!((FEE_SPLIT_BPS_MIN..=FEE_SPLIT_BPS_MAX).contains(&new_down));
                 }
             }
 
@@ -196,8 +195,6 @@ mod formal_tests {
     // ========================================================================
 
     mod security {
-        use super::*;
-
         /// SEC-2: No Arithmetic Overflow
         /// All operations use saturating_* or checked_*
         #[test]
@@ -558,7 +555,7 @@ mod formal_tests {
                 }
             }
 
-            fn record_success(&mut self, now: i64, burned: u64) {
+            fn record_success(&mut self, now: i64, _burned: u64) {
                 self.consecutive_failures = 0;
                 self.pending_burn_amount = 0;
                 self.last_cycle_timestamp = now;
@@ -592,7 +589,7 @@ mod formal_tests {
         /// Test fee split change bounds
         #[test]
         fn test_fee_split_change_bounds() {
-            let mut state = SimulatedState::new();
+            let state = SimulatedState::new();
             assert_eq!(state.fee_split_bps, 5520);
 
             // Valid change (+500)
@@ -3,6 +3,23 @@ use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
 use anchor_lang::solana_program::program::invoke_signed;
 use crate::constants::*;
 use crate::errors::ErrorCode;
+use crate::helpers::math::root_share;
+use crate::helpers::venue::pump_creator_vault;
+
+/// Fail unless `remaining` compute units cover a phase needing `required`
+pub fn check_compute_budget(remaining: u64, required: u64) -> Result<()> {
+    require!(remaining >= required, ErrorCode::InsufficientComputeRemaining);
+    Ok(())
+}
+
+/// Abort before an expensive phase when the transaction cannot finish it
+/// (sol_remaining_compute_units syscall)
+pub fn require_compute_remaining(required: u64) -> Result<()> {
+    check_compute_budget(
+        anchor_lang::solana_program::compute_units::sol_remaining_compute_units(),
+        required,
+    )
+}
 
 /// Helper function to collect creator fees CPI (extracted to reduce stack usage)
 /// Used for PumpFun bonding curve tokens
@@ -35,7 +52,36 @@ pub fn collect_creator_fee_cpi<'info>(
         pump_swap_program.to_account_info(),
     ]);
 
-    invoke_signed(&*instruction, &*account_infos, &[seeds])?;
+    invoke_signed(&instruction, &*account_infos, &[seeds])?;
+    Ok(())
+}
+
+/// Metaplex UpdateMetadataAccountV2 signed by dat_authority (the metadata update authority)
+/// `data` is built by update_metadata_v2_data
+#[inline(never)]
+pub fn update_metadata_cpi<'info>(
+    metadata: &AccountInfo<'info>,
+    dat_authority: &AccountInfo<'info>,
+    token_metadata_program: &AccountInfo<'info>,
+    data: Vec<u8>,
+    seeds: &[&[u8]],
+) -> Result<()> {
+    let instruction = Box::new(Instruction {
+        program_id: MPL_TOKEN_METADATA_PROGRAM,
+        accounts: vec![
+            AccountMeta::new(*metadata.key, false),
+            AccountMeta::new_readonly(*dat_authority.key, true),
+        ],
+        data,
+    });
+
+    let account_infos = Box::new([
+        metadata.to_account_info(),
+        dat_authority.to_account_info(),
+        token_metadata_program.to_account_info(),
+    ]);
+
+    invoke_signed(&instruction, &*account_infos, &[seeds])?;
     Ok(())
 }
 
@@ -43,6 +89,7 @@ pub fn collect_creator_fee_cpi<'info>(
 /// This is used for tokens that have migrated from bonding curve to AMM
 /// The DAT authority PDA must be set as the coin_creator in PumpSwap
 #[inline(never)]
+#[allow(clippy::too_many_arguments)]
 pub fn collect_amm_creator_fee_cpi<'info>(
     quote_mint: &AccountInfo<'info>,
     quote_token_program: &AccountInfo<'info>,
@@ -79,7 +126,43 @@ pub fn collect_amm_creator_fee_cpi<'info>(
         pump_swap_program.to_account_info(),
     ]);
 
-    invoke_signed(&*instruction, &*account_infos, &[seeds])?;
+    invoke_signed(&instruction, &*account_infos, &[seeds])?;
+    Ok(())
+}
+
+/// Set a migrated pool's coin_creator via PumpSwap's set_coin_creator
+/// PumpSwap copies the creator of the completed bonding curve (the metadata update
+/// authority when there is none), so for DAT-created tokens this registers dat_authority
+#[inline(never)]
+pub fn set_amm_coin_creator_cpi<'info>(
+    pool: &AccountInfo<'info>,
+    metadata: &AccountInfo<'info>,
+    bonding_curve: &AccountInfo<'info>,
+    event_authority: &AccountInfo<'info>,
+    pump_swap_program: &AccountInfo<'info>,
+    seeds: &[&[u8]],
+) -> Result<()> {
+    let instruction = Box::new(Instruction {
+        program_id: PUMP_SWAP_PROGRAM,
+        accounts: vec![
+            AccountMeta::new(*pool.key, false),
+            AccountMeta::new_readonly(*metadata.key, false),
+            AccountMeta::new_readonly(*bonding_curve.key, false),
+            AccountMeta::new_readonly(*event_authority.key, false),
+            AccountMeta::new_readonly(PUMP_SWAP_PROGRAM, false),
+        ],
+        data: PUMPSWAP_SET_COIN_CREATOR_DISCRIMINATOR.to_vec(),
+    });
+
+    let account_infos = Box::new([
+        pool.to_account_info(),
+        metadata.to_account_info(),
+        bonding_curve.to_account_info(),
+        event_authority.to_account_info(),
+        pump_swap_program.to_account_info(),
+    ]);
+
+    invoke_signed(&instruction, &*account_infos, &[seeds])?;
     Ok(())
 }
 
@@ -94,7 +177,7 @@ pub fn split_fees_to_root<'info>(
     fee_split_bps: u16,
     seeds: &[&[u8]],
 ) -> Result<u64> {
-    let sol_for_root = total_lamports.saturating_sub((total_lamports * fee_split_bps as u64) / 10000);
+    let sol_for_root = root_share(total_lamports, fee_split_bps);
 
     if sol_for_root > 0 {
         // HIGH-03 FIX: Record balance before transfer for verification
@@ -125,9 +208,148 @@ pub fn split_fees_to_root<'info>(
     Ok(sol_for_root)
 }
 
+/// Pay a Jito tip from dat_authority to one of the JITO_TIP_ACCOUNTS
+pub fn pay_jito_tip<'info>(
+    dat_authority: &AccountInfo<'info>,
+    tip_account: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    tip_lamports: u64,
+    seeds: &[&[u8]],
+) -> Result<()> {
+    require!(JITO_TIP_ACCOUNTS.contains(tip_account.key), ErrorCode::InvalidJitoTipAccount);
+
+    invoke_signed(
+        &anchor_lang::solana_program::system_instruction::transfer(
+            dat_authority.key,
+            tip_account.key,
+            tip_lamports
+        ),
+        &[
+            dat_authority.to_account_info(),
+            tip_account.to_account_info(),
+            system_program.to_account_info()
+        ],
+        &[seeds]
+    )?;
+    Ok(())
+}
+
+/// Address of the lookup table `authority` creates at `recent_slot`
+/// seeds: [authority, recent_slot (LE)] under ADDRESS_LOOKUP_TABLE_PROGRAM
+pub fn derive_lookup_table_address(authority: &Pubkey, recent_slot: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[authority.as_ref(), &recent_slot.to_le_bytes()], &ADDRESS_LOOKUP_TABLE_PROGRAM)
+}
+
+/// Static accounts shared by every cycle transaction: DAT PDAs, pump programs and their
+/// global PDAs, fee program and token programs. Invoked program IDs stay in the message
+/// (a transaction cannot load them from a lookup table), so this program's ID is not included
+pub fn cycle_lut_static_addresses(dat_state: &Pubkey, dat_authority: &Pubkey) -> Vec<Pubkey> {
+    vec![
+        *dat_state,
+        *dat_authority,
+        pump_creator_vault(dat_authority),
+        PUMP_PROGRAM,
+        PUMP_EVENT_AUTHORITY,
+        PUMP_GLOBAL_VOLUME_ACCUMULATOR,
+        PUMP_FEE_PROGRAM,
+        PUMP_SWAP_PROGRAM,
+        PUMPSWAP_GLOBAL_CONFIG,
+        PUMPSWAP_EVENT_AUTHORITY,
+        PUMPSWAP_GLOBAL_VOLUME_ACCUMULATOR,
+        WSOL_MINT,
+        anchor_spl::token::ID,
+        TOKEN_2022_PROGRAM,
+        anchor_spl::associated_token::ID,
+        anchor_lang::system_program::ID,
+    ]
+}
+
+/// Create an Address Lookup Table whose authority is dat_authority (signs via seeds)
+#[allow(clippy::too_many_arguments)]
+pub fn create_lookup_table_cpi<'info>(
+    lookup_table: &AccountInfo<'info>,
+    dat_authority: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    lookup_table_program: &AccountInfo<'info>,
+    recent_slot: u64,
+    bump_seed: u8,
+    seeds: &[&[u8]],
+) -> Result<()> {
+    let mut data = Vec::with_capacity(13);
+    data.extend_from_slice(&ALT_CREATE_LOOKUP_TABLE_TAG.to_le_bytes());
+    data.extend_from_slice(&recent_slot.to_le_bytes());
+    data.push(bump_seed);
+
+    let ix = Instruction {
+        program_id: ADDRESS_LOOKUP_TABLE_PROGRAM,
+        accounts: vec![
+            AccountMeta::new(lookup_table.key(), false),
+            AccountMeta::new_readonly(dat_authority.key(), true),
+            AccountMeta::new(payer.key(), true),
+            AccountMeta::new_readonly(system_program.key(), false),
+        ],
+        data,
+    };
+    invoke_signed(
+        &ix,
+        &[
+            lookup_table.clone(),
+            dat_authority.clone(),
+            payer.clone(),
+            system_program.clone(),
+            lookup_table_program.clone(),
+        ],
+        &[seeds],
+    )?;
+    Ok(())
+}
+
+/// Append addresses to a dat_authority-owned lookup table; payer funds the extra rent
+pub fn extend_lookup_table_cpi<'info>(
+    lookup_table: &AccountInfo<'info>,
+    dat_authority: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    lookup_table_program: &AccountInfo<'info>,
+    new_addresses: &[Pubkey],
+    seeds: &[&[u8]],
+) -> Result<()> {
+    let mut data = Vec::with_capacity(12 + 32 * new_addresses.len());
+    data.extend_from_slice(&ALT_EXTEND_LOOKUP_TABLE_TAG.to_le_bytes());
+    data.extend_from_slice(&(new_addresses.len() as u64).to_le_bytes());
+    for address in new_addresses {
+        data.extend_from_slice(address.as_ref());
+    }
+
+    let ix = Instruction {
+        program_id: ADDRESS_LOOKUP_TABLE_PROGRAM,
+        accounts: vec![
+            AccountMeta::new(lookup_table.key(), false),
+            AccountMeta::new_readonly(dat_authority.key(), true),
+            AccountMeta::new(payer.key(), true),
+            AccountMeta::new_readonly(system_program.key(), false),
+        ],
+        data,
+    };
+    invoke_signed(
+        &ix,
+        &[
+            lookup_table.clone(),
+            dat_authority.clone(),
+            payer.clone(),
+            system_program.clone(),
+            lookup_table_program.clone(),
+        ],
+        &[seeds],
+    )?;
+    Ok(())
+}
+
 /// Minimal CPI executor for PumpFun buy (CORRECT 16-account format)
 /// Based on successful devnet tx 3Rqh43z2Vt2BkSPbkchLKsJr4CZiNbqbfRgapJtuGqfoaKLuyCNYbRyvCwv7ksRRdsRPTjdQGCTfgeZQMmJGksHW
 #[inline(never)]
+#[allow(clippy::too_many_arguments)]
 pub fn execute_pumpfun_cpi<'info>(
     global_config: Pubkey,
     fee_recipient: Pubkey,
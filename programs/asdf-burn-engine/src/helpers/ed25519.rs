@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+use crate::constants::*;
+use crate::errors::ErrorCode;
+
+/// Ed25519SigVerify instruction header: num_signatures(1) + padding(1)
+const ED25519_HEADER_LEN: usize = 2;
+
+/// Per-signature offsets entry: 7 x u16
+const ED25519_OFFSETS_LEN: usize = 14;
+
+/// Instruction index meaning "data lives in this ed25519 instruction"
+const ED25519_SELF_INDEX: u16 = u16::MAX;
+
+fn read_u16(data: &[u8], at: usize) -> Result<u16> {
+    let bytes = data.get(at..at + 2).ok_or(ErrorCode::InvalidAttestation)?;
+    Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+/// Canonical message attestors sign for register_validated_fees
+pub fn fee_attestation_message(
+    mint: &Pubkey,
+    end_slot: u64,
+    fee_amount: u64,
+    tx_count: u32,
+    merkle_root: &[u8; 32],
+) -> Vec<u8> {
+    let mut msg = Vec::with_capacity(FEE_ATTESTATION_DOMAIN.len() + 32 + 8 + 8 + 4 + 32);
+    msg.extend_from_slice(FEE_ATTESTATION_DOMAIN);
+    msg.extend_from_slice(mint.as_ref());
+    msg.extend_from_slice(&end_slot.to_le_bytes());
+    msg.extend_from_slice(&fee_amount.to_le_bytes());
+    msg.extend_from_slice(&tx_count.to_le_bytes());
+    msg.extend_from_slice(merkle_root);
+    msg
+}
+
+/// Parse an Ed25519SigVerify instruction and return the pubkeys that signed `message`
+/// Only self-contained entries (all instruction indexes = u16::MAX) are accepted, so
+/// offsets cannot point at data in other instructions. The runtime has already
+/// verified every signature in the instruction if the transaction executed.
+pub fn parse_ed25519_signers(data: &[u8], message: &[u8]) -> Result<Vec<Pubkey>> {
+    require!(data.len() >= ED25519_HEADER_LEN, ErrorCode::InvalidAttestation);
+    let count = data[0] as usize;
+
+    let mut signers = Vec::with_capacity(count);
+    for i in 0..count {
+        let base = ED25519_HEADER_LEN + i * ED25519_OFFSETS_LEN;
+        let sig_ix = read_u16(data, base + 2)?;
+        let pubkey_offset = read_u16(data, base + 4)? as usize;
+        let pubkey_ix = read_u16(data, base + 6)?;
+        let msg_offset = read_u16(data, base + 8)? as usize;
+        let msg_size = read_u16(data, base + 10)? as usize;
+        let msg_ix = read_u16(data, base + 12)?;
+
+        require!(
+            sig_ix == ED25519_SELF_INDEX && pubkey_ix == ED25519_SELF_INDEX && msg_ix == ED25519_SELF_INDEX,
+            ErrorCode::InvalidAttestation
+        );
+
+        let signed = data.get(msg_offset..msg_offset + msg_size).ok_or(ErrorCode::InvalidAttestation)?;
+        if signed != message {
+            continue;
+        }
+
+        let pubkey = data.get(pubkey_offset..pubkey_offset + 32).ok_or(ErrorCode::InvalidAttestation)?;
+        signers.push(Pubkey::try_from(pubkey).map_err(|_| ErrorCode::InvalidAttestation)?);
+    }
+
+    Ok(signers)
+}
+
+/// Count distinct attestors that signed `message` in ed25519 instructions preceding
+/// the current instruction (instructions sysvar introspection)
+pub fn count_attestor_signatures(
+    instructions_sysvar: &AccountInfo,
+    attestors: &[Pubkey],
+    message: &[u8],
+) -> Result<u8> {
+    let current = load_current_index_checked(instructions_sysvar)? as usize;
+
+    let mut seen: Vec<Pubkey> = Vec::with_capacity(attestors.len());
+    for index in 0..current {
+        let ix = load_instruction_at_checked(index, instructions_sysvar)?;
+        if ix.program_id != ed25519_program::ID {
+            continue;
+        }
+        for signer in parse_ed25519_signers(&ix.data, message)? {
+            if attestors.contains(&signer) && !seen.contains(&signer) {
+                seen.push(signer);
+            }
+        }
+    }
+
+    Ok(seen.len() as u8)
+}
@@ -69,25 +69,25 @@ pub fn calculate_tokens_out(sol_in: u64, quote_res: u64, base_res: u64, supply:
         500_000_000_001..=700_000_000_000 => 110,
         700_000_000_001..=900_000_000_000 => 105,
         900_000_000_001..=2_000_000_000_000 => 100,
-        2_000_000_001..=3_000_000_000_000 => 95,
-        3_000_000_001..=4_000_000_000_000 => 90,
-        4_000_000_001..=4_500_000_000_000 => 85,
-        4_500_000_001..=5_000_000_000_000 => 80,
-        5_000_000_001..=6_000_000_000_000 => 80,
-        6_000_000_001..=7_000_000_000_000 => 75,
-        7_000_000_001..=8_000_000_000_000 => 70,
-        8_000_000_001..=9_000_000_000_000 => 65,
-        9_000_000_001..=10_000_000_000_000 => 60,
-        10_000_000_001..=11_000_000_000_000 => 55,
-        11_000_000_001..=12_000_000_000_000 => 53,
-        12_000_000_001..=13_000_000_000_000 => 50,
-        13_000_000_001..=14_000_000_000_000 => 48,
-        14_000_000_001..=15_000_000_000_000 => 45,
-        15_000_000_001..=16_000_000_000_000 => 43,
-        16_000_000_001..=17_000_000_000_000 => 40,
-        17_000_000_001..=18_000_000_000_000 => 38,
-        18_000_000_001..=19_000_000_000_000 => 35,
-        19_000_000_001..=20_000_000_000_000 => 33,
+        2_000_000_000_001..=3_000_000_000_000 => 95,
+        3_000_000_000_001..=4_000_000_000_000 => 90,
+        4_000_000_000_001..=4_500_000_000_000 => 85,
+        4_500_000_000_001..=5_000_000_000_000 => 80,
+        5_000_000_000_001..=6_000_000_000_000 => 80,
+        6_000_000_000_001..=7_000_000_000_000 => 75,
+        7_000_000_000_001..=8_000_000_000_000 => 70,
+        8_000_000_000_001..=9_000_000_000_000 => 65,
+        9_000_000_000_001..=10_000_000_000_000 => 60,
+        10_000_000_000_001..=11_000_000_000_000 => 55,
+        11_000_000_000_001..=12_000_000_000_000 => 53,
+        12_000_000_000_001..=13_000_000_000_000 => 50,
+        13_000_000_000_001..=14_000_000_000_000 => 48,
+        14_000_000_000_001..=15_000_000_000_000 => 45,
+        15_000_000_000_001..=16_000_000_000_000 => 43,
+        16_000_000_000_001..=17_000_000_000_000 => 40,
+        17_000_000_000_001..=18_000_000_000_000 => 38,
+        18_000_000_000_001..=19_000_000_000_000 => 35,
+        19_000_000_000_001..=20_000_000_000_000 => 33,
         _ => 30,
     };
 
@@ -120,6 +120,41 @@ pub fn calculate_tokens_out(sol_in: u64, quote_res: u64, base_res: u64, supply:
     Ok(out as u64)
 }
 
+/// Split a cycle's bought tokens into (burn, community treasury) shares
+/// burn = amount * burn_bps / 10000, treasury = remainder (rounding favors the treasury by < 1 unit)
+pub fn calculate_burn_split(amount: u64, burn_bps: u16) -> Result<(u64, u64)> {
+    require!(burn_bps <= 10000, ErrorCode::InvalidBurnRatio);
+
+    let burn_amount = (amount as u128)
+        .checked_mul(burn_bps as u128)
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_div(10000)
+        .ok_or(ErrorCode::MathOverflow)? as u64;
+    let treasury_amount = amount.saturating_sub(burn_amount);
+
+    Ok((burn_amount, treasury_amount))
+}
+
+/// Carve the holder reflection out of the burned share: reflection_bps of `amount`,
+/// capped at `burn_amount`. Returns (tokens_to_burn, reflection_amount)
+pub fn calculate_reflection_split(amount: u64, burn_amount: u64, reflection_bps: u16) -> Result<(u64, u64)> {
+    require!(reflection_bps <= 10000, ErrorCode::InvalidReflectionRatio);
+
+    let reflection = ((amount as u128) * reflection_bps as u128 / 10000) as u64;
+    let reflection = reflection.min(burn_amount);
+
+    Ok((burn_amount - reflection, reflection))
+}
+
+/// Ratio part/total in basis points, capped at 10000 (0 if total is 0)
+pub fn calculate_share_bps(part: u64, total: u64) -> u16 {
+    if total == 0 {
+        return 0;
+    }
+    let bps = (part as u128) * 10000 / total as u128;
+    bps.min(10000) as u16
+}
+
 /// Format token amount with decimals for readable logs
 /// Most tokens have 6 decimals, so we divide by 1_000_000
 pub fn format_tokens(amount: u64) -> (u64, u64) {
@@ -149,16 +184,143 @@ pub fn deserialize_bonding_curve(data: &[u8]) -> Result<(u64, u64)> {
     Ok((virtual_token_reserves, virtual_sol_reserves))
 }
 
-/// Helper function to calculate buy parameters for PumpFun
-/// Returns (max_sol_cost, desired_tokens)
-/// PumpFun buy instruction expects: token_amount (how many tokens we want) and max_sol_cost (max SOL we'll pay)
-#[inline(never)]
-pub fn calculate_buy_amount_and_slippage(
+/// PumpFun BondingCurve offsets (8-byte discriminator excluded)
+/// Layout: virtual_token(8) | virtual_sol(8) | real_token(8) | real_sol(8) | token_total_supply(8)
+///         | complete(1) | creator(32) | is_mayhem_mode(1, Mayhem curves only: 82-byte accounts)
+const CURVE_REAL_TOKEN_RESERVES_OFFSET: usize = 16;
+const CURVE_TOKEN_TOTAL_SUPPLY_OFFSET: usize = 32;
+const CURVE_MAYHEM_FLAG_OFFSET: usize = 73;
+const CURVE_STANDARD_LEN: usize = 73;
+
+/// Bonding curve fields used by the buy math
+/// Fields past the virtual reserves are None for a truncated snapshot (shorter than a standard curve)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BondingCurve {
+    pub virtual_token_reserves: u64,
+    pub virtual_sol_reserves: u64,
+    pub real_token_reserves: Option<u64>,
+    pub token_total_supply: Option<u64>,
+    pub is_mayhem: bool,
+}
+
+impl BondingCurve {
+    /// Supply the curve was minted with: 2B for Mayhem Mode, 1B otherwise
+    pub fn max_supply(&self) -> u64 {
+        if self.is_mayhem {
+            PUMP_MAYHEM_TOKEN_TOTAL_SUPPLY
+        } else {
+            PUMP_TOKEN_TOTAL_SUPPLY
+        }
+    }
+
+    /// Cap a token quote at what the curve can still sell
+    pub fn cap_tokens_out(&self, tokens: u64) -> u64 {
+        self.real_token_reserves.map_or(tokens, |real| tokens.min(real))
+    }
+}
+
+fn read_curve_u64(data: &[u8], at: usize) -> Option<u64> {
+    if data.len() < CURVE_STANDARD_LEN {
+        return None;
+    }
+    let bytes: [u8; 8] = data.get(at..at + 8)?.try_into().ok()?;
+    Some(u64::from_le_bytes(bytes))
+}
+
+/// Parse a PumpFun bonding curve (data without discriminator), detecting Mayhem Mode
+/// A recorded token_total_supply above the mode's supply means a misread layout
+pub fn parse_bonding_curve(data: &[u8]) -> Result<BondingCurve> {
+    let (virtual_token_reserves, virtual_sol_reserves) = deserialize_bonding_curve(data)?;
+
+    let curve = BondingCurve {
+        virtual_token_reserves,
+        virtual_sol_reserves,
+        real_token_reserves: read_curve_u64(data, CURVE_REAL_TOKEN_RESERVES_OFFSET),
+        token_total_supply: read_curve_u64(data, CURVE_TOKEN_TOTAL_SUPPLY_OFFSET),
+        is_mayhem: data.get(CURVE_MAYHEM_FLAG_OFFSET).is_some_and(|flag| *flag != 0),
+    };
+
+    if let Some(supply) = curve.token_total_supply {
+        require!(supply <= curve.max_supply(), ErrorCode::InvalidPool);
+    }
+
+    Ok(curve)
+}
+
+/// Mayhem Mode flag of a bonding curve account (raw account data, discriminator included)
+pub fn is_mayhem_bonding_curve(data: &[u8]) -> bool {
+    data.get(8 + CURVE_MAYHEM_FLAG_OFFSET).is_some_and(|flag| *flag != 0)
+}
+
+/// Implied execution price: lamports per token base unit, scaled by PRICE_SCALE
+pub fn calculate_execution_price(sol_in: u64, tokens_out: u64) -> Result<u64> {
+    require!(tokens_out > 0, ErrorCode::InsufficientPoolLiquidity);
+
+    let price = (sol_in as u128)
+        .checked_mul(PRICE_SCALE)
+        .ok_or(ErrorCode::MathOverflow)?
+        / tokens_out as u128;
+
+    u64::try_from(price).map_err(|_| ErrorCode::MathOverflow.into())
+}
+
+/// Token amount worth `lamports` at a PRICE_SCALE price (inverse of calculate_execution_price)
+pub fn sol_to_token_amount(lamports: u64, price: u64) -> Result<u64> {
+    require!(price > 0, ErrorCode::NoPriceObservation);
+
+    let tokens = (lamports as u128)
+        .checked_mul(PRICE_SCALE)
+        .ok_or(ErrorCode::MathOverflow)?
+        / price as u128;
+
+    u64::try_from(tokens).map_err(|_| ErrorCode::MathOverflow.into())
+}
+
+/// Lamports needed to bring `balance` up to `floor + AUTHORITY_TOP_UP_HEADROOM` (0 if already there)
+pub fn authority_top_up_amount(balance: u64, floor: u64) -> u64 {
+    floor.saturating_add(AUTHORITY_TOP_UP_HEADROOM).saturating_sub(balance)
+}
+
+/// Price circuit breaker: reject if price deviates more than max_deviation_bps
+/// from the last observation (either direction). No-op without an observation or when disabled.
+pub fn check_price_deviation(price: u64, last_known_price: u64, max_deviation_bps: u16) -> Result<()> {
+    if last_known_price == 0 || max_deviation_bps == 0 {
+        return Ok(());
+    }
+
+    // Cross-multiplied so a fraction of a bp above the bound is not truncated away
+    let deviation = (price.abs_diff(last_known_price) as u128) * 10000;
+
+    #[cfg(feature = "verbose")]
+    msg!("Price check: price={}, last={}, deviation={} bps", price, last_known_price, deviation / last_known_price as u128);
+
+    require!(
+        deviation <= max_deviation_bps as u128 * last_known_price as u128,
+        ErrorCode::PriceImpactTooHigh
+    );
+    Ok(())
+}
+
+/// Caller price bound: reject a buy whose effective price (sol_spent / tokens_received,
+/// PRICE_SCALE-scaled like calculate_execution_price) exceeds max_price. No-op without a bound.
+pub fn check_max_price(sol_spent: u64, tokens_received: u64, max_price: Option<u64>) -> Result<()> {
+    let Some(max_price) = max_price else {
+        return Ok(());
+    };
+    require!(tokens_received > 0, ErrorCode::SlippageExceeded);
+
+    let price = calculate_execution_price(sol_spent, tokens_received)?;
+    require!(price <= max_price, ErrorCode::SlippageExceeded);
+    Ok(())
+}
+
+/// Shared buy preconditions: cap the budget and parse the bonding curve (standard or Mayhem)
+/// Returns (final_amount, curve)
+fn buy_budget_and_reserves(
     buy_amount: u64,
     bonding_curve_data: &[u8],
     max_fees_per_cycle: u64,
-    slippage_bps: u16, // Now actually used - max 500 bps (5%)
-) -> Result<(u64, u64)> {
+) -> Result<(u64, BondingCurve)> {
     // buy_amount already has rent subtracted, just cap it
     let capped = buy_amount.min(max_fees_per_cycle);
 
@@ -166,10 +328,10 @@ pub fn calculate_buy_amount_and_slippage(
     require!(bonding_curve_data.len() >= 32, ErrorCode::InvalidPool);
 
     // Deserialize bonding curve manually (skip 8-byte discriminator)
-    let (virtual_token_reserves, virtual_sol_reserves) = deserialize_bonding_curve(&bonding_curve_data[8..])?;
+    let curve = parse_bonding_curve(&bonding_curve_data[8..])?;
+    let (virtual_token_reserves, virtual_sol_reserves) = (curve.virtual_token_reserves, curve.virtual_sol_reserves);
 
     // Minimum pool liquidity check: require at least 0.01 SOL in virtual reserves
-    const MIN_POOL_LIQUIDITY: u64 = 10_000_000; // 0.01 SOL
     require!(
         virtual_sol_reserves >= MIN_POOL_LIQUIDITY,
         ErrorCode::InsufficientPoolLiquidity
@@ -184,18 +346,42 @@ pub fn calculate_buy_amount_and_slippage(
     let max_safe = virtual_sol_reserves / 100;
     let final_amount = capped.min(max_safe);
 
+    Ok((final_amount, curve))
+}
+
+/// Helper function to calculate buy parameters for PumpFun
+/// Returns (max_sol_cost, desired_tokens, execution_price)
+/// PumpFun buy instruction expects: token_amount (how many tokens we want) and max_sol_cost (max SOL we'll pay)
+/// Aborts with PriceImpactTooHigh if the implied price deviates from last_known_price
+/// by more than max_price_deviation_bps (pass 0 for either to skip the check)
+#[inline(never)]
+pub fn calculate_buy_amount_and_slippage(
+    buy_amount: u64,
+    bonding_curve_data: &[u8],
+    max_fees_per_cycle: u64,
+    slippage_bps: u16, // Now actually used - max 500 bps (5%)
+    last_known_price: u64,
+    max_price_deviation_bps: u16,
+) -> Result<(u64, u64, u64)> {
+    let (final_amount, curve) = buy_budget_and_reserves(buy_amount, bonding_curve_data, max_fees_per_cycle)?;
+
     // Only attempt calculation if we have something to buy
     if final_amount == 0 {
-        return Ok((0, 0));
+        return Ok((0, 0, 0));
     }
 
     // Calculate how many tokens we expect to receive with our SOL
     // Use PumpFun's exact formula: tokens_out = (sol_in * virtual_token_reserves) / (virtual_sol_reserves + sol_in)
-    let expected_tokens = calculate_tokens_out_pumpfun(
+    // capped at the real reserves left on the curve (sized from 2B on Mayhem curves)
+    let expected_tokens = curve.cap_tokens_out(calculate_tokens_out_pumpfun(
         final_amount,
-        virtual_sol_reserves,
-        virtual_token_reserves,
-    )?;
+        curve.virtual_sol_reserves,
+        curve.virtual_token_reserves,
+    )?);
+
+    // Circuit breaker: compare implied execution price with the last observation
+    let execution_price = calculate_execution_price(final_amount, expected_tokens)?;
+    check_price_deviation(execution_price, last_known_price, max_price_deviation_bps)?;
 
     // Apply configurable slippage tolerance (default 500 bps = 5%)
     // slippage_bps is capped at 500 in update_parameters
@@ -206,6 +392,141 @@ pub fn calculate_buy_amount_and_slippage(
     msg!("Expected tokens: {}, Target tokens ({}% slippage): {}",
          expected_tokens, slippage_bps as f64 / 100.0, target_tokens);
 
-    // Return (max_sol_cost, desired_token_amount)
-    Ok((final_amount, target_tokens))
+    // Return (max_sol_cost, desired_token_amount, execution_price)
+    Ok((final_amount, target_tokens, execution_price))
+}
+
+/// Root treasury's share of a secondary's fees: the part fee_split_bps does not keep
+pub fn root_share(total_lamports: u64, fee_split_bps: u16) -> u64 {
+    total_lamports.saturating_sub((total_lamports as u128 * fee_split_bps as u128 / 10000) as u64)
+}
+
+/// Split one collect of the shared creator vault across tokens, pro rata to their pending fees
+/// The last token with pending fees takes the rounding remainder; with no pending fees at all
+/// nothing is attributed (the lamports stay unattributed in dat_authority)
+pub fn attribute_collected(collected: u64, pending: &[u64]) -> Vec<u64> {
+    let total_pending: u128 = pending.iter().map(|&p| p as u128).sum();
+    let mut shares = vec![0u64; pending.len()];
+    let Some(last) = pending.iter().rposition(|&p| p > 0) else {
+        return shares;
+    };
+    let mut remaining = collected;
+    for (i, &p) in pending.iter().enumerate().take(last) {
+        let share = (collected as u128 * p as u128 / total_pending) as u64;
+        shares[i] = share;
+        remaining -= share;
+    }
+    shares[last] = remaining;
+    shares
+}
+
+/// Price impact of spending sol_in on the bonding curve, in bps
+/// Execution price over spot price: ((vsol + sol_in) / vtoken) / (vsol / vtoken) - 1 = sol_in / vsol
+pub fn bonding_curve_price_impact_bps(sol_in: u64, bonding_curve_data: &[u8]) -> Result<u16> {
+    require!(bonding_curve_data.len() >= 32, ErrorCode::InvalidPool);
+    let (_, virtual_sol_reserves) = deserialize_bonding_curve(&bonding_curve_data[8..])?;
+    require!(virtual_sol_reserves > 0, ErrorCode::InsufficientPoolLiquidity);
+
+    let impact = (sol_in as u128) * 10_000 / virtual_sol_reserves as u128;
+    Ok(impact.min(u16::MAX as u128) as u16)
+}
+
+/// AMM counterpart of calculate_buy_amount_and_slippage, quoted from PumpSwap vault balances
+/// Returns (max_sol_cost, desired_tokens, execution_price)
+/// Same budget cap, 1%-of-reserves cap, circuit breaker and slippage as the bonding curve path;
+/// tokens are quoted with the constant-product formula and market cap fee tiers
+#[inline(never)]
+#[allow(clippy::too_many_arguments)]
+pub fn calculate_amm_buy_amount_and_slippage(
+    buy_amount: u64,
+    base_reserves: u64,
+    quote_reserves: u64,
+    supply: u64,
+    max_fees_per_cycle: u64,
+    slippage_bps: u16,
+    last_known_price: u64,
+    max_price_deviation_bps: u16,
+) -> Result<(u64, u64, u64)> {
+    let capped = buy_amount.min(max_fees_per_cycle);
+
+    // Minimum pool liquidity check: require at least 0.01 SOL in the quote vault
+    require!(quote_reserves >= MIN_POOL_LIQUIDITY, ErrorCode::InsufficientPoolLiquidity);
+    require!(base_reserves > 0, ErrorCode::InsufficientPoolLiquidity);
+
+    let final_amount = capped.min(quote_reserves / 100);
+    if final_amount == 0 {
+        return Ok((0, 0, 0));
+    }
+
+    let expected_tokens = calculate_tokens_out(final_amount, quote_reserves, base_reserves, supply)?;
+
+    let execution_price = calculate_execution_price(final_amount, expected_tokens)?;
+    check_price_deviation(execution_price, last_known_price, max_price_deviation_bps)?;
+
+    let slippage_multiplier = 10000u128.saturating_sub(slippage_bps as u128);
+    let target_tokens = ((expected_tokens as u128) * slippage_multiplier / 10000) as u64;
+
+    #[cfg(feature = "verbose")]
+    msg!("AMM quote: sol_in={}, expected={}, target={}", final_amount, expected_tokens, target_tokens);
+
+    Ok((final_amount, target_tokens, execution_price))
+}
+
+/// Inverse PumpFun formula: SOL needed to receive exactly tokens_out (rounded up)
+/// sol_in = ceil(tokens_out * virtual_sol_reserves / (virtual_token_reserves - tokens_out))
+pub fn calculate_sol_in_for_tokens_pumpfun(
+    tokens_out: u64,
+    virtual_sol_reserves: u64,
+    virtual_token_reserves: u64,
+) -> Result<u64> {
+    require!(tokens_out < virtual_token_reserves, ErrorCode::InsufficientPoolLiquidity);
+
+    let numerator = (tokens_out as u128)
+        .checked_mul(virtual_sol_reserves as u128)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let denominator = (virtual_token_reserves - tokens_out) as u128;
+    let sol_in = numerator.div_ceil(denominator);
+
+    u64::try_from(sol_in).map_err(|_| ErrorCode::MathOverflow.into())
+}
+
+/// Candidate exact-out buy math (shadow mode only, never executed)
+/// Returns (max_sol_cost, desired_tokens)
+/// Instead of shaving slippage off the expected tokens, reserve the slippage on the
+/// SOL side: target the tokens that (budget / (1 + slippage)) buys, then allow the
+/// exact cost of those tokens plus slippage, capped at the budget.
+pub fn calculate_buy_exact_out(
+    buy_amount: u64,
+    bonding_curve_data: &[u8],
+    max_fees_per_cycle: u64,
+    slippage_bps: u16,
+) -> Result<(u64, u64)> {
+    let (final_amount, curve) = buy_budget_and_reserves(buy_amount, bonding_curve_data, max_fees_per_cycle)?;
+    let (virtual_token_reserves, virtual_sol_reserves) = (curve.virtual_token_reserves, curve.virtual_sol_reserves);
+
+    if final_amount == 0 {
+        return Ok((0, 0));
+    }
+
+    let slippage_factor = 10000u128 + slippage_bps as u128;
+    let pre_slippage_budget = ((final_amount as u128) * 10000 / slippage_factor) as u64;
+
+    let desired_tokens = curve.cap_tokens_out(calculate_tokens_out_pumpfun(
+        pre_slippage_budget,
+        virtual_sol_reserves,
+        virtual_token_reserves,
+    )?);
+    if desired_tokens == 0 {
+        return Ok((0, 0));
+    }
+
+    let exact_cost = calculate_sol_in_for_tokens_pumpfun(
+        desired_tokens,
+        virtual_sol_reserves,
+        virtual_token_reserves,
+    )?;
+    let with_slippage = ((exact_cost as u128) * slippage_factor).div_ceil(10000);
+    let max_sol_cost = with_slippage.min(final_amount as u128) as u64;
+
+    Ok((max_sol_cost, desired_tokens))
 }
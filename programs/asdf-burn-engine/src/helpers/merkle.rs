@@ -0,0 +1,67 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::hash::hashv;
+
+/// Domain separation prefixes (prevent leaf/node second-preimage attacks)
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+const ROLLUP_PREFIX: &[u8] = &[2];
+
+/// Hash a fee attestation leaf: sha256(0x00 || slot LE || signature || fee LE)
+pub fn fee_leaf_hash(slot: u64, signature: &[u8; 64], fee: u64) -> [u8; 32] {
+    hashv(&[LEAF_PREFIX, &slot.to_le_bytes(), signature, &fee.to_le_bytes()]).to_bytes()
+}
+
+/// Hash a daily TokenStats snapshot leaf:
+/// sha256(0x00 || mint || total_burned LE || total_sol_collected LE || total_sol_used LE
+///        || total_buybacks LE || usd_value_burned LE)
+pub fn stats_leaf_hash(
+    mint: &Pubkey,
+    total_burned: u64,
+    total_sol_collected: u64,
+    total_sol_used: u64,
+    total_buybacks: u64,
+    usd_value_burned: u64,
+) -> [u8; 32] {
+    hashv(&[
+        LEAF_PREFIX,
+        mint.as_ref(),
+        &total_burned.to_le_bytes(),
+        &total_sol_collected.to_le_bytes(),
+        &total_sol_used.to_le_bytes(),
+        &total_buybacks.to_le_bytes(),
+        &usd_value_burned.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+/// Chain one stats leaf into a global roll-up checksum: sha256(0x02 || prev || leaf)
+/// Starts from [0; 32]; leaves are chained in ascending mint order
+pub fn rollup_checksum(prev: &[u8; 32], leaf: &[u8; 32]) -> [u8; 32] {
+    hashv(&[ROLLUP_PREFIX, prev, leaf]).to_bytes()
+}
+
+/// Hash a holder reflection leaf: sha256(0x00 || epoch LE || holder || amount LE)
+pub fn reflection_leaf_hash(epoch: u64, holder: &Pubkey, amount: u64) -> [u8; 32] {
+    hashv(&[LEAF_PREFIX, &epoch.to_le_bytes(), holder.as_ref(), &amount.to_le_bytes()]).to_bytes()
+}
+
+/// Hash two child nodes: sha256(0x01 || left || right)
+pub fn merkle_node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    hashv(&[NODE_PREFIX, left, right]).to_bytes()
+}
+
+/// Verify an index-ordered Merkle proof (bit i of index = 1 → node is the right child at level i)
+/// Rejects indices that do not fit the proof depth so each index maps to one position
+pub fn verify_merkle_proof(root: &[u8; 32], leaf: [u8; 32], index: u32, proof: &[[u8; 32]]) -> bool {
+    let mut node = leaf;
+    let mut idx = index as u64;
+    for sibling in proof {
+        node = if idx & 1 == 0 {
+            merkle_node_hash(&node, sibling)
+        } else {
+            merkle_node_hash(sibling, &node)
+        };
+        idx >>= 1;
+    }
+    idx == 0 && node == *root
+}
@@ -0,0 +1,129 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::ErrorCode;
+
+/// Metaplex Key::MetadataV1 (first byte of a metadata account)
+const METADATA_KEY_V1: u8 = 4;
+
+/// Borsh sizes of Metaplex Creator (address, verified, share), Collection (verified, key)
+/// and Uses (use_method, remaining, total)
+const CREATOR_LEN: usize = 32 + 1 + 1;
+const COLLECTION_LEN: usize = 1 + 32;
+const USES_LEN: usize = 1 + 8 + 8;
+
+/// Fields of a Metaplex metadata account that update_token_metadata needs
+/// Manual parsing (no mpl-token-metadata dependency) - same approach as parse_mint_risk_flags
+///
+/// Layout: key(1) | update_authority(32) | mint(32) | name | symbol | uri (borsh strings)
+///         | seller_fee_basis_points(2) | creators Option<Vec<Creator>> | primary_sale_happened(1)
+///         | is_mutable(1) | edition_nonce Option<u8> | token_standard Option<u8>
+///         | collection Option<Collection> | uses Option<Uses> | ...
+pub struct MetaplexMetadata<'a> {
+    pub update_authority: Pubkey,
+    pub mint: Pubkey,
+    pub is_mutable: bool,
+    /// seller_fee_basis_points + creators, borsh-encoded as in DataV2
+    pub fee_and_creators: &'a [u8],
+    /// collection + uses, borsh-encoded as in DataV2
+    pub collection_and_uses: &'a [u8],
+}
+
+/// Forward-only borsh reader over raw account data
+struct Reader<'a> {
+    data: &'a [u8],
+    at: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self.at.checked_add(len).ok_or(ErrorCode::InvalidMetadata)?;
+        let bytes = self.data.get(self.at..end).ok_or(ErrorCode::InvalidMetadata)?;
+        self.at = end;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        let bytes: [u8; 4] = self.take(4)?.try_into().map_err(|_| ErrorCode::InvalidMetadata)?;
+        Ok(u32::from_le_bytes(bytes))
+    }
+
+    fn pubkey(&mut self) -> Result<Pubkey> {
+        let bytes: [u8; 32] = self.take(32)?.try_into().map_err(|_| ErrorCode::InvalidMetadata)?;
+        Ok(Pubkey::new_from_array(bytes))
+    }
+
+    /// Skip a borsh String
+    fn skip_string(&mut self) -> Result<()> {
+        let len = self.u32()? as usize;
+        self.take(len)?;
+        Ok(())
+    }
+
+    /// Skip a borsh Option of a fixed-size value
+    fn skip_option(&mut self, len: usize) -> Result<()> {
+        if self.u8()? != 0 {
+            self.take(len)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse a Metaplex metadata account (raw account data)
+pub fn parse_metaplex_metadata(data: &[u8]) -> Result<MetaplexMetadata<'_>> {
+    let mut reader = Reader { data, at: 0 };
+    require!(reader.u8()? == METADATA_KEY_V1, ErrorCode::InvalidMetadata);
+    let update_authority = reader.pubkey()?;
+    let mint = reader.pubkey()?;
+    reader.skip_string()?; // name
+    reader.skip_string()?; // symbol
+    reader.skip_string()?; // uri
+
+    let fee_start = reader.at;
+    reader.take(2)?; // seller_fee_basis_points
+    if reader.u8()? != 0 {
+        let creators = reader.u32()? as usize;
+        reader.take(creators.checked_mul(CREATOR_LEN).ok_or(ErrorCode::InvalidMetadata)?)?;
+    }
+    let fee_end = reader.at;
+
+    reader.take(1)?; // primary_sale_happened
+    let is_mutable = reader.u8()? != 0;
+    reader.skip_option(1)?; // edition_nonce
+    reader.skip_option(1)?; // token_standard
+
+    let collection_start = reader.at;
+    reader.skip_option(COLLECTION_LEN)?;
+    reader.skip_option(USES_LEN)?;
+
+    Ok(MetaplexMetadata {
+        update_authority,
+        mint,
+        is_mutable,
+        fee_and_creators: &data[fee_start..fee_end],
+        collection_and_uses: &data[collection_start..reader.at],
+    })
+}
+
+/// Instruction data for Metaplex UpdateMetadataAccountV2 that replaces name / symbol / uri
+/// Seller fee, creators, collection and uses are re-sent unchanged; update authority,
+/// primary sale and mutability are left as they are (None)
+pub fn update_metadata_v2_data(metadata: &MetaplexMetadata, name: &str, symbol: &str, uri: &str) -> Vec<u8> {
+    let mut data = Vec::with_capacity(
+        2 + 12 + name.len() + symbol.len() + uri.len()
+            + metadata.fee_and_creators.len() + metadata.collection_and_uses.len() + 3,
+    );
+    data.push(MPL_UPDATE_METADATA_ACCOUNT_V2_DISCRIMINATOR);
+    data.push(1); // Some(DataV2)
+    for field in [name, symbol, uri] {
+        data.extend_from_slice(&(field.len() as u32).to_le_bytes());
+        data.extend_from_slice(field.as_bytes());
+    }
+    data.extend_from_slice(metadata.fee_and_creators);
+    data.extend_from_slice(metadata.collection_and_uses);
+    data.extend_from_slice(&[0, 0, 0]); // new_update_authority, primary_sale_happened, is_mutable
+    data
+}
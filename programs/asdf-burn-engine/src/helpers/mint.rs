@@ -0,0 +1,152 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::ErrorCode;
+
+/// Base mint layout size (shared by SPL Token and Token2022)
+pub const MINT_BASE_LEN: usize = 82;
+
+/// Token2022 account type byte offset (extensions pad the base state to 165 bytes)
+const TOKEN_2022_ACCOUNT_TYPE_OFFSET: usize = 165;
+
+/// Token2022 TLV extension data start (after the account type byte)
+const TOKEN_2022_TLV_START: usize = 166;
+
+/// Token2022 AccountType::Mint
+const TOKEN_2022_ACCOUNT_TYPE_MINT: u8 = 1;
+
+/// Token2022 extension type discriminators (spl-token-2022 ExtensionType)
+const EXT_UNINITIALIZED: u16 = 0;
+const EXT_TRANSFER_FEE_CONFIG: u16 = 1;
+const EXT_DEFAULT_ACCOUNT_STATE: u16 = 6;
+const EXT_NON_TRANSFERABLE: u16 = 9;
+const EXT_PERMANENT_DELEGATE: u16 = 12;
+const EXT_TRANSFER_HOOK: u16 = 14;
+const EXT_PAUSABLE: u16 = 26;
+
+/// AccountState::Frozen (DefaultAccountState extension value)
+const ACCOUNT_STATE_FROZEN: u8 = 2;
+
+/// Parse raw mint data and return MINT_RISK_* flags
+/// Manual parsing (no spl-token-2022 dependency) - same approach as deserialize_bonding_curve
+///
+/// Layout:
+/// - [0..82]    base mint (freeze_authority COption<Pubkey> at 46..82)
+/// - [82..165]  zero padding (Token2022 with extensions only)
+/// - [165]      account type (1 = Mint)
+/// - [166..]    TLV entries: type u16 | length u16 | value
+pub fn parse_mint_risk_flags(data: &[u8], is_token_2022: bool) -> Result<u8> {
+    require!(data.len() >= MINT_BASE_LEN, ErrorCode::InvalidMint);
+
+    let mut flags = 0u8;
+
+    // freeze_authority: COption tag (4 bytes, 1 = Some)
+    let freeze_tag = u32::from_le_bytes(
+        data[46..50].try_into().map_err(|_| ErrorCode::InvalidMint)?
+    );
+    if freeze_tag != 0 {
+        flags |= MINT_RISK_FREEZE_AUTHORITY;
+    }
+
+    // SPL Token mints (and Token2022 mints without extensions) stop here
+    if !is_token_2022 || data.len() == MINT_BASE_LEN {
+        return Ok(flags);
+    }
+
+    require!(
+        data.len() > TOKEN_2022_TLV_START
+            && data[TOKEN_2022_ACCOUNT_TYPE_OFFSET] == TOKEN_2022_ACCOUNT_TYPE_MINT,
+        ErrorCode::InvalidMint
+    );
+
+    let mut offset = TOKEN_2022_TLV_START;
+    while offset + 4 <= data.len() {
+        let ext_type = u16::from_le_bytes([data[offset], data[offset + 1]]);
+        let ext_len = u16::from_le_bytes([data[offset + 2], data[offset + 3]]) as usize;
+
+        // Uninitialized type marks the end of TLV data
+        if ext_type == EXT_UNINITIALIZED {
+            break;
+        }
+
+        let value_start = offset + 4;
+        let value_end = value_start.checked_add(ext_len).ok_or(ErrorCode::MathOverflow)?;
+        require!(value_end <= data.len(), ErrorCode::InvalidMint);
+        let value = &data[value_start..value_end];
+
+        match ext_type {
+            EXT_TRANSFER_FEE_CONFIG => flags |= MINT_RISK_TRANSFER_FEE,
+            EXT_DEFAULT_ACCOUNT_STATE if value.first() == Some(&ACCOUNT_STATE_FROZEN) => {
+                flags |= MINT_RISK_DEFAULT_FROZEN
+            }
+            EXT_NON_TRANSFERABLE => flags |= MINT_RISK_NON_TRANSFERABLE,
+            // OptionalNonZeroPubkey: all zeros = no delegate
+            EXT_PERMANENT_DELEGATE if value.iter().any(|b| *b != 0) => {
+                flags |= MINT_RISK_PERMANENT_DELEGATE
+            }
+            // authority (32) + program_id (32): hook only active when program_id is set
+            EXT_TRANSFER_HOOK if value.len() >= 64 && value[32..64].iter().any(|b| *b != 0) => {
+                flags |= MINT_RISK_TRANSFER_HOOK
+            }
+            EXT_PAUSABLE => flags |= MINT_RISK_PAUSABLE,
+            _ => {}
+        }
+
+        offset = value_end;
+    }
+
+    #[cfg(feature = "verbose")]
+    msg!("Mint risk flags: {:#010b}", flags);
+
+    Ok(flags)
+}
+
+/// TransferFeeConfig value layout: config authority (32), withdraw authority (32),
+/// withheld_amount (8), then older and newer TransferFee { epoch u64, maximum_fee u64, bps u16 }
+const TRANSFER_FEE_OLDER_OFFSET: usize = 72;
+const TRANSFER_FEE_LEN: usize = 18;
+
+/// Token2022 transfer fee: ceil(amount * bps / 10000), capped at maximum_fee
+pub fn calculate_transfer_fee(amount: u64, fee_bps: u16, maximum_fee: u64) -> u64 {
+    if fee_bps == 0 || amount == 0 {
+        return 0;
+    }
+    let fee = (amount as u128 * fee_bps as u128).div_ceil(10_000);
+    fee.min(maximum_fee as u128) as u64
+}
+
+/// Fee withheld when `amount` of this mint is transferred during `epoch`
+/// (0 for SPL Token mints and Token2022 mints without TransferFeeConfig)
+pub fn transfer_fee_for(data: &[u8], is_token_2022: bool, epoch: u64, amount: u64) -> Result<u64> {
+    if !is_token_2022 || data.len() <= TOKEN_2022_TLV_START {
+        return Ok(0);
+    }
+
+    let mut offset = TOKEN_2022_TLV_START;
+    while offset + 4 <= data.len() {
+        let ext_type = u16::from_le_bytes([data[offset], data[offset + 1]]);
+        let ext_len = u16::from_le_bytes([data[offset + 2], data[offset + 3]]) as usize;
+        if ext_type == EXT_UNINITIALIZED {
+            break;
+        }
+
+        let value_start = offset + 4;
+        let value_end = value_start.checked_add(ext_len).ok_or(ErrorCode::MathOverflow)?;
+        require!(value_end <= data.len(), ErrorCode::InvalidMint);
+
+        if ext_type == EXT_TRANSFER_FEE_CONFIG {
+            require!(ext_len >= TRANSFER_FEE_OLDER_OFFSET + 2 * TRANSFER_FEE_LEN, ErrorCode::InvalidMint);
+            let older = value_start + TRANSFER_FEE_OLDER_OFFSET;
+            let newer = older + TRANSFER_FEE_LEN;
+            let newer_epoch = u64::from_le_bytes(data[newer..newer + 8].try_into().map_err(|_| ErrorCode::InvalidMint)?);
+            // The newer fee takes effect at its epoch (set two epochs ahead by the fee authority)
+            let fee = if epoch >= newer_epoch { newer } else { older };
+            let maximum_fee = u64::from_le_bytes(data[fee + 8..fee + 16].try_into().map_err(|_| ErrorCode::InvalidMint)?);
+            let fee_bps = u16::from_le_bytes([data[fee + 16], data[fee + 17]]);
+            return Ok(calculate_transfer_fee(amount, fee_bps, maximum_fee));
+        }
+
+        offset = value_end;
+    }
+
+    Ok(0)
+}
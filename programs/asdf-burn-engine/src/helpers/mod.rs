@@ -1,5 +1,19 @@
 pub mod cpi;
+pub mod ed25519;
 pub mod math;
+pub mod merkle;
+pub mod metadata;
+pub mod mint;
+pub mod oracle;
+pub mod randomness;
+pub mod venue;
 
 pub use cpi::*;
+pub use ed25519::*;
 pub use math::*;
+pub use merkle::*;
+pub use metadata::*;
+pub use mint::*;
+pub use oracle::*;
+pub use randomness::*;
+pub use venue::*;
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::ErrorCode;
+
+/// Pyth PriceUpdateV2 field offsets (8-byte discriminator included)
+/// Layout: discriminator(8) | write_authority(32) | verification_level(1-2) | PriceFeedMessage | posted_slot(8)
+const VERIFICATION_LEVEL_OFFSET: usize = 40;
+
+/// VerificationLevel::Full tag (Partial carries an extra num_signatures byte)
+const VERIFICATION_LEVEL_FULL: u8 = 1;
+
+/// PriceFeedMessage start when verification is Full
+const PRICE_MESSAGE_OFFSET: usize = 41;
+
+/// PriceFeedMessage: feed_id(32) | price(8) | conf(8) | exponent(4) | publish_time(8) | ...
+const PRICE_MESSAGE_MIN_LEN: usize = 32 + 8 + 8 + 4 + 8;
+
+/// Oracle price reading (price * 10^exponent = USD per SOL)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OraclePrice {
+    pub price: i64,
+    pub exponent: i32,
+    pub publish_time: i64,
+}
+
+/// Parse a Pyth PriceUpdateV2 account for the SOL/USD feed
+/// Manual parsing (no pyth-solana-receiver-sdk dependency) - same approach as parse_mint_risk_flags
+/// Requires Full verification, the SOL/USD feed id, a positive price and
+/// publish_time no older than ORACLE_MAX_AGE_SECONDS
+pub fn parse_sol_usd_price(data: &[u8], now: i64) -> Result<OraclePrice> {
+    require!(
+        data.len() >= PRICE_MESSAGE_OFFSET + PRICE_MESSAGE_MIN_LEN,
+        ErrorCode::InvalidOracle
+    );
+    require!(data[..8] == PYTH_PRICE_UPDATE_DISCRIMINATOR, ErrorCode::InvalidOracle);
+    require!(
+        data[VERIFICATION_LEVEL_OFFSET] == VERIFICATION_LEVEL_FULL,
+        ErrorCode::InvalidOracle
+    );
+
+    let msg = &data[PRICE_MESSAGE_OFFSET..];
+    require!(msg[..32] == SOL_USD_FEED_ID, ErrorCode::InvalidOracle);
+
+    let price = i64::from_le_bytes(msg[32..40].try_into().map_err(|_| ErrorCode::InvalidOracle)?);
+    let exponent = i32::from_le_bytes(msg[48..52].try_into().map_err(|_| ErrorCode::InvalidOracle)?);
+    let publish_time = i64::from_le_bytes(msg[52..60].try_into().map_err(|_| ErrorCode::InvalidOracle)?);
+
+    require!(price > 0, ErrorCode::InvalidOracle);
+    require!(
+        now.saturating_sub(publish_time) <= ORACLE_MAX_AGE_SECONDS,
+        ErrorCode::OraclePriceStale
+    );
+
+    #[cfg(feature = "verbose")]
+    msg!("SOL/USD oracle: price={} expo={} publish_time={}", price, exponent, publish_time);
+
+    Ok(OraclePrice { price, exponent, publish_time })
+}
+
+/// Convert lamports to micro-USD (6 decimals) using a SOL/USD oracle price
+/// usd_micro = lamports * price * 10^(exponent + 6) / 10^9
+pub fn lamports_to_usd_micro(lamports: u64, oracle: &OraclePrice) -> Result<u64> {
+    require!(oracle.price > 0, ErrorCode::InvalidOracle);
+
+    let mut value = (lamports as u128)
+        .checked_mul(oracle.price as u128)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let scale = oracle.exponent + USD_DECIMALS as i32 - 9;
+    require!(scale.abs() <= 30, ErrorCode::InvalidOracle);
+    if scale >= 0 {
+        value = value
+            .checked_mul(10u128.pow(scale as u32))
+            .ok_or(ErrorCode::MathOverflow)?;
+    } else {
+        value /= 10u128.pow((-scale) as u32);
+    }
+
+    u64::try_from(value).map_err(|_| ErrorCode::MathOverflow.into())
+}
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use crate::constants::SWITCHBOARD_RANDOMNESS_DISCRIMINATOR;
+use crate::errors::ErrorCode;
+
+/// SlotHashes sysvar entry: slot (u64 LE) + bank hash (32 bytes)
+const SLOT_HASH_ENTRY_LEN: usize = 40;
+
+/// Bank hash of the first produced slot at or after `slot`, from raw SlotHashes sysvar data
+/// (u64 LE entry count, then entries newest first). None if not yet produced, or if it may
+/// have been evicted (every remaining entry is newer and none matches `slot` exactly)
+pub fn slot_hash_at_or_after(data: &[u8], slot: u64) -> Option<(u64, [u8; 32])> {
+    let count = u64::from_le_bytes(data.get(..8)?.try_into().ok()?) as usize;
+    let mut found = None;
+    for i in 0..count {
+        let start = 8 + i * SLOT_HASH_ENTRY_LEN;
+        let entry = data.get(start..start + SLOT_HASH_ENTRY_LEN)?;
+        let entry_slot = u64::from_le_bytes(entry[..8].try_into().ok()?);
+        if entry_slot < slot {
+            return found;
+        }
+        let mut hash = [0u8; 32];
+        hash.copy_from_slice(&entry[8..]);
+        found = Some((entry_slot, hash));
+    }
+    found.filter(|(entry_slot, _)| *entry_slot == slot)
+}
+
+/// Order-sensitive commitment to a draw's candidate list
+pub fn draw_candidates_hash(candidates: &[Pubkey]) -> [u8; 32] {
+    let keys: Vec<&[u8]> = candidates.iter().map(|k| k.as_ref()).collect();
+    hashv(&keys).to_bytes()
+}
+
+/// Draw seed: sha256(slot_hash || candidates_hash || draw nonce LE)
+pub fn draw_seed(slot_hash: &[u8; 32], candidates_hash: &[u8; 32], nonce: u64) -> [u8; 32] {
+    hashv(&[slot_hash, candidates_hash, &nonce.to_le_bytes()]).to_bytes()
+}
+
+/// Pick an index with probability proportional to its weight (None if all weights are 0)
+pub fn weighted_index(weights: &[u64], seed: &[u8; 32]) -> Option<usize> {
+    let total: u128 = weights.iter().map(|w| *w as u128).sum();
+    if total == 0 {
+        return None;
+    }
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&seed[..16]);
+    let target = u128::from_le_bytes(bytes) % total;
+
+    let mut cumulative = 0u128;
+    weights.iter().position(|w| {
+        cumulative += *w as u128;
+        target < cumulative
+    })
+}
+
+/// Switchboard On-Demand RandomnessAccountData offsets (8-byte discriminator included)
+/// Layout: discriminator(8) | authority(32) | queue(32) | seed_slothash(32) | seed_slot(8)
+///         | oracle(32) | reveal_slot(8) | value(32) | ...
+const SB_SEED_SLOT_OFFSET: usize = 104;
+const SB_REVEAL_SLOT_OFFSET: usize = 144;
+const SB_VALUE_OFFSET: usize = 152;
+
+/// Switchboard randomness reading
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SwitchboardRandomness {
+    /// Slot the randomness was committed in
+    pub seed_slot: u64,
+    /// Slot the oracle revealed `value` in (older than seed_slot = not yet revealed)
+    pub reveal_slot: u64,
+    pub value: [u8; 32],
+}
+
+impl SwitchboardRandomness {
+    /// Whether the oracle revealed a value for the current commitment
+    pub fn is_revealed(&self) -> bool {
+        self.reveal_slot >= self.seed_slot && self.reveal_slot > 0
+    }
+}
+
+/// Parse a Switchboard On-Demand RandomnessAccountData account
+/// Manual parsing (no switchboard-on-demand dependency) - same approach as parse_sol_usd_price
+pub fn parse_switchboard_randomness(data: &[u8]) -> Result<SwitchboardRandomness> {
+    require!(data.len() >= SB_VALUE_OFFSET + 32, ErrorCode::InvalidRandomnessAccount);
+    require!(data[..8] == SWITCHBOARD_RANDOMNESS_DISCRIMINATOR, ErrorCode::InvalidRandomnessAccount);
+
+    let read_u64 = |at: usize| -> Result<u64> {
+        Ok(u64::from_le_bytes(data[at..at + 8].try_into().map_err(|_| ErrorCode::InvalidRandomnessAccount)?))
+    };
+    let mut value = [0u8; 32];
+    value.copy_from_slice(&data[SB_VALUE_OFFSET..SB_VALUE_OFFSET + 32]);
+
+    Ok(SwitchboardRandomness {
+        seed_slot: read_u64(SB_SEED_SLOT_OFFSET)?,
+        reveal_slot: read_u64(SB_REVEAL_SLOT_OFFSET)?,
+        value,
+    })
+}
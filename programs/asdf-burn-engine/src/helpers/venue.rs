@@ -0,0 +1,173 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::helpers::math::{calculate_tokens_out, calculate_tokens_out_pumpfun, deserialize_bonding_curve};
+
+/// PumpFun BondingCurve: discriminator(8) | 5 u64 reserves/supply (40) | complete(1) | creator(32)
+const BONDING_CURVE_COMPLETE_OFFSET: usize = 48;
+const BONDING_CURVE_CREATOR_OFFSET: usize = 49;
+
+/// PumpSwap Pool field offsets (8-byte discriminator included)
+/// Layout: discriminator(8) | pool_bump(1) | index(2) | creator(32) | base_mint(32) | quote_mint(32)
+///         | lp_mint(32) | pool_base_token_account(32) | pool_quote_token_account(32)
+///         | lp_supply(8) | coin_creator(32)
+const POOL_BASE_MINT_OFFSET: usize = 43;
+const POOL_QUOTE_MINT_OFFSET: usize = 75;
+const POOL_BASE_TOKEN_ACCOUNT_OFFSET: usize = 139;
+const POOL_QUOTE_TOKEN_ACCOUNT_OFFSET: usize = 171;
+const POOL_MIN_LEN: usize = 203;
+const POOL_COIN_CREATOR_OFFSET: usize = 211;
+
+/// PumpFun Global field offsets (8-byte discriminator included)
+/// Layout: discriminator(8) | initialized(1) | authority(32) | fee_recipient(32) | 5 u64 (40)
+///         | withdraw_authority(32) | enable_migrate(1) | pool_migration_fee(8)
+///         | creator_fee_basis_points(8) | fee_recipients([Pubkey; 7]) | ...
+const PUMP_GLOBAL_DISCRIMINATOR: [u8; 8] = [167, 232, 232, 177, 200, 108, 114, 127];
+const GLOBAL_FEE_RECIPIENT_OFFSET: usize = 41;
+const GLOBAL_FEE_RECIPIENTS_OFFSET: usize = 162;
+const GLOBAL_EXTRA_FEE_RECIPIENTS: usize = 7;
+const GLOBAL_MIN_LEN: usize = GLOBAL_FEE_RECIPIENTS_OFFSET + 32 * GLOBAL_EXTRA_FEE_RECIPIENTS;
+
+/// A venue's quote for spending sol_in on the token
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VenueQuote {
+    /// VENUE_* identifier
+    pub venue: u8,
+    /// Bonding curve or pool account quoted
+    pub account: Pubkey,
+    /// Tokens received for sol_in (fees included)
+    pub tokens_out: u64,
+}
+
+/// PumpSwap pool accounts needed to quote and execute a buy
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PumpSwapPoolInfo {
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub pool_base_token_account: Pubkey,
+    pub pool_quote_token_account: Pubkey,
+}
+
+impl PumpSwapPoolInfo {
+    /// Require the provided mints and vaults to be the ones this pool trades
+    pub fn check_accounts(
+        &self,
+        base_mint: &Pubkey,
+        quote_mint: &Pubkey,
+        pool_base_token_account: &Pubkey,
+        pool_quote_token_account: &Pubkey,
+    ) -> Result<()> {
+        require!(self.base_mint == *base_mint, ErrorCode::PoolBaseMintMismatch);
+        require!(self.quote_mint == *quote_mint, ErrorCode::PoolQuoteMintMismatch);
+        require!(self.pool_base_token_account == *pool_base_token_account, ErrorCode::PoolBaseVaultMismatch);
+        require!(self.pool_quote_token_account == *pool_quote_token_account, ErrorCode::PoolQuoteVaultMismatch);
+        Ok(())
+    }
+}
+
+fn read_pubkey(data: &[u8], at: usize) -> Result<Pubkey> {
+    let bytes: [u8; 32] = data[at..at + 32].try_into().map_err(|_| ErrorCode::InvalidPool)?;
+    Ok(Pubkey::new_from_array(bytes))
+}
+
+/// Quote a PumpFun bonding curve (raw account data, discriminator included)
+/// Returns None once the curve is complete (migrated, no longer tradable)
+pub fn quote_bonding_curve(account: Pubkey, data: &[u8], sol_in: u64) -> Result<Option<VenueQuote>> {
+    require!(data.len() > BONDING_CURVE_COMPLETE_OFFSET, ErrorCode::InvalidPool);
+    if data[BONDING_CURVE_COMPLETE_OFFSET] != 0 {
+        return Ok(None);
+    }
+
+    let (virtual_token_reserves, virtual_sol_reserves) = deserialize_bonding_curve(&data[8..])?;
+    let tokens_out = calculate_tokens_out_pumpfun(sol_in, virtual_sol_reserves, virtual_token_reserves)?;
+
+    Ok(Some(VenueQuote { venue: VENUE_BONDING_CURVE, account, tokens_out }))
+}
+
+/// Parse the vault accounts of a PumpSwap pool (raw account data, discriminator included)
+pub fn parse_pumpswap_pool(data: &[u8]) -> Result<PumpSwapPoolInfo> {
+    require!(data.len() >= POOL_MIN_LEN, ErrorCode::InvalidPool);
+
+    Ok(PumpSwapPoolInfo {
+        base_mint: read_pubkey(data, POOL_BASE_MINT_OFFSET)?,
+        quote_mint: read_pubkey(data, POOL_QUOTE_MINT_OFFSET)?,
+        pool_base_token_account: read_pubkey(data, POOL_BASE_TOKEN_ACCOUNT_OFFSET)?,
+        pool_quote_token_account: read_pubkey(data, POOL_QUOTE_TOKEN_ACCOUNT_OFFSET)?,
+    })
+}
+
+/// coin_creator of a PumpSwap pool (raw account data, discriminator included)
+/// Receives the pool's creator fees; default until set_coin_creator runs after migration
+pub fn pumpswap_pool_coin_creator(data: &[u8]) -> Result<Pubkey> {
+    require!(data.len() >= POOL_COIN_CREATOR_OFFSET + 32, ErrorCode::InvalidPool);
+    read_pubkey(data, POOL_COIN_CREATOR_OFFSET)
+}
+
+/// Creator of a PumpFun bonding curve and whether it is complete (raw account data, discriminator included)
+pub fn bonding_curve_creator(data: &[u8]) -> Result<(Pubkey, bool)> {
+    require!(data.len() >= BONDING_CURVE_CREATOR_OFFSET + 32, ErrorCode::InvalidBondingCurve);
+    Ok((read_pubkey(data, BONDING_CURVE_CREATOR_OFFSET)?, data[BONDING_CURVE_COMPLETE_OFFSET] != 0))
+}
+
+/// Creator of a completed (migrated) PumpFun bonding curve (raw account data, discriminator included)
+pub fn migrated_curve_creator(data: &[u8]) -> Result<Pubkey> {
+    let (creator, complete) = bonding_curve_creator(data)?;
+    require!(complete, ErrorCode::CurveNotMigrated);
+    Ok(creator)
+}
+
+/// PumpFun creator vault PDA for `creator`: ["creator-vault", creator] under PUMP_PROGRAM
+pub fn pump_creator_vault(creator: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[PUMP_CREATOR_VAULT_SEED, creator.as_ref()], &PUMP_PROGRAM).0
+}
+
+/// Protocol fee recipients configured in the PumpFun Global account (raw data, discriminator included)
+/// fee_recipient followed by the non-empty fee_recipients entries, in account order
+pub fn parse_pump_global_fee_recipients(data: &[u8]) -> Result<Vec<Pubkey>> {
+    require!(
+        data.len() >= GLOBAL_MIN_LEN && data[..8] == PUMP_GLOBAL_DISCRIMINATOR,
+        ErrorCode::InvalidPumpGlobalConfig
+    );
+
+    let mut recipients = Vec::with_capacity(1 + GLOBAL_EXTRA_FEE_RECIPIENTS);
+    recipients.push(read_pubkey(data, GLOBAL_FEE_RECIPIENT_OFFSET)?);
+    for i in 0..GLOBAL_EXTRA_FEE_RECIPIENTS {
+        let recipient = read_pubkey(data, GLOBAL_FEE_RECIPIENTS_OFFSET + 32 * i)?;
+        if recipient != Pubkey::default() && !recipients.contains(&recipient) {
+            recipients.push(recipient);
+        }
+    }
+    Ok(recipients)
+}
+
+/// Balance of an SPL Token / Token2022 account passed as a raw AccountInfo
+pub fn token_account_amount(account: &AccountInfo) -> Result<u64> {
+    require!(
+        account.owner == &anchor_spl::token::ID || account.owner == &anchor_spl::token_2022::ID,
+        ErrorCode::InvalidPool
+    );
+    let token_account = TokenAccount::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+    Ok(token_account.amount)
+}
+
+/// Quote a PumpSwap pool from its vault balances (market cap fee tiers applied)
+pub fn quote_pumpswap_pool(
+    account: Pubkey,
+    base_reserves: u64,
+    quote_reserves: u64,
+    supply: u64,
+    sol_in: u64,
+) -> Result<VenueQuote> {
+    let tokens_out = calculate_tokens_out(sol_in, quote_reserves, base_reserves, supply)?;
+    Ok(VenueQuote { venue: VENUE_PUMPSWAP, account, tokens_out })
+}
+
+/// Pick the venue yielding the most tokens for the same lamports
+/// Ties keep the earliest quote (bonding curve is quoted first)
+pub fn best_venue_quote(quotes: &[VenueQuote]) -> Option<VenueQuote> {
+    quotes.iter().fold(None, |best: Option<VenueQuote>, q| match best {
+        Some(b) if b.tokens_out >= q.tokens_out => Some(b),
+        _ => Some(*q),
+    })
+}
@@ -0,0 +1,108 @@
+//! Stable CPI surface for third-party programs (feature "cpi")
+//!
+//! The Anchor-generated `crate::cpi` module mirrors every instruction exactly,
+//! including optional accounts added over time. This module pins the fee deposit
+//! integration to the accounts an integrator actually has to provide, and
+//! derives the protocol PDAs so callers never hand-roll seeds or discriminators.
+//!
+//! ```toml
+//! asdf_dat = { package = "asdf-burn-engine", version = "0.1", features = ["cpi"] }
+//! ```
+use anchor_lang::prelude::*;
+
+/// Protocol PDA derivation (see crate::client for the full set with bumps)
+pub mod pda {
+    use super::*;
+    use crate::client;
+
+    pub fn dat_state() -> Pubkey {
+        client::find_dat_state().0
+    }
+
+    pub fn dat_authority() -> Pubkey {
+        client::find_dat_authority().0
+    }
+
+    pub fn rebate_pool() -> Pubkey {
+        client::find_rebate_pool().0
+    }
+
+    pub fn user_stats(user: &Pubkey) -> Pubkey {
+        client::find_user_stats(user).0
+    }
+
+    pub fn app_stats(app_id: u32) -> Pubkey {
+        client::find_app_stats(app_id).0
+    }
+
+    pub fn referral_stats(referrer: &Pubkey) -> Pubkey {
+        client::find_referral_stats(referrer).0
+    }
+}
+
+/// Accounts for an $ASDF fee deposit
+pub struct DepositFeeAsdf<'info> {
+    /// pda::dat_state()
+    pub dat_state: AccountInfo<'info>,
+    /// pda::dat_authority()
+    pub dat_authority: AccountInfo<'info>,
+    /// pda::rebate_pool() (mut)
+    pub rebate_pool: AccountInfo<'info>,
+    /// pda::user_stats(user) (mut, created on first deposit)
+    pub user_stats: AccountInfo<'info>,
+    /// User credited with the contribution
+    pub user: AccountInfo<'info>,
+    /// Payer's $ASDF account (mut)
+    pub payer_token_account: AccountInfo<'info>,
+    /// dat_authority's $ASDF account (mut)
+    pub dat_asdf_account: AccountInfo<'info>,
+    /// rebate_pool's $ASDF account (mut)
+    pub rebate_pool_ata: AccountInfo<'info>,
+    /// $ASDF mint
+    pub asdf_mint: AccountInfo<'info>,
+    /// Signer funding the deposit and the UserStats rent (mut)
+    pub payer: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    /// pda::app_stats(app_id) to attribute the deposit to a registered app (mut)
+    pub app_stats: Option<AccountInfo<'info>>,
+    /// pda::referral_stats(referrer) - required when the user has a referrer (mut)
+    pub referral_stats: Option<AccountInfo<'info>>,
+}
+
+/// Deposit `amount` $ASDF: 99.448% to the root cycle burn, 0.552% to the rebate pool
+/// `signer_seeds` signs for `payer` when it is a PDA of the calling program
+pub fn deposit_fee_asdf<'info>(
+    program: AccountInfo<'info>,
+    accounts: DepositFeeAsdf<'info>,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let cpi_accounts = crate::cpi::accounts::DepositFeeAsdf {
+        dat_state: accounts.dat_state,
+        dat_authority: accounts.dat_authority,
+        rebate_pool: accounts.rebate_pool,
+        user_stats: accounts.user_stats,
+        user: accounts.user,
+        payer_token_account: accounts.payer_token_account,
+        dat_asdf_account: accounts.dat_asdf_account,
+        rebate_pool_ata: accounts.rebate_pool_ata,
+        asdf_mint: accounts.asdf_mint,
+        payer: accounts.payer,
+        token_program: accounts.token_program,
+        system_program: accounts.system_program,
+        user_ata: None,
+        target_token_stats: None,
+        rebate_epoch: None,
+        previous_rebate_epoch: None,
+        app_stats: accounts.app_stats,
+        referral_stats: accounts.referral_stats,
+    };
+
+    crate::cpi::deposit_fee_asdf(
+        CpiContext::new_with_signer(program, cpi_accounts, signer_seeds),
+        amount,
+        None,
+    )?;
+    Ok(())
+}
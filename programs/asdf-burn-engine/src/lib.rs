@@ -1,10 +1,12 @@
+// Anchor 0.31's generated IDL resize handler still calls the deprecated AccountInfo::realloc
+#![allow(deprecated)]
+
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::instruction::Instruction;
 use anchor_lang::solana_program::program::invoke_signed;
 use anchor_spl::{
     token,
     token_interface::{self as token_interface, TokenInterface, TokenAccount, Mint},
-    associated_token::AssociatedToken,
 };
 
 // Include unit tests module (only compiled when running tests)
@@ -21,14 +23,24 @@ pub mod contexts;
 pub mod errors;
 pub mod events;
 pub mod helpers;
+pub mod migrations;
 pub mod state;
 
+// Off-chain Rust client: PDA helpers and instruction builders
+#[cfg(any(test, feature = "no-entrypoint"))]
+pub mod client;
+
+// Stable CPI surface for third-party programs
+#[cfg(feature = "cpi")]
+pub mod integration;
+
 // Re-export for external access
 pub use constants::*;
 pub use contexts::*;
 pub use errors::ErrorCode;  // Explicit import to avoid ambiguity with anchor_lang
 pub use events::*;
 pub use helpers::*;
+pub use migrations::AccountKind;
 pub use state::*;
 
 declare_id!("ASDFc5hkEM2MF8mrAAtCPieV6x6h1B5BwjgztFt7Xbui");
@@ -41,50 +53,117 @@ declare_id!("ASDFc5hkEM2MF8mrAAtCPieV6x6h1B5BwjgztFt7Xbui");
 /// CORRECT 16-account format based on successful devnet tx 3Rqh43z2...
 #[inline(never)]
 fn build_account_infos_root<'info>(accounts: &ExecuteBuy<'info>) -> Vec<AccountInfo<'info>> {
-    let mut accs = Vec::with_capacity(16);
-    accs.push(accounts.pump_global_config.to_account_info());      // 0
-    accs.push(accounts.protocol_fee_recipient.to_account_info());  // 1
-    accs.push(accounts.asdf_mint.to_account_info());               // 2
-    accs.push(accounts.pool.to_account_info());                    // 3
-    accs.push(accounts.pool_asdf_account.to_account_info());       // 4
-    accs.push(accounts.dat_asdf_account.to_account_info());        // 5
-    accs.push(accounts.dat_authority.to_account_info());           // 6
-    accs.push(accounts.system_program.to_account_info());          // 7
-    accs.push(accounts.token_program.to_account_info());           // 8 - token_program BEFORE creator_vault!
-    accs.push(accounts.creator_vault.to_account_info());           // 9 - creator_vault AFTER token_program!
-    accs.push(accounts.pump_event_authority.to_account_info());    // 10
-    accs.push(accounts.pump_swap_program.to_account_info());       // 11
-    accs.push(accounts.global_volume_accumulator.to_account_info()); // 12
-    accs.push(accounts.user_volume_accumulator.to_account_info());   // 13
-    accs.push(accounts.fee_config.to_account_info());              // 14
-    accs.push(accounts.fee_program.to_account_info());             // 15
-    accs
+    vec![
+        accounts.pump_global_config.to_account_info(),      // 0
+        accounts.protocol_fee_recipient.to_account_info(),  // 1
+        accounts.asdf_mint.to_account_info(),               // 2
+        accounts.pool.to_account_info(),                    // 3
+        accounts.pool_asdf_account.to_account_info(),       // 4
+        accounts.dat_asdf_account.to_account_info(),        // 5
+        accounts.dat_authority.to_account_info(),           // 6
+        accounts.system_program.to_account_info(),          // 7
+        accounts.token_program.to_account_info(),           // 8 - token_program BEFORE creator_vault!
+        accounts.creator_vault.to_account_info(),           // 9 - creator_vault AFTER token_program!
+        accounts.pump_event_authority.to_account_info(),    // 10
+        accounts.pump_swap_program.to_account_info(),       // 11
+        accounts.global_volume_accumulator.to_account_info(), // 12
+        accounts.user_volume_accumulator.to_account_info(),   // 13
+        accounts.fee_config.to_account_info(),              // 14
+        accounts.fee_program.to_account_info(),             // 15
+    ]
+}
+
+/// Validate protocol_fee_recipient against this cycle's rotation entry
+/// The entry must be a recipient configured in the PumpFun Global account, whose
+/// list is also the rotation when no FeeRecipients account is set.
+/// Returns the next rotation index, or None for a Mayhem Mode curve, which pays
+/// MAYHEM_FEE_RECIPIENT through Token2022 and does not consume a rotation slot
+fn check_protocol_fee_recipient(
+    recipient: &Pubkey,
+    configured: Option<&[Pubkey]>,
+    pump_global_config: &AccountInfo,
+    index: u8,
+    token_program: &Pubkey,
+    is_mayhem: bool,
+) -> Result<Option<u8>> {
+    if is_mayhem {
+        require!(
+            *recipient == MAYHEM_FEE_RECIPIENT && *token_program == TOKEN_2022_PROGRAM,
+            ErrorCode::InvalidFeeRecipient
+        );
+        return Ok(None);
+    }
+
+    let global_recipients = parse_pump_global_fee_recipients(&pump_global_config.try_borrow_data()?)?;
+    let active = configured.filter(|list| !list.is_empty()).unwrap_or(&global_recipients);
+    let (expected, next) = fee_recipient_rotation(active, index);
+    require!(
+        *recipient == expected && global_recipients.contains(recipient),
+        ErrorCode::InvalidFeeRecipient
+    );
+    Ok(Some(next))
 }
 
 /// Inner execute buy logic - uses Vec on heap to avoid stack overflow
+/// Takes the accounts struct so execute_buy and execute_buy_tranche share the CPI path
+/// max_price: optional bound on the post-CPI effective price (see check_max_price)
 #[inline(never)]
-fn execute_buy_inner(ctx: Context<ExecuteBuy>, buy_amount: u64) -> Result<()> {
-    let bump = ctx.accounts.dat_state.dat_authority_bump;
-    let max_fees = ctx.accounts.dat_state.max_fees_per_cycle;
-    let slippage = ctx.accounts.dat_state.slippage_bps;
+fn execute_buy_inner(accounts: &mut ExecuteBuy, buy_amount: u64, max_price: Option<u64>) -> Result<()> {
+    require_compute_remaining(CU_PHASE_PUMPFUN_BUY)?;
+
+    let state = accounts.dat_state.load()?;
+    let bump = state.dat_authority_bump;
+    let max_fees = state.max_fees_per_cycle;
+    let slippage = state.slippage_bps;
+    let fee_recipient_index = state.current_fee_recipient_index;
+
+    // Price circuit breaker only tracks the root token (last_known_price is a single observation)
+    let is_root = state.root_token_mint() == Some(accounts.asdf_mint.key());
+    let (last_price, max_deviation) = if is_root {
+        (state.last_known_price, state.max_price_deviation_bps)
+    } else {
+        (0, 0)
+    };
+    drop(state);
 
     // NOTE: reload() required before reading pool state - Anchor doesn't auto-reload for manual invoke_signed CPI
-    ctx.accounts.pool_asdf_account.reload()?;
-    let pool_data = ctx.accounts.pool.try_borrow_data()?.to_vec();
-    let (max_sol_cost, desired_tokens) = calculate_buy_amount_and_slippage(buy_amount, &pool_data, max_fees, slippage)?;
+    accounts.pool_asdf_account.reload()?;
+    let pool_data = accounts.pool.try_borrow_data()?.to_vec();
+    let (max_sol_cost, desired_tokens, execution_price) = calculate_buy_amount_and_slippage(
+        buy_amount, &pool_data, max_fees, slippage, last_price, max_deviation,
+    )?;
+
+    // Shadow mode: compute the candidate math from the same pool snapshot (never executed)
+    let shadow_enabled = accounts.shadow_comparison.as_ref().is_some_and(|c| c.shadow_math);
+    let shadow = if shadow_enabled {
+        Some(calculate_buy_exact_out(buy_amount, &pool_data, max_fees, slippage)?)
+    } else {
+        None
+    };
+    let balance_before = accounts.dat_asdf_account.amount;
+    let lamports_before = accounts.dat_authority.lamports();
+
+    let next_recipient_index = check_protocol_fee_recipient(
+        accounts.protocol_fee_recipient.key,
+        accounts.fee_recipients.as_ref().map(|list| list.active()),
+        &accounts.pump_global_config,
+        fee_recipient_index,
+        &accounts.token_program.key(),
+        is_mayhem_bonding_curve(&pool_data),
+    )?;
 
     // Build account infos on heap in separate stack frame
-    let accs = build_account_infos_root(&ctx.accounts);
+    let accs = build_account_infos_root(accounts);
 
     let seeds: &[&[u8]] = &[DAT_AUTHORITY_SEED, &[bump]];
     execute_pumpfun_cpi(
-        ctx.accounts.pump_global_config.key(),
-        ctx.accounts.protocol_fee_recipient.key(),
-        ctx.accounts.asdf_mint.key(),
-        ctx.accounts.pool.key(),
-        ctx.accounts.pool_asdf_account.key(),
-        ctx.accounts.dat_asdf_account.key(),
-        ctx.accounts.dat_authority.key(),
+        accounts.pump_global_config.key(),
+        accounts.protocol_fee_recipient.key(),
+        accounts.asdf_mint.key(),
+        accounts.pool.key(),
+        accounts.pool_asdf_account.key(),
+        accounts.dat_asdf_account.key(),
+        accounts.dat_authority.key(),
         max_sol_cost,
         desired_tokens,
         &accs,
@@ -92,9 +171,57 @@ fn execute_buy_inner(ctx: Context<ExecuteBuy>, buy_amount: u64) -> Result<()> {
     )?;
 
     // NOTE: reload() required after CPI to get updated token balance - Anchor doesn't auto-reload for invoke_signed
-    ctx.accounts.dat_asdf_account.reload()?;
-    ctx.accounts.dat_state.pending_burn_amount = ctx.accounts.dat_asdf_account.amount;
-    ctx.accounts.dat_state.last_cycle_sol = max_sol_cost;
+    accounts.dat_asdf_account.reload()?;
+    let mut state = accounts.dat_state.load_mut()?;
+    state.pending_burn_amount = accounts.dat_asdf_account.amount;
+    state.last_cycle_sol = max_sol_cost;
+    if let Some(next) = next_recipient_index {
+        state.current_fee_recipient_index = next;
+    }
+    if is_root && execution_price > 0 {
+        state.last_known_price = execution_price;
+    }
+    let sequence = state.next_event_sequence();
+    drop(state);
+
+    let clock = Clock::get()?;
+    let actual_tokens = accounts.dat_asdf_account.amount.saturating_sub(balance_before);
+
+    // The pool may have moved between the caller's quote and execution
+    let sol_spent = lamports_before.saturating_sub(accounts.dat_authority.lamports());
+    check_max_price(sol_spent, actual_tokens, max_price)?;
+
+    emit!(BuyExecuted::observed(
+        accounts.asdf_mint.key(),
+        VENUE_BONDING_CURVE,
+        (lamports_before, accounts.dat_authority.lamports()),
+        (balance_before, accounts.dat_asdf_account.amount),
+        sequence,
+        clock.unix_timestamp,
+    ));
+
+    if let (Some(new), Some(comparison)) = (shadow, accounts.shadow_comparison.as_mut()) {
+        let (token_delta, sol_delta) = comparison.record(
+            (max_sol_cost, desired_tokens),
+            new,
+            actual_tokens,
+            clock.slot,
+            clock.unix_timestamp,
+        );
+
+        emit!(ShadowMathCompared {
+            mint: accounts.asdf_mint.key(),
+            old_sol_cost: max_sol_cost,
+            old_tokens: desired_tokens,
+            new_sol_cost: new.0,
+            new_tokens: new.1,
+            actual_tokens,
+            token_delta,
+            sol_delta,
+            samples: comparison.samples,
+            timestamp: clock.unix_timestamp,
+        });
+    }
     Ok(())
 }
 
@@ -102,41 +229,55 @@ fn execute_buy_inner(ctx: Context<ExecuteBuy>, buy_amount: u64) -> Result<()> {
 /// CORRECT 16-account format based on successful devnet tx 3Rqh43z2...
 #[inline(never)]
 fn build_account_infos_secondary<'info>(accounts: &ExecuteBuySecondary<'info>) -> Vec<AccountInfo<'info>> {
-    let mut accs = Vec::with_capacity(16);
-    accs.push(accounts.pump_global_config.to_account_info());      // 0
-    accs.push(accounts.protocol_fee_recipient.to_account_info());  // 1
-    accs.push(accounts.asdf_mint.to_account_info());               // 2
-    accs.push(accounts.pool.to_account_info());                    // 3
-    accs.push(accounts.pool_asdf_account.to_account_info());       // 4
-    accs.push(accounts.dat_asdf_account.to_account_info());        // 5
-    accs.push(accounts.dat_authority.to_account_info());           // 6
-    accs.push(accounts.system_program.to_account_info());          // 7
-    accs.push(accounts.token_program.to_account_info());           // 8 - token_program BEFORE creator_vault!
-    accs.push(accounts.creator_vault.to_account_info());           // 9 - creator_vault AFTER token_program!
-    accs.push(accounts.pump_event_authority.to_account_info());    // 10
-    accs.push(accounts.pump_swap_program.to_account_info());       // 11
-    accs.push(accounts.global_volume_accumulator.to_account_info()); // 12
-    accs.push(accounts.user_volume_accumulator.to_account_info());   // 13
-    accs.push(accounts.fee_config.to_account_info());              // 14
-    accs.push(accounts.fee_program.to_account_info());             // 15
-    accs
+    vec![
+        accounts.pump_global_config.to_account_info(),      // 0
+        accounts.protocol_fee_recipient.to_account_info(),  // 1
+        accounts.asdf_mint.to_account_info(),               // 2
+        accounts.pool.to_account_info(),                    // 3
+        accounts.pool_asdf_account.to_account_info(),       // 4
+        accounts.dat_asdf_account.to_account_info(),        // 5
+        accounts.dat_authority.to_account_info(),           // 6
+        accounts.system_program.to_account_info(),          // 7
+        accounts.token_program.to_account_info(),           // 8 - token_program BEFORE creator_vault!
+        accounts.creator_vault.to_account_info(),           // 9 - creator_vault AFTER token_program!
+        accounts.pump_event_authority.to_account_info(),    // 10
+        accounts.pump_swap_program.to_account_info(),       // 11
+        accounts.global_volume_accumulator.to_account_info(), // 12
+        accounts.user_volume_accumulator.to_account_info(),   // 13
+        accounts.fee_config.to_account_info(),              // 14
+        accounts.fee_program.to_account_info(),             // 15
+    ]
 }
 
 /// Execute secondary buy CPI (separate to reduce stack in main function)
 #[inline(never)]
 fn execute_buy_secondary_cpi(ctx: &mut Context<ExecuteBuySecondary>, buy_amount: u64, bump: u8) -> Result<()> {
-    let max_fees = ctx.accounts.dat_state.max_fees_per_cycle;
-    let slippage = ctx.accounts.dat_state.slippage_bps;
+    let (max_fees, slippage, fee_recipient_index) = {
+        let state = ctx.accounts.dat_state.load()?;
+        (state.max_fees_per_cycle, state.slippage_bps, state.current_fee_recipient_index)
+    };
 
     // NOTE: reload() required before reading pool state - Anchor doesn't auto-reload for manual invoke_signed CPI
     ctx.accounts.pool_asdf_account.reload()?;
     let pool_data = ctx.accounts.pool.try_borrow_data()?.to_vec();
-    let (max_sol_cost, desired_tokens) = calculate_buy_amount_and_slippage(buy_amount, &pool_data, max_fees, slippage)?;
+    // Secondary tokens are not tracked by the root price circuit breaker
+    let (max_sol_cost, desired_tokens, _) = calculate_buy_amount_and_slippage(buy_amount, &pool_data, max_fees, slippage, 0, 0)?;
 
     let seeds: &[&[u8]] = &[DAT_AUTHORITY_SEED, &[bump]];
 
+    let next_recipient_index = check_protocol_fee_recipient(
+        ctx.accounts.protocol_fee_recipient.key,
+        ctx.accounts.fee_recipients.as_ref().map(|list| list.active()),
+        &ctx.accounts.pump_global_config,
+        fee_recipient_index,
+        &ctx.accounts.token_program.key(),
+        is_mayhem_bonding_curve(&pool_data),
+    )?;
+
     // Build account infos on heap in separate stack frame
-    let accs = build_account_infos_secondary(&ctx.accounts);
+    let accs = build_account_infos_secondary(ctx.accounts);
+    let balance_before = ctx.accounts.dat_asdf_account.amount;
+    let lamports_before = ctx.accounts.dat_authority.lamports();
 
     execute_pumpfun_cpi(
         ctx.accounts.pump_global_config.key(),
@@ -154,11 +295,63 @@ fn execute_buy_secondary_cpi(ctx: &mut Context<ExecuteBuySecondary>, buy_amount:
 
     // NOTE: reload() required after CPI to get updated token balance - Anchor doesn't auto-reload for invoke_signed
     ctx.accounts.dat_asdf_account.reload()?;
-    ctx.accounts.dat_state.pending_burn_amount = ctx.accounts.dat_asdf_account.amount;
-    ctx.accounts.dat_state.last_cycle_sol = max_sol_cost;
+    let mut state = ctx.accounts.dat_state.load_mut()?;
+    state.pending_burn_amount = ctx.accounts.dat_asdf_account.amount;
+    state.last_cycle_sol = max_sol_cost;
+    if let Some(next) = next_recipient_index {
+        state.current_fee_recipient_index = next;
+    }
+
+    emit!(BuyExecuted::observed(
+        ctx.accounts.asdf_mint.key(),
+        VENUE_BONDING_CURVE,
+        (lamports_before, ctx.accounts.dat_authority.lamports()),
+        (balance_before, ctx.accounts.dat_asdf_account.amount),
+        state.next_event_sequence(),
+        Clock::get()?.unix_timestamp,
+    ));
     Ok(())
 }
 
+/// Derive PumpSwap AMM buy parameters from on-chain pool state (mirrors execute_buy_inner)
+/// Mints and vaults are checked against the pool account; the budget is capped by the DAT WSOL balance
+/// Returns (max_sol_cost, desired_tokens, execution_price)
+#[inline(never)]
+fn quote_amm_buy(accounts: &ExecuteBuyAMM, allocated_lamports: u64) -> Result<(u64, u64, u64)> {
+    let info = parse_pumpswap_pool(&accounts.pool.try_borrow_data()?)?;
+    info.check_accounts(
+        &accounts.base_mint.key(),
+        accounts.quote_mint.key,
+        accounts.pool_base_token_account.key,
+        accounts.pool_quote_token_account.key,
+    )?;
+
+    let base_reserves = token_account_amount(&accounts.pool_base_token_account)?;
+    let quote_reserves = token_account_amount(&accounts.pool_quote_token_account)?;
+    let buy_amount = allocated_lamports.min(token_account_amount(&accounts.dat_wsol_account)?);
+    require!(buy_amount >= MINIMUM_BUY_AMOUNT, ErrorCode::InsufficientFees);
+
+    // Price circuit breaker only tracks the root token (last_known_price is a single observation)
+    let state = accounts.dat_state.load()?;
+    let is_root = state.root_token_mint() == Some(accounts.base_mint.key());
+    let (last_price, max_deviation) = if is_root {
+        (state.last_known_price, state.max_price_deviation_bps)
+    } else {
+        (0, 0)
+    };
+
+    calculate_amm_buy_amount_and_slippage(
+        buy_amount,
+        base_reserves,
+        quote_reserves,
+        accounts.base_mint.supply,
+        state.max_fees_per_cycle,
+        state.slippage_bps,
+        last_price,
+        max_deviation,
+    )
+}
+
 /// CPI executor for PumpSwap AMM buy (for migrated tokens)
 /// Account order matches PumpSwap AMM buy instruction from official IDL
 #[inline(never)]
@@ -270,1245 +463,6642 @@ fn execute_pumpswap_amm_cpi_inner<'info>(
     Ok(())
 }
 
-#[program]
-pub mod asdf_dat {
-    use super::*;
+/// CPI executor for PumpSwap AMM deposit (add liquidity)
+/// Account order matches PumpSwap AMM deposit instruction from official IDL
+#[inline(never)]
+fn execute_pumpswap_deposit_cpi<'info>(
+    accounts: &ProvideLiquidity<'info>,
+    lp_token_amount_out: u64,  // LP tokens to mint to dat_authority
+    max_base_amount_in: u64,   // max bought tokens to deposit
+    max_quote_amount_in: u64,  // max WSOL to deposit
+    bump: u8,                  // dat_authority bump
+) -> Result<()> {
+    // Instruction data: discriminator + lp_token_amount_out + max_base_amount_in + max_quote_amount_in
+    let mut data = Vec::with_capacity(32);
+    data.extend_from_slice(&PUMPSWAP_DEPOSIT_DISCRIMINATOR);
+    data.extend_from_slice(&lp_token_amount_out.to_le_bytes());
+    data.extend_from_slice(&max_base_amount_in.to_le_bytes());
+    data.extend_from_slice(&max_quote_amount_in.to_le_bytes());
 
-    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
-        let state = &mut ctx.accounts.dat_state;
-        let clock = Clock::get()?;
-        
-        state.admin = ctx.accounts.admin.key();
-        state.asdf_mint = ASDF_MINT;
-        state.wsol_mint = WSOL_MINT;
-        state.pool_address = POOL_PUMPSWAP;
-        state.pump_swap_program = PUMP_SWAP_PROGRAM;
-        state.total_burned = 0;
-        state.total_sol_collected = 0;
-        state.total_buybacks = 0;
-        state.failed_cycles = 0;
-        state.consecutive_failures = 0;
-        state.is_active = true;
-        state.emergency_pause = false;
-        state.last_cycle_timestamp = 0;
-        state.initialized_at = clock.unix_timestamp;
-        state.last_am_execution = 0;
-        state.last_pm_execution = 0;
-        state.min_fees_threshold = MIN_FEES_TO_CLAIM;
-        state.max_fees_per_cycle = MAX_FEES_PER_CYCLE;
-        state.slippage_bps = INITIAL_SLIPPAGE_BPS;
-        state.min_cycle_interval = MIN_CYCLE_INTERVAL;
-        state.dat_authority_bump = ctx.bumps.dat_authority;
-        state.current_fee_recipient_index = 0;
-        state.last_known_price = 0;
-        state.pending_burn_amount = 0;
-        state.root_token_mint = None;        // No root token by default
-        state.fee_split_bps = 5520;          // 55.2% keep, 44.8% to root
-        state.last_sol_sent_to_root = 0;
-        // Security audit additions (v2)
-        state.pending_admin = None;           // No pending admin transfer
-        state.pending_fee_split = None;       // No pending fee split change
-        state.pending_fee_split_timestamp = 0;
-        state.admin_operation_cooldown = 3600; // Default 1 hour cooldown
-        // HIGH-01 FIX: Separate timestamp for direct fee split changes
-        state.last_direct_fee_split_timestamp = 0;
+    let ix_accounts = vec![
+        // 1. pool (mut)
+        AccountMeta::new(accounts.pool.key(), false),
+        // 2. global_config
+        AccountMeta::new_readonly(accounts.global_config.key(), false),
+        // 3. user (signer) - dat_authority provides liquidity
+        AccountMeta::new_readonly(accounts.dat_authority.key(), true),
+        // 4. base_mint
+        AccountMeta::new_readonly(accounts.base_mint.key(), false),
+        // 5. quote_mint (WSOL)
+        AccountMeta::new_readonly(accounts.quote_mint.key(), false),
+        // 6. lp_mint (mut)
+        AccountMeta::new(accounts.lp_mint.key(), false),
+        // 7. user_base_token_account (mut)
+        AccountMeta::new(accounts.dat_token_account.key(), false),
+        // 8. user_quote_token_account (mut)
+        AccountMeta::new(accounts.dat_wsol_account.key(), false),
+        // 9. user_pool_token_account (mut) - receives LP tokens
+        AccountMeta::new(accounts.dat_lp_token_account.key(), false),
+        // 10. pool_base_token_account (mut)
+        AccountMeta::new(accounts.pool_base_token_account.key(), false),
+        // 11. pool_quote_token_account (mut)
+        AccountMeta::new(accounts.pool_quote_token_account.key(), false),
+        // 12. token_program
+        AccountMeta::new_readonly(accounts.token_program.key(), false),
+        // 13. token_2022_program
+        AccountMeta::new_readonly(accounts.token_2022_program.key(), false),
+        // 14. event_authority (PDA)
+        AccountMeta::new_readonly(accounts.event_authority.key(), false),
+        // 15. program (PumpSwap AMM)
+        AccountMeta::new_readonly(accounts.pump_swap_program.key(), false),
+    ];
 
-        emit!(DATInitialized {
-            admin: state.admin,
-            dat_authority: ctx.accounts.dat_authority.key(),
-            timestamp: clock.unix_timestamp,
-        });
+    let ix = Instruction {
+        program_id: PUMP_SWAP_PROGRAM,
+        accounts: ix_accounts,
+        data,
+    };
 
-        Ok(())
-    }
+    let account_infos = &[
+        accounts.pool.to_account_info(),
+        accounts.global_config.to_account_info(),
+        accounts.dat_authority.to_account_info(),
+        accounts.base_mint.to_account_info(),
+        accounts.quote_mint.to_account_info(),
+        accounts.lp_mint.to_account_info(),
+        accounts.dat_token_account.to_account_info(),
+        accounts.dat_wsol_account.to_account_info(),
+        accounts.dat_lp_token_account.to_account_info(),
+        accounts.pool_base_token_account.to_account_info(),
+        accounts.pool_quote_token_account.to_account_info(),
+        accounts.token_program.to_account_info(),
+        accounts.token_2022_program.to_account_info(),
+        accounts.event_authority.to_account_info(),
+        accounts.pump_swap_program.to_account_info(),
+    ];
 
-    // Initialize per-token statistics tracking
-    pub fn initialize_token_stats(ctx: Context<InitializeTokenStats>) -> Result<()> {
-        let stats = &mut ctx.accounts.token_stats;
-        let clock = Clock::get()?;
-
-        stats.mint = ctx.accounts.mint.key();
-        stats.total_burned = 0;
-        stats.total_sol_collected = 0;
-        stats.total_sol_used = 0;
-        stats.total_sol_sent_to_root = 0;
-        stats.total_sol_received_from_others = 0;
-        stats.total_buybacks = 0;
-        stats.last_cycle_timestamp = 0;
-        stats.last_cycle_sol = 0;
-        stats.last_cycle_burned = 0;
-        stats.is_root_token = false;  // Will be set when assigned as root
-        stats.bump = ctx.bumps.token_stats;
-        // Initialize new fields for per-token fee tracking
-        stats.pending_fees_lamports = 0;
-        stats.last_fee_update_timestamp = clock.unix_timestamp;
-        stats.cycles_participated = 0;
-
-        emit!(TokenStatsInitialized {
-            mint: stats.mint,
-            timestamp: clock.unix_timestamp,
-        });
+    let seeds: &[&[u8]] = &[DAT_AUTHORITY_SEED, &[bump]];
 
-        Ok(())
-    }
+    invoke_signed(&ix, account_infos, &[seeds])?;
+    Ok(())
+}
 
-    // Set the root token that receives 44.8% from other tokens
-    pub fn set_root_token(ctx: Context<SetRootToken>, root_mint: Pubkey) -> Result<()> {
-        let state = &mut ctx.accounts.dat_state;
-        let clock = Clock::get()?;
+/// Shared PumpSwap AMM buy: quote from pool reserves, CPI, slippage check, burn tracking
+fn execute_buy_amm_inner(accounts: &mut ExecuteBuyAMM, allocated_lamports: u64) -> Result<()> {
+    // Derive desired_tokens / max_sol_cost from pool reserves, like the bonding curve path
+    let (max_sol_cost, desired_tokens, execution_price) = quote_amm_buy(accounts, allocated_lamports)?;
+    require!(desired_tokens > 0, ErrorCode::InsufficientPoolLiquidity);
 
-        // Verify admin authorization
-        require!(
-            ctx.accounts.admin.key() == state.admin,
-            ErrorCode::UnauthorizedAccess
-        );
+    require_compute_remaining(CU_PHASE_AMM_BUY)?;
 
-        // Verify TokenStats exists for this mint
-        require!(
-            ctx.accounts.root_token_stats.mint == root_mint,
-            ErrorCode::InvalidRootToken
-        );
+    // Get bump before CPI
+    let bump = accounts.dat_state.load()?.dat_authority_bump;
 
-        // Mark previous root as non-root (if any)
-        // Note: This would require passing old root token stats too
-        // For now, admin must manually handle old root if changing
+    let clock = Clock::get()?;
+    accounts.dat_state.load_mut()?.acquire_cycle_lock(accounts.base_mint.key())?;
+    accounts.cycle_state.begin_buy(false, VENUE_PUMPSWAP, clock.unix_timestamp, clock.slot)?;
 
-        // Update state
-        state.root_token_mint = Some(root_mint);
+    msg!("Executing PumpSwap AMM buy: {} tokens for max {} lamports",
+        desired_tokens, max_sol_cost);
 
-        // Mark this token as root
-        let root_stats = &mut ctx.accounts.root_token_stats;
-        root_stats.is_root_token = true;
+    // Record token and WSOL balances before buy
+    let tokens_before = accounts.dat_token_account.amount;
+    let wsol_before = token_account_amount(&accounts.dat_wsol_account)?;
 
-        emit!(RootTokenSet {
-            root_mint,
-            fee_split_bps: state.fee_split_bps,
-            timestamp: clock.unix_timestamp,
-        });
+    // Execute the PumpSwap AMM CPI (borrows accounts immutably)
+    execute_pumpswap_amm_cpi_inner(accounts, desired_tokens, max_sol_cost, bump)?;
 
-        Ok(())
+    // NOTE: reload() required after CPI to get updated token balance - Anchor doesn't auto-reload for invoke_signed
+    accounts.dat_token_account.reload()?;
+    let tokens_after = accounts.dat_token_account.amount;
+    let wsol_after = token_account_amount(&accounts.dat_wsol_account)?;
+    let tokens_received = tokens_after.saturating_sub(tokens_before);
+
+    msg!("AMM buy complete: received {} tokens", tokens_received);
+
+    // desired_tokens already has slippage applied to the on-chain quote
+    require!(tokens_received >= desired_tokens, ErrorCode::SlippageExceeded);
+
+    // Update state for burn tracking (mutable borrow after CPI)
+    let mut state = accounts.dat_state.load_mut()?;
+    let is_root = state.root_token_mint() == Some(accounts.base_mint.key());
+    state.pending_burn_amount = tokens_received;
+    state.last_cycle_sol = max_sol_cost;
+    if is_root && execution_price > 0 {
+        state.last_known_price = execution_price;
     }
 
-    /// Update ASDF mint address (admin only, TESTING mode only)
-    /// Used for devnet testing where the initial mint may be incorrect.
-    /// This instruction is DISABLED on mainnet (TESTING_MODE = false).
-    #[cfg(feature = "testing")]
-    pub fn update_asdf_mint(ctx: Context<AdminControl>, new_asdf_mint: Pubkey) -> Result<()> {
-        let state = &mut ctx.accounts.dat_state;
-        let clock = Clock::get()?;
-
-        // Update the mint
-        let old_mint = state.asdf_mint;
-        state.asdf_mint = new_asdf_mint;
+    emit!(BuyExecuted::observed(
+        accounts.base_mint.key(),
+        VENUE_PUMPSWAP,
+        (wsol_before, wsol_after),
+        (tokens_before, tokens_after),
+        state.next_event_sequence(),
+        clock.unix_timestamp,
+    ));
 
-        msg!(
-            "ASDF mint updated: {} -> {} (TESTING MODE ONLY)",
-            old_mint,
-            new_asdf_mint
-        );
+    Ok(())
+}
 
-        emit!(AsdfMintUpdated {
-            old_mint,
-            new_mint: new_asdf_mint,
-            timestamp: clock.unix_timestamp,
-        });
+/// Create dat_authority's WSOL ATA if missing, then move `amount` native lamports into it
+fn wrap_dat_wsol<'info>(accounts: &ExecuteBuyAMM<'info>, amount: u64) -> Result<()> {
+    let seeds: &[&[u8]] = &[DAT_AUTHORITY_SEED, &[accounts.dat_state.load()?.dat_authority_bump]];
+    let signer_seeds: &[&[&[u8]]] = &[seeds];
 
-        Ok(())
-    }
+    anchor_spl::associated_token::create_idempotent(CpiContext::new_with_signer(
+        accounts.associated_token_program.to_account_info(),
+        anchor_spl::associated_token::Create {
+            payer: accounts.dat_authority.to_account_info(),
+            associated_token: accounts.dat_wsol_account.to_account_info(),
+            authority: accounts.dat_authority.to_account_info(),
+            mint: accounts.quote_mint.to_account_info(),
+            system_program: accounts.system_program.to_account_info(),
+            token_program: accounts.quote_token_program.to_account_info(),
+        },
+        signer_seeds,
+    ))?;
+
+    invoke_signed(
+        &anchor_lang::solana_program::system_instruction::transfer(
+            accounts.dat_authority.key,
+            accounts.dat_wsol_account.key,
+            amount,
+        ),
+        &[
+            accounts.dat_authority.to_account_info(),
+            accounts.dat_wsol_account.to_account_info(),
+            accounts.system_program.to_account_info(),
+        ],
+        signer_seeds,
+    )?;
 
-    // Update the fee split ratio (admin only)
-    // Bounded between 1000 (10%) and 9000 (90%) to prevent extreme configurations
-    // HIGH-02 FIX: Maximum 5% (500 bps) change per call to prevent instant rug
-    // HIGH-03 FIX: 1 hour cooldown between changes to prevent rapid manipulation
-    // NOTE: For larger changes, use propose_fee_split + execute_fee_split (timelocked)
-    pub fn update_fee_split(ctx: Context<AdminControl>, new_fee_split_bps: u16) -> Result<()> {
-        require!(
-            new_fee_split_bps >= 1000 && new_fee_split_bps <= 9000,
-            ErrorCode::InvalidFeeSplit
-        );
+    token::sync_native(CpiContext::new(
+        accounts.quote_token_program.to_account_info(),
+        token::SyncNative { account: accounts.dat_wsol_account.to_account_info() },
+    ))
+}
 
-        let state = &mut ctx.accounts.dat_state;
-        let clock = Clock::get()?;
+/// Close dat_authority's WSOL ATA, returning unspent WSOL and rent as native SOL
+fn close_dat_wsol<'info>(accounts: &ExecuteBuyAMM<'info>) -> Result<()> {
+    let seeds: &[&[u8]] = &[DAT_AUTHORITY_SEED, &[accounts.dat_state.load()?.dat_authority_bump]];
+    token::close_account(CpiContext::new_with_signer(
+        accounts.quote_token_program.to_account_info(),
+        token::CloseAccount {
+            account: accounts.dat_wsol_account.to_account_info(),
+            destination: accounts.dat_authority.to_account_info(),
+            authority: accounts.dat_authority.to_account_info(),
+        },
+        &[seeds],
+    ))
+}
 
-        // HIGH-01 FIX: Enforce cooldown between DIRECT fee split changes
-        // Uses separate timestamp from propose_fee_split to prevent bypass attacks
-        let elapsed = clock.unix_timestamp.saturating_sub(state.last_direct_fee_split_timestamp);
-        require!(
-            elapsed >= state.admin_operation_cooldown,
-            ErrorCode::CycleTooSoon
-        );
+/// Flip is_paused on every TokenStats in remaining_accounts (pause_category / resume_category)
+/// Each account must be a writable TokenStats owned by this program and tagged with `category`
+fn set_category_paused<'info>(
+    accounts: &'info [AccountInfo<'info>],
+    category: u8,
+    paused: bool,
+) -> Result<u32> {
+    require!(category != UNCATEGORIZED, ErrorCode::InvalidParameter);
+    require!(!accounts.is_empty(), ErrorCode::InvalidParameter);
+
+    let mut updated: u32 = 0;
+    for info in accounts.iter() {
+        require!(info.is_writable, ErrorCode::InvalidParameter);
+        // AccountLoader::try_from checks owner == program and the TokenStats discriminator
+        let loader: AccountLoader<'info, TokenStats> = AccountLoader::try_from(info)?;
+        let mut stats = loader.load_mut()?;
+        require!(stats.category == category, ErrorCode::CategoryMismatch);
+
+        if (stats.is_paused != 0) != paused {
+            stats.is_paused = paused as u8;
+            updated = updated.saturating_add(1);
+        }
+    }
 
-        let old_fee_split_bps = state.fee_split_bps;
+    Ok(updated)
+}
 
-        // Limit instant changes to max 5% (500 bps) per call
-        // HIGH-01 FIX: Use pure unsigned arithmetic to avoid any signed overflow concerns
-        let delta: u16 = if new_fee_split_bps >= old_fee_split_bps {
-            new_fee_split_bps - old_fee_split_bps
-        } else {
-            old_fee_split_bps - new_fee_split_bps
-        };
-        require!(delta <= 500, ErrorCode::FeeSplitDeltaTooLarge);
+/// Append an admin operation to the AdminLog (skipped until initialize_admin_log)
+fn log_admin_op(
+    admin_log: &mut Option<Box<Account<AdminLog>>>,
+    op: u8,
+    subject: Pubkey,
+    old_value: u64,
+    new_value: u64,
+    signer: Pubkey,
+) -> Result<()> {
+    if let Some(log) = admin_log.as_mut() {
+        log.record(AdminLogEntry {
+            op,
+            subject,
+            old_value,
+            new_value,
+            signer,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+    }
+    Ok(())
+}
 
-        state.fee_split_bps = new_fee_split_bps;
-        // HIGH-01 FIX: Update SEPARATE timestamp for direct path
-        state.last_direct_fee_split_timestamp = clock.unix_timestamp;
+/// Queue a DATState parameter change behind `delay` seconds (>= admin_operation_cooldown)
+/// `preview` is a copy of DATState the change is applied to, so out-of-bounds values are
+/// rejected at proposal time; changes proposed together are checked against each other
+fn queue_parameter_change(
+    queue: &mut ChangeQueue,
+    preview: &mut DATState,
+    param: u8,
+    value: u64,
+    delay: i64,
+) -> Result<u64> {
+    preview.apply_parameter(param, value)?;
+    queue_change(queue, preview, param, Pubkey::default(), value, delay)
+}
 
-        emit!(FeeSplitUpdated {
-            old_bps: old_fee_split_bps,
-            new_bps: new_fee_split_bps,
-            timestamp: clock.unix_timestamp,
-        });
+/// Queue a change to `subject` behind `delay` seconds (>= admin_operation_cooldown)
+/// Callers validate the value first by applying it to a copy of the target account
+fn queue_change(
+    queue: &mut ChangeQueue,
+    state: &DATState,
+    param: u8,
+    subject: Pubkey,
+    value: u64,
+    delay: i64,
+) -> Result<u64> {
+    require!(
+        delay >= state.admin_operation_cooldown && delay <= MAX_CHANGE_DELAY,
+        ErrorCode::InvalidParameter
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let id = queue.enqueue(param, subject, value, now, delay)?;
+    emit!(ParameterChangeProposed {
+        id,
+        param,
+        subject,
+        value,
+        executable_at: now.saturating_add(delay),
+        timestamp: now,
+    });
+    Ok(id)
+}
 
-        Ok(())
+/// Randomized timing: collections must land inside the mint's settled CycleWindow
+fn require_cycle_window(state: &DATState, cycle_window: Option<&Account<CycleWindow>>, now: i64) -> Result<()> {
+    if state.randomized_timing == 0 {
+        return Ok(());
     }
+    let window = cycle_window.ok_or(ErrorCode::CycleWindowMissing)?;
+    require!(window.contains(now), ErrorCode::OutsideCycleWindow);
+    Ok(())
+}
 
-    // Update pending fees for a specific token (admin/monitor only)
-    // Used by off-chain fee monitor to track per-token fee attribution
-    pub fn update_pending_fees(
-        ctx: Context<UpdatePendingFees>,
-        amount_lamports: u64,
-    ) -> Result<()> {
-        let token_stats = &mut ctx.accounts.token_stats;
-        let clock = Clock::get()?;
+/// Pay a buy instruction's optional Jito tip from dat_authority, before the buy is sized
+/// Returns the tip paid (0 when none was requested)
+fn pay_cycle_tip<'info>(
+    state: &DATState,
+    cycle_state: &CycleState,
+    dat_authority: &AccountInfo<'info>,
+    tip_account: Option<&AccountInfo<'info>>,
+    system_program: &AccountInfo<'info>,
+    tip_lamports: Option<u64>,
+    mint: Pubkey,
+) -> Result<u64> {
+    let spendable = state.spendable_lamports(dat_authority.lamports(), cycle_state.rent_reserve() + SAFETY_BUFFER);
+    let tip = state.check_jito_tip(tip_lamports, spendable)?;
+    if tip == 0 {
+        return Ok(0);
+    }
+    let tip_account = tip_account.ok_or(ErrorCode::JitoTipAccountMissing)?;
+    let seeds: &[&[u8]] = &[DAT_AUTHORITY_SEED, &[state.dat_authority_bump]];
+    pay_jito_tip(dat_authority, tip_account, system_program, tip, seeds)?;
+
+    emit!(JitoTipPaid {
+        mint,
+        tip_account: tip_account.key(),
+        amount: tip,
+        cycle_seq: cycle_state.sequence,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(tip)
+}
 
-        // Rate limiting: minimum 10 seconds between updates per token
-        const MIN_FEE_UPDATE_INTERVAL: i64 = 10;
-        require!(
-            clock.unix_timestamp >= token_stats.last_fee_update_timestamp + MIN_FEE_UPDATE_INTERVAL,
-            ErrorCode::CycleTooSoon
-        );
+/// Count a completed cycle for a registered keeper and pay its tiered reward from dat_authority
+/// Payment is skipped (not failed) when dat_authority is short or system_program is omitted
+#[inline(never)]
+fn reward_keeper<'info>(
+    state: &DATState,
+    keeper_info: &mut KeeperInfo,
+    keeper: &AccountInfo<'info>,
+    dat_authority: &AccountInfo<'info>,
+    system_program: Option<&AccountInfo<'info>>,
+    mint: Pubkey,
+    now: i64,
+) -> Result<()> {
+    require!(keeper_info.keeper == keeper.key(), ErrorCode::KeeperMismatch);
+    if !keeper_info.is_active {
+        return Ok(());
+    }
 
-        // Check pending fees cap (69 SOL max)
-        let new_total = token_stats.pending_fees_lamports.saturating_add(amount_lamports);
-        require!(new_total <= MAX_PENDING_FEES, ErrorCode::PendingFeesOverflow);
+    let reward_bps = keeper_info.reward_bps();
+    let reward = keeper_info.record_cycle(state.last_cycle_sol, now);
+    let available = state.spendable_lamports(dat_authority.lamports(), PAYER_REFUND_RESERVE);
+    let paid = match system_program {
+        Some(system_program) if reward > 0 && available >= reward => {
+            invoke_signed(
+                &anchor_lang::solana_program::system_instruction::transfer(dat_authority.key, keeper.key, reward),
+                &[dat_authority.clone(), keeper.clone(), system_program.clone()],
+                &[&[DAT_AUTHORITY_SEED, &[state.dat_authority_bump]]],
+            )?;
+            reward
+        }
+        _ => 0,
+    };
+    keeper_info.total_rewards = keeper_info.total_rewards.saturating_add(paid);
+
+    emit!(KeeperRewarded {
+        keeper: keeper_info.keeper,
+        mint,
+        successful_cycles: keeper_info.successful_cycles,
+        reward_bps,
+        amount: paid,
+        timestamp: now,
+    });
+    Ok(())
+}
 
-        // Accumulate pending fees
-        token_stats.pending_fees_lamports = new_total;
+/// Reserve and insurance cuts of SOL just collected from the creator vault
+/// (collect_fees_batch, run_ecosystem_cycle)
+#[allow(clippy::too_many_arguments)]
+fn take_vault_cuts<'info>(
+    state: &DATState,
+    dat_authority: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    reserve_vault: Option<&mut Box<Account<'info, ReserveVault>>>,
+    insurance_fund: Option<&mut Box<Account<'info, InsuranceFund>>>,
+    mint: Pubkey,
+    sol_from_vault: u64,
+    now: i64,
+) -> Result<()> {
+    let seeds: &[&[u8]] = &[DAT_AUTHORITY_SEED, &[state.dat_authority_bump]];
 
-        token_stats.last_fee_update_timestamp = clock.unix_timestamp;
+    let reserve_cut = state.reserve_cut(sol_from_vault);
+    if reserve_cut > 0 {
+        let reserve_vault = reserve_vault.ok_or(ErrorCode::ReserveVaultMissing)?;
+        invoke_signed(
+            &anchor_lang::solana_program::system_instruction::transfer(
+                dat_authority.key,
+                &reserve_vault.key(),
+                reserve_cut,
+            ),
+            &[dat_authority.clone(), reserve_vault.to_account_info(), system_program.clone()],
+            &[seeds],
+        )?;
+        reserve_vault.total_reserved = reserve_vault.total_reserved.saturating_add(reserve_cut);
 
-        emit!(PendingFeesUpdated {
-            mint: ctx.accounts.mint.key(),
-            amount: amount_lamports,
-            total_pending: token_stats.pending_fees_lamports,
-            timestamp: clock.unix_timestamp,
+        emit!(ReserveFunded {
+            mint,
+            amount: reserve_cut,
+            total_reserved: reserve_vault.total_reserved,
+            timestamp: now,
         });
+    }
 
-        #[cfg(feature = "verbose")]
-        msg!("Pending fees updated for mint {}: +{} lamports (total: {})",
-            ctx.accounts.mint.key(),
-            amount_lamports,
-            token_stats.pending_fees_lamports
-        );
+    let insurance_cut = state.insurance_cut(sol_from_vault);
+    if insurance_cut > 0 {
+        let insurance_fund = insurance_fund.ok_or(ErrorCode::InsuranceFundMissing)?;
+        invoke_signed(
+            &anchor_lang::solana_program::system_instruction::transfer(
+                dat_authority.key,
+                &insurance_fund.key(),
+                insurance_cut,
+            ),
+            &[dat_authority.clone(), insurance_fund.to_account_info(), system_program.clone()],
+            &[seeds],
+        )?;
+        insurance_fund.record_deposit(insurance_cut);
 
-        Ok(())
+        emit!(InsuranceDeposited {
+            mint,
+            amount: insurance_cut,
+            total_deposited: insurance_fund.total_deposited,
+            timestamp: now,
+        });
     }
+    Ok(())
+}
 
-    /// Initialize validator state for trustless per-token fee tracking
-    /// Must be called once per token before register_validated_fees can be used
-    pub fn initialize_validator(ctx: Context<InitializeValidator>) -> Result<()> {
-        let state = &mut ctx.accounts.validator_state;
-        let clock = Clock::get()?;
+/// PumpFun buy of one run_ecosystem_cycle token (root or secondary) through the cycle's shared accounts
+/// Returns (sol_used, tokens_received, execution_price), sol_used from the dat_authority lamport delta
+#[inline(never)]
+#[allow(clippy::too_many_arguments)]
+fn ecosystem_pump_buy<'info>(
+    accounts: &mut RunEcosystemCycle<'info>,
+    mint: &AccountInfo<'info>,
+    curve: &AccountInfo<'info>,
+    curve_token_account: &AccountInfo<'info>,
+    dat_token_account: &AccountInfo<'info>,
+    buy_amount: u64,
+    last_price: u64,
+    max_deviation: u16,
+) -> Result<(u64, u64, u64)> {
+    let (max_fees_per_cycle, slippage_bps, bump) = {
+        let state = accounts.dat_state.load()?;
+        (state.max_fees_per_cycle, state.slippage_bps, state.dat_authority_bump)
+    };
+    let pool_data = curve.try_borrow_data()?.to_vec();
+    // Every buy shares protocol_fee_recipient, so each curve must be in the same mode as the root
+    require!(
+        is_mayhem_bonding_curve(&pool_data) == (*accounts.protocol_fee_recipient.key == MAYHEM_FEE_RECIPIENT),
+        ErrorCode::InvalidFeeRecipient
+    );
+    let (max_sol_cost, desired_tokens, execution_price) = calculate_buy_amount_and_slippage(
+        buy_amount, &pool_data, max_fees_per_cycle, slippage_bps, last_price, max_deviation,
+    )?;
+    let seeds: &[&[u8]] = &[DAT_AUTHORITY_SEED, &[bump]];
+    let balance_before = token_account_amount(dat_token_account)?;
+    let lamports_before = accounts.dat_authority.lamports();
+
+    // Same 16-account order as build_account_infos_root
+    let accs = vec![
+        accounts.pump_global_config.clone(),
+        accounts.protocol_fee_recipient.clone(),
+        mint.clone(),
+        curve.clone(),
+        curve_token_account.clone(),
+        dat_token_account.clone(),
+        accounts.dat_authority.clone(),
+        accounts.system_program.to_account_info(),
+        accounts.token_program.to_account_info(),
+        accounts.creator_vault.clone(),
+        accounts.pump_event_authority.clone(),
+        accounts.pump_swap_program.clone(),
+        accounts.global_volume_accumulator.clone(),
+        accounts.user_volume_accumulator.clone(),
+        accounts.fee_config.clone(),
+        accounts.fee_program.clone(),
+    ];
+    execute_pumpfun_cpi(
+        accounts.pump_global_config.key(),
+        accounts.protocol_fee_recipient.key(),
+        mint.key(),
+        curve.key(),
+        curve_token_account.key(),
+        dat_token_account.key(),
+        accounts.dat_authority.key(),
+        max_sol_cost,
+        desired_tokens,
+        &accs,
+        seeds,
+    )?;
+    let balance_after = token_account_amount(dat_token_account)?;
+    let tokens_received = balance_after.saturating_sub(balance_before);
+    let lamports_after = accounts.dat_authority.lamports();
+    let sol_used = lamports_before.saturating_sub(lamports_after);
+
+    emit!(BuyExecuted::observed(
+        mint.key(),
+        VENUE_BONDING_CURVE,
+        (lamports_before, lamports_after),
+        (balance_before, balance_after),
+        accounts.dat_state.load_mut()?.next_event_sequence(),
+        Clock::get()?.unix_timestamp,
+    ));
+    Ok((sol_used, tokens_received, execution_price))
+}
 
-        state.mint = ctx.accounts.mint.key();
-        state.bonding_curve = ctx.accounts.bonding_curve.key();
-        state.last_validated_slot = clock.slot;
-        state.total_validated_lamports = 0;
-        state.total_validated_count = 0;
-        state.fee_rate_bps = 50; // 0.5% default PumpFun creator fee
-        state.bump = ctx.bumps.validator_state;
-        state._reserved = [0u8; 32];
+/// One secondary of run_ecosystem_cycle: settle its allocation, send the root share to the
+/// root treasury, buy on its bonding curve and burn the whole DAT balance
+/// accs: [token_stats, mint, bonding_curve, curve_token_account, dat_token_account, token_config, cycle_state]
+/// Returns the SOL sent to the root treasury
+#[inline(never)]
+fn run_ecosystem_secondary<'info>(
+    accounts: &mut RunEcosystemCycle<'info>,
+    accs: &'info [AccountInfo<'info>],
+    allocation: u64,
+    now: i64,
+) -> Result<u64> {
+    let (stats_info, mint, curve, curve_token_account, dat_token_account, token_config, cycle_state) =
+        (&accs[0], &accs[1], &accs[2], &accs[3], &accs[4], &accs[5], &accs[6]);
+
+    require!(stats_info.is_writable, ErrorCode::InvalidParameter);
+    // AccountLoader::try_from checks owner == program and the TokenStats discriminator
+    let loader: AccountLoader<'info, TokenStats> = AccountLoader::try_from(stats_info)?;
+    let mut stats = loader.load_mut()?;
+    require!(stats.mint == mint.key() && stats.is_root_token == 0, ErrorCode::InvalidParameter);
+    require!(stats.is_paused == 0, ErrorCode::TokenPaused);
+    require!(
+        stats.burn_bps == 10_000 && stats.reflection_bps == 0 && stats.partner == Pubkey::default(),
+        ErrorCode::EcosystemCycleUnsupported
+    );
+    require!(*mint.owner == accounts.token_program.key(), ErrorCode::InvalidParameter);
+    require!(curve.owner == &PUMP_PROGRAM, ErrorCode::InvalidBondingCurve);
+    require!(
+        dat_token_account.key() == stats.active_token_account(accounts.dat_authority.key, &accounts.token_program.key()),
+        ErrorCode::InvalidTokenAccount
+    );
+
+    // Only secondaries of the primary root (no TokenConfig, or assigned to it)
+    let (expected_config, _) = Pubkey::find_program_address(&[TOKEN_CONFIG_SEED, stats.mint.as_ref()], &crate::ID);
+    require!(token_config.key() == expected_config, ErrorCode::InvalidParameter);
+    let assigned_root = assigned_root_of(token_config)?;
+    require!(
+        assigned_root == Pubkey::default() || assigned_root == accounts.root_mint.key(),
+        ErrorCode::InvalidRootToken
+    );
+    // A standalone cycle in flight owns the DAT balance this burns
+    require_secondary_idle(cycle_state, &stats.mint)?;
+
+    stats.settle_pending_fees(allocation)?;
+    require_compute_remaining(CU_PHASE_FEE_SPLIT + CU_PHASE_PUMPFUN_BUY + CU_PHASE_BURN)?;
+
+    let fee_split_bps = accounts.dat_state.load()?.fee_split_bps;
+    let seeds: &[&[u8]] = &[DAT_AUTHORITY_SEED, &[accounts.dat_state.load()?.dat_authority_bump]];
+    let sol_to_root = split_fees_to_root(
+        &accounts.dat_authority,
+        &accounts.root_treasury,
+        &accounts.system_program.to_account_info(),
+        allocation,
+        fee_split_bps,
+        seeds,
+    )?;
+    let buy_amount = ((allocation as u128 * fee_split_bps as u128 / 10000) as u64)
+        .saturating_sub(accounts.root_cycle_state.ata_reserve());
+    require!(buy_amount >= MINIMUM_BUY_AMOUNT, ErrorCode::InsufficientFees);
 
-        emit!(ValidatorInitialized {
-            mint: state.mint,
-            bonding_curve: state.bonding_curve,
-            slot: clock.slot,
-            timestamp: clock.unix_timestamp,
-        });
+    // Secondary tokens are not tracked by the root price circuit breaker
+    let (sol_used, _, _) = ecosystem_pump_buy(
+        accounts, mint, curve, curve_token_account, dat_token_account, buy_amount, 0, 0,
+    )?;
 
-        #[cfg(feature = "verbose")]
-        msg!("Validator initialized for mint {} with bonding curve {}",
-            state.mint, state.bonding_curve);
+    // 100% burn, no reflection: everything the DAT account holds goes
+    let tokens_burned = token_account_amount(dat_token_account)?;
+    if tokens_burned > 0 {
+        token_interface::burn(
+            CpiContext::new_with_signer(
+                accounts.token_program.to_account_info(),
+                token_interface::Burn {
+                    mint: mint.clone(),
+                    from: dat_token_account.clone(),
+                    authority: accounts.dat_authority.clone(),
+                },
+                &[seeds],
+            ),
+            tokens_burned,
+        )?;
 
-        Ok(())
+        #[cfg(feature = "standard-burn-events")]
+        emit!(TokenBurn {
+            mint: stats.mint,
+            amount: tokens_burned,
+            authority: accounts.dat_authority.key(),
+            reference: stats_info.key(),
+        });
     }
 
-    /// ADMIN ONLY - Reset validator slot to current slot
-    /// Used when validator has been inactive for too long (slot delta > 1000)
-    /// This allows the validator daemon to resume operation without redeploying
-    pub fn reset_validator_slot(ctx: Context<ResetValidatorSlot>) -> Result<()> {
-        let state = &mut ctx.accounts.validator_state;
-        let clock = Clock::get()?;
-
-        let old_slot = state.last_validated_slot;
-        state.last_validated_slot = clock.slot;
-
-        emit!(ValidatorSlotReset {
-            mint: state.mint,
-            old_slot,
-            new_slot: clock.slot,
-            timestamp: clock.unix_timestamp,
-        });
+    stats.total_sol_collected = stats.total_sol_collected.saturating_add(allocation);
+    stats.total_sol_sent_to_root = stats.total_sol_sent_to_root.saturating_add(sol_to_root);
+    stats.total_sol_used = stats.total_sol_used.saturating_add(sol_used);
+    stats.total_burned = stats.total_burned.saturating_add(tokens_burned);
+    stats.total_buybacks = stats.total_buybacks.saturating_add(1);
+    stats.cycles_participated = stats.cycles_participated.saturating_add(1);
+    stats.last_cycle_timestamp = now;
+    stats.last_cycle_sol = sol_used;
+    stats.last_cycle_burned = tokens_burned;
+    stats.consecutive_failures = 0;
+
+    emit!(EcosystemSecondaryCompleted {
+        mint: stats.mint,
+        allocation,
+        sol_to_root,
+        sol_used,
+        tokens_burned,
+        timestamp: now,
+    });
+    Ok(sol_to_root)
+}
 
-        #[cfg(feature = "verbose")]
-        msg!("Validator slot reset from {} to {} for mint {}",
-            old_slot, clock.slot, state.mint);
+/// TokenConfig.assigned_root of a secondary (default when its TokenConfig was never created)
+fn assigned_root_of(token_config: &AccountInfo) -> Result<Pubkey> {
+    if token_config.owner != &crate::ID || token_config.data_is_empty() {
+        return Ok(Pubkey::default());
+    }
+    let config = TokenConfig::try_deserialize(&mut &token_config.try_borrow_data()?[..])?;
+    Ok(config.assigned_root)
+}
 
-        Ok(())
+/// A secondary's CycleState is between cycles (one never created has no cycle in flight)
+fn require_secondary_idle(cycle_state: &AccountInfo, mint: &Pubkey) -> Result<()> {
+    let (expected, _) = Pubkey::find_program_address(&[CYCLE_STATE_SEED, mint.as_ref()], &crate::ID);
+    require!(cycle_state.key() == expected, ErrorCode::InvalidParameter);
+    if cycle_state.owner != &crate::ID || cycle_state.data_is_empty() {
+        return Ok(());
     }
+    let cycle = CycleState::try_deserialize(&mut &cycle_state.try_borrow_data()?[..])?;
+    require!(cycle.is_between_cycles(), ErrorCode::InvalidCyclePhase);
+    Ok(())
+}
 
-    /// ADMIN ONLY - Register validated fees extracted from PumpFun transaction logs
-    /// Only admin can call this to commit validated fee data
-    ///
-    /// Security: Protected by admin check, slot progression, and fee caps
-    pub fn register_validated_fees(
-        ctx: Context<RegisterValidatedFees>,
-        fee_amount: u64,
-        end_slot: u64,
-        tx_count: u32,
-    ) -> Result<()> {
-        let validator = &mut ctx.accounts.validator_state;
-        let token_stats = &mut ctx.accounts.token_stats;
-        let clock = Clock::get()?;
+/// Fee attestation sanity checks (register_validated_fees and its batch variant)
+fn check_fee_attestation(validator: &ValidatorState, token_stats: &TokenStats, entry: &ValidatedFeeEntry) -> Result<()> {
+    // Validation 1: Slot progression (prevent double-counting)
+    require!(
+        entry.end_slot > validator.last_validated_slot,
+        ErrorCode::StaleValidation
+    );
+
+    // Validation 2: Slot range sanity (max 1000 slots ~7 minutes)
+    let slot_delta = entry.end_slot.saturating_sub(validator.last_validated_slot);
+    require!(slot_delta <= 1000, ErrorCode::SlotRangeTooLarge);
+
+    // Validation 3: Fee amount sanity check
+    // Per-validator cap (default 0.01 SOL per slot, see update_validator_limits)
+    let max_fee_for_range = slot_delta.saturating_mul(validator.fee_cap_per_slot());
+    require!(entry.fee_amount <= max_fee_for_range, ErrorCode::FeeTooHigh);
+
+    // Validation 4: TX count sanity (default max 100 TX per slot)
+    require!(
+        entry.tx_count <= (slot_delta as u32).saturating_mul(validator.tx_cap_per_slot()),
+        ErrorCode::TooManyTransactions
+    );
+
+    // Validation 5: Pending fees cap (69 SOL max) - re-checked on finalize
+    let new_pending = token_stats.pending_fees_lamports.saturating_add(entry.fee_amount);
+    require!(new_pending <= MAX_PENDING_FEES, ErrorCode::PendingFeesOverflow);
+
+    Ok(())
+}
 
-        // Validation 1: Slot progression (prevent double-counting)
+/// Authorize a fee attestation poster. The admin needs M-of-N attestor signatures
+/// over this entry's message; anyone else backs the claim with their ValidatorBond.
+/// Returns (bond recorded on the attestation, attestor signature count)
+fn authorize_fee_attestation(
+    poster: Pubkey,
+    admin: Pubkey,
+    validator_set: &ValidatorSet,
+    instructions_sysvar: &AccountInfo,
+    bond: Option<&mut Account<ValidatorBond>>,
+    entry: &ValidatedFeeEntry,
+) -> Result<(Pubkey, u8)> {
+    let message = fee_attestation_message(
+        &entry.mint, entry.end_slot, entry.fee_amount, entry.tx_count, &entry.merkle_root,
+    );
+    let attestations = count_attestor_signatures(instructions_sysvar, validator_set.active(), &message)?;
+
+    if poster == admin {
         require!(
-            end_slot > validator.last_validated_slot,
-            ErrorCode::StaleValidation
+            validator_set.threshold > 0 && attestations >= validator_set.threshold,
+            ErrorCode::InsufficientAttestations
         );
+        return Ok((Pubkey::default(), attestations));
+    }
 
-        // Validation 2: Slot range sanity (max 1000 slots ~7 minutes)
-        let slot_delta = end_slot.saturating_sub(validator.last_validated_slot);
-        require!(slot_delta <= 1000, ErrorCode::SlotRangeTooLarge);
-
-        // Validation 3: Fee amount sanity check
-        // Max reasonable: 0.01 SOL per slot (very active token)
-        let max_fee_for_range = slot_delta.saturating_mul(10_000_000); // 0.01 SOL * slots
-        require!(fee_amount <= max_fee_for_range, ErrorCode::FeeTooHigh);
+    // Permissionless path: the poster's stake backs the claim
+    let bond = bond.ok_or(ErrorCode::InsufficientBond)?;
+    require!(bond.operator == poster, ErrorCode::InvalidBondAccount);
+    require!(bond.can_attest(entry.fee_amount), ErrorCode::InsufficientBond);
+    bond.open_attestations = bond.open_attestations.saturating_add(1);
+    bond.total_attestations = bond.total_attestations.saturating_add(1);
+    Ok((bond.key(), attestations))
+}
 
-        // Validation 4: TX count sanity (max 100 TX per slot)
-        require!(tx_count <= (slot_delta as u32).saturating_mul(100), ErrorCode::TooManyTransactions);
+/// Consume the attested slot range and announce the attestation
+fn commit_fee_attestation(attestation: &FeeAttestation, validator: &mut ValidatorState, attestations: u8, clock: &Clock) {
+    validator.last_validated_slot = attestation.end_slot;
+
+    emit!(FeeAttestationPosted {
+        mint: attestation.mint,
+        merkle_root: attestation.merkle_root,
+        fee_amount: attestation.fee_amount,
+        tx_count: attestation.tx_count,
+        start_slot: attestation.start_slot,
+        end_slot: attestation.end_slot,
+        challenge_deadline_slot: clock.slot.saturating_add(ATTESTATION_CHALLENGE_WINDOW_SLOTS),
+        attestations,
+        timestamp: clock.unix_timestamp,
+    });
+
+    #[cfg(feature = "verbose")]
+    msg!("Attested {} lamports for {} (slot {}, {} TXs)",
+        attestation.fee_amount, attestation.mint, attestation.end_slot, attestation.tx_count);
+}
 
-        // Validation 5: Pending fees cap (69 SOL max)
-        let new_pending = token_stats.pending_fees_lamports.saturating_add(fee_amount);
-        require!(new_pending <= MAX_PENDING_FEES, ErrorCode::PendingFeesOverflow);
+/// Accumulate pending fees for a token (shared by admin and monitor paths)
+/// Enforces per-token rate limiting and the MAX_PENDING_FEES cap
+fn apply_pending_fees(token_stats: &mut TokenStats, mint: Pubkey, amount_lamports: u64, clock: &Clock) -> Result<()> {
+    // Rate limiting: minimum 10 seconds between updates per token
+    const MIN_FEE_UPDATE_INTERVAL: i64 = 10;
+    require!(
+        clock.unix_timestamp >= token_stats.last_fee_update_timestamp + MIN_FEE_UPDATE_INTERVAL,
+        ErrorCode::CycleTooSoon
+    );
+
+    // Check pending fees cap (69 SOL max)
+    let new_total = token_stats.pending_fees_lamports.saturating_add(amount_lamports);
+    require!(new_total <= MAX_PENDING_FEES, ErrorCode::PendingFeesOverflow);
+
+    // Accumulate pending fees
+    token_stats.pending_fees_lamports = new_total;
+
+    token_stats.last_fee_update_timestamp = clock.unix_timestamp;
+
+    emit!(PendingFeesUpdated {
+        mint,
+        amount: amount_lamports,
+        total_pending: token_stats.pending_fees_lamports,
+        timestamp: clock.unix_timestamp,
+    });
+
+    #[cfg(feature = "verbose")]
+    msg!("Pending fees updated for mint {}: +{} lamports (total: {})",
+        mint,
+        amount_lamports,
+        token_stats.pending_fees_lamports
+    );
 
-        // Update validator state
-        validator.last_validated_slot = end_slot;
-        validator.total_validated_lamports = validator
-            .total_validated_lamports
-            .saturating_add(fee_amount);
-        validator.total_validated_count = validator
-            .total_validated_count
-            .saturating_add(1);
+    Ok(())
+}
 
-        // Update token stats (THIS IS THE KEY - trustless fee attribution!)
-        token_stats.pending_fees_lamports = new_pending;
-        token_stats.last_fee_update_timestamp = clock.unix_timestamp;
+/// Pay a user's rebate (rebate share of pending, boosted by a locked stake) from the pool and reset pending
+/// Shared by the admin push (process_user_rebate) and user pull (claim_rebate) paths
+#[allow(clippy::too_many_arguments)]
+fn settle_user_rebate<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    asdf_mint: &InterfaceAccount<'info, Mint>,
+    rebate_pool: &mut Account<'info, RebatePool>,
+    rebate_pool_ata: &InterfaceAccount<'info, TokenAccount>,
+    user_ata: &InterfaceAccount<'info, TokenAccount>,
+    user_stats: &mut Account<'info, UserStats>,
+    stake_account: Option<&StakeAccount>,
+    clock: &Clock,
+) -> Result<(u64, u64)> {
+    // Validate: pending >= threshold
+    require!(
+        user_stats.pending_contribution >= rebate_pool.rebate_threshold(),
+        ErrorCode::BelowRebateThreshold
+    );
+
+    let pending = user_stats.pending_contribution;
+
+    // Calculate rebate amount (rebate share of pending, 0.552% by default)
+    let mut rebate_amount = rebate_pool.rebate_amount(pending)?;
+
+    // Staking boost on the part of pending covered by a locked stake
+    if let Some(stake) = stake_account {
+        let boost_bps = stake.boost_bps(clock.unix_timestamp);
+        if boost_bps > 0 {
+            let staked_rebate = rebate_pool.rebate_amount(pending.min(stake.amount))?;
+            let boost = (staked_rebate as u128 * boost_bps as u128 / 10_000) as u64;
+            rebate_amount = rebate_amount.checked_add(boost).ok_or(ErrorCode::MathOverflow)?;
+        }
+    }
 
-        emit!(ValidatedFeesRegistered {
-            mint: validator.mint,
-            fee_amount,
-            end_slot,
-            tx_count,
-            total_pending: token_stats.pending_fees_lamports,
-            timestamp: clock.unix_timestamp,
-        });
+    // Validate pool has sufficient funds (epoch reservations are not spendable)
+    require!(
+        rebate_pool.available(rebate_pool_ata.amount) >= rebate_amount,
+        ErrorCode::RebatePoolInsufficient
+    );
+
+    // Transfer rebate from pool → user ATA
+    let rebate_pool_bump = rebate_pool.bump;
+    let seeds: &[&[u8]] = &[REBATE_POOL_SEED, &[rebate_pool_bump]];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: rebate_pool_ata.to_account_info(),
+                mint: asdf_mint.to_account_info(),
+                to: user_ata.to_account_info(),
+                authority: rebate_pool.to_account_info(),
+            },
+            &[seeds],
+        ),
+        rebate_amount,
+        asdf_mint.decimals,
+    )?;
 
-        #[cfg(feature = "verbose")]
-        msg!("Registered {} lamports for {} (slot {}, {} TXs)",
-            fee_amount, validator.mint, end_slot, tx_count);
+    // Update user stats
+    user_stats.pending_contribution = 0;
+    user_stats.total_contributed = user_stats.total_contributed.saturating_add(pending);
+    user_stats.total_rebate = user_stats.total_rebate.saturating_add(rebate_amount);
+    user_stats.last_update_timestamp = clock.unix_timestamp;
+    user_stats.last_update_slot = clock.slot;
 
-        Ok(())
-    }
+    // Update rebate pool stats
+    rebate_pool.total_distributed = rebate_pool.total_distributed.saturating_add(rebate_amount);
+    rebate_pool.rebates_count = rebate_pool.rebates_count.saturating_add(1);
+    rebate_pool.last_rebate_timestamp = clock.unix_timestamp;
+    rebate_pool.last_rebate_slot = clock.slot;
 
-    /// Sync validator slot to current slot (permissionless)
-    ///
-    /// This instruction allows anyone to reset the last_validated_slot to the current slot
-    /// when the validator state has become stale (> MAX_SLOT_RANGE behind current slot).
-    /// This is useful after periods of inactivity to allow the daemon to resume operation.
-    ///
-    /// Note: This does NOT affect fee attribution - it simply allows new validations to proceed.
-    /// Any fees from the skipped slots are lost (this is acceptable for inactivity periods).
-    pub fn sync_validator_slot(ctx: Context<SyncValidatorSlot>) -> Result<()> {
-        let validator = &mut ctx.accounts.validator_state;
-        let clock = Clock::get()?;
-        let current_slot = clock.slot;
+    Ok((pending, rebate_amount))
+}
 
-        // Only allow sync if the validator is stale (more than MAX_SLOT_RANGE behind)
-        let slot_delta = current_slot.saturating_sub(validator.last_validated_slot);
-        require!(slot_delta > 1000, ErrorCode::ValidatorNotStale);
+/// Pay a user's pro-rata share of a closed RebateEpoch and clear their epoch contribution
+/// Shared by claim_epoch_rebate and deposit_fee_asdf (rollover into a new epoch)
+fn settle_epoch_rebate<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    asdf_mint: &InterfaceAccount<'info, Mint>,
+    rebate_pool: &mut Account<'info, RebatePool>,
+    rebate_epoch: &mut Account<'info, RebateEpoch>,
+    rebate_pool_ata: &InterfaceAccount<'info, TokenAccount>,
+    user_ata: &InterfaceAccount<'info, TokenAccount>,
+    user_stats: &mut Account<'info, UserStats>,
+) -> Result<(u64, u64)> {
+    require!(!rebate_epoch.is_open(), ErrorCode::RebateEpochNotClosed);
+    require!(
+        rebate_epoch.epoch == user_stats.contribution_epoch && user_stats.epoch_contribution > 0,
+        ErrorCode::NoEpochContribution
+    );
+
+    let contribution = user_stats.epoch_contribution;
+    let rebate_amount = rebate_epoch.share_of(contribution);
+
+    if rebate_amount > 0 {
+        let rebate_pool_bump = rebate_pool.bump;
+        let seeds: &[&[u8]] = &[REBATE_POOL_SEED, &[rebate_pool_bump]];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: rebate_pool_ata.to_account_info(),
+                    mint: asdf_mint.to_account_info(),
+                    to: user_ata.to_account_info(),
+                    authority: rebate_pool.to_account_info(),
+                },
+                &[seeds],
+            ),
+            rebate_amount,
+            asdf_mint.decimals,
+        )?;
+    }
 
-        let old_slot = validator.last_validated_slot;
-        validator.last_validated_slot = current_slot;
+    rebate_epoch.claimed_amount = rebate_epoch.claimed_amount.saturating_add(rebate_amount);
+    rebate_pool.epoch_reserved = rebate_pool.epoch_reserved.saturating_sub(rebate_amount);
+    rebate_pool.total_distributed = rebate_pool.total_distributed.saturating_add(rebate_amount);
 
-        emit!(ValidatorSlotSynced {
-            mint: validator.mint,
-            old_slot,
-            new_slot: current_slot,
-            slot_delta,
-            timestamp: clock.unix_timestamp,
-        });
+    user_stats.epoch_contribution = 0;
+    user_stats.total_contributed = user_stats.total_contributed.saturating_add(contribution);
+    user_stats.total_rebate = user_stats.total_rebate.saturating_add(rebate_amount);
 
-        #[cfg(feature = "verbose")]
-        msg!("Synced validator slot for {} from {} to {} (delta: {})",
-            validator.mint, old_slot, current_slot, slot_delta);
+    Ok((contribution, rebate_amount))
+}
 
-        Ok(())
+/// Initial dev-buy of a token created by create_v2 in the same instruction
+/// dat_authority spends `lamports` on the fresh bonding curve; the tokens land in its
+/// Token2022 ATA (created here, the admin pays rent) where the next cycle burns them.
+/// `accs` is in execute_pumpfun_cpi order
+#[inline(never)]
+fn initial_dev_buy<'info>(
+    state: &DATState,
+    accs: &[AccountInfo<'info>],
+    admin: &AccountInfo<'info>,
+    associated_token_program: &AccountInfo<'info>,
+    lamports: u64,
+    is_mayhem: bool,
+) -> Result<()> {
+    let (global, fee_recipient, mint, bonding_curve) = (&accs[0], &accs[1], &accs[2], &accs[3]);
+    let (dat_token_account, dat_authority, token_program) = (&accs[5], &accs[6], &accs[8]);
+
+    require!(lamports > 0 && lamports <= state.max_fees_per_cycle, ErrorCode::InvalidParameter);
+    require!(
+        state.spendable_lamports(dat_authority.lamports(), RENT_EXEMPT_MINIMUM + SAFETY_BUFFER) >= lamports,
+        ErrorCode::InsufficientFees
+    );
+    require!(
+        dat_token_account.key()
+            == anchor_spl::associated_token::get_associated_token_address_with_program_id(
+                dat_authority.key,
+                mint.key,
+                &TOKEN_2022_PROGRAM,
+            ),
+        ErrorCode::InvalidTokenAccount
+    );
+    require!(accs[9].key() == pump_creator_vault(dat_authority.key), ErrorCode::InvalidCreatorVault);
+    if is_mayhem {
+        require!(fee_recipient.key() == MAYHEM_FEE_RECIPIENT, ErrorCode::InvalidFeeRecipient);
+    } else {
+        require!(global.owner == &PUMP_PROGRAM, ErrorCode::InvalidPumpGlobalConfig);
+        let recipients = parse_pump_global_fee_recipients(&global.try_borrow_data()?)?;
+        require!(recipients.contains(fee_recipient.key), ErrorCode::InvalidFeeRecipient);
     }
 
-    // Migrate existing TokenStats accounts to include new fields
-    // Call this once per existing token to initialize the new fields
-    pub fn migrate_token_stats(ctx: Context<MigrateTokenStats>) -> Result<()> {
-        use anchor_lang::solana_program::program::invoke;
-        use anchor_lang::solana_program::system_instruction;
+    anchor_spl::associated_token::create_idempotent(CpiContext::new(
+        associated_token_program.clone(),
+        anchor_spl::associated_token::Create {
+            payer: admin.clone(),
+            associated_token: dat_token_account.clone(),
+            authority: dat_authority.clone(),
+            mint: mint.clone(),
+            system_program: accs[7].clone(),
+            token_program: token_program.clone(),
+        },
+    ))?;
+
+    let curve_data = bonding_curve.try_borrow_data()?.to_vec();
+    let (max_sol_cost, desired_tokens, _) = calculate_buy_amount_and_slippage(
+        lamports, &curve_data, state.max_fees_per_cycle, state.slippage_bps, 0, 0,
+    )?;
+    let lamports_before = dat_authority.lamports();
 
-        let token_stats_account = &ctx.accounts.token_stats;
-        let mint = &ctx.accounts.mint;
+    let seeds: &[&[u8]] = &[DAT_AUTHORITY_SEED, &[state.dat_authority_bump]];
+    execute_pumpfun_cpi(
+        global.key(),
+        fee_recipient.key(),
+        mint.key(),
+        bonding_curve.key(),
+        accs[4].key(),
+        dat_token_account.key(),
+        dat_authority.key(),
+        max_sol_cost,
+        desired_tokens,
+        accs,
+        seeds,
+    )?;
 
-        // Verify PDA
-        let (expected_pda, bump) = Pubkey::find_program_address(
-            &[TOKEN_STATS_SEED, mint.key().as_ref()],
-            &crate::ID
-        );
-        require!(token_stats_account.key() == expected_pda, ErrorCode::InvalidParameter);
-        msg!("PDA verified: bump = {}", bump);
+    emit!(InitialBuyExecuted {
+        mint: mint.key(),
+        sol_spent: lamports_before.saturating_sub(dat_authority.lamports()),
+        tokens_received: token_account_amount(dat_token_account)?,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
 
-        let clock = Clock::get()?;
+    Ok(())
+}
 
-        // Check current account size
-        let current_data = token_stats_account.try_borrow_data()?;
-        let current_size = current_data.len();
+/// List `stats.mint` in `page` and record the slot on the stats
+fn register_in_token_registry(
+    registry: &mut TokenRegistry,
+    page: &mut TokenRegistryPage,
+    stats: &mut TokenStats,
+) -> Result<()> {
+    require!(stats.in_registry == 0, ErrorCode::TokenAlreadyRegistered);
+    page.add(stats.mint)?;
+    registry.token_count = registry.token_count.saturating_add(1);
+    stats.registry_page = page.page;
+    stats.in_registry = 1;
+
+    emit!(TokenRegistryUpdated {
+        mint: stats.mint,
+        page: page.page,
+        registered: true,
+        token_count: registry.token_count,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
 
-        // Old size: 8 (discriminator) + 106 (old struct without 3 new fields) = 114 bytes
-        // New size: 8 (discriminator) + 130 (new struct with 3 new fields) = 138 bytes
-        const OLD_SIZE: usize = 114;
-        const NEW_SIZE: usize = 138;
+    Ok(())
+}
 
-        if current_size >= NEW_SIZE {
-            msg!("TokenStats already migrated (size: {})", current_size);
-            return Ok(());
-        }
+/// Unlist a closing TokenStats' mint (no-op for stats that were never registered)
+fn unregister_from_token_registry(
+    registry: Option<&mut TokenRegistry>,
+    page: Option<&mut TokenRegistryPage>,
+    stats: &TokenStats,
+) -> Result<()> {
+    if stats.in_registry == 0 {
+        return Ok(());
+    }
+    let (Some(registry), Some(page)) = (registry, page) else {
+        return err!(ErrorCode::TokenNotInRegistry);
+    };
+    page.remove(&stats.mint)?;
+    registry.token_count = registry.token_count.saturating_sub(1);
 
-        if current_size != OLD_SIZE {
-            msg!("Unexpected TokenStats size: {}. Expected {} or {}", current_size, OLD_SIZE, NEW_SIZE);
-            return err!(ErrorCode::AccountSizeMismatch);
-        }
+    emit!(TokenRegistryUpdated {
+        mint: stats.mint,
+        page: page.page,
+        registered: false,
+        token_count: registry.token_count,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
 
-        msg!("Migrating TokenStats from size {} to {}", OLD_SIZE, NEW_SIZE);
+    Ok(())
+}
 
-        // Read old data (copy before realloc)
-        let mut old_data = vec![0u8; OLD_SIZE];
-        old_data.copy_from_slice(&current_data[..OLD_SIZE]);
-        drop(current_data); // Release borrow
+/// Fill a freshly created TokenStats and screen its mint (rejects dangerous extensions)
+/// Shared by initialize_token_stats and onboard_external_token
+fn init_token_stats(stats: &mut TokenStats, mint: &AccountInfo, payer: Pubkey, bump: u8) -> Result<()> {
+    let clock = Clock::get()?;
+
+    stats.mint = mint.key();
+    stats.total_burned = 0;
+    stats.total_sol_collected = 0;
+    stats.total_sol_used = 0;
+    stats.total_sol_sent_to_root = 0;
+    stats.total_sol_received_from_others = 0;
+    stats.total_buybacks = 0;
+    stats.last_cycle_timestamp = 0;
+    stats.last_cycle_sol = 0;
+    stats.last_cycle_burned = 0;
+    stats.is_root_token = 0;  // Will be set when assigned as root
+    stats.bump = bump;
+    // Initialize new fields for per-token fee tracking
+    stats.pending_fees_lamports = 0;
+    stats.last_fee_update_timestamp = clock.unix_timestamp;
+    stats.cycles_participated = 0;
+    // 100% burn by default (community treasury opt-in per token)
+    stats.burn_bps = DEFAULT_BURN_BPS;
+    stats.total_sent_to_community_treasury = 0;
+
+    // Screen mint for freeze authority and dangerous Token2022 extensions
+    let is_token_2022 = mint.owner == &TOKEN_2022_PROGRAM;
+    let risk_flags = parse_mint_risk_flags(&mint.try_borrow_data()?, is_token_2022)?;
+    require!(risk_flags & MINT_RISK_REJECT_MASK == 0, ErrorCode::DangerousMintExtension);
+    stats.risk_flags = risk_flags;
+
+    emit!(TokenStatsInitialized {
+        mint: stats.mint,
+        timestamp: clock.unix_timestamp,
+    });
+
+    // Active DAT token account = dat_authority ATA until rotated
+    stats.dat_token_account = Pubkey::default();
+    stats.token_account_nonce = 0;
+    stats.usd_value_burned = 0;
+    stats.category = UNCATEGORIZED;
+    stats.is_paused = 0;
+    // Rent-equivalent refund to the initializer at the first completed burn
+    stats.stats_payer = payer;
+    stats.payer_refunded = 0;
+    stats.partner = Pubkey::default();
+    stats.partner_share_bps = 0;
+    stats.total_partner_paid = 0;
+    stats.pending_partner = Pubkey::default();
+    stats.pending_partner_share_bps = 0;
+    stats.pending_partner_timestamp = 0;
+    stats.dust_threshold = 0;
+    stats.dust_burned = 0;
+    stats.consecutive_failures = 0;
+    stats.last_failure_timestamp = 0;
+    stats.reflection_bps = 0;
+    stats.liquidity_bps = 0;
+    stats.total_liquidity_sol = 0;
+    stats.version = TOKEN_STATS_VERSION;
+    stats.partner_paid_pending = 0;
+
+    // Surface remaining risk to the admin before the token is wired into cycles
+    if risk_flags != 0 {
+        emit!(MintRiskFlagged {
+            mint: stats.mint,
+            risk_flags,
+            timestamp: clock.unix_timestamp,
+        });
+    }
 
-        // Reallocate account
-        let rent = Rent::get()?;
-        let new_lamports = rent.minimum_balance(NEW_SIZE);
-        let current_lamports = token_stats_account.lamports();
+    Ok(())
+}
 
-        if new_lamports > current_lamports {
-            let lamports_diff = new_lamports - current_lamports;
-            invoke(
-                &system_instruction::transfer(
-                    ctx.accounts.admin.key,
-                    token_stats_account.key,
-                    lamports_diff,
-                ),
-                &[
-                    ctx.accounts.admin.to_account_info(),
-                    token_stats_account.to_account_info(),
-                    ctx.accounts.system_program.to_account_info(),
-                ],
-            )?;
-        }
+#[program]
+pub mod asdf_dat {
+    use super::*;
 
-        // Realloc the account to new size
-        {
-            let mut lamports = token_stats_account.lamports.borrow_mut();
-            **lamports = new_lamports;
-        }
-        token_stats_account.realloc(NEW_SIZE, false).map_err(|_| ErrorCode::AccountSizeMismatch)?;
-
-        // Write data back with new fields
-        let mut new_data = token_stats_account.try_borrow_mut_data()?;
-        new_data[..OLD_SIZE].copy_from_slice(&old_data);
-
-        // Add new fields at the end (after byte 114)
-        // pending_fees_lamports: u64 = 0
-        new_data[114..122].copy_from_slice(&0u64.to_le_bytes());
-        // last_fee_update_timestamp: i64 = current timestamp
-        new_data[122..130].copy_from_slice(&clock.unix_timestamp.to_le_bytes());
-        // cycles_participated: u64 = total_buybacks (read from old data at offset 72)
-        let total_buybacks = u64::from_le_bytes(
-            old_data[80..88].try_into().map_err(|_| ErrorCode::InvalidParameter)?
-        );
-        new_data[130..138].copy_from_slice(&total_buybacks.to_le_bytes());
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        let mut state = ctx.accounts.dat_state.load_init()?;
+        let clock = Clock::get()?;
+        
+        state.admin = ctx.accounts.admin.key();
+        state.asdf_mint = ASDF_MINT;
+        state.wsol_mint = WSOL_MINT;
+        state.pool_address = POOL_PUMPSWAP;
+        state.pump_swap_program = PUMP_SWAP_PROGRAM;
+        state.total_burned = 0;
+        state.total_sol_collected = 0;
+        state.total_buybacks = 0;
+        state.failed_cycles = 0;
+        state.consecutive_failures = 0;
+        state.is_active = 1;
+        state.emergency_pause = 0;
+        state.last_cycle_timestamp = 0;
+        state.initialized_at = clock.unix_timestamp;
+        state.last_am_execution = 0;
+        state.last_pm_execution = 0;
+        state.min_fees_threshold = MIN_FEES_TO_CLAIM;
+        state.max_fees_per_cycle = MAX_FEES_PER_CYCLE;
+        state.slippage_bps = INITIAL_SLIPPAGE_BPS;
+        state.min_cycle_interval = MIN_CYCLE_INTERVAL;
+        state.dat_authority_bump = ctx.bumps.dat_authority;
+        state.current_fee_recipient_index = 0;
+        state.last_known_price = 0;
+        state.pending_burn_amount = 0;
+        state.root_token_mint = Pubkey::default(); // No root token by default
+        state.fee_split_bps = 5520;          // 55.2% keep, 44.8% to root
+        state.last_sol_sent_to_root = 0;
+        // Security audit additions (v2)
+        state.pending_admin = Pubkey::default(); // No pending admin transfer
+        state.pending_fee_split = 0;             // No pending fee split change
+        state.pending_fee_split_timestamp = 0;
+        state.admin_operation_cooldown = 3600; // Default 1 hour cooldown
+        // HIGH-01 FIX: Separate timestamp for direct fee split changes
+        state.last_direct_fee_split_timestamp = 0;
+        state.max_price_deviation_bps = DEFAULT_MAX_PRICE_DEVIATION_BPS;
+        state.cycle_lock = Pubkey::default();
+        state.governance_epoch = 0;
+        state.governance_epoch_started_at = 0;
+        state.last_upgrade_slot = 0;
+        state.event_sequence = 0;
+        state.auto_resume_after = 0;
+        state.auto_paused_at = 0;
+        state.failure_window_start = 0;
+        state.failing_tokens_in_window = 0;
+        state.auto_pause_failure_threshold = DEFAULT_AUTO_PAUSE_FAILURE_THRESHOLD;
+        state.root_escrow_lamports = 0;
+        state.dev_fee_bps = DEV_FEE_BPS;
+        state.pending_dev_fee_bps = 0;
+        state.pending_dev_fee_timestamp = 0;
+        state.dev_wallet = DEV_WALLET;
+        state.pending_dev_wallet = Pubkey::default();
+        state.reserve_bps = 0;
+        state.insurance_bps = 0;
+        state.randomized_timing = 0;
+        state.max_jito_tip_lamports = 0;
+        state.cycle_lut = Pubkey::default();
+        state.version = DAT_STATE_VERSION;
+        state.pending_import_hash = [0; 32];
 
-        msg!("TokenStats migrated successfully: pending_fees=0, timestamp={}, cycles_participated={}",
-            clock.unix_timestamp,
-            total_buybacks
-        );
+        emit!(DATInitialized {
+            admin: state.admin,
+            dat_authority: ctx.accounts.dat_authority.key(),
+            timestamp: clock.unix_timestamp,
+        });
 
         Ok(())
     }
 
-    /// Migrate DATState account to add new fields (one-time migration)
-    /// This handles the account reallocation from 382 to 390 bytes
-    /// Adding: last_direct_fee_split_timestamp (i64 = 8 bytes)
-    pub fn migrate_dat_state(ctx: Context<MigrateDatState>) -> Result<()> {
-        use anchor_lang::solana_program::program::invoke;
-        use anchor_lang::solana_program::system_instruction;
+    // Initialize per-token statistics tracking (and list the mint in the TokenRegistry)
+    pub fn initialize_token_stats(ctx: Context<InitializeTokenStats>) -> Result<()> {
+        let mut stats = ctx.accounts.token_stats.load_init()?;
+        init_token_stats(
+            &mut stats,
+            &ctx.accounts.mint,
+            ctx.accounts.payer.key(),
+            ctx.bumps.token_stats,
+        )?;
+        register_in_token_registry(
+            &mut ctx.accounts.token_registry,
+            &mut ctx.accounts.token_registry_page,
+            &mut stats,
+        )
+    }
 
-        let dat_state_account = &ctx.accounts.dat_state;
+    /// Append page `page` (= page_count) to the TokenRegistry, once every existing page is full
+    /// Permissionless: the payer funds the page rent
+    pub fn create_token_registry_page(ctx: Context<CreateTokenRegistryPage>, page: u16) -> Result<()> {
+        let registry = &mut ctx.accounts.token_registry;
+        require!(page == registry.page_count, ErrorCode::InvalidParameter);
+        require!(registry.can_add_page(), ErrorCode::TokenRegistryNotFull);
+        registry.bump = ctx.bumps.token_registry;
+        registry.page_count += 1;
+
+        let registry_page = &mut ctx.accounts.token_registry_page;
+        registry_page.page = page;
+        registry_page.count = 0;
+        registry_page.bump = ctx.bumps.token_registry_page;
+
+        emit!(TokenRegistryPageCreated {
+            page,
+            payer: ctx.accounts.payer.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-        // Constants for migration
-        const OLD_SIZE: usize = 382;  // Current on-chain size (8 discriminator + 374 old struct)
-        const NEW_SIZE: usize = 390;  // New size (8 discriminator + 382 new struct)
+        Ok(())
+    }
 
-        let current_data = dat_state_account.try_borrow_data()?;
-        let current_size = current_data.len();
+    /// List a token whose stats predate the TokenRegistry (permissionless backfill)
+    pub fn register_token(ctx: Context<RegisterToken>) -> Result<()> {
+        register_in_token_registry(
+            &mut ctx.accounts.token_registry,
+            &mut ctx.accounts.token_registry_page,
+            &mut *ctx.accounts.token_stats.load_mut()?,
+        )
+    }
 
-        msg!("DATState migration: current size = {}, target size = {}", current_size, NEW_SIZE);
+    // Set the root token that receives 44.8% from other tokens
+    pub fn set_root_token(ctx: Context<SetRootToken>, root_mint: Pubkey) -> Result<()> {
+        let mut state = ctx.accounts.dat_state.load_mut()?;
+        let clock = Clock::get()?;
 
-        // Already migrated?
-        if current_size >= NEW_SIZE {
-            msg!("DATState already migrated (size: {})", current_size);
-            return Ok(());
-        }
+        // Verify admin authorization
+        require!(
+            ctx.accounts.admin.key() == state.admin,
+            ErrorCode::UnauthorizedAccess
+        );
+
+        // Verify TokenStats exists for this mint
+        require!(
+            ctx.accounts.root_token_stats.load()?.mint == root_mint,
+            ErrorCode::InvalidRootToken
+        );
 
-        if current_size != OLD_SIZE {
-            msg!("Unexpected DATState size: {}. Expected {} or {}", current_size, OLD_SIZE, NEW_SIZE);
-            return err!(ErrorCode::AccountSizeMismatch);
+        // Unset the previous root in the same transaction so at most one primary root is flagged
+        if let Some(old_root) = state.root_token_mint().filter(|old| *old != root_mint) {
+            let mut old_stats = ctx.accounts.old_root_token_stats.as_ref()
+                .ok_or(ErrorCode::InvalidRootToken)?
+                .load_mut()?;
+            require!(old_stats.mint == old_root, ErrorCode::InvalidRootToken);
+            old_stats.is_root_token = 0;
+
+            emit!(RootTokenUnset {
+                root_mint: old_root,
+                timestamp: clock.unix_timestamp,
+            });
         }
 
-        // Verify admin from raw data (admin is at offset 8, after discriminator)
-        let admin_bytes = &current_data[8..40];
-        let stored_admin = Pubkey::try_from(admin_bytes).map_err(|_| ErrorCode::InvalidParameter)?;
-        require!(stored_admin == ctx.accounts.admin.key(), ErrorCode::UnauthorizedAccess);
+        // Update state
+        state.root_token_mint = root_mint;
+        log_admin_op(&mut ctx.accounts.admin_log, ADMIN_OP_SET_ROOT_TOKEN, root_mint, 0, 0, ctx.accounts.admin.key())?;
 
-        // Copy old data before realloc
-        let mut old_data = vec![0u8; OLD_SIZE];
-        old_data.copy_from_slice(&current_data[..OLD_SIZE]);
-        drop(current_data); // Release borrow
+        // Mark this token as root
+        ctx.accounts.root_token_stats.load_mut()?.is_root_token = 1;
 
-        // Calculate rent for new size
-        let rent = Rent::get()?;
-        let new_lamports = rent.minimum_balance(NEW_SIZE);
-        let current_lamports = dat_state_account.lamports();
+        emit!(RootTokenSet {
+            root_mint,
+            fee_split_bps: state.fee_split_bps,
+            timestamp: clock.unix_timestamp,
+        });
 
-        // Transfer additional lamports if needed
-        if new_lamports > current_lamports {
-            let lamports_diff = new_lamports - current_lamports;
-            msg!("Transferring {} lamports for rent", lamports_diff);
-            invoke(
-                &system_instruction::transfer(
-                    ctx.accounts.admin.key,
-                    dat_state_account.key,
-                    lamports_diff,
-                ),
-                &[
-                    ctx.accounts.admin.to_account_info(),
-                    dat_state_account.to_account_info(),
-                    ctx.accounts.system_program.to_account_info(),
-                ],
-            )?;
-        }
+        Ok(())
+    }
 
-        // Realloc the account to new size
-        dat_state_account.realloc(NEW_SIZE, false).map_err(|_| ErrorCode::AccountSizeMismatch)?;
+    /// Create the admin audit log (admin only)
+    /// Admin instructions append to it once passed as admin_log
+    pub fn initialize_admin_log(ctx: Context<InitializeAdminLog>) -> Result<()> {
+        let log = &mut ctx.accounts.admin_log;
+        log.total_recorded = 0;
+        log.head = 0;
+        log.entries = [AdminLogEntry::default(); ADMIN_LOG_LEN];
+        log.bump = ctx.bumps.admin_log;
+
+        emit!(AdminLogInitialized {
+            admin_log: log.key(),
+            admin: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
 
-        // Write data back with new field
-        let mut new_data = dat_state_account.try_borrow_mut_data()?;
-        new_data[..OLD_SIZE].copy_from_slice(&old_data);
+    /// Create the RootSet of additional roots (admin only)
+    pub fn initialize_root_set(ctx: Context<InitializeRootSet>) -> Result<()> {
+        let root_set = &mut ctx.accounts.root_set;
+        root_set.roots = [Pubkey::default(); MAX_ROOTS];
+        root_set.root_count = 0;
+        root_set.bump = ctx.bumps.root_set;
+        Ok(())
+    }
 
-        // Add last_direct_fee_split_timestamp at the end (bytes 382-390)
-        // Initialize to 0 (no direct fee split has been done yet)
-        new_data[382..390].copy_from_slice(&0i64.to_le_bytes());
+    /// List an additional root (admin only); it collects from its own root treasury
+    pub fn add_root(ctx: Context<ManageRootSet>) -> Result<()> {
+        let mut root_stats = ctx.accounts.root_token_stats.load_mut()?;
+        let root_mint = root_stats.mint;
+        // The primary root is implicit
+        require!(ctx.accounts.dat_state.load()?.root_token_mint() != Some(root_mint), ErrorCode::RootAlreadyInSet);
 
-        msg!("DATState migrated successfully from {} to {} bytes", OLD_SIZE, NEW_SIZE);
-        msg!("Added field: last_direct_fee_split_timestamp = 0");
+        let root_set = &mut ctx.accounts.root_set;
+        root_set.add(root_mint)?;
+        root_stats.is_root_token = 1;
 
+        emit!(RootSetUpdated {
+            root_mint,
+            added: true,
+            root_count: root_set.root_count,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
         Ok(())
     }
 
-    pub fn collect_fees(ctx: Context<CollectFees>, is_root_token: bool, for_ecosystem: bool) -> Result<()> {
-        let state = &mut ctx.accounts.dat_state;
-        let clock = Clock::get()?;
+    /// Unlist an additional root (admin only)
+    /// Secondaries still assigned to it fail their split until reassigned
+    pub fn remove_root(ctx: Context<ManageRootSet>) -> Result<()> {
+        let mut root_stats = ctx.accounts.root_token_stats.load_mut()?;
+        let root_mint = root_stats.mint;
+        let root_set = &mut ctx.accounts.root_set;
+        root_set.remove(&root_mint)?;
+        root_stats.is_root_token = 0;
 
-        require!(state.is_active && !state.emergency_pause, ErrorCode::DATNotActive);
+        emit!(RootSetUpdated {
+            root_mint,
+            added: false,
+            root_count: root_set.root_count,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
 
-        // Enforce minimum cycle interval (disabled in testing mode)
-        if !TESTING_MODE {
-            require!(
-                clock.unix_timestamp - state.last_cycle_timestamp >= state.min_cycle_interval,
-                ErrorCode::CycleTooSoon
-            );
+    /// Assign a secondary to a listed root (admin only, Pubkey::default() = primary root)
+    pub fn assign_root(ctx: Context<AssignRoot>, root_mint: Pubkey) -> Result<()> {
+        let stats = ctx.accounts.token_stats.load()?;
+        let mint = stats.mint;
+        require!(stats.is_root_token == 0, ErrorCode::InvalidRootToken);
+        if root_mint != Pubkey::default() {
+            let listed = ctx.accounts.root_set.as_ref().is_some_and(|set| set.contains(&root_mint));
+            require!(listed, ErrorCode::RootNotInSet);
         }
 
-        state.last_cycle_timestamp = clock.unix_timestamp;
+        let clock = Clock::get()?;
+        let config = &mut ctx.accounts.token_config;
+        config.mint = mint;
+        config.assigned_root = root_mint;
+        config.updated_at = clock.unix_timestamp;
+        config.bump = ctx.bumps.token_config;
+        config.version = TOKEN_CONFIG_VERSION;
+
+        emit!(RootAssigned {
+            mint,
+            root_mint,
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
 
-        // NOTE: AM/PM execution limits removed - random timing now controlled by TypeScript daemon
-        // The orchestrator handles 1/day per token scheduling with randomized timing
+    /// Set a secondary's root flush schedule (admin only)
+    /// flush_every_cycles > 0 streams: execute_buy_secondary accrues the root share and
+    /// flush_to_root transfers it every flush_every_cycles cycles; 0 restores lump transfers
+    pub fn configure_root_stream(ctx: Context<ConfigureRootStream>, flush_every_cycles: u16) -> Result<()> {
+        let stats = ctx.accounts.token_stats.load()?;
+        require!(stats.is_root_token == 0, ErrorCode::InvalidRootToken);
+        require!(flush_every_cycles <= MAX_ROOT_FLUSH_EVERY_CYCLES, ErrorCode::InvalidParameter);
+
+        let stream = &mut ctx.accounts.root_stream;
+        stream.mint = stats.mint;
+        stream.flush_every_cycles = flush_every_cycles;
+        stream.bump = ctx.bumps.root_stream;
+
+        emit!(RootStreamConfigured {
+            mint: stream.mint,
+            flush_every_cycles,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
 
-        // Enforce minimum fees threshold (disabled in testing mode)
-        // NOTE: Skip threshold check when for_ecosystem=true (N+1 pattern)
-        // In N+1, the first token drains the vault and subsequent tokens use datAuthority balance
-        // The threshold check only applies to standalone/first-token collections
-        if !TESTING_MODE && !for_ecosystem {
-            let vault_balance = ctx.accounts.creator_vault.lamports();
-            require!(vault_balance >= state.min_fees_threshold, ErrorCode::InsufficientFees);
-        }
+    /// Transfer a secondary's accrued root share to its assigned root's treasury (permissionless)
+    /// Due once flush_every_cycles cycles accrued, or at any time after streaming was turned off
+    pub fn flush_to_root(ctx: Context<FlushToRoot>) -> Result<()> {
 
-        let seeds = &[DAT_AUTHORITY_SEED, &[state.dat_authority_bump]];
+        let root_mint = RootSet::resolve(
+            assigned_root_of(&ctx.accounts.token_config)?,
+            ctx.accounts.dat_state.load()?.root_token_mint(),
+            ctx.accounts.root_set.as_deref(),
+        )?;
+        let (expected_treasury, _) = Pubkey::find_program_address(
+            &[ROOT_TREASURY_SEED, root_mint.as_ref()],
+            ctx.program_id
+        );
+        require!(expected_treasury == ctx.accounts.root_treasury.key(), ErrorCode::InvalidRootTreasury);
+        require!(ctx.accounts.root_stream.flush_due(), ErrorCode::RootFlushNotDue);
 
-        // Track vault balance before collection
-        let vault_balance_before = ctx.accounts.creator_vault.lamports();
+        let clock = Clock::get()?;
+        let stream = &mut ctx.accounts.root_stream;
+        let cycles = stream.cycles_accrued;
+        let amount = stream.take_flush(clock.unix_timestamp);
 
-        // STEP 1: Collect from creator vault (all tokens)
-        collect_creator_fee_cpi(
-            &ctx.accounts.dat_authority,
-            &ctx.accounts.creator_vault,
-            &ctx.accounts.system_program,
-            &ctx.accounts.pump_event_authority,
-            &ctx.accounts.pump_swap_program,
-            seeds,
+        let mut state = ctx.accounts.dat_state.load_mut()?;
+        state.root_escrow_lamports = state.root_escrow_lamports.saturating_sub(amount);
+        let seeds: &[&[u8]] = &[DAT_AUTHORITY_SEED, &[state.dat_authority_bump]];
+        invoke_signed(
+            &anchor_lang::solana_program::system_instruction::transfer(
+                ctx.accounts.dat_authority.key,
+                ctx.accounts.root_treasury.key,
+                amount,
+            ),
+            &[
+                ctx.accounts.dat_authority.to_account_info(),
+                ctx.accounts.root_treasury.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+            &[seeds],
         )?;
 
-        // Track SOL collected from vault
-        let vault_balance_after = ctx.accounts.creator_vault.lamports();
-        let sol_from_vault = vault_balance_before.saturating_sub(vault_balance_after);
-        ctx.accounts.token_stats.total_sol_collected = ctx.accounts.token_stats.total_sol_collected.saturating_add(sol_from_vault);
+        let mut token_stats = ctx.accounts.token_stats.load_mut()?;
+        token_stats.total_sol_sent_to_root = token_stats.total_sol_sent_to_root.saturating_add(amount);
 
-        // STEP 2: If root token, also collect from root treasury
-        if is_root_token {
-            if let Some(root_treasury) = &ctx.accounts.root_treasury {
-                let treasury_amt = root_treasury.lamports();
-                if treasury_amt > 0 {
-                    // Root treasury is a PDA: seeds = ["root_treasury", root_token_mint, bump]
-                    let root_mint = state.root_token_mint
-                        .ok_or(ErrorCode::InvalidRootToken)?;
-                    let (expected_treasury, bump) = Pubkey::find_program_address(
-                        &[ROOT_TREASURY_SEED, root_mint.as_ref()],
-                        ctx.program_id
-                    );
-                    require!(expected_treasury == *root_treasury.key, ErrorCode::InvalidRootTreasury);
+        emit!(RootFlushed {
+            mint: stream.mint,
+            root_mint,
+            amount,
+            cycles,
+            total_flushed: stream.total_flushed,
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
 
-                    // Create seeds with bump for signing
-                    let bump_slice = &[bump];
-                    let treasury_seeds: &[&[u8]] = &[ROOT_TREASURY_SEED, root_mint.as_ref(), bump_slice];
+    /// Grow a TokenConfig created before assigned_root existed (admin only)
+    /// The zero-filled assigned_root keeps the token on the primary root
+    pub fn migrate_token_config(ctx: Context<MigrateTokenConfig>) -> Result<()> {
+        migrations::migrate(
+            AccountKind::TokenConfig,
+            &ctx.accounts.token_config,
+            &ctx.accounts.admin.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &migrations::MigrationEnv::default(),
+        )?;
+        Ok(())
+    }
 
-                    invoke_signed(
-                        &anchor_lang::solana_program::system_instruction::transfer(
-                            root_treasury.key,
-                            ctx.accounts.dat_authority.key,
-                            treasury_amt
-                        ),
-                        &[
-                            root_treasury.to_account_info(),
-                            ctx.accounts.dat_authority.to_account_info(),
-                            ctx.accounts.system_program.to_account_info()
-                        ],
-                        &[treasury_seeds]
-                    )?;
+    /// Update ASDF mint address (admin only, TESTING mode only)
+    /// Used for devnet testing where the initial mint may be incorrect.
+    /// This instruction is DISABLED on mainnet (TESTING_MODE = false).
+    #[cfg(feature = "testing")]
+    pub fn update_asdf_mint(ctx: Context<AdminControl>, new_asdf_mint: Pubkey) -> Result<()> {
+        let mut state = ctx.accounts.dat_state.load_mut()?;
+        let clock = Clock::get()?;
 
-                    // Track SOL received from other tokens
-                    ctx.accounts.token_stats.total_sol_received_from_others =
-                        ctx.accounts.token_stats.total_sol_received_from_others.saturating_add(treasury_amt);
-                    ctx.accounts.token_stats.total_sol_collected =
-                        ctx.accounts.token_stats.total_sol_collected.saturating_add(treasury_amt);
+        // Update the mint
+        let old_mint = state.asdf_mint;
+        state.asdf_mint = new_asdf_mint;
 
-                    emit!(RootTreasuryCollected {
-                        root_mint,
-                        amount: treasury_amt,
-                        timestamp: clock.unix_timestamp
-                    });
-                    msg!("Root treasury collected: {} lamports", treasury_amt);
-                }
-            }
+        msg!(
+            "ASDF mint updated: {} -> {} (TESTING MODE ONLY)",
+            old_mint,
+            new_asdf_mint
+        );
+
+        emit!(AsdfMintUpdated {
+            old_mint,
+            new_mint: new_asdf_mint,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    // Update the fee split ratio (admin only)
+    // Bounded between 1000 (10%) and 9000 (90%) to prevent extreme configurations
+    // HIGH-02 FIX: Maximum 5% (500 bps) change per call to prevent instant rug
+    // NOTE: For larger changes, use propose_fee_split + execute_fee_split (timelocked)
+    // Timelocked: queues the change for execute_change after admin_operation_cooldown
+    pub fn update_fee_split(ctx: Context<ChangeQueueAdmin>, new_fee_split_bps: u16) -> Result<()> {
+        let mut preview = DATState::clone(&*ctx.accounts.dat_state.load()?);
+        require!(
+            new_fee_split_bps.abs_diff(preview.fee_split_bps) <= 500,
+            ErrorCode::FeeSplitDeltaTooLarge
+        );
+        let delay = preview.admin_operation_cooldown;
+        queue_parameter_change(&mut ctx.accounts.change_queue, &mut preview, ADMIN_OP_FEE_SPLIT, new_fee_split_bps as u64, delay)?;
+        Ok(())
+    }
+
+    // Update the root price circuit breaker (admin only)
+    // max_deviation_bps = 0 disables the check; reset_observation clears last_known_price
+    // so the next root buy re-seeds the baseline after a legitimate market move
+    // Timelocked: queues the change(s) for execute_change after admin_operation_cooldown
+    pub fn update_price_guard(
+        ctx: Context<ChangeQueueAdmin>,
+        max_deviation_bps: u16,
+        reset_observation: bool,
+    ) -> Result<()> {
+        let mut preview = DATState::clone(&*ctx.accounts.dat_state.load()?);
+        let delay = preview.admin_operation_cooldown;
+        let queue = &mut ctx.accounts.change_queue;
+
+        queue_parameter_change(queue, &mut preview, ADMIN_OP_PRICE_GUARD, max_deviation_bps as u64, delay)?;
+        if reset_observation {
+            queue_parameter_change(queue, &mut preview, ADMIN_OP_PRICE_OBSERVATION_RESET, 1, delay)?;
         }
+        Ok(())
+    }
 
-        // Reset pending fees unless in ecosystem mode (where orchestrator manages distribution)
-        if !for_ecosystem {
-            ctx.accounts.token_stats.pending_fees_lamports = 0;
-            msg!("Pending fees reset (standalone mode)");
-        } else {
-            msg!("Ecosystem mode: pending fees NOT reset (orchestrator will distribute)");
+    // Enable/disable shadow-mode buy math for a mint (admin only)
+    // While enabled, execute_buy also computes the candidate exact-out parameters,
+    // still executes with the current math, and records the delta in ShadowComparison.
+    // reset clears accumulated samples (e.g. before evaluating a revised candidate)
+    pub fn configure_shadow_math(
+        ctx: Context<ConfigureShadowMath>,
+        enabled: bool,
+        reset: bool,
+    ) -> Result<()> {
+        let comparison = &mut ctx.accounts.shadow_comparison;
+        let mint = ctx.accounts.mint.key();
+        let now = Clock::get()?.unix_timestamp;
+
+        let is_new = comparison.mint == Pubkey::default();
+        if is_new || reset {
+            comparison.mint = mint;
+            comparison.samples = 0;
+            comparison.last_slot = 0;
+            comparison.last_old_sol_cost = 0;
+            comparison.last_old_tokens = 0;
+            comparison.last_new_sol_cost = 0;
+            comparison.last_new_tokens = 0;
+            comparison.last_actual_tokens = 0;
+            comparison.cumulative_token_delta = 0;
+            comparison.cumulative_sol_delta = 0;
+            comparison.max_token_delta_bps = 0;
+            comparison.bump = ctx.bumps.shadow_comparison;
         }
+        comparison.shadow_math = enabled;
+        comparison.updated_at = now;
+
+        emit!(ShadowMathConfigured {
+            mint,
+            enabled,
+            reset,
+            timestamp: now,
+        });
 
-        msg!("Fees collected (for_ecosystem: {})", for_ecosystem);
         Ok(())
     }
 
-    /// Collect fees from PumpSwap AMM creator vault
-    /// Used for tokens that have migrated from bonding curve to AMM
-    /// Requires: DAT authority PDA must be set as coin_creator in PumpSwap
-    /// IMPORTANT: This collects WSOL (SPL Token), not native SOL
-    pub fn collect_fees_amm(ctx: Context<CollectFeesAMM>) -> Result<()> {
-        let state = &ctx.accounts.dat_state;
-        require!(state.is_active && !state.emergency_pause, ErrorCode::DATNotActive);
-
-        let bump = state.dat_authority_bump;
-        let seeds: &[&[u8]] = &[DAT_AUTHORITY_SEED, &[bump]];
+    // Update pending fees for a specific token (admin/monitor only)
+    // Used by off-chain fee monitor to track per-token fee attribution
+    pub fn update_pending_fees(
+        ctx: Context<UpdatePendingFees>,
+        amount_lamports: u64,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        apply_pending_fees(&mut *ctx.accounts.token_stats.load_mut()?, ctx.accounts.mint.key(), amount_lamports, &clock)
+    }
 
-        // Track WSOL balance before collection
-        let wsol_before = ctx.accounts.dat_wsol_account.amount;
+    /// Update pending fees from a registered monitor key (quota-bounded)
+    /// If the update would exceed the key's daily quota, the key is suspended,
+    /// MonitorKeySuspended is emitted and the update is dropped (not applied)
+    pub fn update_pending_fees_monitor(
+        ctx: Context<UpdatePendingFeesMonitor>,
+        amount_lamports: u64,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let monitor_key = &mut ctx.accounts.monitor_key;
+        require!(!monitor_key.is_suspended, ErrorCode::MonitorKeySuspended);
+
+        let remaining = monitor_key.remaining_quota(clock.unix_timestamp);
+        if amount_lamports > remaining {
+            // Suspend instead of erroring so the suspension persists
+            monitor_key.is_suspended = true;
+            emit!(MonitorKeySuspended {
+                authority: monitor_key.authority,
+                mint: ctx.accounts.mint.key(),
+                attempted_lamports: amount_lamports,
+                attributed_today: monitor_key.attributed_today,
+                daily_quota_lamports: monitor_key.daily_quota_lamports,
+                timestamp: clock.unix_timestamp,
+            });
+            msg!("Monitor key {} suspended: quota exceeded", monitor_key.authority);
+            return Ok(());
+        }
 
-        // Call PumpSwap's collect_coin_creator_fee via CPI
-        // DAT authority PDA signs as the coin_creator
-        collect_amm_creator_fee_cpi(
-            &ctx.accounts.wsol_mint.to_account_info(),
-            &ctx.accounts.token_program.to_account_info(),
-            &ctx.accounts.dat_authority.to_account_info(),
-            &ctx.accounts.creator_vault_authority.to_account_info(),
-            &ctx.accounts.creator_vault_ata.to_account_info(),
-            &ctx.accounts.dat_wsol_account.to_account_info(),
-            &ctx.accounts.pump_swap_program.to_account_info(),
-            seeds,
-        )?;
+        monitor_key.attributed_today = monitor_key.attributed_today.saturating_add(amount_lamports);
+        monitor_key.total_attributed = monitor_key.total_attributed.saturating_add(amount_lamports);
 
-        // NOTE: reload() required after CPI to get updated WSOL balance - Anchor doesn't auto-reload for invoke_signed
-        ctx.accounts.dat_wsol_account.reload()?;
-        let wsol_after = ctx.accounts.dat_wsol_account.amount;
-        let wsol_collected = wsol_after.saturating_sub(wsol_before);
+        apply_pending_fees(&mut *ctx.accounts.token_stats.load_mut()?, ctx.accounts.mint.key(), amount_lamports, &clock)
+    }
 
-        // Update token stats
-        ctx.accounts.token_stats.total_sol_collected =
-            ctx.accounts.token_stats.total_sol_collected.saturating_add(wsol_collected);
+    /// Register a monitor key with a daily attribution quota (admin only)
+    pub fn register_monitor_key(ctx: Context<RegisterMonitorKey>, daily_quota_lamports: u64) -> Result<()> {
+        require!(
+            daily_quota_lamports > 0 && daily_quota_lamports <= MAX_PENDING_FEES,
+            ErrorCode::InvalidParameter
+        );
 
-        msg!("AMM creator fees collected: {} WSOL", wsol_collected);
-        emit!(AmmFeesCollected {
-            mint: ctx.accounts.token_stats.mint,
-            wsol_amount: wsol_collected,
-            timestamp: Clock::get()?.unix_timestamp,
+        let clock = Clock::get()?;
+        let monitor_key = &mut ctx.accounts.monitor_key;
+        monitor_key.authority = ctx.accounts.monitor.key();
+        monitor_key.daily_quota_lamports = daily_quota_lamports;
+        monitor_key.attributed_today = 0;
+        monitor_key.current_day = clock.unix_timestamp / SECONDS_PER_DAY;
+        monitor_key.total_attributed = 0;
+        monitor_key.is_suspended = false;
+        monitor_key.bump = ctx.bumps.monitor_key;
+
+        emit!(MonitorKeyRegistered {
+            authority: monitor_key.authority,
+            daily_quota_lamports,
+            timestamp: clock.unix_timestamp,
         });
 
         Ok(())
     }
 
-    /// Unwrap WSOL to native SOL in DAT authority account
-    /// Call this after collect_fees_amm to convert WSOL to SOL for buyback
-    pub fn unwrap_wsol(ctx: Context<UnwrapWsol>) -> Result<()> {
-        let state = &ctx.accounts.dat_state;
-        require!(state.is_active && !state.emergency_pause, ErrorCode::DATNotActive);
+    /// Update a monitor key's quota and suspension flag (admin only)
+    /// Passing suspended = false reinstates a key suspended for exceeding its quota
+    pub fn update_monitor_key(
+        ctx: Context<UpdateMonitorKey>,
+        daily_quota_lamports: u64,
+        suspended: bool,
+    ) -> Result<()> {
+        require!(
+            daily_quota_lamports > 0 && daily_quota_lamports <= MAX_PENDING_FEES,
+            ErrorCode::InvalidParameter
+        );
 
-        let bump = state.dat_authority_bump;
-        let seeds: &[&[u8]] = &[DAT_AUTHORITY_SEED, &[bump]];
+        let monitor_key = &mut ctx.accounts.monitor_key;
+        monitor_key.daily_quota_lamports = daily_quota_lamports;
+        monitor_key.is_suspended = suspended;
 
-        // Get WSOL balance to unwrap
-        let wsol_amount = ctx.accounts.dat_wsol_account.amount;
-        require!(wsol_amount > 0, ErrorCode::InsufficientFees);
+        emit!(MonitorKeyUpdated {
+            authority: monitor_key.authority,
+            daily_quota_lamports,
+            is_suspended: suspended,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-        // Close the WSOL token account (transfers lamports to dat_authority)
-        let cpi_accounts = anchor_spl::token::CloseAccount {
-            account: ctx.accounts.dat_wsol_account.to_account_info(),
-            destination: ctx.accounts.dat_authority.to_account_info(),
-            authority: ctx.accounts.dat_authority.to_account_info(),
-        };
-        let signer_seeds: &[&[&[u8]]] = &[seeds];
-        let cpi_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            cpi_accounts,
-            signer_seeds,
-        );
-        anchor_spl::token::close_account(cpi_ctx)?;
+        Ok(())
+    }
+
+    /// Register the signer as a keeper earning tiered rewards for the cycles it completes
+    /// (permissionless, keeper funds rent)
+    pub fn register_keeper(ctx: Context<RegisterKeeper>) -> Result<()> {
+        let clock = Clock::get()?;
+        let keeper_info = &mut ctx.accounts.keeper_info;
+        keeper_info.keeper = ctx.accounts.keeper.key();
+        keeper_info.is_active = true;
+        keeper_info.registered_at = clock.unix_timestamp;
+        keeper_info.successful_cycles = 0;
+        keeper_info.total_rewards = 0;
+        keeper_info.last_cycle_timestamp = 0;
+        keeper_info.bump = ctx.bumps.keeper_info;
+
+        emit!(KeeperRegistered {
+            keeper: keeper_info.keeper,
+            timestamp: clock.unix_timestamp,
+        });
 
-        msg!("WSOL unwrapped: {} lamports now in DAT authority", wsol_amount);
         Ok(())
     }
 
-    /// Wrap native SOL to WSOL for AMM buyback
-    /// Call this before execute_buy_amm when root token is on PumpSwap AMM
-    /// The dat_wsol_account must already exist (created by caller)
-    pub fn wrap_wsol(ctx: Context<WrapWsol>, amount: u64) -> Result<()> {
-        let state = &ctx.accounts.dat_state;
-        require!(state.is_active && !state.emergency_pause, ErrorCode::DATNotActive);
-        require!(amount > 0, ErrorCode::InsufficientFees);
+    /// Stop a keeper's rewards (admin or the keeper itself)
+    /// The record is kept; the keeper may still crank cycles, unrewarded
+    pub fn deactivate_keeper(ctx: Context<DeactivateKeeper>) -> Result<()> {
+        let keeper_info = &mut ctx.accounts.keeper_info;
+        require!(keeper_info.is_active, ErrorCode::KeeperInactive);
+        keeper_info.is_active = false;
+
+        emit!(KeeperDeactivated {
+            keeper: keeper_info.keeper,
+            deactivated_by: ctx.accounts.authority.key(),
+            successful_cycles: keeper_info.successful_cycles,
+            total_rewards: keeper_info.total_rewards,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-        let bump = state.dat_authority_bump;
-        let seeds: &[&[u8]] = &[DAT_AUTHORITY_SEED, &[bump]];
+        Ok(())
+    }
 
-        // Verify sufficient balance in dat_authority
-        let available = ctx.accounts.dat_authority.lamports()
-            .saturating_sub(RENT_EXEMPT_MINIMUM + SAFETY_BUFFER);
-        require!(available >= amount, ErrorCode::InsufficientFees);
+    /// Initialize validator state for trustless per-token fee tracking
+    /// Must be called once per token before register_validated_fees can be used
+    pub fn initialize_validator(ctx: Context<InitializeValidator>) -> Result<()> {
+        let state = &mut ctx.accounts.validator_state;
+        let clock = Clock::get()?;
 
-        // Transfer native SOL from dat_authority to dat_wsol_account
-        let transfer_ix = anchor_lang::solana_program::system_instruction::transfer(
-            &ctx.accounts.dat_authority.key(),
-            &ctx.accounts.dat_wsol_account.key(),
-            amount,
+        state.mint = ctx.accounts.mint.key();
+        state.bonding_curve = ctx.accounts.bonding_curve.key();
+        state.last_validated_slot = clock.slot;
+        state.total_validated_lamports = 0;
+        state.total_validated_count = 0;
+        state.fee_rate_bps = 50; // 0.5% default PumpFun creator fee
+        state.bump = ctx.bumps.validator_state;
+        state.max_fee_per_slot = DEFAULT_MAX_FEE_PER_SLOT;
+        state.max_tx_per_slot = DEFAULT_MAX_TX_PER_SLOT;
+        state._reserved = [0u8; 20];
+
+        emit!(ValidatorInitialized {
+            mint: state.mint,
+            bonding_curve: state.bonding_curve,
+            slot: clock.slot,
+            timestamp: clock.unix_timestamp,
+        });
+
+        #[cfg(feature = "verbose")]
+        msg!("Validator initialized for mint {} with bonding curve {}",
+            state.mint, state.bonding_curve);
+
+        Ok(())
+    }
+
+    /// ADMIN ONLY - Close a validator state and recover its rent
+    /// Requires zero pending fees on the token; rent goes to rent_destination
+    pub fn close_validator_state(ctx: Context<CloseValidatorState>) -> Result<()> {
+        require!(ctx.accounts.token_stats.load()?.pending_fees_lamports == 0, ErrorCode::PendingFeesNotZero);
+
+        emit!(ValidatorStateClosed {
+            mint: ctx.accounts.validator_state.mint,
+            rent_destination: ctx.accounts.rent_destination.key(),
+            lamports_recovered: ctx.accounts.validator_state.to_account_info().lamports(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// ADMIN ONLY - Close the stats of a dead or mistakenly onboarded token
+    /// Requires zero pending fees and nothing awaiting burn; the root token cannot be closed
+    pub fn close_token_stats(ctx: Context<CloseTokenStats>) -> Result<()> {
+        let token_stats = ctx.accounts.token_stats.load()?;
+
+        require!(
+            ctx.accounts.dat_state.load()?.root_token_mint() != Some(token_stats.mint),
+            ErrorCode::CannotCloseRootToken
         );
-        invoke_signed(
-            &transfer_ix,
-            &[
-                ctx.accounts.dat_authority.to_account_info(),
-                ctx.accounts.dat_wsol_account.to_account_info(),
-                ctx.accounts.system_program.to_account_info(),
-            ],
-            &[seeds],
+        require!(token_stats.pending_fees_lamports == 0, ErrorCode::PendingFeesNotZero);
+        require!(ctx.accounts.dat_token_account.amount == 0, ErrorCode::PendingBurnNotZero);
+        unregister_from_token_registry(
+            ctx.accounts.token_registry.as_deref_mut(),
+            ctx.accounts.token_registry_page.as_deref_mut().map(|page| &mut **page),
+            &token_stats,
         )?;
 
-        // Sync native - updates the WSOL token balance to match lamports
-        let sync_accounts = token::SyncNative {
-            account: ctx.accounts.dat_wsol_account.to_account_info(),
-        };
-        let cpi_ctx = CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            sync_accounts,
-        );
-        token::sync_native(cpi_ctx)?;
+        emit!(TokenStatsClosed {
+            mint: token_stats.mint,
+            rent_destination: ctx.accounts.rent_destination.key(),
+            lamports_recovered: ctx.accounts.token_stats.to_account_info().lamports(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-        msg!("WSOL wrapped: {} lamports converted to WSOL", amount);
         Ok(())
     }
 
-    /// Execute buy on bonding curve - ROOT TOKEN ONLY (simpler, no split logic)
-    /// For secondary tokens, use execute_buy_secondary instead
-    pub fn execute_buy(
-        ctx: Context<ExecuteBuy>,
-        allocated_lamports: Option<u64>,
+    /// ADMIN ONLY - Update a validator's per-slot sanity caps
+    /// Lets very active tokens attest legitimate fees above the defaults,
+    /// bounded by MAX_FEE_PER_SLOT_LIMIT / MAX_TX_PER_SLOT_LIMIT
+    /// Timelocked: queues both caps for execute_change after admin_operation_cooldown
+    pub fn update_validator_limits(
+        ctx: Context<UpdateValidatorLimits>,
+        max_fee_per_slot: u64,
+        max_tx_per_slot: u32,
     ) -> Result<()> {
-        require!(ctx.accounts.dat_state.is_active && !ctx.accounts.dat_state.emergency_pause, ErrorCode::DATNotActive);
+        let state = ctx.accounts.dat_state.load()?;
+        let delay = state.admin_operation_cooldown;
+        let mint = ctx.accounts.validator_state.mint;
+        let mut preview = ValidatorState::clone(&ctx.accounts.validator_state);
+        let queue = &mut ctx.accounts.change_queue;
+
+        for (param, value) in [
+            (ADMIN_OP_VALIDATOR_MAX_FEE, max_fee_per_slot),
+            (ADMIN_OP_VALIDATOR_MAX_TX, max_tx_per_slot as u64),
+        ] {
+            preview.apply_parameter(param, value)?;
+            queue_change(queue, &state, param, mint, value, delay)?;
+        }
+        Ok(())
+    }
 
-        // Calculate buy amount (root token - no ATA reserve needed)
-        let buy_amount = match allocated_lamports {
-            Some(a) => a.saturating_sub(SAFETY_BUFFER),
-            None => ctx.accounts.dat_authority.lamports().saturating_sub(RENT_EXEMPT_MINIMUM + SAFETY_BUFFER),
-        };
-        require!(buy_amount >= MINIMUM_BUY_AMOUNT, ErrorCode::InsufficientFees);
+    /// ADMIN ONLY - Reset validator slot to current slot
+    /// Used when validator has been inactive for too long (slot delta > 1000)
+    /// This allows the validator daemon to resume operation without redeploying
+    pub fn reset_validator_slot(ctx: Context<ResetValidatorSlot>) -> Result<()> {
+        let state = &mut ctx.accounts.validator_state;
+        let clock = Clock::get()?;
 
-        // Delegate to CPI helper
-        execute_buy_inner(ctx, buy_amount)
+        let old_slot = state.last_validated_slot;
+        state.last_validated_slot = clock.slot;
+
+        emit!(ValidatorSlotReset {
+            mint: state.mint,
+            old_slot,
+            new_slot: clock.slot,
+            timestamp: clock.unix_timestamp,
+        });
+
+        #[cfg(feature = "verbose")]
+        msg!("Validator slot reset from {} to {} for mint {}",
+            old_slot, clock.slot, state.mint);
+
+        Ok(())
     }
 
-    /// Execute buy for SECONDARY tokens (includes fee split to root treasury)
-    pub fn execute_buy_secondary(
-        mut ctx: Context<ExecuteBuySecondary>,
-        allocated_lamports: Option<u64>,
+    /// Commit a fee attestation for PumpFun transaction logs (admin or bonded validator)
+    /// The daemon posts a Merkle root of (slot, tx signature, fee) leaves instead of
+    /// crediting raw totals. Fees are credited by finalize_attestation once the
+    /// challenge window passes without a successful challenge.
+    ///
+    /// Admin posts require ed25519 signatures from at least validator_set.threshold attestors
+    /// over fee_attestation_message(), placed as Ed25519SigVerify instructions before this one.
+    /// Any other poster must pass its ValidatorBond: the claim is capped at the bond and
+    /// disputed through slash_validator, so the stake replaces the attestor quorum.
+    ///
+    /// Security: Protected by admin quorum or bond, slot progression, fee caps and fraud proofs
+    pub fn register_validated_fees(
+        ctx: Context<RegisterValidatedFees>,
+        fee_amount: u64,
+        end_slot: u64,
+        tx_count: u32,
+        merkle_root: [u8; 32],
     ) -> Result<()> {
-        let state = &mut ctx.accounts.dat_state;
-        require!(state.is_active && !state.emergency_pause, ErrorCode::DATNotActive);
-        require!(state.root_token_mint.is_some(), ErrorCode::InvalidRootToken);
+        let clock = Clock::get()?;
+        let entry = ValidatedFeeEntry {
+            mint: ctx.accounts.validator_state.mint,
+            fee_amount,
+            end_slot,
+            tx_count,
+            merkle_root,
+        };
 
-        let bump = state.dat_authority_bump;
-        let fee_split_bps = state.fee_split_bps;
-        // Defensive check: fee_split_bps must be valid (1000-9000 range enforced by update_fee_split)
-        require!(fee_split_bps > 0 && fee_split_bps <= 10000, ErrorCode::InvalidFeeSplit);
-        let seeds: &[&[u8]] = &[DAT_AUTHORITY_SEED, &[bump]];
+        // Validations 1-5: slot progression, range, fee/TX sanity, pending cap
+        check_fee_attestation(&ctx.accounts.validator_state, &*ctx.accounts.token_stats.load()?, &entry)?;
+
+        // Validation 6: admin attestor quorum or poster bond
+        let (bond_key, attestations) = authorize_fee_attestation(
+            ctx.accounts.poster.key(),
+            ctx.accounts.dat_state.load()?.admin,
+            &ctx.accounts.validator_set,
+            &ctx.accounts.instructions_sysvar,
+            ctx.accounts.validator_bond.as_mut(),
+            &entry,
+        )?;
 
-        // Calculate available and split to root
-        let available = allocated_lamports.unwrap_or(
-            ctx.accounts.dat_authority.lamports().saturating_sub(RENT_EXEMPT_MINIMUM + SAFETY_BUFFER)
+        // Record the commitment; slot range is consumed now so it cannot be re-posted
+        let attestation = &mut ctx.accounts.fee_attestation;
+        attestation.set_inner(FeeAttestation {
+            mint: entry.mint,
+            poster: ctx.accounts.poster.key(),
+            merkle_root,
+            fee_amount,
+            tx_count,
+            start_slot: ctx.accounts.validator_state.last_validated_slot,
+            end_slot,
+            posted_slot: clock.slot,
+            bump: ctx.bumps.fee_attestation,
+            bond: bond_key,
+        });
+        commit_fee_attestation(attestation, &mut ctx.accounts.validator_state, attestations, &clock);
+
+        Ok(())
+    }
+
+    /// Commit fee attestations for several tokens in one transaction
+    /// Same checks and authorization as register_validated_fees, applied per entry
+    /// (admin posts need the attestor quorum over every entry's message).
+    ///
+    /// remaining_accounts: 3 per entry, in entry order:
+    /// [validator_state (mut), token_stats, fee_attestation PDA (mut, uninitialized)]
+    /// Atomic: any failing entry reverts the whole batch
+    pub fn register_validated_fees_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RegisterValidatedFeesBatch<'info>>,
+        entries: Vec<ValidatedFeeEntry>,
+    ) -> Result<()> {
+
+        require!(
+            !entries.is_empty() && entries.len() <= MAX_FEE_BATCH_SIZE,
+            ErrorCode::InvalidParameter
+        );
+        require!(
+            ctx.remaining_accounts.len() == entries.len() * 3,
+            ErrorCode::InvalidParameter
         );
-        require!(available >= MIN_FEES_FOR_SPLIT, ErrorCode::InsufficientFees);
 
-        // CRITICAL-03 FIX: Root treasury is REQUIRED for secondary tokens
-        // Without this check, callers could pass root_treasury=None and skip the 44.8% fee split
-        require!(ctx.accounts.root_treasury.is_some(), ErrorCode::InvalidRootTreasury);
+        let clock = Clock::get()?;
+        let rent = Rent::get()?;
+        let poster = ctx.accounts.poster.key();
+        let space = 8 + FeeAttestation::LEN;
+
+        for (entry, accs) in entries.iter().zip(ctx.remaining_accounts.chunks(3)) {
+            let (validator_info, stats_info, attestation_info) = (&accs[0], &accs[1], &accs[2]);
+
+            require!(validator_info.is_writable, ErrorCode::InvalidParameter);
+            // Account::try_from / AccountLoader::try_from check owner == program and the discriminator
+            let mut validator: Account<'info, ValidatorState> = Account::try_from(validator_info)?;
+            let stats_loader: AccountLoader<'info, TokenStats> = AccountLoader::try_from(stats_info)?;
+            let token_stats = stats_loader.load()?;
+            require!(validator.mint == entry.mint, ErrorCode::MintMismatch);
+            require!(token_stats.mint == entry.mint, ErrorCode::MintMismatch);
+
+            check_fee_attestation(&validator, &token_stats, entry)?;
+            let (bond_key, attestations) = authorize_fee_attestation(
+                poster,
+                ctx.accounts.dat_state.load()?.admin,
+                &ctx.accounts.validator_set,
+                &ctx.accounts.instructions_sysvar,
+                ctx.accounts.validator_bond.as_mut(),
+                entry,
+            )?;
 
-        // Execute split - SECURITY: Validate root_treasury PDA before transfer
-        if let Some(treasury) = &ctx.accounts.root_treasury {
-            // CRITICAL-01 FIX: Validate root_treasury is the correct PDA
-            let root_mint = state.root_token_mint.ok_or(ErrorCode::InvalidRootToken)?;
-            let (expected_treasury, _bump) = Pubkey::find_program_address(
-                &[ROOT_TREASURY_SEED, root_mint.as_ref()],
-                ctx.program_id
+            // Create the attestation PDA (same seeds as the single-entry init)
+            let end_slot_bytes = entry.end_slot.to_le_bytes();
+            let (expected, bump) = Pubkey::find_program_address(
+                &[FEE_ATTESTATION_SEED, entry.mint.as_ref(), &end_slot_bytes],
+                ctx.program_id,
             );
-            require!(expected_treasury == *treasury.key, ErrorCode::InvalidRootTreasury);
+            require!(attestation_info.key() == expected, ErrorCode::InvalidParameter);
 
-            let sol_for_root = split_fees_to_root(
-                &ctx.accounts.dat_authority,
-                treasury,
-                &ctx.accounts.system_program,
-                available,
-                fee_split_bps,
-                seeds,
+            let bump_slice = &[bump];
+            let attestation_seeds: &[&[u8]] = &[FEE_ATTESTATION_SEED, entry.mint.as_ref(), &end_slot_bytes, bump_slice];
+            invoke_signed(
+                &anchor_lang::solana_program::system_instruction::create_account(
+                    &poster,
+                    &expected,
+                    rent.minimum_balance(space),
+                    space as u64,
+                    ctx.program_id,
+                ),
+                &[
+                    ctx.accounts.poster.to_account_info(),
+                    attestation_info.clone(),
+                    ctx.accounts.system_program.to_account_info(),
+                ],
+                &[attestation_seeds],
             )?;
-            if sol_for_root > 0 {
-                state.last_sol_sent_to_root = sol_for_root;
-            }
-        }
 
-        // Calculate remaining buy amount after split
-        let buy_amount = match allocated_lamports {
-            Some(a) => ((a * fee_split_bps as u64) / 10000).saturating_sub(ATA_RENT_RESERVE),
-            None => ctx.accounts.dat_authority.lamports().saturating_sub(RENT_EXEMPT_MINIMUM + SAFETY_BUFFER + ATA_RENT_RESERVE),
-        };
-        require!(buy_amount >= MINIMUM_BUY_AMOUNT, ErrorCode::InsufficientFees);
+            let attestation = FeeAttestation {
+                mint: entry.mint,
+                poster,
+                merkle_root: entry.merkle_root,
+                fee_amount: entry.fee_amount,
+                tx_count: entry.tx_count,
+                start_slot: validator.last_validated_slot,
+                end_slot: entry.end_slot,
+                posted_slot: clock.slot,
+                bump,
+                bond: bond_key,
+            };
+            commit_fee_attestation(&attestation, &mut validator, attestations, &clock);
+
+            let mut data = attestation_info.try_borrow_mut_data()?;
+            attestation.try_serialize(&mut &mut data[..])?;
+            drop(data);
+            validator.exit(&crate::ID)?;
+        }
 
-        // Execute buy CPI (delegated to reduce stack)
-        execute_buy_secondary_cpi(&mut ctx, buy_amount, bump)
+        Ok(())
     }
 
-    /// Execute buy on PumpSwap AMM pool (for migrated tokens)
-    /// This instruction handles tokens that have graduated from bonding curve to AMM
-    /// Requires WSOL in dat_wsol_account for the buy operation
-    ///
-    /// MEDIUM-01 FIX: Added slippage validation to ensure received tokens meet minimum threshold
-    pub fn execute_buy_amm(
-        ctx: Context<ExecuteBuyAMM>,
-        desired_tokens: u64,     // Amount of tokens to buy
-        max_sol_cost: u64,       // Maximum SOL to spend (in lamports, will use WSOL)
+    /// Configure the M-of-N fee attestor set (admin only)
+    /// 1 <= threshold <= attestors.len() <= MAX_ATTESTORS, no duplicates or default keys
+    pub fn configure_validator_set(
+        ctx: Context<ConfigureValidatorSet>,
+        attestors: Vec<Pubkey>,
+        threshold: u8,
     ) -> Result<()> {
-        // Check state conditions first (read-only)
-        require!(ctx.accounts.dat_state.is_active && !ctx.accounts.dat_state.emergency_pause, ErrorCode::DATNotActive);
-
-        // MEDIUM-01 FIX: Validate max_sol_cost against configured limits
-        let max_fees = ctx.accounts.dat_state.max_fees_per_cycle;
-        let slippage_bps = ctx.accounts.dat_state.slippage_bps;
-        require!(max_sol_cost <= max_fees, ErrorCode::InvalidParameter);
-
-        // Get bump before CPI
-        let bump = ctx.accounts.dat_state.dat_authority_bump;
+        require!(
+            !attestors.is_empty() && attestors.len() <= MAX_ATTESTORS,
+            ErrorCode::InvalidValidatorSet
+        );
+        require!(
+            threshold >= 1 && threshold as usize <= attestors.len(),
+            ErrorCode::InvalidValidatorSet
+        );
+        for (i, key) in attestors.iter().enumerate() {
+            require!(*key != Pubkey::default(), ErrorCode::InvalidValidatorSet);
+            require!(!attestors[..i].contains(key), ErrorCode::InvalidValidatorSet);
+        }
 
-        msg!("Executing PumpSwap AMM buy: {} tokens for max {} lamports",
-            desired_tokens, max_sol_cost);
+        let clock = Clock::get()?;
+        let set = &mut ctx.accounts.validator_set;
+        set.attestors = [Pubkey::default(); MAX_ATTESTORS];
+        set.attestors[..attestors.len()].copy_from_slice(&attestors);
+        set.attestor_count = attestors.len() as u8;
+        set.threshold = threshold;
+        set.updated_at = clock.unix_timestamp;
+        set.bump = ctx.bumps.validator_set;
+
+        emit!(ValidatorSetUpdated {
+            attestors,
+            threshold,
+            timestamp: clock.unix_timestamp,
+        });
 
-        // Record token balance before buy
-        let tokens_before = ctx.accounts.dat_token_account.amount;
+        Ok(())
+    }
 
-        // Execute the PumpSwap AMM CPI (borrows ctx immutably)
-        execute_pumpswap_amm_cpi_inner(&ctx.accounts, desired_tokens, max_sol_cost, bump)?;
+    /// Set the PumpFun protocol fee recipient rotation (admin only)
+    /// Restarts the rotation at the first entry
+    pub fn configure_fee_recipients(
+        ctx: Context<ConfigureFeeRecipients>,
+        recipients: Vec<Pubkey>,
+    ) -> Result<()> {
+        require!(
+            !recipients.is_empty() && recipients.len() <= MAX_FEE_RECIPIENTS,
+            ErrorCode::InvalidFeeRecipients
+        );
+        for (i, key) in recipients.iter().enumerate() {
+            require!(*key != Pubkey::default(), ErrorCode::InvalidFeeRecipients);
+            require!(!recipients[..i].contains(key), ErrorCode::InvalidFeeRecipients);
+        }
 
-        // NOTE: reload() required after CPI to get updated token balance - Anchor doesn't auto-reload for invoke_signed
-        ctx.accounts.dat_token_account.reload()?;
-        let tokens_after = ctx.accounts.dat_token_account.amount;
-        let tokens_received = tokens_after.saturating_sub(tokens_before);
-
-        msg!("AMM buy complete: received {} tokens", tokens_received);
-
-        // MEDIUM-01 FIX: Validate slippage - ensure we received minimum expected tokens
-        // Calculate minimum acceptable: desired_tokens * (1 - slippage_bps/10000)
-        let min_tokens = (desired_tokens as u128)
-            .saturating_mul(10000 - slippage_bps as u128)
-            .saturating_div(10000) as u64;
-        require!(tokens_received >= min_tokens, ErrorCode::SlippageExceeded);
-
-        // Update state for burn tracking (mutable borrow after CPI)
-        let state = &mut ctx.accounts.dat_state;
-        state.pending_burn_amount = tokens_received;
-        state.last_cycle_sol = max_sol_cost;
-
-        emit!(BuyExecuted {
-            tokens_bought: tokens_received,
-            sol_spent: max_sol_cost,
-            timestamp: Clock::get()?.unix_timestamp,
+        let clock = Clock::get()?;
+        let list = &mut ctx.accounts.fee_recipients;
+        list.recipients = [Pubkey::default(); MAX_FEE_RECIPIENTS];
+        list.recipients[..recipients.len()].copy_from_slice(&recipients);
+        list.count = recipients.len() as u8;
+        list.updated_at = clock.unix_timestamp;
+        list.bump = ctx.bumps.fee_recipients;
+        ctx.accounts.dat_state.load_mut()?.current_fee_recipient_index = 0;
+
+        emit!(FeeRecipientsUpdated {
+            recipients,
+            timestamp: clock.unix_timestamp,
         });
 
         Ok(())
     }
 
-    // Finalize allocated cycle - Reset pending_fees and increment cycles_participated
-    // Called by ecosystem orchestrator after execute_buy with allocated_lamports
-    // This is a separate lightweight instruction to avoid stack overflow
-    // actually_participated: bool - If true, reset pending_fees. If false (deferred), preserve them.
-    pub fn finalize_allocated_cycle(ctx: Context<FinalizeAllocatedCycle>, actually_participated: bool) -> Result<()> {
-        let stats = &mut ctx.accounts.token_stats;
+    /// Dispute a pending fee attestation with a Merkle fraud proof (permissionless)
+    /// Proves one of: leaf slot outside the attested range, leaf index beyond tx_count,
+    /// leaf fee above MAX_FEE_PER_TX, or the same tx signature committed twice
+    /// On success the attestation is closed (rent to challenger) and never credited
+    pub fn challenge_attestation(
+        ctx: Context<ChallengeAttestation>,
+        leaf: FeeLeafProof,
+        duplicate: Option<FeeLeafProof>,
+    ) -> Result<()> {
+        let attestation = &ctx.accounts.fee_attestation;
+        let clock = Clock::get()?;
 
-        if actually_participated {
-            // Token participated in this cycle - reset pending_fees
-            stats.pending_fees_lamports = 0;
-            stats.cycles_participated = stats.cycles_participated.saturating_add(1);
-            msg!("Finalized allocated cycle: pending_fees reset, cycles: {}", stats.cycles_participated);
-        } else {
-            // Token was deferred - preserve pending_fees for next cycle
-            msg!("Deferred finalization: pending_fees preserved ({} lamports) for next cycle",
-                stats.pending_fees_lamports);
-        }
+        require!(attestation.in_challenge_window(clock.slot), ErrorCode::ChallengeWindowClosed);
+        // Bonded posters must not be able to self-challenge their way out of a slash
+        require!(!attestation.is_bonded(), ErrorCode::BondedAttestation);
+
+        let fraud_reason = attestation.fraud_reason(&leaf, duplicate.as_ref())?;
+
+        emit!(FeeAttestationChallenged {
+            mint: attestation.mint,
+            end_slot: attestation.end_slot,
+            challenger: ctx.accounts.challenger.key(),
+            fraud_reason,
+            fee_amount_rejected: attestation.fee_amount,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("Fee attestation for {} (end slot {}) rejected: reason {}",
+            attestation.mint, attestation.end_slot, fraud_reason);
 
         Ok(())
     }
 
-
-    pub fn burn_and_update(ctx: Context<BurnAndUpdate>) -> Result<()> {
-        let state = &mut ctx.accounts.dat_state;
+    /// Credit an unchallenged attestation after its window (permissionless)
+    pub fn finalize_attestation(ctx: Context<FinalizeAttestation>) -> Result<()> {
+        let attestation = &ctx.accounts.fee_attestation;
+        let validator = &mut ctx.accounts.validator_state;
+        let mut token_stats = ctx.accounts.token_stats.load_mut()?;
         let clock = Clock::get()?;
 
-        require!(state.pending_burn_amount > 0, ErrorCode::NoPendingBurn);
+        require!(!attestation.in_challenge_window(clock.slot), ErrorCode::ChallengeWindowOpen);
 
-        let tokens_to_burn = state.pending_burn_amount;
-        let seeds = &[DAT_AUTHORITY_SEED, &[state.dat_authority_bump]];
+        let new_pending = token_stats.pending_fees_lamports.saturating_add(attestation.fee_amount);
+        require!(new_pending <= MAX_PENDING_FEES, ErrorCode::PendingFeesOverflow);
 
-        token_interface::burn(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                token_interface::Burn {
-                    mint: ctx.accounts.asdf_mint.to_account_info(),
-                    from: ctx.accounts.dat_asdf_account.to_account_info(),
-                    authority: ctx.accounts.dat_authority.to_account_info(),
-                },
-                &[seeds]
-            ),
-            tokens_to_burn
-        )?;
-
-        // Update per-token statistics
-        let token_stats = &mut ctx.accounts.token_stats;
-        token_stats.total_burned = token_stats.total_burned.saturating_add(tokens_to_burn);
-        token_stats.total_sol_used = token_stats.total_sol_used.saturating_add(state.last_cycle_sol);
-        token_stats.total_buybacks = token_stats.total_buybacks.saturating_add(1);
-        token_stats.last_cycle_timestamp = clock.unix_timestamp;
-        token_stats.last_cycle_sol = state.last_cycle_sol;
-        token_stats.last_cycle_burned = tokens_to_burn;
-
-        // Update total_sol_sent_to_root if this was a secondary token cycle
-        if state.last_sol_sent_to_root > 0 {
-            token_stats.total_sol_sent_to_root =
-                token_stats.total_sol_sent_to_root.saturating_add(state.last_sol_sent_to_root);
-            msg!("Token stats updated: {} lamports sent to root (total: {})",
-                state.last_sol_sent_to_root,
-                token_stats.total_sol_sent_to_root);
+        // Release the backing bond's lock on this attestation
+        if attestation.is_bonded() {
+            let bond = ctx.accounts.validator_bond.as_mut().ok_or(ErrorCode::InvalidBondAccount)?;
+            bond.open_attestations = bond.open_attestations.saturating_sub(1);
         }
 
-        // Update global state and reset tracking variables
-        state.last_cycle_burned = tokens_to_burn;
-        state.consecutive_failures = 0;
-        state.pending_burn_amount = 0;
-        state.last_sol_sent_to_root = 0;  // Reset for next cycle
+        // Update validator state
+        validator.total_validated_lamports = validator
+            .total_validated_lamports
+            .saturating_add(attestation.fee_amount);
+        validator.total_validated_count = validator
+            .total_validated_count
+            .saturating_add(1);
 
-        let (whole, frac) = format_tokens(tokens_to_burn);
-        msg!("Epoch #{} complete: {}.{:06} tokens burned ({} units)",
-            token_stats.total_buybacks, whole, frac, tokens_to_burn);
+        // Update token stats (trustless fee attribution - survived the challenge window)
+        token_stats.pending_fees_lamports = new_pending;
+        token_stats.last_fee_update_timestamp = clock.unix_timestamp;
 
-        emit!(CycleCompleted {
-            cycle_number: token_stats.total_buybacks as u32,
-            tokens_burned: tokens_to_burn,
-            sol_used: state.last_cycle_sol,
-            total_burned: token_stats.total_burned,
-            total_sol_collected: token_stats.total_sol_collected,
+        emit!(ValidatedFeesRegistered {
+            mint: attestation.mint,
+            fee_amount: attestation.fee_amount,
+            end_slot: attestation.end_slot,
+            tx_count: attestation.tx_count,
+            total_pending: token_stats.pending_fees_lamports,
             timestamp: clock.unix_timestamp,
         });
 
-        Ok(())
-    }
-
-    pub fn record_failure(ctx: Context<RecordFailure>, error_code: u32) -> Result<()> {
-        let state = &mut ctx.accounts.dat_state;
-        state.failed_cycles = state.failed_cycles.saturating_add(1);
-        state.consecutive_failures = state.consecutive_failures.saturating_add(1);
-        if state.consecutive_failures >= 5 {
-            state.emergency_pause = true;
-        }
-        emit!(CycleFailed {
-            failed_count: state.failed_cycles,
-            consecutive_failures: state.consecutive_failures,
-            error_code,
-            timestamp: Clock::get()?.unix_timestamp,
-        });
-        Ok(())
-    }
+        #[cfg(feature = "verbose")]
+        msg!("Finalized {} lamports for {} (slot {}, {} TXs)",
+            attestation.fee_amount, attestation.mint, attestation.end_slot, attestation.tx_count);
 
-    pub fn emergency_pause(ctx: Context<AdminControl>) -> Result<()> {
-        let state = &mut ctx.accounts.dat_state;
-        state.emergency_pause = true;
-        state.is_active = false;
-        emit!(EmergencyAction {
-            action: "PAUSE".to_string(),
-            admin: ctx.accounts.admin.key(),
-            timestamp: Clock::get()?.unix_timestamp,
-        });
         Ok(())
     }
 
-    pub fn resume(ctx: Context<AdminControl>) -> Result<()> {
-        let state = &mut ctx.accounts.dat_state;
-        state.emergency_pause = false;
-        state.is_active = true;
-        state.consecutive_failures = 0;
-        emit!(StatusChanged {
-            is_active: true,
-            emergency_pause: false,
-            timestamp: Clock::get()?.unix_timestamp,
-        });
-        Ok(())
-    }
+    /// Deposit SOL into the caller's validator bond (creates the bond on first deposit)
+    /// A bond of at least MIN_VALIDATOR_BOND lets the operator post fee attestations
+    pub fn bond_validator(ctx: Context<BondValidator>, amount: u64) -> Result<()> {
+        use anchor_lang::solana_program::program::invoke;
 
-    pub fn update_parameters(
-        ctx: Context<AdminControl>,
-        new_min_fees: Option<u64>,
-        new_max_fees: Option<u64>,
-        new_slippage_bps: Option<u16>,
-        new_min_interval: Option<i64>,
-    ) -> Result<()> {
-        let state = &mut ctx.accounts.dat_state;
+        require!(amount > 0, ErrorCode::InvalidParameter);
 
-        // Validate slippage: min 0.1% (10 bps), max 5% (500 bps)
-        // Disallow 0 to prevent division issues in buy calculations
-        if let Some(v) = new_slippage_bps {
-            require!(v >= 10 && v <= 500, ErrorCode::SlippageConfigTooHigh);
-            state.slippage_bps = v;
-        }
+        let clock = Clock::get()?;
+        let operator = ctx.accounts.operator.key();
 
-        // Validate min_interval: must be positive
-        if let Some(v) = new_min_interval {
-            require!(v > 0, ErrorCode::InvalidParameter);
-            state.min_cycle_interval = v;
-        }
+        invoke(
+            &anchor_lang::solana_program::system_instruction::transfer(
+                &operator,
+                &ctx.accounts.validator_bond.key(),
+                amount,
+            ),
+            &[
+                ctx.accounts.operator.to_account_info(),
+                ctx.accounts.validator_bond.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+        )?;
 
-        // Apply fee thresholds with bounds validation
-        // min_fees: must be at least 0.001 SOL (1_000_000 lamports) and at most 1 SOL
-        if let Some(v) = new_min_fees {
-            require!(v >= 1_000_000 && v <= 1_000_000_000, ErrorCode::InvalidParameter);
-            state.min_fees_threshold = v;
-        }
-        // max_fees: must be at least 0.01 SOL (10_000_000 lamports)
-        if let Some(v) = new_max_fees {
-            require!(v >= 10_000_000, ErrorCode::InvalidParameter);
-            state.max_fees_per_cycle = v;
+        let bond = &mut ctx.accounts.validator_bond;
+        if bond.operator == Pubkey::default() {
+            bond.operator = operator;
+            bond.bonded_at = clock.unix_timestamp;
+            bond.bump = ctx.bumps.validator_bond;
         }
+        bond.bonded_lamports = bond.bonded_lamports.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
 
-        // Validate min <= max after both are set
-        require!(
-            state.min_fees_threshold <= state.max_fees_per_cycle,
-            ErrorCode::InvalidParameter
-        );
+        emit!(ValidatorBondChanged {
+            operator,
+            deposited: amount,
+            withdrawn: 0,
+            bonded_lamports: bond.bonded_lamports,
+            timestamp: clock.unix_timestamp,
+        });
 
         Ok(())
     }
 
-    /// DEPRECATED: Use propose_admin_transfer + accept_admin_transfer instead
-    /// Kept for backwards compatibility - now just proposes the transfer
-    pub fn transfer_admin(ctx: Context<TransferAdmin>) -> Result<()> {
-        let state = &mut ctx.accounts.dat_state;
-        state.pending_admin = Some(ctx.accounts.new_admin.key());
-        emit!(AdminTransferProposed {
-            current_admin: ctx.accounts.admin.key(),
-            proposed_admin: ctx.accounts.new_admin.key(),
+    /// Withdraw stake from the caller's validator bond
+    /// Only when no attestation backed by the bond is unresolved; the remainder
+    /// must be zero (full exit) or still at least MIN_VALIDATOR_BOND
+    pub fn withdraw_bond(ctx: Context<WithdrawBond>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidParameter);
+
+        let bond = &mut ctx.accounts.validator_bond;
+        require!(bond.open_attestations == 0, ErrorCode::BondLocked);
+        require!(bond.can_withdraw(amount), ErrorCode::InsufficientBond);
+
+        bond.bonded_lamports -= amount;
+        bond.sub_lamports(amount)?;
+        ctx.accounts.operator.add_lamports(amount)?;
+
+        emit!(ValidatorBondChanged {
+            operator: bond.operator,
+            deposited: 0,
+            withdrawn: amount,
+            bonded_lamports: bond.bonded_lamports,
             timestamp: Clock::get()?.unix_timestamp,
         });
-        Ok(())
-    }
 
-    /// Propose a new admin (two-step transfer for security)
-    pub fn propose_admin_transfer(ctx: Context<ProposeAdminTransfer>) -> Result<()> {
-        let state = &mut ctx.accounts.dat_state;
-        state.pending_admin = Some(ctx.accounts.new_admin.key());
-        emit!(AdminTransferProposed {
-            current_admin: ctx.accounts.admin.key(),
-            proposed_admin: ctx.accounts.new_admin.key(),
-            timestamp: Clock::get()?.unix_timestamp,
-        });
         Ok(())
     }
 
-    /// Accept admin transfer (must be called by the proposed admin)
-    pub fn accept_admin_transfer(ctx: Context<AcceptAdminTransfer>) -> Result<()> {
-        let state = &mut ctx.accounts.dat_state;
-        let old_admin = state.admin;
-        let new_admin = ctx.accounts.new_admin.key();
+    /// Dispute a bonded fee attestation with a Merkle fraud proof (permissionless)
+    /// Same fraud proofs as challenge_attestation. On success the attestation is closed
+    /// (rent to challenger), never credited, and SLASH_BPS of the bond is burned
+    pub fn slash_validator(
+        ctx: Context<SlashValidator>,
+        leaf: FeeLeafProof,
+        duplicate: Option<FeeLeafProof>,
+    ) -> Result<()> {
+        let attestation = &ctx.accounts.fee_attestation;
+        let clock = Clock::get()?;
 
-        state.admin = new_admin;
-        state.pending_admin = None;
+        require!(attestation.in_challenge_window(clock.slot), ErrorCode::ChallengeWindowClosed);
+        require!(attestation.is_bonded(), ErrorCode::UnbondedAttestation);
+
+        let fraud_reason = attestation.fraud_reason(&leaf, duplicate.as_ref())?;
+
+        let bond = &mut ctx.accounts.validator_bond;
+        let slashed = bond.slash_amount();
+        bond.bonded_lamports -= slashed;
+        bond.open_attestations = bond.open_attestations.saturating_sub(1);
+        bond.slash_count = bond.slash_count.saturating_add(1);
+        bond.total_slashed = bond.total_slashed.saturating_add(slashed);
+        bond.sub_lamports(slashed)?;
+        ctx.accounts.incinerator.add_lamports(slashed)?;
+
+        emit!(FeeAttestationChallenged {
+            mint: attestation.mint,
+            end_slot: attestation.end_slot,
+            challenger: ctx.accounts.challenger.key(),
+            fraud_reason,
+            fee_amount_rejected: attestation.fee_amount,
+            timestamp: clock.unix_timestamp,
+        });
 
-        emit!(AdminTransferred {
-            old_admin,
-            new_admin,
-            timestamp: Clock::get()?.unix_timestamp,
+        emit!(ValidatorSlashed {
+            operator: bond.operator,
+            mint: attestation.mint,
+            end_slot: attestation.end_slot,
+            challenger: ctx.accounts.challenger.key(),
+            fraud_reason,
+            slashed_lamports: slashed,
+            remaining_bond: bond.bonded_lamports,
+            timestamp: clock.unix_timestamp,
         });
+
+        msg!("Validator {} slashed {} lamports (attestation {} / {}, reason {})",
+            bond.operator, slashed, attestation.mint, attestation.end_slot, fraud_reason);
+
         Ok(())
     }
 
-    /// Cancel a pending admin transfer (called by current admin)
-    pub fn cancel_admin_transfer(ctx: Context<CancelAdminTransfer>) -> Result<()> {
-        let state = &mut ctx.accounts.dat_state;
+    /// Sync validator slot to current slot (permissionless)
+    ///
+    /// This instruction allows anyone to reset the last_validated_slot to the current slot
+    /// when the validator state has become stale (> MAX_SLOT_RANGE behind current slot).
+    /// This is useful after periods of inactivity to allow the daemon to resume operation.
+    ///
+    /// Note: This does NOT affect fee attribution - it simply allows new validations to proceed.
+    /// Any fees from the skipped slots are lost (this is acceptable for inactivity periods).
+    pub fn sync_validator_slot(ctx: Context<SyncValidatorSlot>) -> Result<()> {
+        let validator = &mut ctx.accounts.validator_state;
         let clock = Clock::get()?;
-        // Constraint already validates pending_admin.is_some() in context
-        let cancelled_admin = state.pending_admin.ok_or(ErrorCode::NoPendingAdminTransfer)?;
-        state.pending_admin = None;
+        let current_slot = clock.slot;
 
-        emit!(AdminTransferCancelled {
-            admin: ctx.accounts.admin.key(),
-            cancelled_new_admin: cancelled_admin,
+        // Only allow sync if the validator is stale (more than MAX_SLOT_RANGE behind)
+        let slot_delta = current_slot.saturating_sub(validator.last_validated_slot);
+        require!(slot_delta > 1000, ErrorCode::ValidatorNotStale);
+
+        let old_slot = validator.last_validated_slot;
+        validator.last_validated_slot = current_slot;
+
+        emit!(ValidatorSlotSynced {
+            mint: validator.mint,
+            old_slot,
+            new_slot: current_slot,
+            slot_delta,
             timestamp: clock.unix_timestamp,
         });
+
+        #[cfg(feature = "verbose")]
+        msg!("Synced validator slot for {} from {} to {} (delta: {})",
+            validator.mint, old_slot, current_slot, slot_delta);
+
         Ok(())
     }
 
-    /// Propose a fee split change (subject to timelock)
-    pub fn propose_fee_split(ctx: Context<ProposeAdminTransfer>, new_fee_split_bps: u16) -> Result<()> {
-        require!(
-            new_fee_split_bps > 0 && new_fee_split_bps < 10000,
-            ErrorCode::InvalidParameter
-        );
-
-        let state = &mut ctx.accounts.dat_state;
-        let clock = Clock::get()?;
+    // Migrate existing TokenStats accounts to include new fields
+    // Call this once per existing token to initialize the new fields
+    pub fn migrate_token_stats(ctx: Context<MigrateTokenStats>) -> Result<()> {
+        let mint = &ctx.accounts.mint;
 
-        state.pending_fee_split = Some(new_fee_split_bps);
-        state.pending_fee_split_timestamp = clock.unix_timestamp;
+        // Verify PDA
+        let (expected_pda, _) = Pubkey::find_program_address(
+            &[TOKEN_STATS_SEED, mint.key().as_ref()],
+            &crate::ID
+        );
+        require!(ctx.accounts.token_stats.key() == expected_pda, ErrorCode::InvalidParameter);
 
-        msg!("Fee split change proposed: {} bps, can execute after {} seconds",
-             new_fee_split_bps, state.admin_operation_cooldown);
+        // Layout history and per-version defaults: migrations::MIGRATIONS
+        let env = migrations::MigrationEnv {
+            now: Clock::get()?.unix_timestamp,
+            mint_risk_flags: Some(migrations::mint_risk_flags(mint)?),
+        };
+        migrations::migrate(
+            AccountKind::TokenStats,
+            &ctx.accounts.token_stats,
+            &ctx.accounts.admin.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &env,
+        )?;
         Ok(())
     }
 
-    /// Execute a pending fee split change (after cooldown period)
-    pub fn execute_fee_split(ctx: Context<ProposeAdminTransfer>) -> Result<()> {
-        let state = &mut ctx.accounts.dat_state;
-        let clock = Clock::get()?;
+    /// Migrate DATState account to add new fields (one-time migration)
+    /// Handles layered reallocation from any known older layout (see migrations::MIGRATIONS)
+    /// `target_version` must be the version this build migrates to (DAT_STATE_VERSION), so a
+    /// script written against another build fails instead of producing an unexpected layout
+    pub fn migrate_dat_state(ctx: Context<MigrateDatState>, target_version: u8) -> Result<()> {
+        require!(target_version == DAT_STATE_VERSION, ErrorCode::MigrationVersionMismatch);
+
+        // Admin read from raw data: an old layout cannot deserialize as DATState
+        let dat_state = &ctx.accounts.dat_state;
+        let stored_admin = migrations::dat_state_admin(dat_state)?;
+        require!(stored_admin == ctx.accounts.admin.key(), ErrorCode::UnauthorizedAccess);
 
-        require!(state.pending_fee_split.is_some(), ErrorCode::NoPendingFeeSplit);
+        let previous_size = migrations::migrate(
+            AccountKind::DatState,
+            dat_state,
+            &ctx.accounts.admin.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &migrations::MigrationEnv::default(),
+        )?;
 
-        let elapsed = clock.unix_timestamp.saturating_sub(state.pending_fee_split_timestamp);
-        require!(
-            elapsed >= state.admin_operation_cooldown,
-            ErrorCode::CycleTooSoon // Reusing existing error for timelock
-        );
+        emit!(AccountMigrated {
+            account: dat_state.key(),
+            kind: AccountKind::DatState,
+            previous_size: previous_size as u32,
+            version: DAT_STATE_VERSION,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
 
-        let new_fee_split = state.pending_fee_split
-            .ok_or(ErrorCode::NoPendingFeeSplit)?;
-        let old_fee_split = state.fee_split_bps;
+    /// Upgrade any registered account to its current layout through the MIGRATIONS steps
+    /// Admin only except UserStats (permissionless, payer covers the extra rent)
+    pub fn migrate_account(ctx: Context<MigrateAccount>, kind: AccountKind) -> Result<()> {
+        let account = &ctx.accounts.account;
+        if kind != AccountKind::UserStats {
+            let admin = migrations::dat_state_admin(&ctx.accounts.dat_state)?;
+            require!(admin == ctx.accounts.payer.key(), ErrorCode::UnauthorizedAccess);
+        }
+        if kind == AccountKind::DatState {
+            require!(account.key() == ctx.accounts.dat_state.key(), ErrorCode::InvalidParameter);
+        }
 
-        state.fee_split_bps = new_fee_split;
-        state.pending_fee_split = None;
-        state.pending_fee_split_timestamp = 0;
+        // The mint must be the one the stats track (first field, at the same offset in every layout)
+        let mut mint_risk_flags = None;
+        if let (AccountKind::TokenStats, Some(mint)) = (kind, ctx.accounts.mint.as_ref()) {
+            let data = account.try_borrow_data()?;
+            require!(data.len() >= 40 && data[8..40] == mint.key().to_bytes(), ErrorCode::MintMismatch);
+            drop(data);
+            mint_risk_flags = Some(migrations::mint_risk_flags(mint)?);
+        }
 
-        emit!(FeeSplitUpdated {
-            old_bps: old_fee_split,
-            new_bps: new_fee_split,
-            timestamp: clock.unix_timestamp,
+        let env = migrations::MigrationEnv {
+            now: Clock::get()?.unix_timestamp,
+            mint_risk_flags,
+        };
+        let previous_size = migrations::migrate(
+            kind,
+            account,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &env,
+        )?;
+
+        emit!(AccountMigrated {
+            account: account.key(),
+            kind,
+            previous_size: previous_size as u32,
+            version: kind.current_version(),
+            timestamp: env.now,
         });
         Ok(())
     }
 
-    /// Create a PumpFun token using create_v2 (Token2022) without Mayhem Mode
-    /// Standard Token2022 token with 1B supply
-    pub fn create_pumpfun_token_v2(
-        ctx: Context<CreatePumpfunTokenV2>,
-        name: String,
-        symbol: String,
-        uri: String,
-    ) -> Result<()> {
-        let state = &ctx.accounts.dat_state;
+    pub fn collect_fees(ctx: Context<CollectFees>, is_root_token: bool, for_ecosystem: bool) -> Result<()> {
+        let mut token_stats = ctx.accounts.token_stats.load_mut()?;
+        let mut state = ctx.accounts.dat_state.load_mut()?;
+        let clock = Clock::get()?;
+        let collected_before = token_stats.total_sol_collected;
 
-        msg!("Creating PumpFun token via create_v2 (Token2022, no Mayhem)");
-        msg!("Name: {}, Symbol: {}, Creator: {}", name, symbol, ctx.accounts.dat_authority.key());
+        require!(state.is_active != 0 && state.emergency_pause == 0, ErrorCode::DATNotActive);
+        require!(token_stats.is_paused == 0, ErrorCode::TokenPaused);
 
-        let mut data = Vec::new();
+        // Enforce minimum cycle interval (disabled in testing mode)
+        if !TESTING_MODE {
+            require!(
+                clock.unix_timestamp - state.last_cycle_timestamp >= state.min_cycle_interval,
+                ErrorCode::CycleTooSoon
+            );
+        }
 
-        // Discriminator for create_v2
-        data.extend_from_slice(&PUMPFUN_CREATE_V2_DISCRIMINATOR);
+        require_compute_remaining(CU_PHASE_COLLECT)?;
 
-        // Name (String)
-        data.extend_from_slice(&(name.len() as u32).to_le_bytes());
-        data.extend_from_slice(name.as_bytes());
+        state.last_cycle_timestamp = clock.unix_timestamp;
+        state.acquire_cycle_lock(ctx.accounts.token_mint.key())?;
+        ctx.accounts.cycle_state.begin_collect(clock.unix_timestamp, clock.slot)?;
+        ctx.accounts.cycle_state.refresh_reserves(&Rent::get()?);
 
-        // Symbol (String)
-        data.extend_from_slice(&(symbol.len() as u32).to_le_bytes());
-        data.extend_from_slice(symbol.as_bytes());
+        // NOTE: AM/PM execution limits removed - the orchestrator handles 1/day per token scheduling.
+        // With randomized_timing on, the time of day is fixed on-chain by the mint's CycleWindow
+        require_cycle_window(&state, ctx.accounts.cycle_window.as_deref(), clock.unix_timestamp)?;
 
-        // URI (String)
-        data.extend_from_slice(&(uri.len() as u32).to_le_bytes());
-        data.extend_from_slice(uri.as_bytes());
+        // Enforce minimum fees threshold (disabled in testing mode)
+        // NOTE: Skip threshold check when for_ecosystem=true (N+1 pattern)
+        // In N+1, the first token drains the vault and subsequent tokens use datAuthority balance
+        // The threshold check only applies to standalone/first-token collections
+        if !TESTING_MODE && !for_ecosystem {
+            let vault_balance = ctx.accounts.creator_vault.lamports();
+            require!(vault_balance >= state.min_fees_threshold, ErrorCode::InsufficientFees);
+        }
 
-        // Creator (Pubkey)
-        data.extend_from_slice(&ctx.accounts.dat_authority.key().to_bytes());
+        let seeds = &[DAT_AUTHORITY_SEED, &[state.dat_authority_bump]];
+
+        // Track vault balance before collection
+        let vault_balance_before = ctx.accounts.creator_vault.lamports();
+
+        // STEP 1: Collect from creator vault (all tokens)
+        collect_creator_fee_cpi(
+            &ctx.accounts.dat_authority,
+            &ctx.accounts.creator_vault,
+            &ctx.accounts.system_program,
+            &ctx.accounts.pump_event_authority,
+            &ctx.accounts.pump_swap_program,
+            seeds,
+        )?;
+
+        // Track SOL collected from vault
+        let vault_balance_after = ctx.accounts.creator_vault.lamports();
+        let sol_from_vault = vault_balance_before.saturating_sub(vault_balance_after);
+        token_stats.total_sol_collected = token_stats.total_sol_collected.saturating_add(sol_from_vault);
+
+        // Listing partner revenue share, on this token's attributed fees (the creator vault
+        // is shared by every token, and root treasury inflows are not its own fees)
+        let partner_cut = token_stats.partner_cut_due();
+        if partner_cut > 0 {
+            let partner = ctx.accounts.partner.as_ref().ok_or(ErrorCode::PartnerAccountsMissing)?;
+            let partner_stats = ctx.accounts.partner_stats.as_mut().ok_or(ErrorCode::PartnerAccountsMissing)?;
+
+            // A payout that would leave the partner below rent exemption would fail the cycle
+            if partner.lamports().saturating_add(partner_cut) >= ctx.accounts.cycle_state.rent_reserve() {
+                invoke_signed(
+                    &anchor_lang::solana_program::system_instruction::transfer(
+                        ctx.accounts.dat_authority.key,
+                        partner.key,
+                        partner_cut,
+                    ),
+                    &[
+                        ctx.accounts.dat_authority.to_account_info(),
+                        partner.to_account_info(),
+                        ctx.accounts.system_program.to_account_info(),
+                    ],
+                    &[&seeds[..]],
+                )?;
+                token_stats.record_partner_payout(partner_cut);
+                partner_stats.total_paid = partner_stats.total_paid.saturating_add(partner_cut);
+                partner_stats.payouts_count = partner_stats.payouts_count.saturating_add(1);
+                partner_stats.last_paid_at = clock.unix_timestamp;
+
+                emit!(PartnerPaid {
+                    mint: token_stats.mint,
+                    partner: partner.key(),
+                    amount: partner_cut,
+                    total_partner_paid: token_stats.total_partner_paid,
+                    timestamp: clock.unix_timestamp,
+                });
+            }
+        }
+
+        // Treasury reserve: keep reserve_bps of the vault fees back from the buyback
+        // (vault fees only, not root treasury inflows)
+        let reserve_cut = state.reserve_cut(sol_from_vault);
+        if reserve_cut > 0 {
+            let reserve_vault = ctx.accounts.reserve_vault.as_mut().ok_or(ErrorCode::ReserveVaultMissing)?;
+            invoke_signed(
+                &anchor_lang::solana_program::system_instruction::transfer(
+                    ctx.accounts.dat_authority.key,
+                    &reserve_vault.key(),
+                    reserve_cut,
+                ),
+                &[
+                    ctx.accounts.dat_authority.to_account_info(),
+                    reserve_vault.to_account_info(),
+                    ctx.accounts.system_program.to_account_info(),
+                ],
+                &[&seeds[..]],
+            )?;
+            reserve_vault.total_reserved = reserve_vault.total_reserved.saturating_add(reserve_cut);
+
+            emit!(ReserveFunded {
+                mint: token_stats.mint,
+                amount: reserve_cut,
+                total_reserved: reserve_vault.total_reserved,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+
+        // Insurance fund deposit (vault fees only, like the reserve)
+        let insurance_cut = state.insurance_cut(sol_from_vault);
+        if insurance_cut > 0 {
+            let insurance_fund = ctx.accounts.insurance_fund.as_mut().ok_or(ErrorCode::InsuranceFundMissing)?;
+            invoke_signed(
+                &anchor_lang::solana_program::system_instruction::transfer(
+                    ctx.accounts.dat_authority.key,
+                    &insurance_fund.key(),
+                    insurance_cut,
+                ),
+                &[
+                    ctx.accounts.dat_authority.to_account_info(),
+                    insurance_fund.to_account_info(),
+                    ctx.accounts.system_program.to_account_info(),
+                ],
+                &[&seeds[..]],
+            )?;
+            insurance_fund.record_deposit(insurance_cut);
+
+            emit!(InsuranceDeposited {
+                mint: token_stats.mint,
+                amount: insurance_cut,
+                total_deposited: insurance_fund.total_deposited,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+
+        // STEP 2: If root token, also collect from root treasury
+        if is_root_token {
+            if let Some(root_treasury) = &ctx.accounts.root_treasury {
+                let treasury_amt = root_treasury.lamports();
+                if treasury_amt > 0 {
+                    // Root treasury is a PDA: seeds = ["root_treasury", root_mint, bump]
+                    // Every root (primary or listed in the RootSet) drains only its own treasury
+                    require!(token_stats.is_root_token != 0, ErrorCode::InvalidRootToken);
+                    let root_mint = token_stats.mint;
+                    let (expected_treasury, bump) = Pubkey::find_program_address(
+                        &[ROOT_TREASURY_SEED, root_mint.as_ref()],
+                        ctx.program_id
+                    );
+                    require!(expected_treasury == *root_treasury.key, ErrorCode::InvalidRootTreasury);
+
+                    // Create seeds with bump for signing
+                    let bump_slice = &[bump];
+                    let treasury_seeds: &[&[u8]] = &[ROOT_TREASURY_SEED, root_mint.as_ref(), bump_slice];
+
+                    invoke_signed(
+                        &anchor_lang::solana_program::system_instruction::transfer(
+                            root_treasury.key,
+                            ctx.accounts.dat_authority.key,
+                            treasury_amt
+                        ),
+                        &[
+                            root_treasury.to_account_info(),
+                            ctx.accounts.dat_authority.to_account_info(),
+                            ctx.accounts.system_program.to_account_info()
+                        ],
+                        &[treasury_seeds]
+                    )?;
+
+                    // Track SOL received from other tokens
+                    token_stats.total_sol_received_from_others =
+                        token_stats.total_sol_received_from_others.saturating_add(treasury_amt);
+                    token_stats.total_sol_collected =
+                        token_stats.total_sol_collected.saturating_add(treasury_amt);
+
+                    emit!(RootTreasuryCollected {
+                        root_mint,
+                        amount: treasury_amt,
+                        timestamp: clock.unix_timestamp
+                    });
+                    msg!("Root treasury collected: {} lamports", treasury_amt);
+                }
+            }
+        }
+
+        if let Some(daily) = ctx.accounts.daily_stats.as_mut() {
+            let collected = token_stats.total_sol_collected.saturating_sub(collected_before);
+            daily.record(clock.unix_timestamp, collected, 0)?;
+        }
+
+        // Reset pending fees unless in ecosystem mode (where orchestrator manages distribution)
+        if !for_ecosystem {
+            token_stats.reset_pending_fees();
+            msg!("Pending fees reset (standalone mode)");
+        } else {
+            msg!("Ecosystem mode: pending fees NOT reset (orchestrator will distribute)");
+        }
+
+        msg!("Fees collected (for_ecosystem: {})", for_ecosystem);
+        Ok(())
+    }
+
+    /// Collect creator fees for several bonding-curve tokens in one instruction
+    /// Every token shares dat_authority's creator vault, so the PumpFun collect CPI runs once
+    /// and its outflow is attributed to the tokens pro rata to their pending_fees_lamports
+    /// (see attribute_collected), taking the reserve / insurance cuts per token as collect_fees
+    /// does. Ecosystem-mode collection: pending fees are kept for the orchestrator, and no cycle
+    /// is started (each token's cycle still begins with collect_fees). Refused while a cycle
+    /// holds the lock, while randomized_timing is on, and for tokens with a listing partner.
+    ///
+    /// remaining_accounts: [token_stats (mut)] per token, each mint at most once
+    /// Atomic: any failing token reverts the whole batch
+    pub fn collect_fees_batch<'info>(ctx: Context<'_, '_, 'info, 'info, CollectFeesBatch<'info>>) -> Result<()> {
+        let state = ctx.accounts.dat_state.load()?;
+        require!(state.is_active != 0 && state.emergency_pause == 0, ErrorCode::DATNotActive);
+        require!(state.cycle_lock().is_none(), ErrorCode::CycleLocked);
+        require!(state.randomized_timing == 0, ErrorCode::BatchCollectUnsupported);
+
+        let tokens = ctx.remaining_accounts.len();
+        require!(tokens > 0 && tokens <= MAX_COLLECT_BATCH_SIZE, ErrorCode::InvalidParameter);
+
+        let mut all_stats: Vec<AccountLoader<'info, TokenStats>> = Vec::with_capacity(tokens);
+        let mut pending: Vec<u64> = Vec::with_capacity(tokens);
+        for stats_info in ctx.remaining_accounts.iter() {
+            require!(stats_info.is_writable, ErrorCode::InvalidParameter);
+            require!(
+                all_stats.iter().all(|s| s.key() != stats_info.key()),
+                ErrorCode::InvalidParameter
+            );
+            // AccountLoader::try_from checks owner == program and the TokenStats discriminator
+            let loader: AccountLoader<'info, TokenStats> = AccountLoader::try_from(stats_info)?;
+            {
+                let stats = loader.load()?;
+                require!(stats.is_paused == 0, ErrorCode::TokenPaused);
+                require!(stats.partner == Pubkey::default(), ErrorCode::BatchCollectUnsupported);
+                pending.push(stats.pending_fees_lamports);
+            }
+            all_stats.push(loader);
+        }
+
+        require_compute_remaining(CU_PHASE_COLLECT)?;
+        let clock = Clock::get()?;
+        let seeds: &[&[u8]] = &[DAT_AUTHORITY_SEED, &[state.dat_authority_bump]];
+        let vault_balance_before = ctx.accounts.creator_vault.lamports();
+        collect_creator_fee_cpi(
+            &ctx.accounts.dat_authority,
+            &ctx.accounts.creator_vault,
+            &ctx.accounts.system_program,
+            &ctx.accounts.pump_event_authority,
+            &ctx.accounts.pump_swap_program,
+            seeds,
+        )?;
+        let total_collected = vault_balance_before.saturating_sub(ctx.accounts.creator_vault.lamports());
+
+        let shares = attribute_collected(total_collected, &pending);
+        for (loader, share) in all_stats.iter().zip(shares.iter().copied()) {
+            let mut stats = loader.load_mut()?;
+            stats.total_sol_collected = stats.total_sol_collected.saturating_add(share);
+
+            take_vault_cuts(
+                &state,
+                &ctx.accounts.dat_authority,
+                &ctx.accounts.system_program.to_account_info(),
+                ctx.accounts.reserve_vault.as_mut(),
+                ctx.accounts.insurance_fund.as_mut(),
+                stats.mint,
+                share,
+                clock.unix_timestamp,
+            )?;
+        }
+        let attributed: u64 = shares.iter().sum();
+
+        emit!(FeesBatchCollected {
+            tokens: tokens as u8,
+            total_collected,
+            unattributed: total_collected - attributed,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("Batch collected {} lamports for {} tokens", total_collected, tokens);
+        Ok(())
+    }
+
+    /// Run a whole ecosystem cycle in one transaction: collect the shared creator vault,
+    /// buy and burn up to MAX_ECOSYSTEM_SECONDARIES secondaries (their root share goes to the
+    /// root treasury), then drain the root treasury into the root buy.
+    /// Replaces the N+1 collect_fees / execute_buy_secondary / burn_and_update choreography:
+    /// any failing step reverts the whole cycle, so no partially-completed state is left behind.
+    /// The root buy is queued in pending_burn_amount and completed by burn_and_update (which
+    /// keeps the root's burn split, reflection and keeper reward). Secondaries must burn 100%
+    /// with no reflection or partner; their CycleState is not advanced.
+    /// With more than one secondary the account list needs a v0 transaction with an Address
+    /// Lookup Table holding the static accounts.
+    ///
+    /// secondary_allocations[i]: lamports settled from secondary i's pending fees
+    /// root_allocation: cap on the root's own vault share (None = buy with everything spendable)
+    /// remaining_accounts: 6 per secondary: [token_stats (mut), mint (mut), bonding_curve (mut),
+    /// curve_token_account (mut), dat_token_account (mut), token_config]
+    pub fn run_ecosystem_cycle<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RunEcosystemCycle<'info>>,
+        secondary_allocations: Vec<u64>,
+        root_allocation: Option<u64>,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let state = ctx.accounts.dat_state.load()?;
+        require!(state.is_active != 0 && state.emergency_pause == 0, ErrorCode::DATNotActive);
+        require!(state.randomized_timing == 0, ErrorCode::EcosystemCycleUnsupported);
+        require!(ctx.accounts.root_token_stats.load()?.is_paused == 0, ErrorCode::TokenPaused);
+        require!(state.fee_split_bps > 0 && state.fee_split_bps <= 10000, ErrorCode::InvalidFeeSplit);
+
+        // Enforce minimum cycle interval (disabled in testing mode)
+        if !TESTING_MODE {
+            require!(
+                clock.unix_timestamp - state.last_cycle_timestamp >= state.min_cycle_interval,
+                ErrorCode::CycleTooSoon
+            );
+        }
+
+        let secondaries = secondary_allocations.len();
+        require!(
+            secondaries <= MAX_ECOSYSTEM_SECONDARIES && ctx.remaining_accounts.len() == secondaries * 7,
+            ErrorCode::InvalidParameter
+        );
+        let allocated = secondary_allocations
+            .iter()
+            .try_fold(0u64, |total, a| total.checked_add(*a))
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        // Every buy pays the same protocol fee recipient, so the rotation advances once
+        let next_recipient_index = check_protocol_fee_recipient(
+            ctx.accounts.protocol_fee_recipient.key,
+            ctx.accounts.fee_recipients.as_ref().map(|list| list.active()),
+            &ctx.accounts.pump_global_config,
+            state.current_fee_recipient_index,
+            &ctx.accounts.token_program.key(),
+            is_mayhem_bonding_curve(&ctx.accounts.root_pool.try_borrow_data()?),
+        )?;
+        drop(state);
+
+        let root_mint = ctx.accounts.root_mint.key();
+        {
+            let mut state = ctx.accounts.dat_state.load_mut()?;
+            state.last_cycle_timestamp = clock.unix_timestamp;
+            state.acquire_cycle_lock(root_mint)?;
+        }
+        ctx.accounts.root_cycle_state.begin_collect(clock.unix_timestamp, clock.slot)?;
+        ctx.accounts.root_cycle_state.refresh_reserves(&Rent::get()?);
+
+        // STEP 1: Collect from creator vault (all bonding-curve tokens)
+        require_compute_remaining(CU_PHASE_COLLECT)?;
+        let seeds: &[&[u8]] = &[DAT_AUTHORITY_SEED, &[ctx.accounts.dat_state.load()?.dat_authority_bump]];
+        let vault_balance_before = ctx.accounts.creator_vault.lamports();
+        collect_creator_fee_cpi(
+            &ctx.accounts.dat_authority,
+            &ctx.accounts.creator_vault,
+            &ctx.accounts.system_program,
+            &ctx.accounts.pump_event_authority,
+            &ctx.accounts.pump_swap_program,
+            seeds,
+        )?;
+        let sol_from_vault = vault_balance_before.saturating_sub(ctx.accounts.creator_vault.lamports());
+        take_vault_cuts(
+            &*ctx.accounts.dat_state.load()?,
+            &ctx.accounts.dat_authority,
+            &ctx.accounts.system_program.to_account_info(),
+            ctx.accounts.reserve_vault.as_mut(),
+            ctx.accounts.insurance_fund.as_mut(),
+            root_mint,
+            sol_from_vault,
+            clock.unix_timestamp,
+        )?;
+
+        // STEP 2: Secondaries - split to the root treasury, buy and burn
+        let mut sol_to_root: u64 = 0;
+        for (accs, allocation) in ctx.remaining_accounts.chunks(7).zip(secondary_allocations) {
+            let sent = run_ecosystem_secondary(ctx.accounts, accs, allocation, clock.unix_timestamp)?;
+            sol_to_root = sol_to_root.saturating_add(sent);
+
+            emit!(FeesRedirectedToRoot {
+                from_token: accs[1].key(),
+                to_root: root_mint,
+                amount: sent,
+                venue: VENUE_BONDING_CURVE,
+                cycle_seq: ctx.accounts.root_cycle_state.sequence,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+
+        // STEP 3: Drain the root treasury (this cycle's shares plus anything left from earlier ones)
+        let treasury_amt = ctx.accounts.root_treasury.lamports();
+        if treasury_amt > 0 {
+            let treasury_seeds: &[&[u8]] = &[ROOT_TREASURY_SEED, root_mint.as_ref(), &[ctx.bumps.root_treasury]];
+            invoke_signed(
+                &anchor_lang::solana_program::system_instruction::transfer(
+                    ctx.accounts.root_treasury.key,
+                    ctx.accounts.dat_authority.key,
+                    treasury_amt,
+                ),
+                &[
+                    ctx.accounts.root_treasury.to_account_info(),
+                    ctx.accounts.dat_authority.to_account_info(),
+                    ctx.accounts.system_program.to_account_info(),
+                ],
+                &[treasury_seeds],
+            )?;
+
+            emit!(RootTreasuryCollected {
+                root_mint,
+                amount: treasury_amt,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+
+        // Root keeps the vault fees not allocated to a secondary
+        {
+            let mut root_stats = ctx.accounts.root_token_stats.load_mut()?;
+            root_stats.total_sol_received_from_others =
+                root_stats.total_sol_received_from_others.saturating_add(treasury_amt);
+            root_stats.total_sol_collected = root_stats.total_sol_collected
+                .saturating_add(sol_from_vault.saturating_sub(allocated))
+                .saturating_add(treasury_amt);
+        }
+
+        // STEP 4: Root buy, burned later by burn_and_update
+        let spendable = ctx.accounts.dat_state.load()?.spendable_lamports(
+            ctx.accounts.dat_authority.lamports(),
+            ctx.accounts.root_cycle_state.rent_reserve() + SAFETY_BUFFER,
+        );
+        let buy_amount = match root_allocation {
+            Some(a) => a.saturating_add(treasury_amt).saturating_sub(SAFETY_BUFFER).min(spendable),
+            None => spendable,
+        };
+        require!(buy_amount >= MINIMUM_BUY_AMOUNT, ErrorCode::InsufficientFees);
+        require_compute_remaining(CU_PHASE_PUMPFUN_BUY)?;
+        ctx.accounts.root_cycle_state.begin_buy(false, VENUE_BONDING_CURVE, clock.unix_timestamp, clock.slot)?;
+
+        let root_mint_info = ctx.accounts.root_mint.to_account_info();
+        let root_pool = ctx.accounts.root_pool.to_account_info();
+        let root_pool_token_account = ctx.accounts.root_pool_token_account.to_account_info();
+        let root_dat_token_account = ctx.accounts.root_dat_token_account.to_account_info();
+        let last_price = ctx.accounts.dat_state.load()?.last_known_price;
+        let max_deviation = ctx.accounts.dat_state.load()?.max_price_deviation_bps;
+        let (sol_used, _, execution_price) = ecosystem_pump_buy(
+            ctx.accounts,
+            &root_mint_info,
+            &root_pool,
+            &root_pool_token_account,
+            &root_dat_token_account,
+            buy_amount,
+            last_price,
+            max_deviation,
+        )?;
+
+        // NOTE: reload() required after CPI to get updated token balance - Anchor doesn't auto-reload for invoke_signed
+        ctx.accounts.root_dat_token_account.reload()?;
+        let mut state = ctx.accounts.dat_state.load_mut()?;
+        state.pending_burn_amount = ctx.accounts.root_dat_token_account.amount;
+        state.last_cycle_sol = sol_used;
+        state.last_sol_sent_to_root = 0;
+        if let Some(next) = next_recipient_index {
+            state.current_fee_recipient_index = next;
+        }
+        if execution_price > 0 {
+            state.last_known_price = execution_price;
+        }
+
+        emit!(EcosystemCycleExecuted {
+            root_mint,
+            secondaries: secondaries as u8,
+            sol_from_vault,
+            sol_to_root,
+            root_buy_lamports: sol_used,
+            pending_burn_amount: state.pending_burn_amount,
+            cycle_seq: ctx.accounts.root_cycle_state.sequence,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("Ecosystem cycle: {} secondaries, {} lamports to root, root buy {} lamports",
+            secondaries, sol_to_root, sol_used);
+        Ok(())
+    }
+
+    /// Create the Address Lookup Table for batched cycle transactions (admin only)
+    /// dat_authority is the table authority. The table is seeded with cycle_lut_static_addresses
+    /// and recorded in DATState.cycle_lut, replacing any previous table (left untouched on-chain).
+    /// recent_slot must still be in SlotHashes, as the lookup table program requires
+    pub fn create_cycle_lut(ctx: Context<CreateCycleLut>, recent_slot: u64) -> Result<()> {
+        let dat_authority = ctx.accounts.dat_authority.key();
+        let (lookup_table, bump_seed) = derive_lookup_table_address(&dat_authority, recent_slot);
+        require!(ctx.accounts.lookup_table.key() == lookup_table, ErrorCode::InvalidLookupTable);
+
+        let seeds: &[&[u8]] = &[DAT_AUTHORITY_SEED, &[ctx.accounts.dat_state.load()?.dat_authority_bump]];
+        let admin = ctx.accounts.admin.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+        create_lookup_table_cpi(
+            &ctx.accounts.lookup_table,
+            &ctx.accounts.dat_authority,
+            &admin,
+            &system_program,
+            &ctx.accounts.address_lookup_table_program,
+            recent_slot,
+            bump_seed,
+            seeds,
+        )?;
+
+        let addresses = cycle_lut_static_addresses(&ctx.accounts.dat_state.key(), &dat_authority);
+        extend_lookup_table_cpi(
+            &ctx.accounts.lookup_table,
+            &ctx.accounts.dat_authority,
+            &admin,
+            &system_program,
+            &ctx.accounts.address_lookup_table_program,
+            &addresses,
+            seeds,
+        )?;
+
+        let previous = std::mem::replace(&mut ctx.accounts.dat_state.load_mut()?.cycle_lut, lookup_table);
+
+        emit!(CycleLutCreated {
+            lookup_table,
+            previous,
+            recent_slot,
+            addresses: addresses.len() as u8,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Cycle LUT {} created with {} static addresses", lookup_table, addresses.len());
+        Ok(())
+    }
+
+    /// Append addresses to the recorded cycle Address Lookup Table (admin only)
+    /// For accounts that are not program constants: pump global config, fee config,
+    /// fee recipients, root and secondary token accounts. The admin pays the extra rent
+    pub fn extend_cycle_lut(ctx: Context<ExtendCycleLut>, addresses: Vec<Pubkey>) -> Result<()> {
+        require!(
+            !addresses.is_empty() && addresses.len() <= MAX_LUT_EXTEND_ADDRESSES,
+            ErrorCode::InvalidParameter
+        );
+
+        let seeds: &[&[u8]] = &[DAT_AUTHORITY_SEED, &[ctx.accounts.dat_state.load()?.dat_authority_bump]];
+        extend_lookup_table_cpi(
+            &ctx.accounts.lookup_table,
+            &ctx.accounts.dat_authority,
+            &ctx.accounts.admin.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &ctx.accounts.address_lookup_table_program,
+            &addresses,
+            seeds,
+        )?;
+
+        emit!(CycleLutExtended {
+            lookup_table: ctx.accounts.lookup_table.key(),
+            added: addresses.len() as u8,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Collect fees from PumpSwap AMM creator vault
+    /// Used for tokens that have migrated from bonding curve to AMM
+    /// Requires: DAT authority PDA must be set as coin_creator in PumpSwap
+    /// IMPORTANT: This collects WSOL (SPL Token), not native SOL
+    /// The treasury reserve (reserve_bps) and insurance deposit (insurance_bps) are only
+    /// taken from native SOL in collect_fees
+    pub fn collect_fees_amm(ctx: Context<CollectFeesAMM>) -> Result<()> {
+        let mut token_stats = ctx.accounts.token_stats.load_mut()?;
+        let state = ctx.accounts.dat_state.load()?;
+        require!(state.is_active != 0 && state.emergency_pause == 0, ErrorCode::DATNotActive);
+        require!(token_stats.is_paused == 0, ErrorCode::TokenPaused);
+
+        let bump = state.dat_authority_bump;
+        let seeds: &[&[u8]] = &[DAT_AUTHORITY_SEED, &[bump]];
+
+        require_compute_remaining(CU_PHASE_COLLECT)?;
+
+        let clock = Clock::get()?;
+        require_cycle_window(&state, ctx.accounts.cycle_window.as_deref(), clock.unix_timestamp)?;
+        drop(state);
+        ctx.accounts.dat_state.load_mut()?.acquire_cycle_lock(ctx.accounts.token_mint.key())?;
+        ctx.accounts.cycle_state.begin_collect(clock.unix_timestamp, clock.slot)?;
+        ctx.accounts.cycle_state.refresh_reserves(&Rent::get()?);
+
+        // Track WSOL balance before collection
+        let wsol_before = ctx.accounts.dat_wsol_account.amount;
+
+        // Call PumpSwap's collect_coin_creator_fee via CPI
+        // DAT authority PDA signs as the coin_creator
+        collect_amm_creator_fee_cpi(
+            &ctx.accounts.wsol_mint.to_account_info(),
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.dat_authority.to_account_info(),
+            &ctx.accounts.creator_vault_authority.to_account_info(),
+            &ctx.accounts.creator_vault_ata.to_account_info(),
+            &ctx.accounts.dat_wsol_account.to_account_info(),
+            &ctx.accounts.pump_swap_program.to_account_info(),
+            seeds,
+        )?;
+
+        // NOTE: reload() required after CPI to get updated WSOL balance - Anchor doesn't auto-reload for invoke_signed
+        ctx.accounts.dat_wsol_account.reload()?;
+        let wsol_after = ctx.accounts.dat_wsol_account.amount;
+        let wsol_collected = wsol_after.saturating_sub(wsol_before);
+
+        // Update token stats
+        token_stats.total_sol_collected =
+            token_stats.total_sol_collected.saturating_add(wsol_collected);
+
+        let now = Clock::get()?.unix_timestamp;
+        if let Some(daily) = ctx.accounts.daily_stats.as_mut() {
+            daily.record(now, wsol_collected, 0)?;
+        }
+
+        msg!("AMM creator fees collected: {} WSOL", wsol_collected);
+        emit!(AmmFeesCollected {
+            mint: token_stats.mint,
+            wsol_amount: wsol_collected,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Onboard an externally launched PumpFun token whose creator fees now point at the DAT
+    /// Pump's set_creator is gated by its global set_creator_authority, so the creator hands
+    /// the live bonding curve to dat_authority off-chain first; this instruction checks the
+    /// handoff and creates TokenStats / TokenConfig
+    pub fn onboard_external_token(ctx: Context<OnboardExternalToken>) -> Result<()> {
+        let original_creator = ctx.accounts.creator.key();
+
+        let (creator, complete) = bonding_curve_creator(&ctx.accounts.bonding_curve.try_borrow_data()?)?;
+        require!(!complete, ErrorCode::CurveAlreadyMigrated);
+        require!(creator == ctx.accounts.dat_authority.key(), ErrorCode::CreatorHandoffFailed);
+
+        let mut stats = ctx.accounts.token_stats.load_init()?;
+        init_token_stats(
+            &mut stats,
+            &ctx.accounts.mint,
+            original_creator,
+            ctx.bumps.token_stats,
+        )?;
+        register_in_token_registry(
+            &mut ctx.accounts.token_registry,
+            &mut ctx.accounts.token_registry_page,
+            &mut stats,
+        )?;
+
+        let clock = Clock::get()?;
+        let config = &mut ctx.accounts.token_config;
+        config.mint = ctx.accounts.mint.key();
+        config.updated_at = clock.unix_timestamp;
+        config.bump = ctx.bumps.token_config;
+        config.version = TOKEN_CONFIG_VERSION;
+        config.original_creator = original_creator;
+        config.onboarded_at = clock.unix_timestamp;
+
+        emit!(ExternalTokenOnboarded {
+            mint: config.mint,
+            original_creator,
+            bonding_curve: ctx.accounts.bonding_curve.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Start the offboarding timelock of an onboarded token (original creator only)
+    pub fn request_offboard(ctx: Context<RequestOffboard>) -> Result<()> {
+        let config = &mut ctx.accounts.token_config;
+        require!(config.offboard_requested_at == 0, ErrorCode::OffboardAlreadyRequested);
+
+        let clock = Clock::get()?;
+        config.offboard_requested_at = clock.unix_timestamp;
+        config.updated_at = clock.unix_timestamp;
+
+        emit!(OffboardRequested {
+            mint: config.mint,
+            original_creator: config.original_creator,
+            executable_at: clock.unix_timestamp.saturating_add(OFFBOARD_TIMELOCK),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Offboard a token after OFFBOARD_TIMELOCK (original creator only)
+    /// Pays the token's pending fees to its root and closes TokenStats + TokenConfig.
+    /// The bonding curve keeps dat_authority as creator: only pump's set_creator_authority
+    /// can move it back.
+    pub fn offboard_token(ctx: Context<OffboardToken>) -> Result<()> {
+        let token_stats = ctx.accounts.token_stats.load()?;
+        let clock = Clock::get()?;
+        let mint = ctx.accounts.mint.key();
+        let state = ctx.accounts.dat_state.load()?;
+        ctx.accounts.token_config.require_offboard_ready(clock.unix_timestamp)?;
+        require!(state.root_token_mint() != Some(mint), ErrorCode::CannotCloseRootToken);
+        require!(state.cycle_lock() != Some(mint), ErrorCode::CycleLocked);
+        require!(ctx.accounts.dat_token_account.amount == 0, ErrorCode::PendingBurnNotZero);
+        unregister_from_token_registry(
+            ctx.accounts.token_registry.as_deref_mut(),
+            ctx.accounts.token_registry_page.as_deref_mut().map(|page| &mut **page),
+            &token_stats,
+        )?;
+
+        let root_mint = RootSet::resolve(
+            ctx.accounts.token_config.assigned_root,
+            state.root_token_mint(),
+            ctx.accounts.root_set.as_deref().map(|set| &**set),
+        )?;
+        let (expected_treasury, _) = Pubkey::find_program_address(&[ROOT_TREASURY_SEED, root_mint.as_ref()], ctx.program_id);
+        require!(ctx.accounts.root_treasury.key() == expected_treasury, ErrorCode::InvalidRootTreasury);
+
+        let seeds: &[&[u8]] = &[DAT_AUTHORITY_SEED, &[state.dat_authority_bump]];
+
+        // Pending fees were collected on this token's behalf: all of it goes to the root
+        let fees_settled = token_stats.pending_fees_lamports.min(state.spendable_lamports(
+            ctx.accounts.dat_authority.lamports(),
+            RENT_EXEMPT_MINIMUM + SAFETY_BUFFER,
+        ));
+        if fees_settled > 0 {
+            split_fees_to_root(
+                &ctx.accounts.dat_authority,
+                &ctx.accounts.root_treasury,
+                &ctx.accounts.system_program.to_account_info(),
+                fees_settled,
+                0,
+                seeds,
+            )?;
+        }
+
+        emit!(TokenOffboarded {
+            mint,
+            original_creator: ctx.accounts.creator.key(),
+            root_mint,
+            fees_settled,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Register dat_authority as coin_creator of a migrated token's PumpSwap pool
+    /// Runs PumpSwap's set_coin_creator so collect_fees_amm works after migration without
+    /// a manual step. Permissionless; a no-op once the pool already pays dat_authority
+    pub fn claim_amm_creator(ctx: Context<ClaimAmmCreator>) -> Result<()> {
+        let mint = ctx.accounts.token_mint.key();
+        let dat_authority = ctx.accounts.dat_authority.key();
+
+        let info = parse_pumpswap_pool(&ctx.accounts.pool.try_borrow_data()?)?;
+        require!(info.base_mint == mint, ErrorCode::PoolBaseMintMismatch);
+        require!(
+            migrated_curve_creator(&ctx.accounts.bonding_curve.try_borrow_data()?)? == dat_authority,
+            ErrorCode::NotCurveCreator
+        );
+
+        let previous_creator = pumpswap_pool_coin_creator(&ctx.accounts.pool.try_borrow_data()?)?;
+        if previous_creator == dat_authority {
+            msg!("PumpSwap pool already pays dat_authority");
+            return Ok(());
+        }
+
+        let seeds: &[&[u8]] = &[DAT_AUTHORITY_SEED, &[ctx.accounts.dat_state.load()?.dat_authority_bump]];
+        set_amm_coin_creator_cpi(
+            &ctx.accounts.pool,
+            &ctx.accounts.metadata,
+            &ctx.accounts.bonding_curve,
+            &ctx.accounts.event_authority,
+            &ctx.accounts.pump_swap_program,
+            seeds,
+        )?;
+
+        require!(
+            pumpswap_pool_coin_creator(&ctx.accounts.pool.try_borrow_data()?)? == dat_authority,
+            ErrorCode::CoinCreatorMismatch
+        );
+
+        emit!(AmmCreatorClaimed {
+            mint,
+            pool: ctx.accounts.pool.key(),
+            previous_creator,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Unwrap WSOL to native SOL in DAT authority account
+    /// Call this after collect_fees_amm to convert WSOL to SOL for buyback
+    pub fn unwrap_wsol(ctx: Context<UnwrapWsol>) -> Result<()> {
+        let state = ctx.accounts.dat_state.load()?;
+        require!(state.is_active != 0 && state.emergency_pause == 0, ErrorCode::DATNotActive);
+
+        let bump = state.dat_authority_bump;
+        let seeds: &[&[u8]] = &[DAT_AUTHORITY_SEED, &[bump]];
+
+        // Get WSOL balance to unwrap
+        let wsol_amount = ctx.accounts.dat_wsol_account.amount;
+        require!(wsol_amount > 0, ErrorCode::InsufficientFees);
+
+        // Close the WSOL token account (transfers lamports to dat_authority)
+        let cpi_accounts = anchor_spl::token::CloseAccount {
+            account: ctx.accounts.dat_wsol_account.to_account_info(),
+            destination: ctx.accounts.dat_authority.to_account_info(),
+            authority: ctx.accounts.dat_authority.to_account_info(),
+        };
+        let signer_seeds: &[&[&[u8]]] = &[seeds];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        anchor_spl::token::close_account(cpi_ctx)?;
+
+        msg!("WSOL unwrapped: {} lamports now in DAT authority", wsol_amount);
+        Ok(())
+    }
+
+    /// Recover WSOL left in dat_wsol_account by a failed AMM buy (permissionless)
+    /// Only once no cycle holds the lock and none has completed for CYCLE_ABORT_TIMEOUT;
+    /// the account is closed into dat_authority, so the SOL is available to the next cycle
+    pub fn recover_wsol(ctx: Context<RecoverWsol>) -> Result<()> {
+        let state = ctx.accounts.dat_state.load()?;
+        let clock = Clock::get()?;
+        state.require_cycles_stale(clock.unix_timestamp, CYCLE_ABORT_TIMEOUT)?;
+
+        let wsol_amount = ctx.accounts.dat_wsol_account.amount;
+        require!(wsol_amount > 0, ErrorCode::InsufficientFees);
+
+        let bump = state.dat_authority_bump;
+        let seeds: &[&[u8]] = &[DAT_AUTHORITY_SEED, &[bump]];
+        let before = ctx.accounts.dat_authority.lamports();
+
+        let cpi_accounts = anchor_spl::token::CloseAccount {
+            account: ctx.accounts.dat_wsol_account.to_account_info(),
+            destination: ctx.accounts.dat_authority.to_account_info(),
+            authority: ctx.accounts.dat_authority.to_account_info(),
+        };
+        let signer_seeds: &[&[&[u8]]] = &[seeds];
+        anchor_spl::token::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        ))?;
+
+        emit!(WsolRecovered {
+            wsol_account: ctx.accounts.dat_wsol_account.key(),
+            wsol_amount,
+            lamports_recovered: ctx.accounts.dat_authority.lamports().saturating_sub(before),
+            recovered_by: ctx.accounts.caller.key(),
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Wrap native SOL to WSOL for AMM buyback
+    /// Call this before execute_buy_amm when root token is on PumpSwap AMM
+    /// The WSOL ATA is created on first use; its rent comes from payer, never dat_authority
+    pub fn wrap_wsol(ctx: Context<WrapWsol>, amount: u64) -> Result<()> {
+        let state = ctx.accounts.dat_state.load()?;
+        require!(state.is_active != 0 && state.emergency_pause == 0, ErrorCode::DATNotActive);
+        require!(amount > 0, ErrorCode::InsufficientFees);
+
+        let bump = state.dat_authority_bump;
+        let seeds: &[&[u8]] = &[DAT_AUTHORITY_SEED, &[bump]];
+
+        // Verify sufficient balance in dat_authority (ATA rent, if any, was paid by payer)
+        let available = state.spendable_lamports(
+            ctx.accounts.dat_authority.lamports(),
+            RENT_EXEMPT_MINIMUM + SAFETY_BUFFER,
+        );
+        require!(available >= amount, ErrorCode::InsufficientFees);
+
+        // Transfer native SOL from dat_authority to dat_wsol_account
+        let transfer_ix = anchor_lang::solana_program::system_instruction::transfer(
+            &ctx.accounts.dat_authority.key(),
+            &ctx.accounts.dat_wsol_account.key(),
+            amount,
+        );
+        invoke_signed(
+            &transfer_ix,
+            &[
+                ctx.accounts.dat_authority.to_account_info(),
+                ctx.accounts.dat_wsol_account.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+            &[seeds],
+        )?;
+
+        // Sync native - updates the WSOL token balance to match lamports
+        let sync_accounts = token::SyncNative {
+            account: ctx.accounts.dat_wsol_account.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            sync_accounts,
+        );
+        token::sync_native(cpi_ctx)?;
+
+        msg!("WSOL wrapped: {} lamports converted to WSOL", amount);
+        Ok(())
+    }
+
+    /// Execute buy on bonding curve - ROOT TOKEN ONLY (simpler, no split logic)
+    /// For secondary tokens, use execute_buy_secondary instead
+    /// max_price_lamports_per_token: optional bound on the effective price (lamports per token
+    /// base unit, PRICE_SCALE-scaled), checked after the CPI; fails with SlippageExceeded
+    /// tip_lamports: optional Jito tip paid from dat_authority to jito_tip_account
+    /// (<= max_jito_tip_lamports), taken out of the allocation before the buy is sized
+    pub fn execute_buy(
+        ctx: Context<ExecuteBuy>,
+        allocated_lamports: Option<u64>,
+        max_price_lamports_per_token: Option<u64>,
+        tip_lamports: Option<u64>,
+    ) -> Result<()> {
+        require!(ctx.accounts.dat_state.load()?.is_active != 0 && ctx.accounts.dat_state.load()?.emergency_pause == 0, ErrorCode::DATNotActive);
+
+        let tip = pay_cycle_tip(
+            &*ctx.accounts.dat_state.load()?,
+            &ctx.accounts.cycle_state,
+            &ctx.accounts.dat_authority,
+            ctx.accounts.jito_tip_account.as_ref(),
+            &ctx.accounts.system_program.to_account_info(),
+            tip_lamports,
+            ctx.accounts.asdf_mint.key(),
+        )?;
+        let allocated_lamports = allocated_lamports.map(|a| a.saturating_sub(tip));
+
+        // Calculate buy amount (root token - no ATA reserve needed)
+        let buy_amount = match allocated_lamports {
+            Some(a) => a.saturating_sub(SAFETY_BUFFER),
+            None => ctx.accounts.dat_state.load()?.spendable_lamports(
+                ctx.accounts.dat_authority.lamports(),
+                ctx.accounts.cycle_state.rent_reserve() + SAFETY_BUFFER,
+            ),
+        };
+        require!(buy_amount >= MINIMUM_BUY_AMOUNT, ErrorCode::InsufficientFees);
+
+        let clock = Clock::get()?;
+        ctx.accounts.dat_state.load_mut()?.acquire_cycle_lock(ctx.accounts.asdf_mint.key())?;
+        ctx.accounts.cycle_state.begin_buy(false, VENUE_BONDING_CURVE, clock.unix_timestamp, clock.slot)?;
+
+        // Delegate to CPI helper
+        execute_buy_inner(ctx.accounts, buy_amount, max_price_lamports_per_token)
+    }
+
+    /// Start a TWAP-style DCA plan for a mint (admin only)
+    /// Splits total_lamports into tranche_count tranches of at most ceil(total / count),
+    /// executed by execute_buy_tranche at least min_slot_spacing slots apart
+    pub fn start_dca(
+        ctx: Context<StartDca>,
+        total_lamports: u64,
+        tranche_count: u8,
+        min_slot_spacing: u64,
+    ) -> Result<()> {
+        require!(
+            (2..=MAX_DCA_TRANCHES).contains(&tranche_count),
+            ErrorCode::InvalidParameter
+        );
+        require!(min_slot_spacing >= MIN_DCA_SLOT_SPACING, ErrorCode::InvalidParameter);
+        require!(
+            total_lamports <= ctx.accounts.dat_state.load()?.max_fees_per_cycle,
+            ErrorCode::InvalidParameter
+        );
+
+        let tranche_lamports = total_lamports
+            .checked_add(tranche_count as u64 - 1)
+            .ok_or(ErrorCode::MathOverflow)?
+            / tranche_count as u64;
+        require!(tranche_lamports >= MINIMUM_BUY_AMOUNT, ErrorCode::InsufficientFees);
+
+        let dca = &mut ctx.accounts.dca_state;
+        require!(!dca.is_active(), ErrorCode::DcaAlreadyActive);
+
+        let clock = Clock::get()?;
+        dca.mint = ctx.accounts.mint.key();
+        dca.total_lamports = total_lamports;
+        dca.remaining_lamports = total_lamports;
+        dca.tranche_lamports = tranche_lamports;
+        dca.tranche_count = tranche_count;
+        dca.tranches_executed = 0;
+        dca.min_slot_spacing = min_slot_spacing;
+        dca.last_tranche_slot = 0;
+        dca.started_at = clock.unix_timestamp;
+        dca.bump = ctx.bumps.dca_state;
+
+        emit!(DcaStarted {
+            mint: dca.mint,
+            total_lamports,
+            tranche_count,
+            tranche_lamports,
+            min_slot_spacing,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Execute the next DCA tranche on the bonding curve
+    /// Enforces per-tranche maximum and minimum slot spacing on-chain
+    pub fn execute_buy_tranche(ctx: Context<ExecuteBuyTranche>) -> Result<()> {
+        let clock = Clock::get()?;
+        require!(
+            ctx.accounts.buy.dat_state.load()?.is_active != 0 && ctx.accounts.buy.dat_state.load()?.emergency_pause == 0,
+            ErrorCode::DATNotActive
+        );
+
+        let dca = &ctx.accounts.dca_state;
+        require!(dca.is_active(), ErrorCode::DcaNotActive);
+        if dca.tranches_executed > 0 {
+            require!(
+                clock.slot >= dca.last_tranche_slot.saturating_add(dca.min_slot_spacing),
+                ErrorCode::DcaTrancheTooSoon
+            );
+        }
+
+        let tranche = dca.remaining_lamports.min(dca.tranche_lamports);
+        let available = ctx.accounts.buy.dat_state.load()?.spendable_lamports(
+            ctx.accounts.buy.dat_authority.lamports(),
+            ctx.accounts.buy.cycle_state.rent_reserve() + SAFETY_BUFFER,
+        );
+        require!(available >= tranche, ErrorCode::InsufficientFees);
+
+        let buy_amount = tranche.saturating_sub(SAFETY_BUFFER);
+        require!(buy_amount >= MINIMUM_BUY_AMOUNT, ErrorCode::InsufficientFees);
+
+        // Later tranches spend the same collection and may follow the previous tranche's burn
+        let continuation = dca.tranches_executed > 0;
+        ctx.accounts.buy.dat_state.load_mut()?.acquire_cycle_lock(ctx.accounts.buy.asdf_mint.key())?;
+        ctx.accounts.buy.cycle_state.begin_buy(continuation, VENUE_BONDING_CURVE, clock.unix_timestamp, clock.slot)?;
+
+        execute_buy_inner(&mut ctx.accounts.buy, buy_amount, None)?;
+
+        let dca = &mut ctx.accounts.dca_state;
+        dca.remaining_lamports = dca.remaining_lamports.saturating_sub(tranche);
+        dca.tranches_executed = dca.tranches_executed.saturating_add(1);
+        dca.last_tranche_slot = clock.slot;
+
+        emit!(DcaTrancheExecuted {
+            mint: dca.mint,
+            tranche_index: dca.tranches_executed,
+            tranche_lamports: tranche,
+            remaining_lamports: dca.remaining_lamports,
+            slot: clock.slot,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Seal the size of the next bonding-curve buy for a mint (admin only)
+    /// commitment = sha256(mint || amount LE || salt); replaces any open commitment.
+    /// reveal_and_buy must follow within BUY_REVEAL_WINDOW_SLOTS
+    pub fn commit_buy(ctx: Context<CommitBuy>, commitment: [u8; 32]) -> Result<()> {
+        require!(commitment != [0u8; 32], ErrorCode::InvalidParameter);
+
+        let clock = Clock::get()?;
+        let commit = &mut ctx.accounts.buy_commitment;
+        commit.mint = ctx.accounts.mint.key();
+        commit.commitment = commitment;
+        commit.committed_slot = clock.slot;
+        commit.bump = ctx.bumps.buy_commitment;
+
+        emit!(BuyCommitted {
+            mint: commit.mint,
+            commitment,
+            committed_slot: clock.slot,
+            reveal_deadline: commit.reveal_deadline(),
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Bind a freshly committed Switchboard randomness account to a mint's next cycle window (admin only)
+    /// The randomness must have been committed in the previous slot and not yet revealed, so its
+    /// value is unknown to everyone, the admin included. Refused while a window is pending or open
+    pub fn request_cycle_randomness(ctx: Context<RequestCycleRandomness>) -> Result<()> {
+        let clock = Clock::get()?;
+        let randomness = {
+            let data = ctx.accounts.randomness_account.try_borrow_data()?;
+            parse_switchboard_randomness(&data)?
+        };
+        require!(
+            randomness.seed_slot == clock.slot.saturating_sub(1) && !randomness.is_revealed(),
+            ErrorCode::RandomnessNotFresh
+        );
+
+        let window = &mut ctx.accounts.cycle_window;
+        window.mint = ctx.accounts.mint.key();
+        window.bump = ctx.bumps.cycle_window;
+        window.request(ctx.accounts.randomness_account.key(), randomness.seed_slot, clock.unix_timestamp)?;
+
+        emit!(CycleRandomnessRequested {
+            mint: window.mint,
+            randomness_account: window.randomness_account,
+            seed_slot: randomness.seed_slot,
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Fix a mint's cycle window from the revealed randomness (permissionless)
+    /// Anyone can lock in the value as soon as the oracle reveals it
+    pub fn settle_cycle_window(ctx: Context<SettleCycleWindow>) -> Result<()> {
+        let randomness = {
+            let data = ctx.accounts.randomness_account.try_borrow_data()?;
+            parse_switchboard_randomness(&data)?
+        };
+        let window = &mut ctx.accounts.cycle_window;
+        require!(randomness.seed_slot == window.seed_slot, ErrorCode::RandomnessMismatch);
+        require!(randomness.is_revealed(), ErrorCode::RandomnessNotRevealed);
+
+        window.settle(&randomness.value)?;
+
+        emit!(CycleWindowSettled {
+            mint: window.mint,
+            randomness: randomness.value,
+            window_start: window.window_start,
+            window_end: window.window_end,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Reveal a committed buy size and execute it on the bonding curve - ROOT TOKEN ONLY
+    /// Must land within BUY_REVEAL_WINDOW_SLOTS of commit_buy; the commitment is consumed
+    pub fn reveal_and_buy(ctx: Context<RevealAndBuy>, amount: u64, salt: [u8; 32]) -> Result<()> {
+        let clock = Clock::get()?;
+        require!(
+            ctx.accounts.buy.dat_state.load()?.is_active != 0 && ctx.accounts.buy.dat_state.load()?.emergency_pause == 0,
+            ErrorCode::DATNotActive
+        );
+
+        let committed_slot = ctx.accounts.buy_commitment.committed_slot;
+        ctx.accounts.buy_commitment.reveal(amount, &salt, clock.slot)?;
+
+        let available = ctx.accounts.buy.dat_state.load()?.spendable_lamports(
+            ctx.accounts.buy.dat_authority.lamports(),
+            ctx.accounts.buy.cycle_state.rent_reserve() + SAFETY_BUFFER,
+        );
+        require!(available >= amount, ErrorCode::InsufficientFees);
+
+        let buy_amount = amount.saturating_sub(SAFETY_BUFFER);
+        require!(buy_amount >= MINIMUM_BUY_AMOUNT, ErrorCode::InsufficientFees);
+
+        ctx.accounts.buy.dat_state.load_mut()?.acquire_cycle_lock(ctx.accounts.buy.asdf_mint.key())?;
+        ctx.accounts.buy.cycle_state.begin_buy(false, VENUE_BONDING_CURVE, clock.unix_timestamp, clock.slot)?;
+
+        execute_buy_inner(&mut ctx.accounts.buy, buy_amount, None)?;
+
+        emit!(BuyRevealed {
+            mint: ctx.accounts.buy_commitment.mint,
+            amount,
+            committed_slot,
+            slot: clock.slot,
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Cancel the remaining tranches of a DCA plan (admin only)
+    /// Unspent lamports stay in dat_authority for the next cycle
+    pub fn cancel_dca(ctx: Context<CancelDca>) -> Result<()> {
+        let dca = &mut ctx.accounts.dca_state;
+        require!(dca.is_active(), ErrorCode::DcaNotActive);
+
+        let remaining = dca.remaining_lamports;
+        dca.remaining_lamports = 0;
+
+        emit!(DcaCancelled {
+            mint: dca.mint,
+            tranches_executed: dca.tranches_executed,
+            remaining_lamports: remaining,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Create the cycle state machine for a mint (permissionless, payer funds rent)
+    /// Required by collect_fees*, execute_buy* and burn_and_update for that mint
+    pub fn initialize_cycle_state(ctx: Context<InitializeCycleState>) -> Result<()> {
+        let cycle = &mut ctx.accounts.cycle_state;
+        let clock = Clock::get()?;
+
+        cycle.mint = ctx.accounts.token_stats.load()?.mint;
+        cycle.phase = CyclePhase::Idle;
+        cycle.sequence = 0;
+        cycle.phase_updated_at = clock.unix_timestamp;
+        cycle.phase_slot = clock.slot;
+        cycle.aborted_cycles = 0;
+        cycle.bump = ctx.bumps.cycle_state;
+        cycle.buy_venue = VENUE_BONDING_CURVE;
+        cycle.dev_fee_paid_sequence = 0;
+        cycle.liquidity_sequence = 0;
+
+        emit!(CycleStateInitialized {
+            mint: cycle.mint,
+            cycle_state: cycle.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Create the recent-cycle ring buffer for a mint (permissionless, payer funds rent)
+    /// burn_and_update records each cycle into it once passed as cycle_history
+    pub fn initialize_cycle_history(ctx: Context<InitializeCycleHistory>) -> Result<()> {
+        let history = &mut ctx.accounts.cycle_history;
+        history.mint = ctx.accounts.token_stats.load()?.mint;
+        history.total_recorded = 0;
+        history.head = 0;
+        history.records = [CycleRecord::default(); CYCLE_HISTORY_LEN];
+        history.bump = ctx.bumps.cycle_history;
+
+        emit!(CycleHistoryInitialized {
+            mint: history.mint,
+            cycle_history: history.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Create a mint's daily stats bucket (permissionless, payer funds rent)
+    /// `day` must be today or tomorrow so buckets can be created ahead of the day rollover
+    pub fn initialize_daily_stats(ctx: Context<InitializeDailyStats>, day: i64) -> Result<()> {
+        let today = DailyStats::day_index(Clock::get()?.unix_timestamp);
+        require!(day == today || day == today + 1, ErrorCode::DailyStatsDayMismatch);
+
+        let daily = &mut ctx.accounts.daily_stats;
+        daily.mint = ctx.accounts.token_stats.load()?.mint;
+        daily.day = day;
+        daily.sol_collected = 0;
+        daily.tokens_burned = 0;
+        daily.bump = ctx.bumps.daily_stats;
+
+        emit!(DailyStatsInitialized {
+            mint: daily.mint,
+            day,
+            daily_stats: daily.key(),
+        });
+        Ok(())
+    }
+
+    /// Recover a cycle stuck in Collected/Bought (permissionless after CYCLE_ABORT_TIMEOUT)
+    /// Collected lamports stay in dat_authority and bought tokens stay in the DAT token
+    /// account; both are picked up by the next cycle
+    pub fn abort_cycle(ctx: Context<AbortCycle>) -> Result<()> {
+        let cycle = &mut ctx.accounts.cycle_state;
+        let clock = Clock::get()?;
+        let stuck_seconds = clock.unix_timestamp.saturating_sub(cycle.phase_updated_at);
+
+        let aborted_phase = cycle.abort(clock.unix_timestamp, clock.slot, CYCLE_ABORT_TIMEOUT)?;
+        if ctx.accounts.dat_state.load()?.cycle_lock() == Some(cycle.mint) {
+            ctx.accounts.dat_state.load_mut()?.cycle_lock = Pubkey::default();
+        }
+
+        emit!(CycleAborted {
+            mint: cycle.mint,
+            sequence: cycle.sequence,
+            aborted_phase,
+            stuck_seconds,
+            aborted_by: ctx.accounts.caller.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Execute buy for SECONDARY tokens (includes fee split to root treasury)
+    pub fn execute_buy_secondary(
+        mut ctx: Context<ExecuteBuySecondary>,
+        allocated_lamports: Option<u64>,
+        tip_lamports: Option<u64>,
+    ) -> Result<()> {
+        // Assigned root (TokenConfig) or the primary root
+        let root_mint = RootSet::resolve(
+            assigned_root_of(&ctx.accounts.token_config)?,
+            ctx.accounts.dat_state.load()?.root_token_mint(),
+            ctx.accounts.root_set.as_deref().map(|set| &**set),
+        )?;
+
+        let mut state = ctx.accounts.dat_state.load_mut()?;
+        require!(state.is_active != 0 && state.emergency_pause == 0, ErrorCode::DATNotActive);
+
+        // Jito tip comes out of the allocation before the root split
+        let tip = pay_cycle_tip(
+            &state,
+            &ctx.accounts.cycle_state,
+            &ctx.accounts.dat_authority,
+            ctx.accounts.jito_tip_account.as_ref(),
+            &ctx.accounts.system_program.to_account_info(),
+            tip_lamports,
+            ctx.accounts.asdf_mint.key(),
+        )?;
+        let allocated_lamports = allocated_lamports.map(|a| a.saturating_sub(tip));
+
+        let bump = state.dat_authority_bump;
+        let fee_split_bps = state.fee_split_bps;
+        // Defensive check: fee_split_bps must be valid (1000-9000 range enforced by update_fee_split)
+        require!(fee_split_bps > 0 && fee_split_bps <= 10000, ErrorCode::InvalidFeeSplit);
+        let seeds: &[&[u8]] = &[DAT_AUTHORITY_SEED, &[bump]];
+
+        // Reserves follow the rent parameters read at collect time
+        let rent_reserve = ctx.accounts.cycle_state.rent_reserve();
+        let ata_reserve = ctx.accounts.cycle_state.ata_reserve();
+
+        // Calculate available and split to root
+        let available = allocated_lamports.unwrap_or(
+            state.spendable_lamports(ctx.accounts.dat_authority.lamports(), rent_reserve + SAFETY_BUFFER)
+        );
+        require!(available >= MIN_FEES_FOR_SPLIT, ErrorCode::InsufficientFees);
+
+        // CRITICAL-03 FIX: Root treasury is REQUIRED for secondary tokens
+        // Without this check, callers could pass root_treasury=None and skip the 44.8% fee split
+        require!(ctx.accounts.root_treasury.is_some(), ErrorCode::InvalidRootTreasury);
+
+        // The split must not run unless the buy that follows can also complete
+        require_compute_remaining(CU_PHASE_FEE_SPLIT + CU_PHASE_PUMPFUN_BUY)?;
+
+        // Execute split - SECURITY: Validate root_treasury PDA before transfer
+        if let Some(treasury) = &ctx.accounts.root_treasury {
+            // CRITICAL-01 FIX: Validate root_treasury is the correct PDA
+            let (expected_treasury, _bump) = Pubkey::find_program_address(
+                &[ROOT_TREASURY_SEED, root_mint.as_ref()],
+                ctx.program_id
+            );
+            require!(expected_treasury == *treasury.key, ErrorCode::InvalidRootTreasury);
+
+            // Streaming: accrue the root share (reserved in dat_authority) until flush_to_root
+            if let Some(stream) = ctx.accounts.root_stream.as_mut().filter(|s| s.is_streaming()) {
+                let accrued = root_share(available, fee_split_bps);
+                stream.accrue(accrued);
+                state.root_escrow_lamports = state.root_escrow_lamports.saturating_add(accrued);
+
+                emit!(RootShareAccrued {
+                    mint: stream.mint,
+                    amount: accrued,
+                    accrued_lamports: stream.accrued_lamports,
+                    cycles_accrued: stream.cycles_accrued,
+                    cycle_seq: ctx.accounts.cycle_state.sequence,
+                    timestamp: Clock::get()?.unix_timestamp,
+                });
+            } else {
+                let sol_for_root = split_fees_to_root(
+                    &ctx.accounts.dat_authority,
+                    treasury,
+                    &ctx.accounts.system_program,
+                    available,
+                    fee_split_bps,
+                    seeds,
+                )?;
+                if sol_for_root > 0 {
+                    state.last_sol_sent_to_root = sol_for_root;
+
+                    emit!(FeesRedirectedToRoot {
+                        from_token: ctx.accounts.asdf_mint.key(),
+                        to_root: root_mint,
+                        amount: sol_for_root,
+                        venue: VENUE_BONDING_CURVE,
+                        cycle_seq: ctx.accounts.cycle_state.sequence,
+                        timestamp: Clock::get()?.unix_timestamp,
+                    });
+                }
+            }
+        }
+
+        // Calculate remaining buy amount after split (accrued root shares stay reserved)
+        let buy_amount = match allocated_lamports {
+            Some(a) => ((a * fee_split_bps as u64) / 10000).saturating_sub(ata_reserve),
+            None => state.spendable_lamports(
+                ctx.accounts.dat_authority.lamports(),
+                rent_reserve + SAFETY_BUFFER + ata_reserve,
+            ),
+        };
+        require!(buy_amount >= MINIMUM_BUY_AMOUNT, ErrorCode::InsufficientFees);
+
+        let clock = Clock::get()?;
+        state.acquire_cycle_lock(ctx.accounts.asdf_mint.key())?;
+        drop(state);
+        ctx.accounts.cycle_state.begin_buy(false, VENUE_BONDING_CURVE, clock.unix_timestamp, clock.slot)?;
+
+        // Execute buy CPI (delegated to reduce stack)
+        execute_buy_secondary_cpi(&mut ctx, buy_amount, bump)
+    }
+
+    /// Execute buy on PumpSwap AMM pool (for migrated tokens)
+    /// This instruction handles tokens that have graduated from bonding curve to AMM
+    /// Requires WSOL in dat_wsol_account for the buy operation
+    ///
+    /// MEDIUM-01 FIX: Added slippage validation to ensure received tokens meet minimum threshold
+    pub fn execute_buy_amm(
+        ctx: Context<ExecuteBuyAMM>,
+        allocated_lamports: u64, // WSOL budget for this buy (capped by the DAT WSOL balance)
+    ) -> Result<()> {
+        // Check state conditions first (read-only)
+        {
+            let state = ctx.accounts.dat_state.load()?;
+            require!(state.is_active != 0 && state.emergency_pause == 0, ErrorCode::DATNotActive);
+        }
+        execute_buy_amm_inner(ctx.accounts, allocated_lamports)
+    }
+
+    /// PumpSwap AMM buy in a single instruction: wraps exactly `amount` lamports from
+    /// dat_authority into its WSOL ATA (created if missing), buys, then closes the ATA
+    /// so unspent WSOL and the account rent return to dat_authority as native SOL
+    pub fn execute_buy_amm_wrapped(ctx: Context<ExecuteBuyAMM>, amount: u64) -> Result<()> {
+        require!(ctx.accounts.dat_state.load()?.is_active != 0 && ctx.accounts.dat_state.load()?.emergency_pause == 0, ErrorCode::DATNotActive);
+        require!(amount > 0, ErrorCode::InsufficientFees);
+        require!(ctx.accounts.quote_mint.key() == WSOL_MINT, ErrorCode::PoolQuoteMintMismatch);
+        require!(
+            ctx.accounts.dat_wsol_account.key()
+                == anchor_spl::associated_token::get_associated_token_address(&ctx.accounts.dat_authority.key(), &WSOL_MINT),
+            ErrorCode::InvalidParameter
+        );
+
+        // The ATA rent is only borrowed: it comes back when the account is closed
+        let ata_rent = if ctx.accounts.dat_wsol_account.data_is_empty() {
+            ctx.accounts.cycle_state.ata_reserve()
+        } else {
+            0
+        };
+        let available = ctx.accounts.dat_state.load()?.spendable_lamports(
+            ctx.accounts.dat_authority.lamports(),
+            RENT_EXEMPT_MINIMUM + SAFETY_BUFFER + ata_rent,
+        );
+        require!(available >= amount, ErrorCode::InsufficientFees);
+
+        wrap_dat_wsol(ctx.accounts, amount)?;
+        execute_buy_amm_inner(ctx.accounts, amount)?;
+        let unspent = token_account_amount(&ctx.accounts.dat_wsol_account)?;
+        close_dat_wsol(ctx.accounts)?;
+
+        msg!("Wrapped AMM buy: {} lamports wrapped, {} unspent returned to DAT authority", amount, unspent);
+        Ok(())
+    }
+
+    /// Seed the token's PumpSwap pool with the cycle's liquidity share (admin only)
+    /// Runs between execute_buy_amm and burn_and_update, once per cycle: deposits at most
+    /// liquidity_bps of the bought tokens and of the cycle's SOL (kept as WSOL by the
+    /// orchestrator); deposited tokens leave pending_burn_amount, LP tokens stay with dat_authority
+    pub fn provide_liquidity(
+        ctx: Context<ProvideLiquidity>,
+        lp_token_amount_out: u64,
+        max_base_amount_in: u64,
+        max_quote_amount_in: u64,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let state = ctx.accounts.dat_state.load()?;
+        require!(state.is_active != 0 && state.emergency_pause == 0, ErrorCode::DATNotActive);
+        require!(lp_token_amount_out > 0, ErrorCode::InvalidParameter);
+        {
+            let token_stats = ctx.accounts.token_stats.load()?;
+            require!(token_stats.is_paused == 0, ErrorCode::TokenPaused);
+            require!(token_stats.liquidity_bps > 0, ErrorCode::LiquidityModeDisabled);
+            require!(
+                max_base_amount_in <= token_stats.liquidity_share(state.pending_burn_amount)
+                    && max_quote_amount_in <= token_stats.liquidity_share(state.last_cycle_sol),
+                ErrorCode::LiquidityExceedsShare
+            );
+        }
+        let bump = state.dat_authority_bump;
+        drop(state);
+        ctx.accounts.cycle_state.mark_liquidity_provided()?;
+
+        let base_before = ctx.accounts.dat_token_account.amount;
+        let quote_before = ctx.accounts.dat_wsol_account.amount;
+        let lp_before = ctx.accounts.dat_lp_token_account.amount;
+
+        execute_pumpswap_deposit_cpi(
+            ctx.accounts,
+            lp_token_amount_out,
+            max_base_amount_in,
+            max_quote_amount_in,
+            bump,
+        )?;
+
+        // NOTE: reload() required after CPI to get updated balances
+        ctx.accounts.dat_token_account.reload()?;
+        ctx.accounts.dat_wsol_account.reload()?;
+        ctx.accounts.dat_lp_token_account.reload()?;
+        let base_amount = base_before.saturating_sub(ctx.accounts.dat_token_account.amount);
+        let quote_amount = quote_before.saturating_sub(ctx.accounts.dat_wsol_account.amount);
+        let lp_tokens = ctx.accounts.dat_lp_token_account.amount.saturating_sub(lp_before);
+        require!(
+            base_amount <= max_base_amount_in && quote_amount <= max_quote_amount_in,
+            ErrorCode::LiquidityExceedsShare
+        );
+
+        // Deposited tokens are no longer burned by burn_and_update
+        let mut state = ctx.accounts.dat_state.load_mut()?;
+        state.pending_burn_amount = state.pending_burn_amount.saturating_sub(base_amount);
+
+        let mut token_stats = ctx.accounts.token_stats.load_mut()?;
+        token_stats.total_liquidity_sol = token_stats.total_liquidity_sol.saturating_add(quote_amount);
+
+        emit!(LiquidityProvided {
+            mint: token_stats.mint,
+            pool: ctx.accounts.pool.key(),
+            base_amount,
+            quote_amount,
+            lp_tokens,
+            total_liquidity_sol: token_stats.total_liquidity_sol,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Settle an ecosystem allocation against a token's pending fees (admin only)
+    /// Deducts exactly settled_amount instead of resetting, so fees attributed after the
+    /// allocation snapshot survive, partial allocations settle correctly, and a daemon
+    /// restarting mid-cycle only re-settles what it has not settled yet
+    pub fn settle_pending_fees(ctx: Context<SettlePendingFees>, mint: Pubkey, settled_amount: u64) -> Result<()> {
+        require!(settled_amount > 0, ErrorCode::InvalidParameter);
+
+        let mut stats = ctx.accounts.token_stats.load_mut()?;
+        let remaining_pending = stats.settle_pending_fees(settled_amount)?;
+        stats.cycles_participated = stats.cycles_participated.saturating_add(1);
+
+        emit!(PendingFeesSettled {
+            mint,
+            settled_amount,
+            remaining_pending,
+            cycles_participated: stats.cycles_participated,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    // Finalize allocated cycle - Reset pending_fees and increment cycles_participated
+    // Called by ecosystem orchestrator after execute_buy with allocated_lamports
+    // This is a separate lightweight instruction to avoid stack overflow
+    // actually_participated: bool - If true, reset pending_fees. If false (deferred), preserve them.
+    // NOTE: Blind reset - prefer settle_pending_fees, which deducts only the allocated amount
+    pub fn finalize_allocated_cycle(ctx: Context<FinalizeAllocatedCycle>, actually_participated: bool) -> Result<()> {
+        let mut stats = ctx.accounts.token_stats.load_mut()?;
+
+        if actually_participated {
+            // Token participated in this cycle - reset pending_fees
+            stats.reset_pending_fees();
+            stats.cycles_participated = stats.cycles_participated.saturating_add(1);
+            msg!("Finalized allocated cycle: pending_fees reset, cycles: {}", stats.cycles_participated);
+        } else {
+            // Token was deferred - preserve pending_fees for next cycle
+            msg!("Deferred finalization: pending_fees preserved ({} lamports) for next cycle",
+                stats.pending_fees_lamports);
+        }
+
+        // The cycle ends here for this mint (deferred, or bought and burned): free the lock
+        // its collect_fees took so the next mint can collect
+        if ctx.accounts.dat_state.load_mut()?.release_idle_cycle_lock(stats.mint) {
+            msg!("Cycle lock released");
+        }
+
+        Ok(())
+    }
+
+
+    pub fn burn_and_update(ctx: Context<BurnAndUpdate>) -> Result<()> {
+        let mut state = ctx.accounts.dat_state.load_mut()?;
+        let clock = Clock::get()?;
+
+        let (is_paused, burn_bps, reflection_bps) = {
+            let stats = ctx.accounts.token_stats.load()?;
+            (stats.is_paused, stats.burn_bps, stats.reflection_bps)
+        };
+        require!(state.pending_burn_amount > 0, ErrorCode::NoPendingBurn);
+        require!(is_paused == 0, ErrorCode::TokenPaused);
+        require_compute_remaining(CU_PHASE_BURN)?;
+        state.release_cycle_lock(ctx.accounts.asdf_mint.key())?;
+        ctx.accounts.cycle_state.complete_burn(clock.unix_timestamp, clock.slot)?;
+
+        // Split pending tokens: burn_bps burned, remainder to community treasury
+        // Reflection mode redirects reflection_bps of the burned share to holders
+        let (burn_share, treasury_amount) =
+            calculate_burn_split(state.pending_burn_amount, burn_bps)?;
+        let (tokens_to_burn, reflection_amount) =
+            calculate_reflection_split(state.pending_burn_amount, burn_share, reflection_bps)?;
+        let seeds = &[DAT_AUTHORITY_SEED, &[state.dat_authority_bump]];
+
+        if tokens_to_burn > 0 {
+            token_interface::burn(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    token_interface::Burn {
+                        mint: ctx.accounts.asdf_mint.to_account_info(),
+                        from: ctx.accounts.dat_asdf_account.to_account_info(),
+                        authority: ctx.accounts.dat_authority.to_account_info(),
+                    },
+                    &[seeds]
+                ),
+                tokens_to_burn
+            )?;
+
+            #[cfg(feature = "standard-burn-events")]
+            emit!(TokenBurn {
+                mint: ctx.accounts.asdf_mint.key(),
+                amount: tokens_to_burn,
+                authority: ctx.accounts.dat_authority.key(),
+                reference: ctx.accounts.token_stats.key(),
+            });
+        }
+
+        if treasury_amount > 0 {
+            let treasury = ctx.accounts.community_treasury
+                .as_mut()
+                .ok_or(ErrorCode::InvalidCommunityTreasury)?;
+            let treasury_before = treasury.amount;
+
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    token_interface::TransferChecked {
+                        from: ctx.accounts.dat_asdf_account.to_account_info(),
+                        mint: ctx.accounts.asdf_mint.to_account_info(),
+                        to: treasury.to_account_info(),
+                        authority: ctx.accounts.dat_authority.to_account_info(),
+                    },
+                    &[seeds]
+                ),
+                treasury_amount,
+                ctx.accounts.asdf_mint.decimals,
+            )?;
+
+            // Net of a Token2022 transfer fee (withheld in the treasury account)
+            treasury.reload()?;
+            let treasury_received = treasury.amount.saturating_sub(treasury_before);
+
+            let mut token_stats = ctx.accounts.token_stats.load_mut()?;
+            token_stats.total_sent_to_community_treasury =
+                token_stats.total_sent_to_community_treasury.saturating_add(treasury_received);
+
+            emit!(CommunityTreasuryFunded {
+                mint: token_stats.mint,
+                amount: treasury_received,
+                burn_bps,
+                total_sent: token_stats.total_sent_to_community_treasury,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+
+        if reflection_amount > 0 {
+            let rewards_token_account = ctx.accounts.rewards_token_account
+                .as_mut()
+                .ok_or(ErrorCode::InvalidRewardsVault)?;
+            let rewards_vault = ctx.accounts.rewards_vault
+                .as_mut()
+                .ok_or(ErrorCode::InvalidRewardsVault)?;
+            let rewards_before = rewards_token_account.amount;
+
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    token_interface::TransferChecked {
+                        from: ctx.accounts.dat_asdf_account.to_account_info(),
+                        mint: ctx.accounts.asdf_mint.to_account_info(),
+                        to: rewards_token_account.to_account_info(),
+                        authority: ctx.accounts.dat_authority.to_account_info(),
+                    },
+                    &[seeds]
+                ),
+                reflection_amount,
+                ctx.accounts.asdf_mint.decimals,
+            )?;
+
+            // Holders can only claim what arrived: accrue net of a Token2022 transfer fee
+            rewards_token_account.reload()?;
+            let reflected = rewards_token_account.amount.saturating_sub(rewards_before);
+            rewards_vault.accrue(reflected);
+
+            emit!(ReflectionFunded {
+                mint: rewards_vault.mint,
+                amount: reflected,
+                reflection_bps,
+                total_reflected: rewards_vault.total_reflected,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+
+        // Recent-cycle ring buffer (skipped until initialize_cycle_history)
+        if let Some(history) = ctx.accounts.cycle_history.as_mut() {
+            history.record(CycleRecord {
+                timestamp: clock.unix_timestamp,
+                sol_used: state.last_cycle_sol,
+                tokens_burned: tokens_to_burn,
+                venue: ctx.accounts.cycle_state.buy_venue,
+            });
+        }
+        if let Some(daily) = ctx.accounts.daily_stats.as_mut() {
+            daily.record(clock.unix_timestamp, 0, tokens_to_burn)?;
+        }
+
+        // Update per-token statistics
+        let mut token_stats = ctx.accounts.token_stats.load_mut()?;
+        token_stats.total_burned = token_stats.total_burned.saturating_add(tokens_to_burn);
+        token_stats.total_sol_used = token_stats.total_sol_used.saturating_add(state.last_cycle_sol);
+        token_stats.total_buybacks = token_stats.total_buybacks.saturating_add(1);
+        token_stats.last_cycle_timestamp = clock.unix_timestamp;
+        token_stats.last_cycle_sol = state.last_cycle_sol;
+        token_stats.last_cycle_burned = tokens_to_burn;
+
+        // USD accounting: value the burned share of the cycle's SOL at the oracle price
+        if let Some(oracle_account) = &ctx.accounts.price_oracle {
+            let oracle = parse_sol_usd_price(&oracle_account.try_borrow_data()?, clock.unix_timestamp)?;
+            let sol_value = (state.last_cycle_sol as u128)
+                .checked_mul(tokens_to_burn as u128)
+                .ok_or(ErrorCode::MathOverflow)?
+                .checked_div(state.pending_burn_amount as u128)
+                .ok_or(ErrorCode::MathOverflow)? as u64;
+            let usd_value = lamports_to_usd_micro(sol_value, &oracle)?;
+            token_stats.usd_value_burned = token_stats.usd_value_burned.saturating_add(usd_value);
+
+            emit!(BurnValued {
+                mint: token_stats.mint,
+                tokens_burned: tokens_to_burn,
+                sol_value,
+                usd_value,
+                total_usd_value_burned: token_stats.usd_value_burned,
+                oracle_price: oracle.price,
+                oracle_exponent: oracle.exponent,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+
+        // Update total_sol_sent_to_root if this was a secondary token cycle
+        if state.last_sol_sent_to_root > 0 {
+            token_stats.total_sol_sent_to_root =
+                token_stats.total_sol_sent_to_root.saturating_add(state.last_sol_sent_to_root);
+            msg!("Token stats updated: {} lamports sent to root (total: {})",
+                state.last_sol_sent_to_root,
+                token_stats.total_sol_sent_to_root);
+        }
+
+        // One-time refund of the initializer's rent, funded by dat_authority
+        // Deferred (not failed) when the payer account is omitted or dat_authority is short
+        if tokens_to_burn > 0 && token_stats.payer_refund_due() {
+            if let (Some(payer), Some(system_program)) =
+                (&ctx.accounts.stats_payer, &ctx.accounts.system_program)
+            {
+                let refund = Rent::get()?.minimum_balance(8 + TokenStats::LEN);
+                let available = state.spendable_lamports(ctx.accounts.dat_authority.lamports(), PAYER_REFUND_RESERVE);
+
+                if available >= refund {
+                    invoke_signed(
+                        &anchor_lang::solana_program::system_instruction::transfer(
+                            ctx.accounts.dat_authority.key,
+                            payer.key,
+                            refund
+                        ),
+                        &[
+                            ctx.accounts.dat_authority.to_account_info(),
+                            payer.to_account_info(),
+                            system_program.to_account_info()
+                        ],
+                        &[seeds]
+                    )?;
+                    token_stats.payer_refunded = 1;
+
+                    emit!(StatsPayerRefunded {
+                        mint: token_stats.mint,
+                        payer: payer.key(),
+                        amount: refund,
+                        timestamp: clock.unix_timestamp,
+                    });
+                }
+            }
+        }
+
+        // Keeper reward: only registered, active crankers signing with their keeper_info earn it
+        if let (Some(keeper), Some(keeper_info)) = (&ctx.accounts.keeper, ctx.accounts.keeper_info.as_mut()) {
+            reward_keeper(
+                &state,
+                keeper_info,
+                &keeper.to_account_info(),
+                &ctx.accounts.dat_authority,
+                ctx.accounts.system_program.as_deref(),
+                token_stats.mint,
+                clock.unix_timestamp,
+            )?;
+        }
+
+        // Update global state and reset tracking variables
+        state.last_cycle_burned = tokens_to_burn;
+        state.consecutive_failures = 0;
+        token_stats.consecutive_failures = 0;
+        state.pending_burn_amount = 0;
+        state.last_sol_sent_to_root = 0;  // Reset for next cycle
+
+        let (whole, frac) = format_tokens(tokens_to_burn);
+        msg!("Epoch #{} complete: {}.{:06} tokens burned ({} units)",
+            token_stats.total_buybacks, whole, frac, tokens_to_burn);
+
+        emit!(CycleCompleted {
+            mint: token_stats.mint,
+            venue: ctx.accounts.cycle_state.buy_venue,
+            cycle_seq: ctx.accounts.cycle_state.sequence,
+            cycle_number: token_stats.total_buybacks as u32,
+            tokens_burned: tokens_to_burn,
+            sol_used: state.last_cycle_sol,
+            total_burned: token_stats.total_burned,
+            total_sol_collected: token_stats.total_sol_collected,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Burn-adjusted circulating supply (permissionless view)
+    /// circulating = mint supply - community treasury - pending burn - rebate pool
+    /// Returned via return data with every input included for transparency
+    pub fn view_circulating_supply(ctx: Context<ViewCirculatingSupply>) -> Result<CirculatingSupply> {
+        let balance = |acc: &Option<InterfaceAccount<TokenAccount>>| acc.as_ref().map_or(0, |a| a.amount);
+
+        let total_supply = ctx.accounts.mint.supply;
+        let community_treasury = balance(&ctx.accounts.community_treasury);
+        let pending_burn = balance(&ctx.accounts.dat_token_account);
+        let rebate_pool = balance(&ctx.accounts.rebate_pool_ata);
+
+        Ok(CirculatingSupply {
+            mint: ctx.accounts.mint.key(),
+            total_supply,
+            total_burned: ctx.accounts.token_stats.load()?.total_burned,
+            community_treasury,
+            pending_burn,
+            rebate_pool,
+            circulating: CirculatingSupply::compute(total_supply, community_treasury, pending_burn, rebate_pool),
+            slot: Clock::get()?.slot,
+        })
+    }
+
+    /// Best-execution venue selector (permissionless pre-instruction)
+    /// Quotes sol_in on the residual bonding curve and every PumpSwap pool passed in
+    /// remaining_accounts, and returns the venue yielding the most tokens. The caller
+    /// routes the buy to execute_buy* (bonding curve) or execute_buy_amm (pool) accordingly
+    pub fn select_best_venue<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SelectBestVenue<'info>>,
+        sol_in: u64,
+    ) -> Result<VenueSelection> {
+        require!(sol_in >= MINIMUM_BUY_AMOUNT, ErrorCode::InsufficientFees);
+        require!(ctx.remaining_accounts.chunks_exact(3).remainder().is_empty(), ErrorCode::InvalidParameter);
+        require!(ctx.remaining_accounts.len() / 3 <= MAX_VENUE_POOLS, ErrorCode::InvalidParameter);
+
+        let mint = ctx.accounts.mint.key();
+        let mut quotes: Vec<VenueQuote> = Vec::with_capacity(1 + MAX_VENUE_POOLS);
+
+        if let Some(curve) = &ctx.accounts.bonding_curve {
+            let (expected_curve, _) = Pubkey::find_program_address(
+                &[PUMP_BONDING_CURVE_SEED, mint.as_ref()],
+                &PUMP_PROGRAM
+            );
+            require!(curve.key() == expected_curve, ErrorCode::InvalidBondingCurve);
+
+            if let Some(quote) = quote_bonding_curve(curve.key(), &curve.try_borrow_data()?, sol_in)? {
+                quotes.push(quote);
+            }
+        }
+
+        for accounts in ctx.remaining_accounts.chunks(3) {
+            let pool = &accounts[0];
+            require!(pool.owner == &PUMP_SWAP_PROGRAM, ErrorCode::InvalidPool);
+
+            let info = parse_pumpswap_pool(&pool.try_borrow_data()?)?;
+            require!(info.base_mint == mint, ErrorCode::MintMismatch);
+            require!(accounts[1].key() == info.pool_base_token_account, ErrorCode::InvalidPool);
+            require!(accounts[2].key() == info.pool_quote_token_account, ErrorCode::InvalidPool);
+
+            let base_vault = InterfaceAccount::<TokenAccount>::try_from(&accounts[1])?;
+            let quote_vault = InterfaceAccount::<TokenAccount>::try_from(&accounts[2])?;
+
+            quotes.push(quote_pumpswap_pool(
+                pool.key(),
+                base_vault.amount,
+                quote_vault.amount,
+                ctx.accounts.mint.supply,
+                sol_in,
+            )?);
+        }
+
+        let best = best_venue_quote(&quotes).ok_or(ErrorCode::NoVenueAvailable)?;
+        let clock = Clock::get()?;
+
+        emit!(VenueSelected {
+            mint,
+            sol_in,
+            venue: best.venue,
+            venue_account: best.account,
+            tokens_out: best.tokens_out,
+            venues_quoted: quotes.len() as u8,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(VenueSelection {
+            mint,
+            sol_in,
+            venue: best.venue,
+            venue_account: best.account,
+            tokens_out: best.tokens_out,
+            venues_quoted: quotes.len() as u8,
+            slot: clock.slot,
+        })
+    }
+
+    /// Bonding curve buy quote (permissionless view)
+    /// Runs the same calculate_buy_amount_and_slippage as execute_buy against the live curve,
+    /// including the root token's price circuit breaker, so clients never re-implement it
+    pub fn quote_buy(ctx: Context<QuoteBuy>, lamports: u64) -> Result<BuyQuote> {
+        let state = ctx.accounts.dat_state.load()?;
+        let mint = ctx.accounts.mint.key();
+
+        let (last_price, max_deviation) = if state.root_token_mint() == Some(mint) {
+            (state.last_known_price, state.max_price_deviation_bps)
+        } else {
+            (0, 0)
+        };
+
+        let curve_data = ctx.accounts.bonding_curve.try_borrow_data()?;
+        let (max_sol_cost, expected_tokens, execution_price) = calculate_buy_amount_and_slippage(
+            lamports, &curve_data, state.max_fees_per_cycle, state.slippage_bps, last_price, max_deviation,
+        )?;
+        let price_impact_bps = bonding_curve_price_impact_bps(max_sol_cost, &curve_data)?;
+
+        Ok(BuyQuote {
+            mint,
+            lamports,
+            max_sol_cost,
+            expected_tokens,
+            price_impact_bps,
+            execution_price,
+            slot: Clock::get()?.slot,
+        })
+    }
+
+    /// Create the community treasury token PDA for a mint (admin only)
+    /// Required before setting a burn ratio below 100% for that token
+    pub fn initialize_community_treasury(ctx: Context<InitializeCommunityTreasury>) -> Result<()> {
+        msg!("Community treasury initialized for mint {}: {}",
+            ctx.accounts.mint.key(),
+            ctx.accounts.community_treasury.key());
+        Ok(())
+    }
+
+    /// Create a mint's holder reflection vault (admin only, required before reflection_bps > 0 burns)
+    pub fn initialize_rewards_vault(ctx: Context<InitializeRewardsVault>) -> Result<()> {
+        let vault = &mut ctx.accounts.rewards_vault;
+        vault.mint = ctx.accounts.mint.key();
+        vault.epoch = 0;
+        vault.merkle_root = [0u8; 32];
+        vault.epoch_amount = 0;
+        vault.epoch_claimed = 0;
+        vault.unallocated = 0;
+        vault.total_reflected = 0;
+        vault.total_claimed = 0;
+        vault.bump = ctx.bumps.rewards_vault;
+
+        msg!("Rewards vault initialized for mint {}: {}",
+            vault.mint,
+            ctx.accounts.rewards_token_account.key());
+        Ok(())
+    }
+
+    /// Post a holder snapshot allocating `amount` reflected tokens (admin only)
+    /// Leaves: reflection_leaf_hash(epoch, holder, amount), computed off-chain pro-rata
+    /// to holder balances; the previous epoch's unclaimed remainder rolls over
+    pub fn post_reflection_snapshot(
+        ctx: Context<PostReflectionSnapshot>,
+        merkle_root: [u8; 32],
+        amount: u64,
+    ) -> Result<()> {
+        let vault = &mut ctx.accounts.rewards_vault;
+        vault.post_snapshot(merkle_root, amount)?;
+
+        emit!(ReflectionSnapshotPosted {
+            mint: vault.mint,
+            epoch: vault.epoch,
+            merkle_root,
+            amount,
+            unallocated: vault.unallocated,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Claim a holder's allocation from the current reflection snapshot (once per epoch)
+    pub fn claim_reflection(
+        ctx: Context<ClaimReflection>,
+        amount: u64,
+        index: u32,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let holder = ctx.accounts.holder.key();
+        let vault = &mut ctx.accounts.rewards_vault;
+        let epoch = vault.epoch;
+        let leaf = reflection_leaf_hash(epoch, &holder, amount);
+        require!(
+            verify_merkle_proof(&vault.merkle_root, leaf, index, &proof),
+            ErrorCode::InvalidMerkleProof
+        );
+        vault.claim(amount)?;
+
+        let bump = ctx.accounts.dat_state.load()?.dat_authority_bump;
+        let seeds: &[&[u8]] = &[DAT_AUTHORITY_SEED, &[bump]];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.rewards_token_account.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.holder_token_account.to_account_info(),
+                    authority: ctx.accounts.dat_authority.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+            ctx.accounts.mint.decimals,
+        )?;
+
+        let claim_record = &mut ctx.accounts.claim_record;
+        claim_record.holder = holder;
+        claim_record.epoch = epoch;
+        claim_record.amount = amount;
+        claim_record.bump = ctx.bumps.claim_record;
+
+        emit!(ReflectionClaimed {
+            mint: ctx.accounts.mint.key(),
+            epoch,
+            holder,
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Rotate a mint's DAT token account after a compromise (admin only)
+    /// Creates a fresh PDA token account, moves the full balance, revokes any
+    /// delegate on the old account and points TokenStats at the new account
+    pub fn rotate_token_account(ctx: Context<RotateTokenAccount>, nonce: u8) -> Result<()> {
+        let bump = ctx.accounts.dat_state.load()?.dat_authority_bump;
+        let seeds: &[&[u8]] = &[DAT_AUTHORITY_SEED, &[bump]];
+        let amount = ctx.accounts.old_token_account.amount;
+
+        // Move balance: old → new
+        if amount > 0 {
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    token_interface::TransferChecked {
+                        from: ctx.accounts.old_token_account.to_account_info(),
+                        mint: ctx.accounts.mint.to_account_info(),
+                        to: ctx.accounts.new_token_account.to_account_info(),
+                        authority: ctx.accounts.dat_authority.to_account_info(),
+                    },
+                    &[seeds],
+                ),
+                amount,
+                ctx.accounts.mint.decimals,
+            )?;
+        }
+
+        // Revoke any delegate left on the old account
+        token_interface::revoke(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::Revoke {
+                    source: ctx.accounts.old_token_account.to_account_info(),
+                    authority: ctx.accounts.dat_authority.to_account_info(),
+                },
+                &[seeds],
+            ),
+        )?;
+
+        let mut token_stats = ctx.accounts.token_stats.load_mut()?;
+        token_stats.dat_token_account = ctx.accounts.new_token_account.key();
+        token_stats.token_account_nonce = nonce;
+
+        emit!(TokenAccountRotated {
+            mint: ctx.accounts.mint.key(),
+            old_account: ctx.accounts.old_token_account.key(),
+            new_account: ctx.accounts.new_token_account.key(),
+            amount_moved: amount,
+            nonce,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("DAT token account rotated: {} tokens moved to {}",
+            amount, ctx.accounts.new_token_account.key());
+
+        Ok(())
+    }
+
+    /// Set the per-token burn ratio (admin only)
+    /// burn_and_update burns burn_bps of pending tokens, remainder goes to community treasury
+    /// Bounded between MIN_BURN_BPS (50%) and 10000 (100% burn)
+    /// Timelocked: queues the change for execute_change after admin_operation_cooldown
+    pub fn set_burn_ratio(ctx: Context<SetBurnRatio>, new_burn_bps: u16) -> Result<()> {
+        // Partial burns need an initialized community treasury to receive the remainder
+        require!(
+            new_burn_bps == 10000 || ctx.accounts.community_treasury.is_some(),
+            ErrorCode::InvalidCommunityTreasury
+        );
+
+        let token_stats = ctx.accounts.token_stats.load()?;
+        TokenStats::clone(&token_stats).apply_parameter(ADMIN_OP_BURN_RATIO, new_burn_bps as u64)?;
+        let state = ctx.accounts.dat_state.load()?;
+        let delay = state.admin_operation_cooldown;
+        queue_change(&mut ctx.accounts.change_queue, &state, ADMIN_OP_BURN_RATIO, token_stats.mint, new_burn_bps as u64, delay)?;
+        Ok(())
+    }
+
+    /// Set the share of each buyback reflected to holders instead of burned (admin only)
+    /// Taken out of the burned share, so it can never exceed burn_bps; 0 disables reflection
+    /// Timelocked: queues the change for execute_change after admin_operation_cooldown
+    pub fn set_reflection_ratio(ctx: Context<SetReflectionRatio>, new_reflection_bps: u16) -> Result<()> {
+        let token_stats = ctx.accounts.token_stats.load()?;
+        TokenStats::clone(&token_stats).apply_parameter(ADMIN_OP_REFLECTION_RATIO, new_reflection_bps as u64)?;
+        let state = ctx.accounts.dat_state.load()?;
+        let delay = state.admin_operation_cooldown;
+        queue_change(&mut ctx.accounts.change_queue, &state, ADMIN_OP_REFLECTION_RATIO, token_stats.mint, new_reflection_bps as u64, delay)?;
+        Ok(())
+    }
+
+    /// Set the share of each cycle provide_liquidity may deposit into the PumpSwap pool
+    /// (admin only, 0 disables liquidity seeding)
+    /// Timelocked: queues the change for execute_change after admin_operation_cooldown
+    pub fn set_liquidity_ratio(ctx: Context<SetLiquidityRatio>, new_liquidity_bps: u16) -> Result<()> {
+        let token_stats = ctx.accounts.token_stats.load()?;
+        TokenStats::clone(&token_stats).apply_parameter(ADMIN_OP_LIQUIDITY_RATIO, new_liquidity_bps as u64)?;
+        let state = ctx.accounts.dat_state.load()?;
+        let delay = state.admin_operation_cooldown;
+        queue_change(&mut ctx.accounts.change_queue, &state, ADMIN_OP_LIQUIDITY_RATIO, token_stats.mint, new_liquidity_bps as u64, delay)?;
+        Ok(())
+    }
+
+    /// Set the per-token dust threshold (admin only, 0 disables sweep_dust)
+    /// Timelocked: queues the change for execute_change after admin_operation_cooldown
+    pub fn set_dust_threshold(ctx: Context<SetDustThreshold>, threshold: u64) -> Result<()> {
+        let token_stats = ctx.accounts.token_stats.load()?;
+        TokenStats::clone(&token_stats).apply_parameter(ADMIN_OP_DUST_THRESHOLD, threshold)?;
+        let state = ctx.accounts.dat_state.load()?;
+        let delay = state.admin_operation_cooldown;
+        queue_change(&mut ctx.accounts.change_queue, &state, ADMIN_OP_DUST_THRESHOLD, token_stats.mint, threshold, delay)?;
+        Ok(())
+    }
+
+    /// Burn a residual DAT token balance left by buy safety margins (permissionless)
+    /// Only between cycles (no buy or burn in flight for the mint) and only when the
+    /// whole balance is within token_stats.dust_threshold
+    pub fn sweep_dust(ctx: Context<SweepDust>, mint: Pubkey) -> Result<()> {
+        require!(ctx.accounts.cycle_state.is_between_cycles(), ErrorCode::InvalidCyclePhase);
+        require!(ctx.accounts.dat_state.load()?.cycle_lock() != Some(mint), ErrorCode::CycleLocked);
+        let mut stats = ctx.accounts.token_stats.load_mut()?;
+        require!(stats.is_paused == 0, ErrorCode::TokenPaused);
+
+        let amount = stats.dust_sweep_amount(ctx.accounts.dat_token_account.amount)?;
+
+        let seeds: &[&[u8]] = &[DAT_AUTHORITY_SEED, &[ctx.accounts.dat_state.load()?.dat_authority_bump]];
+        token_interface::burn(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::Burn {
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    from: ctx.accounts.dat_token_account.to_account_info(),
+                    authority: ctx.accounts.dat_authority.to_account_info(),
+                },
+                &[seeds]
+            ),
+            amount
+        )?;
+
+        #[cfg(feature = "standard-burn-events")]
+        emit!(TokenBurn {
+            mint,
+            amount,
+            authority: ctx.accounts.dat_authority.key(),
+            reference: ctx.accounts.token_stats.key(),
+        });
+
+        stats.dust_burned = stats.dust_burned.saturating_add(amount);
+        stats.total_burned = stats.total_burned.saturating_add(amount);
+        {
+            let mut state = ctx.accounts.dat_state.load_mut()?;
+            state.total_burned = state.total_burned.saturating_add(amount);
+        }
+
+        emit!(DustSwept {
+            mint,
+            amount,
+            dust_burned: stats.dust_burned,
+            swept_by: ctx.accounts.caller.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Commit the Merkle root of all TokenStats for a day (admin only, once per day)
+    /// Leaves: stats_leaf_hash() of each TokenStats, ordered by mint bytes ascending
+    pub fn commit_daily_snapshot(
+        ctx: Context<CommitDailySnapshot>,
+        day: u64,
+        merkle_root: [u8; 32],
+        token_count: u32,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let today = (clock.unix_timestamp / SECONDS_PER_DAY) as u64;
+        require!(day <= today, ErrorCode::InvalidSnapshotDay);
+        require!(token_count > 0, ErrorCode::InvalidParameter);
+
+        let snapshot = &mut ctx.accounts.stats_snapshot;
+        snapshot.day = day;
+        snapshot.merkle_root = merkle_root;
+        snapshot.token_count = token_count;
+        snapshot.committer = ctx.accounts.admin.key();
+        snapshot.committed_at = clock.unix_timestamp;
+        snapshot.bump = ctx.bumps.stats_snapshot;
+
+        emit!(DailySnapshotCommitted {
+            day,
+            merkle_root,
+            token_count,
+            committer: snapshot.committer,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Verify a TokenStats leaf is included in a daily snapshot (permissionless)
+    /// Fails with InvalidMerkleProof otherwise; usable via CPI or simulation
+    pub fn verify_snapshot_inclusion(ctx: Context<VerifySnapshotInclusion>, proof: StatsLeafProof) -> Result<()> {
+        ctx.accounts.stats_snapshot.verify_inclusion(&proof)
+    }
+
+    /// Recompute ecosystem aggregates into GlobalStats (admin only)
+    /// remaining_accounts: TokenStats, strictly ascending by mint across all batches
+    /// begin = true resets the accumulator; finalize = true commits it and emits the checksum
+    pub fn rollup_global_stats<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RollupGlobalStats<'info>>,
+        begin: bool,
+        finalize: bool,
+    ) -> Result<()> {
+        let global = &mut ctx.accounts.global_stats;
+        global.bump = ctx.bumps.global_stats;
+        if begin {
+            global.begin();
+        }
+
+        for info in ctx.remaining_accounts.iter() {
+            // AccountLoader::try_from checks owner == program and the TokenStats discriminator
+            let stats: AccountLoader<'info, TokenStats> = AccountLoader::try_from(info)?;
+            global.accumulate(&*stats.load()?)?;
+        }
+
+        if finalize {
+            let now = Clock::get()?.unix_timestamp;
+            global.commit(now)?;
+            emit!(GlobalStatsRolledUp {
+                token_count: global.token_count,
+                total_burned: global.total_burned,
+                total_sol_collected: global.total_sol_collected,
+                total_sol_used: global.total_sol_used,
+                total_buybacks: global.total_buybacks,
+                usd_value_burned: global.usd_value_burned,
+                checksum: global.checksum,
+                timestamp: now,
+            });
+        }
+        Ok(())
+    }
+
+    /// On-chain accounting tripwire (permissionless): fails with an Invariant* error naming
+    /// the first broken invariant across the TokenStats in remaining_accounts (ascending mint)
+    ///
+    /// Checks every token's counters (TokenStats::check_invariants), that the batch used no
+    /// more SOL than it collected, and that dat_authority (above its rent floor) plus the
+    /// creator vault cover all pending fee attributions.
+    pub fn verify_invariants<'info>(
+        ctx: Context<'_, '_, 'info, 'info, VerifyInvariants<'info>>,
+    ) -> Result<()> {
+        let mut tokens_checked: u32 = 0;
+        let mut last_mint: Option<Pubkey> = None;
+        let mut pending: u128 = 0;
+        let mut collected: u128 = 0;
+        let mut used: u128 = 0;
+
+        for info in ctx.remaining_accounts.iter() {
+            // AccountLoader::try_from checks owner == program and the TokenStats discriminator
+            let loader: AccountLoader<'info, TokenStats> = AccountLoader::try_from(info)?;
+            let stats = loader.load()?;
+            // Ascending order rules out passing a token twice to inflate the sums
+            require!(
+                last_mint.is_none_or(|prev| stats.mint > prev),
+                ErrorCode::InvariantTokensOutOfOrder
+            );
+            last_mint = Some(stats.mint);
+
+            if let Err(err) = stats.check_invariants() {
+                msg!("Invariant broken for mint {}", stats.mint);
+                return Err(err);
+            }
+            pending += stats.pending_fees_lamports as u128;
+            collected += stats.total_sol_collected as u128;
+            used += stats.total_sol_used as u128;
+            tokens_checked += 1;
+        }
+
+        require!(used <= collected, ErrorCode::InvariantSolUsedExceedsCollected);
+
+        let authority = &ctx.accounts.dat_authority;
+        let floor = Rent::get()?.minimum_balance(authority.data_len());
+        let backing = (authority.lamports().saturating_sub(floor) as u128)
+            + ctx.accounts.creator_vault.lamports() as u128;
+        if backing < pending {
+            msg!("Pending fees {} exceed backing {}", pending, backing);
+            return err!(ErrorCode::InvariantPendingFeesUnbacked);
+        }
+
+        emit!(InvariantsVerified {
+            tokens_checked,
+            pending_fees_lamports: pending.min(u64::MAX as u128) as u64,
+            backing_lamports: backing.min(u64::MAX as u128) as u64,
+            total_sol_collected: collected.min(u64::MAX as u128) as u64,
+            total_sol_used: used.min(u64::MAX as u128) as u64,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Propose a token's listing partner revenue share (admin only, subject to timelock)
+    /// partner_share_bps = 0 proposes removing the current partner (partner is ignored)
+    pub fn propose_token_partner(
+        ctx: Context<ProposeTokenPartner>,
+        partner: Pubkey,
+        partner_share_bps: u16,
+    ) -> Result<()> {
+        require!(partner_share_bps <= MAX_PARTNER_SHARE_BPS, ErrorCode::PartnerShareTooHigh);
+
+        let clock = Clock::get()?;
+        let mut token_stats = ctx.accounts.token_stats.load_mut()?;
+
+        // Removal keeps the current partner pending so apply loads its PartnerStats
+        let partner = if partner_share_bps == 0 { token_stats.partner } else { partner };
+        require!(partner != Pubkey::default(), ErrorCode::InvalidParameter);
+
+        token_stats.pending_partner = partner;
+        token_stats.pending_partner_share_bps = partner_share_bps;
+        token_stats.pending_partner_timestamp = clock.unix_timestamp;
+
+        emit!(PartnerShareProposed {
+            mint: token_stats.mint,
+            partner,
+            partner_share_bps,
+            executable_at: clock.unix_timestamp.saturating_add(ctx.accounts.dat_state.load()?.admin_operation_cooldown),
+        });
+        Ok(())
+    }
+
+    /// Apply a proposed partner revenue share (admin only, after cooldown period)
+    pub fn apply_token_partner(ctx: Context<ApplyTokenPartner>) -> Result<()> {
+        let mut token_stats = ctx.accounts.token_stats.load_mut()?;
+        let clock = Clock::get()?;
+
+        require!(token_stats.pending_partner_timestamp != 0, ErrorCode::NoPendingPartnerChange);
+        ctx.accounts.dat_state.load()?.require_current_epoch(token_stats.pending_partner_timestamp)?;
+
+        let elapsed = clock.unix_timestamp.saturating_sub(token_stats.pending_partner_timestamp);
+        require!(
+            elapsed >= ctx.accounts.dat_state.load()?.admin_operation_cooldown,
+            ErrorCode::CycleTooSoon // Reusing existing error for timelock
+        );
+
+        let old_partner = token_stats.partner;
+        let old_share_bps = token_stats.partner_share_bps;
+
+        token_stats.partner = if token_stats.pending_partner_share_bps == 0 {
+            Pubkey::default()
+        } else {
+            token_stats.pending_partner
+        };
+        token_stats.partner_share_bps = token_stats.pending_partner_share_bps;
+        token_stats.pending_partner = Pubkey::default();
+        token_stats.pending_partner_share_bps = 0;
+        token_stats.pending_partner_timestamp = 0;
+
+        // Initialize a newly created PartnerStats
+        let partner_stats = &mut ctx.accounts.partner_stats;
+        if partner_stats.partner == Pubkey::default() {
+            partner_stats.partner = token_stats.partner;
+            partner_stats.total_paid = 0;
+            partner_stats.payouts_count = 0;
+            partner_stats.last_paid_at = 0;
+            partner_stats.bump = ctx.bumps.partner_stats;
+        }
+
+        emit!(PartnerShareUpdated {
+            mint: token_stats.mint,
+            old_partner,
+            new_partner: token_stats.partner,
+            old_share_bps,
+            new_share_bps: token_stats.partner_share_bps,
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Configure operational alert thresholds (admin only, 0 disables an alert)
+    /// Creates the AlertConfig with every alert disabled on first use
+    /// Timelocked: queues the three thresholds for execute_change after admin_operation_cooldown
+    pub fn configure_alerts(
+        ctx: Context<ConfigureAlerts>,
+        min_authority_balance: u64,
+        max_consecutive_failures: u8,
+        min_rebate_pool_balance: u64,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.alert_config;
+        config.bump = ctx.bumps.alert_config;
+        let mut preview = AlertConfig::clone(config);
+
+        let state = ctx.accounts.dat_state.load()?;
+        let delay = state.admin_operation_cooldown;
+        let queue = &mut ctx.accounts.change_queue;
+        for (param, value) in [
+            (ADMIN_OP_ALERT_MIN_AUTHORITY_BALANCE, min_authority_balance),
+            (ADMIN_OP_ALERT_MAX_CONSECUTIVE_FAILURES, max_consecutive_failures as u64),
+            (ADMIN_OP_ALERT_MIN_REBATE_POOL_BALANCE, min_rebate_pool_balance),
+        ] {
+            preview.apply_parameter(param, value)?;
+            queue_change(queue, &state, param, Pubkey::default(), value, delay)?;
+        }
+        Ok(())
+    }
+
+    /// Check alert thresholds and emit AlertRaised for each breach (permissionless)
+    /// Returns the number of alerts raised via return data
+    pub fn check_alerts(ctx: Context<CheckAlerts>) -> Result<u8> {
+        let clock = Clock::get()?;
+        let breaches = ctx.accounts.alert_config.breaches(
+            ctx.accounts.dat_authority.lamports(),
+            ctx.accounts.dat_state.load()?.consecutive_failures,
+            ctx.accounts.rebate_pool_ata.as_ref().map(|ata| ata.amount),
+        );
+
+        for (kind, value, threshold) in breaches.iter() {
+            emit!(AlertRaised {
+                kind: *kind,
+                value: *value,
+                threshold: *threshold,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+
+        Ok(breaches.len() as u8)
+    }
+
+    /// Set a token's ecosystem page / manifest URI (admin only, empty string clears it)
+    pub fn set_token_info_uri(ctx: Context<SetTokenInfoUri>, info_uri: String) -> Result<()> {
+        let encoded = TokenConfig::encode_uri(&info_uri).ok_or(ErrorCode::InfoUriTooLong)?;
+        let config = &mut ctx.accounts.token_config;
+        let clock = Clock::get()?;
+
+        config.mint = ctx.accounts.token_stats.load()?.mint;
+        config.info_uri = encoded;
+        config.updated_at = clock.unix_timestamp;
+        config.bump = ctx.bumps.token_config;
+        config.version = TOKEN_CONFIG_VERSION;
+
+        emit!(TokenInfoUriSet {
+            mint: config.mint,
+            info_uri,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Ecosystem directory entry for a token (permissionless view)
+    /// Returned via return data; info_uri is empty when no TokenConfig was set
+    pub fn view_token_info(ctx: Context<ViewTokenInfo>) -> Result<TokenInfo> {
+        let stats = ctx.accounts.token_stats.load()?;
+
+        Ok(TokenInfo {
+            mint: stats.mint,
+            info_uri: ctx.accounts.token_config.as_ref().map_or(String::new(), |c| c.uri()),
+            is_root_token: stats.is_root_token != 0,
+            category: stats.category,
+            is_paused: stats.is_paused != 0,
+            burn_bps: stats.burn_bps,
+            total_burned: stats.total_burned,
+            total_sol_collected: stats.total_sol_collected,
+            usd_value_burned: stats.usd_value_burned,
+        })
+    }
+
+    /// TokenStats in a migration-stable layout (permissionless view)
+    /// Returned via return data so clients simulate instead of decoding the account
+    pub fn get_token_stats(ctx: Context<GetTokenStats>) -> Result<TokenStatsView> {
+        Ok(TokenStatsView::from_stats(&*ctx.accounts.token_stats.load()?))
+    }
+
+    /// Next cycle step for a mint (permissionless view)
+    /// buy_amount mirrors a standalone execute_buy (no allocation) at the current balance
+    pub fn get_cycle_preview(ctx: Context<GetCyclePreview>) -> Result<CyclePreview> {
+        let state = ctx.accounts.dat_state.load()?;
+        let stats = ctx.accounts.token_stats.load()?;
+        let cycle = &ctx.accounts.cycle_state;
+        let clock = Clock::get()?;
+
+        let can_collect = stats.is_paused == 0
+            && cycle.phase != CyclePhase::Bought
+            && state.collect_allowed(&stats.mint, clock.unix_timestamp, !TESTING_MODE);
+        let buy_amount = state.spendable_lamports(
+            ctx.accounts.dat_authority.lamports(),
+            cycle.rent_reserve() + SAFETY_BUFFER,
+        );
+
+        Ok(CyclePreview {
+            mint: stats.mint,
+            phase: cycle.phase,
+            sequence: cycle.sequence,
+            cycle_lock: state.cycle_lock(),
+            can_collect,
+            next_cycle_at: state.next_cycle_at(),
+            pending_fees_lamports: stats.pending_fees_lamports,
+            creator_vault_lamports: ctx.accounts.creator_vault.lamports(),
+            buy_amount,
+            slot: clock.slot,
+        })
+    }
+
+    /// A user's pending rebate and whether claim_rebate would pay it now (permissionless view)
+    pub fn get_pending_rebate(ctx: Context<GetPendingRebate>) -> Result<PendingRebate> {
+        let pool = &ctx.accounts.rebate_pool;
+        let user_stats = &ctx.accounts.user_stats;
+
+        let pending = user_stats.pending_contribution;
+        let rebate_threshold = pool.rebate_threshold();
+        let rebate_amount = pool.rebate_amount(pending)?;
+        let claimable = pending >= rebate_threshold
+            && pool.available(ctx.accounts.rebate_pool_ata.amount) >= rebate_amount;
+
+        Ok(PendingRebate {
+            user: user_stats.user,
+            pending_contribution: pending,
+            rebate_threshold,
+            rebate_amount,
+            claimable,
+            contribution_epoch: user_stats.contribution_epoch,
+            epoch_contribution: user_stats.epoch_contribution,
+        })
+    }
+
+    /// Tag a token with an operational category (admin only, 0 = uncategorized)
+    pub fn set_token_category(ctx: Context<SetTokenCategory>, category: u8) -> Result<()> {
+        let mut token_stats = ctx.accounts.token_stats.load_mut()?;
+        let old_category = token_stats.category;
+        token_stats.category = category;
+
+        emit!(TokenCategorySet {
+            mint: token_stats.mint,
+            old_category,
+            new_category: category,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Pause every token of a category (admin only)
+    /// remaining_accounts: writable TokenStats of the category's tokens
+    /// Blocks collect_fees, collect_fees_amm and burn_and_update for those tokens
+    pub fn pause_category<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CategoryControl<'info>>,
+        category: u8,
+    ) -> Result<()> {
+        let tokens_updated = set_category_paused(ctx.remaining_accounts, category, true)?;
+        emit!(CategoryPauseChanged {
+            category,
+            paused: true,
+            tokens_updated,
+            admin: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Resume every token of a category (admin only)
+    /// remaining_accounts: writable TokenStats of the category's tokens
+    pub fn resume_category<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CategoryControl<'info>>,
+        category: u8,
+    ) -> Result<()> {
+        let tokens_updated = set_category_paused(ctx.remaining_accounts, category, false)?;
+        emit!(CategoryPauseChanged {
+            category,
+            paused: false,
+            tokens_updated,
+            admin: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// With token_stats, the failure is isolated to that token: it pauses alone after
+    /// TOKEN_PAUSE_FAILURE_THRESHOLD consecutive failures, and the global pause only trips
+    /// when several tokens fail within FAILURE_ESCALATION_WINDOW
+    pub fn record_failure(ctx: Context<RecordFailure>, error_code: u32) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let mut state = ctx.accounts.dat_state.load_mut()?;
+        let cycle = ctx.accounts.cycle_state.as_ref();
+
+        let consecutive_failures = match ctx.accounts.token_stats.as_ref() {
+            Some(loader) => {
+                let mut stats = loader.load_mut()?;
+                if let Some(cycle) = cycle {
+                    require!(cycle.mint == stats.mint, ErrorCode::MintMismatch);
+                }
+                let previous_failure = stats.last_failure_timestamp;
+                if stats.record_failure(now) {
+                    emit!(TokenAutoPaused {
+                        mint: stats.mint,
+                        consecutive_failures: stats.consecutive_failures,
+                        timestamp: now,
+                    });
+                }
+                if state.record_token_failure(previous_failure, now) {
+                    emit!(FailureEscalated {
+                        failing_tokens: state.failing_tokens_in_window,
+                        window_start: state.failure_window_start,
+                        timestamp: now,
+                    });
+                }
+                stats.consecutive_failures
+            }
+            None => {
+                state.record_cycle_failure(now);
+                state.consecutive_failures
+            }
+        };
+
+        // A failed buy reverts without releasing the lock its collect_fees took
+        let stats_mint = match ctx.accounts.token_stats.as_ref() {
+            Some(loader) => Some(loader.load()?.mint),
+            None => None,
+        };
+        let failed_mint = stats_mint.or(cycle.map(|c| c.mint));
+        if let Some(mint) = failed_mint {
+            if state.release_idle_cycle_lock(mint) {
+                msg!("Cycle lock released");
+            }
+        }
+
+        emit!(CycleFailed {
+            mint: cycle.map_or(Pubkey::default(), |c| c.mint),
+            venue: cycle.map_or(0, |c| c.buy_venue),
+            cycle_seq: cycle.map_or(0, |c| c.sequence),
+            failed_count: state.failed_cycles,
+            consecutive_failures,
+            error_code,
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    /// Lift a single token's pause (admin only), e.g. after record_failure isolated it
+    pub fn resume_token(ctx: Context<ResumeToken>) -> Result<()> {
+        let mut stats = ctx.accounts.token_stats.load_mut()?;
+        stats.is_paused = 0;
+        stats.consecutive_failures = 0;
+        emit!(TokenResumed {
+            mint: stats.mint,
+            admin: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// ADMIN ONLY - Release a cycle lock left behind by a crashed orchestrator
+    /// Escape hatch: prefer abort_cycle, which also resets the mint's CycleState
+    pub fn force_unlock(ctx: Context<AdminControl>) -> Result<()> {
+        let mut state = ctx.accounts.dat_state.load_mut()?;
+        let mint = state.cycle_lock().ok_or(ErrorCode::CycleLockNotHeld)?;
+        state.cycle_lock = Pubkey::default();
+        log_admin_op(&mut ctx.accounts.admin_log, ADMIN_OP_FORCE_UNLOCK, mint, 0, 0, ctx.accounts.admin.key())?;
+
+        emit!(CycleLockForceReleased {
+            mint,
+            admin: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn emergency_pause(ctx: Context<AdminControl>) -> Result<()> {
+        let mut state = ctx.accounts.dat_state.load_mut()?;
+        state.emergency_pause = 1;
+        state.is_active = 0;
+        // A manual pause is never lifted by try_resume
+        state.auto_paused_at = 0;
+        log_admin_op(&mut ctx.accounts.admin_log, ADMIN_OP_PAUSE, Pubkey::default(), 0, 0, ctx.accounts.admin.key())?;
+        emit!(EmergencyAction {
+            action: "PAUSE".to_string(),
+            admin: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn resume(ctx: Context<AdminControl>) -> Result<()> {
+        let mut state = ctx.accounts.dat_state.load_mut()?;
+        state.emergency_pause = 0;
+        state.is_active = 1;
+        state.consecutive_failures = 0;
+        state.auto_paused_at = 0;
+        log_admin_op(&mut ctx.accounts.admin_log, ADMIN_OP_RESUME, Pubkey::default(), 0, 0, ctx.accounts.admin.key())?;
+        emit!(StatusChanged {
+            is_active: true,
+            emergency_pause: false,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Set the cool-off after which try_resume may lift an automatic pause (admin only, 0 = disabled)
+    /// Timelocked: queues the change for execute_change after admin_operation_cooldown
+    pub fn set_auto_resume_after(ctx: Context<ChangeQueueAdmin>, auto_resume_after: i64) -> Result<()> {
+        require!(auto_resume_after >= 0, ErrorCode::InvalidParameter);
+        let mut preview = DATState::clone(&*ctx.accounts.dat_state.load()?);
+        let delay = preview.admin_operation_cooldown;
+        queue_parameter_change(&mut ctx.accounts.change_queue, &mut preview, ADMIN_OP_AUTO_RESUME_AFTER, auto_resume_after as u64, delay)?;
+        Ok(())
+    }
+
+    /// Set the consecutive-failure count that trips the automatic pause (admin only)
+    /// Bounded to MIN_/MAX_AUTO_PAUSE_FAILURE_THRESHOLD
+    /// Timelocked: queues the change for execute_change after admin_operation_cooldown
+    pub fn update_failure_threshold(ctx: Context<ChangeQueueAdmin>, threshold: u8) -> Result<()> {
+        let mut preview = DATState::clone(&*ctx.accounts.dat_state.load()?);
+        let delay = preview.admin_operation_cooldown;
+        queue_parameter_change(&mut ctx.accounts.change_queue, &mut preview, ADMIN_OP_FAILURE_THRESHOLD, threshold as u64, delay)?;
+        Ok(())
+    }
+
+    /// Set the share of collected creator-vault SOL kept in the ReserveVault (admin only, 0 = disabled)
+    /// Bounded to MAX_RESERVE_BPS
+    /// Timelocked: queues the change for execute_change after admin_operation_cooldown
+    pub fn set_reserve_ratio(ctx: Context<ChangeQueueAdmin>, reserve_bps: u16) -> Result<()> {
+        let mut preview = DATState::clone(&*ctx.accounts.dat_state.load()?);
+        let delay = preview.admin_operation_cooldown;
+        queue_parameter_change(&mut ctx.accounts.change_queue, &mut preview, ADMIN_OP_RESERVE_BPS, reserve_bps as u64, delay)?;
+        Ok(())
+    }
+
+    /// Set the share of collected creator-vault SOL deposited into the InsuranceFund (admin only, 0 = disabled)
+    /// Bounded to MAX_INSURANCE_BPS
+    /// Timelocked: queues the change for execute_change after admin_operation_cooldown
+    pub fn set_insurance_ratio(ctx: Context<ChangeQueueAdmin>, insurance_bps: u16) -> Result<()> {
+        let mut preview = DATState::clone(&*ctx.accounts.dat_state.load()?);
+        let delay = preview.admin_operation_cooldown;
+        queue_parameter_change(&mut ctx.accounts.change_queue, &mut preview, ADMIN_OP_INSURANCE_BPS, insurance_bps as u64, delay)?;
+        Ok(())
+    }
+
+    /// Require collections to land inside the mint's VRF-settled CycleWindow (admin only)
+    /// Timelocked: queues the change for execute_change after admin_operation_cooldown
+    pub fn set_randomized_timing(ctx: Context<ChangeQueueAdmin>, enabled: bool) -> Result<()> {
+        let mut preview = DATState::clone(&*ctx.accounts.dat_state.load()?);
+        let delay = preview.admin_operation_cooldown;
+        queue_parameter_change(&mut ctx.accounts.change_queue, &mut preview, ADMIN_OP_RANDOMIZED_TIMING, enabled as u64, delay)?;
+        Ok(())
+    }
+
+    /// Set the largest Jito tip execute_buy / execute_buy_secondary may pay (admin only, 0 = disabled)
+    /// Bounded to MAX_JITO_TIP_LAMPORTS
+    /// Timelocked: queues the change for execute_change after admin_operation_cooldown
+    pub fn set_max_jito_tip(ctx: Context<ChangeQueueAdmin>, max_tip_lamports: u64) -> Result<()> {
+        let mut preview = DATState::clone(&*ctx.accounts.dat_state.load()?);
+        let delay = preview.admin_operation_cooldown;
+        queue_parameter_change(&mut ctx.accounts.change_queue, &mut preview, ADMIN_OP_MAX_JITO_TIP, max_tip_lamports, delay)?;
+        Ok(())
+    }
+
+    /// Create the parameter change queue and name its veto guardian (admin only)
+    pub fn initialize_change_queue(ctx: Context<InitializeChangeQueue>) -> Result<()> {
+        let guardian = ctx.accounts.guardian.key();
+        require!(guardian != ctx.accounts.admin.key(), ErrorCode::InvalidParameter);
+
+        let queue = &mut ctx.accounts.change_queue;
+        queue.guardian = guardian;
+        queue.next_id = 0;
+        queue.changes = [PendingChange::default(); MAX_PENDING_CHANGES];
+        queue.bump = ctx.bumps.change_queue;
+        queue.version = CHANGE_QUEUE_VERSION;
+
+        emit!(ChangeQueueInitialized {
+            change_queue: queue.key(),
+            guardian,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Queue a parameter change (ADMIN_OP_* tag) with its own delay (admin only)
+    /// delay must be within [admin_operation_cooldown, MAX_CHANGE_DELAY]
+    pub fn propose_change(ctx: Context<ChangeQueueAdmin>, param: u8, value: u64, delay: i64) -> Result<()> {
+        let mut preview = DATState::clone(&*ctx.accounts.dat_state.load()?);
+        queue_parameter_change(&mut ctx.accounts.change_queue, &mut preview, param, value, delay)?;
+        Ok(())
+    }
+
+    /// Apply a queued parameter change once its delay elapsed (admin only)
+    /// Changes outside DATState need their target account (see ChangeTarget)
+    pub fn execute_change(ctx: Context<ChangeQueueAdmin>, id: u64) -> Result<()> {
+        let clock = Clock::get()?;
+        let queue = &mut ctx.accounts.change_queue;
+        let mut state = ctx.accounts.dat_state.load_mut()?;
+
+        let change = queue.take_due(id, clock.unix_timestamp)?;
+        state.require_current_epoch(change.proposed_at)?;
+        let old_value = match ChangeTarget::of(change.param) {
+            ChangeTarget::DatState => state.apply_parameter(change.param, change.value)?,
+            ChangeTarget::TokenStats => {
+                let mut token_stats = ctx.accounts.token_stats.as_ref().ok_or(ErrorCode::ChangeTargetMissing)?.load_mut()?;
+                require_keys_eq!(token_stats.mint, change.subject, ErrorCode::ChangeTargetMismatch);
+                token_stats.apply_parameter(change.param, change.value)?
+            }
+            ChangeTarget::ValidatorState => {
+                let validator = ctx.accounts.validator_state.as_mut().ok_or(ErrorCode::ChangeTargetMissing)?;
+                require_keys_eq!(validator.mint, change.subject, ErrorCode::ChangeTargetMismatch);
+                validator.apply_parameter(change.param, change.value)?
+            }
+            ChangeTarget::RebatePool => ctx.accounts.rebate_pool.as_mut()
+                .ok_or(ErrorCode::ChangeTargetMissing)?
+                .apply_parameter(change.param, change.value)?,
+            ChangeTarget::AlertConfig => {
+                let config = ctx.accounts.alert_config.as_mut().ok_or(ErrorCode::ChangeTargetMissing)?;
+                config.updated_at = clock.unix_timestamp;
+                config.apply_parameter(change.param, change.value)?
+            }
+        };
+        log_admin_op(
+            &mut ctx.accounts.admin_log,
+            change.param,
+            change.subject,
+            old_value,
+            change.value,
+            ctx.accounts.admin.key(),
+        )?;
+
+        // Setter-specific events predate the queue; indexers keep receiving them
+        match change.param {
+            ADMIN_OP_AUTO_RESUME_AFTER => emit!(AutoResumeAfterUpdated {
+                old_auto_resume_after: old_value as i64,
+                new_auto_resume_after: change.value as i64,
+                timestamp: clock.unix_timestamp,
+            }),
+            ADMIN_OP_FAILURE_THRESHOLD => emit!(FailureThresholdUpdated {
+                old_threshold: old_value as u8,
+                new_threshold: change.value as u8,
+                timestamp: clock.unix_timestamp,
+            }),
+            ADMIN_OP_DEV_FEE => emit!(DevFeeUpdated {
+                old_bps: old_value as u16,
+                new_bps: change.value as u16,
+                timestamp: clock.unix_timestamp,
+            }),
+            ADMIN_OP_PRICE_GUARD => emit!(PriceGuardUpdated {
+                old_bps: old_value as u16,
+                new_bps: change.value as u16,
+                observation_reset: false,
+                timestamp: clock.unix_timestamp,
+            }),
+            ADMIN_OP_PRICE_OBSERVATION_RESET => emit!(PriceGuardUpdated {
+                old_bps: state.max_price_deviation_bps,
+                new_bps: state.max_price_deviation_bps,
+                observation_reset: true,
+                timestamp: clock.unix_timestamp,
+            }),
+            ADMIN_OP_FEE_SPLIT => emit!(FeeSplitUpdated {
+                old_bps: old_value as u16,
+                new_bps: change.value as u16,
+                timestamp: clock.unix_timestamp,
+            }),
+            ADMIN_OP_BURN_RATIO => emit!(BurnRatioUpdated {
+                mint: change.subject,
+                old_bps: old_value as u16,
+                new_bps: change.value as u16,
+                timestamp: clock.unix_timestamp,
+            }),
+            ADMIN_OP_REFLECTION_RATIO => emit!(ReflectionRatioUpdated {
+                mint: change.subject,
+                old_bps: old_value as u16,
+                new_bps: change.value as u16,
+                timestamp: clock.unix_timestamp,
+            }),
+            ADMIN_OP_LIQUIDITY_RATIO => emit!(LiquidityRatioUpdated {
+                mint: change.subject,
+                old_bps: old_value as u16,
+                new_bps: change.value as u16,
+                timestamp: clock.unix_timestamp,
+            }),
+            ADMIN_OP_DUST_THRESHOLD => emit!(DustThresholdUpdated {
+                mint: change.subject,
+                old_threshold: old_value,
+                new_threshold: change.value,
+                timestamp: clock.unix_timestamp,
+            }),
+            ADMIN_OP_VALIDATOR_MAX_FEE | ADMIN_OP_VALIDATOR_MAX_TX => {
+                let validator = ctx.accounts.validator_state.as_ref().ok_or(ErrorCode::ChangeTargetMissing)?;
+                let fee_changed = change.param == ADMIN_OP_VALIDATOR_MAX_FEE;
+                emit!(ValidatorLimitsUpdated {
+                    mint: change.subject,
+                    old_max_fee_per_slot: if fee_changed { old_value } else { validator.fee_cap_per_slot() },
+                    new_max_fee_per_slot: validator.fee_cap_per_slot(),
+                    old_max_tx_per_slot: if fee_changed { validator.tx_cap_per_slot() } else { old_value as u32 },
+                    new_max_tx_per_slot: validator.tx_cap_per_slot(),
+                    timestamp: clock.unix_timestamp,
+                });
+            }
+            ADMIN_OP_REFERRAL_SHARE => emit!(ReferralShareUpdated {
+                old_bps: old_value as u16,
+                new_bps: change.value as u16,
+                timestamp: clock.unix_timestamp,
+            }),
+            ADMIN_OP_ONBOARDING_BONUS_BPS | ADMIN_OP_ONBOARDING_BONUS_CAP | ADMIN_OP_ONBOARDING_BONUS_BUDGET => {
+                let rebate_pool = ctx.accounts.rebate_pool.as_ref().ok_or(ErrorCode::ChangeTargetMissing)?;
+                emit!(OnboardingBonusConfigured {
+                    bonus_bps: rebate_pool.bonus_bps,
+                    bonus_cap: rebate_pool.bonus_cap,
+                    bonus_budget: rebate_pool.bonus_budget,
+                    timestamp: clock.unix_timestamp,
+                });
+            }
+            ADMIN_OP_ALERT_MIN_AUTHORITY_BALANCE
+            | ADMIN_OP_ALERT_MAX_CONSECUTIVE_FAILURES
+            | ADMIN_OP_ALERT_MIN_REBATE_POOL_BALANCE => {
+                let config = ctx.accounts.alert_config.as_ref().ok_or(ErrorCode::ChangeTargetMissing)?;
+                emit!(AlertsConfigured {
+                    min_authority_balance: config.min_authority_balance,
+                    max_consecutive_failures: config.max_consecutive_failures,
+                    min_rebate_pool_balance: config.min_rebate_pool_balance,
+                    timestamp: clock.unix_timestamp,
+                });
+            }
+            _ => {}
+        }
+
+        emit!(ParameterChangeExecuted {
+            id,
+            param: change.param,
+            subject: change.subject,
+            old_value,
+            new_value: change.value,
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Drop a queued parameter change (admin only)
+    pub fn cancel_change(ctx: Context<ChangeQueueAdmin>, id: u64) -> Result<()> {
+        let change = ctx.accounts.change_queue.remove(id)?;
+        emit!(ParameterChangeCancelled {
+            id,
+            param: change.param,
+            cancelled_by: ctx.accounts.admin.key(),
+            vetoed: false,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Veto a queued parameter change (guardian only)
+    pub fn veto_change(ctx: Context<GuardianControl>, id: u64) -> Result<()> {
+        let change = ctx.accounts.change_queue.remove(id)?;
+        emit!(ParameterChangeCancelled {
+            id,
+            param: change.param,
+            cancelled_by: ctx.accounts.guardian.key(),
+            vetoed: true,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Configure community fee split voting (admin only)
+    /// quorum: minimum escrowed $ASDF; voting_period within MIN_/MAX_VOTING_PERIOD;
+    /// execution_delay: timelock after voting (at least admin_operation_cooldown)
+    pub fn initialize_governance(
+        ctx: Context<InitializeGovernance>,
+        quorum: u64,
+        voting_period: i64,
+        execution_delay: i64,
+    ) -> Result<()> {
+        require!(quorum > 0, ErrorCode::InvalidParameter);
+        require!(
+            (MIN_VOTING_PERIOD..=MAX_VOTING_PERIOD).contains(&voting_period),
+            ErrorCode::InvalidParameter
+        );
+        require!(
+            execution_delay >= ctx.accounts.dat_state.load()?.admin_operation_cooldown
+                && execution_delay <= MAX_CHANGE_DELAY,
+            ErrorCode::InvalidParameter
+        );
+
+        let governance = &mut ctx.accounts.governance;
+        governance.next_proposal_id = 0;
+        governance.quorum = quorum;
+        governance.voting_period = voting_period;
+        governance.execution_delay = execution_delay;
+        governance.bump = ctx.bumps.governance;
+
+        emit!(GovernanceInitialized {
+            quorum,
+            voting_period,
+            execution_delay,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Propose a new fee split for community vote (permissionless, proposer funds rent)
+    pub fn create_proposal(ctx: Context<CreateProposal>, new_fee_split_bps: u16) -> Result<()> {
+        require!(
+            (1000..=9000).contains(&new_fee_split_bps),
+            ErrorCode::InvalidFeeSplit
+        );
+        let clock = Clock::get()?;
+        let governance = &mut ctx.accounts.governance;
+        let id = governance.next_proposal_id;
+        governance.next_proposal_id = id.saturating_add(1);
+
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.id = id;
+        proposal.proposer = ctx.accounts.proposer.key();
+        proposal.new_fee_split_bps = new_fee_split_bps;
+        proposal.created_at = clock.unix_timestamp;
+        proposal.voting_ends_at = clock.unix_timestamp.saturating_add(governance.voting_period);
+        proposal.votes_for = 0;
+        proposal.votes_against = 0;
+        proposal.executed = false;
+        proposal.bump = ctx.bumps.proposal;
+
+        emit!(FeeSplitProposalCreated {
+            id,
+            proposal: proposal.key(),
+            proposer: proposal.proposer,
+            new_fee_split_bps,
+            voting_ends_at: proposal.voting_ends_at,
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Vote on a fee split proposal by escrowing `amount` $ASDF until voting ends
+    /// Vote weight = escrowed amount + a stake locked through the vote;
+    /// reclaim_vote returns the escrowed tokens afterwards
+    pub fn cast_vote(ctx: Context<CastVote>, support: bool, amount: u64) -> Result<()> {
+        let clock = Clock::get()?;
+        let staked_weight = ctx.accounts.stake_account.as_ref()
+            .map_or(0, |s| s.vote_weight(ctx.accounts.proposal.voting_ends_at));
+        let weight = amount.checked_add(staked_weight).ok_or(ErrorCode::MathOverflow)?;
+        ctx.accounts.proposal.record_vote(support, weight, clock.unix_timestamp)?;
+
+        if amount > 0 {
+            token_interface::transfer_checked(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    token_interface::TransferChecked {
+                        from: ctx.accounts.voter_token_account.to_account_info(),
+                        mint: ctx.accounts.asdf_mint.to_account_info(),
+                        to: ctx.accounts.vote_vault.to_account_info(),
+                        authority: ctx.accounts.voter.to_account_info(),
+                    },
+                ),
+                amount,
+                ctx.accounts.asdf_mint.decimals,
+            )?;
+        }
+
+        let vote_record = &mut ctx.accounts.vote_record;
+        vote_record.proposal = ctx.accounts.proposal.key();
+        vote_record.voter = ctx.accounts.voter.key();
+        vote_record.amount = amount;
+        vote_record.support = support;
+        vote_record.bump = ctx.bumps.vote_record;
+
+        emit!(VoteCast {
+            proposal: vote_record.proposal,
+            voter: vote_record.voter,
+            support,
+            weight,
+            votes_for: ctx.accounts.proposal.votes_for,
+            votes_against: ctx.accounts.proposal.votes_against,
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Return a voter's escrowed $ASDF once voting ended and close the ballot
+    pub fn reclaim_vote(ctx: Context<ReclaimVote>) -> Result<()> {
+        let clock = Clock::get()?;
+        require!(
+            clock.unix_timestamp >= ctx.accounts.proposal.voting_ends_at,
+            ErrorCode::VotingNotEnded
+        );
+
+        let amount = ctx.accounts.vote_record.amount;
+        let bump = ctx.accounts.dat_state.load()?.dat_authority_bump;
+        let seeds: &[&[u8]] = &[DAT_AUTHORITY_SEED, &[bump]];
+
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.vote_vault.to_account_info(),
+                    mint: ctx.accounts.asdf_mint.to_account_info(),
+                    to: ctx.accounts.voter_token_account.to_account_info(),
+                    authority: ctx.accounts.dat_authority.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+            ctx.accounts.asdf_mint.decimals,
+        )?;
+
+        emit!(VoteReclaimed {
+            proposal: ctx.accounts.proposal.key(),
+            voter: ctx.accounts.voter.key(),
+            amount,
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Apply a fee split proposal that reached quorum and majority, after the
+    /// governance timelock (permissionless)
+    pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
+        let clock = Clock::get()?;
+        let governance = &ctx.accounts.governance;
+        let proposal = &mut ctx.accounts.proposal;
+        let mut state = ctx.accounts.dat_state.load_mut()?;
+
+        proposal.require_executable(governance.quorum, governance.execution_delay, clock.unix_timestamp)?;
+        state.require_current_epoch(proposal.created_at)?;
+
+        let old_bps = state.fee_split_bps;
+        state.fee_split_bps = proposal.new_fee_split_bps;
+        proposal.executed = true;
+
+        emit!(FeeSplitUpdated {
+            old_bps,
+            new_bps: proposal.new_fee_split_bps,
+            timestamp: clock.unix_timestamp,
+        });
+        emit!(FeeSplitProposalExecuted {
+            id: proposal.id,
+            proposal: proposal.key(),
+            old_bps,
+            new_bps: proposal.new_fee_split_bps,
+            votes_for: proposal.votes_for,
+            votes_against: proposal.votes_against,
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Lock `amount` $ASDF for at least `lock_duration` seconds (owner funds rent)
+    /// While locked the stake boosts rebates and adds to governance vote weight
+    pub fn stake(ctx: Context<Stake>, amount: u64, lock_duration: i64) -> Result<()> {
+        let clock = Clock::get()?;
+        let stake_account = &mut ctx.accounts.stake_account;
+        stake_account.deposit(amount, lock_duration, clock.unix_timestamp)?;
+        stake_account.owner = ctx.accounts.owner.key();
+        stake_account.bump = ctx.bumps.stake_account;
+
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.owner_token_account.to_account_info(),
+                    mint: ctx.accounts.asdf_mint.to_account_info(),
+                    to: ctx.accounts.stake_vault.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            amount,
+            ctx.accounts.asdf_mint.decimals,
+        )?;
+
+        emit!(Staked {
+            owner: stake_account.owner,
+            amount,
+            total_staked: stake_account.amount,
+            locked_until: stake_account.locked_until,
+            lock_duration: stake_account.lock_duration,
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Re-lock a stake for `lock_duration` seconds from now (never shortens the lock)
+    pub fn extend_stake(ctx: Context<ExtendStake>, lock_duration: i64) -> Result<()> {
+        let clock = Clock::get()?;
+        let stake_account = &mut ctx.accounts.stake_account;
+        stake_account.extend(lock_duration, clock.unix_timestamp)?;
+
+        emit!(StakeExtended {
+            owner: stake_account.owner,
+            locked_until: stake_account.locked_until,
+            lock_duration: stake_account.lock_duration,
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Withdraw the whole stake once its lock expired
+    pub fn unstake(ctx: Context<Unstake>) -> Result<()> {
+        let clock = Clock::get()?;
+        let amount = ctx.accounts.stake_account.withdraw(clock.unix_timestamp)?;
+        let bump = ctx.accounts.dat_state.load()?.dat_authority_bump;
+        let seeds: &[&[u8]] = &[DAT_AUTHORITY_SEED, &[bump]];
+
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.stake_vault.to_account_info(),
+                    mint: ctx.accounts.asdf_mint.to_account_info(),
+                    to: ctx.accounts.owner_token_account.to_account_info(),
+                    authority: ctx.accounts.dat_authority.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+            ctx.accounts.asdf_mint.decimals,
+        )?;
+
+        emit!(Unstaked {
+            owner: ctx.accounts.owner.key(),
+            amount,
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Hand the veto role to a new guardian (current guardian only)
+    pub fn transfer_guardian(ctx: Context<GuardianControl>, new_guardian: Pubkey) -> Result<()> {
+        require!(new_guardian != Pubkey::default(), ErrorCode::InvalidParameter);
+        let queue = &mut ctx.accounts.change_queue;
+        let old_guardian = std::mem::replace(&mut queue.guardian, new_guardian);
+
+        emit!(GuardianTransferred {
+            old_guardian,
+            new_guardian,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Lift an automatic pause once auto_resume_after has elapsed (permissionless)
+    /// Health probe: the root pool must still hold MIN_POOL_LIQUIDITY of SOL.
+    /// Pauses set by emergency_pause are never lifted here
+    pub fn try_resume(ctx: Context<TryResume>) -> Result<()> {
+        let clock = Clock::get()?;
+        ctx.accounts.dat_state.load()?.require_auto_resumable(clock.unix_timestamp)?;
+
+        let pool = &ctx.accounts.pool;
+        let pool_sol_reserves = if pool.owner == &PUMP_PROGRAM {
+            let data = pool.try_borrow_data()?;
+            require!(data.len() >= 32, ErrorCode::InvalidPool);
+            deserialize_bonding_curve(&data[8..])?.1
+        } else {
+            require!(pool.owner == &PUMP_SWAP_PROGRAM, ErrorCode::InvalidPool);
+            let info = parse_pumpswap_pool(&pool.try_borrow_data()?)?;
+            let vault = ctx.accounts.pool_quote_token_account.as_ref().ok_or(ErrorCode::InvalidPool)?;
+            require!(vault.key() == info.pool_quote_token_account, ErrorCode::InvalidPool);
+            vault.amount
+        };
+        require!(pool_sol_reserves >= MIN_POOL_LIQUIDITY, ErrorCode::InsufficientPoolLiquidity);
+
+        let mut state = ctx.accounts.dat_state.load_mut()?;
+        let paused_at = state.auto_paused_at;
+        state.emergency_pause = 0;
+        state.consecutive_failures = 0;
+        state.auto_paused_at = 0;
+
+        emit!(AutoResumed {
+            paused_at,
+            pool_sol_reserves,
+            caller: ctx.accounts.caller.key(),
+            timestamp: clock.unix_timestamp,
+        });
+        emit!(StatusChanged {
+            is_active: state.is_active != 0,
+            emergency_pause: false,
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Open a new governance epoch after a program upgrade (admin only)
+    /// Once per deployment: ProgramData's slot must be newer than the last acknowledged one.
+    /// Timelocked proposals from earlier epochs can no longer execute
+    pub fn acknowledge_upgrade(ctx: Context<AcknowledgeUpgrade>) -> Result<()> {
+        let clock = Clock::get()?;
+        let program_slot = ctx.accounts.program_data.slot;
+        let epoch = ctx.accounts.dat_state.load_mut()?.advance_governance_epoch(program_slot, clock.unix_timestamp)?;
+
+        emit!(GovernanceEpochAdvanced {
+            epoch,
+            program_slot,
+            admin: ctx.accounts.admin.key(),
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Restore dat_authority to its rent floor plus AUTHORITY_TOP_UP_HEADROOM (permissionless)
+    /// The donation is exactly the missing delta, so the instruction cannot be used to park SOL
+    pub fn top_up_authority(ctx: Context<TopUpAuthority>) -> Result<()> {
+        use anchor_lang::solana_program::program::invoke;
+
+        let authority = &ctx.accounts.dat_authority;
+        let floor = Rent::get()?.minimum_balance(authority.data_len());
+        let amount = authority_top_up_amount(authority.lamports(), floor);
+        require!(amount > 0, ErrorCode::AuthorityAboveFloor);
+
+        invoke(
+            &anchor_lang::solana_program::system_instruction::transfer(ctx.accounts.donor.key, authority.key, amount),
+            &[
+                ctx.accounts.donor.to_account_info(),
+                authority.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+        )?;
+
+        emit!(AuthorityToppedUp {
+            donor: ctx.accounts.donor.key(),
+            amount,
+            balance: ctx.accounts.dat_authority.lamports(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Timelocked: queues one change per provided value for execute_change after
+    /// admin_operation_cooldown (bounds checked by DATState::apply_parameter)
+    pub fn update_parameters(
+        ctx: Context<ChangeQueueAdmin>,
+        new_min_fees: Option<u64>,
+        new_max_fees: Option<u64>,
+        new_slippage_bps: Option<u16>,
+        new_min_interval: Option<i64>,
+    ) -> Result<()> {
+        let mut preview = DATState::clone(&*ctx.accounts.dat_state.load()?);
+        let delay = preview.admin_operation_cooldown;
+        let queue = &mut ctx.accounts.change_queue;
+
+        if let Some(v) = new_slippage_bps {
+            queue_parameter_change(queue, &mut preview, ADMIN_OP_SLIPPAGE, v as u64, delay)?;
+        }
+        if let Some(v) = new_min_interval {
+            require!(v > 0, ErrorCode::InvalidParameter);
+            queue_parameter_change(queue, &mut preview, ADMIN_OP_MIN_INTERVAL, v as u64, delay)?;
+        }
+        // Queue the fee bounds in an order that keeps min <= max after each change
+        // (a raised max goes first), so executing in id order always succeeds
+        let raise_max_first = new_max_fees.is_some_and(|max| max >= preview.max_fees_per_cycle);
+        let fee_changes = if raise_max_first {
+            [(ADMIN_OP_MAX_FEES, new_max_fees), (ADMIN_OP_MIN_FEES, new_min_fees)]
+        } else {
+            [(ADMIN_OP_MIN_FEES, new_min_fees), (ADMIN_OP_MAX_FEES, new_max_fees)]
+        };
+        for (param, value) in fee_changes {
+            if let Some(v) = value {
+                queue_parameter_change(queue, &mut preview, param, v, delay)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Export the portable DATState configuration for disaster recovery (permissionless)
+    /// Emits the serialized PortableConfig as ConfigExported chunks sharing one sha256 hash
+    pub fn export_config(ctx: Context<ExportConfig>) -> Result<()> {
+        use anchor_lang::solana_program::hash::hash;
+
+        let blob = PortableConfig::from_state(&*ctx.accounts.dat_state.load()?).encode()?;
+        let config_hash = hash(&blob).to_bytes();
+        let chunk_count = blob.len().div_ceil(CONFIG_EXPORT_CHUNK_SIZE) as u8;
+        let timestamp = Clock::get()?.unix_timestamp;
+
+        for (index, chunk) in blob.chunks(CONFIG_EXPORT_CHUNK_SIZE).enumerate() {
+            emit!(ConfigExported {
+                version: PortableConfig::VERSION,
+                chunk_index: index as u8,
+                chunk_count,
+                config_hash,
+                data: chunk.to_vec(),
+                timestamp,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Queue a blob from export_config for import_config (admin only)
+    /// Governance gate: the DAT must be emergency paused, nothing else may be queued,
+    /// and the blob passes the same bounds as the individual setters. Its hash is held
+    /// in DATState behind an ADMIN_OP_IMPORT_CONFIG change the guardian can veto
+    pub fn propose_import_config(ctx: Context<ChangeQueueAdmin>, blob: Vec<u8>) -> Result<()> {
+        use anchor_lang::solana_program::hash::hash;
+
+        let mut state = ctx.accounts.dat_state.load_mut()?;
+        let queue = &mut ctx.accounts.change_queue;
+        require!(state.emergency_pause != 0, ErrorCode::ImportRequiresPause);
+        require!(queue.pending_count() == 0, ErrorCode::ImportQueueNotEmpty);
+
+        let config = PortableConfig::decode(&blob)?;
+        config.validate(state.admin_operation_cooldown)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let delay = state.admin_operation_cooldown;
+        let id = queue.enqueue(ADMIN_OP_IMPORT_CONFIG, Pubkey::default(), config.version as u64, now, delay)?;
+        state.pending_import_hash = hash(&blob).to_bytes();
+
+        emit!(ParameterChangeProposed {
+            id,
+            param: ADMIN_OP_IMPORT_CONFIG,
+            subject: Pubkey::default(),
+            value: config.version as u64,
+            executable_at: now.saturating_add(delay),
+            timestamp: now,
+        });
+        emit!(ConfigImportProposed {
+            id,
+            admin: ctx.accounts.admin.key(),
+            version: config.version,
+            config_hash: state.pending_import_hash,
+            executable_at: now.saturating_add(delay),
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Restore the blob queued by propose_import_config once its delay elapsed (admin only)
+    /// The DAT must still be paused and the queue hold nothing but this import.
+    /// Resume after reviewing the ConfigImported event
+    pub fn import_config(ctx: Context<ChangeQueueAdmin>, id: u64, blob: Vec<u8>) -> Result<()> {
+        use anchor_lang::solana_program::hash::hash;
+
+        let clock = Clock::get()?;
+        let mut state = ctx.accounts.dat_state.load_mut()?;
+        let queue = &mut ctx.accounts.change_queue;
+        require!(state.emergency_pause != 0, ErrorCode::ImportRequiresPause);
+
+        let change = queue.take_due(id, clock.unix_timestamp)?;
+        require!(change.param == ADMIN_OP_IMPORT_CONFIG, ErrorCode::InvalidParameter);
+        require!(queue.pending_count() == 0, ErrorCode::ImportQueueNotEmpty);
+        state.require_current_epoch(change.proposed_at)?;
+        let config_hash = hash(&blob).to_bytes();
+        require!(config_hash == state.pending_import_hash, ErrorCode::ImportHashMismatch);
+
+        let config = PortableConfig::decode(&blob)?;
+        config.validate(state.admin_operation_cooldown)?;
+        config.apply(&mut state);
+        state.pending_import_hash = [0; 32];
+        log_admin_op(
+            &mut ctx.accounts.admin_log,
+            ADMIN_OP_IMPORT_CONFIG,
+            Pubkey::default(),
+            0,
+            config.version as u64,
+            ctx.accounts.admin.key(),
+        )?;
+
+        emit!(ParameterChangeExecuted {
+            id,
+            param: ADMIN_OP_IMPORT_CONFIG,
+            subject: Pubkey::default(),
+            old_value: 0,
+            new_value: config.version as u64,
+            timestamp: clock.unix_timestamp,
+        });
+        emit!(ConfigImported {
+            admin: ctx.accounts.admin.key(),
+            version: config.version,
+            config_hash,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// DEPRECATED: Use propose_admin_transfer + accept_admin_transfer instead
+    /// Kept for backwards compatibility - now just proposes the transfer
+    pub fn transfer_admin(ctx: Context<TransferAdmin>) -> Result<()> {
+        let mut state = ctx.accounts.dat_state.load_mut()?;
+        state.pending_admin = ctx.accounts.new_admin.key();
+        emit!(AdminTransferProposed {
+            current_admin: ctx.accounts.admin.key(),
+            proposed_admin: ctx.accounts.new_admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Propose a new admin (two-step transfer for security)
+    pub fn propose_admin_transfer(ctx: Context<ProposeAdminTransfer>) -> Result<()> {
+        let mut state = ctx.accounts.dat_state.load_mut()?;
+        state.pending_admin = ctx.accounts.new_admin.key();
+        emit!(AdminTransferProposed {
+            current_admin: ctx.accounts.admin.key(),
+            proposed_admin: ctx.accounts.new_admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Accept admin transfer (must be called by the proposed admin)
+    pub fn accept_admin_transfer(ctx: Context<AcceptAdminTransfer>) -> Result<()> {
+        let mut state = ctx.accounts.dat_state.load_mut()?;
+        let old_admin = state.admin;
+        let new_admin = ctx.accounts.new_admin.key();
+
+        state.admin = new_admin;
+        state.pending_admin = Pubkey::default();
+
+        emit!(AdminTransferred {
+            old_admin,
+            new_admin,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Cancel a pending admin transfer (called by current admin)
+    pub fn cancel_admin_transfer(ctx: Context<CancelAdminTransfer>) -> Result<()> {
+        let mut state = ctx.accounts.dat_state.load_mut()?;
+        let clock = Clock::get()?;
+        // Constraint already validates pending_admin.is_some() in context
+        let cancelled_admin = state.pending_admin().ok_or(ErrorCode::NoPendingAdminTransfer)?;
+        state.pending_admin = Pubkey::default();
+
+        emit!(AdminTransferCancelled {
+            admin: ctx.accounts.admin.key(),
+            cancelled_new_admin: cancelled_admin,
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Propose a fee split change (subject to timelock)
+    pub fn propose_fee_split(ctx: Context<ProposeAdminTransfer>, new_fee_split_bps: u16) -> Result<()> {
+        require!(
+            new_fee_split_bps > 0 && new_fee_split_bps < 10000,
+            ErrorCode::InvalidParameter
+        );
+
+        let mut state = ctx.accounts.dat_state.load_mut()?;
+        let clock = Clock::get()?;
+
+        state.pending_fee_split = new_fee_split_bps;
+        state.pending_fee_split_timestamp = clock.unix_timestamp;
+
+        msg!("Fee split change proposed: {} bps, can execute after {} seconds",
+             new_fee_split_bps, state.admin_operation_cooldown);
+        Ok(())
+    }
+
+    /// Execute a pending fee split change (after cooldown period)
+    pub fn execute_fee_split(ctx: Context<ProposeAdminTransfer>) -> Result<()> {
+        let mut state = ctx.accounts.dat_state.load_mut()?;
+        let clock = Clock::get()?;
+
+        require!(state.pending_fee_split().is_some(), ErrorCode::NoPendingFeeSplit);
+
+        let elapsed = clock.unix_timestamp.saturating_sub(state.pending_fee_split_timestamp);
+        require!(
+            elapsed >= state.admin_operation_cooldown,
+            ErrorCode::CycleTooSoon // Reusing existing error for timelock
+        );
+
+        let new_fee_split = state.pending_fee_split()
+            .ok_or(ErrorCode::NoPendingFeeSplit)?;
+        let old_fee_split = state.fee_split_bps;
+
+        state.fee_split_bps = new_fee_split;
+        state.pending_fee_split = 0;
+        state.pending_fee_split_timestamp = 0;
+
+        emit!(FeeSplitUpdated {
+            old_bps: old_fee_split,
+            new_bps: new_fee_split,
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Propose a dev fee change (capped at MAX_DEV_FEE_BPS, admin only)
+    /// Timelocked: queues the change for execute_change after admin_operation_cooldown
+    pub fn propose_dev_fee(ctx: Context<ChangeQueueAdmin>, new_dev_fee_bps: u16) -> Result<()> {
+        let mut preview = DATState::clone(&*ctx.accounts.dat_state.load()?);
+        let delay = preview.admin_operation_cooldown;
+        queue_parameter_change(&mut ctx.accounts.change_queue, &mut preview, ADMIN_OP_DEV_FEE, new_dev_fee_bps as u64, delay)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        emit!(DevFeeProposed {
+            current_bps: ctx.accounts.dat_state.load()?.dev_fee_bps,
+            proposed_bps: new_dev_fee_bps,
+            executable_at: now.saturating_add(delay),
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    /// Propose a new dev wallet (two-step rotation, like admin transfer)
+    pub fn propose_dev_wallet(ctx: Context<ProposeDevWallet>) -> Result<()> {
+        let mut state = ctx.accounts.dat_state.load_mut()?;
+        state.pending_dev_wallet = ctx.accounts.new_dev_wallet.key();
+        emit!(DevWalletProposed {
+            current_wallet: state.dev_wallet,
+            proposed_wallet: ctx.accounts.new_dev_wallet.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Accept the dev wallet rotation (must be signed by the proposed wallet)
+    pub fn accept_dev_wallet(ctx: Context<AcceptDevWallet>) -> Result<()> {
+        let mut state = ctx.accounts.dat_state.load_mut()?;
+        let old_wallet = state.dev_wallet;
+        let new_wallet = ctx.accounts.new_dev_wallet.key();
+
+        state.dev_wallet = new_wallet;
+        state.pending_dev_wallet = Pubkey::default();
+
+        emit!(DevWalletUpdated {
+            old_wallet,
+            new_wallet,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Move SOL from the root treasury into the bug bounty vault (admin only)
+    /// Creates the vault on first use
+    pub fn fund_bounty_vault(ctx: Context<FundBountyVault>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidParameter);
+
+        let root_mint = ctx.accounts.dat_state.load()?.root_token_mint().ok_or(ErrorCode::InvalidRootToken)?;
+        let (expected_treasury, bump) = Pubkey::find_program_address(
+            &[ROOT_TREASURY_SEED, root_mint.as_ref()],
+            ctx.program_id
+        );
+        require!(expected_treasury == ctx.accounts.root_treasury.key(), ErrorCode::InvalidRootTreasury);
+
+        // Keep the treasury rent-exempt
+        let available = ctx.accounts.root_treasury.lamports().saturating_sub(RENT_EXEMPT_MINIMUM);
+        require!(available >= amount, ErrorCode::InsufficientFees);
+
+        let bump_slice = &[bump];
+        let treasury_seeds: &[&[u8]] = &[ROOT_TREASURY_SEED, root_mint.as_ref(), bump_slice];
+        invoke_signed(
+            &anchor_lang::solana_program::system_instruction::transfer(
+                ctx.accounts.root_treasury.key,
+                &ctx.accounts.bounty_vault.key(),
+                amount
+            ),
+            &[
+                ctx.accounts.root_treasury.to_account_info(),
+                ctx.accounts.bounty_vault.to_account_info(),
+                ctx.accounts.system_program.to_account_info()
+            ],
+            &[treasury_seeds]
+        )?;
+
+        let vault = &mut ctx.accounts.bounty_vault;
+        vault.bump = ctx.bumps.bounty_vault;
+        vault.total_funded = vault.total_funded.saturating_add(amount);
+
+        emit!(BountyVaultFunded {
+            amount,
+            total_funded: vault.total_funded,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Propose a bug bounty payout (subject to timelock)
+    /// Replaces any pending proposal and restarts the admin_operation_cooldown
+    pub fn propose_bounty(
+        ctx: Context<BountyControl>,
+        recipient: Pubkey,
+        amount: u64,
+        report_hash: [u8; 32],
+    ) -> Result<()> {
+        require!(recipient != Pubkey::default(), ErrorCode::InvalidParameter);
+        require!(amount > 0, ErrorCode::InvalidParameter);
+
+        let vault = &mut ctx.accounts.bounty_vault;
+        let rent_floor = Rent::get()?.minimum_balance(8 + BountyVault::LEN);
+        let available = vault.to_account_info().lamports().saturating_sub(rent_floor);
+        require!(available >= amount, ErrorCode::InsufficientBountyFunds);
+
+        let clock = Clock::get()?;
+        vault.pending_recipient = recipient;
+        vault.pending_amount = amount;
+        vault.pending_report_hash = report_hash;
+        vault.pending_proposed_at = clock.unix_timestamp;
+
+        emit!(BountyProposed {
+            recipient,
+            amount,
+            report_hash,
+            executable_at: clock.unix_timestamp.saturating_add(ctx.accounts.dat_state.load()?.admin_operation_cooldown),
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Pay a proposed bug bounty after the cooldown (admin only)
+    /// Arguments must match the pending proposal exactly; the report hash is emitted on-chain
+    pub fn pay_bounty(
+        ctx: Context<PayBounty>,
+        recipient: Pubkey,
+        amount: u64,
+        report_hash: [u8; 32],
+    ) -> Result<()> {
+        let vault = &mut ctx.accounts.bounty_vault;
+        let clock = Clock::get()?;
+
+        require!(vault.has_pending(), ErrorCode::NoPendingBounty);
+        require!(vault.matches_pending(&recipient, amount, &report_hash), ErrorCode::BountyMismatch);
+        require!(ctx.accounts.recipient.key() == recipient, ErrorCode::BountyMismatch);
+        ctx.accounts.dat_state.load()?.require_current_epoch(vault.pending_proposed_at)?;
+
+        let elapsed = clock.unix_timestamp.saturating_sub(vault.pending_proposed_at);
+        require!(
+            elapsed >= ctx.accounts.dat_state.load()?.admin_operation_cooldown,
+            ErrorCode::CycleTooSoon // Reusing existing error for timelock
+        );
+
+        let rent_floor = Rent::get()?.minimum_balance(8 + BountyVault::LEN);
+        let available = vault.to_account_info().lamports().saturating_sub(rent_floor);
+        require!(available >= amount, ErrorCode::InsufficientBountyFunds);
+
+        vault.sub_lamports(amount)?;
+        ctx.accounts.recipient.add_lamports(amount)?;
+
+        vault.total_paid = vault.total_paid.saturating_add(amount);
+        vault.payout_count = vault.payout_count.saturating_add(1);
+        vault.clear_pending();
+
+        emit!(BountyPaid {
+            recipient,
+            amount,
+            report_hash,
+            payout_count: vault.payout_count,
+            total_paid: vault.total_paid,
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Withdraw a pending bug bounty proposal (admin only)
+    pub fn cancel_bounty(ctx: Context<BountyControl>) -> Result<()> {
+        let vault = &mut ctx.accounts.bounty_vault;
+        require!(vault.has_pending(), ErrorCode::NoPendingBounty);
+
+        emit!(BountyCancelled {
+            recipient: vault.pending_recipient,
+            amount: vault.pending_amount,
+            report_hash: vault.pending_report_hash,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        vault.clear_pending();
+        Ok(())
+    }
+
+    /// Set the key allowed to veto emergency withdrawals (admin only)
+    /// Cannot be changed while a withdrawal is pending, so the veto cannot be bypassed
+    pub fn set_emergency_guardian(ctx: Context<SetEmergencyGuardian>, guardian: Pubkey) -> Result<()> {
+        require!(guardian != Pubkey::default(), ErrorCode::InvalidParameter);
+
+        let withdrawal = &mut ctx.accounts.emergency_withdrawal;
+        require!(!withdrawal.has_pending(), ErrorCode::EmergencyWithdrawPending);
+
+        let old_guardian = withdrawal.guardian;
+        withdrawal.guardian = guardian;
+        withdrawal.bump = ctx.bumps.emergency_withdrawal;
+
+        emit!(EmergencyGuardianSet {
+            old_guardian,
+            new_guardian: guardian,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Propose recovering SOL stranded in dat_authority (admin only, cycles must be paused)
+    /// Executable after EMERGENCY_WITHDRAW_TIMELOCK unless the guardian vetoes it
+    pub fn propose_emergency_withdraw(
+        ctx: Context<ProposeEmergencyWithdraw>,
+        amount: u64,
+        destination: Pubkey,
+    ) -> Result<()> {
+        require!(ctx.accounts.dat_state.load()?.emergency_pause != 0, ErrorCode::EmergencyWithdrawRequiresPause);
+        require!(amount > 0, ErrorCode::InvalidParameter);
+        require!(destination != Pubkey::default(), ErrorCode::InvalidParameter);
+
+        let withdrawal = &mut ctx.accounts.emergency_withdrawal;
+        require!(withdrawal.guardian != Pubkey::default(), ErrorCode::GuardianNotSet);
+        require!(!withdrawal.has_pending(), ErrorCode::EmergencyWithdrawPending);
+
+        let authority = &ctx.accounts.dat_authority;
+        let floor = Rent::get()?.minimum_balance(authority.data_len());
+        require!(authority.lamports().saturating_sub(floor) >= amount, ErrorCode::InsufficientFees);
+
+        let clock = Clock::get()?;
+        withdrawal.pending_destination = destination;
+        withdrawal.pending_amount = amount;
+        withdrawal.pending_proposed_at = clock.unix_timestamp;
+
+        emit!(EmergencyWithdrawProposed {
+            destination,
+            amount,
+            executable_at: withdrawal.executable_at(EMERGENCY_WITHDRAW_TIMELOCK),
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Veto a pending emergency withdrawal (guardian or admin)
+    pub fn veto_emergency_withdraw(ctx: Context<VetoEmergencyWithdraw>) -> Result<()> {
+        let withdrawal = &mut ctx.accounts.emergency_withdrawal;
+        require!(withdrawal.has_pending(), ErrorCode::NoPendingEmergencyWithdraw);
+
+        emit!(EmergencyWithdrawVetoed {
+            destination: withdrawal.pending_destination,
+            amount: withdrawal.pending_amount,
+            vetoed_by: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        withdrawal.clear_pending();
+        Ok(())
+    }
+
+    /// Execute a proposed emergency withdrawal after the timelock (admin only, cycles must be paused)
+    pub fn execute_emergency_withdraw(ctx: Context<ExecuteEmergencyWithdraw>) -> Result<()> {
+        require!(ctx.accounts.dat_state.load()?.emergency_pause != 0, ErrorCode::EmergencyWithdrawRequiresPause);
+
+        let withdrawal = &mut ctx.accounts.emergency_withdrawal;
+        require!(withdrawal.has_pending(), ErrorCode::NoPendingEmergencyWithdraw);
+        ctx.accounts.dat_state.load()?.require_current_epoch(withdrawal.pending_proposed_at)?;
+
+        let clock = Clock::get()?;
+        require!(
+            clock.unix_timestamp >= withdrawal.executable_at(EMERGENCY_WITHDRAW_TIMELOCK),
+            ErrorCode::EmergencyWithdrawTimelocked
+        );
+
+        let amount = withdrawal.pending_amount;
+        let authority = &ctx.accounts.dat_authority;
+        let floor = Rent::get()?.minimum_balance(authority.data_len());
+        require!(authority.lamports().saturating_sub(floor) >= amount, ErrorCode::InsufficientFees);
+
+        let bump = ctx.accounts.dat_state.load()?.dat_authority_bump;
+        let seeds: &[&[u8]] = &[DAT_AUTHORITY_SEED, &[bump]];
+        invoke_signed(
+            &anchor_lang::solana_program::system_instruction::transfer(
+                authority.key,
+                ctx.accounts.destination.key,
+                amount
+            ),
+            &[
+                authority.to_account_info(),
+                ctx.accounts.destination.to_account_info(),
+                ctx.accounts.system_program.to_account_info()
+            ],
+            &[seeds]
+        )?;
+
+        withdrawal.total_withdrawn = withdrawal.total_withdrawn.saturating_add(amount);
+        let destination = withdrawal.pending_destination;
+        withdrawal.clear_pending();
+
+        emit!(EmergencyWithdrawExecuted {
+            destination,
+            amount,
+            total_withdrawn: withdrawal.total_withdrawn,
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Create the treasury SOL reserve that collect_fees funds once reserve_bps > 0 (admin only)
+    pub fn initialize_reserve_vault(ctx: Context<InitializeReserveVault>) -> Result<()> {
+        let vault = &mut ctx.accounts.reserve_vault;
+        vault.total_reserved = 0;
+        vault.total_deployed = 0;
+        vault.clear_pending();
+        vault.bump = ctx.bumps.reserve_vault;
+        Ok(())
+    }
+
+    /// Propose deploying reserve SOL to `recipient` (admin only)
+    /// Executable by deploy_reserve after RESERVE_DEPLOY_TIMELOCK
+    pub fn propose_reserve_deployment(ctx: Context<ReserveControl>, recipient: Pubkey, amount: u64) -> Result<()> {
+        require!(recipient != Pubkey::default(), ErrorCode::InvalidParameter);
+        require!(amount > 0, ErrorCode::InvalidParameter);
+
+        let vault = &mut ctx.accounts.reserve_vault;
+        require!(!vault.has_pending(), ErrorCode::ReserveDeploymentPending);
+
+        let rent_floor = Rent::get()?.minimum_balance(8 + ReserveVault::LEN);
+        let available = vault.to_account_info().lamports().saturating_sub(rent_floor);
+        require!(available >= amount, ErrorCode::InsufficientReserveFunds);
+
+        let clock = Clock::get()?;
+        vault.pending_recipient = recipient;
+        vault.pending_amount = amount;
+        vault.pending_proposed_at = clock.unix_timestamp;
+
+        emit!(ReserveDeploymentProposed {
+            recipient,
+            amount,
+            executable_at: vault.executable_at(RESERVE_DEPLOY_TIMELOCK),
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Withdraw a pending reserve deployment (admin only)
+    pub fn cancel_reserve_deployment(ctx: Context<ReserveControl>) -> Result<()> {
+        let vault = &mut ctx.accounts.reserve_vault;
+        require!(vault.has_pending(), ErrorCode::NoPendingReserveDeployment);
+
+        emit!(ReserveDeploymentCancelled {
+            recipient: vault.pending_recipient,
+            amount: vault.pending_amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        vault.clear_pending();
+        Ok(())
+    }
+
+    /// Deploy a proposed amount of reserve SOL after the timelock (admin only)
+    /// The vault keeps its rent-exempt minimum
+    pub fn deploy_reserve(ctx: Context<DeployReserve>) -> Result<()> {
+        let vault = &mut ctx.accounts.reserve_vault;
+        require!(vault.has_pending(), ErrorCode::NoPendingReserveDeployment);
+        ctx.accounts.dat_state.load()?.require_current_epoch(vault.pending_proposed_at)?;
+
+        let clock = Clock::get()?;
+        require!(
+            clock.unix_timestamp >= vault.executable_at(RESERVE_DEPLOY_TIMELOCK),
+            ErrorCode::ReserveDeploymentTimelocked
+        );
+
+        let amount = vault.pending_amount;
+        let rent_floor = Rent::get()?.minimum_balance(8 + ReserveVault::LEN);
+        let available = vault.to_account_info().lamports().saturating_sub(rent_floor);
+        require!(available >= amount, ErrorCode::InsufficientReserveFunds);
+
+        vault.sub_lamports(amount)?;
+        ctx.accounts.recipient.add_lamports(amount)?;
+
+        vault.total_deployed = vault.total_deployed.saturating_add(amount);
+        let recipient = vault.pending_recipient;
+        vault.clear_pending();
+
+        emit!(ReserveDeployed {
+            recipient,
+            amount,
+            total_deployed: vault.total_deployed,
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Create the insurance fund and name the key allowed to veto its payouts (admin only)
+    pub fn initialize_insurance_fund(ctx: Context<InitializeInsuranceFund>) -> Result<()> {
+        let guardian = ctx.accounts.guardian.key();
+        require!(
+            guardian != Pubkey::default() && guardian != ctx.accounts.admin.key(),
+            ErrorCode::InvalidParameter
+        );
+
+        let fund = &mut ctx.accounts.insurance_fund;
+        fund.guardian = guardian;
+        fund.total_deposited = 0;
+        fund.deposit_count = 0;
+        fund.total_paid = 0;
+        fund.payout_count = 0;
+        fund.clear_pending();
+        fund.bump = ctx.bumps.insurance_fund;
+
+        emit!(InsuranceFundInitialized {
+            insurance_fund: fund.key(),
+            guardian,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Propose reimbursing `recipient` for the claim identified by `claim_hash` (admin only)
+    /// Executable after INSURANCE_PAYOUT_TIMELOCK unless the guardian vetoes it
+    pub fn propose_insurance_payout(
+        ctx: Context<ProposeInsurancePayout>,
+        recipient: Pubkey,
+        amount: u64,
+        claim_hash: [u8; 32],
+    ) -> Result<()> {
+        require!(recipient != Pubkey::default(), ErrorCode::InvalidParameter);
+        require!(amount > 0, ErrorCode::InvalidParameter);
+
+        let fund = &mut ctx.accounts.insurance_fund;
+        require!(!fund.has_pending(), ErrorCode::InsurancePayoutPending);
+
+        let rent_floor = Rent::get()?.minimum_balance(8 + InsuranceFund::LEN);
+        let available = fund.to_account_info().lamports().saturating_sub(rent_floor);
+        require!(available >= amount, ErrorCode::InsufficientInsuranceFunds);
+
+        let clock = Clock::get()?;
+        fund.pending_recipient = recipient;
+        fund.pending_amount = amount;
+        fund.pending_claim_hash = claim_hash;
+        fund.pending_proposed_at = clock.unix_timestamp;
+
+        emit!(InsurancePayoutProposed {
+            recipient,
+            amount,
+            claim_hash,
+            executable_at: fund.executable_at(INSURANCE_PAYOUT_TIMELOCK),
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Veto a pending insurance payout (guardian or admin)
+    pub fn veto_insurance_payout(ctx: Context<VetoInsurancePayout>) -> Result<()> {
+        let fund = &mut ctx.accounts.insurance_fund;
+        require!(fund.has_pending(), ErrorCode::NoPendingInsurancePayout);
+
+        emit!(InsurancePayoutVetoed {
+            recipient: fund.pending_recipient,
+            amount: fund.pending_amount,
+            claim_hash: fund.pending_claim_hash,
+            vetoed_by: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        fund.clear_pending();
+        Ok(())
+    }
+
+    /// Pay a proposed insurance claim after the timelock (admin only)
+    /// The fund keeps its rent-exempt minimum
+    pub fn execute_insurance_payout(ctx: Context<ExecuteInsurancePayout>) -> Result<()> {
+        let fund = &mut ctx.accounts.insurance_fund;
+        require!(fund.has_pending(), ErrorCode::NoPendingInsurancePayout);
+        ctx.accounts.dat_state.load()?.require_current_epoch(fund.pending_proposed_at)?;
+
+        let clock = Clock::get()?;
+        require!(
+            clock.unix_timestamp >= fund.executable_at(INSURANCE_PAYOUT_TIMELOCK),
+            ErrorCode::InsurancePayoutTimelocked
+        );
+
+        let amount = fund.pending_amount;
+        let rent_floor = Rent::get()?.minimum_balance(8 + InsuranceFund::LEN);
+        let available = fund.to_account_info().lamports().saturating_sub(rent_floor);
+        require!(available >= amount, ErrorCode::InsufficientInsuranceFunds);
+
+        fund.sub_lamports(amount)?;
+        ctx.accounts.recipient.add_lamports(amount)?;
+
+        fund.total_paid = fund.total_paid.saturating_add(amount);
+        fund.payout_count = fund.payout_count.saturating_add(1);
+        let recipient = fund.pending_recipient;
+        let claim_hash = fund.pending_claim_hash;
+        fund.clear_pending();
+
+        emit!(InsurancePayoutExecuted {
+            recipient,
+            amount,
+            claim_hash,
+            payout_count: fund.payout_count,
+            total_paid: fund.total_paid,
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Create a PumpFun token using create_v2 (Token2022) without Mayhem Mode
+    /// Standard Token2022 token with 1B supply
+    /// initial_buy_lamports: optional dev-buy from dat_authority funds (see initial_dev_buy)
+    pub fn create_pumpfun_token_v2(
+        ctx: Context<CreatePumpfunTokenV2>,
+        name: String,
+        symbol: String,
+        uri: String,
+        initial_buy_lamports: Option<u64>,
+    ) -> Result<()> {
+        let state = ctx.accounts.dat_state.load()?;
+
+        msg!("Creating PumpFun token via create_v2 (Token2022, no Mayhem)");
+        msg!("Name: {}, Symbol: {}, Creator: {}", name, symbol, ctx.accounts.dat_authority.key());
+
+        let mut data = Vec::new();
+
+        // Discriminator for create_v2
+        data.extend_from_slice(&PUMPFUN_CREATE_V2_DISCRIMINATOR);
+
+        // Name (String)
+        data.extend_from_slice(&(name.len() as u32).to_le_bytes());
+        data.extend_from_slice(name.as_bytes());
+
+        // Symbol (String)
+        data.extend_from_slice(&(symbol.len() as u32).to_le_bytes());
+        data.extend_from_slice(symbol.as_bytes());
+
+        // URI (String)
+        data.extend_from_slice(&(uri.len() as u32).to_le_bytes());
+        data.extend_from_slice(uri.as_bytes());
+
+        // Creator (Pubkey)
+        data.extend_from_slice(&ctx.accounts.dat_authority.key().to_bytes());
 
         // is_mayhem_mode (bool - 1 byte) = false
         data.extend_from_slice(&[0u8]); // false for standard Token2022
 
-        // PumpFun's create_v2 requires all Mayhem accounts even when is_mayhem_mode = false
+        // PumpFun's create_v2 requires all Mayhem accounts even when is_mayhem_mode = false
+        let accounts = vec![
+            AccountMeta::new(ctx.accounts.mint.key(), true),
+            AccountMeta::new_readonly(ctx.accounts.mint_authority.key(), false),
+            AccountMeta::new(ctx.accounts.bonding_curve.key(), false),
+            AccountMeta::new(ctx.accounts.associated_bonding_curve.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.global.key(), false),
+            AccountMeta::new(ctx.accounts.dat_authority.key(), true), // user/creator
+            AccountMeta::new_readonly(ctx.accounts.system_program.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.token_2022_program.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.associated_token_program.key(), false),
+            // Mayhem accounts (required even for non-mayhem mode)
+            AccountMeta::new(ctx.accounts.mayhem_program.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.global_params.key(), false),
+            AccountMeta::new(ctx.accounts.sol_vault.key(), false),
+            AccountMeta::new(ctx.accounts.mayhem_state.key(), false),
+            AccountMeta::new(ctx.accounts.mayhem_token_vault.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.event_authority.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.pump_program.key(), false),
+        ];
+
+        let ix = Instruction {
+            program_id: PUMP_PROGRAM,
+            accounts,
+            data,
+        };
+
+        let seeds: &[&[u8]] = &[DAT_AUTHORITY_SEED, &[state.dat_authority_bump]];
+
+        invoke_signed(
+            &ix,
+            &[
+                ctx.accounts.mint.to_account_info(),
+                ctx.accounts.mint_authority.to_account_info(),
+                ctx.accounts.bonding_curve.to_account_info(),
+                ctx.accounts.associated_bonding_curve.to_account_info(),
+                ctx.accounts.global.to_account_info(),
+                ctx.accounts.dat_authority.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+                ctx.accounts.token_2022_program.to_account_info(),
+                ctx.accounts.associated_token_program.to_account_info(),
+                // Mayhem accounts (required even for non-mayhem mode)
+                ctx.accounts.mayhem_program.to_account_info(),
+                ctx.accounts.global_params.to_account_info(),
+                ctx.accounts.sol_vault.to_account_info(),
+                ctx.accounts.mayhem_state.to_account_info(),
+                ctx.accounts.mayhem_token_vault.to_account_info(),
+                ctx.accounts.event_authority.to_account_info(),
+                ctx.accounts.pump_program.to_account_info(),
+            ],
+            &[seeds],
+        )?;
+
+        msg!("Token2022 token created successfully (standard mode)!");
+
+        emit!(TokenCreated {
+            mint: ctx.accounts.mint.key(),
+            bonding_curve: ctx.accounts.bonding_curve.key(),
+            creator: ctx.accounts.dat_authority.key(),
+            name,
+            symbol,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        if let Some(lamports) = initial_buy_lamports {
+            let accounts = &ctx.accounts;
+            let missing = || error!(ErrorCode::DevBuyAccountsMissing);
+            let accs = vec![
+                accounts.global.to_account_info(),
+                accounts.protocol_fee_recipient.clone().ok_or_else(missing)?,
+                accounts.mint.to_account_info(),
+                accounts.bonding_curve.to_account_info(),
+                accounts.associated_bonding_curve.to_account_info(),
+                accounts.dat_token_account.clone().ok_or_else(missing)?,
+                accounts.dat_authority.to_account_info(),
+                accounts.system_program.to_account_info(),
+                accounts.token_2022_program.to_account_info(), // token_program BEFORE creator_vault
+                accounts.creator_vault.clone().ok_or_else(missing)?,
+                accounts.event_authority.to_account_info(),
+                accounts.pump_program.to_account_info(),
+                accounts.global_volume_accumulator.clone().ok_or_else(missing)?,
+                accounts.user_volume_accumulator.clone().ok_or_else(missing)?,
+                accounts.fee_config.clone().ok_or_else(missing)?,
+                accounts.fee_program.clone().ok_or_else(missing)?,
+            ];
+            initial_dev_buy(
+                &state,
+                &accs,
+                &accounts.admin.to_account_info(),
+                &accounts.associated_token_program.to_account_info(),
+                lamports,
+                false,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Create a PumpFun token in Mayhem Mode with AI trading agent
+    /// Uses Token2022 and create_v2 instruction
+    /// Supply: 2 billion tokens (1B + 1B for agent)
+    /// initial_buy_lamports: optional dev-buy from dat_authority funds (see initial_dev_buy)
+    pub fn create_pumpfun_token_mayhem(
+        ctx: Context<CreatePumpfunTokenMayhem>,
+        name: String,
+        symbol: String,
+        uri: String,
+        initial_buy_lamports: Option<u64>,
+    ) -> Result<()> {
+        let state = ctx.accounts.dat_state.load()?;
+
+        msg!("Creating PumpFun token in MAYHEM MODE via CPI");
+        msg!("Name: {}, Symbol: {}, Creator: {}", name, symbol, ctx.accounts.dat_authority.key());
+        msg!("Mayhem Mode: AI agent will trade for 24h");
+
+        let mut data = Vec::new();
+
+        // Discriminator for create_v2: [214, 144, 76, 236, 95, 139, 49, 180]
+        data.extend_from_slice(&[214, 144, 76, 236, 95, 139, 49, 180]);
+
+        // Name (String)
+        data.extend_from_slice(&(name.len() as u32).to_le_bytes());
+        data.extend_from_slice(name.as_bytes());
+
+        // Symbol (String)
+        data.extend_from_slice(&(symbol.len() as u32).to_le_bytes());
+        data.extend_from_slice(symbol.as_bytes());
+
+        // URI (String)
+        data.extend_from_slice(&(uri.len() as u32).to_le_bytes());
+        data.extend_from_slice(uri.as_bytes());
+
+        // Creator (Pubkey)
+        data.extend_from_slice(&ctx.accounts.dat_authority.key().to_bytes());
+
+        // is_mayhem_mode (bool - 1 byte)
+        data.extend_from_slice(&[1u8]); // true for Mayhem Mode
+
         let accounts = vec![
             AccountMeta::new(ctx.accounts.mint.key(), true),
             AccountMeta::new_readonly(ctx.accounts.mint_authority.key(), false),
@@ -1519,7 +7109,6 @@ pub mod asdf_dat {
             AccountMeta::new_readonly(ctx.accounts.system_program.key(), false),
             AccountMeta::new_readonly(ctx.accounts.token_2022_program.key(), false),
             AccountMeta::new_readonly(ctx.accounts.associated_token_program.key(), false),
-            // Mayhem accounts (required even for non-mayhem mode)
             AccountMeta::new(ctx.accounts.mayhem_program.key(), false),
             AccountMeta::new_readonly(ctx.accounts.global_params.key(), false),
             AccountMeta::new(ctx.accounts.sol_vault.key(), false),
@@ -1529,282 +7118,890 @@ pub mod asdf_dat {
             AccountMeta::new_readonly(ctx.accounts.pump_program.key(), false),
         ];
 
-        let ix = Instruction {
-            program_id: PUMP_PROGRAM,
-            accounts,
-            data,
-        };
+        let ix = Instruction {
+            program_id: PUMP_PROGRAM,
+            accounts,
+            data,
+        };
+
+        let seeds: &[&[u8]] = &[DAT_AUTHORITY_SEED, &[state.dat_authority_bump]];
+
+        invoke_signed(
+            &ix,
+            &[
+                ctx.accounts.mint.to_account_info(),
+                ctx.accounts.mint_authority.to_account_info(),
+                ctx.accounts.bonding_curve.to_account_info(),
+                ctx.accounts.associated_bonding_curve.to_account_info(),
+                ctx.accounts.global.to_account_info(),
+                ctx.accounts.dat_authority.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+                ctx.accounts.token_2022_program.to_account_info(),
+                ctx.accounts.associated_token_program.to_account_info(),
+                ctx.accounts.mayhem_program.to_account_info(),
+                ctx.accounts.global_params.to_account_info(),
+                ctx.accounts.sol_vault.to_account_info(),
+                ctx.accounts.mayhem_state.to_account_info(),
+                ctx.accounts.mayhem_token_vault.to_account_info(),
+                ctx.accounts.event_authority.to_account_info(),
+                ctx.accounts.pump_program.to_account_info(),
+            ],
+            &[seeds],
+        )?;
+
+        msg!("Mayhem Mode token created successfully!");
+        msg!("Supply: 2 billion tokens (1B base + 1B for AI agent)");
+
+        emit!(TokenCreated {
+            mint: ctx.accounts.mint.key(),
+            bonding_curve: ctx.accounts.bonding_curve.key(),
+            creator: ctx.accounts.dat_authority.key(),
+            name,
+            symbol,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        if let Some(lamports) = initial_buy_lamports {
+            let accounts = &ctx.accounts;
+            let missing = || error!(ErrorCode::DevBuyAccountsMissing);
+            let accs = vec![
+                accounts.global.to_account_info(),
+                accounts.protocol_fee_recipient.clone().ok_or_else(missing)?,
+                accounts.mint.to_account_info(),
+                accounts.bonding_curve.to_account_info(),
+                accounts.associated_bonding_curve.to_account_info(),
+                accounts.dat_token_account.clone().ok_or_else(missing)?,
+                accounts.dat_authority.to_account_info(),
+                accounts.system_program.to_account_info(),
+                accounts.token_2022_program.to_account_info(), // token_program BEFORE creator_vault
+                accounts.creator_vault.clone().ok_or_else(missing)?,
+                accounts.event_authority.to_account_info(),
+                accounts.pump_program.to_account_info(),
+                accounts.global_volume_accumulator.clone().ok_or_else(missing)?,
+                accounts.user_volume_accumulator.clone().ok_or_else(missing)?,
+                accounts.fee_config.clone().ok_or_else(missing)?,
+                accounts.fee_program.clone().ok_or_else(missing)?,
+            ];
+            initial_dev_buy(
+                &state,
+                &accs,
+                &accounts.admin.to_account_info(),
+                &accounts.associated_token_program.to_account_info(),
+                lamports,
+                true,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// ADMIN ONLY - Rewrite name / symbol / uri of a token's Metaplex metadata
+    /// dat_authority must be the (mutable) metadata's update authority; the CPI is signed
+    /// with its seeds. Seller fee, creators, collection and uses are kept.
+    pub fn update_token_metadata(
+        ctx: Context<UpdateTokenMetadata>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        require!(
+            name.len() <= MPL_MAX_NAME_LEN && symbol.len() <= MPL_MAX_SYMBOL_LEN && uri.len() <= MPL_MAX_URI_LEN,
+            ErrorCode::InvalidParameter
+        );
+
+        let data = {
+            let metadata_data = ctx.accounts.metadata.try_borrow_data()?;
+            let metadata = parse_metaplex_metadata(&metadata_data)?;
+            require!(metadata.mint == ctx.accounts.mint.key(), ErrorCode::InvalidMetadata);
+            require!(
+                metadata.is_mutable && metadata.update_authority == ctx.accounts.dat_authority.key(),
+                ErrorCode::MetadataNotUpdatable
+            );
+            update_metadata_v2_data(&metadata, &name, &symbol, &uri)
+        };
+
+        let seeds: &[&[u8]] = &[DAT_AUTHORITY_SEED, &[ctx.accounts.dat_state.load()?.dat_authority_bump]];
+        update_metadata_cpi(
+            &ctx.accounts.metadata,
+            &ctx.accounts.dat_authority,
+            &ctx.accounts.token_metadata_program,
+            data,
+            seeds,
+        )?;
+
+        emit!(TokenMetadataUpdated {
+            mint: ctx.accounts.mint.key(),
+            name,
+            symbol,
+            uri,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Transfer the dev sustainability fee (DATState.dev_fee_bps, 1% by default)
+    /// Called at the end of each batch transaction, after burn succeeds
+    /// Idempotent per cycle: a repeat for the same cycle sequence fails with DevFeeAlreadyPaid
+    /// 1% today = 99% burns forever
+    pub fn transfer_dev_fee(ctx: Context<TransferDevFee>, secondary_share: u64) -> Result<()> {
+        ctx.accounts.cycle_state.mark_dev_fee_paid()?;
+        let dev_fee = ctx.accounts.dat_state.load()?.dev_fee(secondary_share);
+
+        if dev_fee > 0 {
+            let bump = ctx.accounts.dat_state.load()?.dat_authority_bump;
+            let seeds: &[&[u8]] = &[DAT_AUTHORITY_SEED, &[bump]];
+
+            invoke_signed(
+                &anchor_lang::solana_program::system_instruction::transfer(
+                    ctx.accounts.dat_authority.key,
+                    ctx.accounts.dev_wallet.key,
+                    dev_fee,
+                ),
+                &[
+                    ctx.accounts.dat_authority.to_account_info(),
+                    ctx.accounts.dev_wallet.to_account_info(),
+                    ctx.accounts.system_program.to_account_info(),
+                ],
+                &[seeds],
+            )?;
+
+            msg!("Dev sustainability fee: {} lamports", dev_fee);
+        }
+
+        Ok(())
+    }
+
+    // ══════════════════════════════════════════════════════════════════════════════
+    // EXTERNAL APP INTEGRATION
+    // ══════════════════════════════════════════════════════════════════════════════
+
+    /// Initialize the self-sustaining rebate pool
+    /// Called once during protocol setup
+    pub fn initialize_rebate_pool(ctx: Context<InitializeRebatePool>) -> Result<()> {
+        let rebate_pool = &mut ctx.accounts.rebate_pool;
+        let clock = Clock::get()?;
+
+        rebate_pool.bump = ctx.bumps.rebate_pool;
+        rebate_pool.total_deposited = 0;
+        rebate_pool.total_distributed = 0;
+        rebate_pool.rebates_count = 0;
+        rebate_pool.last_rebate_timestamp = 0;
+        rebate_pool.last_rebate_slot = 0;
+        rebate_pool.unique_recipients = 0;
+        rebate_pool.bonus_bps = 0;
+        rebate_pool.bonus_cap = 0;
+        rebate_pool.bonus_budget = 0;
+        rebate_pool.total_bonus_paid = 0;
+        rebate_pool._reserved = [0u8; 6];
+        rebate_pool.rebate_share = REBATE_SHARE;
+        rebate_pool.rebate_threshold = REBATE_THRESHOLD_SOL_EQUIV;
+        rebate_pool.pending_rebate_share = 0;
+        rebate_pool.pending_rebate_threshold = 0;
+        rebate_pool.pending_params_timestamp = 0;
+        rebate_pool.draw_request_slot = 0;
+        rebate_pool.draw_target_slot = 0;
+        rebate_pool.draw_candidates_hash = [0u8; 32];
+        rebate_pool.draw_randomness = [0u8; 32];
+        rebate_pool.draw_winner = Pubkey::default();
+        rebate_pool.draws_count = 0;
+        rebate_pool.current_epoch = 0;
+        rebate_pool.epoch_started_at = 0;
+        rebate_pool.epoch_reserved = 0;
+        rebate_pool.version = REBATE_POOL_VERSION;
+
+        emit!(RebatePoolInitialized {
+            rebate_pool: ctx.accounts.rebate_pool.key(),
+            rebate_pool_ata: Pubkey::default(), // ATA created separately
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("Rebate pool initialized");
+        Ok(())
+    }
+
+    /// External app deposits $ASDF fees with automatic split
+    /// Split: 99.448% → DAT ATA (burn), 0.552% → Rebate Pool ATA (rebates)
+    ///
+    /// Architecture:
+    /// - Payer transfers full amount
+    /// - 99.448% goes to DAT ATA (included in ROOT cycle single burn)
+    /// - 0.552% goes to Rebate Pool ATA (self-sustaining fund)
+    /// - UserStats.pending_contribution tracks full amount for rebate calculation
+    /// - Token2022 transfer fees: split, pool and contribution use the amounts actually received
+    pub fn deposit_fee_asdf(
+        ctx: Context<DepositFeeAsdf>,
+        amount: u64,
+        target_mint: Option<Pubkey>,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+
+        // Validate minimum deposit (after a Token2022 transfer fee, if the mint charges one)
+        let mint_info = ctx.accounts.asdf_mint.to_account_info();
+        let transfer_fee = transfer_fee_for(
+            &mint_info.try_borrow_data()?,
+            mint_info.owner == &TOKEN_2022_PROGRAM,
+            clock.epoch,
+            amount,
+        )?;
+        require!(amount.saturating_sub(transfer_fee) >= MIN_DEPOSIT_SOL_EQUIV, ErrorCode::DepositBelowMinimum);
+
+        // Depositor-directed burn: route to another ecosystem token instead of the root
+        // Rebates are $ASDF-denominated, so targeted deposits are not credited to user_stats
+        let asdf_mint = ctx.accounts.dat_state.load()?.asdf_mint;
+        let target = target_mint.filter(|mint| *mint != asdf_mint);
+        if let Some(mint) = target {
+            let stats = ctx.accounts.target_token_stats
+                .as_ref()
+                .ok_or(ErrorCode::InvalidDepositTarget)?
+                .load()?;
+            require!(stats.mint == mint, ErrorCode::InvalidDepositTarget);
+            require!(stats.is_paused == 0, ErrorCode::TokenPaused);
+            require!(
+                stats.accepts_token_account(&ctx.accounts.dat_asdf_account.key()),
+                ErrorCode::InvalidTokenAccount
+            );
+            require!(
+                ctx.accounts.rebate_pool_ata.owner == ctx.accounts.rebate_pool.key(),
+                ErrorCode::InvalidRebatePool
+            );
+        }
+
+        // Calculate split (99.448% burn, 0.552% rebate by default)
+        // Using ÷100000 for exact precision
+        let burn_sent = amount
+            .checked_mul(ctx.accounts.rebate_pool.burn_share() as u64)
+            .ok_or(ErrorCode::MathOverflow)?
+            .checked_div(SHARE_DENOMINATOR)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let rebate_sent = amount.saturating_sub(burn_sent);
+        let decimals = ctx.accounts.asdf_mint.decimals;
+        let dat_balance_before = ctx.accounts.dat_asdf_account.amount;
+        let pool_balance_before = ctx.accounts.rebate_pool_ata.amount;
+
+        // Transfer 99.448% → DAT ATA (for burn)
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.payer_token_account.to_account_info(),
+                    mint: mint_info.clone(),
+                    to: ctx.accounts.dat_asdf_account.to_account_info(),
+                    authority: ctx.accounts.payer.to_account_info(),
+                },
+            ),
+            burn_sent,
+            decimals,
+        )?;
+
+        // Transfer 0.552% → Rebate Pool ATA (for rebates)
+        if rebate_sent > 0 {
+            token_interface::transfer_checked(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    token_interface::TransferChecked {
+                        from: ctx.accounts.payer_token_account.to_account_info(),
+                        mint: mint_info,
+                        to: ctx.accounts.rebate_pool_ata.to_account_info(),
+                        authority: ctx.accounts.payer.to_account_info(),
+                    },
+                ),
+                rebate_sent,
+                decimals,
+            )?;
+        }
+
+        // Net amounts from the post-transfer balances: a transfer fee is withheld in the
+        // destination accounts, so only what arrived is burned, pooled and credited
+        ctx.accounts.dat_asdf_account.reload()?;
+        ctx.accounts.rebate_pool_ata.reload()?;
+        let burn_amount = ctx.accounts.dat_asdf_account.amount.saturating_sub(dat_balance_before);
+        let rebate_pool_amount = ctx.accounts.rebate_pool_ata.amount.saturating_sub(pool_balance_before);
+        let received = burn_amount.saturating_add(rebate_pool_amount);
+
+        // Get keys before mutable borrow
+        let user_key = ctx.accounts.user.key();
+        let user_stats_key = ctx.accounts.user_stats.key();
+
+        // Initialize or update user stats
+        let user_stats = &mut ctx.accounts.user_stats;
+
+        // Check if newly initialized (user == default)
+        if user_stats.user == Pubkey::default() {
+            user_stats.bump = ctx.bumps.user_stats;
+            user_stats.user = user_key;
+            user_stats.pending_contribution = 0;
+            user_stats.total_contributed = 0;
+            user_stats.total_rebate = 0;
+            user_stats.onboarding_bonus_claimed = false;
+            user_stats.contribution_epoch = 0;
+            user_stats.epoch_contribution = 0;
+            user_stats.app_id = 0;
+            user_stats.referrer = None;
+            user_stats.version = USER_STATS_VERSION;
+
+            emit!(UserStatsInitialized {
+                user: user_key,
+                user_stats: user_stats_key,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+
+        if let Some(mint) = target {
+            // Credit the target's pending stats so its next cycle accounts for the deposit
+            let target_stats = ctx.accounts.target_token_stats
+                .as_ref()
+                .ok_or(ErrorCode::InvalidDepositTarget)?;
+            target_stats.load_mut()?.credit_deposit(burn_amount)?;
+
+            emit!(FeeDepositTargeted {
+                user: user_key,
+                target_mint: mint,
+                amount,
+                burn_amount,
+                rebate_pool_amount,
+                timestamp: clock.unix_timestamp,
+            });
+
+            msg!("Targeted deposit: {} of {} ({} burn, {} rebate pool)",
+                amount, mint, burn_amount, rebate_pool_amount);
+
+            return Ok(());
+        }
+
+        // Update rebate pool stats ($ASDF deposits only)
+        ctx.accounts.rebate_pool.total_deposited =
+            ctx.accounts.rebate_pool.total_deposited.saturating_add(rebate_pool_amount);
+
+        // Per-app attribution: the first app a user deposits through is credited with the user
+        let mut app_id = 0;
+        if let Some(app_stats) = ctx.accounts.app_stats.as_mut() {
+            let new_user = user_stats.app_id == 0;
+            if new_user {
+                user_stats.app_id = app_stats.app_id;
+            }
+            app_stats.record_deposit(received, rebate_pool_amount, new_user);
+            app_id = app_stats.app_id;
+        }
+
+        // Referral: carve the referrer's reward out of the rebate portion (stays in the pool ATA)
+        if let Some(referrer) = user_stats.referrer {
+            let referral_stats = ctx.accounts.referral_stats
+                .as_mut()
+                .ok_or(ErrorCode::ReferralAccountMissing)?;
+            require!(referral_stats.referrer == referrer, ErrorCode::InvalidReferrer);
 
-        let seeds: &[&[u8]] = &[DAT_AUTHORITY_SEED, &[state.dat_authority_bump]];
+            let reward = ctx.accounts.rebate_pool.referral_reward(rebate_pool_amount);
+            referral_stats.credit(received, reward);
+            ctx.accounts.rebate_pool.referral_reserved =
+                ctx.accounts.rebate_pool.referral_reserved.saturating_add(reward);
 
-        invoke_signed(
-            &ix,
-            &[
-                ctx.accounts.mint.to_account_info(),
-                ctx.accounts.mint_authority.to_account_info(),
-                ctx.accounts.bonding_curve.to_account_info(),
-                ctx.accounts.associated_bonding_curve.to_account_info(),
-                ctx.accounts.global.to_account_info(),
-                ctx.accounts.dat_authority.to_account_info(),
-                ctx.accounts.system_program.to_account_info(),
-                ctx.accounts.token_2022_program.to_account_info(),
-                ctx.accounts.associated_token_program.to_account_info(),
-                // Mayhem accounts (required even for non-mayhem mode)
-                ctx.accounts.mayhem_program.to_account_info(),
-                ctx.accounts.global_params.to_account_info(),
-                ctx.accounts.sol_vault.to_account_info(),
-                ctx.accounts.mayhem_state.to_account_info(),
-                ctx.accounts.mayhem_token_vault.to_account_info(),
-                ctx.accounts.event_authority.to_account_info(),
-                ctx.accounts.pump_program.to_account_info(),
-            ],
-            &[seeds],
-        )?;
+            emit!(ReferralRewardCredited {
+                referrer,
+                user: user_key,
+                deposit_amount: received,
+                reward,
+                pending_rewards: referral_stats.pending_rewards,
+                timestamp: clock.unix_timestamp,
+            });
+        }
 
-        msg!("Token2022 token created successfully (standard mode)!");
+        if let Some(rebate_epoch) = ctx.accounts.rebate_epoch.as_mut() {
+            // Pro-rata model: credit the open epoch instead of pending_contribution
+            require!(rebate_epoch.is_open(), ErrorCode::RebateEpochClosed);
+
+            // One contribution slot per user: settle a closed epoch before rolling over
+            if user_stats.has_unclaimed_epoch(rebate_epoch.epoch) {
+                let previous = ctx.accounts.previous_rebate_epoch
+                    .as_mut()
+                    .ok_or(ErrorCode::UnclaimedEpochRebate)?;
+                let user_ata = ctx.accounts.user_ata
+                    .as_ref()
+                    .ok_or(ErrorCode::UnclaimedEpochRebate)?;
+                let (contribution, rebate_amount) = settle_epoch_rebate(
+                    &ctx.accounts.token_program,
+                    &ctx.accounts.asdf_mint,
+                    &mut ctx.accounts.rebate_pool,
+                    previous,
+                    &ctx.accounts.rebate_pool_ata,
+                    user_ata,
+                    user_stats,
+                )?;
+
+                emit!(EpochRebateClaimed {
+                    user: user_key,
+                    epoch: previous.epoch,
+                    contribution,
+                    rebate_amount,
+                    timestamp: clock.unix_timestamp,
+                });
+            }
 
-        emit!(TokenCreated {
-            mint: ctx.accounts.mint.key(),
-            bonding_curve: ctx.accounts.bonding_curve.key(),
-            creator: ctx.accounts.dat_authority.key(),
-            name,
-            symbol,
-            timestamp: Clock::get()?.unix_timestamp,
+            if user_stats.contribution_epoch != rebate_epoch.epoch {
+                user_stats.contribution_epoch = rebate_epoch.epoch;
+                user_stats.epoch_contribution = 0;
+            }
+            user_stats.epoch_contribution = user_stats.epoch_contribution.saturating_add(received);
+            rebate_epoch.total_contributions = rebate_epoch.total_contributions.saturating_add(received);
+        } else {
+            // Track the full amount received for rebate calculation
+            user_stats.pending_contribution = user_stats.pending_contribution.saturating_add(received);
+        }
+        user_stats.last_update_timestamp = clock.unix_timestamp;
+        user_stats.last_update_slot = clock.slot;
+
+        // One-time onboarding bonus (rebate pool → user ATA)
+        // Eligibility is only consumed once a bonus is actually paid
+        if !user_stats.onboarding_bonus_claimed {
+            if let Some(user_ata) = ctx.accounts.user_ata.as_ref() {
+                let available = ctx.accounts.rebate_pool.available(ctx.accounts.rebate_pool_ata.amount);
+                let bonus = ctx.accounts.rebate_pool.onboarding_bonus(received, available);
+                if bonus > 0 {
+                    let rebate_pool_bump = ctx.accounts.rebate_pool.bump;
+                    let seeds: &[&[u8]] = &[REBATE_POOL_SEED, &[rebate_pool_bump]];
+                    token_interface::transfer_checked(
+                        CpiContext::new_with_signer(
+                            ctx.accounts.token_program.to_account_info(),
+                            token_interface::TransferChecked {
+                                from: ctx.accounts.rebate_pool_ata.to_account_info(),
+                                mint: ctx.accounts.asdf_mint.to_account_info(),
+                                to: user_ata.to_account_info(),
+                                authority: ctx.accounts.rebate_pool.to_account_info(),
+                            },
+                            &[seeds],
+                        ),
+                        bonus,
+                        decimals,
+                    )?;
+
+                    let rebate_pool = &mut ctx.accounts.rebate_pool;
+                    rebate_pool.bonus_budget = rebate_pool.bonus_budget.saturating_sub(bonus);
+                    rebate_pool.total_bonus_paid = rebate_pool.total_bonus_paid.saturating_add(bonus);
+                    user_stats.onboarding_bonus_claimed = true;
+
+                    emit!(OnboardingBonusPaid {
+                        user: user_key,
+                        deposit_amount: amount,
+                        bonus_amount: bonus,
+                        remaining_budget: rebate_pool.bonus_budget,
+                        timestamp: clock.unix_timestamp,
+                    });
+                }
+            }
+        }
+
+        emit!(FeeAsdfDeposited {
+            user: user_key,
+            amount,
+            burn_amount,
+            rebate_pool_amount,
+            pending_contribution: user_stats.pending_contribution,
+            app_id,
+            timestamp: clock.unix_timestamp,
         });
 
+        // Display-ready receipt: wallets render this without re-implementing split math
+        let projected_rebate = user_stats.pending_contribution
+            .checked_mul(ctx.accounts.rebate_pool.rebate_share() as u64)
+            .ok_or(ErrorCode::MathOverflow)?
+            / SHARE_DENOMINATOR;
+        emit!(FeeAsdfDepositedV2 {
+            user: user_key,
+            amount,
+            burn_amount,
+            rebate_pool_amount,
+            burn_bps_effective: calculate_share_bps(burn_amount, amount),
+            rebate_bps_effective: calculate_share_bps(rebate_pool_amount, amount),
+            pending_contribution: user_stats.pending_contribution,
+            projected_rebate,
+            projected_cycle_share_bps: calculate_share_bps(burn_amount, ctx.accounts.dat_asdf_account.amount),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("Fee deposited: {} total ({} burn, {} rebate pool)",
+            amount, burn_amount, rebate_pool_amount);
+
         Ok(())
     }
 
-    /// Create a PumpFun token in Mayhem Mode with AI trading agent
-    /// Uses Token2022 and create_v2 instruction
-    /// Supply: 2 billion tokens (1B + 1B for agent)
-    pub fn create_pumpfun_token_mayhem(
-        ctx: Context<CreatePumpfunTokenMayhem>,
-        name: String,
-        symbol: String,
-        uri: String,
-    ) -> Result<()> {
-        let state = &ctx.accounts.dat_state;
+    /// Name the referrer credited on the caller's deposits (user only, once)
+    pub fn set_referrer(ctx: Context<SetReferrer>) -> Result<()> {
+        let clock = Clock::get()?;
+        let user_key = ctx.accounts.user.key();
+        let referrer = ctx.accounts.referrer.key();
+        require!(referrer != user_key && referrer != Pubkey::default(), ErrorCode::InvalidReferrer);
 
-        msg!("Creating PumpFun token in MAYHEM MODE via CPI");
-        msg!("Name: {}, Symbol: {}, Creator: {}", name, symbol, ctx.accounts.dat_authority.key());
-        msg!("Mayhem Mode: AI agent will trade for 24h");
+        let user_stats_key = ctx.accounts.user_stats.key();
+        let user_stats = &mut ctx.accounts.user_stats;
 
-        let mut data = Vec::new();
+        // Check if newly initialized (user == default)
+        if user_stats.user == Pubkey::default() {
+            user_stats.bump = ctx.bumps.user_stats;
+            user_stats.user = user_key;
+            user_stats.pending_contribution = 0;
+            user_stats.total_contributed = 0;
+            user_stats.total_rebate = 0;
+            user_stats.onboarding_bonus_claimed = false;
+            user_stats.contribution_epoch = 0;
+            user_stats.epoch_contribution = 0;
+            user_stats.app_id = 0;
+            user_stats.referrer = None;
+            user_stats.version = USER_STATS_VERSION;
 
-        // Discriminator for create_v2: [214, 144, 76, 236, 95, 139, 49, 180]
-        data.extend_from_slice(&[214, 144, 76, 236, 95, 139, 49, 180]);
+            emit!(UserStatsInitialized {
+                user: user_key,
+                user_stats: user_stats_key,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+        require!(user_stats.referrer.is_none(), ErrorCode::ReferrerAlreadySet);
+        user_stats.referrer = Some(referrer);
 
-        // Name (String)
-        data.extend_from_slice(&(name.len() as u32).to_le_bytes());
-        data.extend_from_slice(name.as_bytes());
+        let referral_stats = &mut ctx.accounts.referral_stats;
+        if referral_stats.referrer == Pubkey::default() {
+            referral_stats.referrer = referrer;
+            referral_stats.bump = ctx.bumps.referral_stats;
+        }
+        referral_stats.referred_users = referral_stats.referred_users.saturating_add(1);
 
-        // Symbol (String)
-        data.extend_from_slice(&(symbol.len() as u32).to_le_bytes());
-        data.extend_from_slice(symbol.as_bytes());
+        emit!(ReferrerSet {
+            user: user_key,
+            referrer,
+            referred_users: referral_stats.referred_users,
+            timestamp: clock.unix_timestamp,
+        });
 
-        // URI (String)
-        data.extend_from_slice(&(uri.len() as u32).to_le_bytes());
-        data.extend_from_slice(uri.as_bytes());
+        Ok(())
+    }
 
-        // Creator (Pubkey)
-        data.extend_from_slice(&ctx.accounts.dat_authority.key().to_bytes());
+    /// Claim credited referral rewards from the rebate pool (referrer only)
+    pub fn claim_referral_rewards(ctx: Context<ClaimReferralRewards>) -> Result<()> {
+        let amount = ctx.accounts.referral_stats.pending_rewards;
+        require!(amount > 0, ErrorCode::NoReferralRewards);
 
-        // is_mayhem_mode (bool - 1 byte)
-        data.extend_from_slice(&[1u8]); // true for Mayhem Mode
+        let seeds: &[&[u8]] = &[REBATE_POOL_SEED, &[ctx.accounts.rebate_pool.bump]];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.rebate_pool_ata.to_account_info(),
+                    mint: ctx.accounts.asdf_mint.to_account_info(),
+                    to: ctx.accounts.referrer_ata.to_account_info(),
+                    authority: ctx.accounts.rebate_pool.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+            ctx.accounts.asdf_mint.decimals,
+        )?;
 
-        let accounts = vec![
-            AccountMeta::new(ctx.accounts.mint.key(), true),
-            AccountMeta::new_readonly(ctx.accounts.mint_authority.key(), false),
-            AccountMeta::new(ctx.accounts.bonding_curve.key(), false),
-            AccountMeta::new(ctx.accounts.associated_bonding_curve.key(), false),
-            AccountMeta::new_readonly(ctx.accounts.global.key(), false),
-            AccountMeta::new(ctx.accounts.dat_authority.key(), true), // user/creator
-            AccountMeta::new_readonly(ctx.accounts.system_program.key(), false),
-            AccountMeta::new_readonly(ctx.accounts.token_2022_program.key(), false),
-            AccountMeta::new_readonly(ctx.accounts.associated_token_program.key(), false),
-            AccountMeta::new(ctx.accounts.mayhem_program.key(), false),
-            AccountMeta::new_readonly(ctx.accounts.global_params.key(), false),
-            AccountMeta::new(ctx.accounts.sol_vault.key(), false),
-            AccountMeta::new(ctx.accounts.mayhem_state.key(), false),
-            AccountMeta::new(ctx.accounts.mayhem_token_vault.key(), false),
-            AccountMeta::new_readonly(ctx.accounts.event_authority.key(), false),
-            AccountMeta::new_readonly(ctx.accounts.pump_program.key(), false),
-        ];
+        let rebate_pool = &mut ctx.accounts.rebate_pool;
+        rebate_pool.referral_reserved = rebate_pool.referral_reserved.saturating_sub(amount);
+        rebate_pool.total_distributed = rebate_pool.total_distributed.saturating_add(amount);
 
-        let ix = Instruction {
-            program_id: PUMP_PROGRAM,
-            accounts,
-            data,
-        };
+        let referral_stats = &mut ctx.accounts.referral_stats;
+        referral_stats.pending_rewards = 0;
+        referral_stats.total_claimed = referral_stats.total_claimed.saturating_add(amount);
 
-        let seeds: &[&[u8]] = &[DAT_AUTHORITY_SEED, &[state.dat_authority_bump]];
+        emit!(ReferralRewardsClaimed {
+            referrer: referral_stats.referrer,
+            amount,
+            total_claimed: referral_stats.total_claimed,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-        invoke_signed(
-            &ix,
-            &[
-                ctx.accounts.mint.to_account_info(),
-                ctx.accounts.mint_authority.to_account_info(),
-                ctx.accounts.bonding_curve.to_account_info(),
-                ctx.accounts.associated_bonding_curve.to_account_info(),
-                ctx.accounts.global.to_account_info(),
-                ctx.accounts.dat_authority.to_account_info(),
-                ctx.accounts.system_program.to_account_info(),
-                ctx.accounts.token_2022_program.to_account_info(),
-                ctx.accounts.associated_token_program.to_account_info(),
-                ctx.accounts.mayhem_program.to_account_info(),
-                ctx.accounts.global_params.to_account_info(),
-                ctx.accounts.sol_vault.to_account_info(),
-                ctx.accounts.mayhem_state.to_account_info(),
-                ctx.accounts.mayhem_token_vault.to_account_info(),
-                ctx.accounts.event_authority.to_account_info(),
-                ctx.accounts.pump_program.to_account_info(),
-            ],
-            &[seeds],
-        )?;
+        Ok(())
+    }
 
-        msg!("Mayhem Mode token created successfully!");
-        msg!("Supply: 2 billion tokens (1B base + 1B for AI agent)");
+    /// Set the referrer share of referred deposits' rebate portion (admin only, 0 disables)
+    /// Timelocked: queues the change for execute_change after admin_operation_cooldown
+    pub fn set_referral_share(ctx: Context<SetReferralShare>, referral_share_bps: u16) -> Result<()> {
+        RebatePool::clone(&ctx.accounts.rebate_pool).apply_parameter(ADMIN_OP_REFERRAL_SHARE, referral_share_bps as u64)?;
+        let state = ctx.accounts.dat_state.load()?;
+        let delay = state.admin_operation_cooldown;
+        queue_change(&mut ctx.accounts.change_queue, &state, ADMIN_OP_REFERRAL_SHARE, Pubkey::default(), referral_share_bps as u64, delay)?;
+        Ok(())
+    }
 
-        emit!(TokenCreated {
-            mint: ctx.accounts.mint.key(),
-            bonding_curve: ctx.accounts.bonding_curve.key(),
-            creator: ctx.accounts.dat_authority.key(),
-            name,
-            symbol,
-            timestamp: Clock::get()?.unix_timestamp,
+    /// Register an integrating app (admin only)
+    /// Deposits passing the app's AppStats are attributed to it in deposit_fee_asdf
+    pub fn register_app(
+        ctx: Context<RegisterApp>,
+        app_id: u32,
+        authority: Pubkey,
+        fee_share_bps: u16,
+    ) -> Result<()> {
+        require!(app_id != 0, ErrorCode::InvalidParameter);
+        require!(fee_share_bps <= 10000, ErrorCode::InvalidParameter);
+        let clock = Clock::get()?;
+
+        let registry = &mut ctx.accounts.app_registry;
+        registry.bump = ctx.bumps.app_registry;
+        registry.app_count = registry.app_count.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+
+        let app = &mut ctx.accounts.app_stats;
+        app.app_id = app_id;
+        app.authority = authority;
+        app.fee_share_bps = fee_share_bps;
+        app.registered_at = clock.unix_timestamp;
+        app.bump = ctx.bumps.app_stats;
+
+        emit!(AppRegistered {
+            app_id,
+            authority,
+            fee_share_bps,
+            app_stats: app.key(),
+            app_count: registry.app_count,
+            timestamp: clock.unix_timestamp,
         });
 
         Ok(())
     }
 
-    /// Transfer 1% dev sustainability fee
-    /// Called at the end of each batch transaction, after burn succeeds
-    /// 1% today = 99% burns forever
-    pub fn transfer_dev_fee(ctx: Context<TransferDevFee>, secondary_share: u64) -> Result<()> {
-        // Calculate 1% of secondary share
-        let dev_fee = secondary_share
-            .checked_mul(DEV_FEE_BPS as u64)
+    /// External app deposits SOL fees with automatic split
+    /// Split: 99.448% → root treasury (bought and burned by the next ROOT cycle),
+    /// 0.552% → rebate pool PDA (converted to $ASDF via withdraw_rebate_sol)
+    /// UserStats.pending_contribution is credited with the $ASDF equivalent of the
+    /// full amount at the last observed root price, like deposit_fee_asdf
+    pub fn deposit_fee_sol(ctx: Context<DepositFeeSol>, amount: u64) -> Result<()> {
+        use anchor_lang::solana_program::program::invoke;
+
+        let clock = Clock::get()?;
+
+        // Validate minimum deposit
+        require!(amount >= MIN_DEPOSIT_SOL_EQUIV, ErrorCode::DepositBelowMinimum);
+
+        let root_mint = ctx.accounts.dat_state.load()?.root_token_mint().ok_or(ErrorCode::InvalidRootToken)?;
+        let (expected_treasury, _) = Pubkey::find_program_address(
+            &[ROOT_TREASURY_SEED, root_mint.as_ref()],
+            ctx.program_id
+        );
+        require!(expected_treasury == ctx.accounts.root_treasury.key(), ErrorCode::InvalidRootTreasury);
+
+        let asdf_equivalent = sol_to_token_amount(amount, ctx.accounts.dat_state.load()?.last_known_price)?;
+
+        // Same split as $ASDF deposits
+        let burn_lamports = amount
+            .checked_mul(ctx.accounts.rebate_pool.burn_share() as u64)
             .ok_or(ErrorCode::MathOverflow)?
-            .checked_div(10000)
+            .checked_div(SHARE_DENOMINATOR)
             .ok_or(ErrorCode::MathOverflow)?;
+        let rebate_lamports = amount.saturating_sub(burn_lamports);
 
-        if dev_fee > 0 {
-            let bump = ctx.accounts.dat_state.dat_authority_bump;
-            let seeds: &[&[u8]] = &[DAT_AUTHORITY_SEED, &[bump]];
+        // Transfer 99.448% → root treasury (collected into the next ROOT cycle buy)
+        invoke(
+            &anchor_lang::solana_program::system_instruction::transfer(ctx.accounts.payer.key, ctx.accounts.root_treasury.key, burn_lamports),
+            &[
+                ctx.accounts.payer.to_account_info(),
+                ctx.accounts.root_treasury.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+        )?;
 
-            invoke_signed(
+        // Transfer 0.552% → rebate pool PDA
+        if rebate_lamports > 0 {
+            invoke(
                 &anchor_lang::solana_program::system_instruction::transfer(
-                    ctx.accounts.dat_authority.key,
-                    ctx.accounts.dev_wallet.key,
-                    dev_fee,
+                    ctx.accounts.payer.key,
+                    &ctx.accounts.rebate_pool.key(),
+                    rebate_lamports,
                 ),
                 &[
-                    ctx.accounts.dat_authority.to_account_info(),
-                    ctx.accounts.dev_wallet.to_account_info(),
+                    ctx.accounts.payer.to_account_info(),
+                    ctx.accounts.rebate_pool.to_account_info(),
                     ctx.accounts.system_program.to_account_info(),
                 ],
-                &[seeds],
             )?;
+        }
+
+        let user_key = ctx.accounts.user.key();
+        let user_stats_key = ctx.accounts.user_stats.key();
+        let user_stats = &mut ctx.accounts.user_stats;
+
+        // Check if newly initialized (user == default)
+        if user_stats.user == Pubkey::default() {
+            user_stats.bump = ctx.bumps.user_stats;
+            user_stats.user = user_key;
+            user_stats.pending_contribution = 0;
+            user_stats.total_contributed = 0;
+            user_stats.total_rebate = 0;
+            user_stats.onboarding_bonus_claimed = false;
+            user_stats.contribution_epoch = 0;
+            user_stats.epoch_contribution = 0;
+            user_stats.app_id = 0;
+            user_stats.referrer = None;
+            user_stats.version = USER_STATS_VERSION;
+
+            emit!(UserStatsInitialized {
+                user: user_key,
+                user_stats: user_stats_key,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+
+        // Track $ASDF-equivalent amount for rebate calculation
+        user_stats.pending_contribution = user_stats.pending_contribution.saturating_add(asdf_equivalent);
+        user_stats.last_update_timestamp = clock.unix_timestamp;
+        user_stats.last_update_slot = clock.slot;
+
+        emit!(FeeSolDeposited {
+            user: user_key,
+            amount_lamports: amount,
+            burn_lamports,
+            rebate_lamports,
+            asdf_equivalent,
+            pending_contribution: user_stats.pending_contribution,
+            timestamp: clock.unix_timestamp,
+        });
 
-            msg!("Dev sustainability fee: {} lamports", dev_fee);
-        }
+        msg!("SOL fee deposited: {} lamports ({} buyback, {} rebate pool)",
+            amount, burn_lamports, rebate_lamports);
 
         Ok(())
     }
 
-    // ══════════════════════════════════════════════════════════════════════════════
-    // EXTERNAL APP INTEGRATION
-    // ══════════════════════════════════════════════════════════════════════════════
+    /// Withdraw SOL accrued in the rebate pool PDA (admin only)
+    /// The admin swaps it to $ASDF and deposits the result into the rebate pool ATA;
+    /// the PDA always keeps its rent-exempt minimum
+    pub fn withdraw_rebate_sol(ctx: Context<WithdrawRebateSol>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidParameter);
 
-    /// Initialize the self-sustaining rebate pool
-    /// Called once during protocol setup
-    pub fn initialize_rebate_pool(ctx: Context<InitializeRebatePool>) -> Result<()> {
-        let rebate_pool = &mut ctx.accounts.rebate_pool;
+        let pool_info = ctx.accounts.rebate_pool.to_account_info();
+        let min_balance = Rent::get()?.minimum_balance(pool_info.data_len());
+        require!(
+            pool_info.lamports().saturating_sub(min_balance) >= amount,
+            ErrorCode::InsufficientFees
+        );
+
+        **pool_info.try_borrow_mut_lamports()? -= amount;
+        **ctx.accounts.destination.try_borrow_mut_lamports()? += amount;
+
+        emit!(RebateSolWithdrawn {
+            destination: ctx.accounts.destination.key(),
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// External app deposits fees in any non-$ASDF SPL token
+    /// The tokens are escrowed per (user, mint) until the orchestrator converts them to $ASDF
+    /// with convert_token_deposit, which applies the standard burn/rebate split.
+    /// min_asdf_out is the depositor's floor on the conversion (0 = no floor)
+    pub fn deposit_fee_token(ctx: Context<DepositFeeToken>, amount: u64, min_asdf_out: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidParameter);
         let clock = Clock::get()?;
 
-        rebate_pool.bump = ctx.bumps.rebate_pool;
-        rebate_pool.total_deposited = 0;
-        rebate_pool.total_distributed = 0;
-        rebate_pool.rebates_count = 0;
-        rebate_pool.last_rebate_timestamp = 0;
-        rebate_pool.last_rebate_slot = 0;
-        rebate_pool.unique_recipients = 0;
-        rebate_pool._reserved = [0u8; 32];
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.payer_token_account.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.deposit_vault.to_account_info(),
+                    authority: ctx.accounts.payer.to_account_info(),
+                },
+            ),
+            amount,
+            ctx.accounts.mint.decimals,
+        )?;
 
-        emit!(RebatePoolInitialized {
-            rebate_pool: ctx.accounts.rebate_pool.key(),
-            rebate_pool_ata: Pubkey::default(), // ATA created separately
+        let deposit = &mut ctx.accounts.token_deposit;
+        if deposit.user == Pubkey::default() {
+            deposit.user = ctx.accounts.user.key();
+            deposit.mint = ctx.accounts.mint.key();
+            deposit.bump = ctx.bumps.token_deposit;
+        }
+        deposit.amount = deposit.amount.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+        deposit.min_asdf_out = deposit.min_asdf_out.saturating_add(min_asdf_out);
+        deposit.deposited_at = clock.unix_timestamp;
+
+        emit!(TokenFeeEscrowed {
+            user: deposit.user,
+            mint: deposit.mint,
+            amount,
+            min_asdf_out,
+            escrowed_total: deposit.amount,
             timestamp: clock.unix_timestamp,
         });
 
-        msg!("Rebate pool initialized");
         Ok(())
     }
 
-    /// External app deposits $ASDF fees with automatic split
-    /// Split: 99.448% → DAT ATA (burn), 0.552% → Rebate Pool ATA (rebates)
-    ///
-    /// Architecture:
-    /// - Payer transfers full amount
-    /// - 99.448% goes to DAT ATA (included in ROOT cycle single burn)
-    /// - 0.552% goes to Rebate Pool ATA (self-sustaining fund)
-    /// - UserStats.pending_contribution tracks full amount for rebate calculation
-    pub fn deposit_fee_asdf(
-        ctx: Context<DepositFeeAsdf>,
-        amount: u64,
-    ) -> Result<()> {
+    /// Convert an escrowed token deposit to $ASDF (admin only)
+    /// Releases the escrow to the admin and takes asdf_amount from the admin's $ASDF account:
+    /// 99.448% → DAT ATA (root cycle burn), 0.552% → rebate pool ATA,
+    /// and credits the user's pending_contribution like deposit_fee_asdf
+    pub fn convert_token_deposit(ctx: Context<ConvertTokenDeposit>, asdf_amount: u64) -> Result<()> {
         let clock = Clock::get()?;
+        let token_amount = ctx.accounts.token_deposit.amount;
+        require!(token_amount > 0, ErrorCode::NoTokenDeposit);
+        require!(asdf_amount > 0, ErrorCode::InvalidParameter);
+        require!(asdf_amount >= ctx.accounts.token_deposit.min_asdf_out, ErrorCode::SlippageExceeded);
 
-        // Validate minimum deposit
-        require!(amount >= MIN_DEPOSIT_SOL_EQUIV, ErrorCode::DepositBelowMinimum);
+        let bump = ctx.accounts.dat_state.load()?.dat_authority_bump;
+        let seeds: &[&[u8]] = &[DAT_AUTHORITY_SEED, &[bump]];
 
-        // Calculate split (99.448% burn, 0.552% rebate)
-        // Using ÷100000 for exact precision
-        let burn_amount = amount
-            .checked_mul(BURN_SHARE as u64)
+        // Release escrow → admin (swapped off-chain)
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.deposit_vault.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.admin_token_account.to_account_info(),
+                    authority: ctx.accounts.dat_authority.to_account_info(),
+                },
+                &[seeds],
+            ),
+            token_amount,
+            ctx.accounts.mint.decimals,
+        )?;
+
+        let burn_amount = asdf_amount
+            .checked_mul(ctx.accounts.rebate_pool.burn_share() as u64)
             .ok_or(ErrorCode::MathOverflow)?
             .checked_div(SHARE_DENOMINATOR)
             .ok_or(ErrorCode::MathOverflow)?;
-        let rebate_pool_amount = amount.saturating_sub(burn_amount);
+        let rebate_pool_amount = asdf_amount.saturating_sub(burn_amount);
 
         // Transfer 99.448% → DAT ATA (for burn)
-        token_interface::transfer(
+        token_interface::transfer_checked(
             CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
-                token_interface::Transfer {
-                    from: ctx.accounts.payer_token_account.to_account_info(),
+                ctx.accounts.asdf_token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.admin_asdf_account.to_account_info(),
+                    mint: ctx.accounts.asdf_mint.to_account_info(),
                     to: ctx.accounts.dat_asdf_account.to_account_info(),
-                    authority: ctx.accounts.payer.to_account_info(),
+                    authority: ctx.accounts.admin.to_account_info(),
                 },
             ),
             burn_amount,
+            ctx.accounts.asdf_mint.decimals,
         )?;
 
         // Transfer 0.552% → Rebate Pool ATA (for rebates)
         if rebate_pool_amount > 0 {
-            token_interface::transfer(
+            token_interface::transfer_checked(
                 CpiContext::new(
-                    ctx.accounts.token_program.to_account_info(),
-                    token_interface::Transfer {
-                        from: ctx.accounts.payer_token_account.to_account_info(),
+                    ctx.accounts.asdf_token_program.to_account_info(),
+                    token_interface::TransferChecked {
+                        from: ctx.accounts.admin_asdf_account.to_account_info(),
+                        mint: ctx.accounts.asdf_mint.to_account_info(),
                         to: ctx.accounts.rebate_pool_ata.to_account_info(),
-                        authority: ctx.accounts.payer.to_account_info(),
+                        authority: ctx.accounts.admin.to_account_info(),
                     },
                 ),
                 rebate_pool_amount,
+                ctx.accounts.asdf_mint.decimals,
             )?;
         }
 
-        // Update rebate pool stats
-        let rebate_pool = &mut ctx.accounts.rebate_pool;
-        rebate_pool.total_deposited = rebate_pool.total_deposited.saturating_add(rebate_pool_amount);
+        let user_key = ctx.accounts.token_deposit.user;
+        let mint = ctx.accounts.token_deposit.mint;
+        ctx.accounts.token_deposit.clear();
 
-        // Get keys before mutable borrow
-        let user_key = ctx.accounts.user.key();
         let user_stats_key = ctx.accounts.user_stats.key();
-
-        // Initialize or update user stats
         let user_stats = &mut ctx.accounts.user_stats;
 
         // Check if newly initialized (user == default)
@@ -1814,6 +8011,12 @@ pub mod asdf_dat {
             user_stats.pending_contribution = 0;
             user_stats.total_contributed = 0;
             user_stats.total_rebate = 0;
+            user_stats.onboarding_bonus_claimed = false;
+            user_stats.contribution_epoch = 0;
+            user_stats.epoch_contribution = 0;
+            user_stats.app_id = 0;
+            user_stats.referrer = None;
+            user_stats.version = USER_STATS_VERSION;
 
             emit!(UserStatsInitialized {
                 user: user_key,
@@ -1822,23 +8025,365 @@ pub mod asdf_dat {
             });
         }
 
-        // Track full amount for rebate calculation
-        user_stats.pending_contribution = user_stats.pending_contribution.saturating_add(amount);
+        user_stats.pending_contribution = user_stats.pending_contribution.saturating_add(asdf_amount);
         user_stats.last_update_timestamp = clock.unix_timestamp;
         user_stats.last_update_slot = clock.slot;
 
-        emit!(FeeAsdfDeposited {
+        emit!(TokenDepositConverted {
             user: user_key,
-            amount,
+            mint,
+            token_amount,
+            asdf_amount,
             burn_amount,
             rebate_pool_amount,
             pending_contribution: user_stats.pending_contribution,
             timestamp: clock.unix_timestamp,
         });
 
-        msg!("Fee deposited: {} total ({} burn, {} rebate pool)",
-            amount, burn_amount, rebate_pool_amount);
+        Ok(())
+    }
+
+    /// Reclaim an unconverted token deposit (user only)
+    /// Available TOKEN_DEPOSIT_REFUND_DELAY after the latest deposit
+    pub fn refund_token_deposit(ctx: Context<RefundTokenDeposit>) -> Result<()> {
+        let clock = Clock::get()?;
+        let amount = ctx.accounts.token_deposit.amount;
+        require!(amount > 0, ErrorCode::NoTokenDeposit);
+        require!(
+            ctx.accounts.token_deposit.refund_unlocked(clock.unix_timestamp, TOKEN_DEPOSIT_REFUND_DELAY),
+            ErrorCode::TokenDepositLocked
+        );
+
+        let bump = ctx.accounts.dat_state.load()?.dat_authority_bump;
+        let seeds: &[&[u8]] = &[DAT_AUTHORITY_SEED, &[bump]];
+
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.deposit_vault.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.user_token_account.to_account_info(),
+                    authority: ctx.accounts.dat_authority.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+            ctx.accounts.mint.decimals,
+        )?;
+
+        ctx.accounts.token_deposit.clear();
+
+        emit!(TokenDepositRefunded {
+            user: ctx.accounts.user.key(),
+            mint: ctx.accounts.mint.key(),
+            amount,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Configure the first-deposit onboarding bonus (admin only)
+    /// bonus_bps = 0 disables it; bonus_budget replaces the remaining global budget
+    /// Timelocked: queues rate, cap and budget for execute_change after admin_operation_cooldown
+    pub fn configure_onboarding_bonus(
+        ctx: Context<ConfigureOnboardingBonus>,
+        bonus_bps: u16,
+        bonus_cap: u64,
+        bonus_budget: u64,
+    ) -> Result<()> {
+        let mut preview = RebatePool::clone(&ctx.accounts.rebate_pool);
+        let state = ctx.accounts.dat_state.load()?;
+        let delay = state.admin_operation_cooldown;
+        let queue = &mut ctx.accounts.change_queue;
+        for (param, value) in [
+            (ADMIN_OP_ONBOARDING_BONUS_BPS, bonus_bps as u64),
+            (ADMIN_OP_ONBOARDING_BONUS_CAP, bonus_cap),
+            (ADMIN_OP_ONBOARDING_BONUS_BUDGET, bonus_budget),
+        ] {
+            preview.apply_parameter(param, value)?;
+            queue_change(queue, &state, param, Pubkey::default(), value, delay)?;
+        }
+        Ok(())
+    }
+
+    /// Propose new rebate economics (admin only, subject to timelock)
+    /// rebate_share is over SHARE_DENOMINATOR; the burn share is its complement
+    pub fn update_rebate_params(
+        ctx: Context<UpdateRebateParams>,
+        rebate_share: u32,
+        rebate_threshold: u64,
+    ) -> Result<()> {
+        require!(
+            RebatePool::rebate_params_valid(rebate_share, rebate_threshold),
+            ErrorCode::InvalidRebateParams
+        );
+
+        let clock = Clock::get()?;
+        let rebate_pool = &mut ctx.accounts.rebate_pool;
+        rebate_pool.pending_rebate_share = rebate_share;
+        rebate_pool.pending_rebate_threshold = rebate_threshold;
+        rebate_pool.pending_params_timestamp = clock.unix_timestamp;
+
+        emit!(RebateParamsProposed {
+            rebate_share,
+            rebate_threshold,
+            executable_at: clock.unix_timestamp.saturating_add(ctx.accounts.dat_state.load()?.admin_operation_cooldown),
+        });
+        Ok(())
+    }
+
+    /// Apply pending rebate economics (admin only, after cooldown period)
+    pub fn apply_rebate_params(ctx: Context<UpdateRebateParams>) -> Result<()> {
+        let rebate_pool = &mut ctx.accounts.rebate_pool;
+        let clock = Clock::get()?;
+
+        require!(rebate_pool.pending_params_timestamp != 0, ErrorCode::NoPendingRebateParams);
+        ctx.accounts.dat_state.load()?.require_current_epoch(rebate_pool.pending_params_timestamp)?;
+
+        let elapsed = clock.unix_timestamp.saturating_sub(rebate_pool.pending_params_timestamp);
+        require!(
+            elapsed >= ctx.accounts.dat_state.load()?.admin_operation_cooldown,
+            ErrorCode::CycleTooSoon // Reusing existing error for timelock
+        );
+
+        let old_rebate_share = rebate_pool.rebate_share();
+        let old_rebate_threshold = rebate_pool.rebate_threshold();
+
+        rebate_pool.rebate_share = rebate_pool.pending_rebate_share;
+        rebate_pool.rebate_threshold = rebate_pool.pending_rebate_threshold;
+        rebate_pool.pending_rebate_share = 0;
+        rebate_pool.pending_rebate_threshold = 0;
+        rebate_pool.pending_params_timestamp = 0;
+
+        emit!(RebateParamsUpdated {
+            old_rebate_share,
+            new_rebate_share: rebate_pool.rebate_share,
+            old_rebate_threshold,
+            new_rebate_threshold: rebate_pool.rebate_threshold,
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Commit a weighted rebate draw over candidate UserStats PDAs (admin only)
+    /// The draw is seeded by the bank hash of a slot DRAW_DELAY_SLOTS ahead,
+    /// unknown at request time; anyone resolves it with select_rebate_winner
+    pub fn request_rebate_draw(ctx: Context<RequestRebateDraw>, candidates: Vec<Pubkey>) -> Result<()> {
+        require!(
+            !candidates.is_empty() && candidates.len() <= MAX_DRAW_CANDIDATES,
+            ErrorCode::InvalidParameter
+        );
+
+        let clock = Clock::get()?;
+        let rebate_pool = &mut ctx.accounts.rebate_pool;
+        require!(!rebate_pool.draw_pending(clock.slot), ErrorCode::DrawPending);
+
+        rebate_pool.draw_request_slot = clock.slot;
+        rebate_pool.draw_target_slot = clock.slot.saturating_add(DRAW_DELAY_SLOTS);
+        rebate_pool.draw_candidates_hash = draw_candidates_hash(&candidates);
+
+        emit!(RebateDrawRequested {
+            draw: rebate_pool.draws_count,
+            candidates: candidates.len() as u32,
+            candidates_hash: rebate_pool.draw_candidates_hash,
+            target_slot: rebate_pool.draw_target_slot,
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Resolve the pending rebate draw (permissionless)
+    /// Picks a candidate weighted by pending_contribution (candidates below the
+    /// rebate threshold weigh 0) and records the winner and seed in the RebatePool.
+    /// The winner is then paid by process_user_rebate (or claims it).
+    /// remaining_accounts: the committed candidate UserStats, in committed order
+    pub fn select_rebate_winner<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SelectRebateWinner<'info>>,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let rebate_pool = &mut ctx.accounts.rebate_pool;
+
+        require!(rebate_pool.draw_request_slot != 0, ErrorCode::NoDrawPending);
+        require!(clock.slot > rebate_pool.draw_target_slot, ErrorCode::DrawNotReady);
+
+        let candidate_keys: Vec<Pubkey> = ctx.remaining_accounts.iter().map(|a| a.key()).collect();
+        require!(
+            draw_candidates_hash(&candidate_keys) == rebate_pool.draw_candidates_hash,
+            ErrorCode::DrawCandidatesMismatch
+        );
+
+        let (_, slot_hash) = {
+            let data = ctx.accounts.slot_hashes.try_borrow_data()?;
+            slot_hash_at_or_after(&data, rebate_pool.draw_target_slot).ok_or(ErrorCode::DrawExpired)?
+        };
+
+        // Weights are frozen at request time: a deposit after the request would
+        // let a candidate react to the revealed slot hash
+        let threshold = rebate_pool.rebate_threshold();
+        let mut users = Vec::with_capacity(ctx.remaining_accounts.len());
+        let mut weights = Vec::with_capacity(ctx.remaining_accounts.len());
+        for info in ctx.remaining_accounts.iter() {
+            let user_stats: Account<'info, UserStats> = Account::try_from(info)?;
+            require!(
+                user_stats.last_update_slot <= rebate_pool.draw_request_slot,
+                ErrorCode::DrawCandidateChanged
+            );
+            users.push(user_stats.user);
+            weights.push(if user_stats.pending_contribution >= threshold {
+                user_stats.pending_contribution
+            } else {
+                0
+            });
+        }
+
+        let seed = draw_seed(&slot_hash, &rebate_pool.draw_candidates_hash, rebate_pool.draws_count);
+        let winner_index = weighted_index(&weights, &seed).ok_or(ErrorCode::NoEligibleCandidates)?;
+        let total_weight = weights.iter().fold(0u64, |acc, w| acc.saturating_add(*w));
+
+        let draw = rebate_pool.draws_count;
+        let target_slot = rebate_pool.draw_target_slot;
+        rebate_pool.draw_randomness = seed;
+        rebate_pool.draw_winner = users[winner_index];
+        rebate_pool.draws_count = draw.saturating_add(1);
+        rebate_pool.draw_request_slot = 0;
+        rebate_pool.draw_target_slot = 0;
+
+        emit!(RebateWinnerSelected {
+            draw,
+            winner: users[winner_index],
+            winner_weight: weights[winner_index],
+            total_weight,
+            target_slot,
+            randomness: seed,
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Open the first pro-rata rebate epoch (admin only, once)
+    pub fn initialize_rebate_epoch(ctx: Context<InitializeRebateEpoch>) -> Result<()> {
+        let clock = Clock::get()?;
+        let rebate_pool = &mut ctx.accounts.rebate_pool;
+        let rebate_epoch = &mut ctx.accounts.rebate_epoch;
+
+        rebate_epoch.epoch = rebate_pool.current_epoch;
+        rebate_epoch.total_contributions = 0;
+        rebate_epoch.pool_amount = 0;
+        rebate_epoch.claimed_amount = 0;
+        rebate_epoch.started_at = clock.unix_timestamp;
+        rebate_epoch.closed_at = 0;
+        rebate_epoch.bump = ctx.bumps.rebate_epoch;
+        rebate_pool.epoch_started_at = clock.unix_timestamp;
+
+        emit!(RebateEpochOpened {
+            epoch: rebate_epoch.epoch,
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Close the open rebate epoch and open the next (permissionless after REBATE_EPOCH_DURATION)
+    /// Reserves the rebate share of the epoch's contributions, bounded by the
+    /// unreserved pool balance, for pro-rata claims
+    pub fn close_rebate_epoch(ctx: Context<CloseRebateEpoch>) -> Result<()> {
+        let clock = Clock::get()?;
+        let rebate_pool = &mut ctx.accounts.rebate_pool;
+        let rebate_epoch = &mut ctx.accounts.rebate_epoch;
+
+        require!(rebate_epoch.is_open(), ErrorCode::RebateEpochClosed);
+        require!(
+            clock.unix_timestamp.saturating_sub(rebate_epoch.started_at) >= REBATE_EPOCH_DURATION,
+            ErrorCode::RebateEpochActive
+        );
+
+        let earned = (rebate_epoch.total_contributions as u128 * rebate_pool.rebate_share() as u128
+            / SHARE_DENOMINATOR as u128) as u64;
+        let pool_amount = earned.min(rebate_pool.available(ctx.accounts.rebate_pool_ata.amount));
+
+        rebate_epoch.pool_amount = pool_amount;
+        rebate_epoch.closed_at = clock.unix_timestamp;
+        rebate_pool.epoch_reserved = rebate_pool.epoch_reserved.saturating_add(pool_amount);
+        rebate_pool.current_epoch = rebate_pool.current_epoch.saturating_add(1);
+        rebate_pool.epoch_started_at = clock.unix_timestamp;
+
+        let next = &mut ctx.accounts.next_rebate_epoch;
+        next.epoch = rebate_pool.current_epoch;
+        next.total_contributions = 0;
+        next.pool_amount = 0;
+        next.claimed_amount = 0;
+        next.started_at = clock.unix_timestamp;
+        next.closed_at = 0;
+        next.bump = ctx.bumps.next_rebate_epoch;
+
+        emit!(RebateEpochClosed {
+            epoch: rebate_epoch.epoch,
+            total_contributions: rebate_epoch.total_contributions,
+            pool_amount,
+            epoch_reserved: rebate_pool.epoch_reserved,
+            timestamp: clock.unix_timestamp,
+        });
+        emit!(RebateEpochOpened {
+            epoch: next.epoch,
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Claim the pro-rata share of the user's closed contribution epoch
+    pub fn claim_epoch_rebate(ctx: Context<ClaimEpochRebate>) -> Result<()> {
+        let clock = Clock::get()?;
+        let user_stats = &mut ctx.accounts.user_stats;
+
+        let (contribution, rebate_amount) = settle_epoch_rebate(
+            &ctx.accounts.token_program,
+            &ctx.accounts.asdf_mint,
+            &mut ctx.accounts.rebate_pool,
+            &mut ctx.accounts.rebate_epoch,
+            &ctx.accounts.rebate_pool_ata,
+            &ctx.accounts.user_ata,
+            user_stats,
+        )?;
+        user_stats.last_update_timestamp = clock.unix_timestamp;
+        user_stats.last_update_slot = clock.slot;
+
+        emit!(EpochRebateClaimed {
+            user: ctx.accounts.user.key(),
+            epoch: ctx.accounts.rebate_epoch.epoch,
+            contribution,
+            rebate_amount,
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Migrate the rebate pool created before tunable rebate params / draws / epochs existed
+    /// New fields stay zero, which resolves to the constant defaults
+    pub fn migrate_rebate_pool(ctx: Context<MigrateRebatePool>) -> Result<()> {
+        migrations::migrate(
+            AccountKind::RebatePool,
+            &ctx.accounts.rebate_pool,
+            &ctx.accounts.admin.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &migrations::MigrationEnv::default(),
+        )?;
+        Ok(())
+    }
+
+    /// Migrate a UserStats account created before onboarding_bonus_claimed / epoch fields existed
+    /// Permissionless (payer covers the extra rent). V1 users already made
+    /// their first deposit, so they are marked as claimed.
+    pub fn migrate_user_stats(ctx: Context<MigrateUserStats>) -> Result<()> {
+        let previous_size = migrations::migrate(
+            AccountKind::UserStats,
+            &ctx.accounts.user_stats,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &migrations::MigrationEnv::default(),
+        )?;
 
+        msg!("UserStats migrated for {} (from {} bytes)", ctx.accounts.user.key(), previous_size);
         Ok(())
     }
 
@@ -1858,59 +8403,52 @@ pub mod asdf_dat {
         let clock = Clock::get()?;
         let user_stats = &mut ctx.accounts.user_stats;
 
-        // Validate: pending >= threshold
-        require!(
-            user_stats.pending_contribution >= REBATE_THRESHOLD_SOL_EQUIV,
-            ErrorCode::BelowRebateThreshold
-        );
+        let (pending, rebate_amount) = settle_user_rebate(
+            &ctx.accounts.token_program,
+            &ctx.accounts.asdf_mint,
+            &mut ctx.accounts.rebate_pool,
+            &ctx.accounts.rebate_pool_ata,
+            &ctx.accounts.user_ata,
+            user_stats,
+            ctx.accounts.stake_account.as_deref(),
+            &clock,
+        )?;
 
-        let pending = user_stats.pending_contribution;
+        emit!(UserRebateProcessed {
+            user: ctx.accounts.user.key(),
+            pending_burned: pending,
+            rebate_amount,
+            total_contributed: user_stats.total_contributed,
+            total_rebate: user_stats.total_rebate,
+            timestamp: clock.unix_timestamp,
+        });
 
-        // Calculate rebate amount (0.552% of pending)
-        let rebate_amount = pending
-            .checked_mul(REBATE_SHARE as u64)
-            .ok_or(ErrorCode::MathOverflow)?
-            .checked_div(SHARE_DENOMINATOR)
-            .ok_or(ErrorCode::MathOverflow)?;
+        msg!("Rebate processed: {} pending → {} rebate to user",
+            pending, rebate_amount);
 
-        // Validate pool has sufficient funds
-        require!(
-            ctx.accounts.rebate_pool_ata.amount >= rebate_amount,
-            ErrorCode::RebatePoolInsufficient
-        );
+        Ok(())
+    }
 
-        // Transfer rebate from pool → user ATA
-        let rebate_pool_bump = ctx.accounts.rebate_pool.bump;
-        let seeds: &[&[u8]] = &[REBATE_POOL_SEED, &[rebate_pool_bump]];
+    /// Claim rebate - user pulls their own rebate from the pool (permissionless)
+    ///
+    /// Same eligibility and amount as process_user_rebate, but signed by the
+    /// user instead of the admin, so rebates never wait on an admin batch.
+    pub fn claim_rebate(ctx: Context<ClaimRebate>) -> Result<()> {
+        let clock = Clock::get()?;
+        let user_stats = &mut ctx.accounts.user_stats;
 
-        token_interface::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                token_interface::Transfer {
-                    from: ctx.accounts.rebate_pool_ata.to_account_info(),
-                    to: ctx.accounts.user_ata.to_account_info(),
-                    authority: ctx.accounts.rebate_pool.to_account_info(),
-                },
-                &[seeds],
-            ),
-            rebate_amount,
+        let (pending, rebate_amount) = settle_user_rebate(
+            &ctx.accounts.token_program,
+            &ctx.accounts.asdf_mint,
+            &mut ctx.accounts.rebate_pool,
+            &ctx.accounts.rebate_pool_ata,
+            &ctx.accounts.user_ata,
+            user_stats,
+            ctx.accounts.stake_account.as_deref(),
+            &clock,
         )?;
 
-        // Update user stats
-        user_stats.pending_contribution = 0;
-        user_stats.total_contributed = user_stats.total_contributed.saturating_add(pending);
-        user_stats.total_rebate = user_stats.total_rebate.saturating_add(rebate_amount);
-        user_stats.last_update_timestamp = clock.unix_timestamp;
-        user_stats.last_update_slot = clock.slot;
-
-        // Update rebate pool stats
-        let rebate_pool = &mut ctx.accounts.rebate_pool;
-        rebate_pool.total_distributed = rebate_pool.total_distributed.saturating_add(rebate_amount);
-        rebate_pool.rebates_count = rebate_pool.rebates_count.saturating_add(1);
-        rebate_pool.last_rebate_timestamp = clock.unix_timestamp;
-        rebate_pool.last_rebate_slot = clock.slot;
-
-        emit!(UserRebateProcessed {
+        emit!(UserRebateClaimed {
             user: ctx.accounts.user.key(),
             pending_burned: pending,
             rebate_amount,
@@ -1919,8 +8457,7 @@ pub mod asdf_dat {
             timestamp: clock.unix_timestamp,
         });
 
-        msg!("Rebate processed: {} pending → {} rebate to user",
-            pending, rebate_amount);
+        msg!("Rebate claimed: {} pending → {} rebate", pending, rebate_amount);
 
         Ok(())
     }
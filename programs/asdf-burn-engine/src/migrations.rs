@@ -0,0 +1,677 @@
+//! Versioned account migrations
+//!
+//! Each migratable account kind carries a `version: u8`. Layouts older than the field
+//! are recognized by their size, so the upgrade path is always chosen from the account
+//! size. MIGRATIONS lists every known layout of every kind with the step that upgrades it
+//! to the next one. `migrate` reallocs once to the current size, runs the steps in order
+//! on the zero-extended data and stamps the current version.
+//!
+//! Adding a field to a registered kind: append it (after `version`), bump the kind's *_VERSION constant and
+//! register one step from the previous size. A kind joins the table with its first layout
+//! change (add `version` and the steps from its original size).
+//!
+//! Zero-copy kinds (DATState from V19, TokenStats from V13) have a repr(C) layout instead of Borsh: their
+//! last Borsh layout is read through a frozen copy of the struct (e.g. TokenStatsV12) and
+//! relaid out once; later fields take bytes from the struct's `_reserved` padding or are
+//! appended after it.
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke;
+use anchor_lang::Discriminator;
+use anchor_spl::token;
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::helpers::parse_mint_risk_flags;
+use crate::state::{ChangeQueue, DATState, PendingChange, RebatePool, TokenConfig, TokenStats, UserStats};
+
+/// Account kinds registered in MIGRATIONS
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccountKind {
+    DatState,
+    TokenStats,
+    TokenConfig,
+    RebatePool,
+    UserStats,
+    ChangeQueue,
+}
+
+/// Inputs a step may need besides the account data
+#[derive(Clone, Copy, Default)]
+pub struct MigrationEnv {
+    /// Migration time (TokenStats V2 last_fee_update_timestamp)
+    pub now: i64,
+    /// MINT_RISK_* flags of the stats' mint (TokenStats V3; None when no mint was passed)
+    pub mint_risk_flags: Option<u8>,
+}
+
+/// One layout upgrade. `upgrade` receives the whole account data, already resized to the
+/// current layout; bytes past `from_size` arrive zeroed
+pub struct MigrationStep {
+    pub kind: AccountKind,
+    /// Account size (discriminator included) of the layout this step upgrades
+    pub from_size: usize,
+    /// Account size of the layout it produces
+    pub to_size: usize,
+    pub upgrade: fn(&mut [u8], &MigrationEnv) -> Result<()>,
+}
+
+const fn step(
+    kind: AccountKind,
+    from_size: usize,
+    to_size: usize,
+    upgrade: fn(&mut [u8], &MigrationEnv) -> Result<()>,
+) -> MigrationStep {
+    MigrationStep { kind, from_size, to_size, upgrade }
+}
+
+/// Every known layout of every registered kind, oldest first
+pub const MIGRATIONS: &[MigrationStep] = &[
+    // DATState
+    step(AccountKind::DatState, 382, 390, dat_state_v2),          // + last_direct_fee_split_timestamp
+    step(AccountKind::DatState, 390, 392, dat_state_v3),          // + max_price_deviation_bps
+    step(AccountKind::DatState, 392, 425, dat_state_v4),          // + cycle_lock
+    step(AccountKind::DatState, 425, 445, dat_state_v5),          // + governance epoch fields
+    step(AccountKind::DatState, 445, 453, dat_state_v6),          // + event_sequence
+    step(AccountKind::DatState, 453, 469, dat_state_v7),          // + auto-resume fields
+    step(AccountKind::DatState, 469, 478, dat_state_v8),          // + failure escalation window
+    step(AccountKind::DatState, 478, 479, dat_state_v9),          // + auto_pause_failure_threshold
+    step(AccountKind::DatState, 479, 487, dat_state_v10),         // + root_escrow_lamports
+    step(AccountKind::DatState, 487, 563, dat_state_v11),         // + dev fee / dev wallet
+    step(AccountKind::DatState, 563, 565, dat_state_v12),         // + reserve_bps
+    step(AccountKind::DatState, 565, 567, dat_state_v13),         // + insurance_bps
+    step(AccountKind::DatState, 567, 568, dat_state_v14),         // + randomized_timing
+    step(AccountKind::DatState, 568, 576, dat_state_v15),         // + max_jito_tip_lamports
+    step(AccountKind::DatState, 576, 608, dat_state_v16),         // + cycle_lut
+    step(AccountKind::DatState, 608, 609, zero_filled),           // + version
+    step(AccountKind::DatState, 609, 641, dat_state_v18),         // + pending_import_hash
+    step(AccountKind::DatState, 641, 648, dat_state_v19),         // zero-copy relayout
+    // TokenStats (fixed layout: every field at a fixed offset)
+    step(AccountKind::TokenStats, 114, 138, token_stats_v2),      // + pending fees, cycles_participated
+    step(AccountKind::TokenStats, 138, 192, token_stats_v3),      // + burn ratio, risk flags, rotation, category
+    step(AccountKind::TokenStats, 192, 225, zero_filled),         // + stats_payer (unknown), payer_refunded
+    step(AccountKind::TokenStats, 225, 309, zero_filled),         // + listing partner fields
+    step(AccountKind::TokenStats, 309, 325, zero_filled),         // + dust sweeping
+    step(AccountKind::TokenStats, 325, 334, zero_filled),         // + failure isolation
+    step(AccountKind::TokenStats, 334, 336, zero_filled),         // + reflection_bps
+    step(AccountKind::TokenStats, 336, 346, zero_filled),         // + liquidity seeding
+    step(AccountKind::TokenStats, 346, 347, zero_filled),         // + version
+    step(AccountKind::TokenStats, 347, 350, zero_filled),         // + registry slot (unregistered)
+    step(AccountKind::TokenStats, 350, 358, zero_filled),         // + partner_paid_pending
+    step(AccountKind::TokenStats, 358, 360, token_stats_v13),     // zero-copy relayout
+    // TokenConfig
+    step(AccountKind::TokenConfig, 145, 177, zero_filled),        // + assigned_root (primary root)
+    step(AccountKind::TokenConfig, 177, 178, zero_filled),        // + version
+    step(AccountKind::TokenConfig, 178, 218, zero_filled),        // + original_creator, onboarded_at (not onboarded)
+    step(AccountKind::TokenConfig, 218, 226, zero_filled),        // + offboard_requested_at (not requested)
+    // RebatePool (zero = constant defaults, nothing pending, no draws or epochs, no referrals)
+    step(AccountKind::RebatePool, 89, 121, zero_filled),          // + tunable rebate params
+    step(AccountKind::RebatePool, 121, 241, zero_filled),         // + weighted rebate draw
+    step(AccountKind::RebatePool, 241, 265, zero_filled),         // + pro-rata rebate epochs
+    step(AccountKind::RebatePool, 265, 275, zero_filled),         // + referrals
+    step(AccountKind::RebatePool, 275, 276, zero_filled),         // + version
+    // UserStats
+    step(AccountKind::UserStats, 81, 82, user_stats_v2),          // + onboarding_bonus_claimed
+    step(AccountKind::UserStats, 82, 98, zero_filled),            // + contribution epoch fields
+    step(AccountKind::UserStats, 98, 102, zero_filled),           // + app_id
+    step(AccountKind::UserStats, 102, 135, zero_filled),          // + referrer (None)
+    step(AccountKind::UserStats, 135, 136, zero_filled),          // + version
+    // ChangeQueue
+    step(AccountKind::ChangeQueue, 577, 1090, change_queue_v2),    // + change subjects, version
+];
+
+impl AccountKind {
+    pub fn discriminator(self) -> &'static [u8] {
+        match self {
+            AccountKind::DatState => DATState::DISCRIMINATOR,
+            AccountKind::TokenStats => TokenStats::DISCRIMINATOR,
+            AccountKind::TokenConfig => TokenConfig::DISCRIMINATOR,
+            AccountKind::RebatePool => RebatePool::DISCRIMINATOR,
+            AccountKind::UserStats => UserStats::DISCRIMINATOR,
+            AccountKind::ChangeQueue => ChangeQueue::DISCRIMINATOR,
+        }
+    }
+
+    /// Account size of the current layout (discriminator included)
+    pub fn current_size(self) -> usize {
+        8 + match self {
+            AccountKind::DatState => DATState::LEN,
+            AccountKind::TokenStats => TokenStats::LEN,
+            AccountKind::TokenConfig => TokenConfig::LEN,
+            AccountKind::RebatePool => RebatePool::LEN,
+            AccountKind::UserStats => UserStats::LEN,
+            AccountKind::ChangeQueue => ChangeQueue::LEN,
+        }
+    }
+
+    pub fn current_version(self) -> u8 {
+        match self {
+            AccountKind::DatState => DAT_STATE_VERSION,
+            AccountKind::TokenStats => TOKEN_STATS_VERSION,
+            AccountKind::TokenConfig => TOKEN_CONFIG_VERSION,
+            AccountKind::RebatePool => REBATE_POOL_VERSION,
+            AccountKind::UserStats => USER_STATS_VERSION,
+            AccountKind::ChangeQueue => CHANGE_QUEUE_VERSION,
+        }
+    }
+
+    /// Steps from a layout of `size` bytes to the current one (empty when already current)
+    pub fn steps_from(self, size: usize) -> Result<Vec<&'static MigrationStep>> {
+        let mut steps = Vec::new();
+        let mut at = size;
+        while at != self.current_size() {
+            let step = MIGRATIONS
+                .iter()
+                .find(|s| s.kind == self && s.from_size == at)
+                .ok_or(ErrorCode::AccountSizeMismatch)?;
+            steps.push(step);
+            at = step.to_size;
+        }
+        Ok(steps)
+    }
+
+    /// Write the current version (Borsh round trip: UserStats ends with an Option, so the
+    /// field has no fixed offset; zero-copy kinds are copied out and back)
+    fn stamp_version(self, data: &mut [u8]) -> Result<()> {
+        let version = self.current_version();
+        match self {
+            AccountKind::DatState => update_pod::<DATState>(data, |a| a.version = version),
+            AccountKind::TokenStats => update_pod::<TokenStats>(data, |a| a.version = version),
+            AccountKind::TokenConfig => update::<TokenConfig>(data, |a| a.version = version),
+            AccountKind::RebatePool => update::<RebatePool>(data, |a| a.version = version),
+            AccountKind::UserStats => update::<UserStats>(data, |a| a.version = version),
+            AccountKind::ChangeQueue => update::<ChangeQueue>(data, |a| a.version = version),
+        }
+    }
+}
+
+/// Upgrade account data of `from_size` bytes (zero-extended to the current size) in place
+pub fn apply_steps(kind: AccountKind, data: &mut [u8], from_size: usize, env: &MigrationEnv) -> Result<usize> {
+    require!(data.len() == kind.current_size(), ErrorCode::AccountSizeMismatch);
+    let steps = kind.steps_from(from_size)?;
+    for step in &steps {
+        (step.upgrade)(data, env)?;
+    }
+    kind.stamp_version(data)?;
+    Ok(steps.len())
+}
+
+/// Realloc `account` to the current layout of `kind` and upgrade it; `payer` tops up rent
+/// Returns the previous size (equal to the current size when nothing was done)
+pub fn migrate<'info>(
+    kind: AccountKind,
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    env: &MigrationEnv,
+) -> Result<usize> {
+    require!(account.owner == &crate::ID, ErrorCode::InvalidAccountOwner);
+    let current_size = account.data_len();
+    require!(
+        current_size >= 8 && account.try_borrow_data()?[..8] == *kind.discriminator(),
+        ErrorCode::MigrationKindMismatch
+    );
+
+    let new_size = kind.current_size();
+    if current_size >= new_size {
+        msg!("{:?} already migrated (size: {})", kind, current_size);
+        return Ok(current_size);
+    }
+    // Fail on an unknown layout before moving any lamports
+    kind.steps_from(current_size)?;
+
+    let new_lamports = Rent::get()?.minimum_balance(new_size);
+    let current_lamports = account.lamports();
+    if new_lamports > current_lamports {
+        invoke(
+            &anchor_lang::solana_program::system_instruction::transfer(payer.key, account.key, new_lamports - current_lamports),
+            &[payer.clone(), account.clone(), system_program.clone()],
+        )?;
+    }
+
+    account.resize(new_size).map_err(|_| ErrorCode::AccountSizeMismatch)?;
+    let mut data = account.try_borrow_mut_data()?;
+    data[current_size..].fill(0);
+    let steps = apply_steps(kind, &mut data[..], current_size, env)?;
+
+    msg!("{:?} migrated from {} to {} bytes ({} steps)", kind, current_size, new_size, steps);
+    Ok(current_size)
+}
+
+/// Admin stored in raw DATState data (first field, so readable from every layout)
+pub fn dat_state_admin(dat_state: &AccountInfo) -> Result<Pubkey> {
+    let data = dat_state.try_borrow_data()?;
+    require!(data.len() >= 40, ErrorCode::AccountSizeMismatch);
+    Pubkey::try_from(&data[8..40]).map_err(|_| error!(ErrorCode::InvalidParameter))
+}
+
+/// MINT_RISK_* flags of a mint (0 for accounts not owned by a token program)
+pub fn mint_risk_flags(mint: &AccountInfo) -> Result<u8> {
+    let is_token_2022 = mint.owner == &TOKEN_2022_PROGRAM;
+    if is_token_2022 || mint.owner == &token::ID {
+        parse_mint_risk_flags(&mint.try_borrow_data()?, is_token_2022)
+    } else {
+        Ok(0)
+    }
+}
+
+/// Byte offset of DATState.cycle_lock in account data (discriminator included)
+/// Walks the Borsh Option tags before it (None = 1 byte, Some = 1 + payload), since
+/// bytes past the serialized length may be stale after an Option was cleared
+pub fn dat_state_cycle_lock_offset(data: &[u8]) -> Result<usize> {
+    // Fixed-size prefix: discriminator through pending_burn_amount
+    const ROOT_TOKEN_MINT_OFFSET: usize = 287;
+
+    let option_len = |at: usize, payload: usize| -> Result<usize> {
+        match data.get(at) {
+            Some(0) => Ok(1),
+            Some(1) => Ok(1 + payload),
+            _ => err!(ErrorCode::AccountSizeMismatch),
+        }
+    };
+
+    let mut at = ROOT_TOKEN_MINT_OFFSET;
+    at += option_len(at, 32)?;  // root_token_mint
+    at += 2 + 8;                // fee_split_bps, last_sol_sent_to_root
+    at += option_len(at, 32)?;  // pending_admin
+    at += option_len(at, 2)?;   // pending_fee_split
+    at += 8 * 3 + 2;            // fee split timestamps, admin_operation_cooldown, max_price_deviation_bps
+    require!(at < data.len(), ErrorCode::AccountSizeMismatch);
+
+    Ok(at)
+}
+
+/// Borsh round trip: deserialize, edit, serialize back in place
+fn update<T: AccountSerialize + AccountDeserialize>(data: &mut [u8], edit: impl FnOnce(&mut T)) -> Result<()> {
+    let mut account = T::try_deserialize(&mut &data[..])?;
+    edit(&mut account);
+    account.try_serialize(&mut &mut data[..])?;
+    Ok(())
+}
+
+/// Borsh round trip on a frozen layout without discriminator impls (data[8..])
+fn update_borsh<T: AnchorSerialize + AnchorDeserialize>(data: &mut [u8], edit: impl FnOnce(&mut T)) -> Result<()> {
+    let mut account = T::deserialize(&mut &data[8..])?;
+    edit(&mut account);
+    account.serialize(&mut &mut data[8..])?;
+    Ok(())
+}
+
+/// Zero-copy round trip: copy out, edit, copy back (the data need not be 8-byte aligned)
+fn update_pod<T: bytemuck::Pod>(data: &mut [u8], edit: impl FnOnce(&mut T)) -> Result<()> {
+    let body = data.get_mut(8..8 + std::mem::size_of::<T>()).ok_or(ErrorCode::AccountSizeMismatch)?;
+    let mut account: T = bytemuck::pod_read_unaligned(body);
+    edit(&mut account);
+    body.copy_from_slice(bytemuck::bytes_of(&account));
+    Ok(())
+}
+
+/// New fields default to zero, which the zero-extended data already holds
+fn zero_filled(_data: &mut [u8], _env: &MigrationEnv) -> Result<()> {
+    Ok(())
+}
+
+// DATState up to V18: Borsh encodes a None option in 1 byte, so a field's offset depends on
+// which options are set and the bytes after the serialized struct may be stale. Each step
+// sets its fields through a round trip of the frozen V18 layout instead of writing offsets
+// (fields a step predates deserialize from the zero-extended tail).
+
+/// DATState V18, the last Borsh layout (frozen: read by the V2..V19 steps)
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct DatStateV18 {
+    pub admin: Pubkey,
+    pub asdf_mint: Pubkey,
+    pub wsol_mint: Pubkey,
+    pub pool_address: Pubkey,
+    pub pump_swap_program: Pubkey,
+    pub total_burned: u64,
+    pub total_sol_collected: u64,
+    pub total_buybacks: u32,
+    pub failed_cycles: u32,
+    pub consecutive_failures: u8,
+    pub is_active: bool,
+    pub emergency_pause: bool,
+    pub last_cycle_timestamp: i64,
+    pub initialized_at: i64,
+    pub last_am_execution: i64,
+    pub last_pm_execution: i64,
+    pub last_cycle_sol: u64,
+    pub last_cycle_burned: u64,
+    pub min_fees_threshold: u64,
+    pub max_fees_per_cycle: u64,
+    pub slippage_bps: u16,
+    pub min_cycle_interval: i64,
+    pub dat_authority_bump: u8,
+    pub current_fee_recipient_index: u8,
+    pub last_known_price: u64,
+    pub pending_burn_amount: u64,
+    pub root_token_mint: Option<Pubkey>,
+    pub fee_split_bps: u16,
+    pub last_sol_sent_to_root: u64,
+    pub pending_admin: Option<Pubkey>,
+    pub pending_fee_split: Option<u16>,
+    pub pending_fee_split_timestamp: i64,
+    pub admin_operation_cooldown: i64,
+    pub last_direct_fee_split_timestamp: i64,
+    pub max_price_deviation_bps: u16,
+    pub cycle_lock: Option<Pubkey>,
+    pub governance_epoch: u32,
+    pub governance_epoch_started_at: i64,
+    pub last_upgrade_slot: u64,
+    pub event_sequence: u64,
+    pub auto_resume_after: i64,
+    pub auto_paused_at: i64,
+    pub failure_window_start: i64,
+    pub failing_tokens_in_window: u8,
+    pub auto_pause_failure_threshold: u8,
+    pub root_escrow_lamports: u64,
+    pub dev_fee_bps: u16,
+    pub pending_dev_fee_bps: u16,
+    pub pending_dev_fee_timestamp: i64,
+    pub dev_wallet: Pubkey,
+    pub pending_dev_wallet: Pubkey,
+    pub reserve_bps: u16,
+    pub insurance_bps: u16,
+    pub randomized_timing: bool,
+    pub max_jito_tip_lamports: u64,
+    pub cycle_lut: Pubkey,
+    pub version: u8,
+    pub pending_import_hash: [u8; 32],
+}
+
+/// Layouts before cycle_lock: the byte where its tag lands may be stale
+fn clear_cycle_lock_tag(data: &mut [u8]) -> Result<()> {
+    let at = dat_state_cycle_lock_offset(data)?;
+    data[at] = 0;
+    Ok(())
+}
+
+fn dat_state_v2(data: &mut [u8], _env: &MigrationEnv) -> Result<()> {
+    clear_cycle_lock_tag(data)?;
+    update_borsh::<DatStateV18>(data, |s| s.last_direct_fee_split_timestamp = 0)
+}
+
+fn dat_state_v3(data: &mut [u8], _env: &MigrationEnv) -> Result<()> {
+    clear_cycle_lock_tag(data)?;
+    update_borsh::<DatStateV18>(data, |s| s.max_price_deviation_bps = DEFAULT_MAX_PRICE_DEVIATION_BPS)
+}
+
+fn dat_state_v4(data: &mut [u8], _env: &MigrationEnv) -> Result<()> {
+    clear_cycle_lock_tag(data)?;
+    update_borsh::<DatStateV18>(data, |s| s.cycle_lock = None)
+}
+
+fn dat_state_v5(data: &mut [u8], _env: &MigrationEnv) -> Result<()> {
+    update_borsh::<DatStateV18>(data, |s| {
+        s.governance_epoch = 0;
+        s.governance_epoch_started_at = 0;
+        s.last_upgrade_slot = 0;
+    })
+}
+
+fn dat_state_v6(data: &mut [u8], _env: &MigrationEnv) -> Result<()> {
+    update_borsh::<DatStateV18>(data, |s| s.event_sequence = 0)
+}
+
+fn dat_state_v7(data: &mut [u8], _env: &MigrationEnv) -> Result<()> {
+    update_borsh::<DatStateV18>(data, |s| {
+        s.auto_resume_after = 0;
+        s.auto_paused_at = 0;
+    })
+}
+
+fn dat_state_v8(data: &mut [u8], _env: &MigrationEnv) -> Result<()> {
+    update_borsh::<DatStateV18>(data, |s| {
+        s.failure_window_start = 0;
+        s.failing_tokens_in_window = 0;
+    })
+}
+
+fn dat_state_v9(data: &mut [u8], _env: &MigrationEnv) -> Result<()> {
+    update_borsh::<DatStateV18>(data, |s| s.auto_pause_failure_threshold = DEFAULT_AUTO_PAUSE_FAILURE_THRESHOLD)
+}
+
+fn dat_state_v10(data: &mut [u8], _env: &MigrationEnv) -> Result<()> {
+    update_borsh::<DatStateV18>(data, |s| s.root_escrow_lamports = 0)
+}
+
+fn dat_state_v11(data: &mut [u8], _env: &MigrationEnv) -> Result<()> {
+    update_borsh::<DatStateV18>(data, |s| {
+        s.dev_fee_bps = DEV_FEE_BPS;
+        s.pending_dev_fee_bps = 0;
+        s.pending_dev_fee_timestamp = 0;
+        s.dev_wallet = DEV_WALLET;
+        s.pending_dev_wallet = Pubkey::default();
+    })
+}
+
+fn dat_state_v12(data: &mut [u8], _env: &MigrationEnv) -> Result<()> {
+    update_borsh::<DatStateV18>(data, |s| s.reserve_bps = 0)
+}
+
+fn dat_state_v13(data: &mut [u8], _env: &MigrationEnv) -> Result<()> {
+    update_borsh::<DatStateV18>(data, |s| s.insurance_bps = 0)
+}
+
+fn dat_state_v14(data: &mut [u8], _env: &MigrationEnv) -> Result<()> {
+    update_borsh::<DatStateV18>(data, |s| s.randomized_timing = false)
+}
+
+fn dat_state_v15(data: &mut [u8], _env: &MigrationEnv) -> Result<()> {
+    update_borsh::<DatStateV18>(data, |s| s.max_jito_tip_lamports = 0)
+}
+
+fn dat_state_v16(data: &mut [u8], _env: &MigrationEnv) -> Result<()> {
+    update_borsh::<DatStateV18>(data, |s| s.cycle_lut = Pubkey::default())
+}
+
+fn dat_state_v18(data: &mut [u8], _env: &MigrationEnv) -> Result<()> {
+    update_borsh::<DatStateV18>(data, |s| s.pending_import_hash = [0; 32])
+}
+
+/// DATState V19: the Borsh V18 fields relaid out for zero-copy (Options as default values,
+/// flags as 0 / 1). Stale bytes past the Borsh encoding are overwritten
+fn dat_state_v19(data: &mut [u8], _env: &MigrationEnv) -> Result<()> {
+    let v18 = DatStateV18::deserialize(&mut &data[8..])?;
+    let state = DATState {
+        admin: v18.admin,
+        asdf_mint: v18.asdf_mint,
+        wsol_mint: v18.wsol_mint,
+        pool_address: v18.pool_address,
+        pump_swap_program: v18.pump_swap_program,
+        root_token_mint: v18.root_token_mint.unwrap_or_default(),
+        pending_admin: v18.pending_admin.unwrap_or_default(),
+        cycle_lock: v18.cycle_lock.unwrap_or_default(),
+        dev_wallet: v18.dev_wallet,
+        pending_dev_wallet: v18.pending_dev_wallet,
+        cycle_lut: v18.cycle_lut,
+        pending_import_hash: v18.pending_import_hash,
+        total_burned: v18.total_burned,
+        total_sol_collected: v18.total_sol_collected,
+        last_cycle_timestamp: v18.last_cycle_timestamp,
+        initialized_at: v18.initialized_at,
+        last_am_execution: v18.last_am_execution,
+        last_pm_execution: v18.last_pm_execution,
+        last_cycle_sol: v18.last_cycle_sol,
+        last_cycle_burned: v18.last_cycle_burned,
+        min_fees_threshold: v18.min_fees_threshold,
+        max_fees_per_cycle: v18.max_fees_per_cycle,
+        min_cycle_interval: v18.min_cycle_interval,
+        last_known_price: v18.last_known_price,
+        pending_burn_amount: v18.pending_burn_amount,
+        last_sol_sent_to_root: v18.last_sol_sent_to_root,
+        pending_fee_split_timestamp: v18.pending_fee_split_timestamp,
+        admin_operation_cooldown: v18.admin_operation_cooldown,
+        last_direct_fee_split_timestamp: v18.last_direct_fee_split_timestamp,
+        governance_epoch_started_at: v18.governance_epoch_started_at,
+        last_upgrade_slot: v18.last_upgrade_slot,
+        event_sequence: v18.event_sequence,
+        auto_resume_after: v18.auto_resume_after,
+        auto_paused_at: v18.auto_paused_at,
+        failure_window_start: v18.failure_window_start,
+        root_escrow_lamports: v18.root_escrow_lamports,
+        pending_dev_fee_timestamp: v18.pending_dev_fee_timestamp,
+        max_jito_tip_lamports: v18.max_jito_tip_lamports,
+        total_buybacks: v18.total_buybacks,
+        failed_cycles: v18.failed_cycles,
+        governance_epoch: v18.governance_epoch,
+        slippage_bps: v18.slippage_bps,
+        fee_split_bps: v18.fee_split_bps,
+        pending_fee_split: v18.pending_fee_split.unwrap_or(0),
+        max_price_deviation_bps: v18.max_price_deviation_bps,
+        dev_fee_bps: v18.dev_fee_bps,
+        pending_dev_fee_bps: v18.pending_dev_fee_bps,
+        reserve_bps: v18.reserve_bps,
+        insurance_bps: v18.insurance_bps,
+        consecutive_failures: v18.consecutive_failures,
+        is_active: v18.is_active as u8,
+        emergency_pause: v18.emergency_pause as u8,
+        dat_authority_bump: v18.dat_authority_bump,
+        current_fee_recipient_index: v18.current_fee_recipient_index,
+        failing_tokens_in_window: v18.failing_tokens_in_window,
+        auto_pause_failure_threshold: v18.auto_pause_failure_threshold,
+        randomized_timing: v18.randomized_timing as u8,
+        version: v18.version,
+        _reserved: [0; 11],
+    };
+    data[8..8 + DATState::LEN].copy_from_slice(bytemuck::bytes_of(&state));
+    Ok(())
+}
+
+/// TokenStats V2: last_fee_update_timestamp = now, cycles_participated = total_buybacks
+fn token_stats_v2(data: &mut [u8], env: &MigrationEnv) -> Result<()> {
+    data[122..130].copy_from_slice(&env.now.to_le_bytes());
+    let total_buybacks: [u8; 8] = data[80..88].try_into().map_err(|_| ErrorCode::InvalidParameter)?;
+    data[130..138].copy_from_slice(&total_buybacks);
+    Ok(())
+}
+
+/// TokenStats V3: burn_bps = 100% (unchanged behavior), risk flags screened from the mint,
+/// category UNCATEGORIZED; rotation, USD value and pause stay zero
+fn token_stats_v3(data: &mut [u8], env: &MigrationEnv) -> Result<()> {
+    data[138..140].copy_from_slice(&DEFAULT_BURN_BPS.to_le_bytes());
+    data[148] = env.mint_risk_flags.ok_or(ErrorCode::MigrationMintMissing)?;
+    data[190] = UNCATEGORIZED;
+    Ok(())
+}
+
+/// TokenStats V12, the last Borsh layout (frozen: read only by token_stats_v13)
+#[derive(AnchorDeserialize)]
+pub struct TokenStatsV12 {
+    pub mint: Pubkey,
+    pub total_burned: u64,
+    pub total_sol_collected: u64,
+    pub total_sol_used: u64,
+    pub total_sol_sent_to_root: u64,
+    pub total_sol_received_from_others: u64,
+    pub total_buybacks: u64,
+    pub last_cycle_timestamp: i64,
+    pub last_cycle_sol: u64,
+    pub last_cycle_burned: u64,
+    pub is_root_token: bool,
+    pub bump: u8,
+    pub pending_fees_lamports: u64,
+    pub last_fee_update_timestamp: i64,
+    pub cycles_participated: u64,
+    pub burn_bps: u16,
+    pub total_sent_to_community_treasury: u64,
+    pub risk_flags: u8,
+    pub dat_token_account: Pubkey,
+    pub token_account_nonce: u8,
+    pub usd_value_burned: u64,
+    pub category: u8,
+    pub is_paused: bool,
+    pub stats_payer: Pubkey,
+    pub payer_refunded: bool,
+    pub partner: Pubkey,
+    pub partner_share_bps: u16,
+    pub total_partner_paid: u64,
+    pub pending_partner: Pubkey,
+    pub pending_partner_share_bps: u16,
+    pub pending_partner_timestamp: i64,
+    pub dust_threshold: u64,
+    pub dust_burned: u64,
+    pub consecutive_failures: u8,
+    pub last_failure_timestamp: i64,
+    pub reflection_bps: u16,
+    pub liquidity_bps: u16,
+    pub total_liquidity_sol: u64,
+    pub version: u8,
+    pub registry_page: u16,
+    pub in_registry: bool,
+    pub partner_paid_pending: u64,
+}
+
+/// TokenStats V13: the Borsh V12 fields relaid out for zero-copy (same values, flags as 0 / 1)
+fn token_stats_v13(data: &mut [u8], _env: &MigrationEnv) -> Result<()> {
+    let v12 = TokenStatsV12::deserialize(&mut &data[8..])?;
+    let stats = TokenStats {
+        mint: v12.mint,
+        dat_token_account: v12.dat_token_account,
+        stats_payer: v12.stats_payer,
+        partner: v12.partner,
+        pending_partner: v12.pending_partner,
+        total_burned: v12.total_burned,
+        total_sol_collected: v12.total_sol_collected,
+        total_sol_used: v12.total_sol_used,
+        total_sol_sent_to_root: v12.total_sol_sent_to_root,
+        total_sol_received_from_others: v12.total_sol_received_from_others,
+        total_buybacks: v12.total_buybacks,
+        last_cycle_timestamp: v12.last_cycle_timestamp,
+        last_cycle_sol: v12.last_cycle_sol,
+        last_cycle_burned: v12.last_cycle_burned,
+        pending_fees_lamports: v12.pending_fees_lamports,
+        last_fee_update_timestamp: v12.last_fee_update_timestamp,
+        cycles_participated: v12.cycles_participated,
+        total_sent_to_community_treasury: v12.total_sent_to_community_treasury,
+        usd_value_burned: v12.usd_value_burned,
+        total_partner_paid: v12.total_partner_paid,
+        pending_partner_timestamp: v12.pending_partner_timestamp,
+        dust_threshold: v12.dust_threshold,
+        dust_burned: v12.dust_burned,
+        last_failure_timestamp: v12.last_failure_timestamp,
+        total_liquidity_sol: v12.total_liquidity_sol,
+        partner_paid_pending: v12.partner_paid_pending,
+        burn_bps: v12.burn_bps,
+        partner_share_bps: v12.partner_share_bps,
+        pending_partner_share_bps: v12.pending_partner_share_bps,
+        reflection_bps: v12.reflection_bps,
+        liquidity_bps: v12.liquidity_bps,
+        registry_page: v12.registry_page,
+        is_root_token: v12.is_root_token as u8,
+        bump: v12.bump,
+        risk_flags: v12.risk_flags,
+        token_account_nonce: v12.token_account_nonce,
+        category: v12.category,
+        is_paused: v12.is_paused as u8,
+        payer_refunded: v12.payer_refunded as u8,
+        consecutive_failures: v12.consecutive_failures,
+        version: v12.version,
+        in_registry: v12.in_registry as u8,
+        _reserved: [0; 2],
+    };
+    data[8..8 + TokenStats::LEN].copy_from_slice(bytemuck::bytes_of(&stats));
+    Ok(())
+}
+
+/// UserStats V2: V1 users already made their first deposit, so the bonus counts as claimed
+fn user_stats_v2(data: &mut [u8], _env: &MigrationEnv) -> Result<()> {
+    data[81] = 1;
+    Ok(())
+}
+
+/// ChangeQueue V2: every slot grows by its subject (default for V1 changes, which all
+/// targeted DATState), so the slots are respaced last to first once the bump is saved
+fn change_queue_v2(data: &mut [u8], _env: &MigrationEnv) -> Result<()> {
+    const SLOTS_OFFSET: usize = 8 + 32 + 8;
+    let old_bump_at = SLOTS_OFFSET + PendingChange::V1_LEN * MAX_PENDING_CHANGES;
+    let new_bump_at = SLOTS_OFFSET + PendingChange::LEN * MAX_PENDING_CHANGES;
+
+    let bump = data[old_bump_at];
+    for slot in (0..MAX_PENDING_CHANGES).rev() {
+        let from = SLOTS_OFFSET + slot * PendingChange::V1_LEN;
+        let to = SLOTS_OFFSET + slot * PendingChange::LEN;
+        data.copy_within(from..from + PendingChange::V1_LEN, to);
+        data[to + PendingChange::V1_LEN..to + PendingChange::LEN].fill(0);
+    }
+    data[new_bump_at] = bump;
+    Ok(())
+}
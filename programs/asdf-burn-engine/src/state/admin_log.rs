@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+
+/// One admin operation (appended by the admin instructions once the AdminLog exists)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AdminLogEntry {
+    /// ADMIN_OP_* instruction tag
+    pub op: u8,
+
+    /// Account the operation targets, e.g. the new root mint (default = none)
+    pub subject: Pubkey,
+
+    /// Value before the operation (0 when not applicable)
+    pub old_value: u64,
+
+    /// Value after the operation (0 when not applicable)
+    pub new_value: u64,
+
+    /// Admin that signed the operation
+    pub signer: Pubkey,
+
+    /// Operation timestamp
+    pub timestamp: i64,
+}
+
+impl AdminLogEntry {
+    /// Serialized size: u8 (1) + 2 Pubkey (64) + 3 u64/i64 (24) = 89 bytes
+    pub const LEN: usize = 1 + 32 + 8 + 8 + 32 + 8;
+}
+
+/// Fixed-size ring buffer of the last ADMIN_LOG_LEN admin operations
+///
+/// Permanent on-chain audit trail for token holders: event history can be
+/// pruned by RPC providers, account data cannot. `head` is the slot the next
+/// entry overwrites.
+///
+/// PDA Seeds: ["admin_log_v1"]
+#[account]
+pub struct AdminLog {
+    /// Entries written since initialization (the buffer holds the last min(total, 64))
+    pub total_recorded: u64,
+
+    /// Index of the next slot to overwrite
+    pub head: u8,
+
+    /// Ring buffer slots (unwritten slots are zeroed)
+    pub entries: [AdminLogEntry; ADMIN_LOG_LEN],
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl AdminLog {
+    /// Account size: u64 (8) + u8 (1) + 64 entries (5696) + u8 (1) = 5706 bytes
+    pub const LEN: usize = 8 + 1 + AdminLogEntry::LEN * ADMIN_LOG_LEN + 1;
+
+    /// Append an entry, overwriting the oldest once the buffer is full
+    pub fn record(&mut self, entry: AdminLogEntry) {
+        self.entries[self.head as usize] = entry;
+        self.head = ((self.head as usize + 1) % ADMIN_LOG_LEN) as u8;
+        self.total_recorded = self.total_recorded.saturating_add(1);
+    }
+
+    /// Most recent entry, if any
+    pub fn latest(&self) -> Option<&AdminLogEntry> {
+        if self.total_recorded == 0 {
+            return None;
+        }
+        let idx = (self.head as usize + ADMIN_LOG_LEN - 1) % ADMIN_LOG_LEN;
+        Some(&self.entries[idx])
+    }
+
+    /// Stored entries, oldest first
+    pub fn chronological(&self) -> Vec<AdminLogEntry> {
+        let stored = self.total_recorded.min(ADMIN_LOG_LEN as u64) as usize;
+        let start = (self.head as usize + ADMIN_LOG_LEN - stored) % ADMIN_LOG_LEN;
+        (0..stored)
+            .map(|i| self.entries[(start + i) % ADMIN_LOG_LEN])
+            .collect()
+    }
+}
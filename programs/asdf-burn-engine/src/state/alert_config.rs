@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::ErrorCode;
+
+/// Operational alert thresholds checked by check_alerts()
+///
+/// Each breached threshold emits AlertRaised so webhook listeners can page
+/// without custom indexer logic. A zero threshold disables that alert.
+///
+/// PDA Seeds: ["alert_config_v1"]
+#[account]
+pub struct AlertConfig {
+    /// Alert when dat_authority holds fewer lamports than this
+    pub min_authority_balance: u64,
+
+    /// Alert when DATState.consecutive_failures exceeds this
+    pub max_consecutive_failures: u8,
+
+    /// Alert when the rebate pool $ASDF balance is below this
+    pub min_rebate_pool_balance: u64,
+
+    /// Timestamp of the last configuration change
+    pub updated_at: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl AlertConfig {
+    /// Account size: 3 u64/i64 (24) + 2 u8 (2) = 26 bytes
+    pub const LEN: usize = 8 + 1 + 8 + 8 + 1;
+
+    /// Breached alerts as (ALERT_* kind, observed value, threshold)
+    /// rebate_pool_balance is None when the rebate pool account was not supplied
+    pub fn breaches(
+        &self,
+        authority_balance: u64,
+        consecutive_failures: u8,
+        rebate_pool_balance: Option<u64>,
+    ) -> Vec<(u8, u64, u64)> {
+        let mut alerts = Vec::new();
+        if self.min_authority_balance > 0 && authority_balance < self.min_authority_balance {
+            alerts.push((ALERT_LOW_AUTHORITY_BALANCE, authority_balance, self.min_authority_balance));
+        }
+        if self.max_consecutive_failures > 0 && consecutive_failures > self.max_consecutive_failures {
+            alerts.push((
+                ALERT_CONSECUTIVE_FAILURES,
+                consecutive_failures as u64,
+                self.max_consecutive_failures as u64,
+            ));
+        }
+        if let Some(balance) = rebate_pool_balance {
+            if self.min_rebate_pool_balance > 0 && balance < self.min_rebate_pool_balance {
+                alerts.push((ALERT_LOW_REBATE_POOL, balance, self.min_rebate_pool_balance));
+            }
+        }
+        alerts
+    }
+
+    /// Validate and apply an alert ChangeQueue parameter (ADMIN_OP_* tag); returns the previous value
+    pub fn apply_parameter(&mut self, param: u8, value: u64) -> Result<u64> {
+        let old_value = match param {
+            ADMIN_OP_ALERT_MIN_AUTHORITY_BALANCE => std::mem::replace(&mut self.min_authority_balance, value),
+            ADMIN_OP_ALERT_MAX_CONSECUTIVE_FAILURES => {
+                require!(value <= u8::MAX as u64, ErrorCode::InvalidParameter);
+                std::mem::replace(&mut self.max_consecutive_failures, value as u8) as u64
+            }
+            ADMIN_OP_ALERT_MIN_REBATE_POOL_BALANCE => std::mem::replace(&mut self.min_rebate_pool_balance, value),
+            _ => return err!(ErrorCode::InvalidParameter),
+        };
+        Ok(old_value)
+    }
+}
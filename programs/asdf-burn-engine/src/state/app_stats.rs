@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+
+/// Registry of integrating external apps
+///
+/// Created by the first register_app() call.
+///
+/// PDA Seeds: ["app_registry_v1"]
+#[account]
+pub struct AppRegistry {
+    /// Number of registered apps
+    pub app_count: u32,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl AppRegistry {
+    /// Account size: u32 (4) + u8 (1) = 5 bytes
+    pub const LEN: usize = 4 + 1;
+}
+
+/// Per-app attribution of external deposits
+///
+/// Credited by deposit_fee_asdf() when the deposit passes the app's account.
+/// A user counts toward unique_users of the first app they deposit through.
+///
+/// PDA Seeds: ["app_stats_v1", app_id LE]
+#[account]
+pub struct AppStats {
+    /// App identifier (non-zero, chosen at registration)
+    pub app_id: u32,
+
+    /// App operator wallet
+    pub authority: Pubkey,
+
+    /// Fee share agreed with the app (bps), recorded for off-chain settlement
+    pub fee_share_bps: u16,
+
+    /// Lifetime $ASDF deposited through the app
+    pub total_deposited: u64,
+
+    /// Number of deposits through the app
+    pub deposits_count: u64,
+
+    /// Users whose first attributed deposit came through the app
+    pub unique_users: u64,
+
+    /// Lifetime $ASDF routed to the rebate pool by the app's deposits
+    pub rebates_generated: u64,
+
+    /// Registration timestamp
+    pub registered_at: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl AppStats {
+    /// Account size: u32 (4) + Pubkey (32) + u16 (2) + 5 u64/i64 (40) + u8 (1) = 79 bytes
+    pub const LEN: usize = 4 + 32 + 2 + 8 * 5 + 1;
+
+    /// Attribute a deposit to the app
+    pub fn record_deposit(&mut self, amount: u64, rebate_pool_amount: u64, new_user: bool) {
+        self.total_deposited = self.total_deposited.saturating_add(amount);
+        self.deposits_count = self.deposits_count.saturating_add(1);
+        self.rebates_generated = self.rebates_generated.saturating_add(rebate_pool_amount);
+        if new_user {
+            self.unique_users = self.unique_users.saturating_add(1);
+        }
+    }
+}
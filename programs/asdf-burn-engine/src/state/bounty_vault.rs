@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+/// Escrow for security bug bounty payouts
+///
+/// Funded from the root treasury by fund_bounty_vault(). A payout is first
+/// proposed (recipient, amount, report hash), then executed by pay_bounty()
+/// once dat_state.admin_operation_cooldown has elapsed, so every payout is
+/// publicly visible before funds move. The report hash is emitted on-chain.
+///
+/// PDA Seeds: ["bounty_vault"]
+/// Bounty funds are held in the PDA itself (on top of its rent)
+#[account]
+pub struct BountyVault {
+    /// Cumulative lamports received from the treasury
+    pub total_funded: u64,
+
+    /// Cumulative lamports paid out
+    pub total_paid: u64,
+
+    /// Number of bounties paid
+    pub payout_count: u32,
+
+    /// Proposed payout recipient (Pubkey::default() = no pending payout)
+    pub pending_recipient: Pubkey,
+
+    /// Proposed payout amount
+    pub pending_amount: u64,
+
+    /// Hash of the vulnerability report backing the proposed payout
+    pub pending_report_hash: [u8; 32],
+
+    /// When the pending payout was proposed (timelock start)
+    pub pending_proposed_at: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl BountyVault {
+    /// Account size: 3 u64/i64 (24) + u64 (8) + u32 (4) + Pubkey (32) + hash (32) + u8 (1) = 101 bytes
+    pub const LEN: usize = 8 * 3 + 8 + 4 + 32 + 32 + 1;
+
+    /// Whether a payout is awaiting its timelock
+    pub fn has_pending(&self) -> bool {
+        self.pending_recipient != Pubkey::default()
+    }
+
+    /// Whether (recipient, amount, report_hash) is exactly the pending proposal
+    pub fn matches_pending(&self, recipient: &Pubkey, amount: u64, report_hash: &[u8; 32]) -> bool {
+        self.has_pending()
+            && self.pending_recipient == *recipient
+            && self.pending_amount == amount
+            && self.pending_report_hash == *report_hash
+    }
+
+    /// Clear the pending proposal
+    pub fn clear_pending(&mut self) {
+        self.pending_recipient = Pubkey::default();
+        self.pending_amount = 0;
+        self.pending_report_hash = [0u8; 32];
+        self.pending_proposed_at = 0;
+    }
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use crate::constants::{BUY_REVEAL_MIN_DELAY_SLOTS, BUY_REVEAL_WINDOW_SLOTS};
+use crate::errors::ErrorCode;
+
+/// Sealed buy size for commit-reveal buybacks
+///
+/// commit_buy stores sha256(mint || amount LE || salt) without revealing the
+/// amount; reveal_and_buy must land within BUY_REVEAL_WINDOW_SLOTS after the
+/// commit and spends exactly the committed amount. Searchers see the buy size
+/// only in the transaction that executes it. Each commitment is single-use.
+///
+/// PDA Seeds: ["buy_commit_v1", mint]
+#[account]
+pub struct BuyCommitment {
+    /// Token mint the committed buy targets
+    pub mint: Pubkey,
+
+    /// sha256(mint || amount LE || salt) ([0; 32] = no open commitment)
+    pub commitment: [u8; 32],
+
+    /// Slot commit_buy landed in
+    pub committed_slot: u64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl BuyCommitment {
+    /// Account size: Pubkey (32) + hash (32) + u64 (8) + u8 (1) = 73 bytes
+    pub const LEN: usize = 32 + 32 + 8 + 1;
+
+    /// Commitment to a buy of `amount` lamports of `mint`, blinded by `salt`
+    pub fn hash(mint: &Pubkey, amount: u64, salt: &[u8; 32]) -> [u8; 32] {
+        hashv(&[mint.as_ref(), &amount.to_le_bytes(), salt]).to_bytes()
+    }
+
+    /// Whether a commitment awaits its reveal
+    pub fn is_open(&self) -> bool {
+        self.commitment != [0u8; 32]
+    }
+
+    /// Last slot the open commitment may be revealed in
+    pub fn reveal_deadline(&self) -> u64 {
+        self.committed_slot.saturating_add(BUY_REVEAL_WINDOW_SLOTS)
+    }
+
+    /// Check a reveal at `slot` against the open commitment and consume it
+    pub fn reveal(&mut self, amount: u64, salt: &[u8; 32], slot: u64) -> Result<()> {
+        require!(self.is_open(), ErrorCode::NoBuyCommitment);
+        require!(
+            slot >= self.committed_slot.saturating_add(BUY_REVEAL_MIN_DELAY_SLOTS),
+            ErrorCode::BuyRevealTooEarly
+        );
+        require!(slot <= self.reveal_deadline(), ErrorCode::BuyRevealExpired);
+        require!(
+            Self::hash(&self.mint, amount, salt) == self.commitment,
+            ErrorCode::BuyCommitmentMismatch
+        );
+        self.commitment = [0u8; 32];
+        Ok(())
+    }
+}
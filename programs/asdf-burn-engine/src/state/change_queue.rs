@@ -0,0 +1,139 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::ErrorCode;
+
+/// One queued parameter change (param 0 = empty slot)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PendingChange {
+    /// Queue-assigned identifier (referenced by execute_change / cancel_change / veto_change)
+    pub id: u64,
+
+    /// Parameter being changed: the ADMIN_OP_* tag of its setter
+    pub param: u8,
+
+    /// Proposed value (bps, lamports, seconds or count depending on param)
+    pub value: u64,
+
+    /// When the change was proposed (checked against the governance epoch)
+    pub proposed_at: i64,
+
+    /// Earliest execution time (proposed_at + the change's delay)
+    pub executable_at: i64,
+
+    /// Account the change applies to: the mint for per-token parameters
+    /// (TokenStats / ValidatorState), default for DATState and singleton PDAs
+    pub subject: Pubkey,
+}
+
+impl PendingChange {
+    /// Serialized size: 4 u64/i64 (32) + u8 (1) + Pubkey (32) = 65 bytes
+    pub const LEN: usize = 8 + 1 + 8 + 8 + 8 + 32;
+
+    /// Size before subject was added (ChangeQueue V1)
+    pub const V1_LEN: usize = 8 + 1 + 8 + 8 + 8;
+}
+
+/// Account a queued change is applied to by execute_change
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChangeTarget {
+    DatState,
+    TokenStats,
+    ValidatorState,
+    RebatePool,
+    AlertConfig,
+}
+
+impl ChangeTarget {
+    /// Target of an ADMIN_OP_* tag
+    pub fn of(param: u8) -> ChangeTarget {
+        match param {
+            ADMIN_OP_BURN_RATIO
+            | ADMIN_OP_DUST_THRESHOLD
+            | ADMIN_OP_REFLECTION_RATIO
+            | ADMIN_OP_LIQUIDITY_RATIO => ChangeTarget::TokenStats,
+            ADMIN_OP_VALIDATOR_MAX_FEE | ADMIN_OP_VALIDATOR_MAX_TX => ChangeTarget::ValidatorState,
+            ADMIN_OP_REFERRAL_SHARE
+            | ADMIN_OP_ONBOARDING_BONUS_BPS
+            | ADMIN_OP_ONBOARDING_BONUS_CAP
+            | ADMIN_OP_ONBOARDING_BONUS_BUDGET => ChangeTarget::RebatePool,
+            ADMIN_OP_ALERT_MIN_AUTHORITY_BALANCE
+            | ADMIN_OP_ALERT_MAX_CONSECUTIVE_FAILURES
+            | ADMIN_OP_ALERT_MIN_REBATE_POOL_BALANCE => ChangeTarget::AlertConfig,
+            _ => ChangeTarget::DatState,
+        }
+    }
+}
+
+/// Timelocked queue of parameter changes
+///
+/// Generalizes propose_fee_split / execute_fee_split: every sensitive parameter
+/// (slippage, fee thresholds, intervals, failure threshold, dev fee, per-token
+/// ratios, validator caps, rebate and alert settings) is proposed with its own
+/// delay and applied by execute_change once it elapses. The guardian can veto
+/// any queued change.
+///
+/// PDA Seeds: ["change_queue_v1"]
+#[account]
+pub struct ChangeQueue {
+    /// Account allowed to veto queued changes (rotated by the guardian itself)
+    pub guardian: Pubkey,
+
+    /// Identifier assigned to the next proposal
+    pub next_id: u64,
+
+    /// Queue slots
+    pub changes: [PendingChange; MAX_PENDING_CHANGES],
+
+    /// PDA bump seed
+    pub bump: u8,
+
+    /// Layout version (CHANGE_QUEUE_VERSION, see migrations::MIGRATIONS)
+    pub version: u8,
+}
+
+impl ChangeQueue {
+    /// Account size: Pubkey (32) + u64 (8) + 16 changes (1040) + 2 u8 (2) = 1082 bytes
+    pub const LEN: usize = 32 + 8 + PendingChange::LEN * MAX_PENDING_CHANGES + 1 + 1;
+
+    /// Queue a change to `subject` executable `delay` seconds from now; returns its id
+    pub fn enqueue(&mut self, param: u8, subject: Pubkey, value: u64, now: i64, delay: i64) -> Result<u64> {
+        require!(param != 0, ErrorCode::InvalidParameter);
+        let slot = self.changes.iter_mut()
+            .find(|c| c.param == 0)
+            .ok_or(ErrorCode::ChangeQueueFull)?;
+
+        let id = self.next_id;
+        *slot = PendingChange {
+            id,
+            param,
+            value,
+            proposed_at: now,
+            executable_at: now.saturating_add(delay),
+            subject,
+        };
+        self.next_id = self.next_id.saturating_add(1);
+        Ok(id)
+    }
+
+    /// Remove a queued change (cancel / veto), returning it
+    pub fn remove(&mut self, id: u64) -> Result<PendingChange> {
+        let slot = self.changes.iter_mut()
+            .find(|c| c.param != 0 && c.id == id)
+            .ok_or(ErrorCode::PendingChangeNotFound)?;
+        Ok(std::mem::take(slot))
+    }
+
+    /// Remove a queued change whose delay has elapsed, returning it
+    pub fn take_due(&mut self, id: u64, now: i64) -> Result<PendingChange> {
+        let change = self.changes.iter()
+            .find(|c| c.param != 0 && c.id == id)
+            .ok_or(ErrorCode::PendingChangeNotFound)?;
+        require!(now >= change.executable_at, ErrorCode::ChangeTimelockActive);
+        self.remove(id)
+    }
+
+    /// Number of queued changes
+    pub fn pending_count(&self) -> usize {
+        self.changes.iter().filter(|c| c.param != 0).count()
+    }
+}
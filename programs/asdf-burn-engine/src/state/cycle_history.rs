@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+
+/// One completed buyback cycle (written by burn_and_update)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CycleRecord {
+    /// Burn timestamp
+    pub timestamp: i64,
+
+    /// SOL spent on the cycle's buy (lamports)
+    pub sol_used: u64,
+
+    /// Tokens burned by the cycle
+    pub tokens_burned: u64,
+
+    /// Venue of the cycle's buy (VENUE_BONDING_CURVE or VENUE_PUMPSWAP)
+    pub venue: u8,
+}
+
+impl CycleRecord {
+    /// Serialized size: i64 + 2 u64 (24) + u8 (1) = 25 bytes
+    pub const LEN: usize = 8 + 8 + 8 + 1;
+}
+
+/// Fixed-size ring buffer of a mint's last CYCLE_HISTORY_LEN cycles
+///
+/// Lets dashboards read recent cycles from account data instead of replaying
+/// transaction history. `head` is the slot the next record overwrites.
+///
+/// PDA Seeds: ["cycle_history_v1", mint]
+#[account]
+pub struct CycleHistory {
+    /// Token mint this history tracks
+    pub mint: Pubkey,
+
+    /// Records written since initialization (the buffer holds the last min(total, 32))
+    pub total_recorded: u64,
+
+    /// Index of the next slot to overwrite
+    pub head: u8,
+
+    /// Ring buffer slots (unwritten slots are zeroed)
+    pub records: [CycleRecord; CYCLE_HISTORY_LEN],
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl CycleHistory {
+    /// Account size: Pubkey (32) + u64 (8) + u8 (1) + 32 records (800) + u8 (1) = 842 bytes
+    pub const LEN: usize = 32 + 8 + 1 + CycleRecord::LEN * CYCLE_HISTORY_LEN + 1;
+
+    /// Append a record, overwriting the oldest once the buffer is full
+    pub fn record(&mut self, record: CycleRecord) {
+        self.records[self.head as usize] = record;
+        self.head = ((self.head as usize + 1) % CYCLE_HISTORY_LEN) as u8;
+        self.total_recorded = self.total_recorded.saturating_add(1);
+    }
+
+    /// Most recent record, if any
+    pub fn latest(&self) -> Option<&CycleRecord> {
+        if self.total_recorded == 0 {
+            return None;
+        }
+        let idx = (self.head as usize + CYCLE_HISTORY_LEN - 1) % CYCLE_HISTORY_LEN;
+        Some(&self.records[idx])
+    }
+
+    /// Stored records, oldest first
+    pub fn chronological(&self) -> Vec<CycleRecord> {
+        let stored = self.total_recorded.min(CYCLE_HISTORY_LEN as u64) as usize;
+        let start = (self.head as usize + CYCLE_HISTORY_LEN - stored) % CYCLE_HISTORY_LEN;
+        (0..stored)
+            .map(|i| self.records[(start + i) % CYCLE_HISTORY_LEN])
+            .collect()
+    }
+}
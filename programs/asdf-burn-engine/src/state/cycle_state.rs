@@ -0,0 +1,154 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::ErrorCode;
+
+/// Phase of a mint's buyback cycle
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CyclePhase {
+    /// No cycle in progress (initial state, or after abort_cycle)
+    Idle,
+    /// Fees collected into dat_authority, buy pending
+    Collected,
+    /// Tokens bought, burn pending
+    Bought,
+    /// Cycle completed
+    Burned,
+}
+
+/// On-chain buyback cycle state machine for a single mint
+///
+/// Idle/Burned → Collected (collect_fees*) → Bought (execute_buy*) → Burned (burn_and_update)
+/// Enforces that a buy follows a collect and a burn follows every buy.
+/// abort_cycle returns a cycle stuck in Collected/Bought to Idle after CYCLE_ABORT_TIMEOUT.
+///
+/// PDA Seeds: ["cycle_v1", mint]
+#[account]
+pub struct CycleState {
+    /// Token mint this cycle state tracks
+    pub mint: Pubkey,
+
+    /// Current phase
+    pub phase: CyclePhase,
+
+    /// Cycle sequence number (incremented when a new cycle starts collecting)
+    pub sequence: u64,
+
+    /// Timestamp of the last phase transition
+    pub phase_updated_at: i64,
+
+    /// Slot of the last phase transition
+    pub phase_slot: u64,
+
+    /// Number of cycles recovered via abort_cycle
+    pub aborted_cycles: u32,
+
+    /// PDA bump seed
+    pub bump: u8,
+
+    /// dat_authority rent-exempt reserve read from the Rent sysvar at collect (0 = not yet read)
+    pub rent_exempt_reserve: u64,
+
+    /// Token account rent reserve read from the Rent sysvar at collect (0 = not yet read)
+    pub ata_rent_reserve: u64,
+
+    /// Venue of the latest buy (VENUE_BONDING_CURVE or VENUE_PUMPSWAP), recorded at burn
+    pub buy_venue: u8,
+
+    /// Cycle sequence whose dev fee transfer_dev_fee last paid (0 = never)
+    pub dev_fee_paid_sequence: u64,
+
+    /// Cycle sequence provide_liquidity last seeded the pool in (0 = never)
+    pub liquidity_sequence: u64,
+}
+
+impl CycleState {
+    /// Account size: Pubkey(32) + enum(1) + 7 u64/i64 (56) + u32(4) + 2 u8 (2) = 95 bytes
+    pub const LEN: usize = 32 + 1 + 8 * 7 + 4 + 1 + 1;
+
+    /// Recompute effective reserves from the current rent parameters
+    pub fn refresh_reserves(&mut self, rent: &Rent) {
+        self.rent_exempt_reserve = rent.minimum_balance(0);
+        self.ata_rent_reserve = rent.minimum_balance(ATA_RESERVE_ACCOUNT_LEN);
+    }
+
+    /// dat_authority rent-exempt reserve (RENT_EXEMPT_MINIMUM until first read)
+    pub fn rent_reserve(&self) -> u64 {
+        if self.rent_exempt_reserve == 0 { RENT_EXEMPT_MINIMUM } else { self.rent_exempt_reserve }
+    }
+
+    /// Token account rent reserve (ATA_RENT_RESERVE until first read)
+    pub fn ata_reserve(&self) -> u64 {
+        if self.ata_rent_reserve == 0 { ATA_RENT_RESERVE } else { self.ata_rent_reserve }
+    }
+
+    fn transition(&mut self, phase: CyclePhase, now: i64, slot: u64) {
+        self.phase = phase;
+        self.phase_updated_at = now;
+        self.phase_slot = slot;
+    }
+
+    /// collect_fees*: starts a new cycle from Idle/Burned; repeated collects join the open cycle
+    pub fn begin_collect(&mut self, now: i64, slot: u64) -> Result<()> {
+        match self.phase {
+            CyclePhase::Idle | CyclePhase::Burned => {
+                self.sequence = self.sequence.saturating_add(1);
+            }
+            CyclePhase::Collected => {}
+            CyclePhase::Bought => return err!(ErrorCode::InvalidCyclePhase),
+        }
+        self.transition(CyclePhase::Collected, now, slot);
+        Ok(())
+    }
+
+    /// execute_buy*: requires Collected (DCA tranche continuations may also follow a burn)
+    pub fn begin_buy(&mut self, allow_after_burn: bool, venue: u8, now: i64, slot: u64) -> Result<()> {
+        let allowed = self.phase == CyclePhase::Collected
+            || (allow_after_burn && self.phase == CyclePhase::Burned);
+        require!(allowed, ErrorCode::InvalidCyclePhase);
+        self.buy_venue = venue;
+        self.transition(CyclePhase::Bought, now, slot);
+        Ok(())
+    }
+
+    /// burn_and_update: requires Bought
+    pub fn complete_burn(&mut self, now: i64, slot: u64) -> Result<()> {
+        require!(self.phase == CyclePhase::Bought, ErrorCode::InvalidCyclePhase);
+        self.transition(CyclePhase::Burned, now, slot);
+        Ok(())
+    }
+
+    /// abort_cycle: resets a cycle stuck in Collected/Bought for at least timeout seconds
+    /// Returns the aborted phase
+    pub fn abort(&mut self, now: i64, slot: u64, timeout: i64) -> Result<CyclePhase> {
+        let aborted = self.phase;
+        require!(
+            aborted == CyclePhase::Collected || aborted == CyclePhase::Bought,
+            ErrorCode::InvalidCyclePhase
+        );
+        require!(now.saturating_sub(self.phase_updated_at) >= timeout, ErrorCode::CycleNotStuck);
+
+        self.aborted_cycles = self.aborted_cycles.saturating_add(1);
+        self.transition(CyclePhase::Idle, now, slot);
+        Ok(aborted)
+    }
+
+    /// transfer_dev_fee: charges the dev fee at most once per cycle sequence
+    pub fn mark_dev_fee_paid(&mut self) -> Result<()> {
+        require!(self.sequence > self.dev_fee_paid_sequence, ErrorCode::DevFeeAlreadyPaid);
+        self.dev_fee_paid_sequence = self.sequence;
+        Ok(())
+    }
+
+    /// provide_liquidity: between buy and burn, at most once per cycle sequence
+    pub fn mark_liquidity_provided(&mut self) -> Result<()> {
+        require!(self.phase == CyclePhase::Bought, ErrorCode::InvalidCyclePhase);
+        require!(self.sequence > self.liquidity_sequence, ErrorCode::LiquidityAlreadyProvided);
+        self.liquidity_sequence = self.sequence;
+        Ok(())
+    }
+
+    /// No buy or burn in flight (Idle or Burned)
+    pub fn is_between_cycles(&self) -> bool {
+        matches!(self.phase, CyclePhase::Idle | CyclePhase::Burned)
+    }
+}
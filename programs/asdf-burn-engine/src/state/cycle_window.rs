@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use crate::constants::{CYCLE_RANDOMNESS_TIMEOUT, CYCLE_WINDOW_DURATION, CYCLE_WINDOW_MIN_DELAY, CYCLE_WINDOW_SPREAD};
+use crate::errors::ErrorCode;
+
+/// VRF-settled execution window for a mint's next collection
+///
+/// request_cycle_randomness binds a freshly committed Switchboard randomness
+/// account; once the oracle reveals, anyone may settle_cycle_window, which
+/// places the window CYCLE_WINDOW_MIN_DELAY plus a random share of
+/// CYCLE_WINDOW_SPREAD after the request. While dat_state.randomized_timing is
+/// on, collect_fees only runs inside [window_start, window_end). A new request
+/// is refused until the previous window has closed, so a window cannot be re-rolled.
+///
+/// PDA Seeds: ["cycle_window_v1", mint]
+#[account]
+pub struct CycleWindow {
+    /// Token mint this window gates
+    pub mint: Pubkey,
+
+    /// Switchboard randomness account bound by the latest request
+    pub randomness_account: Pubkey,
+
+    /// Commit slot of the bound randomness (must still match at settlement)
+    pub seed_slot: u64,
+
+    /// When the latest randomness was requested (0 = never)
+    pub requested_at: i64,
+
+    /// Window opening time (0 = not settled)
+    pub window_start: i64,
+
+    /// Window closing time, exclusive (0 = not settled)
+    pub window_end: i64,
+
+    /// Number of windows settled
+    pub windows_settled: u64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl CycleWindow {
+    /// Account size: 2 Pubkey (64) + 5 u64/i64 (40) + u8 (1) = 105 bytes
+    pub const LEN: usize = 32 * 2 + 8 * 5 + 1;
+
+    /// Whether a request awaits its settlement
+    pub fn is_pending(&self) -> bool {
+        self.requested_at != 0 && self.window_end == 0
+    }
+
+    /// Whether a new request may replace the current state at `now`:
+    /// no unexpired pending request and no window still open or upcoming
+    pub fn can_request(&self, now: i64) -> bool {
+        if self.is_pending() {
+            return now.saturating_sub(self.requested_at) >= CYCLE_RANDOMNESS_TIMEOUT;
+        }
+        now >= self.window_end
+    }
+
+    /// Bind a committed randomness account and clear the previous window
+    pub fn request(&mut self, randomness_account: Pubkey, seed_slot: u64, now: i64) -> Result<()> {
+        require!(self.can_request(now), ErrorCode::CycleWindowActive);
+        self.randomness_account = randomness_account;
+        self.seed_slot = seed_slot;
+        self.requested_at = now;
+        self.window_start = 0;
+        self.window_end = 0;
+        Ok(())
+    }
+
+    /// Place the window from the revealed randomness value
+    pub fn settle(&mut self, value: &[u8; 32]) -> Result<()> {
+        require!(self.is_pending(), ErrorCode::NoCycleRandomnessRequest);
+
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&value[..8]);
+        let offset = (u64::from_le_bytes(bytes) % CYCLE_WINDOW_SPREAD) as i64;
+
+        self.window_start = self.requested_at
+            .saturating_add(CYCLE_WINDOW_MIN_DELAY)
+            .saturating_add(offset);
+        self.window_end = self.window_start.saturating_add(CYCLE_WINDOW_DURATION);
+        self.windows_settled = self.windows_settled.saturating_add(1);
+        Ok(())
+    }
+
+    /// Whether `now` falls inside the settled window
+    pub fn contains(&self, now: i64) -> bool {
+        self.window_end != 0 && now >= self.window_start && now < self.window_end
+    }
+}
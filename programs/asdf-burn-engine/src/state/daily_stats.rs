@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::ErrorCode;
+
+/// Per-mint daily activity bucket for charting straight from account data
+///
+/// collect_fees* and burn_and_update add to the current day's bucket when it is
+/// passed; days without a bucket simply go unrecorded.
+///
+/// PDA Seeds: ["daily_stats_v1", mint, day LE] (day = unix_timestamp / SECONDS_PER_DAY)
+#[account]
+pub struct DailyStats {
+    /// Token mint this bucket tracks
+    pub mint: Pubkey,
+
+    /// Day index (UTC days since the Unix epoch)
+    pub day: i64,
+
+    /// SOL credited to the token's total_sol_collected during the day (lamports)
+    pub sol_collected: u64,
+
+    /// Tokens burned during the day
+    pub tokens_burned: u64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl DailyStats {
+    /// Account size: Pubkey (32) + i64 (8) + 2 u64 (16) + u8 (1) = 57 bytes
+    pub const LEN: usize = 32 + 8 + 8 + 8 + 1;
+
+    /// Day index of a unix timestamp
+    pub fn day_index(timestamp: i64) -> i64 {
+        timestamp.div_euclid(SECONDS_PER_DAY)
+    }
+
+    /// Add activity at `now`; the bucket must be that day's
+    pub fn record(&mut self, now: i64, sol_collected: u64, tokens_burned: u64) -> Result<()> {
+        require!(self.day == Self::day_index(now), ErrorCode::DailyStatsDayMismatch);
+        self.sol_collected = self.sol_collected.saturating_add(sol_collected);
+        self.tokens_burned = self.tokens_burned.saturating_add(tokens_burned);
+        Ok(())
+    }
+}
@@ -1,10 +1,26 @@
 use anchor_lang::prelude::*;
+use crate::constants::{
+    ADMIN_OP_AUTO_RESUME_AFTER, ADMIN_OP_DEV_FEE, ADMIN_OP_FAILURE_THRESHOLD, ADMIN_OP_FEE_SPLIT, ADMIN_OP_INSURANCE_BPS,
+    ADMIN_OP_MAX_FEES, ADMIN_OP_MAX_JITO_TIP, ADMIN_OP_MIN_FEES, ADMIN_OP_MIN_INTERVAL,
+    ADMIN_OP_PRICE_GUARD, ADMIN_OP_PRICE_OBSERVATION_RESET, ADMIN_OP_RANDOMIZED_TIMING,
+    ADMIN_OP_RESERVE_BPS, ADMIN_OP_SLIPPAGE,
+    FAILURE_ESCALATION_MIN_TOKENS, FAILURE_ESCALATION_WINDOW, MAX_AUTO_PAUSE_FAILURE_THRESHOLD,
+    MAX_DEV_FEE_BPS, MAX_INSURANCE_BPS, MAX_JITO_TIP_LAMPORTS, MAX_RESERVE_BPS,
+    MIN_AUTO_PAUSE_FAILURE_THRESHOLD,
+};
+use crate::errors::ErrorCode;
 
 /// Global DAT configuration and statistics
 ///
 /// Stores system-wide settings, admin controls, and cumulative metrics.
 /// Only one DATState account exists per program instance.
-#[account]
+///
+/// Zero-copy: loaded in place through AccountLoader instead of deserialized. Fields are
+/// grouped by alignment (Pubkeys, hash, 8-byte, 4-byte, 2-byte, 1-byte) so the repr(C)
+/// layout has no padding. Options are stored as their default value (Pubkey::default(),
+/// 0) and read through the same-named methods; flags are u8 (0 / 1). Layouts up to V18
+/// were Borsh and are relaid out by migrations::dat_state_v19.
+#[account(zero_copy)]
 pub struct DATState {
     /// Current admin authority
     pub admin: Pubkey,
@@ -21,26 +37,36 @@ pub struct DATState {
     /// PumpSwap program ID
     pub pump_swap_program: Pubkey,
 
-    /// Total tokens burned across all cycles
-    pub total_burned: u64,
+    /// Root token mint that receives 44.8% from secondaries
+    /// Pubkey::default() = None (read through root_token_mint())
+    pub root_token_mint: Pubkey,
 
-    /// Total SOL collected across all cycles
-    pub total_sol_collected: u64,
+    /// Two-step admin transfer: proposed new admin
+    /// Pubkey::default() = None (read through pending_admin())
+    pub pending_admin: Pubkey,
 
-    /// Total number of successful buyback cycles
-    pub total_buybacks: u32,
+    /// Cycle lock: mint whose collect → buy → burn sequence owns pending_burn_amount
+    /// Acquired by collect/buy, released by burn_and_update
+    /// Pubkey::default() = None (read through cycle_lock())
+    pub cycle_lock: Pubkey,
 
-    /// Total number of failed cycles
-    pub failed_cycles: u32,
+    /// Wallet receiving the dev fee
+    pub dev_wallet: Pubkey,
 
-    /// Consecutive failure count (resets on success)
-    pub consecutive_failures: u8,
+    /// Proposed dev wallet awaiting its own acceptance (default = none)
+    pub pending_dev_wallet: Pubkey,
+
+    /// Address Lookup Table holding the static cycle accounts (default = none, set by create_cycle_lut)
+    pub cycle_lut: Pubkey,
 
-    /// Whether DAT is active
-    pub is_active: bool,
+    /// sha256 of the config blob queued by propose_import_config (zeroed when none)
+    pub pending_import_hash: [u8; 32],
 
-    /// Emergency pause flag
-    pub emergency_pause: bool,
+    /// Total tokens burned across all cycles
+    pub total_burned: u64,
+
+    /// Total SOL collected across all cycles
+    pub total_sol_collected: u64,
 
     /// Timestamp of last cycle execution
     pub last_cycle_timestamp: i64,
@@ -66,67 +92,383 @@ pub struct DATState {
     /// Maximum fees allowed per cycle
     pub max_fees_per_cycle: u64,
 
-    /// Slippage tolerance in basis points
-    pub slippage_bps: u16,
-
     /// Minimum interval between cycles (seconds)
     pub min_cycle_interval: i64,
 
-    /// PDA bump for DAT authority
-    pub dat_authority_bump: u8,
-
-    /// Current fee recipient index (for rotation)
-    pub current_fee_recipient_index: u8,
-
-    /// Last known token price
+    /// Last observed root token price (PRICE_SCALE fixed-point, 0 = no observation)
     pub last_known_price: u64,
 
     /// Pending burn amount (tokens waiting to be burned)
     pub pending_burn_amount: u64,
 
-    /// Root token mint that receives 44.8% from secondaries
-    pub root_token_mint: Option<Pubkey>,
-
-    /// Fee split in basis points: 5520 = 55.2% keep, 44.8% to root
-    pub fee_split_bps: u16,
-
     /// SOL sent to root in last cycle (for stats tracking)
     pub last_sol_sent_to_root: u64,
 
-    // Security audit additions (v2)
-
-    /// Two-step admin transfer: proposed new admin
-    pub pending_admin: Option<Pubkey>,
-
-    /// Timelock: proposed fee split change
-    pub pending_fee_split: Option<u16>,
-
     /// Timelock: when fee split was proposed
     pub pending_fee_split_timestamp: i64,
 
     /// Timelock: cooldown period in seconds (default 3600 = 1hr)
     pub admin_operation_cooldown: i64,
 
-    // HIGH-01 FIX: Separate timestamp for direct fee split changes
-    /// Last time update_fee_split was called (direct path)
-    /// Separate from pending_fee_split_timestamp to prevent bypass attacks
+    /// Unused: update_fee_split changes are queued in the ChangeQueue (kept for layout)
     pub last_direct_fee_split_timestamp: i64,
+
+    /// When the current governance epoch started (0 = never advanced)
+    pub governance_epoch_started_at: i64,
+
+    /// ProgramData deployment slot acknowledged by the last acknowledge_upgrade
+    pub last_upgrade_slot: u64,
+
+    /// Monotonic sequence stamped on BuyExecuted so indexers can order and gap-check buys
+    pub event_sequence: u64,
+
+    /// Seconds after an automatic pause before try_resume may lift it (0 = disabled)
+    pub auto_resume_after: i64,
+
+    /// When record_failure tripped the pause (0 = not auto-paused; cleared by emergency_pause/resume)
+    pub auto_paused_at: i64,
+
+    /// Start of the current escalation window (0 = no failure yet)
+    pub failure_window_start: i64,
+
+    /// Root shares accrued in RootStreams but not yet flushed (lamports held by dat_authority)
+    pub root_escrow_lamports: u64,
+
+    /// Unused: dev fee changes are queued in the ChangeQueue (kept for layout)
+    pub pending_dev_fee_timestamp: i64,
+
+    /// Largest Jito tip a buy instruction may pay from dat_authority (lamports, 0 = tips disabled)
+    pub max_jito_tip_lamports: u64,
+
+    /// Total number of successful buyback cycles
+    pub total_buybacks: u32,
+
+    /// Total number of failed cycles
+    pub failed_cycles: u32,
+
+    /// Incremented by acknowledge_upgrade; a proposal belongs to the epoch in force
+    /// at its timestamp and cannot execute once a newer epoch has started
+    pub governance_epoch: u32,
+
+    /// Slippage tolerance in basis points
+    pub slippage_bps: u16,
+
+    /// Fee split in basis points: 5520 = 55.2% keep, 44.8% to root
+    pub fee_split_bps: u16,
+
+    /// Timelock: proposed fee split change
+    /// 0 = None (read through pending_fee_split())
+    pub pending_fee_split: u16,
+
+    /// Price circuit breaker: max bps deviation of a root buy's implied price
+    /// from last_known_price (0 = disabled)
+    pub max_price_deviation_bps: u16,
+
+    /// Share of the secondary share paid by transfer_dev_fee (bps)
+    pub dev_fee_bps: u16,
+
+    /// Unused: dev fee changes are queued in the ChangeQueue (kept for layout)
+    pub pending_dev_fee_bps: u16,
+
+    /// Share of SOL collected from creator vaults kept in the ReserveVault (bps, <= MAX_RESERVE_BPS)
+    pub reserve_bps: u16,
+
+    /// Share of SOL collected from creator vaults deposited into the InsuranceFund (bps, <= MAX_INSURANCE_BPS)
+    pub insurance_bps: u16,
+
+    /// Consecutive failure count (resets on success)
+    pub consecutive_failures: u8,
+
+    /// Whether DAT is active (0 / 1)
+    pub is_active: u8,
+
+    /// Emergency pause flag (0 / 1)
+    pub emergency_pause: u8,
+
+    /// PDA bump for DAT authority
+    pub dat_authority_bump: u8,
+
+    /// Current fee recipient index (for rotation)
+    pub current_fee_recipient_index: u8,
+
+    /// Distinct tokens that failed since failure_window_start
+    pub failing_tokens_in_window: u8,
+
+    /// Consecutive record_failure calls that trip the automatic pause
+    /// (MIN_/MAX_AUTO_PAUSE_FAILURE_THRESHOLD bounds, set by update_failure_threshold)
+    pub auto_pause_failure_threshold: u8,
+
+    /// Whether collect_fees only runs inside the mint's VRF-settled CycleWindow (0 / 1)
+    pub randomized_timing: u8,
+
+    /// Layout version (see migrations::MIGRATIONS), stamped at init and by migrate_account
+    pub version: u8,
+
+    /// Unused padding to a multiple of 8 bytes (room for future fields)
+    pub _reserved: [u8; 11],
 }
 
 impl DATState {
-    /// Account size calculation:
-    /// - 5 Pubkeys: 32 * 5 = 160 bytes (admin, asdf_mint, wsol_mint, pool_address, pump_swap_program)
-    /// - 17 u64/i64: 8 * 17 = 136 bytes (total_burned, total_sol_collected, last_cycle_timestamp,
-    ///   initialized_at, last_am_execution, last_pm_execution, last_cycle_sol, last_cycle_burned,
-    ///   min_fees_threshold, max_fees_per_cycle, min_cycle_interval, last_known_price,
-    ///   pending_burn_amount, last_sol_sent_to_root, pending_fee_split_timestamp, admin_operation_cooldown,
-    ///   last_direct_fee_split_timestamp)
-    /// - 2 u32: 4 * 2 = 8 bytes (total_buybacks, failed_cycles)
-    /// - 5 u8/bool: 1 * 5 = 5 bytes (consecutive_failures, is_active, emergency_pause,
-    ///   dat_authority_bump, current_fee_recipient_index)
-    /// - 2 u16: 2 * 2 = 4 bytes (slippage_bps, fee_split_bps)
-    /// - 2 Option<Pubkey>: 33 * 2 = 66 bytes (root_token_mint, pending_admin)
-    /// - 1 Option<u16>: 3 bytes (pending_fee_split)
-    /// Total: 160 + 136 + 8 + 5 + 4 + 66 + 3 = 382 bytes
-    pub const LEN: usize = 32 * 5 + 8 * 17 + 4 * 2 + 1 * 5 + 2 * 2 + 33 * 2 + 3;
+    /// 11 Pubkeys (352) + pending_import_hash (32) + 26 u64/i64 (208) + 3 u32 (12)
+    /// + 8 u16 (16) + 9 u8 (9) + 11 reserved = 640 bytes
+    pub const LEN: usize = std::mem::size_of::<DATState>();
+
+    /// Root token mint, if one was set
+    pub fn root_token_mint(&self) -> Option<Pubkey> {
+        some_key(self.root_token_mint)
+    }
+
+    /// Proposed admin awaiting accept_admin_transfer
+    pub fn pending_admin(&self) -> Option<Pubkey> {
+        some_key(self.pending_admin)
+    }
+
+    /// Proposed fee split awaiting execute_fee_split (1000..=9000, so 0 never collides)
+    pub fn pending_fee_split(&self) -> Option<u16> {
+        (self.pending_fee_split != 0).then_some(self.pending_fee_split)
+    }
+
+    /// Mint holding the cycle lock (None = unlocked)
+    pub fn cycle_lock(&self) -> Option<Pubkey> {
+        some_key(self.cycle_lock)
+    }
+
+    /// Share of `collected` lamports collect_fees moves into the ReserveVault
+    pub fn reserve_cut(&self, collected: u64) -> u64 {
+        (collected as u128 * self.reserve_bps as u128 / 10_000) as u64
+    }
+
+    /// Share of `collected` lamports collect_fees deposits into the InsuranceFund
+    pub fn insurance_cut(&self, collected: u64) -> u64 {
+        (collected as u128 * self.insurance_bps as u128 / 10_000) as u64
+    }
+
+    /// Validate a requested Jito tip against max_jito_tip_lamports and the spendable balance
+    /// Returns the tip to pay (0 when none was requested)
+    pub fn check_jito_tip(&self, tip_lamports: Option<u64>, spendable: u64) -> Result<u64> {
+        let tip = tip_lamports.unwrap_or(0);
+        require!(tip <= self.max_jito_tip_lamports, ErrorCode::JitoTipAboveMax);
+        require!(tip <= spendable, ErrorCode::InsufficientFees);
+        Ok(tip)
+    }
+
+    /// Reject a timelocked proposal made before the current governance epoch started
+    pub fn require_current_epoch(&self, proposed_at: i64) -> Result<()> {
+        require!(proposed_at > self.governance_epoch_started_at, ErrorCode::StaleGovernanceProposal);
+        Ok(())
+    }
+
+    /// Open a new governance epoch for a newly deployed program version
+    /// DATState's own proposals (admin transfer, fee split, dev fee, dev wallet) are dropped outright;
+    /// proposals stored in other accounts are rejected by require_current_epoch
+    pub fn advance_governance_epoch(&mut self, program_slot: u64, now: i64) -> Result<u32> {
+        require!(program_slot > self.last_upgrade_slot, ErrorCode::UpgradeAlreadyAcknowledged);
+
+        self.governance_epoch = self.governance_epoch.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+        self.governance_epoch_started_at = now;
+        self.last_upgrade_slot = program_slot;
+        self.pending_admin = Pubkey::default();
+        self.pending_fee_split = 0;
+        self.pending_fee_split_timestamp = 0;
+        self.pending_dev_wallet = Pubkey::default();
+        Ok(self.governance_epoch)
+    }
+
+    /// Take the cycle lock for mint (re-entrant for the holder)
+    pub fn acquire_cycle_lock(&mut self, mint: Pubkey) -> Result<()> {
+        match self.cycle_lock() {
+            Some(holder) if holder != mint => err!(ErrorCode::CycleLocked),
+            _ => {
+                self.cycle_lock = mint;
+                Ok(())
+            }
+        }
+    }
+
+    /// dat_authority lamports spendable above `reserve`, excluding SOL escrowed for roots
+    pub fn spendable_lamports(&self, authority_lamports: u64, reserve: u64) -> u64 {
+        authority_lamports.saturating_sub(reserve.saturating_add(self.root_escrow_lamports))
+    }
+
+    /// Earliest timestamp min_cycle_interval allows the next collect
+    pub fn next_cycle_at(&self) -> i64 {
+        self.last_cycle_timestamp.saturating_add(self.min_cycle_interval)
+    }
+
+    /// Whether collect_fees's global gates pass for mint at now
+    /// (active, unpaused, cycle lock free or held by mint, interval elapsed when enforced)
+    pub fn collect_allowed(&self, mint: &Pubkey, now: i64, enforce_interval: bool) -> bool {
+        self.is_active != 0
+            && self.emergency_pause == 0
+            && self.cycle_lock().is_none_or(|holder| holder == *mint)
+            && (!enforce_interval || now >= self.next_cycle_at())
+    }
+
+    /// Count a failed cycle; trips the emergency pause (stamped for try_resume) at
+    /// auto_pause_failure_threshold consecutive failures. Returns whether this call tripped it
+    pub fn record_cycle_failure(&mut self, now: i64) -> bool {
+        self.failed_cycles = self.failed_cycles.saturating_add(1);
+        self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+        if self.consecutive_failures >= self.auto_pause_failure_threshold && self.emergency_pause == 0 {
+            self.emergency_pause = 1;
+            self.auto_paused_at = now;
+            return true;
+        }
+        false
+    }
+
+    /// Count a failed cycle attributed to one token. The global pause only trips once
+    /// FAILURE_ESCALATION_MIN_TOKENS distinct tokens failed within FAILURE_ESCALATION_WINDOW;
+    /// previous_token_failure (the token's last failure, 0 = never) counts each token once per window.
+    /// Returns whether this call tripped the pause
+    pub fn record_token_failure(&mut self, previous_token_failure: i64, now: i64) -> bool {
+        self.failed_cycles = self.failed_cycles.saturating_add(1);
+        // failure_window_start == 0: no window opened yet
+        if self.failure_window_start == 0
+            || now.saturating_sub(self.failure_window_start) >= FAILURE_ESCALATION_WINDOW
+        {
+            self.failure_window_start = now;
+            self.failing_tokens_in_window = 0;
+        }
+        if previous_token_failure < self.failure_window_start {
+            self.failing_tokens_in_window = self.failing_tokens_in_window.saturating_add(1);
+        }
+        if self.failing_tokens_in_window >= FAILURE_ESCALATION_MIN_TOKENS && self.emergency_pause == 0 {
+            self.emergency_pause = 1;
+            self.auto_paused_at = now;
+            return true;
+        }
+        false
+    }
+
+    /// Require an automatic pause whose cool-off has elapsed (manual pauses never auto-resume)
+    pub fn require_auto_resumable(&self, now: i64) -> Result<()> {
+        require!(
+            self.emergency_pause != 0 && self.auto_paused_at > 0 && self.auto_resume_after > 0,
+            ErrorCode::AutoResumeUnavailable
+        );
+        require!(
+            now >= self.auto_paused_at.saturating_add(self.auto_resume_after),
+            ErrorCode::AutoResumeCoolOff
+        );
+        Ok(())
+    }
+
+    /// Dev fee owed on a cycle's secondary share
+    pub fn dev_fee(&self, secondary_share: u64) -> u64 {
+        (secondary_share as u128 * self.dev_fee_bps as u128 / 10000) as u64
+    }
+
+    /// Validate and apply a ChangeQueue parameter (ADMIN_OP_* tag); returns the previous value
+    /// Bounds match the former instant setters (update_parameters, update_failure_threshold, ...)
+    pub fn apply_parameter(&mut self, param: u8, value: u64) -> Result<u64> {
+        let old_value = match param {
+            ADMIN_OP_SLIPPAGE => {
+                // min 0.1% (10 bps), max 5% (500 bps); 0 would break buy calculations
+                require!((10..=500).contains(&value), ErrorCode::SlippageConfigTooHigh);
+                std::mem::replace(&mut self.slippage_bps, value as u16) as u64
+            }
+            ADMIN_OP_MIN_INTERVAL => {
+                require!(value > 0 && value <= i64::MAX as u64, ErrorCode::InvalidParameter);
+                std::mem::replace(&mut self.min_cycle_interval, value as i64) as u64
+            }
+            ADMIN_OP_MIN_FEES => {
+                // at least 0.001 SOL and at most 1 SOL, never above max_fees_per_cycle
+                require!((1_000_000..=1_000_000_000).contains(&value), ErrorCode::InvalidParameter);
+                require!(value <= self.max_fees_per_cycle, ErrorCode::InvalidParameter);
+                std::mem::replace(&mut self.min_fees_threshold, value)
+            }
+            ADMIN_OP_MAX_FEES => {
+                // at least 0.01 SOL, never below min_fees_threshold
+                require!(value >= 10_000_000, ErrorCode::InvalidParameter);
+                require!(value >= self.min_fees_threshold, ErrorCode::InvalidParameter);
+                std::mem::replace(&mut self.max_fees_per_cycle, value)
+            }
+            ADMIN_OP_AUTO_RESUME_AFTER => {
+                require!(value <= i64::MAX as u64, ErrorCode::InvalidParameter);
+                std::mem::replace(&mut self.auto_resume_after, value as i64) as u64
+            }
+            ADMIN_OP_FAILURE_THRESHOLD => {
+                require!(
+                    (MIN_AUTO_PAUSE_FAILURE_THRESHOLD as u64..=MAX_AUTO_PAUSE_FAILURE_THRESHOLD as u64).contains(&value),
+                    ErrorCode::InvalidParameter
+                );
+                std::mem::replace(&mut self.auto_pause_failure_threshold, value as u8) as u64
+            }
+            ADMIN_OP_DEV_FEE => {
+                require!(value <= MAX_DEV_FEE_BPS as u64, ErrorCode::DevFeeAboveCap);
+                std::mem::replace(&mut self.dev_fee_bps, value as u16) as u64
+            }
+            ADMIN_OP_RESERVE_BPS => {
+                require!(value <= MAX_RESERVE_BPS as u64, ErrorCode::InvalidReserveRatio);
+                std::mem::replace(&mut self.reserve_bps, value as u16) as u64
+            }
+            ADMIN_OP_INSURANCE_BPS => {
+                require!(value <= MAX_INSURANCE_BPS as u64, ErrorCode::InvalidInsuranceRatio);
+                std::mem::replace(&mut self.insurance_bps, value as u16) as u64
+            }
+            ADMIN_OP_RANDOMIZED_TIMING => {
+                require!(value <= 1, ErrorCode::InvalidParameter);
+                std::mem::replace(&mut self.randomized_timing, value as u8) as u64
+            }
+            ADMIN_OP_MAX_JITO_TIP => {
+                require!(value <= MAX_JITO_TIP_LAMPORTS, ErrorCode::JitoTipAboveMax);
+                std::mem::replace(&mut self.max_jito_tip_lamports, value)
+            }
+            ADMIN_OP_PRICE_GUARD => {
+                // 0 disables the circuit breaker
+                require!(value <= 10000, ErrorCode::InvalidParameter);
+                std::mem::replace(&mut self.max_price_deviation_bps, value as u16) as u64
+            }
+            ADMIN_OP_PRICE_OBSERVATION_RESET => {
+                // the next root buy re-seeds the baseline
+                require!(value == 1, ErrorCode::InvalidParameter);
+                std::mem::replace(&mut self.last_known_price, 0)
+            }
+            ADMIN_OP_FEE_SPLIT => {
+                // 10%-90%
+                require!((1000..=9000).contains(&value), ErrorCode::InvalidFeeSplit);
+                std::mem::replace(&mut self.fee_split_bps, value as u16) as u64
+            }
+            _ => return err!(ErrorCode::InvalidParameter),
+        };
+
+        Ok(old_value)
+    }
+
+    /// Advance and return the event sequence number
+    pub fn next_event_sequence(&mut self) -> u64 {
+        self.event_sequence = self.event_sequence.saturating_add(1);
+        self.event_sequence
+    }
+
+    /// Release the cycle lock held by mint
+    pub fn release_cycle_lock(&mut self, mint: Pubkey) -> Result<()> {
+        require!(self.cycle_lock() == Some(mint), ErrorCode::CycleLockNotHeld);
+        self.cycle_lock = Pubkey::default();
+        Ok(())
+    }
+
+    /// Release mint's cycle lock when its cycle ends without a burn (finalize_allocated_cycle,
+    /// record_failure); kept while a buy left a burn pending for burn_and_update.
+    /// Returns whether the lock was released
+    pub fn release_idle_cycle_lock(&mut self, mint: Pubkey) -> bool {
+        if self.cycle_lock() != Some(mint) || self.pending_burn_amount > 0 {
+            return false;
+        }
+        self.cycle_lock = Pubkey::default();
+        true
+    }
+
+    /// Require that no cycle is in flight and none has completed for at least timeout seconds
+    pub fn require_cycles_stale(&self, now: i64, timeout: i64) -> Result<()> {
+        require!(self.cycle_lock().is_none(), ErrorCode::CycleLocked);
+        require!(now.saturating_sub(self.last_cycle_timestamp) >= timeout, ErrorCode::CycleNotStuck);
+        Ok(())
+    }
+}
+
+/// Option view of a Pubkey slot stored as Pubkey::default() when empty
+fn some_key(key: Pubkey) -> Option<Pubkey> {
+    (key != Pubkey::default()).then_some(key)
 }
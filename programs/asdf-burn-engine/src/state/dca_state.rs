@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+/// TWAP-style DCA plan for a single buyback allocation
+///
+/// Splits one cycle's allocation into N tranches executed across separate
+/// transactions via execute_buy_tranche(), enforcing a per-tranche maximum
+/// and a minimum slot spacing on-chain to limit price impact.
+///
+/// PDA Seeds: ["dca_v1", mint]
+#[account]
+pub struct DcaState {
+    /// Token mint being bought
+    pub mint: Pubkey,
+
+    /// Total lamports allocated to this plan
+    pub total_lamports: u64,
+
+    /// Lamports not yet spent by executed tranches
+    pub remaining_lamports: u64,
+
+    /// Maximum lamports per tranche: ceil(total_lamports / tranche_count)
+    pub tranche_lamports: u64,
+
+    /// Number of tranches in the plan
+    pub tranche_count: u8,
+
+    /// Number of tranches executed so far
+    pub tranches_executed: u8,
+
+    /// Minimum slots between two tranches
+    pub min_slot_spacing: u64,
+
+    /// Slot of the last executed tranche
+    pub last_tranche_slot: u64,
+
+    /// Timestamp when the plan was started
+    pub started_at: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl DcaState {
+    /// Account size: Pubkey(32) + 6 u64/i64 fields (48) + 3 u8 (3) = 83 bytes
+    pub const LEN: usize = 32 + 8 * 6 + 3;
+
+    /// Whether tranches remain to be executed
+    pub fn is_active(&self) -> bool {
+        self.tranches_executed < self.tranche_count && self.remaining_lamports > 0
+    }
+}
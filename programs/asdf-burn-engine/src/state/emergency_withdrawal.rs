@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+/// Timelocked recovery of SOL stranded in dat_authority
+///
+/// Last-resort exit if the PumpFun integration breaks permanently. The admin
+/// proposes (amount, destination) while cycles are paused; the withdrawal can
+/// only execute after EMERGENCY_WITHDRAW_TIMELOCK, and the guardian (or the
+/// admin) can veto it at any point before then. The guardian cannot be
+/// replaced while a withdrawal is pending.
+///
+/// PDA Seeds: ["emergency_withdraw"]
+#[account]
+pub struct EmergencyWithdrawal {
+    /// Key allowed to veto a pending withdrawal (Pubkey::default() = not set)
+    pub guardian: Pubkey,
+
+    /// Proposed destination (Pubkey::default() = no pending withdrawal)
+    pub pending_destination: Pubkey,
+
+    /// Proposed amount in lamports
+    pub pending_amount: u64,
+
+    /// When the pending withdrawal was proposed (timelock start)
+    pub pending_proposed_at: i64,
+
+    /// Cumulative lamports withdrawn
+    pub total_withdrawn: u64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl EmergencyWithdrawal {
+    /// Account size: 2 Pubkey (64) + 3 u64/i64 (24) + u8 (1) = 89 bytes
+    pub const LEN: usize = 32 * 2 + 8 * 3 + 1;
+
+    /// Whether a withdrawal is awaiting its timelock
+    pub fn has_pending(&self) -> bool {
+        self.pending_destination != Pubkey::default()
+    }
+
+    /// Earliest timestamp the pending withdrawal may execute
+    pub fn executable_at(&self, timelock: i64) -> i64 {
+        self.pending_proposed_at.saturating_add(timelock)
+    }
+
+    /// Clear the pending proposal
+    pub fn clear_pending(&mut self) {
+        self.pending_destination = Pubkey::default();
+        self.pending_amount = 0;
+        self.pending_proposed_at = 0;
+    }
+}
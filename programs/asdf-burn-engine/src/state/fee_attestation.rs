@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::helpers::{fee_leaf_hash, verify_merkle_proof};
+
+/// Optimistic fee attestation posted by register_validated_fees
+///
+/// The daemon commits to a Merkle root of (slot, tx signature, fee) leaves
+/// instead of crediting raw totals. Anyone can dispute within the challenge
+/// window via challenge_attestation(); unchallenged attestations are credited
+/// to TokenStats.pending_fees_lamports by finalize_attestation().
+/// Bonded attestations are disputed through slash_validator() instead.
+///
+/// PDA Seeds: ["fee_attestation", mint, end_slot (u64 LE)]
+#[account]
+pub struct FeeAttestation {
+    /// Token mint the fees are attributed to
+    pub mint: Pubkey,
+
+    /// Account that posted the attestation (receives rent on finalize)
+    pub poster: Pubkey,
+
+    /// Merkle root of fee leaves (see fee_leaf_hash)
+    pub merkle_root: [u8; 32],
+
+    /// Claimed total fees (sum of leaf fees)
+    pub fee_amount: u64,
+
+    /// Claimed number of leaves
+    pub tx_count: u32,
+
+    /// Exclusive lower slot bound (validator.last_validated_slot at posting)
+    pub start_slot: u64,
+
+    /// Inclusive upper slot bound
+    pub end_slot: u64,
+
+    /// Slot the attestation was posted (challenge window start)
+    pub posted_slot: u64,
+
+    /// PDA bump seed
+    pub bump: u8,
+
+    /// ValidatorBond backing this attestation (Pubkey::default() = admin-posted)
+    pub bond: Pubkey,
+}
+
+/// Merkle inclusion proof for a single fee leaf (challenge_attestation argument)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct FeeLeafProof {
+    pub slot: u64,
+    pub signature: [u8; 64],
+    pub fee: u64,
+    /// Leaf position in the tree (left/right ordering at each level)
+    pub index: u32,
+    pub proof: Vec<[u8; 32]>,
+}
+
+/// One token's fee attestation (register_validated_fees_batch argument)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ValidatedFeeEntry {
+    pub mint: Pubkey,
+    pub fee_amount: u64,
+    pub end_slot: u64,
+    pub tx_count: u32,
+    pub merkle_root: [u8; 32],
+}
+
+impl FeeAttestation {
+    /// Account size: 3 Pubkey (96) + root (32) + 4 u64 (32) + u32 (4) + u8 (1) = 165 bytes
+    pub const LEN: usize = 32 * 3 + 32 + 8 * 4 + 4 + 1;
+
+    /// Whether this attestation is backed by a validator bond
+    pub fn is_bonded(&self) -> bool {
+        self.bond != Pubkey::default()
+    }
+
+    /// Whether challenges are still accepted at `slot`
+    pub fn in_challenge_window(&self, slot: u64) -> bool {
+        slot <= self.posted_slot.saturating_add(ATTESTATION_CHALLENGE_WINDOW_SLOTS)
+    }
+
+    /// Verify a leaf proof against the committed root
+    pub fn verify_leaf(&self, leaf: &FeeLeafProof) -> Result<()> {
+        require!(leaf.proof.len() <= MAX_MERKLE_PROOF_DEPTH, ErrorCode::InvalidMerkleProof);
+        let hash = fee_leaf_hash(leaf.slot, &leaf.signature, leaf.fee);
+        require!(
+            verify_merkle_proof(&self.merkle_root, hash, leaf.index, &leaf.proof),
+            ErrorCode::InvalidMerkleProof
+        );
+        Ok(())
+    }
+
+    /// Return the FRAUD_* reason proven by a committed leaf (and optional duplicate),
+    /// or NoFraudProven if the leaves are consistent with the attestation
+    pub fn fraud_reason(&self, leaf: &FeeLeafProof, duplicate: Option<&FeeLeafProof>) -> Result<u8> {
+        self.verify_leaf(leaf)?;
+
+        if let Some(other) = duplicate {
+            self.verify_leaf(other)?;
+            require!(other.index != leaf.index, ErrorCode::NoFraudProven);
+            require!(other.signature == leaf.signature, ErrorCode::NoFraudProven);
+            return Ok(FRAUD_DUPLICATE_TX);
+        }
+
+        if leaf.slot <= self.start_slot || leaf.slot > self.end_slot {
+            return Ok(FRAUD_SLOT_OUT_OF_RANGE);
+        }
+        if leaf.index >= self.tx_count {
+            return Ok(FRAUD_INDEX_BEYOND_COUNT);
+        }
+        if leaf.fee > MAX_FEE_PER_TX {
+            return Ok(FRAUD_FEE_TOO_HIGH);
+        }
+
+        err!(ErrorCode::NoFraudProven)
+    }
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_FEE_RECIPIENTS;
+
+/// PumpFun protocol fee recipient rotation
+///
+/// Buys must pass the entry at DATState.current_fee_recipient_index, which
+/// advances after every bonding curve buy. Every entry must also be one of the
+/// recipients in the PumpFun Global account, which is the rotation used when
+/// this account is omitted.
+///
+/// PDA Seeds: ["fee_recipients_v1"]
+#[account]
+pub struct FeeRecipients {
+    /// Recipient pubkeys (first count entries are active)
+    pub recipients: [Pubkey; MAX_FEE_RECIPIENTS],
+
+    /// Number of active recipients
+    pub count: u8,
+
+    /// Timestamp of last configuration change
+    pub updated_at: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl FeeRecipients {
+    /// Account size: 8 Pubkey (256) + u8 (1) + i64 (8) + bump (1) = 266 bytes
+    pub const LEN: usize = 32 * MAX_FEE_RECIPIENTS + 1 + 8 + 1;
+
+    /// Active recipients
+    pub fn active(&self) -> &[Pubkey] {
+        &self.recipients[..(self.count as usize).min(MAX_FEE_RECIPIENTS)]
+    }
+}
+
+/// Recipient expected at rotation `index` and the index for the next cycle
+/// `active` must be non-empty
+pub fn fee_recipient_rotation(active: &[Pubkey], index: u8) -> (Pubkey, u8) {
+    let i = index as usize % active.len();
+    (active[i], ((i + 1) % active.len()) as u8)
+}
@@ -0,0 +1,132 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::helpers::{rollup_checksum, stats_leaf_hash};
+use crate::state::TokenStats;
+
+/// Ecosystem-wide aggregates recomputed from every TokenStats account
+///
+/// DATState.total_burned / total_sol_collected only see part of the flow since
+/// per-token stats took over. rollup_global_stats rebuilds the totals over one
+/// or more batches of TokenStats (remaining_accounts, strictly ascending mint
+/// order across batches) into the pending_* accumulator, then the final batch
+/// commits them. The checksum chains stats_leaf_hash() of every rolled-up token
+/// so indexers can recompute and verify it off-chain.
+///
+/// PDA Seeds: ["global_stats_v1"]
+#[account]
+pub struct GlobalStats {
+    /// Committed: tokens burned across all TokenStats
+    pub total_burned: u64,
+
+    /// Committed: SOL collected across all TokenStats
+    pub total_sol_collected: u64,
+
+    /// Committed: SOL used for buybacks across all TokenStats
+    pub total_sol_used: u64,
+
+    /// Committed: buyback cycles across all TokenStats
+    pub total_buybacks: u64,
+
+    /// Committed: micro-USD value burned across all TokenStats
+    pub usd_value_burned: u64,
+
+    /// Committed: number of TokenStats accounts rolled up
+    pub token_count: u32,
+
+    /// Committed: chained checksum of every rolled-up stats leaf
+    pub checksum: [u8; 32],
+
+    /// When the committed totals were last finalized
+    pub rolled_up_at: i64,
+
+    /// Roll-up in progress: accumulated tokens burned
+    pub pending_total_burned: u64,
+
+    /// Roll-up in progress: accumulated SOL collected
+    pub pending_total_sol_collected: u64,
+
+    /// Roll-up in progress: accumulated SOL used
+    pub pending_total_sol_used: u64,
+
+    /// Roll-up in progress: accumulated buyback cycles
+    pub pending_total_buybacks: u64,
+
+    /// Roll-up in progress: accumulated micro-USD value burned
+    pub pending_usd_value_burned: u64,
+
+    /// Roll-up in progress: TokenStats accounts accumulated so far
+    pub pending_token_count: u32,
+
+    /// Roll-up in progress: running checksum
+    pub pending_checksum: [u8; 32],
+
+    /// Roll-up in progress: last mint accumulated (next mint must be greater)
+    pub cursor: Pubkey,
+
+    /// Whether a roll-up has been started and not yet finalized
+    pub in_progress: bool,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl GlobalStats {
+    /// Account size: 10 u64 (80) + i64 (8) + 2 u32 (8) + 2 checksum (64) + Pubkey (32) + bool (1) + u8 (1) = 194 bytes
+    pub const LEN: usize = 8 * 10 + 8 + 4 * 2 + 32 * 2 + 32 + 1 + 1;
+
+    /// Reset the accumulator for a new roll-up
+    pub fn begin(&mut self) {
+        self.pending_total_burned = 0;
+        self.pending_total_sol_collected = 0;
+        self.pending_total_sol_used = 0;
+        self.pending_total_buybacks = 0;
+        self.pending_usd_value_burned = 0;
+        self.pending_token_count = 0;
+        self.pending_checksum = [0u8; 32];
+        self.cursor = Pubkey::default();
+        self.in_progress = true;
+    }
+
+    /// Add one TokenStats to the accumulator (mints must arrive in strictly ascending order)
+    pub fn accumulate(&mut self, stats: &TokenStats) -> Result<()> {
+        require!(self.in_progress, ErrorCode::RollupNotStarted);
+        require!(
+            self.pending_token_count == 0 || stats.mint > self.cursor,
+            ErrorCode::RollupOutOfOrder
+        );
+
+        self.pending_total_burned = self.pending_total_burned.checked_add(stats.total_burned).ok_or(ErrorCode::MathOverflow)?;
+        self.pending_total_sol_collected = self.pending_total_sol_collected.checked_add(stats.total_sol_collected).ok_or(ErrorCode::MathOverflow)?;
+        self.pending_total_sol_used = self.pending_total_sol_used.checked_add(stats.total_sol_used).ok_or(ErrorCode::MathOverflow)?;
+        self.pending_total_buybacks = self.pending_total_buybacks.checked_add(stats.total_buybacks).ok_or(ErrorCode::MathOverflow)?;
+        self.pending_usd_value_burned = self.pending_usd_value_burned.checked_add(stats.usd_value_burned).ok_or(ErrorCode::MathOverflow)?;
+        self.pending_token_count = self.pending_token_count.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+
+        let leaf = stats_leaf_hash(
+            &stats.mint,
+            stats.total_burned,
+            stats.total_sol_collected,
+            stats.total_sol_used,
+            stats.total_buybacks,
+            stats.usd_value_burned,
+        );
+        self.pending_checksum = rollup_checksum(&self.pending_checksum, &leaf);
+        self.cursor = stats.mint;
+        Ok(())
+    }
+
+    /// Publish the accumulator as the committed totals
+    pub fn commit(&mut self, now: i64) -> Result<()> {
+        require!(self.in_progress, ErrorCode::RollupNotStarted);
+        self.total_burned = self.pending_total_burned;
+        self.total_sol_collected = self.pending_total_sol_collected;
+        self.total_sol_used = self.pending_total_sol_used;
+        self.total_buybacks = self.pending_total_buybacks;
+        self.usd_value_burned = self.pending_usd_value_burned;
+        self.token_count = self.pending_token_count;
+        self.checksum = self.pending_checksum;
+        self.rolled_up_at = now;
+        self.in_progress = false;
+        Ok(())
+    }
+}
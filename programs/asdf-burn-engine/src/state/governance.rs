@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+
+/// Community governance settings for fee split proposals
+///
+/// Votes are weighted by $ASDF escrowed into the proposal's vote vault for the
+/// voting period (returned by reclaim_vote), so the same tokens cannot vote twice.
+///
+/// PDA Seeds: ["governance_v1"]
+#[account]
+pub struct Governance {
+    /// Identifier assigned to the next proposal
+    pub next_proposal_id: u64,
+
+    /// Minimum total escrowed $ASDF (for + against) for a proposal to pass
+    pub quorum: u64,
+
+    /// Seconds a proposal accepts votes
+    pub voting_period: i64,
+
+    /// Timelock between the end of voting and execution
+    pub execution_delay: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl Governance {
+    /// Account size: 4 u64/i64 (32) + u8 (1) = 33 bytes
+    pub const LEN: usize = 8 * 4 + 1;
+}
+
+/// Community proposal to change DATState.fee_split_bps
+///
+/// PDA Seeds: ["fee_split_proposal_v1", id LE]
+#[account]
+pub struct FeeSplitProposal {
+    /// Governance-assigned identifier
+    pub id: u64,
+
+    /// Account that created the proposal
+    pub proposer: Pubkey,
+
+    /// Proposed fee split (bps kept by secondaries)
+    pub new_fee_split_bps: u16,
+
+    /// Creation timestamp (checked against the governance epoch at execution)
+    pub created_at: i64,
+
+    /// Votes are accepted until this timestamp
+    pub voting_ends_at: i64,
+
+    /// Escrowed $ASDF voting for
+    pub votes_for: u64,
+
+    /// Escrowed $ASDF voting against
+    pub votes_against: u64,
+
+    /// Whether execute_proposal applied the fee split
+    pub executed: bool,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl FeeSplitProposal {
+    /// Account size: Pubkey (32) + 5 u64/i64 (40) + u16 (2) + bool (1) + u8 (1) = 76 bytes
+    pub const LEN: usize = 8 + 32 + 2 + 8 + 8 + 8 + 8 + 1 + 1;
+
+    /// Count `weight` escrowed tokens for or against while voting is open
+    pub fn record_vote(&mut self, support: bool, weight: u64, now: i64) -> Result<()> {
+        require!(weight > 0, ErrorCode::InvalidParameter);
+        require!(now < self.voting_ends_at, ErrorCode::VotingClosed);
+        let tally = if support { &mut self.votes_for } else { &mut self.votes_against };
+        *tally = tally.checked_add(weight).ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Quorum reached and a strict majority in favor
+    pub fn passed(&self, quorum: u64) -> bool {
+        self.votes_for.saturating_add(self.votes_against) >= quorum
+            && self.votes_for > self.votes_against
+    }
+
+    /// Gate for execute_proposal: passed, not yet executed, and past the timelock
+    pub fn require_executable(&self, quorum: u64, execution_delay: i64, now: i64) -> Result<()> {
+        require!(!self.executed, ErrorCode::ProposalAlreadyExecuted);
+        require!(
+            now >= self.voting_ends_at.saturating_add(execution_delay),
+            ErrorCode::CycleTooSoon // Reusing existing error for timelock
+        );
+        require!(self.passed(quorum), ErrorCode::ProposalNotPassed);
+        Ok(())
+    }
+}
+
+/// One voter's escrowed ballot on a proposal (closed by reclaim_vote)
+///
+/// PDA Seeds: ["vote_v1", proposal, voter]
+#[account]
+pub struct VoteRecord {
+    /// Proposal voted on
+    pub proposal: Pubkey,
+
+    /// Voter (receives the escrowed tokens back)
+    pub voter: Pubkey,
+
+    /// $ASDF escrowed (vote weight)
+    pub amount: u64,
+
+    /// For (true) or against (false)
+    pub support: bool,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl VoteRecord {
+    /// Account size: 2 Pubkey (64) + u64 (8) + bool (1) + u8 (1) = 74 bytes
+    pub const LEN: usize = 32 * 2 + 8 + 1 + 1;
+}
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+
+/// Insurance fund for users affected by program bugs
+///
+/// Capitalized by collect_fees, which deposits dat_state.insurance_bps of the SOL
+/// drained from each creator vault. Reimbursements (e.g. a misattributed rebate)
+/// are proposed by the admin with the hash of the claim they settle, and execute
+/// after INSURANCE_PAYOUT_TIMELOCK unless the guardian (or the admin) vetoes them.
+///
+/// PDA Seeds: ["insurance_fund_v1"]
+/// Insurance funds are held in the PDA itself (on top of its rent)
+#[account]
+pub struct InsuranceFund {
+    /// Key allowed to veto a pending payout
+    pub guardian: Pubkey,
+
+    /// Cumulative lamports deposited by cycles
+    pub total_deposited: u64,
+
+    /// Number of cycle deposits
+    pub deposit_count: u64,
+
+    /// Cumulative lamports paid out
+    pub total_paid: u64,
+
+    /// Number of payouts executed
+    pub payout_count: u32,
+
+    /// Proposed payout recipient (Pubkey::default() = no pending payout)
+    pub pending_recipient: Pubkey,
+
+    /// Proposed payout amount
+    pub pending_amount: u64,
+
+    /// Hash of the claim (affected user, incident, evidence) backing the proposed payout
+    pub pending_claim_hash: [u8; 32],
+
+    /// When the pending payout was proposed (timelock start)
+    pub pending_proposed_at: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl InsuranceFund {
+    /// Account size: 2 Pubkey (64) + 5 u64/i64 (40) + u32 (4) + hash (32) + u8 (1) = 141 bytes
+    pub const LEN: usize = 32 * 2 + 8 * 5 + 4 + 32 + 1;
+
+    /// Whether a payout is awaiting its timelock
+    pub fn has_pending(&self) -> bool {
+        self.pending_recipient != Pubkey::default()
+    }
+
+    /// Earliest timestamp the pending payout may execute
+    pub fn executable_at(&self, timelock: i64) -> i64 {
+        self.pending_proposed_at.saturating_add(timelock)
+    }
+
+    /// Account a cycle deposit
+    pub fn record_deposit(&mut self, amount: u64) {
+        self.total_deposited = self.total_deposited.saturating_add(amount);
+        self.deposit_count = self.deposit_count.saturating_add(1);
+    }
+
+    /// Clear the pending proposal
+    pub fn clear_pending(&mut self) {
+        self.pending_recipient = Pubkey::default();
+        self.pending_amount = 0;
+        self.pending_claim_hash = [0u8; 32];
+        self.pending_proposed_at = 0;
+    }
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use crate::constants::KEEPER_REWARD_TIERS;
+
+/// Registered cycle keeper (cranker) and its performance record
+///
+/// Any address may register_keeper to crank burn_and_update for rewards. Each
+/// completed cycle it signs increments successful_cycles and pays a reward of
+/// the cycle's SOL at the tier its track record has reached
+/// (KEEPER_REWARD_TIERS). Unregistered or deactivated crankers can still
+/// complete cycles, but earn nothing.
+///
+/// PDA Seeds: ["keeper_v1", keeper]
+#[account]
+pub struct KeeperInfo {
+    /// Cranker address (signs burn_and_update, receives rewards)
+    pub keeper: Pubkey,
+
+    /// Whether the keeper still earns rewards (cleared by deactivate_keeper)
+    pub is_active: bool,
+
+    /// When the keeper registered
+    pub registered_at: i64,
+
+    /// Cycles completed by this keeper while active
+    pub successful_cycles: u64,
+
+    /// Lifetime rewards paid to the keeper (lamports)
+    pub total_rewards: u64,
+
+    /// When the keeper last completed a cycle (0 = never)
+    pub last_cycle_timestamp: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl KeeperInfo {
+    /// Account size: Pubkey (32) + bool (1) + 4 u64/i64 (32) + u8 (1) = 66 bytes
+    pub const LEN: usize = 32 + 1 + 8 * 4 + 1;
+
+    /// Reward tier for the keeper's current track record (bps of the cycle's SOL)
+    pub fn reward_bps(&self) -> u16 {
+        KEEPER_REWARD_TIERS
+            .iter()
+            .rev()
+            .find(|(min_cycles, _)| self.successful_cycles >= *min_cycles)
+            .map_or(0, |(_, bps)| *bps)
+    }
+
+    /// Count a completed cycle; returns the reward earned on `cycle_sol` at the tier
+    /// reached before this cycle
+    pub fn record_cycle(&mut self, cycle_sol: u64, now: i64) -> u64 {
+        let reward = (cycle_sol as u128 * self.reward_bps() as u128 / 10_000) as u64;
+        self.successful_cycles = self.successful_cycles.saturating_add(1);
+        self.last_cycle_timestamp = now;
+        reward
+    }
+}
@@ -1,11 +1,83 @@
+pub mod admin_log;
+pub mod alert_config;
+pub mod app_stats;
+pub mod bounty_vault;
+pub mod buy_commitment;
+pub mod change_queue;
+pub mod cycle_history;
+pub mod cycle_state;
+pub mod cycle_window;
+pub mod daily_stats;
 pub mod dat_state;
+pub mod dca_state;
+pub mod emergency_withdrawal;
+pub mod fee_attestation;
+pub mod fee_recipients;
+pub mod global_stats;
+pub mod governance;
+pub mod insurance_fund;
+pub mod keeper_info;
+pub mod monitor_key;
+pub mod partner_stats;
+pub mod portable_config;
+pub mod rebate_epoch;
 pub mod rebate_pool;
+pub mod referral_stats;
+pub mod reserve_vault;
+pub mod rewards_vault;
+pub mod root_set;
+pub mod root_stream;
+pub mod shadow_comparison;
+pub mod stake_account;
+pub mod stats_snapshot;
+pub mod token_config;
+pub mod token_deposit;
+pub mod token_registry;
 pub mod token_stats;
 pub mod user_stats;
+pub mod validator_bond;
+pub mod validator_set;
 pub mod validator_state;
+pub mod views;
 
+pub use admin_log::*;
+pub use alert_config::*;
+pub use app_stats::*;
+pub use bounty_vault::*;
+pub use buy_commitment::*;
+pub use change_queue::*;
+pub use cycle_history::*;
+pub use cycle_state::*;
+pub use cycle_window::*;
+pub use daily_stats::*;
 pub use dat_state::*;
+pub use dca_state::*;
+pub use emergency_withdrawal::*;
+pub use fee_attestation::*;
+pub use fee_recipients::*;
+pub use global_stats::*;
+pub use governance::*;
+pub use insurance_fund::*;
+pub use keeper_info::*;
+pub use monitor_key::*;
+pub use partner_stats::*;
+pub use portable_config::*;
+pub use rebate_epoch::*;
 pub use rebate_pool::*;
+pub use referral_stats::*;
+pub use reserve_vault::*;
+pub use rewards_vault::*;
+pub use root_set::*;
+pub use root_stream::*;
+pub use shadow_comparison::*;
+pub use stake_account::*;
+pub use stats_snapshot::*;
+pub use token_config::*;
+pub use token_deposit::*;
+pub use token_registry::*;
 pub use token_stats::*;
 pub use user_stats::*;
+pub use validator_bond::*;
+pub use validator_set::*;
 pub use validator_state::*;
+pub use views::*;
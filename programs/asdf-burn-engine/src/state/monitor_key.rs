@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::constants::SECONDS_PER_DAY;
+
+/// Registered fee monitor key with a per-day attribution quota
+///
+/// Bounds the damage a single compromised monitor can do through
+/// update_pending_fees_monitor(). A key that exceeds its quota is
+/// suspended automatically until the admin reinstates it.
+///
+/// PDA Seeds: ["monitor_v1", authority]
+#[account]
+pub struct MonitorKey {
+    /// Monitor signing key
+    pub authority: Pubkey,
+
+    /// Maximum lamports this key may attribute per UTC day
+    pub daily_quota_lamports: u64,
+
+    /// Lamports attributed in the current day
+    pub attributed_today: u64,
+
+    /// Current day index (unix_timestamp / SECONDS_PER_DAY)
+    pub current_day: i64,
+
+    /// Cumulative lamports attributed by this key
+    pub total_attributed: u64,
+
+    /// Suspended after exceeding quota (admin must reinstate)
+    pub is_suspended: bool,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl MonitorKey {
+    /// Account size: Pubkey(32) + 4 u64/i64 (32) + bool(1) + u8(1) = 66 bytes
+    pub const LEN: usize = 32 + 8 * 4 + 1 + 1;
+
+    /// Roll the daily window and return the remaining quota for today
+    pub fn remaining_quota(&mut self, now: i64) -> u64 {
+        let day = now / SECONDS_PER_DAY;
+        if day != self.current_day {
+            self.current_day = day;
+            self.attributed_today = 0;
+        }
+        self.daily_quota_lamports.saturating_sub(self.attributed_today)
+    }
+}
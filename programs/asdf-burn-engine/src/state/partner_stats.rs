@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+/// Listing partner revenue share totals
+///
+/// One account per partner (exchange or launchpad) across every token that
+/// names it in TokenStats.partner. Credited by collect_fees().
+///
+/// PDA Seeds: ["partner_stats_v1", partner]
+#[account]
+pub struct PartnerStats {
+    /// Partner wallet receiving the revenue share
+    pub partner: Pubkey,
+
+    /// Lifetime SOL paid to the partner (lamports)
+    pub total_paid: u64,
+
+    /// Number of payouts
+    pub payouts_count: u64,
+
+    /// Timestamp of the last payout
+    pub last_paid_at: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl PartnerStats {
+    /// Account size: Pubkey (32) + 3 u64/i64 (24) + u8 (1) = 57 bytes
+    pub const LEN: usize = 32 + 8 * 3 + 1;
+}
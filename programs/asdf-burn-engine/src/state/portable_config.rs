@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use super::DATState;
+
+/// Portable DATState configuration (export_config / import_config)
+///
+/// Carries the operator-set parameters needed to restore a deployment under a
+/// new program id. Admin, bumps, counters and in-flight cycle state are not
+/// portable: the new deployment keeps its own. root_token_mint is exported for
+/// reference only - re-run set_root_token once the root TokenStats exists.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PortableConfig {
+    /// Blob format version (PortableConfig::VERSION)
+    pub version: u8,
+
+    pub asdf_mint: Pubkey,
+    pub wsol_mint: Pubkey,
+    pub pool_address: Pubkey,
+    pub pump_swap_program: Pubkey,
+    pub root_token_mint: Option<Pubkey>,
+
+    pub min_fees_threshold: u64,
+    pub max_fees_per_cycle: u64,
+    pub slippage_bps: u16,
+    pub min_cycle_interval: i64,
+    pub fee_split_bps: u16,
+    pub admin_operation_cooldown: i64,
+    pub max_price_deviation_bps: u16,
+
+    /// Circuit breaker baseline (0 = re-seed on the next root buy)
+    pub last_known_price: u64,
+}
+
+impl PortableConfig {
+    /// Current blob format version
+    pub const VERSION: u8 = 1;
+
+    /// Snapshot the portable part of DATState
+    pub fn from_state(state: &DATState) -> Self {
+        Self {
+            version: Self::VERSION,
+            asdf_mint: state.asdf_mint,
+            wsol_mint: state.wsol_mint,
+            pool_address: state.pool_address,
+            pump_swap_program: state.pump_swap_program,
+            root_token_mint: state.root_token_mint(),
+            min_fees_threshold: state.min_fees_threshold,
+            max_fees_per_cycle: state.max_fees_per_cycle,
+            slippage_bps: state.slippage_bps,
+            min_cycle_interval: state.min_cycle_interval,
+            fee_split_bps: state.fee_split_bps,
+            admin_operation_cooldown: state.admin_operation_cooldown,
+            max_price_deviation_bps: state.max_price_deviation_bps,
+            last_known_price: state.last_known_price,
+        }
+    }
+
+    /// Serialized blob as exported
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        self.try_to_vec().map_err(|_| ErrorCode::InvalidConfigBlob.into())
+    }
+
+    /// Parse a blob; rejects trailing bytes and unknown versions
+    pub fn decode(blob: &[u8]) -> Result<Self> {
+        let config = Self::try_from_slice(blob).map_err(|_| ErrorCode::InvalidConfigBlob)?;
+        require!(config.version == Self::VERSION, ErrorCode::ConfigVersionMismatch);
+        Ok(config)
+    }
+
+    /// Same bounds as the individual admin setters; the timelock can only be lengthened
+    pub fn validate(&self, current_cooldown: i64) -> Result<()> {
+        require!(self.slippage_bps >= 10 && self.slippage_bps <= 500, ErrorCode::SlippageConfigTooHigh);
+        require!(self.min_cycle_interval > 0, ErrorCode::InvalidParameter);
+        require!(
+            self.min_fees_threshold >= 1_000_000 && self.min_fees_threshold <= 1_000_000_000,
+            ErrorCode::InvalidParameter
+        );
+        require!(self.max_fees_per_cycle >= 10_000_000, ErrorCode::InvalidParameter);
+        require!(self.min_fees_threshold <= self.max_fees_per_cycle, ErrorCode::InvalidParameter);
+        require!(self.fee_split_bps >= 1000 && self.fee_split_bps <= 9000, ErrorCode::InvalidFeeSplit);
+        require!(self.max_price_deviation_bps <= 10000, ErrorCode::InvalidParameter);
+        require!(self.admin_operation_cooldown >= current_cooldown, ErrorCode::InvalidParameter);
+        Ok(())
+    }
+
+    /// Write the portable parameters into DATState (root_token_mint excluded)
+    pub fn apply(&self, state: &mut DATState) {
+        state.asdf_mint = self.asdf_mint;
+        state.wsol_mint = self.wsol_mint;
+        state.pool_address = self.pool_address;
+        state.pump_swap_program = self.pump_swap_program;
+        state.min_fees_threshold = self.min_fees_threshold;
+        state.max_fees_per_cycle = self.max_fees_per_cycle;
+        state.slippage_bps = self.slippage_bps;
+        state.min_cycle_interval = self.min_cycle_interval;
+        state.fee_split_bps = self.fee_split_bps;
+        state.admin_operation_cooldown = self.admin_operation_cooldown;
+        state.max_price_deviation_bps = self.max_price_deviation_bps;
+        state.last_known_price = self.last_known_price;
+    }
+}
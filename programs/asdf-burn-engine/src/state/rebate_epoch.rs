@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+/// Pro-rata rebate epoch
+///
+/// Deposits made with the open epoch accumulate into total_contributions
+/// (and the depositor's UserStats.epoch_contribution). close_rebate_epoch()
+/// freezes the epoch and reserves its pool_amount; each contributor then
+/// claims pool_amount * contribution / total_contributions.
+///
+/// PDA Seeds: ["rebate_epoch_v1", epoch (u64 LE)]
+#[account]
+pub struct RebateEpoch {
+    /// Epoch index (RebatePool.current_epoch when opened)
+    pub epoch: u64,
+
+    /// Sum of all contributions made during the epoch
+    pub total_contributions: u64,
+
+    /// $ASDF reserved for the epoch's contributors at close
+    pub pool_amount: u64,
+
+    /// $ASDF already claimed from pool_amount
+    pub claimed_amount: u64,
+
+    /// Timestamp the epoch opened
+    pub started_at: i64,
+
+    /// Timestamp the epoch closed (0 = open)
+    pub closed_at: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl RebateEpoch {
+    /// Account size: 6 u64/i64 (48) + u8 (1) = 49 bytes
+    pub const LEN: usize = 8 * 6 + 1;
+
+    /// Whether the epoch still accepts contributions
+    pub fn is_open(&self) -> bool {
+        self.closed_at == 0
+    }
+
+    /// Pro-rata share of pool_amount for a contribution (floored, never exceeds pool_amount in sum)
+    pub fn share_of(&self, contribution: u64) -> u64 {
+        if self.total_contributions == 0 {
+            return 0;
+        }
+        (self.pool_amount as u128 * contribution as u128 / self.total_contributions as u128) as u64
+    }
+}
@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::ErrorCode;
 
 /// Rebate Pool authority PDA for external app integration
 ///
@@ -11,7 +13,15 @@ use anchor_lang::prelude::*;
 ///
 /// Funding flow:
 /// - deposit_fee_asdf() splits: 99.448% → DAT ATA, 0.552% → Rebate Pool ATA
-/// - process_user_rebate() transfers from pool → user ATA
+/// - process_user_rebate() / claim_rebate() transfer from pool → user ATA
+/// - Split and threshold default to the constants until update_rebate_params
+///   is applied (admin, timelocked by admin_operation_cooldown)
+/// - request_rebate_draw() / select_rebate_winner() pick a winner weighted by
+///   pending_contribution from SlotHashes entropy; the seed is stored here
+/// - close_rebate_epoch() reserves each epoch's pro-rata pool (epoch_reserved);
+///   the other payouts only spend the unreserved balance
+/// - deposit_fee_asdf() pays a one-time onboarding bonus (pool → user ATA)
+///   on a user's first deposit while the bonus budget lasts
 ///
 /// PDA Seeds: ["rebate_pool"]
 #[account]
@@ -37,8 +47,79 @@ pub struct RebatePool {
     /// Total users who received rebates (unique count)
     pub unique_recipients: u64,
 
+    /// Onboarding bonus rate on a first deposit in bps (0 = disabled)
+    pub bonus_bps: u16,
+
+    /// Maximum onboarding bonus per user ($ASDF base units)
+    pub bonus_cap: u64,
+
+    /// Remaining global onboarding bonus budget ($ASDF base units)
+    pub bonus_budget: u64,
+
+    /// Total onboarding bonuses paid (lifetime)
+    pub total_bonus_paid: u64,
+
     /// Reserved for future use
-    pub _reserved: [u8; 32],
+    pub _reserved: [u8; 6],
+
+    // Tunable rebate economics (update_rebate_params → apply_rebate_params)
+
+    /// Rebate share of each deposit over SHARE_DENOMINATOR (0 = REBATE_SHARE default)
+    pub rebate_share: u32,
+
+    /// Rebate eligibility threshold (0 = REBATE_THRESHOLD_SOL_EQUIV default)
+    pub rebate_threshold: u64,
+
+    /// Timelock: proposed rebate share
+    pub pending_rebate_share: u32,
+
+    /// Timelock: proposed rebate threshold
+    pub pending_rebate_threshold: u64,
+
+    /// Timelock: when the pending params were proposed (0 = none pending)
+    pub pending_params_timestamp: i64,
+
+    // Weighted rebate lottery (request_rebate_draw → select_rebate_winner)
+
+    /// Slot the pending draw was requested (0 = no draw pending)
+    pub draw_request_slot: u64,
+
+    /// Slot whose bank hash seeds the pending draw
+    pub draw_target_slot: u64,
+
+    /// Commitment to the pending draw's candidate UserStats list
+    pub draw_candidates_hash: [u8; 32],
+
+    /// Seed of the last completed draw (auditable: see draw_seed)
+    pub draw_randomness: [u8; 32],
+
+    /// Winner of the last completed draw
+    pub draw_winner: Pubkey,
+
+    /// Number of completed draws (nonce of the next draw)
+    pub draws_count: u64,
+
+    // Pro-rata rebate epochs (see RebateEpoch)
+
+    /// Index of the open RebateEpoch
+    pub current_epoch: u64,
+
+    /// Timestamp the open epoch started
+    pub epoch_started_at: i64,
+
+    /// $ASDF reserved for closed epochs and not yet claimed
+    pub epoch_reserved: u64,
+
+    // Referrals (see ReferralStats)
+
+    /// Referrer share of a referred deposit's rebate portion in bps (0 = disabled)
+    pub referral_share_bps: u16,
+
+    /// $ASDF credited to referrers and not yet claimed
+    pub referral_reserved: u64,
+
+    /// Layout version (see migrations::MIGRATIONS), stamped at init and by migrate_account
+    pub version: u8,
 }
 
 impl RebatePool {
@@ -50,7 +131,103 @@ impl RebatePool {
     /// - last_rebate_timestamp: 8 bytes (i64)
     /// - last_rebate_slot: 8 bytes (u64)
     /// - unique_recipients: 8 bytes (u64)
-    /// - _reserved: 32 bytes
-    /// Total: 81 bytes
-    pub const LEN: usize = 1 + 8 + 8 + 8 + 8 + 8 + 8 + 32;
+    /// - bonus_bps: 2 bytes (u16)
+    /// - bonus_cap, bonus_budget, total_bonus_paid: 24 bytes (3 u64)
+    /// - _reserved: 6 bytes
+    /// - rebate_share, pending_rebate_share: 8 bytes (2 u32)
+    /// - rebate_threshold, pending_rebate_threshold, pending_params_timestamp: 24 bytes (3 u64/i64)
+    /// - draw_request_slot, draw_target_slot, draws_count: 24 bytes (3 u64)
+    /// - draw_candidates_hash, draw_randomness, draw_winner: 96 bytes (3 x 32)
+    /// - current_epoch, epoch_started_at, epoch_reserved: 24 bytes (3 u64/i64)
+    /// - referral_share_bps: 2 bytes (u16), referral_reserved: 8 bytes (u64)
+    /// - version: 1 byte (u8)
+    ///
+    /// Total: 268 bytes (bonus fields carved from the former 32 reserved bytes)
+    pub const LEN: usize = 1 + 8 + 8 + 8 + 8 + 8 + 8 + 2 + 8 + 8 + 8 + 6 + 4 + 8 + 4 + 8 + 8
+        + 8 + 8 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 2 + 8 + 1;
+
+    /// Pool balance not reserved for closed epochs or unclaimed referral rewards
+    pub fn available(&self, pool_balance: u64) -> u64 {
+        pool_balance
+            .saturating_sub(self.epoch_reserved)
+            .saturating_sub(self.referral_reserved)
+    }
+
+    /// Referrer reward carved from a referred deposit's rebate portion
+    pub fn referral_reward(&self, rebate_pool_amount: u64) -> u64 {
+        (rebate_pool_amount as u128 * self.referral_share_bps as u128 / 10000) as u64
+    }
+
+    /// Whether a draw is pending and its target slot hash can still be in SlotHashes at `slot`
+    pub fn draw_pending(&self, slot: u64) -> bool {
+        self.draw_request_slot != 0
+            && slot <= self.draw_target_slot.saturating_add(SLOT_HASHES_WINDOW)
+    }
+
+    /// Effective rebate share (over SHARE_DENOMINATOR)
+    pub fn rebate_share(&self) -> u32 {
+        if self.rebate_share == 0 { REBATE_SHARE } else { self.rebate_share }
+    }
+
+    /// Effective burn share (over SHARE_DENOMINATOR), complement of the rebate share
+    pub fn burn_share(&self) -> u32 {
+        SHARE_DENOMINATOR as u32 - self.rebate_share()
+    }
+
+    /// Effective rebate eligibility threshold
+    pub fn rebate_threshold(&self) -> u64 {
+        if self.rebate_threshold == 0 { REBATE_THRESHOLD_SOL_EQUIV } else { self.rebate_threshold }
+    }
+
+    /// Rebate paid on a pending contribution (rebate share of pending, 0.552% by default)
+    pub fn rebate_amount(&self, pending: u64) -> Result<u64> {
+        let amount = pending
+            .checked_mul(self.rebate_share() as u64)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(amount / SHARE_DENOMINATOR)
+    }
+
+    /// Whether proposed params are within bounds
+    pub fn rebate_params_valid(rebate_share: u32, rebate_threshold: u64) -> bool {
+        rebate_share > 0
+            && rebate_share <= MAX_REBATE_SHARE
+            && (MIN_REBATE_THRESHOLD..=MAX_REBATE_THRESHOLD).contains(&rebate_threshold)
+    }
+
+    /// Onboarding bonus for a first deposit of `amount`:
+    /// min(amount * bonus_bps, bonus_cap, bonus_budget, pool_balance), 0 when disabled
+    pub fn onboarding_bonus(&self, amount: u64, pool_balance: u64) -> u64 {
+        if self.bonus_bps == 0 {
+            return 0;
+        }
+        let by_rate = ((amount as u128) * self.bonus_bps as u128 / 10000) as u64;
+        by_rate
+            .min(self.bonus_cap)
+            .min(self.bonus_budget)
+            .min(pool_balance)
+    }
+
+    /// Validate and apply a rebate ChangeQueue parameter (ADMIN_OP_* tag); returns the previous value
+    pub fn apply_parameter(&mut self, param: u8, value: u64) -> Result<u64> {
+        let old_value = match param {
+            ADMIN_OP_REFERRAL_SHARE => {
+                require!(value <= MAX_REFERRAL_SHARE_BPS as u64, ErrorCode::InvalidParameter);
+                std::mem::replace(&mut self.referral_share_bps, value as u16) as u64
+            }
+            ADMIN_OP_ONBOARDING_BONUS_BPS => {
+                require!(value <= MAX_ONBOARDING_BONUS_BPS as u64, ErrorCode::InvalidParameter);
+                std::mem::replace(&mut self.bonus_bps, value as u16) as u64
+            }
+            ADMIN_OP_ONBOARDING_BONUS_CAP => {
+                require!(value <= MAX_ONBOARDING_BONUS_CAP, ErrorCode::InvalidParameter);
+                std::mem::replace(&mut self.bonus_cap, value)
+            }
+            ADMIN_OP_ONBOARDING_BONUS_BUDGET => {
+                require!(value <= MAX_ONBOARDING_BONUS_BUDGET, ErrorCode::InvalidParameter);
+                std::mem::replace(&mut self.bonus_budget, value)
+            }
+            _ => return err!(ErrorCode::InvalidParameter),
+        };
+        Ok(old_value)
+    }
 }
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+/// Referral totals for one referrer
+///
+/// Created by set_referrer() for the first user naming the referrer. Credited by
+/// deposit_fee_asdf() with RebatePool.referral_share_bps of each referred
+/// deposit's rebate portion; the reward stays in the rebate pool ATA
+/// (RebatePool.referral_reserved) until claim_referral_rewards().
+///
+/// PDA Seeds: ["referral_stats_v1", referrer]
+#[account]
+pub struct ReferralStats {
+    /// Referrer wallet
+    pub referrer: Pubkey,
+
+    /// Users who named this referrer
+    pub referred_users: u64,
+
+    /// Lifetime $ASDF deposited by referred users
+    pub total_referred_volume: u64,
+
+    /// Rewards credited and not yet claimed ($ASDF)
+    pub pending_rewards: u64,
+
+    /// Lifetime rewards claimed ($ASDF)
+    pub total_claimed: u64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl ReferralStats {
+    /// Account size: Pubkey (32) + 4 u64 (32) + u8 (1) = 65 bytes
+    pub const LEN: usize = 32 + 8 * 4 + 1;
+
+    /// Credit a referred deposit and its reward
+    pub fn credit(&mut self, deposit_amount: u64, reward: u64) {
+        self.total_referred_volume = self.total_referred_volume.saturating_add(deposit_amount);
+        self.pending_rewards = self.pending_rewards.saturating_add(reward);
+    }
+}
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+/// Treasury SOL reserve (rainy-day fund)
+///
+/// collect_fees moves dat_state.reserve_bps of the SOL drained from the creator
+/// vault into this PDA instead of leaving it for the buyback. The admin spends it
+/// on rent, keeper rewards or incident response by proposing (recipient, amount)
+/// with propose_reserve_deployment, then executing deploy_reserve once
+/// RESERVE_DEPLOY_TIMELOCK has elapsed, so every deployment is public first.
+///
+/// PDA Seeds: ["reserve_vault_v1"]
+/// Reserve funds are held in the PDA itself (on top of its rent)
+#[account]
+pub struct ReserveVault {
+    /// Cumulative lamports kept back by collect_fees
+    pub total_reserved: u64,
+
+    /// Cumulative lamports deployed out of the reserve
+    pub total_deployed: u64,
+
+    /// Proposed deployment recipient (Pubkey::default() = no pending deployment)
+    pub pending_recipient: Pubkey,
+
+    /// Proposed deployment amount
+    pub pending_amount: u64,
+
+    /// When the pending deployment was proposed (timelock start)
+    pub pending_proposed_at: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl ReserveVault {
+    /// Account size: 2 u64 (16) + Pubkey (32) + u64/i64 (16) + u8 (1) = 65 bytes
+    pub const LEN: usize = 8 * 2 + 32 + 8 * 2 + 1;
+
+    /// Whether a deployment is awaiting its timelock
+    pub fn has_pending(&self) -> bool {
+        self.pending_recipient != Pubkey::default()
+    }
+
+    /// Earliest timestamp the pending deployment may execute
+    pub fn executable_at(&self, timelock: i64) -> i64 {
+        self.pending_proposed_at.saturating_add(timelock)
+    }
+
+    /// Clear the pending proposal
+    pub fn clear_pending(&mut self) {
+        self.pending_recipient = Pubkey::default();
+        self.pending_amount = 0;
+        self.pending_proposed_at = 0;
+    }
+}
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+
+/// Holder reflection accounting for one mint
+///
+/// burn_and_update sends token_stats.reflection_bps of each buyback to the
+/// rewards token PDA instead of burning it. The admin periodically posts a Merkle
+/// snapshot allocating the accrued tokens pro-rata to holders; each holder claims
+/// their leaf once per epoch. Posting a new snapshot rolls the unclaimed remainder
+/// of the previous epoch back into the next allocation.
+///
+/// PDA Seeds: ["rewards_vault_v1", mint]
+#[account]
+pub struct RewardsVault {
+    /// Token mint whose buybacks are reflected
+    pub mint: Pubkey,
+
+    /// Current snapshot epoch (0 = no snapshot posted yet)
+    pub epoch: u64,
+
+    /// Merkle root of the current snapshot's (holder, amount) leaves
+    pub merkle_root: [u8; 32],
+
+    /// Tokens allocated by the current snapshot
+    pub epoch_amount: u64,
+
+    /// Tokens claimed against the current snapshot
+    pub epoch_claimed: u64,
+
+    /// Reflected tokens not yet allocated to a snapshot
+    pub unallocated: u64,
+
+    /// Lifetime tokens reflected by burn_and_update
+    pub total_reflected: u64,
+
+    /// Lifetime tokens claimed by holders
+    pub total_claimed: u64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl RewardsVault {
+    /// Account size: Pubkey (32) + 6 u64 (48) + [u8; 32] (32) + u8 (1) = 113 bytes
+    pub const LEN: usize = 32 + 8 + 32 + 8 * 5 + 1;
+
+    /// Credit tokens reflected by a burn
+    pub fn accrue(&mut self, amount: u64) {
+        self.unallocated = self.unallocated.saturating_add(amount);
+        self.total_reflected = self.total_reflected.saturating_add(amount);
+    }
+
+    /// Start a new epoch allocating `amount` under `merkle_root`
+    /// The previous epoch's unclaimed remainder is rolled into the allocatable balance
+    pub fn post_snapshot(&mut self, merkle_root: [u8; 32], amount: u64) -> Result<()> {
+        let unclaimed = self.epoch_amount.saturating_sub(self.epoch_claimed);
+        let allocatable = self.unallocated.checked_add(unclaimed).ok_or(ErrorCode::MathOverflow)?;
+        require!(amount > 0 && amount <= allocatable, ErrorCode::ReflectionSnapshotExceedsAccrued);
+
+        self.unallocated = allocatable - amount;
+        self.epoch = self.epoch.saturating_add(1);
+        self.merkle_root = merkle_root;
+        self.epoch_amount = amount;
+        self.epoch_claimed = 0;
+        Ok(())
+    }
+
+    /// Record a holder claim against the current snapshot
+    pub fn claim(&mut self, amount: u64) -> Result<()> {
+        require!(self.epoch > 0, ErrorCode::ReflectionSnapshotExhausted);
+        let claimed = self.epoch_claimed.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+        require!(claimed <= self.epoch_amount, ErrorCode::ReflectionSnapshotExhausted);
+        self.epoch_claimed = claimed;
+        self.total_claimed = self.total_claimed.saturating_add(amount);
+        Ok(())
+    }
+}
+
+/// Marks a holder's claim for one reflection epoch (prevents double claims)
+///
+/// PDA Seeds: ["reflection_claim_v1", rewards_vault, epoch LE, holder]
+#[account]
+pub struct ReflectionClaim {
+    /// Holder who claimed
+    pub holder: Pubkey,
+
+    /// Epoch claimed
+    pub epoch: u64,
+
+    /// Tokens claimed
+    pub amount: u64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl ReflectionClaim {
+    /// Account size: Pubkey (32) + 2 u64 (16) + u8 (1) = 49 bytes
+    pub const LEN: usize = 32 + 8 * 2 + 1;
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_ROOTS;
+use crate::errors::ErrorCode;
+
+/// Additional root tokens (e.g. region- or theme-specific roots)
+///
+/// DATState.root_token_mint stays the primary root; secondaries without a
+/// TokenConfig.assigned_root keep splitting to it. Each listed root collects
+/// from its own ["root_treasury", root_mint] PDA.
+///
+/// PDA Seeds: ["root_set_v1"]
+#[account]
+pub struct RootSet {
+    /// Listed roots; only the first root_count entries are meaningful
+    pub roots: [Pubkey; MAX_ROOTS],
+
+    /// Number of listed roots
+    pub root_count: u8,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl RootSet {
+    /// Account size: 8 Pubkey (256) + u8 (1) + u8 (1) = 258 bytes
+    pub const LEN: usize = 32 * MAX_ROOTS + 1 + 1;
+
+    /// Listed roots
+    pub fn active(&self) -> &[Pubkey] {
+        &self.roots[..self.root_count as usize]
+    }
+
+    pub fn contains(&self, mint: &Pubkey) -> bool {
+        self.active().contains(mint)
+    }
+
+    /// List a root (fails if already listed or the set is full)
+    pub fn add(&mut self, mint: Pubkey) -> Result<()> {
+        require!(!self.contains(&mint), ErrorCode::RootAlreadyInSet);
+        let count = self.root_count as usize;
+        require!(count < MAX_ROOTS, ErrorCode::RootSetFull);
+        self.roots[count] = mint;
+        self.root_count += 1;
+        Ok(())
+    }
+
+    /// Unlist a root, moving the last entry into its slot
+    pub fn remove(&mut self, mint: &Pubkey) -> Result<()> {
+        let at = self.active().iter().position(|r| r == mint).ok_or(ErrorCode::RootNotInSet)?;
+        let last = self.root_count as usize - 1;
+        self.roots[at] = self.roots[last];
+        self.roots[last] = Pubkey::default();
+        self.root_count -= 1;
+        Ok(())
+    }
+
+    /// Root a secondary splits to: assigned_root when set (it must still be listed),
+    /// otherwise the primary root
+    pub fn resolve(assigned_root: Pubkey, primary: Option<Pubkey>, root_set: Option<&RootSet>) -> Result<Pubkey> {
+        if assigned_root == Pubkey::default() {
+            return primary.ok_or(error!(ErrorCode::InvalidRootToken));
+        }
+        require!(root_set.is_some_and(|set| set.contains(&assigned_root)), ErrorCode::RootNotInSet);
+        Ok(assigned_root)
+    }
+}
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+/// Root share streaming for one secondary
+///
+/// While flush_every_cycles > 0, execute_buy_secondary accrues the root's share
+/// here (the SOL stays in dat_authority, reserved via DATState.root_escrow_lamports)
+/// instead of transferring it every cycle; flush_to_root moves it to the assigned
+/// root's treasury in one transfer once flush_every_cycles cycles have accrued.
+///
+/// PDA Seeds: ["root_stream_v1", mint]
+#[account]
+pub struct RootStream {
+    /// Secondary token mint
+    pub mint: Pubkey,
+
+    /// Root share accrued since the last flush (lamports)
+    pub accrued_lamports: u64,
+
+    /// Cycles accrued since the last flush
+    pub cycles_accrued: u16,
+
+    /// Cycles between flushes (0 = streaming off, lump transfers each cycle)
+    pub flush_every_cycles: u16,
+
+    /// Lifetime lamports flushed to the root treasury
+    pub total_flushed: u64,
+
+    /// Timestamp of the last flush (0 = never)
+    pub last_flush_at: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl RootStream {
+    /// Account size: Pubkey (32) + 3 u64/i64 (24) + 2 u16 (4) + u8 (1) = 61 bytes
+    pub const LEN: usize = 32 + 8 + 2 + 2 + 8 + 8 + 1;
+
+    pub fn is_streaming(&self) -> bool {
+        self.flush_every_cycles > 0
+    }
+
+    /// Accrue one cycle's root share
+    pub fn accrue(&mut self, lamports: u64) {
+        self.accrued_lamports = self.accrued_lamports.saturating_add(lamports);
+        self.cycles_accrued = self.cycles_accrued.saturating_add(1);
+    }
+
+    /// Whether flush_to_root may run: something accrued, and either the schedule
+    /// is reached or streaming was turned off with a balance left over
+    pub fn flush_due(&self) -> bool {
+        self.accrued_lamports > 0
+            && (!self.is_streaming() || self.cycles_accrued >= self.flush_every_cycles)
+    }
+
+    /// Reset the accrual after a flush; returns the flushed amount
+    pub fn take_flush(&mut self, now: i64) -> u64 {
+        let amount = self.accrued_lamports;
+        self.accrued_lamports = 0;
+        self.cycles_accrued = 0;
+        self.total_flushed = self.total_flushed.saturating_add(amount);
+        self.last_flush_at = now;
+        amount
+    }
+}
@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+
+/// Shadow-mode comparison between the live buy math and a candidate
+///
+/// When shadow_math is enabled, execute_buy computes both the current
+/// (slippage-on-output) parameters and the candidate exact-out parameters
+/// from the same pool snapshot, executes with the current math, and records
+/// the delta here. Lets a math migration be validated against live data.
+///
+/// PDA Seeds: ["shadow_v1", mint]
+#[account]
+pub struct ShadowComparison {
+    /// Token mint being compared
+    pub mint: Pubkey,
+
+    /// Whether execute_buy records comparisons for this mint
+    pub shadow_math: bool,
+
+    /// Number of comparisons recorded
+    pub samples: u64,
+
+    /// Slot of the last comparison
+    pub last_slot: u64,
+
+    /// Current math: max SOL cost of the last buy
+    pub last_old_sol_cost: u64,
+
+    /// Current math: desired tokens of the last buy
+    pub last_old_tokens: u64,
+
+    /// Candidate math: max SOL cost of the last buy
+    pub last_new_sol_cost: u64,
+
+    /// Candidate math: desired tokens of the last buy
+    pub last_new_tokens: u64,
+
+    /// Tokens actually received by the last buy (executed with current math)
+    pub last_actual_tokens: u64,
+
+    /// Sum of (new - old) desired tokens across samples
+    pub cumulative_token_delta: i64,
+
+    /// Sum of (new - old) max SOL cost across samples
+    pub cumulative_sol_delta: i64,
+
+    /// Largest |new - old| desired tokens seen, in bps of the old value
+    pub max_token_delta_bps: u16,
+
+    /// Last comparison (or configuration) timestamp
+    pub updated_at: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl ShadowComparison {
+    /// Account size: Pubkey(32) + 10 u64/i64 (80) + u16(2) + bool(1) + u8(1) = 116 bytes
+    pub const LEN: usize = 32 + 8 * 10 + 2 + 1 + 1;
+
+    /// Record one old-vs-new comparison and return (token_delta, sol_delta)
+    pub fn record(
+        &mut self,
+        old: (u64, u64),
+        new: (u64, u64),
+        actual_tokens: u64,
+        slot: u64,
+        now: i64,
+    ) -> (i64, i64) {
+        let (old_sol, old_tokens) = old;
+        let (new_sol, new_tokens) = new;
+        let token_delta = signed_delta(new_tokens, old_tokens);
+        let sol_delta = signed_delta(new_sol, old_sol);
+
+        let delta_bps = if old_tokens == 0 {
+            if new_tokens == 0 { 0 } else { 10000 }
+        } else {
+            ((new_tokens.abs_diff(old_tokens) as u128) * 10000 / old_tokens as u128).min(u16::MAX as u128) as u16
+        };
+
+        self.samples = self.samples.saturating_add(1);
+        self.last_slot = slot;
+        self.last_old_sol_cost = old_sol;
+        self.last_old_tokens = old_tokens;
+        self.last_new_sol_cost = new_sol;
+        self.last_new_tokens = new_tokens;
+        self.last_actual_tokens = actual_tokens;
+        self.cumulative_token_delta = self.cumulative_token_delta.saturating_add(token_delta);
+        self.cumulative_sol_delta = self.cumulative_sol_delta.saturating_add(sol_delta);
+        self.max_token_delta_bps = self.max_token_delta_bps.max(delta_bps);
+        self.updated_at = now;
+
+        (token_delta, sol_delta)
+    }
+}
+
+/// new - old as i64, saturating at the i64 bounds
+fn signed_delta(new: u64, old: u64) -> i64 {
+    if new >= old {
+        i64::try_from(new - old).unwrap_or(i64::MAX)
+    } else {
+        i64::try_from(old - new).map(|d| -d).unwrap_or(i64::MIN)
+    }
+}
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use crate::constants::{MAX_STAKE_BOOST_BPS, MAX_STAKE_LOCK, MIN_STAKE_LOCK};
+use crate::errors::ErrorCode;
+
+/// $ASDF locked by a user for a fee-share boost and governance weight
+///
+/// stake() escrows the tokens in a dat_authority-owned vault until locked_until.
+/// While locked, the stake boosts the rebate on contributions up to the staked
+/// amount (longer locks boost more) and adds its amount to the owner's vote weight.
+///
+/// PDA Seeds: ["stake_v1", owner]
+#[account]
+pub struct StakeAccount {
+    /// User who staked (receives the tokens back on unstake)
+    pub owner: Pubkey,
+
+    /// $ASDF held in the stake vault
+    pub amount: u64,
+
+    /// Tokens cannot be withdrawn before this timestamp
+    pub locked_until: i64,
+
+    /// Lock length committed to (sets the boost)
+    pub lock_duration: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl StakeAccount {
+    /// Account size: Pubkey (32) + 3 u64/i64 (24) + u8 (1) = 57 bytes
+    pub const LEN: usize = 32 + 8 * 3 + 1;
+
+    /// Whether the stake is still locked at `now`
+    pub fn is_locked(&self, now: i64) -> bool {
+        self.amount > 0 && now < self.locked_until
+    }
+
+    /// Add `amount` locked for at least `lock_duration` from `now`
+    /// A deposit never shortens an active lock
+    pub fn deposit(&mut self, amount: u64, lock_duration: i64, now: i64) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidParameter);
+        require!(
+            (MIN_STAKE_LOCK..=MAX_STAKE_LOCK).contains(&lock_duration),
+            ErrorCode::InvalidLockDuration
+        );
+        if !self.is_locked(now) {
+            self.lock_duration = 0;
+        }
+        self.amount = self.amount.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+        self.lock_duration = self.lock_duration.max(lock_duration);
+        self.locked_until = self.locked_until.max(now.saturating_add(lock_duration));
+        Ok(())
+    }
+
+    /// Re-lock for `lock_duration` from `now`; must not end before the current lock
+    pub fn extend(&mut self, lock_duration: i64, now: i64) -> Result<()> {
+        require!(self.amount > 0, ErrorCode::NoStake);
+        require!(
+            (MIN_STAKE_LOCK..=MAX_STAKE_LOCK).contains(&lock_duration),
+            ErrorCode::InvalidLockDuration
+        );
+        let locked_until = now.saturating_add(lock_duration);
+        require!(locked_until >= self.locked_until, ErrorCode::InvalidLockDuration);
+        self.locked_until = locked_until;
+        self.lock_duration = lock_duration;
+        Ok(())
+    }
+
+    /// Rebate boost in bps of the base rebate: 0 once unlocked, MAX_STAKE_BOOST_BPS at MAX_STAKE_LOCK
+    pub fn boost_bps(&self, now: i64) -> u64 {
+        if !self.is_locked(now) {
+            return 0;
+        }
+        MAX_STAKE_BOOST_BPS * self.lock_duration.min(MAX_STAKE_LOCK) as u64 / MAX_STAKE_LOCK as u64
+    }
+
+    /// Vote weight the stake adds to a ballot on a proposal voting until `voting_ends_at`
+    /// (only stakes that stay locked through the vote count)
+    pub fn vote_weight(&self, voting_ends_at: i64) -> u64 {
+        if self.locked_until >= voting_ends_at { self.amount } else { 0 }
+    }
+
+    /// Withdraw the whole stake once unlocked; returns the amount released
+    pub fn withdraw(&mut self, now: i64) -> Result<u64> {
+        require!(self.amount > 0, ErrorCode::NoStake);
+        require!(now >= self.locked_until, ErrorCode::StakeLocked);
+        let amount = self.amount;
+        self.amount = 0;
+        self.lock_duration = 0;
+        Ok(amount)
+    }
+}
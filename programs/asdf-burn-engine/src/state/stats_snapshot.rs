@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::helpers::{stats_leaf_hash, verify_merkle_proof};
+use crate::state::TokenStats;
+
+/// Daily Merkle commitment of every TokenStats account
+///
+/// Leaves are stats_leaf_hash() of each TokenStats, ordered by mint bytes
+/// ascending (leaf index = rank). Third parties can prove a token's historical
+/// stats with verify_snapshot_inclusion() after the live account has changed.
+///
+/// PDA Seeds: ["stats_snapshot_v1", day (u64 LE)] where day = unix_timestamp / 86400
+#[account]
+pub struct StatsSnapshot {
+    /// Day index (unix_timestamp / SECONDS_PER_DAY)
+    pub day: u64,
+
+    /// Merkle root of all TokenStats leaves for the day
+    pub merkle_root: [u8; 32],
+
+    /// Number of leaves (TokenStats accounts) in the tree
+    pub token_count: u32,
+
+    /// Operator that committed the snapshot
+    pub committer: Pubkey,
+
+    /// Commit timestamp
+    pub committed_at: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+/// Canonical snapshot leaf for one TokenStats account
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct StatsSnapshotLeaf {
+    pub mint: Pubkey,
+    pub total_burned: u64,
+    pub total_sol_collected: u64,
+    pub total_sol_used: u64,
+    pub total_buybacks: u64,
+    pub usd_value_burned: u64,
+}
+
+/// Merkle inclusion proof for a snapshot leaf (verify_snapshot_inclusion argument)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct StatsLeafProof {
+    pub leaf: StatsSnapshotLeaf,
+    /// Leaf position in the mint-ordered tree
+    pub index: u32,
+    pub proof: Vec<[u8; 32]>,
+}
+
+impl StatsSnapshotLeaf {
+    /// Leaf for the current state of a TokenStats account
+    pub fn from_stats(stats: &TokenStats) -> Self {
+        Self {
+            mint: stats.mint,
+            total_burned: stats.total_burned,
+            total_sol_collected: stats.total_sol_collected,
+            total_sol_used: stats.total_sol_used,
+            total_buybacks: stats.total_buybacks,
+            usd_value_burned: stats.usd_value_burned,
+        }
+    }
+
+    pub fn hash(&self) -> [u8; 32] {
+        stats_leaf_hash(
+            &self.mint,
+            self.total_burned,
+            self.total_sol_collected,
+            self.total_sol_used,
+            self.total_buybacks,
+            self.usd_value_burned,
+        )
+    }
+}
+
+impl StatsSnapshot {
+    /// Account size: u64 (8) + root (32) + u32 (4) + Pubkey (32) + i64 (8) + u8 (1) = 85 bytes
+    pub const LEN: usize = 8 + 32 + 4 + 32 + 8 + 1;
+
+    /// Verify a leaf proof against the committed root
+    pub fn verify_inclusion(&self, leaf: &StatsLeafProof) -> Result<()> {
+        require!(leaf.proof.len() <= MAX_MERKLE_PROOF_DEPTH, ErrorCode::InvalidMerkleProof);
+        require!(leaf.index < self.token_count, ErrorCode::InvalidMerkleProof);
+        require!(
+            verify_merkle_proof(&self.merkle_root, leaf.leaf.hash(), leaf.index, &leaf.proof),
+            ErrorCode::InvalidMerkleProof
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use crate::constants::OFFBOARD_TIMELOCK;
+use crate::errors::ErrorCode;
+
+/// Maximum length of a token's info URI in bytes
+pub const MAX_INFO_URI_LEN: usize = 96;
+
+/// Per-token ecosystem directory metadata
+///
+/// Holds an admin-set URI pointing to the token's ecosystem page or manifest,
+/// so the on-chain registry alone can render a complete ecosystem directory,
+/// and the root a secondary is assigned to. Externally launched tokens also
+/// record who onboarded them.
+///
+/// PDA Seeds: ["token_config_v1", mint]
+#[account]
+pub struct TokenConfig {
+    /// Token mint this config describes
+    pub mint: Pubkey,
+
+    /// UTF-8 info URI, zero-padded to MAX_INFO_URI_LEN
+    pub info_uri: [u8; MAX_INFO_URI_LEN],
+
+    /// Timestamp of the last update
+    pub updated_at: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+
+    /// Root this secondary splits fees to (default = DATState.root_token_mint)
+    pub assigned_root: Pubkey,
+
+    /// Layout version (see migrations::MIGRATIONS), stamped at init and by migrate_account
+    pub version: u8,
+
+    /// Creator that handed its bonding curve to dat_authority (default = launched by the DAT)
+    pub original_creator: Pubkey,
+
+    /// When onboard_external_token ran (0 = not onboarded)
+    pub onboarded_at: i64,
+
+    /// When the original creator called request_offboard (0 = not requested)
+    pub offboard_requested_at: i64,
+}
+
+impl TokenConfig {
+    /// Account size: Pubkey(32) + info_uri(96) + i64(8) + u8(1) + Pubkey(32) + u8(1) + Pubkey(32) + 2 * i64(16) = 218 bytes
+    pub const LEN: usize = 32 + MAX_INFO_URI_LEN + 8 + 1 + 32 + 1 + 32 + 8 + 8;
+
+    /// Zero-pad a URI into the fixed-size field (None if longer than MAX_INFO_URI_LEN)
+    pub fn encode_uri(uri: &str) -> Option<[u8; MAX_INFO_URI_LEN]> {
+        let bytes = uri.as_bytes();
+        if bytes.len() > MAX_INFO_URI_LEN {
+            return None;
+        }
+        let mut out = [0u8; MAX_INFO_URI_LEN];
+        out[..bytes.len()].copy_from_slice(bytes);
+        Some(out)
+    }
+
+    /// Whether this token was onboarded by an external creator (who may offboard it)
+    pub fn is_onboarded(&self) -> bool {
+        self.onboarded_at != 0 && self.original_creator != Pubkey::default()
+    }
+
+    /// Fail unless offboarding was requested at least OFFBOARD_TIMELOCK before `now`
+    pub fn require_offboard_ready(&self, now: i64) -> Result<()> {
+        require!(self.offboard_requested_at != 0, ErrorCode::OffboardNotRequested);
+        require!(
+            now >= self.offboard_requested_at.saturating_add(OFFBOARD_TIMELOCK),
+            ErrorCode::OffboardTimelockActive
+        );
+        Ok(())
+    }
+
+    /// Stored URI without trailing padding
+    pub fn uri(&self) -> String {
+        let len = self.info_uri.iter().position(|b| *b == 0).unwrap_or(MAX_INFO_URI_LEN);
+        String::from_utf8_lossy(&self.info_uri[..len]).into_owned()
+    }
+}
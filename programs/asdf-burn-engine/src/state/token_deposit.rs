@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+/// Escrowed non-$ASDF fee deposit awaiting conversion
+///
+/// One account per (user, mint). deposit_fee_token() escrows the tokens in a
+/// dat_authority-owned vault; convert_token_deposit() releases them to the
+/// orchestrator in exchange for $ASDF, which takes the standard burn/rebate split.
+/// If no conversion happens, the user can reclaim the tokens after
+/// TOKEN_DEPOSIT_REFUND_DELAY via refund_token_deposit().
+///
+/// PDA Seeds: ["token_deposit_v1", user, mint]
+#[account]
+pub struct TokenDeposit {
+    /// User credited once the deposit is converted
+    pub user: Pubkey,
+
+    /// Deposited token mint
+    pub mint: Pubkey,
+
+    /// Tokens held in the escrow vault
+    pub amount: u64,
+
+    /// Minimum $ASDF the conversion must deliver (summed across deposits)
+    pub min_asdf_out: u64,
+
+    /// Timestamp of the latest deposit (refund delay restarts on each deposit)
+    pub deposited_at: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl TokenDeposit {
+    /// Account size: 2 Pubkey (64) + 3 u64/i64 (24) + u8 (1) = 89 bytes
+    pub const LEN: usize = 32 * 2 + 8 * 3 + 1;
+
+    /// Whether the user may reclaim the escrowed tokens at `now`
+    pub fn refund_unlocked(&self, now: i64, refund_delay: i64) -> bool {
+        now >= self.deposited_at.saturating_add(refund_delay)
+    }
+
+    /// Empty the escrow after conversion or refund
+    pub fn clear(&mut self) {
+        self.amount = 0;
+        self.min_asdf_out = 0;
+    }
+}
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use crate::constants::TOKEN_REGISTRY_PAGE_SIZE;
+use crate::errors::ErrorCode;
+
+/// Header of the on-chain list of ecosystem tokens
+///
+/// Lets clients enumerate every mint with a TokenStats by reading pages
+/// 0..page_count (getMultipleAccounts) instead of getProgramAccounts.
+///
+/// PDA Seeds: ["token_registry_v1"]
+#[account]
+pub struct TokenRegistry {
+    /// Number of created pages (page indices 0..page_count)
+    pub page_count: u16,
+
+    /// Registered mints across all pages
+    pub token_count: u32,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl TokenRegistry {
+    /// Account size: u16 (2) + u32 (4) + u8 (1) = 7 bytes
+    pub const LEN: usize = 2 + 4 + 1;
+
+    /// A new page may be created only once every existing page has been filled
+    pub fn can_add_page(&self) -> bool {
+        self.token_count as usize >= self.page_count as usize * TOKEN_REGISTRY_PAGE_SIZE
+    }
+}
+
+/// One fixed-size page of the TokenRegistry
+///
+/// Registration appends to any page with room; removal moves the page's last
+/// entry into the freed slot, so order within a page is not stable.
+///
+/// PDA Seeds: ["token_registry_page_v1", page LE]
+#[account]
+pub struct TokenRegistryPage {
+    /// Page index
+    pub page: u16,
+
+    /// Number of listed mints; only the first `count` entries are meaningful
+    pub count: u16,
+
+    /// Listed mints
+    pub mints: [Pubkey; TOKEN_REGISTRY_PAGE_SIZE],
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl TokenRegistryPage {
+    /// Account size: u16 (2) + u16 (2) + 64 Pubkey (2048) + u8 (1) = 2053 bytes
+    pub const LEN: usize = 2 + 2 + 32 * TOKEN_REGISTRY_PAGE_SIZE + 1;
+
+    /// Listed mints
+    pub fn active(&self) -> &[Pubkey] {
+        &self.mints[..self.count as usize]
+    }
+
+    /// List a mint (fails if the page is full)
+    pub fn add(&mut self, mint: Pubkey) -> Result<()> {
+        let count = self.count as usize;
+        require!(count < TOKEN_REGISTRY_PAGE_SIZE, ErrorCode::TokenRegistryPageFull);
+        self.mints[count] = mint;
+        self.count += 1;
+        Ok(())
+    }
+
+    /// Unlist a mint, moving the last entry into its slot
+    pub fn remove(&mut self, mint: &Pubkey) -> Result<()> {
+        let at = self.active().iter().position(|m| m == mint).ok_or(ErrorCode::TokenNotInRegistry)?;
+        let last = self.count as usize - 1;
+        self.mints[at] = self.mints[last];
+        self.mints[last] = Pubkey::default();
+        self.count -= 1;
+        Ok(())
+    }
+}
@@ -1,14 +1,39 @@
 use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use crate::constants::{
+    ADMIN_OP_BURN_RATIO, ADMIN_OP_DUST_THRESHOLD, ADMIN_OP_LIQUIDITY_RATIO, ADMIN_OP_REFLECTION_RATIO,
+    MAX_DUST_THRESHOLD, MAX_LIQUIDITY_BPS, MAX_PENDING_FEES, MIN_BURN_BPS, TOKEN_PAUSE_FAILURE_THRESHOLD,
+};
+use crate::errors::ErrorCode;
 
 /// Per-token statistics tracking
 ///
 /// Each token in the ecosystem has its own TokenStats account
 /// to track individual metrics like burns, fees, and cycles.
-#[account]
+///
+/// Zero-copy: loaded in place through AccountLoader instead of deserialized. Fields are
+/// grouped by alignment (Pubkeys, 8-byte, 2-byte, 1-byte) so the repr(C) layout has no
+/// padding; flags are u8 (0 / 1). Layouts up to V12 were Borsh and are relaid out by
+/// migrations::token_stats_v13. New fields take bytes from `_reserved` or are appended
+/// after it, keeping the size a multiple of 8.
+#[account(zero_copy)]
 pub struct TokenStats {
-    /// The token mint this stats account tracks
+    /// The token mint this stats account tracks (first field in every layout)
     pub mint: Pubkey,
 
+    /// Active DAT token account for this mint (token account rotation, compromise recovery)
+    /// Pubkey::default() = dat_authority ATA (never rotated)
+    pub dat_token_account: Pubkey,
+
+    /// Account that paid rent to initialize this TokenStats (default = unknown, pre-migration)
+    pub stats_payer: Pubkey,
+
+    /// Listing partner receiving a share of collected fees (default = no partner)
+    pub partner: Pubkey,
+
+    /// Timelock: proposed partner
+    pub pending_partner: Pubkey,
+
     /// Total tokens burned for this specific token
     pub total_burned: u64,
 
@@ -36,14 +61,6 @@ pub struct TokenStats {
     /// Tokens burned in last cycle
     pub last_cycle_burned: u64,
 
-    /// Whether this is the root token (receives 44.8% from all secondaries)
-    pub is_root_token: bool,
-
-    /// PDA bump seed
-    pub bump: u8,
-
-    // Per-token fee tracking for multi-token ecosystems
-
     /// Accumulated fees not yet collected (daemon tracks attribution)
     pub pending_fees_lamports: u64,
 
@@ -52,9 +69,223 @@ pub struct TokenStats {
 
     /// Number of ecosystem cycles this token participated in
     pub cycles_participated: u64,
+
+    /// Total tokens sent to the community treasury instead of burned
+    pub total_sent_to_community_treasury: u64,
+
+    /// Cumulative USD value of burned tokens in micro-USD (SOL/USD oracle at burn time)
+    pub usd_value_burned: u64,
+
+    /// Lifetime SOL paid to the partner for this token (lamports)
+    pub total_partner_paid: u64,
+
+    /// Timelock: when the partner change was proposed (0 = none pending)
+    pub pending_partner_timestamp: i64,
+
+    /// Largest DAT token balance sweep_dust may burn outside a cycle (0 = sweeping disabled)
+    pub dust_threshold: u64,
+
+    /// Lifetime tokens burned by sweep_dust (included in total_burned)
+    pub dust_burned: u64,
+
+    /// When record_failure last counted a failure of this token (0 = never)
+    pub last_failure_timestamp: i64,
+
+    /// Lifetime SOL deposited into the PumpSwap pool by provide_liquidity (lamports)
+    pub total_liquidity_sol: u64,
+
+    /// Part of pending_fees_lamports the partner cut was already paid on (ecosystem collects
+    /// keep pending fees until the allocation settles them)
+    pub partner_paid_pending: u64,
+
+    /// Share of each buyback burned in basis points (10000 = 100% burn)
+    /// Remainder is transferred to the community treasury token PDA
+    pub burn_bps: u16,
+
+    /// Partner share of the fees attributed to this token (bps, <= MAX_PARTNER_SHARE_BPS)
+    pub partner_share_bps: u16,
+
+    /// Timelock: proposed partner share
+    pub pending_partner_share_bps: u16,
+
+    /// Share of each buyback sent to the RewardsVault instead of burned (bps, <= burn_bps)
+    pub reflection_bps: u16,
+
+    /// Share of each cycle's SOL and bought tokens provide_liquidity may deposit (bps, <= MAX_LIQUIDITY_BPS)
+    pub liquidity_bps: u16,
+
+    /// TokenRegistryPage listing this mint (meaningful when in_registry)
+    pub registry_page: u16,
+
+    /// Whether this is the root token (receives 44.8% from all secondaries)
+    pub is_root_token: u8,
+
+    /// PDA bump seed
+    pub bump: u8,
+
+    /// MINT_RISK_* bitmask detected at initialization (Token2022 extension screening)
+    pub risk_flags: u8,
+
+    /// Nonce of the active rotated account (seeds: ["dat_token_account", mint, nonce])
+    pub token_account_nonce: u8,
+
+    /// Operational category tag (0 = uncategorized), e.g. venue or launchpad
+    pub category: u8,
+
+    /// Per-token pause flag (set by pause_category / resume_category, record_failure;
+    /// cleared by resume_category / resume_token)
+    pub is_paused: u8,
+
+    /// Whether stats_payer has received its one-time rent refund
+    pub payer_refunded: u8,
+
+    /// record_failure calls since this token's last completed burn
+    pub consecutive_failures: u8,
+
+    /// Layout version (see migrations::MIGRATIONS), stamped at init and by migrate_account
+    pub version: u8,
+
+    /// Listed in the TokenRegistry (0 for stats created before the registry until register_token)
+    pub in_registry: u8,
+
+    /// Unused, keeps the size a multiple of 8
+    pub _reserved: [u8; 2],
 }
 
 impl TokenStats {
-    /// Account size: Pubkey(32) + 12 u64/i64 fields (96) + bool(1) + u8(1) = 130 bytes
-    pub const LEN: usize = 32 + 8 * 12 + 1 + 1;
+    /// Account size: 5 Pubkey (160) + 21 u64/i64 (168) + 6 u16 (12) + 10 u8 (10) + reserved (2) = 352 bytes
+    pub const LEN: usize = std::mem::size_of::<TokenStats>();
+
+    /// Deduct exactly `settled` lamports from pending fees (ecosystem allocation settlement)
+    /// Returns the remaining pending fees; fails rather than clamping on over-settlement
+    pub fn settle_pending_fees(&mut self, settled: u64) -> Result<u64> {
+        self.pending_fees_lamports = self.pending_fees_lamports
+            .checked_sub(settled)
+            .ok_or(ErrorCode::SettlementExceedsPending)?;
+        // Settlement consumes the oldest (already paid on) pending fees first
+        self.partner_paid_pending = self.partner_paid_pending.saturating_sub(settled);
+        Ok(self.pending_fees_lamports)
+    }
+
+    /// Credit a targeted deposit's burn slice to pending fees (deposit_fee_asdf)
+    /// Enforces the MAX_PENDING_FEES cap; returns the new pending total
+    pub fn credit_deposit(&mut self, amount: u64) -> Result<u64> {
+        let new_total = self.pending_fees_lamports.saturating_add(amount);
+        require!(new_total <= MAX_PENDING_FEES, ErrorCode::PendingFeesOverflow);
+        self.pending_fees_lamports = new_total;
+        Ok(new_total)
+    }
+
+    /// Clear pending fees (standalone collect, finalize_allocated_cycle)
+    pub fn reset_pending_fees(&mut self) {
+        self.pending_fees_lamports = 0;
+        self.partner_paid_pending = 0;
+    }
+
+    /// Partner cut of `collected` lamports (0 without a partner)
+    pub fn partner_cut(&self, collected: u64) -> u64 {
+        if self.partner == Pubkey::default() {
+            return 0;
+        }
+        (collected as u128 * self.partner_share_bps as u128 / 10_000) as u64
+    }
+
+    /// Partner cut owed by a collect: taken on the fees attributed to this token that it
+    /// was not yet paid on, never on the shared creator vault (which holds every token's fees)
+    pub fn partner_cut_due(&self) -> u64 {
+        self.partner_cut(self.pending_fees_lamports.saturating_sub(self.partner_paid_pending))
+    }
+
+    /// Record a partner payout of `amount`: every pending fee lamport is now paid on
+    pub fn record_partner_payout(&mut self, amount: u64) {
+        self.total_partner_paid = self.total_partner_paid.saturating_add(amount);
+        self.partner_paid_pending = self.pending_fees_lamports;
+    }
+
+    /// Share of a cycle amount (SOL or bought tokens) provide_liquidity may deposit
+    pub fn liquidity_share(&self, amount: u64) -> u64 {
+        (amount as u128 * self.liquidity_bps as u128 / 10_000) as u64
+    }
+
+    /// Amount sweep_dust burns from a DAT token balance: all of it, if non-zero and within dust_threshold
+    pub fn dust_sweep_amount(&self, balance: u64) -> Result<u64> {
+        require!(balance > 0, ErrorCode::NoDustToSweep);
+        require!(balance <= self.dust_threshold, ErrorCode::DustAboveThreshold);
+        Ok(balance)
+    }
+
+    /// Internally consistent counters (verify_invariants); names the first broken invariant.
+    /// total_sol_used is not checked per token: in N+1 cycles secondaries spend SOL
+    /// collected by the first token, so that bound only holds ecosystem-wide.
+    pub fn check_invariants(&self) -> Result<()> {
+        require!(
+            self.total_partner_paid <= self.total_sol_collected,
+            ErrorCode::InvariantPartnerPaidExceedsCollected
+        );
+        require!(self.dust_burned <= self.total_burned, ErrorCode::InvariantDustExceedsBurned);
+        require!(
+            self.last_cycle_burned <= self.total_burned && self.last_cycle_sol <= self.total_sol_used,
+            ErrorCode::InvariantLastCycleExceedsTotals
+        );
+        Ok(())
+    }
+
+    /// Count a failed cycle of this token; pauses it at TOKEN_PAUSE_FAILURE_THRESHOLD
+    /// consecutive failures. Returns whether this call paused it
+    pub fn record_failure(&mut self, now: i64) -> bool {
+        self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+        self.last_failure_timestamp = now;
+        if self.consecutive_failures >= TOKEN_PAUSE_FAILURE_THRESHOLD && self.is_paused == 0 {
+            self.is_paused = 1;
+            return true;
+        }
+        false
+    }
+
+    /// Validate and apply a per-token ChangeQueue parameter (ADMIN_OP_* tag); returns the previous value
+    pub fn apply_parameter(&mut self, param: u8, value: u64) -> Result<u64> {
+        let old_value = match param {
+            ADMIN_OP_BURN_RATIO => {
+                // MIN_BURN_BPS (50%) to 100%, never below the reflected share
+                require!((MIN_BURN_BPS as u64..=10000).contains(&value), ErrorCode::InvalidBurnRatio);
+                require!(value >= self.reflection_bps as u64, ErrorCode::InvalidReflectionRatio);
+                std::mem::replace(&mut self.burn_bps, value as u16) as u64
+            }
+            ADMIN_OP_DUST_THRESHOLD => {
+                require!(value <= MAX_DUST_THRESHOLD, ErrorCode::InvalidParameter);
+                std::mem::replace(&mut self.dust_threshold, value)
+            }
+            ADMIN_OP_REFLECTION_RATIO => {
+                // taken out of the burned share; 0 disables reflection
+                require!(value <= self.burn_bps as u64, ErrorCode::InvalidReflectionRatio);
+                std::mem::replace(&mut self.reflection_bps, value as u16) as u64
+            }
+            ADMIN_OP_LIQUIDITY_RATIO => {
+                // 0 disables liquidity seeding
+                require!(value <= MAX_LIQUIDITY_BPS as u64, ErrorCode::InvalidLiquidityRatio);
+                std::mem::replace(&mut self.liquidity_bps, value as u16) as u64
+            }
+            _ => return err!(ErrorCode::InvalidParameter),
+        };
+        Ok(old_value)
+    }
+
+    /// Whether the first completed burn still owes stats_payer its rent refund
+    pub fn payer_refund_due(&self) -> bool {
+        self.payer_refunded == 0 && self.stats_payer != Pubkey::default()
+    }
+
+    /// Whether `account` may serve as the DAT token account (any until rotated, then only the rotated one)
+    pub fn accepts_token_account(&self, account: &Pubkey) -> bool {
+        self.dat_token_account == Pubkey::default() || *account == self.dat_token_account
+    }
+
+    /// Active DAT token account: the rotated account, or the dat_authority ATA if never rotated
+    pub fn active_token_account(&self, dat_authority: &Pubkey, token_program: &Pubkey) -> Pubkey {
+        if self.dat_token_account == Pubkey::default() {
+            get_associated_token_address_with_program_id(dat_authority, &self.mint, token_program)
+        } else {
+            self.dat_token_account
+        }
+    }
 }
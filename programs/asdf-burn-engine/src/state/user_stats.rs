@@ -31,6 +31,24 @@ pub struct UserStats {
     /// Proof-of-history: slot of last modification
     /// Additional verification for chronological order
     pub last_update_slot: u64,
+
+    /// One-time onboarding bonus consumed (or user predates the bonus program)
+    pub onboarding_bonus_claimed: bool,
+
+    /// RebateEpoch that epoch_contribution belongs to
+    pub contribution_epoch: u64,
+
+    /// Contribution to contribution_epoch, claimable once that epoch closes
+    pub epoch_contribution: u64,
+
+    /// App credited with this user (first app deposited through, 0 = none)
+    pub app_id: u32,
+
+    /// Referrer credited with a share of this user's deposits (set once)
+    pub referrer: Option<Pubkey>,
+
+    /// Layout version (see migrations::MIGRATIONS), stamped at init and by migrate_account
+    pub version: u8,
 }
 
 impl UserStats {
@@ -42,6 +60,17 @@ impl UserStats {
     /// - total_rebate: 8 bytes (u64)
     /// - last_update_timestamp: 8 bytes (i64)
     /// - last_update_slot: 8 bytes (u64)
-    /// Total: 73 bytes
-    pub const LEN: usize = 1 + 32 + 8 + 8 + 8 + 8 + 8;
+    /// - onboarding_bonus_claimed: 1 byte (bool)
+    /// - contribution_epoch, epoch_contribution: 16 bytes (2 u64)
+    /// - app_id: 4 bytes (u32)
+    /// - referrer: 33 bytes (Option<Pubkey>)
+    /// - version: 1 byte (u8)
+    ///
+    /// Total: 128 bytes
+    pub const LEN: usize = 1 + 32 + 8 + 8 + 8 + 8 + 8 + 1 + 8 + 8 + 4 + 33 + 1;
+
+    /// Whether a contribution from an epoch other than current_epoch (hence closed) is unclaimed
+    pub fn has_unclaimed_epoch(&self, current_epoch: u64) -> bool {
+        self.epoch_contribution > 0 && self.contribution_epoch != current_epoch
+    }
 }
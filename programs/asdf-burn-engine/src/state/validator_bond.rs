@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+
+/// SOL bond staked by a fee-attestation daemon
+///
+/// A bonded operator may post fee attestations through register_validated_fees
+/// without admin or attestor-set approval. Each attestation it posts is backed
+/// by this bond: proving fraud via slash_validator() burns SLASH_BPS of it.
+/// The bond cannot be withdrawn while any of its attestations are unresolved.
+///
+/// PDA Seeds: ["validator_bond", operator]
+/// Bonded lamports are held in the PDA itself (on top of its rent)
+#[account]
+pub struct ValidatorBond {
+    /// Daemon signing key that owns this bond
+    pub operator: Pubkey,
+
+    /// Lamports currently at stake (excludes rent)
+    pub bonded_lamports: u64,
+
+    /// Attestations posted by this operator that are not yet finalized or slashed
+    pub open_attestations: u32,
+
+    /// Number of times this bond was slashed
+    pub slash_count: u32,
+
+    /// Total attestations posted with this bond
+    pub total_attestations: u64,
+
+    /// Cumulative lamports burned by slashing
+    pub total_slashed: u64,
+
+    /// Timestamp of the first deposit
+    pub bonded_at: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl ValidatorBond {
+    /// Account size: Pubkey(32) + 4 u64/i64 (32) + 2 u32 (8) + u8(1) = 73 bytes
+    pub const LEN: usize = 32 + 8 * 4 + 4 * 2 + 1;
+
+    /// Whether the bond may back an attestation claiming fee_amount
+    /// Claims are capped at the stake so a fraudulent claim always costs at least its slash
+    pub fn can_attest(&self, fee_amount: u64) -> bool {
+        self.bonded_lamports >= MIN_VALIDATOR_BOND && fee_amount <= self.bonded_lamports
+    }
+
+    /// Lamports burned by one proven fraud
+    pub fn slash_amount(&self) -> u64 {
+        ((self.bonded_lamports as u128) * SLASH_BPS as u128 / 10000) as u64
+    }
+
+    /// Whether `amount` may be withdrawn: no open attestations, and the
+    /// remainder is either zero (full exit) or still above the minimum
+    pub fn can_withdraw(&self, amount: u64) -> bool {
+        if self.open_attestations > 0 || amount > self.bonded_lamports {
+            return false;
+        }
+        let remaining = self.bonded_lamports - amount;
+        remaining == 0 || remaining >= MIN_VALIDATOR_BOND
+    }
+}
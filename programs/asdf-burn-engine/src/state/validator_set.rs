@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_ATTESTORS;
+
+/// Authorized fee attestors (M-of-N)
+///
+/// register_validated_fees requires ed25519 signatures from at least
+/// `threshold` distinct attestors over the attestation message.
+///
+/// PDA Seeds: ["validator_set"]
+#[account]
+pub struct ValidatorSet {
+    /// Attestor pubkeys (first attestor_count entries are active)
+    pub attestors: [Pubkey; MAX_ATTESTORS],
+
+    /// Number of active attestors (N)
+    pub attestor_count: u8,
+
+    /// Required distinct signatures (M)
+    pub threshold: u8,
+
+    /// Timestamp of last configuration change
+    pub updated_at: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl ValidatorSet {
+    /// Account size: 8 Pubkey (256) + 2 u8 (2) + i64 (8) + bump (1) = 267 bytes
+    pub const LEN: usize = 32 * MAX_ATTESTORS + 1 + 1 + 8 + 1;
+
+    /// Active attestors
+    pub fn active(&self) -> &[Pubkey] {
+        &self.attestors[..(self.attestor_count as usize).min(MAX_ATTESTORS)]
+    }
+}
@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::ErrorCode;
 
 /// Validator state for trustless per-token fee attribution
 ///
@@ -28,11 +30,48 @@ pub struct ValidatorState {
     /// PDA bump seed
     pub bump: u8,
 
+    /// Max attested fees per slot in lamports (0 = DEFAULT_MAX_FEE_PER_SLOT)
+    pub max_fee_per_slot: u64,
+
+    /// Max attested transactions per slot (0 = DEFAULT_MAX_TX_PER_SLOT)
+    pub max_tx_per_slot: u32,
+
     /// Reserved for future use
-    pub _reserved: [u8; 32],
+    pub _reserved: [u8; 20],
 }
 
 impl ValidatorState {
-    /// Account size: 32 + 32 + 8 + 8 + 8 + 2 + 1 + 32 = 123 bytes
-    pub const LEN: usize = 32 + 32 + 8 + 8 + 8 + 2 + 1 + 32;
+    /// Account size: 32 + 32 + 8 + 8 + 8 + 2 + 1 + 8 + 4 + 20 = 123 bytes
+    /// Limits were carved out of the reserved bytes, so existing accounts read them as 0 (defaults)
+    pub const LEN: usize = 32 + 32 + 8 + 8 + 8 + 2 + 1 + 8 + 4 + 20;
+
+    /// Effective fee cap per slot
+    pub fn fee_cap_per_slot(&self) -> u64 {
+        if self.max_fee_per_slot == 0 { DEFAULT_MAX_FEE_PER_SLOT } else { self.max_fee_per_slot }
+    }
+
+    /// Effective transaction cap per slot
+    pub fn tx_cap_per_slot(&self) -> u32 {
+        if self.max_tx_per_slot == 0 { DEFAULT_MAX_TX_PER_SLOT } else { self.max_tx_per_slot }
+    }
+
+    /// Validate and apply a per-slot cap ChangeQueue parameter; returns the previous effective cap
+    /// Bounded by MAX_FEE_PER_SLOT_LIMIT / MAX_TX_PER_SLOT_LIMIT
+    pub fn apply_parameter(&mut self, param: u8, value: u64) -> Result<u64> {
+        match param {
+            ADMIN_OP_VALIDATOR_MAX_FEE => {
+                require!(value > 0 && value <= MAX_FEE_PER_SLOT_LIMIT, ErrorCode::InvalidParameter);
+                let old_value = self.fee_cap_per_slot();
+                self.max_fee_per_slot = value;
+                Ok(old_value)
+            }
+            ADMIN_OP_VALIDATOR_MAX_TX => {
+                require!(value > 0 && value <= MAX_TX_PER_SLOT_LIMIT as u64, ErrorCode::InvalidParameter);
+                let old_value = self.tx_cap_per_slot() as u64;
+                self.max_tx_per_slot = value as u32;
+                Ok(old_value)
+            }
+            _ => err!(ErrorCode::InvalidParameter),
+        }
+    }
 }
@@ -0,0 +1,262 @@
+use anchor_lang::prelude::*;
+use crate::state::{CyclePhase, TokenStats};
+
+/// Return data of view_circulating_supply()
+///
+/// circulating = total_supply - community_treasury - pending_burn - rebate_pool
+/// Mint supply is already net of burns; total_burned is included for transparency.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CirculatingSupply {
+    /// Token mint
+    pub mint: Pubkey,
+
+    /// Current mint supply (after burns)
+    pub total_supply: u64,
+
+    /// Cumulative tokens burned by the DAT (TokenStats.total_burned)
+    pub total_burned: u64,
+
+    /// Balance of the community treasury token PDA
+    pub community_treasury: u64,
+
+    /// Balance of the active DAT token account (bought, awaiting burn)
+    pub pending_burn: u64,
+
+    /// Balance of the rebate pool token account (root token only)
+    pub rebate_pool: u64,
+
+    /// Supply held outside protocol accounts
+    pub circulating: u64,
+
+    /// Slot the figure was computed at
+    pub slot: u64,
+}
+
+/// Return data of view_token_info() - one ecosystem directory entry
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TokenInfo {
+    /// Token mint
+    pub mint: Pubkey,
+
+    /// Ecosystem page / manifest URI (empty when no TokenConfig exists)
+    pub info_uri: String,
+
+    /// Whether this is the root token
+    pub is_root_token: bool,
+
+    /// Operational category tag
+    pub category: u8,
+
+    /// Whether the token is paused
+    pub is_paused: bool,
+
+    /// Share of each buyback burned (bps)
+    pub burn_bps: u16,
+
+    /// Cumulative tokens burned
+    pub total_burned: u64,
+
+    /// Cumulative SOL collected
+    pub total_sol_collected: u64,
+
+    /// Cumulative USD value burned (micro-USD)
+    pub usd_value_burned: u64,
+}
+
+/// Return data of quote_buy() - the bonding curve buy execute_buy would send now
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BuyQuote {
+    /// Token mint
+    pub mint: Pubkey,
+
+    /// Lamports quoted (before the max_fees_per_cycle and 1%-of-reserves caps)
+    pub lamports: u64,
+
+    /// max_sol_cost passed to the PumpFun buy (capped budget)
+    pub max_sol_cost: u64,
+
+    /// Token amount requested from PumpFun (expected output minus slippage_bps)
+    pub expected_tokens: u64,
+
+    /// Execution price over spot price (bps)
+    pub price_impact_bps: u16,
+
+    /// Implied execution price (lamports per token base unit, scaled by PRICE_SCALE)
+    pub execution_price: u64,
+
+    /// Slot the quote was taken at
+    pub slot: u64,
+}
+
+/// Return data of select_best_venue()
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct VenueSelection {
+    /// Token mint
+    pub mint: Pubkey,
+
+    /// Lamports quoted on every venue
+    pub sol_in: u64,
+
+    /// Winning venue (VENUE_BONDING_CURVE or VENUE_PUMPSWAP)
+    pub venue: u8,
+
+    /// Bonding curve or pool account to route the buy through
+    pub venue_account: Pubkey,
+
+    /// Tokens the winning venue yields for sol_in
+    pub tokens_out: u64,
+
+    /// Number of venues quoted
+    pub venues_quoted: u8,
+
+    /// Slot the quotes were taken at
+    pub slot: u64,
+}
+
+/// Return data of get_token_stats() - stable TokenStats layout for clients
+///
+/// Decoupled from the account layout so simulating the view keeps working
+/// across TokenStats migrations.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TokenStatsView {
+    /// Token mint
+    pub mint: Pubkey,
+
+    /// Whether this is the root token
+    pub is_root_token: bool,
+
+    /// Whether the token is paused
+    pub is_paused: bool,
+
+    /// Share of each buyback burned (bps)
+    pub burn_bps: u16,
+
+    /// Cumulative tokens burned (including dust sweeps)
+    pub total_burned: u64,
+
+    /// Cumulative SOL collected
+    pub total_sol_collected: u64,
+
+    /// Cumulative SOL spent on buybacks
+    pub total_sol_used: u64,
+
+    /// Cumulative SOL sent to the root token (secondary)
+    pub total_sol_sent_to_root: u64,
+
+    /// Cumulative SOL received from secondaries (root)
+    pub total_sol_received_from_others: u64,
+
+    /// Completed buyback cycles
+    pub total_buybacks: u64,
+
+    /// Last cycle timestamp
+    pub last_cycle_timestamp: i64,
+
+    /// SOL spent in the last cycle
+    pub last_cycle_sol: u64,
+
+    /// Tokens burned in the last cycle
+    pub last_cycle_burned: u64,
+
+    /// Fees attributed but not yet settled
+    pub pending_fees_lamports: u64,
+
+    /// Tokens sent to the community treasury instead of burned
+    pub total_sent_to_community_treasury: u64,
+
+    /// Cumulative USD value burned (micro-USD)
+    pub usd_value_burned: u64,
+}
+
+/// Return data of get_cycle_preview() - what the next cycle step would see
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CyclePreview {
+    /// Token mint
+    pub mint: Pubkey,
+
+    /// Current cycle phase
+    pub phase: CyclePhase,
+
+    /// Cycle sequence number
+    pub sequence: u64,
+
+    /// Mint holding the ecosystem cycle lock (None = free)
+    pub cycle_lock: Option<Pubkey>,
+
+    /// Whether collect_fees would pass the active / pause / interval gates now
+    pub can_collect: bool,
+
+    /// Earliest timestamp min_cycle_interval allows the next collect
+    pub next_cycle_at: i64,
+
+    /// Fees attributed to this token but not yet settled
+    pub pending_fees_lamports: u64,
+
+    /// Creator vault balance (uncollected fees, shared by bonding-curve tokens)
+    pub creator_vault_lamports: u64,
+
+    /// Lamports a standalone execute_buy would spend now (above reserves and SAFETY_BUFFER)
+    pub buy_amount: u64,
+
+    /// Slot the preview was computed at
+    pub slot: u64,
+}
+
+/// Return data of get_pending_rebate()
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PendingRebate {
+    /// User wallet
+    pub user: Pubkey,
+
+    /// $ASDF awaiting rebate processing
+    pub pending_contribution: u64,
+
+    /// Pending amount required before a rebate can be claimed
+    pub rebate_threshold: u64,
+
+    /// Rebate the pending contribution would pay now
+    pub rebate_amount: u64,
+
+    /// Whether claim_rebate would succeed now (above threshold and pool funded)
+    pub claimable: bool,
+
+    /// RebateEpoch holding epoch_contribution
+    pub contribution_epoch: u64,
+
+    /// Contribution to contribution_epoch (claimable pro rata once it closes)
+    pub epoch_contribution: u64,
+}
+
+impl TokenStatsView {
+    /// Snapshot the client-facing fields of a TokenStats account
+    pub fn from_stats(stats: &TokenStats) -> Self {
+        Self {
+            mint: stats.mint,
+            is_root_token: stats.is_root_token != 0,
+            is_paused: stats.is_paused != 0,
+            burn_bps: stats.burn_bps,
+            total_burned: stats.total_burned,
+            total_sol_collected: stats.total_sol_collected,
+            total_sol_used: stats.total_sol_used,
+            total_sol_sent_to_root: stats.total_sol_sent_to_root,
+            total_sol_received_from_others: stats.total_sol_received_from_others,
+            total_buybacks: stats.total_buybacks,
+            last_cycle_timestamp: stats.last_cycle_timestamp,
+            last_cycle_sol: stats.last_cycle_sol,
+            last_cycle_burned: stats.last_cycle_burned,
+            pending_fees_lamports: stats.pending_fees_lamports,
+            total_sent_to_community_treasury: stats.total_sent_to_community_treasury,
+            usd_value_burned: stats.usd_value_burned,
+        }
+    }
+}
+
+impl CirculatingSupply {
+    /// Compute circulating supply from its inputs (protocol holdings never exceed supply)
+    pub fn compute(total_supply: u64, community_treasury: u64, pending_burn: u64, rebate_pool: u64) -> u64 {
+        total_supply
+            .saturating_sub(community_treasury)
+            .saturating_sub(pending_burn)
+            .saturating_sub(rebate_pool)
+    }
+}
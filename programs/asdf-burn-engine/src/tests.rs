@@ -7,13 +7,14 @@
 //
 // Test Categories:
 // 1. Math Functions - calculate_tokens_out_pumpfun, slippage calculations
-// 2. Bonding Curve Parsing - deserialize_bonding_curve
+// 2. Bonding Curve Parsing - deserialize_bonding_curve, Mayhem Mode curves
 // 3. Fee Split Logic - split_fees_to_root calculations
 // 4. Error Conditions - All 18 error codes
 // 5. State Validation - DATState and TokenStats invariants
 // ============================================================================
 
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 mod tests {
     // Import all items from crate root for nested test modules
     #[allow(unused_imports)]
@@ -22,12 +23,64 @@ mod tests {
         MIN_FEES_TO_CLAIM, MAX_FEES_PER_CYCLE, INITIAL_SLIPPAGE_BPS, MIN_CYCLE_INTERVAL,
         MAX_PENDING_FEES,
         DAT_STATE_SEED, DAT_AUTHORITY_SEED, TOKEN_STATS_SEED, ROOT_TREASURY_SEED,
+        DEFAULT_BURN_BPS, MIN_BURN_BPS,
         // Functions
-        calculate_tokens_out_pumpfun, deserialize_bonding_curve,
+        calculate_tokens_out_pumpfun, deserialize_bonding_curve, calculate_burn_split,
+        parse_mint_risk_flags,
         // Types
         ErrorCode,
     };
 
+    /// TokenStats of a fresh mint: full burn, every counter and setting zeroed
+    /// Tests override the fields they exercise with struct-update syntax
+    fn token_stats_fixture() -> crate::state::TokenStats {
+        crate::state::TokenStats {
+            mint: anchor_lang::prelude::Pubkey::new_unique(),
+            dat_token_account: anchor_lang::prelude::Pubkey::default(),
+            stats_payer: anchor_lang::prelude::Pubkey::default(),
+            partner: anchor_lang::prelude::Pubkey::default(),
+            pending_partner: anchor_lang::prelude::Pubkey::default(),
+            total_burned: 0,
+            total_sol_collected: 0,
+            total_sol_used: 0,
+            total_sol_sent_to_root: 0,
+            total_sol_received_from_others: 0,
+            total_buybacks: 0,
+            last_cycle_timestamp: 0,
+            last_cycle_sol: 0,
+            last_cycle_burned: 0,
+            pending_fees_lamports: 0,
+            last_fee_update_timestamp: 0,
+            cycles_participated: 0,
+            total_sent_to_community_treasury: 0,
+            usd_value_burned: 0,
+            total_partner_paid: 0,
+            pending_partner_timestamp: 0,
+            dust_threshold: 0,
+            dust_burned: 0,
+            last_failure_timestamp: 0,
+            total_liquidity_sol: 0,
+            partner_paid_pending: 0,
+            burn_bps: DEFAULT_BURN_BPS,
+            partner_share_bps: 0,
+            pending_partner_share_bps: 0,
+            reflection_bps: 0,
+            liquidity_bps: 0,
+            registry_page: 0,
+            is_root_token: 0,
+            bump: 255,
+            risk_flags: 0,
+            token_account_nonce: 0,
+            category: 0,
+            is_paused: 0,
+            payer_refunded: 0,
+            consecutive_failures: 0,
+            version: 0,
+            in_registry: 0,
+            _reserved: [0; 2],
+        }
+    }
+
     // ========================================================================
     // 1. MATH FUNCTION TESTS
     // ========================================================================
@@ -146,6 +199,86 @@ mod tests {
             let result = deserialize_bonding_curve(&data);
             assert!(result.is_ok());
         }
+
+        /// Full curve account (discriminator included): 81 bytes, 82 with the Mayhem flag
+        fn full_curve(virtual_token: u64, virtual_sol: u64, real_token: u64, supply: u64, mayhem: Option<bool>) -> Vec<u8> {
+            let mut data = vec![0u8; 81];
+            data[8..16].copy_from_slice(&virtual_token.to_le_bytes());
+            data[16..24].copy_from_slice(&virtual_sol.to_le_bytes());
+            data[24..32].copy_from_slice(&real_token.to_le_bytes());
+            data[40..48].copy_from_slice(&supply.to_le_bytes());
+            if let Some(flag) = mayhem {
+                data.push(flag as u8);
+            }
+            data
+        }
+
+        #[test]
+        fn test_parse_bonding_curve_detects_mayhem() {
+            use crate::{is_mayhem_bonding_curve, parse_bonding_curve};
+
+            let standard = full_curve(1_073_000_000_000_000, 30_000_000_000, 793_100_000_000_000, 1_000_000_000_000_000, None);
+            let curve = parse_bonding_curve(&standard[8..]).unwrap();
+            assert!(!curve.is_mayhem);
+            assert_eq!(curve.max_supply(), crate::PUMP_TOKEN_TOTAL_SUPPLY);
+            assert_eq!(curve.real_token_reserves, Some(793_100_000_000_000));
+            assert!(!is_mayhem_bonding_curve(&standard));
+
+            let mayhem = full_curve(2_146_000_000_000_000, 30_000_000_000, 1_586_200_000_000_000, 2_000_000_000_000_000, Some(true));
+            assert_eq!(mayhem.len(), 82);
+            let curve = parse_bonding_curve(&mayhem[8..]).unwrap();
+            assert!(curve.is_mayhem);
+            assert_eq!(curve.max_supply(), crate::PUMP_MAYHEM_TOKEN_TOTAL_SUPPLY);
+            assert_eq!(curve.token_total_supply, Some(2_000_000_000_000_000));
+            assert!(is_mayhem_bonding_curve(&mayhem));
+
+            // create_v2 without Mayhem Mode: flag byte present but cleared
+            let cleared = full_curve(1_073_000_000_000_000, 30_000_000_000, 1, 1_000_000_000_000_000, Some(false));
+            assert!(!parse_bonding_curve(&cleared[8..]).unwrap().is_mayhem);
+        }
+
+        #[test]
+        fn test_parse_bonding_curve_rejects_supply_above_mode() {
+            use crate::parse_bonding_curve;
+
+            // 2B supply without the Mayhem flag is a misread layout
+            let data = full_curve(2_146_000_000_000_000, 30_000_000_000, 1, 2_000_000_000_000_000, None);
+            assert!(parse_bonding_curve(&data[8..]).is_err());
+
+            // Truncated snapshots only carry the reserves
+            let curve = parse_bonding_curve(&data[8..32]).unwrap();
+            assert_eq!(curve.real_token_reserves, None);
+            assert_eq!(curve.token_total_supply, None);
+            assert!(!curve.is_mayhem);
+        }
+
+        #[test]
+        fn test_buy_math_on_mayhem_curve() {
+            use crate::calculate_buy_amount_and_slippage;
+
+            let sol_reserves: u64 = 30_000_000_000;
+            let budget: u64 = 100_000_000;
+            let mayhem = full_curve(2_146_000_000_000_000, sol_reserves, 1_586_200_000_000_000, 2_000_000_000_000_000, Some(true));
+            let (sol, target, _) = calculate_buy_amount_and_slippage(budget, &mayhem, u64::MAX, 0, 0, 0).unwrap();
+
+            let expected = calculate_tokens_out_pumpfun(budget, sol_reserves, 2_146_000_000_000_000).unwrap();
+            assert_eq!(sol, budget);
+            assert_eq!(target, expected);
+
+            // Twice the virtual tokens of a standard curve at the same SOL reserves
+            let standard = full_curve(1_073_000_000_000_000, sol_reserves, 793_100_000_000_000, 1_000_000_000_000_000, None);
+            let (_, standard_target, _) = calculate_buy_amount_and_slippage(budget, &standard, u64::MAX, 0, 0, 0).unwrap();
+            assert!(target > standard_target);
+        }
+
+        #[test]
+        fn test_buy_math_capped_at_real_reserves() {
+            use crate::calculate_buy_amount_and_slippage;
+
+            let data = full_curve(1_073_000_000_000_000, 30_000_000_000, 1_000_000, 1_000_000_000_000_000, None);
+            let (_, target, _) = calculate_buy_amount_and_slippage(100_000_000, &data, u64::MAX, 0, 0, 0).unwrap();
+            assert_eq!(target, 1_000_000);
+        }
     }
 
     // ========================================================================
@@ -153,8 +286,6 @@ mod tests {
     // ========================================================================
 
     mod fee_split_tests {
-        use super::*;
-
         #[test]
         fn test_fee_split_default_ratio() {
             // Default: 55.2% keep, 44.8% to root (5520 bps)
@@ -266,7 +397,7 @@ mod tests {
         fn test_rent_exempt_minimum() {
             // Rent exempt minimum for basic account
             const RENT_EXEMPT_MINIMUM: u64 = 890880;
-            assert!(RENT_EXEMPT_MINIMUM < 1_000_000); // Less than 0.001 SOL
+            const { assert!(RENT_EXEMPT_MINIMUM < 1_000_000) }; // Less than 0.001 SOL
         }
     }
 
@@ -275,22 +406,23 @@ mod tests {
     // ========================================================================
 
     mod state_tests {
-        use super::*;
-
         #[test]
         fn test_dat_state_size() {
-            // DATState should be 382 bytes according to size calculation
-            // See state/dat_state.rs for detailed breakdown
-            // HIGH-01 FIX: Added last_direct_fee_split_timestamp (+8 bytes)
+            // Zero-copy (V19): 11 Pubkeys (352) + pending_import_hash (32) + 26 u64/i64 (208)
+            // + 3 u32 (12) + 8 u16 (16) + 9 u8 + 11 reserved = 640, a multiple of 8 so the
+            // repr(C) layout has no padding
             use crate::state::DATState;
-            assert_eq!(DATState::LEN, 382, "DATState size mismatch");
+            assert_eq!(DATState::LEN, 640, "DATState size mismatch");
+            assert_eq!(DATState::LEN % 8, 0);
         }
 
         #[test]
         fn test_token_stats_size() {
-            // TokenStats should be 130 bytes (see state/token_stats.rs)
+            // Zero-copy (V13): 5 Pubkeys (160) + 21 u64/i64 (168) + 6 u16 (12) + 10 u8
+            // + 2 reserved = 352, a multiple of 8 so the repr(C) layout has no padding
             use crate::state::TokenStats;
-            assert_eq!(TokenStats::LEN, 130, "TokenStats size mismatch");
+            assert_eq!(TokenStats::LEN, 352, "TokenStats size mismatch");
+            assert_eq!(TokenStats::LEN % 8, 0);
         }
 
         #[test]
@@ -331,6 +463,27 @@ mod tests {
         fn test_root_treasury_seed() {
             assert_eq!(ROOT_TREASURY_SEED, b"root_treasury");
         }
+
+        #[test]
+        fn test_wsol_mint_is_native_mint() {
+            assert_eq!(crate::WSOL_MINT, anchor_spl::token::spl_token::native_mint::ID);
+            assert_ne!(crate::WSOL_MINT, anchor_spl::token::ID, "Not the token program");
+        }
+
+        #[test]
+        fn test_rotated_token_account_pda_unique_per_nonce() {
+            use crate::DAT_TOKEN_ACCOUNT_SEED;
+            let mint = Pubkey::new_unique();
+            let (first, _) = Pubkey::find_program_address(
+                &[DAT_TOKEN_ACCOUNT_SEED, mint.as_ref(), &[1u8]],
+                &crate::ID,
+            );
+            let (second, _) = Pubkey::find_program_address(
+                &[DAT_TOKEN_ACCOUNT_SEED, mint.as_ref(), &[2u8]],
+                &crate::ID,
+            );
+            assert_ne!(first, second, "Each rotation must derive a fresh account");
+        }
     }
 
     // ========================================================================
@@ -415,7 +568,7 @@ mod tests {
         #[test]
         fn test_slippage_not_too_high() {
             // Slippage should not exceed 20%
-            assert!(INITIAL_SLIPPAGE_BPS <= 2000);
+            const { assert!(INITIAL_SLIPPAGE_BPS <= 2000) };
         }
 
         #[test]
@@ -427,8 +580,8 @@ mod tests {
         #[test]
         fn test_max_pending_fees_is_reasonable() {
             // MAX_PENDING_FEES should be at least 10 SOL and at most 1000 SOL
-            assert!(MAX_PENDING_FEES >= 10_000_000_000, "MAX_PENDING_FEES too low");
-            assert!(MAX_PENDING_FEES <= 1_000_000_000_000, "MAX_PENDING_FEES too high");
+            const { assert!(MAX_PENDING_FEES >= 10_000_000_000, "MAX_PENDING_FEES too low") };
+            const { assert!(MAX_PENDING_FEES <= 1_000_000_000_000, "MAX_PENDING_FEES too high") };
         }
     }
 
@@ -799,10 +952,9 @@ mod tests {
 
             let result = calculate_tokens_out_pumpfun(sol_in, sol_reserves, token_reserves);
 
-            // Should either return valid result (tokens ≤ reserves) or error
-            match result {
-                Ok(tokens) => assert!(tokens <= token_reserves, "Can't get more than reserves"),
-                Err(_) => (), // Overflow error is acceptable
+            // Should either return valid result (tokens ≤ reserves) or error (overflow is acceptable)
+            if let Ok(tokens) = result {
+                assert!(tokens <= token_reserves, "Can't get more than reserves");
             }
         }
     }
@@ -812,8 +964,6 @@ mod tests {
     // ========================================================================
 
     mod admin_operation_tests {
-        use super::*;
-
         /// Test admin cooldown constant (1 hour default)
         #[test]
         fn test_admin_cooldown_default() {
@@ -864,11 +1014,12 @@ mod tests {
 
             // Step 2: accept_admin_transfer requires new_admin signature
             let signer = new_admin;
-            assert_eq!(pending_admin.unwrap(), signer, "Signer must be pending admin");
+            assert_eq!(pending_admin, Some(signer), "Signer must be pending admin");
 
-            // Invalid case: wrong signer
+            // Invalid cases: wrong signer, or the current admin accepting its own transfer
             let wrong_signer = Pubkey::new_unique();
-            assert_ne!(pending_admin.unwrap(), wrong_signer, "Wrong signer should fail");
+            assert_ne!(pending_admin, Some(wrong_signer), "Wrong signer should fail");
+            assert_ne!(pending_admin, Some(current_admin), "Current admin cannot accept");
         }
     }
 
@@ -877,8 +1028,6 @@ mod tests {
     // ========================================================================
 
     mod validator_tests {
-        use super::*;
-
         /// Test validator slot stale threshold (1000 slots)
         #[test]
         fn test_validator_stale_threshold() {
@@ -922,6 +1071,9 @@ mod tests {
 
             let invalid_range: u64 = 501;
             assert!(invalid_range > max_slot_range, "501 slots should be rejected");
+
+            // 1 SOL over 100 slots stays within the 0.01 SOL per slot cap
+            assert!(fee_amount <= slot_range * 10_000_000, "1 SOL over 100 slots should be valid");
         }
 
         /// Test validator slot progression
@@ -945,6 +1097,7 @@ mod tests {
             // Attempt to register fees with same slot range (should fail)
             let slot_start: u64 = 900;
             let slot_end: u64 = 1000;
+            assert!(slot_start < slot_end);
 
             // slot_end must be > last_validated_slot
             let is_valid = slot_end > last_validated_slot;
@@ -953,6 +1106,7 @@ mod tests {
             // Attempt to register fees with overlapping range
             let slot_start_overlap: u64 = 950;
             let slot_end_overlap: u64 = 1050;
+            assert!(slot_start_overlap < last_validated_slot, "Range starts inside the validated slots");
 
             // Even with overlap, slot_end is now > last_validated_slot, so new fees are valid
             let is_overlap_valid = slot_end_overlap > last_validated_slot;
@@ -1005,8 +1159,6 @@ mod tests {
     // ========================================================================
 
     mod fee_split_timelock_tests {
-        use super::*;
-
         /// Test fee split propose/execute separation (HIGH-01 fix)
         #[test]
         fn test_fee_split_timestamp_separation() {
@@ -1035,11 +1187,7 @@ mod tests {
             let current_bps: u16 = 5520;
             let pending_bps: u16 = 5800; // +280 bps change
 
-            let delta = if pending_bps > current_bps {
-                pending_bps - current_bps
-            } else {
-                current_bps - pending_bps
-            };
+            let delta = pending_bps.abs_diff(current_bps);
 
             assert!(delta <= 500, "280 bps delta should be valid");
 
@@ -1237,4 +1385,5101 @@ mod tests {
             assert!(pending_fee_split.is_none(), "Should trigger NoPendingFeeSplit");
         }
     }
+
+    // ========================================================================
+    // 17. BURN RATIO TESTS (Community Treasury Remainder)
+    // ========================================================================
+
+    mod burn_ratio_tests {
+        use super::*;
+
+        /// Default burn ratio burns everything
+        #[test]
+        fn test_burn_split_default_full_burn() {
+            let (burn, treasury) = calculate_burn_split(1_000_000_000, DEFAULT_BURN_BPS).unwrap();
+            assert_eq!(burn, 1_000_000_000);
+            assert_eq!(treasury, 0);
+        }
+
+        /// 80% burn / 20% treasury
+        #[test]
+        fn test_burn_split_80_20() {
+            let (burn, treasury) = calculate_burn_split(1_000_000_000, 8000).unwrap();
+            assert_eq!(burn, 800_000_000);
+            assert_eq!(treasury, 200_000_000);
+        }
+
+        /// Rounding never loses tokens
+        #[test]
+        fn test_burn_split_conservation() {
+            for amount in [1u64, 7, 999, 123_456_789, u64::MAX] {
+                for bps in [MIN_BURN_BPS, 7777, 9999, DEFAULT_BURN_BPS] {
+                    let (burn, treasury) = calculate_burn_split(amount, bps).unwrap();
+                    assert_eq!(burn + treasury, amount, "Split must conserve tokens");
+                }
+            }
+        }
+
+        /// Burn ratio above 100% is rejected
+        #[test]
+        fn test_burn_split_invalid_bps() {
+            assert!(calculate_burn_split(1_000_000, 10001).is_err());
+        }
+
+        /// Burn floor is at least 50%
+        #[test]
+        fn test_min_burn_bps_floor() {
+            assert_eq!(MIN_BURN_BPS, 5000);
+            const { assert!(MIN_BURN_BPS <= DEFAULT_BURN_BPS) };
+        }
+    }
+
+    // ========================================================================
+    // 18. MINT EXTENSION SCREENING TESTS (Token2022 risk flags)
+    // ========================================================================
+
+    mod mint_screening_tests {
+        use super::*;
+        use crate::{
+            MINT_RISK_FREEZE_AUTHORITY, MINT_RISK_PERMANENT_DELEGATE, MINT_RISK_TRANSFER_HOOK,
+            MINT_RISK_DEFAULT_FROZEN, MINT_RISK_TRANSFER_FEE, MINT_RISK_REJECT_MASK,
+        };
+
+        /// Build a Token2022 mint buffer with the given TLV extensions
+        fn token_2022_mint(extensions: &[(u16, Vec<u8>)]) -> Vec<u8> {
+            let mut data = vec![0u8; 166];
+            data[45] = 1; // is_initialized
+            data[165] = 1; // AccountType::Mint
+            for (ext_type, value) in extensions {
+                data.extend_from_slice(&ext_type.to_le_bytes());
+                data.extend_from_slice(&(value.len() as u16).to_le_bytes());
+                data.extend_from_slice(value);
+            }
+            data
+        }
+
+        #[test]
+        fn test_plain_spl_mint_has_no_flags() {
+            let data = vec![0u8; 82];
+            assert_eq!(parse_mint_risk_flags(&data, false).unwrap(), 0);
+        }
+
+        #[test]
+        fn test_freeze_authority_flagged() {
+            let mut data = vec![0u8; 82];
+            data[46] = 1; // COption::Some
+            let flags = parse_mint_risk_flags(&data, false).unwrap();
+            assert_eq!(flags, MINT_RISK_FREEZE_AUTHORITY);
+        }
+
+        #[test]
+        fn test_short_mint_rejected() {
+            assert!(parse_mint_risk_flags(&[0u8; 40], false).is_err());
+        }
+
+        #[test]
+        fn test_permanent_delegate_rejected() {
+            let data = token_2022_mint(&[(12, vec![7u8; 32])]);
+            let flags = parse_mint_risk_flags(&data, true).unwrap();
+            assert_eq!(flags, MINT_RISK_PERMANENT_DELEGATE);
+            assert_ne!(flags & MINT_RISK_REJECT_MASK, 0, "Permanent delegate must be rejected");
+        }
+
+        #[test]
+        fn test_empty_permanent_delegate_ignored() {
+            let data = token_2022_mint(&[(12, vec![0u8; 32])]);
+            assert_eq!(parse_mint_risk_flags(&data, true).unwrap(), 0);
+        }
+
+        #[test]
+        fn test_multiple_extensions_flagged() {
+            let mut hook = vec![0u8; 64];
+            hook[40] = 9; // program_id set
+            let data = token_2022_mint(&[
+                (1, vec![0u8; 108]), // TransferFeeConfig
+                (6, vec![2u8]),      // DefaultAccountState = Frozen
+                (14, hook),          // TransferHook
+                (18, vec![0u8; 64]), // MetadataPointer (harmless)
+            ]);
+            let flags = parse_mint_risk_flags(&data, true).unwrap();
+            assert_eq!(
+                flags,
+                MINT_RISK_TRANSFER_FEE | MINT_RISK_DEFAULT_FROZEN | MINT_RISK_TRANSFER_HOOK
+            );
+            assert_eq!(flags & MINT_RISK_REJECT_MASK, 0, "Flag-only extensions are accepted");
+        }
+
+        #[test]
+        fn test_truncated_extension_rejected() {
+            let mut data = token_2022_mint(&[]);
+            data.extend_from_slice(&12u16.to_le_bytes());
+            data.extend_from_slice(&32u16.to_le_bytes());
+            data.extend_from_slice(&[1u8; 8]); // value shorter than declared length
+            assert!(parse_mint_risk_flags(&data, true).is_err());
+        }
+    }
+
+    // ========================================================================
+    // 19. DCA TRANCHE TESTS (TWAP-style buyback plans)
+    // ========================================================================
+
+    mod dca_tests {
+        use super::*;
+        use crate::state::DcaState;
+        use crate::{DCA_STATE_SEED, MAX_DCA_TRANCHES, MIN_DCA_SLOT_SPACING};
+        use anchor_lang::prelude::Pubkey;
+
+        fn plan(total: u64, count: u8) -> DcaState {
+            DcaState {
+                mint: Pubkey::new_unique(),
+                total_lamports: total,
+                remaining_lamports: total,
+                tranche_lamports: total.div_ceil(count as u64),
+                tranche_count: count,
+                tranches_executed: 0,
+                min_slot_spacing: MIN_DCA_SLOT_SPACING,
+                last_tranche_slot: 0,
+                started_at: 0,
+                bump: 255,
+            }
+        }
+
+        /// Mirrors the accounting in execute_buy_tranche
+        fn run_tranche(dca: &mut DcaState) -> u64 {
+            let tranche = dca.remaining_lamports.min(dca.tranche_lamports);
+            dca.remaining_lamports -= tranche;
+            dca.tranches_executed += 1;
+            tranche
+        }
+
+        #[test]
+        fn test_dca_state_size() {
+            assert_eq!(DcaState::LEN, 83, "DcaState size mismatch");
+        }
+
+        #[test]
+        fn test_tranches_never_exceed_max() {
+            let mut dca = plan(1_000_000_001, 7);
+            let mut spent = 0u64;
+            while dca.is_active() {
+                let t = run_tranche(&mut dca);
+                assert!(t <= dca.tranche_lamports, "Tranche above per-tranche max");
+                spent += t;
+            }
+            assert_eq!(spent, 1_000_000_001, "Plan must spend exactly the allocation");
+            assert!(dca.tranches_executed <= 7);
+        }
+
+        #[test]
+        fn test_plan_completes_in_tranche_count() {
+            let mut dca = plan(MAX_FEES_PER_CYCLE, MAX_DCA_TRANCHES);
+            while dca.is_active() {
+                run_tranche(&mut dca);
+            }
+            assert_eq!(dca.remaining_lamports, 0);
+            assert!(dca.tranches_executed <= MAX_DCA_TRANCHES);
+        }
+
+        #[test]
+        fn test_cancelled_plan_inactive() {
+            let mut dca = plan(100_000_000, 4);
+            run_tranche(&mut dca);
+            dca.remaining_lamports = 0;
+            assert!(!dca.is_active());
+        }
+
+        #[test]
+        fn test_dca_pda_per_mint() {
+            let program_id = crate::ID;
+            let mint_a = Pubkey::new_unique();
+            let mint_b = Pubkey::new_unique();
+            let (a, _) = Pubkey::find_program_address(&[DCA_STATE_SEED, mint_a.as_ref()], &program_id);
+            let (b, _) = Pubkey::find_program_address(&[DCA_STATE_SEED, mint_b.as_ref()], &program_id);
+            assert_ne!(a, b, "Each mint has its own DCA plan");
+        }
+    }
+
+    // ========================================================================
+    // 20. PRICE CIRCUIT BREAKER TESTS
+    // ========================================================================
+
+    mod price_guard_tests {
+        use super::*;
+        use crate::{calculate_execution_price, check_max_price, check_price_deviation, PRICE_SCALE};
+
+        #[test]
+        fn test_execution_price_scaled() {
+            // 1 SOL for 1_000_000 base units = 1000 lamports per unit
+            let price = calculate_execution_price(1_000_000_000, 1_000_000).unwrap();
+            assert_eq!(price as u128, 1000 * PRICE_SCALE);
+        }
+
+        #[test]
+        fn test_execution_price_zero_tokens_rejected() {
+            assert!(calculate_execution_price(1_000_000_000, 0).is_err());
+        }
+
+        #[test]
+        fn test_no_observation_skips_check() {
+            assert!(check_price_deviation(1_000_000, 0, 3000).is_ok());
+        }
+
+        #[test]
+        fn test_disabled_guard_skips_check() {
+            assert!(check_price_deviation(10_000_000, 1_000_000, 0).is_ok());
+        }
+
+        #[test]
+        fn test_deviation_within_bound() {
+            // +30% exactly is allowed at 3000 bps
+            assert!(check_price_deviation(1_300_000, 1_000_000, 3000).is_ok());
+            assert!(check_price_deviation(700_000, 1_000_000, 3000).is_ok());
+        }
+
+        #[test]
+        fn test_deviation_above_bound_rejected() {
+            assert!(check_price_deviation(1_300_001, 1_000_000, 3000).is_err());
+            assert!(check_price_deviation(699_999, 1_000_000, 3000).is_err());
+        }
+
+        #[test]
+        fn test_pumpfun_buy_price_stable_across_small_buys() {
+            // Two consecutive small buys on the same curve should pass a 5% guard
+            let v_sol = 30_000_000_000u64;
+            let v_tok = 1_073_000_000_000_000u64;
+            let sol_in = 100_000_000u64;
+            let out1 = calculate_tokens_out_pumpfun(sol_in, v_sol, v_tok).unwrap();
+            let p1 = calculate_execution_price(sol_in, out1).unwrap();
+            let out2 = calculate_tokens_out_pumpfun(sol_in, v_sol + sol_in, v_tok - out1).unwrap();
+            let p2 = calculate_execution_price(sol_in, out2).unwrap();
+            assert!(p2 > p1, "Price rises along the curve");
+            assert!(check_price_deviation(p2, p1, 500).is_ok());
+        }
+
+        #[test]
+        fn test_max_price_bound() {
+            let bound = (1000 * PRICE_SCALE) as u64;
+            assert!(check_max_price(1_000_000_000, 1_000_000, None).is_ok(), "No bound");
+            assert!(check_max_price(1_000_000_000, 1_000_000, Some(bound)).is_ok(), "At the bound");
+            assert!(check_max_price(1_000_000_000, 999_999, Some(bound)).is_err(), "Fewer tokens than quoted");
+            assert!(check_max_price(1_000_000_000, 0, Some(bound)).is_err(), "Nothing received");
+        }
+    }
+
+    // ========================================================================
+    // 21. MONITOR KEY QUOTA TESTS
+    // ========================================================================
+
+    mod monitor_quota_tests {
+        use crate::state::MonitorKey;
+        use crate::SECONDS_PER_DAY;
+        use anchor_lang::prelude::Pubkey;
+
+        fn key(quota: u64, day: i64) -> MonitorKey {
+            MonitorKey {
+                authority: Pubkey::new_unique(),
+                daily_quota_lamports: quota,
+                attributed_today: 0,
+                current_day: day,
+                total_attributed: 0,
+                is_suspended: false,
+                bump: 255,
+            }
+        }
+
+        #[test]
+        fn test_monitor_key_size() {
+            assert_eq!(MonitorKey::LEN, 66, "MonitorKey size mismatch");
+        }
+
+        #[test]
+        fn test_quota_consumed_within_day() {
+            let now = 10 * SECONDS_PER_DAY + 100;
+            let mut k = key(1_000_000_000, 10);
+            k.attributed_today = 400_000_000;
+            assert_eq!(k.remaining_quota(now), 600_000_000);
+            assert_eq!(k.current_day, 10);
+        }
+
+        #[test]
+        fn test_quota_resets_next_day() {
+            let mut k = key(1_000_000_000, 10);
+            k.attributed_today = 1_000_000_000;
+            assert_eq!(k.remaining_quota(10 * SECONDS_PER_DAY + 5), 0);
+            assert_eq!(k.remaining_quota(11 * SECONDS_PER_DAY), 1_000_000_000);
+            assert_eq!(k.attributed_today, 0);
+            assert_eq!(k.current_day, 11);
+        }
+
+        #[test]
+        fn test_over_quota_detected() {
+            let mut k = key(500_000_000, 3);
+            k.attributed_today = 450_000_000;
+            let remaining = k.remaining_quota(3 * SECONDS_PER_DAY);
+            assert!(100_000_000 > remaining, "Attribution above quota must trigger suspension");
+            assert!(50_000_000 <= remaining);
+        }
+    }
+
+    // ========================================================================
+    // 22. DEPOSIT RECEIPT TESTS (FeeAsdfDepositedV2 computed fields)
+    // ========================================================================
+
+    mod deposit_receipt_tests {
+        use crate::{calculate_share_bps, BURN_SHARE, REBATE_SHARE, SHARE_DENOMINATOR};
+
+        #[test]
+        fn test_share_bps_zero_total() {
+            assert_eq!(calculate_share_bps(100, 0), 0);
+        }
+
+        #[test]
+        fn test_share_bps_capped() {
+            assert_eq!(calculate_share_bps(200, 100), 10000);
+        }
+
+        #[test]
+        fn test_effective_bps_match_split() {
+            let amount = 1_000_000_000u64;
+            let burn = amount * BURN_SHARE as u64 / SHARE_DENOMINATOR;
+            let rebate = amount - burn;
+            let burn_bps = calculate_share_bps(burn, amount);
+            let rebate_bps = calculate_share_bps(rebate, amount);
+            assert_eq!(burn_bps, 9944); // 99.448% truncated
+            assert_eq!(rebate_bps, 55);  // 0.552% truncated
+            assert!(burn_bps + rebate_bps <= 10000);
+        }
+
+        #[test]
+        fn test_projected_cycle_share() {
+            // Deposit of 250 into an account now holding 1000 pending burn = 25%
+            assert_eq!(calculate_share_bps(250, 1000), 2500);
+        }
+
+        #[test]
+        fn test_projected_rebate_uses_rebate_share() {
+            let pending = 100_000_000u64;
+            let rebate = pending * REBATE_SHARE as u64 / SHARE_DENOMINATOR;
+            assert_eq!(rebate, 552_000);
+        }
+    }
+
+    // ========================================================================
+    // 23. PRICE ORACLE TESTS (Pyth SOL/USD PriceUpdateV2 parsing)
+    // ========================================================================
+
+    mod oracle_tests {
+        use crate::{
+            lamports_to_usd_micro, parse_sol_usd_price, OraclePrice,
+            PYTH_PRICE_UPDATE_DISCRIMINATOR, SOL_USD_FEED_ID, ORACLE_MAX_AGE_SECONDS,
+        };
+
+        /// Build a Full-verification PriceUpdateV2 account
+        fn price_update(feed: [u8; 32], price: i64, expo: i32, publish_time: i64) -> Vec<u8> {
+            let mut data = Vec::new();
+            data.extend_from_slice(&PYTH_PRICE_UPDATE_DISCRIMINATOR);
+            data.extend_from_slice(&[0u8; 32]); // write_authority
+            data.push(1); // VerificationLevel::Full
+            data.extend_from_slice(&feed);
+            data.extend_from_slice(&price.to_le_bytes());
+            data.extend_from_slice(&1_000u64.to_le_bytes()); // conf
+            data.extend_from_slice(&expo.to_le_bytes());
+            data.extend_from_slice(&publish_time.to_le_bytes());
+            data.extend_from_slice(&publish_time.to_le_bytes()); // prev_publish_time
+            data.extend_from_slice(&price.to_le_bytes()); // ema_price
+            data.extend_from_slice(&1_000u64.to_le_bytes()); // ema_conf
+            data.extend_from_slice(&0u64.to_le_bytes()); // posted_slot
+            data
+        }
+
+        #[test]
+        fn test_parse_valid_price() {
+            let data = price_update(SOL_USD_FEED_ID, 15_000_000_000, -8, 1_000);
+            let p = parse_sol_usd_price(&data, 1_030).unwrap();
+            assert_eq!(p, OraclePrice { price: 15_000_000_000, exponent: -8, publish_time: 1_000 });
+        }
+
+        #[test]
+        fn test_wrong_feed_rejected() {
+            let data = price_update([7u8; 32], 15_000_000_000, -8, 1_000);
+            assert!(parse_sol_usd_price(&data, 1_000).is_err());
+        }
+
+        #[test]
+        fn test_stale_price_rejected() {
+            let data = price_update(SOL_USD_FEED_ID, 15_000_000_000, -8, 1_000);
+            assert!(parse_sol_usd_price(&data, 1_000 + ORACLE_MAX_AGE_SECONDS + 1).is_err());
+        }
+
+        #[test]
+        fn test_partial_verification_rejected() {
+            let mut data = price_update(SOL_USD_FEED_ID, 15_000_000_000, -8, 1_000);
+            data[40] = 0; // VerificationLevel::Partial
+            assert!(parse_sol_usd_price(&data, 1_000).is_err());
+        }
+
+        #[test]
+        fn test_non_positive_price_rejected() {
+            let data = price_update(SOL_USD_FEED_ID, 0, -8, 1_000);
+            assert!(parse_sol_usd_price(&data, 1_000).is_err());
+        }
+
+        #[test]
+        fn test_lamports_to_usd_micro() {
+            // 1 SOL at $150.00000000 (expo -8) = 150_000_000 micro-USD
+            let oracle = OraclePrice { price: 15_000_000_000, exponent: -8, publish_time: 0 };
+            assert_eq!(lamports_to_usd_micro(1_000_000_000, &oracle).unwrap(), 150_000_000);
+            // 0.5 SOL
+            assert_eq!(lamports_to_usd_micro(500_000_000, &oracle).unwrap(), 75_000_000);
+        }
+    }
+
+    // ========================================================================
+    // 24. TOKEN CATEGORY TESTS (layout used by migrate_token_stats)
+    // ========================================================================
+
+    mod token_category_tests {
+        use crate::state::TokenStats;
+        use crate::UNCATEGORIZED;
+
+        fn stats(category: u8, is_paused: bool) -> TokenStats {
+            TokenStats {
+                burn_bps: 7500,
+                risk_flags: 0b101,
+                token_account_nonce: 3,
+                usd_value_burned: 42,
+                category,
+                is_paused: is_paused as u8,
+                ..super::token_stats_fixture()
+            }
+        }
+
+        #[test]
+        fn test_zero_copy_offsets() {
+            let s = stats(7, true);
+            let data = bytemuck::bytes_of(&s);
+            assert_eq!(data.len(), TokenStats::LEN);
+            assert_eq!(&data[..32], s.mint.as_ref(), "mint first (migrate_account reads data[8..40])");
+            assert_eq!(&data[160..168], &0u64.to_le_bytes(), "total_burned after the Pubkeys");
+            assert_eq!(&data[264..272], &42u64.to_le_bytes(), "usd_value_burned offset");
+            assert_eq!(&data[328..330], &7500u16.to_le_bytes(), "burn_bps offset");
+            assert_eq!(data[342], 0b101, "risk_flags offset");
+            assert_eq!(data[343], 3, "token_account_nonce offset");
+            assert_eq!(data[344], 7, "category offset");
+            assert_eq!(data[345], 1, "is_paused offset");
+            assert_eq!(&data[350..352], &[0u8; 2][..], "reserved");
+        }
+
+        #[test]
+        fn test_default_category_unpaused() {
+            let s = stats(UNCATEGORIZED, false);
+            assert_eq!(s.category, 0);
+            assert_eq!(s.is_paused, 0);
+        }
+
+        #[test]
+        fn test_token_failures_pause_only_that_token() {
+            let mut s = stats(UNCATEGORIZED, false);
+            for i in 1..crate::TOKEN_PAUSE_FAILURE_THRESHOLD {
+                assert!(!s.record_failure(100 + i as i64));
+            }
+            assert!(s.record_failure(200), "Threshold pauses the token");
+            assert_eq!(s.is_paused, 1);
+            assert_eq!(s.last_failure_timestamp, 200);
+            assert!(!s.record_failure(300), "Already paused");
+            assert_eq!(s.consecutive_failures, crate::TOKEN_PAUSE_FAILURE_THRESHOLD + 1);
+        }
+    }
+
+    // ========================================================================
+    // 25. FEE ATTESTATION TESTS (Merkle commitments + fraud proofs)
+    // ========================================================================
+
+    mod fee_attestation_tests {
+        use crate::state::{FeeAttestation, FeeLeafProof};
+        use crate::{
+            fee_leaf_hash, merkle_node_hash, verify_merkle_proof,
+            ATTESTATION_CHALLENGE_WINDOW_SLOTS, FRAUD_DUPLICATE_TX, FRAUD_FEE_TOO_HIGH,
+            FRAUD_INDEX_BEYOND_COUNT, FRAUD_SLOT_OUT_OF_RANGE, MAX_FEE_PER_TX,
+        };
+        use anchor_lang::prelude::Pubkey;
+
+        type Leaf = (u64, [u8; 64], u64);
+
+        /// Build a 4-leaf tree, returning (root, proofs by index)
+        fn tree(leaves: &[Leaf; 4]) -> ([u8; 32], Vec<Vec<[u8; 32]>>) {
+            let h: Vec<[u8; 32]> = leaves.iter().map(|(s, sig, f)| fee_leaf_hash(*s, sig, *f)).collect();
+            let n01 = merkle_node_hash(&h[0], &h[1]);
+            let n23 = merkle_node_hash(&h[2], &h[3]);
+            let root = merkle_node_hash(&n01, &n23);
+            let proofs = vec![
+                vec![h[1], n23],
+                vec![h[0], n23],
+                vec![h[3], n01],
+                vec![h[2], n01],
+            ];
+            (root, proofs)
+        }
+
+        fn proof(leaves: &[Leaf; 4], proofs: &[Vec<[u8; 32]>], i: usize) -> FeeLeafProof {
+            FeeLeafProof {
+                slot: leaves[i].0,
+                signature: leaves[i].1,
+                fee: leaves[i].2,
+                index: i as u32,
+                proof: proofs[i].clone(),
+            }
+        }
+
+        fn attestation(root: [u8; 32], tx_count: u32) -> FeeAttestation {
+            FeeAttestation {
+                mint: Pubkey::new_unique(),
+                poster: Pubkey::new_unique(),
+                merkle_root: root,
+                fee_amount: 4_000_000,
+                tx_count,
+                start_slot: 100,
+                end_slot: 200,
+                posted_slot: 1_000,
+                bump: 255,
+                bond: Pubkey::default(),
+            }
+        }
+
+        fn honest_leaves() -> [Leaf; 4] {
+            [
+                (110, [1u8; 64], 1_000_000),
+                (120, [2u8; 64], 1_000_000),
+                (150, [3u8; 64], 1_000_000),
+                (200, [4u8; 64], 1_000_000),
+            ]
+        }
+
+        #[test]
+        fn test_fee_attestation_size() {
+            assert_eq!(FeeAttestation::LEN, 165, "FeeAttestation size mismatch");
+        }
+
+        #[test]
+        fn test_valid_proofs_verify() {
+            let leaves = honest_leaves();
+            let (root, proofs) = tree(&leaves);
+            for i in 0..4 {
+                let (s, sig, f) = &leaves[i];
+                assert!(verify_merkle_proof(&root, fee_leaf_hash(*s, sig, *f), i as u32, &proofs[i]));
+            }
+        }
+
+        #[test]
+        fn test_wrong_index_or_leaf_rejected() {
+            let leaves = honest_leaves();
+            let (root, proofs) = tree(&leaves);
+            let (s, sig, f) = &leaves[0];
+            assert!(!verify_merkle_proof(&root, fee_leaf_hash(*s, sig, *f), 1, &proofs[0]));
+            assert!(!verify_merkle_proof(&root, fee_leaf_hash(*s, sig, f + 1), 0, &proofs[0]));
+            // Index beyond proof depth cannot alias a real position
+            assert!(!verify_merkle_proof(&root, fee_leaf_hash(*s, sig, *f), 4, &proofs[0]));
+        }
+
+        #[test]
+        fn test_honest_attestation_has_no_fraud() {
+            let leaves = honest_leaves();
+            let (root, proofs) = tree(&leaves);
+            let att = attestation(root, 4);
+            for i in 0..4 {
+                assert!(att.fraud_reason(&proof(&leaves, &proofs, i), None).is_err());
+            }
+        }
+
+        #[test]
+        fn test_slot_out_of_range_proven() {
+            let mut leaves = honest_leaves();
+            leaves[2].0 = 100; // start_slot is exclusive
+            let (root, proofs) = tree(&leaves);
+            let att = attestation(root, 4);
+            assert_eq!(att.fraud_reason(&proof(&leaves, &proofs, 2), None).unwrap(), FRAUD_SLOT_OUT_OF_RANGE);
+        }
+
+        #[test]
+        fn test_index_beyond_count_proven() {
+            let leaves = honest_leaves();
+            let (root, proofs) = tree(&leaves);
+            let att = attestation(root, 3); // claims 3 TXs but commits 4 leaves
+            assert_eq!(att.fraud_reason(&proof(&leaves, &proofs, 3), None).unwrap(), FRAUD_INDEX_BEYOND_COUNT);
+        }
+
+        #[test]
+        fn test_fee_too_high_proven() {
+            let mut leaves = honest_leaves();
+            leaves[1].2 = MAX_FEE_PER_TX + 1;
+            let (root, proofs) = tree(&leaves);
+            let att = attestation(root, 4);
+            assert_eq!(att.fraud_reason(&proof(&leaves, &proofs, 1), None).unwrap(), FRAUD_FEE_TOO_HIGH);
+        }
+
+        #[test]
+        fn test_duplicate_signature_proven() {
+            let mut leaves = honest_leaves();
+            leaves[3].1 = leaves[0].1; // same tx counted twice
+            let (root, proofs) = tree(&leaves);
+            let att = attestation(root, 4);
+            let a = proof(&leaves, &proofs, 0);
+            let b = proof(&leaves, &proofs, 3);
+            assert_eq!(att.fraud_reason(&a, Some(&b)).unwrap(), FRAUD_DUPLICATE_TX);
+            // Same leaf twice is not a duplicate
+            assert!(att.fraud_reason(&a, Some(&a.clone())).is_err());
+        }
+
+        #[test]
+        fn test_challenge_window() {
+            let att = attestation([0u8; 32], 1);
+            assert!(att.in_challenge_window(1_000 + ATTESTATION_CHALLENGE_WINDOW_SLOTS));
+            assert!(!att.in_challenge_window(1_001 + ATTESTATION_CHALLENGE_WINDOW_SLOTS));
+        }
+    }
+
+    // ========================================================================
+    // 26. CIRCULATING SUPPLY TESTS
+    // ========================================================================
+
+    mod circulating_supply_tests {
+        use crate::state::CirculatingSupply;
+
+        #[test]
+        fn test_circulating_subtracts_holdings() {
+            let c = CirculatingSupply::compute(1_000_000, 50_000, 20_000, 5_000);
+            assert_eq!(c, 925_000);
+        }
+
+        #[test]
+        fn test_circulating_without_holdings() {
+            assert_eq!(CirculatingSupply::compute(1_000_000, 0, 0, 0), 1_000_000);
+        }
+
+        #[test]
+        fn test_circulating_never_underflows() {
+            assert_eq!(CirculatingSupply::compute(100, 80, 30, 10), 0);
+        }
+    }
+
+    // ========================================================================
+    // 27. VALIDATOR SET TESTS (M-of-N ed25519 attestations)
+    // ========================================================================
+
+    mod validator_set_tests {
+        use crate::state::ValidatorSet;
+        use crate::{fee_attestation_message, parse_ed25519_signers, FEE_ATTESTATION_DOMAIN, MAX_ATTESTORS};
+        use anchor_lang::prelude::Pubkey;
+
+        /// Build Ed25519SigVerify data with self-contained entries (pubkey, message)
+        fn ed25519_data(entries: &[(Pubkey, &[u8])], foreign_index: bool) -> Vec<u8> {
+            let header = 2 + entries.len() * 14;
+            let mut offsets = Vec::new();
+            let mut payload = Vec::new();
+            for (pubkey, msg) in entries {
+                let pk_off = header + payload.len();
+                payload.extend_from_slice(pubkey.as_ref());
+                let sig_off = header + payload.len();
+                payload.extend_from_slice(&[9u8; 64]);
+                let msg_off = header + payload.len();
+                payload.extend_from_slice(msg);
+                let ix_index = if foreign_index { 0u16 } else { u16::MAX };
+                for v in [
+                    sig_off as u16, ix_index, pk_off as u16, ix_index,
+                    msg_off as u16, msg.len() as u16, ix_index,
+                ] {
+                    offsets.extend_from_slice(&v.to_le_bytes());
+                }
+            }
+            let mut data = vec![entries.len() as u8, 0];
+            data.extend_from_slice(&offsets);
+            data.extend_from_slice(&payload);
+            data
+        }
+
+        #[test]
+        fn test_validator_set_size() {
+            assert_eq!(ValidatorSet::LEN, 267, "ValidatorSet size mismatch");
+        }
+
+        #[test]
+        fn test_active_attestors() {
+            let mut attestors = [Pubkey::default(); MAX_ATTESTORS];
+            attestors[0] = Pubkey::new_unique();
+            attestors[1] = Pubkey::new_unique();
+            let set = ValidatorSet { attestors, attestor_count: 2, threshold: 2, updated_at: 0, bump: 255 };
+            assert_eq!(set.active().len(), 2);
+            assert!(!set.active().contains(&Pubkey::default()));
+        }
+
+        #[test]
+        fn test_message_layout() {
+            let mint = Pubkey::new_unique();
+            let msg = fee_attestation_message(&mint, 7, 8, 9, &[3u8; 32]);
+            assert_eq!(msg.len(), FEE_ATTESTATION_DOMAIN.len() + 32 + 8 + 8 + 4 + 32);
+            assert!(msg.starts_with(FEE_ATTESTATION_DOMAIN));
+            // Any field change produces a different message
+            assert_ne!(msg, fee_attestation_message(&mint, 7, 8, 10, &[3u8; 32]));
+        }
+
+        #[test]
+        fn test_parse_signers_matching_message() {
+            let msg = fee_attestation_message(&Pubkey::new_unique(), 1, 2, 3, &[0u8; 32]);
+            let a = Pubkey::new_unique();
+            let b = Pubkey::new_unique();
+            let data = ed25519_data(&[(a, &msg), (b, b"other message")], false);
+            let signers = parse_ed25519_signers(&data, &msg).unwrap();
+            assert_eq!(signers, vec![a], "Only signatures over the exact message count");
+        }
+
+        #[test]
+        fn test_foreign_instruction_offsets_rejected() {
+            let msg = b"msg".to_vec();
+            let data = ed25519_data(&[(Pubkey::new_unique(), &msg)], true);
+            assert!(parse_ed25519_signers(&data, &msg).is_err());
+        }
+
+        #[test]
+        fn test_truncated_data_rejected() {
+            let msg = b"msg".to_vec();
+            let mut data = ed25519_data(&[(Pubkey::new_unique(), &msg)], false);
+            data.truncate(20);
+            assert!(parse_ed25519_signers(&data, &msg).is_err());
+        }
+    }
+
+    // ========================================================================
+    // 28. SHADOW MATH TESTS (current vs exact-out buy parameters)
+    // ========================================================================
+
+    mod shadow_math_tests {
+        use crate::state::ShadowComparison;
+        use crate::{
+            bonding_curve_price_impact_bps, calculate_buy_amount_and_slippage, calculate_buy_exact_out,
+            calculate_sol_in_for_tokens_pumpfun, calculate_tokens_out_pumpfun,
+        };
+        use anchor_lang::prelude::Pubkey;
+
+        const VTOKEN: u64 = 1_073_000_000_000_000;
+        const VSOL: u64 = 30_000_000_000;
+
+        fn curve_data() -> Vec<u8> {
+            let mut data = vec![0u8; 8];
+            data.extend_from_slice(&VTOKEN.to_le_bytes());
+            data.extend_from_slice(&VSOL.to_le_bytes());
+            data.extend_from_slice(&[0u8; 8]);
+            data
+        }
+
+        fn empty_comparison() -> ShadowComparison {
+            ShadowComparison {
+                mint: Pubkey::new_unique(),
+                shadow_math: true,
+                samples: 0,
+                last_slot: 0,
+                last_old_sol_cost: 0,
+                last_old_tokens: 0,
+                last_new_sol_cost: 0,
+                last_new_tokens: 0,
+                last_actual_tokens: 0,
+                cumulative_token_delta: 0,
+                cumulative_sol_delta: 0,
+                max_token_delta_bps: 0,
+                updated_at: 0,
+                bump: 255,
+            }
+        }
+
+        #[test]
+        fn test_shadow_comparison_size() {
+            assert_eq!(ShadowComparison::LEN, 116, "ShadowComparison size mismatch");
+        }
+
+        #[test]
+        fn test_inverse_formula_round_trips() {
+            let sol_in = 100_000_000;
+            let tokens = calculate_tokens_out_pumpfun(sol_in, VSOL, VTOKEN).unwrap();
+            let cost = calculate_sol_in_for_tokens_pumpfun(tokens, VSOL, VTOKEN).unwrap();
+            // Rounded up, never more than what actually bought those tokens
+            assert!(cost <= sol_in);
+            assert!(calculate_tokens_out_pumpfun(cost, VSOL, VTOKEN).unwrap() >= tokens);
+        }
+
+        #[test]
+        fn test_inverse_formula_rejects_draining_pool() {
+            assert!(calculate_sol_in_for_tokens_pumpfun(VTOKEN, VSOL, VTOKEN).is_err());
+        }
+
+        #[test]
+        fn test_exact_out_within_budget() {
+            let budget = 200_000_000;
+            let (max_sol_cost, desired) = calculate_buy_exact_out(budget, &curve_data(), u64::MAX, 500).unwrap();
+            assert!(max_sol_cost <= budget);
+            assert!(desired > 0);
+        }
+
+        #[test]
+        fn test_exact_out_requests_more_tokens_than_current() {
+            let budget = 200_000_000;
+            let (old_sol, old_tokens, _) =
+                calculate_buy_amount_and_slippage(budget, &curve_data(), u64::MAX, 500, 0, 0).unwrap();
+            let (new_sol, new_tokens) = calculate_buy_exact_out(budget, &curve_data(), u64::MAX, 500).unwrap();
+            // Slippage on the SOL side keeps more of the expected output than shaving tokens
+            assert!(new_tokens > old_tokens);
+            assert!(new_sol <= old_sol);
+        }
+
+        #[test]
+        fn test_price_impact_is_budget_over_virtual_sol() {
+            assert_eq!(bonding_curve_price_impact_bps(0, &curve_data()).unwrap(), 0);
+            assert_eq!(bonding_curve_price_impact_bps(VSOL / 100, &curve_data()).unwrap(), 100);
+            assert_eq!(bonding_curve_price_impact_bps(u64::MAX, &curve_data()).unwrap(), u16::MAX);
+            assert!(bonding_curve_price_impact_bps(1, &[0u8; 16]).is_err());
+        }
+
+        #[test]
+        fn test_exact_out_zero_budget() {
+            assert_eq!(calculate_buy_exact_out(0, &curve_data(), u64::MAX, 500).unwrap(), (0, 0));
+        }
+
+        #[test]
+        fn test_record_accumulates_deltas() {
+            let mut c = empty_comparison();
+            let (dt, ds) = c.record((1_000, 10_000), (990, 10_300), 10_250, 42, 7);
+            assert_eq!((dt, ds), (300, -10));
+            assert_eq!(c.max_token_delta_bps, 300);
+
+            c.record((1_000, 10_000), (1_000, 9_900), 9_950, 43, 8);
+            assert_eq!(c.samples, 2);
+            assert_eq!(c.cumulative_token_delta, 200);
+            assert_eq!(c.cumulative_sol_delta, -10);
+            assert_eq!(c.max_token_delta_bps, 300, "Max keeps the largest deviation");
+            assert_eq!((c.last_slot, c.last_actual_tokens, c.updated_at), (43, 9_950, 8));
+        }
+
+        #[test]
+        fn test_record_saturates_extreme_deltas() {
+            let mut c = empty_comparison();
+            let (dt, _) = c.record((0, u64::MAX), (0, 0), 0, 1, 1);
+            assert_eq!(dt, i64::MIN);
+            c.record((0, u64::MAX), (0, 0), 0, 2, 2);
+            assert_eq!(c.cumulative_token_delta, i64::MIN);
+        }
+    }
+
+    // ========================================================================
+    // 29. VALIDATOR BOND TESTS (permissionless attestation + slashing)
+    // ========================================================================
+
+    mod validator_bond_tests {
+        use crate::state::ValidatorBond;
+        use crate::{MIN_VALIDATOR_BOND, SLASH_BPS};
+        use anchor_lang::prelude::Pubkey;
+
+        fn bond(bonded_lamports: u64, open_attestations: u32) -> ValidatorBond {
+            ValidatorBond {
+                operator: Pubkey::new_unique(),
+                bonded_lamports,
+                open_attestations,
+                slash_count: 0,
+                total_attestations: 0,
+                total_slashed: 0,
+                bonded_at: 0,
+                bump: 255,
+            }
+        }
+
+        #[test]
+        fn test_validator_bond_size() {
+            assert_eq!(ValidatorBond::LEN, 73, "ValidatorBond size mismatch");
+        }
+
+        #[test]
+        fn test_attest_requires_minimum_bond() {
+            assert!(!bond(MIN_VALIDATOR_BOND - 1, 0).can_attest(1));
+            assert!(bond(MIN_VALIDATOR_BOND, 0).can_attest(1));
+        }
+
+        #[test]
+        fn test_claim_capped_at_bond() {
+            let b = bond(2 * MIN_VALIDATOR_BOND, 0);
+            assert!(b.can_attest(2 * MIN_VALIDATOR_BOND));
+            assert!(!b.can_attest(2 * MIN_VALIDATOR_BOND + 1));
+        }
+
+        #[test]
+        fn test_slash_amount() {
+            let b = bond(4 * MIN_VALIDATOR_BOND, 1);
+            assert_eq!(b.slash_amount(), 4 * MIN_VALIDATOR_BOND * SLASH_BPS as u64 / 10000);
+            assert_eq!(bond(0, 1).slash_amount(), 0);
+        }
+
+        #[test]
+        fn test_repeated_slashing_never_underflows() {
+            let mut b = bond(MIN_VALIDATOR_BOND, 10);
+            for _ in 0..64 {
+                let s = b.slash_amount();
+                b.bonded_lamports -= s;
+            }
+            assert!(b.bonded_lamports < MIN_VALIDATOR_BOND);
+            assert!(!b.can_attest(0), "Slashed-out bond cannot post");
+        }
+
+        #[test]
+        fn test_withdraw_locked_by_open_attestations() {
+            assert!(!bond(3 * MIN_VALIDATOR_BOND, 1).can_withdraw(MIN_VALIDATOR_BOND));
+            assert!(bond(3 * MIN_VALIDATOR_BOND, 0).can_withdraw(MIN_VALIDATOR_BOND));
+        }
+
+        #[test]
+        fn test_withdraw_full_exit_or_above_minimum() {
+            let b = bond(MIN_VALIDATOR_BOND + 500, 0);
+            assert!(b.can_withdraw(MIN_VALIDATOR_BOND + 500), "Full exit allowed");
+            assert!(b.can_withdraw(500), "Remainder at minimum allowed");
+            assert!(!b.can_withdraw(501), "Remainder below minimum rejected");
+            assert!(!b.can_withdraw(MIN_VALIDATOR_BOND + 501), "Cannot exceed bond");
+        }
+    }
+
+    // ========================================================================
+    // 30. BATCH FEE ATTESTATION TESTS (shared per-entry checks)
+    // ========================================================================
+
+    mod fee_batch_tests {
+        use crate::state::{TokenStats, ValidatedFeeEntry, ValidatorState};
+        use crate::{check_fee_attestation, MAX_FEE_BATCH_SIZE, MAX_PENDING_FEES};
+        use anchor_lang::prelude::{AnchorDeserialize, AnchorSerialize, Pubkey};
+
+        fn validator(mint: Pubkey, last_validated_slot: u64) -> ValidatorState {
+            ValidatorState {
+                mint,
+                bonding_curve: Pubkey::new_unique(),
+                last_validated_slot,
+                total_validated_lamports: 0,
+                total_validated_count: 0,
+                fee_rate_bps: 50,
+                bump: 255,
+                max_fee_per_slot: 0,
+                max_tx_per_slot: 0,
+                _reserved: [0u8; 20],
+            }
+        }
+
+        fn stats(mint: Pubkey, pending_fees_lamports: u64) -> TokenStats {
+            TokenStats {
+                mint,
+                pending_fees_lamports,
+                ..super::token_stats_fixture()
+            }
+        }
+
+        fn entry(mint: Pubkey, fee_amount: u64, end_slot: u64, tx_count: u32) -> ValidatedFeeEntry {
+            ValidatedFeeEntry { mint, fee_amount, end_slot, tx_count, merkle_root: [7u8; 32] }
+        }
+
+        #[test]
+        fn test_valid_entry_accepted() {
+            let mint = Pubkey::new_unique();
+            let e = entry(mint, 1_000_000_000, 1_100, 500);
+            assert!(check_fee_attestation(&validator(mint, 1_000), &stats(mint, 0), &e).is_ok());
+        }
+
+        #[test]
+        fn test_stale_and_oversized_ranges_rejected() {
+            let mint = Pubkey::new_unique();
+            let (v, s) = (validator(mint, 1_000), stats(mint, 0));
+            assert!(check_fee_attestation(&v, &s, &entry(mint, 0, 1_000, 0)).is_err(), "Stale end slot");
+            assert!(check_fee_attestation(&v, &s, &entry(mint, 0, 2_001, 0)).is_err(), "Range > 1000 slots");
+        }
+
+        #[test]
+        fn test_fee_and_tx_caps_enforced() {
+            let mint = Pubkey::new_unique();
+            let (v, s) = (validator(mint, 1_000), stats(mint, 0));
+            // 100 slots: max 1 SOL and 10_000 TXs
+            assert!(check_fee_attestation(&v, &s, &entry(mint, 1_000_000_001, 1_100, 1)).is_err());
+            assert!(check_fee_attestation(&v, &s, &entry(mint, 1, 1_100, 10_001)).is_err());
+        }
+
+        #[test]
+        fn test_pending_cap_enforced() {
+            let mint = Pubkey::new_unique();
+            let v = validator(mint, 1_000);
+            let e = entry(mint, 2, 1_001, 1);
+            assert!(check_fee_attestation(&v, &stats(mint, MAX_PENDING_FEES - 2), &e).is_ok());
+            assert!(check_fee_attestation(&v, &stats(mint, MAX_PENDING_FEES - 1), &e).is_err());
+        }
+
+        #[test]
+        fn test_entry_serialization_round_trip() {
+            let e = entry(Pubkey::new_unique(), 42, 9, 3);
+            let bytes = e.try_to_vec().unwrap();
+            assert_eq!(bytes.len(), 32 + 8 + 8 + 4 + 32);
+            let decoded = ValidatedFeeEntry::try_from_slice(&bytes).unwrap();
+            assert_eq!((decoded.mint, decoded.fee_amount, decoded.end_slot), (e.mint, 42, 9));
+        }
+
+        #[test]
+        fn test_batch_fits_transaction_account_limit() {
+            // 3 remaining accounts per entry + 6 fixed accounts, under the 64 account lock limit
+            const { assert!(MAX_FEE_BATCH_SIZE * 3 + 6 <= 64) };
+        }
+    }
+
+    // ========================================================================
+    // 31. BUG BOUNTY VAULT TESTS
+    // ========================================================================
+
+    mod bounty_vault_tests {
+        use crate::state::BountyVault;
+        use anchor_lang::prelude::Pubkey;
+
+        fn vault() -> BountyVault {
+            BountyVault {
+                total_funded: 10_000_000_000,
+                total_paid: 0,
+                payout_count: 0,
+                pending_recipient: Pubkey::default(),
+                pending_amount: 0,
+                pending_report_hash: [0u8; 32],
+                pending_proposed_at: 0,
+                bump: 255,
+            }
+        }
+
+        #[test]
+        fn test_bounty_vault_size() {
+            assert_eq!(BountyVault::LEN, 101, "BountyVault size mismatch");
+        }
+
+        #[test]
+        fn test_no_pending_by_default() {
+            let v = vault();
+            assert!(!v.has_pending());
+            assert!(!v.matches_pending(&Pubkey::default(), 0, &[0u8; 32]), "Empty proposal never matches");
+        }
+
+        #[test]
+        fn test_payout_must_match_proposal_exactly() {
+            let recipient = Pubkey::new_unique();
+            let mut v = vault();
+            v.pending_recipient = recipient;
+            v.pending_amount = 5_000_000_000;
+            v.pending_report_hash = [9u8; 32];
+
+            assert!(v.matches_pending(&recipient, 5_000_000_000, &[9u8; 32]));
+            assert!(!v.matches_pending(&Pubkey::new_unique(), 5_000_000_000, &[9u8; 32]), "Other recipient");
+            assert!(!v.matches_pending(&recipient, 5_000_000_001, &[9u8; 32]), "Other amount");
+            assert!(!v.matches_pending(&recipient, 5_000_000_000, &[8u8; 32]), "Other report");
+        }
+
+        #[test]
+        fn test_clear_pending() {
+            let mut v = vault();
+            v.pending_recipient = Pubkey::new_unique();
+            v.pending_amount = 1;
+            v.pending_report_hash = [1u8; 32];
+            v.pending_proposed_at = 100;
+            v.clear_pending();
+            assert!(!v.has_pending());
+            assert_eq!((v.pending_amount, v.pending_proposed_at), (0, 0));
+            assert_eq!(v.pending_report_hash, [0u8; 32]);
+        }
+
+        #[test]
+        fn test_timelock_boundary() {
+            let cooldown: i64 = 3600;
+            let proposed_at: i64 = 1_000;
+            assert!((proposed_at + cooldown - 1).saturating_sub(proposed_at) < cooldown, "Too early");
+            assert!((proposed_at + cooldown).saturating_sub(proposed_at) >= cooldown, "Executable");
+        }
+    }
+
+    // ========================================================================
+    // 32. VALIDATOR LIMIT TESTS (per-validator fee/TX caps)
+    // ========================================================================
+
+    mod validator_limit_tests {
+        use crate::state::{TokenStats, ValidatedFeeEntry, ValidatorState};
+        use crate::{
+            check_fee_attestation, DEFAULT_MAX_FEE_PER_SLOT, DEFAULT_MAX_TX_PER_SLOT,
+        };
+        use anchor_lang::prelude::Pubkey;
+        use anchor_lang::{AccountDeserialize, AccountSerialize};
+
+        fn validator(mint: Pubkey, max_fee_per_slot: u64, max_tx_per_slot: u32) -> ValidatorState {
+            ValidatorState {
+                mint,
+                bonding_curve: Pubkey::new_unique(),
+                last_validated_slot: 1_000,
+                total_validated_lamports: 0,
+                total_validated_count: 0,
+                fee_rate_bps: 50,
+                bump: 255,
+                max_fee_per_slot,
+                max_tx_per_slot,
+                _reserved: [0u8; 20],
+            }
+        }
+
+        fn stats(mint: Pubkey) -> TokenStats {
+            TokenStats {
+                mint,
+                ..super::token_stats_fixture()
+            }
+        }
+
+        fn entry(mint: Pubkey, fee_amount: u64, tx_count: u32) -> ValidatedFeeEntry {
+            // 10 slots past last_validated_slot
+            ValidatedFeeEntry { mint, fee_amount, end_slot: 1_010, tx_count, merkle_root: [0u8; 32] }
+        }
+
+        #[test]
+        fn test_validator_state_size_unchanged() {
+            assert_eq!(ValidatorState::LEN, 123, "Limits must fit in the former reserved bytes");
+        }
+
+        #[test]
+        fn test_zero_limits_fall_back_to_defaults() {
+            let v = validator(Pubkey::new_unique(), 0, 0);
+            assert_eq!(v.fee_cap_per_slot(), DEFAULT_MAX_FEE_PER_SLOT);
+            assert_eq!(v.tx_cap_per_slot(), DEFAULT_MAX_TX_PER_SLOT);
+        }
+
+        #[test]
+        fn test_legacy_account_reads_default_limits() {
+            // Pre-upgrade accounts carry zeroed reserved bytes where the limits now live
+            let mut data = Vec::new();
+            validator(Pubkey::new_unique(), 0, 0).try_serialize(&mut data).unwrap();
+            assert_eq!(data.len(), 8 + ValidatorState::LEN);
+            let decoded = ValidatorState::try_deserialize(&mut &data[..]).unwrap();
+            assert_eq!(decoded.fee_cap_per_slot(), DEFAULT_MAX_FEE_PER_SLOT);
+        }
+
+        #[test]
+        fn test_default_caps_reject_active_token() {
+            let mint = Pubkey::new_unique();
+            let v = validator(mint, 0, 0);
+            // 10 slots at 0.05 SOL/slot exceeds the 0.01 SOL/slot default
+            assert!(check_fee_attestation(&v, &stats(mint), &entry(mint, 500_000_000, 10)).is_err());
+            assert!(check_fee_attestation(&v, &stats(mint), &entry(mint, 1, 1_001)).is_err());
+        }
+
+        #[test]
+        fn test_raised_caps_accept_active_token() {
+            let mint = Pubkey::new_unique();
+            let v = validator(mint, 50_000_000, 500);
+            assert!(check_fee_attestation(&v, &stats(mint), &entry(mint, 500_000_000, 5_000)).is_ok());
+            assert!(check_fee_attestation(&v, &stats(mint), &entry(mint, 500_000_001, 5_000)).is_err());
+            assert!(check_fee_attestation(&v, &stats(mint), &entry(mint, 500_000_000, 5_001)).is_err());
+        }
+
+        #[test]
+        fn test_queued_limits_bounded_and_return_effective_cap() {
+            use crate::{ADMIN_OP_VALIDATOR_MAX_FEE, ADMIN_OP_VALIDATOR_MAX_TX, MAX_FEE_PER_SLOT_LIMIT};
+            let mut v = validator(Pubkey::new_unique(), 0, 0);
+            assert!(v.apply_parameter(ADMIN_OP_VALIDATOR_MAX_FEE, 0).is_err());
+            assert!(v.apply_parameter(ADMIN_OP_VALIDATOR_MAX_FEE, MAX_FEE_PER_SLOT_LIMIT + 1).is_err());
+            assert_eq!(v.apply_parameter(ADMIN_OP_VALIDATOR_MAX_FEE, 50_000_000).unwrap(), DEFAULT_MAX_FEE_PER_SLOT);
+            assert_eq!(v.apply_parameter(ADMIN_OP_VALIDATOR_MAX_TX, 500).unwrap(), DEFAULT_MAX_TX_PER_SLOT as u64);
+            assert_eq!((v.fee_cap_per_slot(), v.tx_cap_per_slot()), (50_000_000, 500));
+        }
+    }
+
+    // ========================================================================
+    // 33. ONBOARDING BONUS TESTS
+    // ========================================================================
+
+    mod onboarding_bonus_tests {
+        use crate::state::{RebatePool, UserStats};
+        use anchor_lang::prelude::Pubkey;
+        use anchor_lang::AccountSerialize;
+
+        fn pool(bonus_bps: u16, bonus_cap: u64, bonus_budget: u64) -> RebatePool {
+            RebatePool {
+                bump: 255,
+                total_deposited: 0,
+                total_distributed: 0,
+                rebates_count: 0,
+                last_rebate_timestamp: 0,
+                last_rebate_slot: 0,
+                unique_recipients: 0,
+                bonus_bps,
+                bonus_cap,
+                bonus_budget,
+                total_bonus_paid: 0,
+                _reserved: [0u8; 6],
+                rebate_share: 0,
+                rebate_threshold: 0,
+                pending_rebate_share: 0,
+                pending_rebate_threshold: 0,
+                pending_params_timestamp: 0,
+                draw_request_slot: 0,
+                draw_target_slot: 0,
+                draw_candidates_hash: [0u8; 32],
+                draw_randomness: [0u8; 32],
+                draw_winner: Pubkey::default(),
+                draws_count: 0,
+                current_epoch: 0,
+                epoch_started_at: 0,
+                epoch_reserved: 0,
+                referral_share_bps: 0,
+                referral_reserved: 0,
+                version: 0,
+            }
+        }
+
+        #[test]
+        fn test_rebate_pool_size() {
+            assert_eq!(RebatePool::LEN, 268, "Bonus fields in former reserved bytes + rebate params + draw + epochs + referrals + version");
+        }
+
+        #[test]
+        fn test_user_stats_size() {
+            assert_eq!(UserStats::LEN, 128, "UserStats size mismatch");
+        }
+
+        #[test]
+        fn test_claimed_flag_offset_matches_migration() {
+            let stats = UserStats {
+                bump: 255,
+                user: Pubkey::new_unique(),
+                pending_contribution: 0,
+                total_contributed: 0,
+                total_rebate: 0,
+                last_update_timestamp: 0,
+                last_update_slot: 0,
+                onboarding_bonus_claimed: true,
+                contribution_epoch: 0,
+                epoch_contribution: 0,
+                app_id: 0,
+                referrer: None,
+                version: 0,
+            };
+            let mut data = Vec::new();
+            stats.try_serialize(&mut data).unwrap();
+            // referrer None serializes as its tag only
+            assert_eq!(data.len(), 8 + UserStats::LEN - 32);
+            assert_eq!(data[81], 1, "migrate_user_stats writes the flag at byte 81");
+        }
+
+        #[test]
+        fn test_disabled_bonus_is_zero() {
+            assert_eq!(pool(0, u64::MAX, u64::MAX).onboarding_bonus(1_000_000, u64::MAX), 0);
+        }
+
+        #[test]
+        fn test_bonus_by_rate() {
+            // 5% of 1_000_000
+            assert_eq!(pool(500, u64::MAX, u64::MAX).onboarding_bonus(1_000_000, u64::MAX), 50_000);
+        }
+
+        #[test]
+        fn test_bonus_capped_by_cap_budget_and_pool() {
+            let amount = 1_000_000;
+            assert_eq!(pool(500, 10_000, u64::MAX).onboarding_bonus(amount, u64::MAX), 10_000, "Per-user cap");
+            assert_eq!(pool(500, u64::MAX, 7_000).onboarding_bonus(amount, u64::MAX), 7_000, "Global budget");
+            assert_eq!(pool(500, u64::MAX, u64::MAX).onboarding_bonus(amount, 3_000), 3_000, "Pool balance");
+            assert_eq!(pool(500, u64::MAX, 0).onboarding_bonus(amount, u64::MAX), 0, "Budget exhausted");
+        }
+
+        #[test]
+        fn test_queued_bonus_parameters() {
+            use crate::{
+                ADMIN_OP_ONBOARDING_BONUS_BPS, ADMIN_OP_ONBOARDING_BONUS_BUDGET, ADMIN_OP_ONBOARDING_BONUS_CAP,
+                MAX_ONBOARDING_BONUS_BPS, MAX_ONBOARDING_BONUS_BUDGET, MAX_ONBOARDING_BONUS_CAP,
+            };
+            let mut p = pool(0, 0, 0);
+            assert!(p.apply_parameter(ADMIN_OP_ONBOARDING_BONUS_BPS, MAX_ONBOARDING_BONUS_BPS as u64 + 1).is_err());
+            assert!(p.apply_parameter(ADMIN_OP_ONBOARDING_BONUS_CAP, MAX_ONBOARDING_BONUS_CAP + 1).is_err());
+            assert!(p.apply_parameter(ADMIN_OP_ONBOARDING_BONUS_BUDGET, MAX_ONBOARDING_BONUS_BUDGET + 1).is_err());
+            p.apply_parameter(ADMIN_OP_ONBOARDING_BONUS_BPS, 500).unwrap();
+            p.apply_parameter(ADMIN_OP_ONBOARDING_BONUS_CAP, 10_000).unwrap();
+            assert_eq!(p.apply_parameter(ADMIN_OP_ONBOARDING_BONUS_BUDGET, 7_000).unwrap(), 0);
+            assert_eq!((p.bonus_bps, p.bonus_cap, p.bonus_budget), (500, 10_000, 7_000));
+        }
+    }
+
+    // ========================================================================
+    // 34. ACCOUNT CLOSING TESTS
+    // ========================================================================
+
+    mod close_account_tests {
+        use crate::state::TokenStats;
+        use anchor_lang::prelude::Pubkey;
+        use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+
+        fn stats(mint: Pubkey, dat_token_account: Pubkey) -> TokenStats {
+            TokenStats {
+                mint,
+                dat_token_account,
+                ..super::token_stats_fixture()
+            }
+        }
+
+        #[test]
+        fn test_active_account_defaults_to_authority_ata() {
+            let (mint, authority) = (Pubkey::new_unique(), Pubkey::new_unique());
+            let token_program = anchor_spl::token::ID;
+            let ata = get_associated_token_address_with_program_id(&authority, &mint, &token_program);
+            assert_eq!(stats(mint, Pubkey::default()).active_token_account(&authority, &token_program), ata);
+        }
+
+        #[test]
+        fn test_active_account_follows_rotation() {
+            let (mint, authority, rotated) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+            assert_eq!(
+                stats(mint, rotated).active_token_account(&authority, &anchor_spl::token_2022::ID),
+                rotated
+            );
+        }
+
+        #[test]
+        fn test_ata_depends_on_token_program() {
+            let (mint, authority) = (Pubkey::new_unique(), Pubkey::new_unique());
+            let s = stats(mint, Pubkey::default());
+            assert_ne!(
+                s.active_token_account(&authority, &anchor_spl::token::ID),
+                s.active_token_account(&authority, &anchor_spl::token_2022::ID)
+            );
+        }
+
+        #[test]
+        fn test_buy_into_rotated_out_ata_rejected() {
+            let (mint, authority, rotated) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+            let ata = get_associated_token_address_with_program_id(&authority, &mint, &anchor_spl::token::ID);
+            assert!(stats(mint, Pubkey::default()).accepts_token_account(&ata), "ATA in use until rotated");
+            let s = stats(mint, rotated);
+            assert!(!s.accepts_token_account(&ata), "Rotated-out ATA must not receive buys");
+            assert!(s.accepts_token_account(&rotated));
+        }
+    }
+
+    // ========================================================================
+    // 35. STATS PAYER REFUND TESTS
+    // ========================================================================
+
+    mod stats_payer_refund_tests {
+        use crate::state::TokenStats;
+        use crate::{PAYER_REFUND_RESERVE, RENT_EXEMPT_MINIMUM};
+        use anchor_lang::prelude::Pubkey;
+
+        fn stats(stats_payer: Pubkey, payer_refunded: bool) -> TokenStats {
+            TokenStats {
+                stats_payer,
+                payer_refunded: payer_refunded as u8,
+                ..super::token_stats_fixture()
+            }
+        }
+
+        #[test]
+        fn test_refund_due_once() {
+            let payer = Pubkey::new_unique();
+            assert!(stats(payer, false).payer_refund_due());
+            assert!(!stats(payer, true).payer_refund_due(), "Already refunded");
+        }
+
+        #[test]
+        fn test_migrated_accounts_not_refunded() {
+            // Migrated accounts have no recorded payer
+            assert!(!stats(Pubkey::default(), false).payer_refund_due());
+        }
+
+        #[test]
+        fn test_refund_keeps_dat_authority_rent_exempt() {
+            const { assert!(PAYER_REFUND_RESERVE > RENT_EXEMPT_MINIMUM) };
+        }
+    }
+
+    // ========================================================================
+    // 36. VENUE SELECTION TESTS
+    // ========================================================================
+
+    mod venue_selection_tests {
+        use crate::{
+            best_venue_quote, parse_pumpswap_pool, quote_bonding_curve, quote_pumpswap_pool,
+            PumpSwapPoolInfo, VenueQuote, VENUE_BONDING_CURVE, VENUE_PUMPSWAP,
+        };
+        use anchor_lang::prelude::Pubkey;
+
+        fn curve_data(virtual_token: u64, virtual_sol: u64, complete: bool) -> Vec<u8> {
+            let mut data = vec![0u8; 49];
+            data[8..16].copy_from_slice(&virtual_token.to_le_bytes());
+            data[16..24].copy_from_slice(&virtual_sol.to_le_bytes());
+            data[48] = complete as u8;
+            data
+        }
+
+        fn quote(venue: u8, tokens_out: u64) -> VenueQuote {
+            VenueQuote { venue, account: Pubkey::new_unique(), tokens_out }
+        }
+
+        #[test]
+        fn test_bonding_curve_quote() {
+            let curve = Pubkey::new_unique();
+            let q = quote_bonding_curve(curve, &curve_data(1_000_000_000, 30_000_000_000, false), 1_000_000_000)
+                .unwrap()
+                .unwrap();
+            assert_eq!(q.venue, VENUE_BONDING_CURVE);
+            assert_eq!(q.account, curve);
+            // 1e9 * 1e9 / (30e9 + 1e9)
+            assert_eq!(q.tokens_out, 32_258_064);
+        }
+
+        #[test]
+        fn test_completed_curve_not_quoted() {
+            let data = curve_data(1_000_000_000, 30_000_000_000, true);
+            assert!(quote_bonding_curve(Pubkey::new_unique(), &data, 1_000_000_000).unwrap().is_none());
+        }
+
+        #[test]
+        fn test_pool_layout_offsets() {
+            let (base_mint, base_vault, quote_vault) =
+                (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+            let quote_mint = Pubkey::new_unique();
+            let mut data = vec![0u8; 211];
+            data[43..75].copy_from_slice(base_mint.as_ref());
+            data[75..107].copy_from_slice(quote_mint.as_ref());
+            data[139..171].copy_from_slice(base_vault.as_ref());
+            data[171..203].copy_from_slice(quote_vault.as_ref());
+
+            let info = parse_pumpswap_pool(&data).unwrap();
+            assert_eq!(info.base_mint, base_mint);
+            assert_eq!(info.quote_mint, quote_mint);
+            assert_eq!(info.pool_base_token_account, base_vault);
+            assert_eq!(info.pool_quote_token_account, quote_vault);
+            assert!(parse_pumpswap_pool(&data[..202]).is_err(), "Truncated pool rejected");
+        }
+
+        #[test]
+        fn test_pool_coin_creator_offset() {
+            use crate::pumpswap_pool_coin_creator;
+
+            let coin_creator = Pubkey::new_unique();
+            let mut data = vec![0u8; 243];
+            data[211..243].copy_from_slice(coin_creator.as_ref());
+            assert_eq!(pumpswap_pool_coin_creator(&data).unwrap(), coin_creator);
+            assert!(pumpswap_pool_coin_creator(&data[..242]).is_err(), "Truncated pool rejected");
+        }
+
+        #[test]
+        fn test_migrated_curve_creator() {
+            use crate::migrated_curve_creator;
+
+            let creator = Pubkey::new_unique();
+            let mut data = curve_data(0, 0, true);
+            data.extend_from_slice(creator.as_ref());
+            assert_eq!(migrated_curve_creator(&data).unwrap(), creator);
+
+            data[48] = 0;
+            assert!(migrated_curve_creator(&data).is_err(), "Live curve has no AMM pool yet");
+            assert!(migrated_curve_creator(&curve_data(0, 0, true)).is_err(), "Pre-creator layout rejected");
+        }
+
+        #[test]
+        fn test_live_curve_creator_for_onboarding() {
+            use crate::bonding_curve_creator;
+
+            let creator = Pubkey::new_unique();
+            let mut data = curve_data(1_000_000_000, 30_000_000_000, false);
+            data.extend_from_slice(creator.as_ref());
+            assert_eq!(bonding_curve_creator(&data).unwrap(), (creator, false));
+
+            data[48] = 1;
+            assert_eq!(bonding_curve_creator(&data).unwrap(), (creator, true), "Migrated curves cannot be onboarded");
+        }
+
+        #[test]
+        fn test_pool_account_consistency() {
+            let info = PumpSwapPoolInfo {
+                base_mint: Pubkey::new_unique(),
+                quote_mint: Pubkey::new_unique(),
+                pool_base_token_account: Pubkey::new_unique(),
+                pool_quote_token_account: Pubkey::new_unique(),
+            };
+            let (b, q, bv, qv) = (info.base_mint, info.quote_mint, info.pool_base_token_account, info.pool_quote_token_account);
+            let other = Pubkey::new_unique();
+
+            assert!(info.check_accounts(&b, &q, &bv, &qv).is_ok());
+            assert!(info.check_accounts(&other, &q, &bv, &qv).is_err(), "Wrong base mint");
+            assert!(info.check_accounts(&b, &other, &bv, &qv).is_err(), "Wrong quote mint");
+            assert!(info.check_accounts(&b, &q, &other, &qv).is_err(), "Wrong base vault");
+            assert!(info.check_accounts(&b, &q, &bv, &other).is_err(), "Wrong quote vault");
+            assert!(info.check_accounts(&b, &q, &qv, &bv).is_err(), "Swapped vaults");
+        }
+
+        #[test]
+        fn test_deeper_pool_wins() {
+            let supply = 1_000_000_000_000_000;
+            let shallow = quote_pumpswap_pool(Pubkey::new_unique(), 100_000_000_000_000, 50_000_000_000, supply, 1_000_000_000).unwrap();
+            let deep = quote_pumpswap_pool(Pubkey::new_unique(), 400_000_000_000_000, 200_000_000_000, supply, 1_000_000_000).unwrap();
+            assert_eq!(deep.venue, VENUE_PUMPSWAP);
+            assert!(deep.tokens_out > shallow.tokens_out);
+            assert_eq!(best_venue_quote(&[shallow, deep]), Some(deep));
+        }
+
+        #[test]
+        fn test_best_venue_ties_keep_first() {
+            let curve = quote(VENUE_BONDING_CURVE, 500);
+            let pool = quote(VENUE_PUMPSWAP, 500);
+            assert_eq!(best_venue_quote(&[curve, pool]), Some(curve));
+            assert_eq!(best_venue_quote(&[]), None);
+        }
+    }
+
+    // ========================================================================
+    // 37. CYCLE STATE MACHINE TESTS
+    // ========================================================================
+
+    mod cycle_state_tests {
+        use crate::state::{CyclePhase, CycleState};
+        use crate::CYCLE_ABORT_TIMEOUT;
+        use anchor_lang::prelude::Pubkey;
+
+        fn cycle(phase: CyclePhase, phase_updated_at: i64) -> CycleState {
+            CycleState {
+                mint: Pubkey::new_unique(),
+                phase,
+                sequence: 0,
+                phase_updated_at,
+                phase_slot: 0,
+                aborted_cycles: 0,
+                bump: 255,
+                rent_exempt_reserve: 0,
+                ata_rent_reserve: 0,
+                buy_venue: 0,
+                dev_fee_paid_sequence: 0,
+                liquidity_sequence: 0,
+            }
+        }
+
+        #[test]
+        fn test_cycle_state_size() {
+            assert_eq!(CycleState::LEN, 95);
+        }
+
+        #[test]
+        fn test_dev_fee_paid_once_per_cycle() {
+            let mut c = cycle(CyclePhase::Idle, 0);
+            assert!(c.mark_dev_fee_paid().is_err(), "No cycle yet");
+            c.begin_collect(10, 1).unwrap();
+            c.mark_dev_fee_paid().unwrap();
+            assert!(c.mark_dev_fee_paid().is_err(), "Repeat within the same cycle");
+            c.begin_buy(false, 0, 11, 2).unwrap();
+            c.complete_burn(12, 3).unwrap();
+            c.begin_collect(13, 4).unwrap();
+            c.mark_dev_fee_paid().unwrap();
+            assert_eq!(c.dev_fee_paid_sequence, 2);
+        }
+
+        #[test]
+        fn test_between_cycles_only_idle_or_burned() {
+            assert!(cycle(CyclePhase::Idle, 0).is_between_cycles());
+            assert!(cycle(CyclePhase::Burned, 0).is_between_cycles());
+            assert!(!cycle(CyclePhase::Collected, 0).is_between_cycles());
+            assert!(!cycle(CyclePhase::Bought, 0).is_between_cycles());
+        }
+
+        #[test]
+        fn test_reserves_follow_rent_sysvar() {
+            use crate::{ATA_RENT_RESERVE, RENT_EXEMPT_MINIMUM};
+            use anchor_lang::prelude::Rent;
+
+            let mut c = cycle(CyclePhase::Idle, 0);
+            assert_eq!((c.rent_reserve(), c.ata_reserve()), (RENT_EXEMPT_MINIMUM, ATA_RENT_RESERVE), "Static until read");
+
+            // Current mainnet parameters reproduce the static reserve
+            c.refresh_reserves(&Rent::default());
+            assert_eq!(c.rent_reserve(), RENT_EXEMPT_MINIMUM);
+            assert!(c.ata_reserve() <= ATA_RENT_RESERVE);
+
+            // A doubled rent rate is picked up without a program upgrade
+            let doubled = Rent { lamports_per_byte_year: Rent::default().lamports_per_byte_year * 2, ..Rent::default() };
+            c.refresh_reserves(&doubled);
+            assert_eq!(c.rent_reserve(), RENT_EXEMPT_MINIMUM * 2);
+        }
+
+        #[test]
+        fn test_full_cycle() {
+            let mut c = cycle(CyclePhase::Idle, 0);
+            c.begin_collect(10, 1).unwrap();
+            assert_eq!((c.phase, c.sequence), (CyclePhase::Collected, 1));
+            c.begin_collect(11, 2).unwrap();
+            assert_eq!(c.sequence, 1, "Repeated collects join the open cycle");
+            c.begin_buy(false, crate::VENUE_PUMPSWAP, 12, 3).unwrap();
+            assert_eq!(c.buy_venue, crate::VENUE_PUMPSWAP);
+            c.complete_burn(13, 4).unwrap();
+            assert_eq!((c.phase, c.phase_updated_at, c.phase_slot), (CyclePhase::Burned, 13, 4));
+            c.begin_collect(14, 5).unwrap();
+            assert_eq!(c.sequence, 2);
+        }
+
+        #[test]
+        fn test_buy_requires_collect() {
+            assert!(cycle(CyclePhase::Idle, 0).begin_buy(false, 0, 1, 1).is_err());
+            assert!(cycle(CyclePhase::Burned, 0).begin_buy(false, 0, 1, 1).is_err());
+            assert!(cycle(CyclePhase::Bought, 0).begin_buy(false, 0, 1, 1).is_err(), "Burn cannot be skipped");
+            assert!(cycle(CyclePhase::Burned, 0).begin_buy(true, 0, 1, 1).is_ok(), "DCA continuation");
+        }
+
+        #[test]
+        fn test_burn_requires_buy() {
+            assert!(cycle(CyclePhase::Collected, 0).complete_burn(1, 1).is_err());
+            assert!(cycle(CyclePhase::Idle, 0).complete_burn(1, 1).is_err());
+        }
+
+        #[test]
+        fn test_collect_blocked_while_burn_pending() {
+            assert!(cycle(CyclePhase::Bought, 0).begin_collect(1, 1).is_err());
+        }
+
+        #[test]
+        fn test_abort_after_timeout_only() {
+            let mut c = cycle(CyclePhase::Bought, 1_000);
+            assert!(c.abort(1_000 + CYCLE_ABORT_TIMEOUT - 1, 1, CYCLE_ABORT_TIMEOUT).is_err());
+            assert_eq!(c.abort(1_000 + CYCLE_ABORT_TIMEOUT, 1, CYCLE_ABORT_TIMEOUT).unwrap(), CyclePhase::Bought);
+            assert_eq!((c.phase, c.aborted_cycles), (CyclePhase::Idle, 1));
+            assert!(cycle(CyclePhase::Burned, 0).abort(i64::MAX, 1, CYCLE_ABORT_TIMEOUT).is_err(), "Nothing to abort");
+        }
+    }
+
+    // ========================================================================
+    // 38. CYCLE LOCK TESTS
+    // ========================================================================
+
+    mod cycle_lock_tests {
+        use crate::migrations::{apply_steps, dat_state_cycle_lock_offset, AccountKind, DatStateV18, MigrationEnv};
+        use crate::state::DATState;
+        use anchor_lang::prelude::Pubkey;
+        use anchor_lang::{AnchorSerialize, Discriminator};
+
+        fn state(root_token_mint: Option<Pubkey>, pending_admin: Option<Pubkey>, cycle_lock: Option<Pubkey>) -> DATState {
+            DATState {
+                admin: Pubkey::new_unique(),
+                asdf_mint: Pubkey::new_unique(),
+                wsol_mint: Pubkey::new_unique(),
+                pool_address: Pubkey::new_unique(),
+                pump_swap_program: Pubkey::new_unique(),
+                total_burned: 0,
+                total_sol_collected: 0,
+                total_buybacks: 0,
+                failed_cycles: 0,
+                consecutive_failures: 0,
+                is_active: 1,
+                emergency_pause: 0,
+                last_cycle_timestamp: 0,
+                initialized_at: 0,
+                last_am_execution: 0,
+                last_pm_execution: 0,
+                last_cycle_sol: 0,
+                last_cycle_burned: 0,
+                min_fees_threshold: 0,
+                max_fees_per_cycle: 0,
+                slippage_bps: 500,
+                min_cycle_interval: 60,
+                dat_authority_bump: 255,
+                current_fee_recipient_index: 0,
+                last_known_price: 0,
+                pending_burn_amount: 0,
+                root_token_mint: root_token_mint.unwrap_or_default(),
+                fee_split_bps: 5520,
+                last_sol_sent_to_root: 0,
+                pending_admin: pending_admin.unwrap_or_default(),
+                pending_fee_split: 0,
+                pending_fee_split_timestamp: 0,
+                admin_operation_cooldown: 3600,
+                last_direct_fee_split_timestamp: 0,
+                max_price_deviation_bps: 0,
+                cycle_lock: cycle_lock.unwrap_or_default(),
+                governance_epoch: 0,
+                governance_epoch_started_at: 0,
+                last_upgrade_slot: 0,
+                event_sequence: 0,
+                auto_resume_after: 0,
+                auto_paused_at: 0,
+                failure_window_start: 0,
+                failing_tokens_in_window: 0,
+                auto_pause_failure_threshold: crate::DEFAULT_AUTO_PAUSE_FAILURE_THRESHOLD,
+                root_escrow_lamports: 0,
+                dev_fee_bps: crate::DEV_FEE_BPS,
+                pending_dev_fee_bps: 0,
+                pending_dev_fee_timestamp: 0,
+                dev_wallet: crate::DEV_WALLET,
+                pending_dev_wallet: Pubkey::default(),
+                reserve_bps: 0,
+                insurance_bps: 0,
+                randomized_timing: 0,
+                max_jito_tip_lamports: 0,
+                cycle_lut: Pubkey::default(),
+                version: 0,
+                pending_import_hash: [0; 32],
+                _reserved: [0; 11],
+            }
+        }
+
+        /// Last Borsh layout, for the steps that upgrade to it
+        fn legacy_state(root_token_mint: Option<Pubkey>, pending_admin: Option<Pubkey>, cycle_lock: Option<Pubkey>) -> DatStateV18 {
+            DatStateV18 {
+                admin: Pubkey::new_unique(),
+                asdf_mint: Pubkey::new_unique(),
+                wsol_mint: Pubkey::new_unique(),
+                pool_address: Pubkey::new_unique(),
+                pump_swap_program: Pubkey::new_unique(),
+                total_burned: 0,
+                total_sol_collected: 0,
+                total_buybacks: 0,
+                failed_cycles: 0,
+                consecutive_failures: 0,
+                is_active: true,
+                emergency_pause: false,
+                last_cycle_timestamp: 0,
+                initialized_at: 0,
+                last_am_execution: 0,
+                last_pm_execution: 0,
+                last_cycle_sol: 0,
+                last_cycle_burned: 0,
+                min_fees_threshold: 0,
+                max_fees_per_cycle: 0,
+                slippage_bps: 500,
+                min_cycle_interval: 60,
+                dat_authority_bump: 255,
+                current_fee_recipient_index: 0,
+                last_known_price: 0,
+                pending_burn_amount: 0,
+                root_token_mint,
+                fee_split_bps: 5520,
+                last_sol_sent_to_root: 0,
+                pending_admin,
+                pending_fee_split: None,
+                pending_fee_split_timestamp: 0,
+                admin_operation_cooldown: 3600,
+                last_direct_fee_split_timestamp: 0,
+                max_price_deviation_bps: 0,
+                cycle_lock,
+                governance_epoch: 0,
+                governance_epoch_started_at: 0,
+                last_upgrade_slot: 0,
+                event_sequence: 0,
+                auto_resume_after: 0,
+                auto_paused_at: 0,
+                failure_window_start: 0,
+                failing_tokens_in_window: 0,
+                auto_pause_failure_threshold: crate::DEFAULT_AUTO_PAUSE_FAILURE_THRESHOLD,
+                root_escrow_lamports: 0,
+                dev_fee_bps: crate::DEV_FEE_BPS,
+                pending_dev_fee_bps: 0,
+                pending_dev_fee_timestamp: 0,
+                dev_wallet: crate::DEV_WALLET,
+                pending_dev_wallet: Pubkey::default(),
+                reserve_bps: 0,
+                insurance_bps: 0,
+                randomized_timing: false,
+                max_jito_tip_lamports: 0,
+                cycle_lut: Pubkey::default(),
+                version: 0,
+                pending_import_hash: [0; 32],
+            }
+        }
+
+        fn serialized(s: &DatStateV18) -> Vec<u8> {
+            let mut data = DATState::DISCRIMINATOR.to_vec();
+            s.serialize(&mut data).unwrap();
+            data.resize(8 + DATState::LEN, 0);
+            data
+        }
+
+        #[test]
+        fn test_lock_is_exclusive_and_reentrant() {
+            let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+            let mut s = state(None, None, None);
+            s.acquire_cycle_lock(a).unwrap();
+            s.acquire_cycle_lock(a).unwrap();
+            assert!(s.acquire_cycle_lock(b).is_err(), "Other mint blocked while a holds the lock");
+            assert!(s.release_cycle_lock(b).is_err());
+            s.release_cycle_lock(a).unwrap();
+            assert_eq!(s.cycle_lock(), None);
+            s.acquire_cycle_lock(b).unwrap();
+        }
+
+        #[test]
+        fn test_release_requires_lock() {
+            assert!(state(None, None, None).release_cycle_lock(Pubkey::new_unique()).is_err());
+        }
+
+        #[test]
+        fn test_finalize_frees_lock_for_next_mint_collect() {
+            let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+            let mut s = state(None, None, None);
+            s.acquire_cycle_lock(a).unwrap(); // a: collect_fees (ecosystem mode), then deferred
+            assert!(!s.collect_allowed(&b, 0, false));
+            assert!(!s.release_idle_cycle_lock(b), "Only the holder's finalization releases");
+
+            assert!(s.release_idle_cycle_lock(a)); // a: finalize_allocated_cycle
+            assert!(s.collect_allowed(&b, 0, false));
+            s.acquire_cycle_lock(b).unwrap(); // b: collect_fees
+            assert!(!s.release_idle_cycle_lock(a), "Lock no longer a's");
+        }
+
+        #[test]
+        fn test_pending_burn_keeps_lock() {
+            let a = Pubkey::new_unique();
+            let mut s = state(None, None, Some(a));
+            s.pending_burn_amount = 1_000;
+            assert!(!s.release_idle_cycle_lock(a), "burn_and_update releases it");
+            assert_eq!(s.cycle_lock(), Some(a));
+        }
+
+        #[test]
+        fn test_cycles_stale_for_wsol_recovery() {
+            let timeout = crate::CYCLE_ABORT_TIMEOUT;
+            let mut s = state(None, None, None);
+            s.last_cycle_timestamp = 10_000;
+            assert!(s.require_cycles_stale(10_000 + timeout - 1, timeout).is_err(), "Recent cycle");
+            s.require_cycles_stale(10_000 + timeout, timeout).unwrap();
+
+            s.cycle_lock = Pubkey::new_unique();
+            assert!(s.require_cycles_stale(i64::MAX, timeout).is_err(), "Cycle in flight");
+        }
+
+        #[test]
+        fn test_auto_pause_and_cool_off_resume() {
+            let mut s = state(None, None, None);
+            s.is_active = 1;
+            for _ in 1..crate::DEFAULT_AUTO_PAUSE_FAILURE_THRESHOLD {
+                assert!(!s.record_cycle_failure(100));
+            }
+            assert!(s.record_cycle_failure(500), "Threshold trips the pause");
+            assert_eq!((s.emergency_pause, s.auto_paused_at), (1, 500));
+            assert!(!s.record_cycle_failure(600), "Already paused: stamp kept");
+            assert_eq!(s.auto_paused_at, 500);
+
+            assert!(s.require_auto_resumable(i64::MAX).is_err(), "Disabled by default");
+            s.auto_resume_after = 3_600;
+            assert!(s.require_auto_resumable(500 + 3_599).is_err(), "Cool-off running");
+            s.require_auto_resumable(500 + 3_600).unwrap();
+
+            s.auto_paused_at = 0;
+            assert!(s.require_auto_resumable(i64::MAX).is_err(), "Manual pause never auto-resumes");
+        }
+
+        #[test]
+        fn test_configured_failure_threshold() {
+            let mut s = state(None, None, None);
+            s.auto_pause_failure_threshold = crate::MIN_AUTO_PAUSE_FAILURE_THRESHOLD;
+            assert!(!s.record_cycle_failure(100));
+            assert!(s.record_cycle_failure(200), "Tightened threshold trips on the second failure");
+        }
+
+        #[test]
+        fn test_apply_parameter_bounds() {
+            use crate::{ADMIN_OP_DEV_FEE, ADMIN_OP_MAX_FEES, ADMIN_OP_MIN_FEES, ADMIN_OP_SLIPPAGE};
+            let mut s = state(None, None, None);
+            assert_eq!(s.apply_parameter(ADMIN_OP_SLIPPAGE, 300).unwrap(), 500);
+            assert!(s.apply_parameter(ADMIN_OP_SLIPPAGE, 501).is_err());
+            assert!(s.apply_parameter(ADMIN_OP_DEV_FEE, crate::MAX_DEV_FEE_BPS as u64 + 1).is_err());
+            assert!(s.apply_parameter(crate::ADMIN_OP_RESERVE_BPS, crate::MAX_RESERVE_BPS as u64 + 1).is_err());
+            assert!(s.apply_parameter(crate::ADMIN_OP_INSURANCE_BPS, crate::MAX_INSURANCE_BPS as u64 + 1).is_err());
+            assert!(s.apply_parameter(crate::ADMIN_OP_RANDOMIZED_TIMING, 2).is_err(), "Boolean parameter");
+            assert!(s.apply_parameter(crate::ADMIN_OP_MAX_JITO_TIP, crate::MAX_JITO_TIP_LAMPORTS + 1).is_err());
+            assert!(s.apply_parameter(ADMIN_OP_MIN_FEES, 1_000_000).is_err(), "min above max");
+            assert_eq!(s.min_fees_threshold, 0, "Rejected change left state untouched");
+            s.apply_parameter(ADMIN_OP_MAX_FEES, 10_000_000).unwrap();
+            assert_eq!(s.apply_parameter(ADMIN_OP_MIN_FEES, 1_000_000).unwrap(), 0);
+            assert!(s.apply_parameter(crate::ADMIN_OP_PAUSE, 1).is_err(), "Not a queueable parameter");
+            assert!(s.apply_parameter(crate::ADMIN_OP_FEE_SPLIT, 9001).is_err());
+            assert_eq!(s.apply_parameter(crate::ADMIN_OP_FEE_SPLIT, 6000).unwrap(), 5520);
+            assert!(s.apply_parameter(crate::ADMIN_OP_BURN_RATIO, 8000).is_err(), "TokenStats parameter");
+        }
+
+        #[test]
+        fn test_price_guard_queued_and_import_not_executable() {
+            use crate::{ADMIN_OP_IMPORT_CONFIG, ADMIN_OP_PRICE_GUARD, ADMIN_OP_PRICE_OBSERVATION_RESET};
+            let mut s = state(None, None, None);
+            (s.max_price_deviation_bps, s.last_known_price) = (3000, 42);
+            assert!(s.apply_parameter(ADMIN_OP_PRICE_GUARD, 10_001).is_err());
+            assert_eq!(s.apply_parameter(ADMIN_OP_PRICE_GUARD, 0).unwrap(), 3000, "0 disables the guard");
+            assert!(s.apply_parameter(ADMIN_OP_PRICE_OBSERVATION_RESET, 0).is_err(), "Reset carries value 1");
+            assert_eq!(s.apply_parameter(ADMIN_OP_PRICE_OBSERVATION_RESET, 1).unwrap(), 42);
+            assert_eq!((s.max_price_deviation_bps, s.last_known_price), (0, 0));
+            // Imports only run through import_config with the queued blob hash
+            assert!(s.apply_parameter(ADMIN_OP_IMPORT_CONFIG, 1).is_err());
+        }
+
+        #[test]
+        fn test_reserve_cut() {
+            let mut s = state(None, None, None);
+            assert_eq!(s.reserve_cut(1_000_000_000), 0, "Disabled by default");
+            s.apply_parameter(crate::ADMIN_OP_RESERVE_BPS, 1000).unwrap();
+            assert_eq!(s.reserve_cut(1_000_000_000), 100_000_000);
+            assert_eq!(s.reserve_cut(9), 0, "Rounds down");
+            assert_eq!(s.reserve_cut(u64::MAX), u64::MAX / 10, "No overflow");
+        }
+
+        #[test]
+        fn test_insurance_cut_independent_of_reserve() {
+            let mut s = state(None, None, None);
+            s.apply_parameter(crate::ADMIN_OP_RESERVE_BPS, 1000).unwrap();
+            assert_eq!(s.insurance_cut(1_000_000_000), 0, "Disabled by default");
+            s.apply_parameter(crate::ADMIN_OP_INSURANCE_BPS, 200).unwrap();
+            assert_eq!(s.insurance_cut(1_000_000_000), 20_000_000);
+            assert_eq!(s.reserve_cut(1_000_000_000), 100_000_000);
+        }
+
+        #[test]
+        fn test_check_jito_tip() {
+            let mut s = state(None, None, None);
+            assert_eq!(s.check_jito_tip(None, 1_000_000).unwrap(), 0, "No tip requested");
+            assert!(s.check_jito_tip(Some(1), 1_000_000).is_err(), "Tips disabled by default");
+            s.apply_parameter(crate::ADMIN_OP_MAX_JITO_TIP, 100_000).unwrap();
+            assert_eq!(s.check_jito_tip(Some(100_000), 1_000_000).unwrap(), 100_000);
+            assert!(s.check_jito_tip(Some(100_001), 1_000_000).is_err(), "Above configured max");
+            assert!(s.check_jito_tip(Some(50_000), 49_999).is_err(), "Above spendable balance");
+        }
+
+        #[test]
+        fn test_failure_escalation_needs_distinct_tokens() {
+            let window = crate::FAILURE_ESCALATION_WINDOW;
+            let mut s = state(None, None, None);
+            s.is_active = 1;
+
+            // One flaky token failing repeatedly never pauses everything
+            assert!(!s.record_token_failure(0, 1_000));
+            assert!(!s.record_token_failure(1_000, 1_100));
+            assert!(!s.record_token_failure(1_100, 1_200));
+            assert_eq!((s.failing_tokens_in_window, s.failed_cycles), (1, 3));
+            assert_eq!(s.consecutive_failures, 0, "Global streak untouched");
+
+            // A second token failing after the window closed starts a new window
+            assert!(!s.record_token_failure(0, 1_000 + window));
+            assert_eq!((s.failure_window_start, s.failing_tokens_in_window), (1_000 + window, 1));
+
+            // A distinct token failing within the window escalates
+            assert!(s.record_token_failure(1_200, 1_000 + window + 10));
+            assert_eq!((s.emergency_pause, s.auto_paused_at), (1, 1_000 + window + 10));
+        }
+
+        #[test]
+        fn test_root_escrow_not_spendable() {
+            let mut s = state(None, None, None);
+            assert_eq!(s.spendable_lamports(10_000, 1_000), 9_000);
+            s.root_escrow_lamports = 4_000;
+            assert_eq!(s.spendable_lamports(10_000, 1_000), 5_000);
+            assert_eq!(s.spendable_lamports(3_000, 1_000), 0);
+        }
+
+        #[test]
+        fn test_dev_fee_capped_and_queued() {
+            use crate::ADMIN_OP_DEV_FEE;
+            let mut s = state(None, None, None);
+            assert_eq!(s.dev_fee(1_000_000), 10_000, "1% by default");
+            assert!(s.apply_parameter(ADMIN_OP_DEV_FEE, crate::MAX_DEV_FEE_BPS as u64 + 1).is_err(), "Above the cap");
+            assert_eq!(s.apply_parameter(ADMIN_OP_DEV_FEE, crate::MAX_DEV_FEE_BPS as u64).unwrap(), crate::DEV_FEE_BPS as u64);
+            assert_eq!(s.dev_fee(1_000_000), 20_000);
+
+            // A governance epoch drops a pending dev wallet proposal
+            s.pending_dev_wallet = Pubkey::new_unique();
+            s.advance_governance_epoch(1, 10_001).unwrap();
+            assert_eq!(s.pending_dev_wallet, Pubkey::default());
+        }
+
+        #[test]
+        fn test_collect_allowed_preview_gates() {
+            let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+            let mut s = state(None, None, Some(a));
+            s.is_active = 1;
+            s.last_cycle_timestamp = 1_000;
+            s.min_cycle_interval = 60;
+            assert_eq!(s.next_cycle_at(), 1_060);
+
+            assert!(s.collect_allowed(&a, 1_060, true), "Lock holder past the interval");
+            assert!(!s.collect_allowed(&a, 1_059, true), "Interval not elapsed");
+            assert!(s.collect_allowed(&a, 1_059, false), "Interval not enforced");
+            assert!(!s.collect_allowed(&b, 1_060, true), "Lock held by another mint");
+
+            s.emergency_pause = 1;
+            assert!(!s.collect_allowed(&a, 1_060, true));
+        }
+
+        #[test]
+        fn test_cycle_lock_offset_matches_serialization() {
+            let mint = Pubkey::new_unique();
+            for (root, admin) in [(None, None), (Some(Pubkey::new_unique()), None), (Some(Pubkey::new_unique()), Some(Pubkey::new_unique()))] {
+                let data = serialized(&legacy_state(root, admin, Some(mint)));
+                let at = dat_state_cycle_lock_offset(&data).unwrap();
+                assert_eq!(data[at], 1, "Some tag");
+                assert_eq!(&data[at + 1..at + 33], mint.as_ref());
+            }
+        }
+
+        #[test]
+        fn test_stale_tail_ignored() {
+            // pending_admin cleared: serialization shrinks, leaving stale bytes at the old tail
+            let mut data = serialized(&legacy_state(None, Some(Pubkey::new_unique()), None));
+            let mut cleared = DATState::DISCRIMINATOR.to_vec();
+            legacy_state(None, None, None).serialize(&mut cleared).unwrap();
+            data[..cleared.len()].copy_from_slice(&cleared);
+
+            // cycle_lock (None) is followed only by governance epoch fields (20), event_sequence (8),
+            // the auto-resume fields (16), the failure escalation fields (9), the threshold (1),
+            // root_escrow_lamports (8), the dev fee / dev wallet fields (76), reserve_bps (2), insurance_bps (2),
+            // randomized_timing (1), max_jito_tip_lamports (8), cycle_lut (32), version (1) and pending_import_hash (32)
+            let at = dat_state_cycle_lock_offset(&data).unwrap();
+            assert_eq!(at, cleared.len() - 1 - 20 - 8 - 16 - 9 - 1 - 8 - 76 - 2 - 2 - 1 - 8 - 32 - 1 - 32, "cycle_lock tag precedes the fixed-size tail");
+        }
+
+        #[test]
+        fn test_v1_upgrade_through_steps() {
+            // V1 ended at admin_operation_cooldown: 382 bytes with every Option set
+            let original = legacy_state(Some(Pubkey::new_unique()), Some(Pubkey::new_unique()), Some(Pubkey::new_unique()));
+            let mut data = serialized(&original);
+            data[382..].fill(0);
+            let steps = apply_steps(AccountKind::DatState, &mut data, 382, &MigrationEnv::default()).unwrap();
+            assert_eq!(steps + 1, crate::DAT_STATE_VERSION as usize);
+
+            let s: DATState = bytemuck::pod_read_unaligned(&data[8..]);
+            assert_eq!((s.admin, s.root_token_mint(), s.pending_admin()), (original.admin, original.root_token_mint, original.pending_admin));
+            assert_eq!(s.cycle_lock(), None, "V1 had no lock");
+            assert_eq!(s.max_price_deviation_bps, crate::DEFAULT_MAX_PRICE_DEVIATION_BPS);
+            assert_eq!(s.dev_wallet, crate::DEV_WALLET);
+            assert_eq!(s.version, crate::DAT_STATE_VERSION);
+        }
+
+        #[test]
+        fn test_v1_stale_tail_upgrade() {
+            // pending_admin None: the V1 data ends 32 bytes early, the rest of the 382 bytes is stale
+            let original = legacy_state(Some(Pubkey::new_unique()), None, None);
+            let mut data = serialized(&original);
+            let v1_end = 382 - 32 - 2; // pending_admin and pending_fee_split None
+            data[v1_end..382].fill(0xAB);
+            data[382..].fill(0);
+            apply_steps(AccountKind::DatState, &mut data, 382, &MigrationEnv::default()).unwrap();
+
+            let s: DATState = bytemuck::pod_read_unaligned(&data[8..]);
+            assert_eq!(s.cycle_lock(), None, "Stale tag cleared");
+            assert_eq!(s.last_direct_fee_split_timestamp, 0);
+            assert_eq!(s.auto_pause_failure_threshold, crate::DEFAULT_AUTO_PAUSE_FAILURE_THRESHOLD);
+            assert_eq!(s.cycle_lut, Pubkey::default());
+            assert_eq!(s.pending_import_hash, [0; 32], "No import queued");
+        }
+
+        #[test]
+        fn test_v18_relaid_out_for_zero_copy() {
+            let (root, lock) = (Pubkey::new_unique(), Pubkey::new_unique());
+            let mut original = legacy_state(Some(root), None, Some(lock));
+            original.pending_fee_split = Some(6000);
+            original.randomized_timing = true;
+            original.total_burned = 900;
+            original.pending_import_hash = [7; 32];
+            original.version = 18;
+            let mut data = serialized(&original);
+            data[641..].fill(0);
+
+            assert_eq!(apply_steps(AccountKind::DatState, &mut data, 641, &MigrationEnv::default()).unwrap(), 1);
+            let s: DATState = bytemuck::pod_read_unaligned(&data[8..]);
+            assert_eq!(&data[8..40], original.admin.as_ref(), "admin first (migrate_dat_state reads data[8..40])");
+            assert_eq!((s.root_token_mint(), s.pending_admin(), s.cycle_lock()), (Some(root), None, Some(lock)));
+            assert_eq!(s.pending_fee_split(), Some(6000));
+            assert_eq!((s.is_active, s.emergency_pause, s.randomized_timing), (1, 0, 1));
+            assert_eq!((s.total_burned, s.slippage_bps, s.dat_authority_bump), (900, 500, 255));
+            assert_eq!(s.pending_import_hash, [7; 32]);
+            assert_eq!(s.version, crate::DAT_STATE_VERSION);
+        }
+    }
+
+    // ========================================================================
+    // 39. TOKEN INFO URI TESTS
+    // ========================================================================
+
+    mod token_info_uri_tests {
+        use crate::state::{TokenConfig, MAX_INFO_URI_LEN};
+        use anchor_lang::prelude::Pubkey;
+
+        fn config(uri: &str) -> TokenConfig {
+            TokenConfig {
+                mint: Pubkey::new_unique(),
+                info_uri: TokenConfig::encode_uri(uri).unwrap(),
+                updated_at: 0,
+                bump: 255,
+                assigned_root: Pubkey::default(),
+                version: 0,
+                original_creator: Pubkey::default(),
+                onboarded_at: 0,
+                offboard_requested_at: 0,
+            }
+        }
+
+        #[test]
+        fn test_token_config_size() {
+            assert_eq!(TokenConfig::LEN, 218);
+        }
+
+        #[test]
+        fn test_uri_round_trip() {
+            let uri = "https://asdf.example/eco/token.json";
+            assert_eq!(config(uri).uri(), uri);
+            assert_eq!(config("").uri(), "", "Empty URI clears the entry");
+        }
+
+        #[test]
+        fn test_uri_length_limit() {
+            let max = "a".repeat(MAX_INFO_URI_LEN);
+            assert_eq!(config(&max).uri(), max, "Full-length URI has no terminator");
+            assert!(TokenConfig::encode_uri(&"a".repeat(MAX_INFO_URI_LEN + 1)).is_none());
+        }
+
+        #[test]
+        fn test_offboard_timelock() {
+            use crate::constants::OFFBOARD_TIMELOCK;
+
+            let mut cfg = config("");
+            assert!(!cfg.is_onboarded(), "Admin-registered tokens cannot be offboarded");
+            assert!(cfg.require_offboard_ready(i64::MAX).is_err(), "No request, no offboard");
+
+            cfg.original_creator = Pubkey::new_unique();
+            cfg.onboarded_at = 1_000;
+            cfg.offboard_requested_at = 10_000;
+            assert!(cfg.is_onboarded());
+            assert!(cfg.require_offboard_ready(10_000 + OFFBOARD_TIMELOCK - 1).is_err());
+            assert!(cfg.require_offboard_ready(10_000 + OFFBOARD_TIMELOCK).is_ok());
+        }
+    }
+
+    // ========================================================================
+    // 40. REBATE PARAMS TESTS
+    // ========================================================================
+
+    mod rebate_params_tests {
+        use crate::constants::*;
+        use crate::state::RebatePool;
+        use anchor_lang::prelude::Pubkey;
+
+        fn pool(rebate_share: u32, rebate_threshold: u64) -> RebatePool {
+            RebatePool {
+                bump: 255,
+                total_deposited: 0,
+                total_distributed: 0,
+                rebates_count: 0,
+                last_rebate_timestamp: 0,
+                last_rebate_slot: 0,
+                unique_recipients: 0,
+                bonus_bps: 0,
+                bonus_cap: 0,
+                bonus_budget: 0,
+                total_bonus_paid: 0,
+                _reserved: [0u8; 6],
+                rebate_share,
+                rebate_threshold,
+                pending_rebate_share: 0,
+                pending_rebate_threshold: 0,
+                pending_params_timestamp: 0,
+                draw_request_slot: 0,
+                draw_target_slot: 0,
+                draw_candidates_hash: [0u8; 32],
+                draw_randomness: [0u8; 32],
+                draw_winner: Pubkey::default(),
+                draws_count: 0,
+                current_epoch: 0,
+                epoch_started_at: 0,
+                epoch_reserved: 0,
+                referral_share_bps: 0,
+                referral_reserved: 0,
+                version: 0,
+            }
+        }
+
+        #[test]
+        fn test_unset_params_use_constant_defaults() {
+            let p = pool(0, 0);
+            assert_eq!(p.rebate_share(), REBATE_SHARE);
+            assert_eq!(p.burn_share(), BURN_SHARE);
+            assert_eq!(p.rebate_threshold(), REBATE_THRESHOLD_SOL_EQUIV);
+        }
+
+        #[test]
+        fn test_configured_shares_sum_to_denominator() {
+            let p = pool(1_000, 500_000_000);
+            assert_eq!(p.rebate_share(), 1_000);
+            assert_eq!(p.burn_share(), 99_000);
+            assert_eq!((p.burn_share() + p.rebate_share()) as u64, SHARE_DENOMINATOR);
+            assert_eq!(p.rebate_threshold(), 500_000_000);
+        }
+
+        #[test]
+        fn test_param_bounds() {
+            assert!(RebatePool::rebate_params_valid(REBATE_SHARE, REBATE_THRESHOLD_SOL_EQUIV));
+            assert!(RebatePool::rebate_params_valid(MAX_REBATE_SHARE, MAX_REBATE_THRESHOLD));
+            assert!(!RebatePool::rebate_params_valid(0, REBATE_THRESHOLD_SOL_EQUIV));
+            assert!(!RebatePool::rebate_params_valid(MAX_REBATE_SHARE + 1, REBATE_THRESHOLD_SOL_EQUIV));
+            assert!(!RebatePool::rebate_params_valid(REBATE_SHARE, MIN_REBATE_THRESHOLD - 1));
+            assert!(!RebatePool::rebate_params_valid(REBATE_SHARE, MAX_REBATE_THRESHOLD + 1));
+        }
+
+        #[test]
+        fn test_rebate_amount_uses_effective_share() {
+            assert_eq!(pool(0, 0).rebate_amount(SHARE_DENOMINATOR).unwrap(), REBATE_SHARE as u64);
+            assert_eq!(pool(1_000, 500_000_000).rebate_amount(1_000_000).unwrap(), 10_000);
+            assert!(pool(0, 0).rebate_amount(u64::MAX).is_err());
+        }
+    }
+
+    // ========================================================================
+    // 41. STATS SNAPSHOT TESTS
+    // ========================================================================
+
+    mod stats_snapshot_tests {
+        use crate::helpers::merkle_node_hash;
+        use crate::state::{StatsLeafProof, StatsSnapshot, StatsSnapshotLeaf};
+        use anchor_lang::prelude::Pubkey;
+
+        fn leaf(burned: u64) -> StatsSnapshotLeaf {
+            StatsSnapshotLeaf {
+                mint: Pubkey::new_unique(),
+                total_burned: burned,
+                total_sol_collected: 5_000_000_000,
+                total_sol_used: 4_900_000_000,
+                total_buybacks: 12,
+                usd_value_burned: 1_000_000,
+            }
+        }
+
+        fn snapshot(root: [u8; 32], token_count: u32) -> StatsSnapshot {
+            StatsSnapshot {
+                day: 20_000,
+                merkle_root: root,
+                token_count,
+                committer: Pubkey::new_unique(),
+                committed_at: 0,
+                bump: 255,
+            }
+        }
+
+        #[test]
+        fn test_stats_snapshot_size() {
+            assert_eq!(StatsSnapshot::LEN, 85);
+        }
+
+        #[test]
+        fn test_inclusion_proof() {
+            let leaves = [leaf(1), leaf(2), leaf(3)];
+            let h: Vec<[u8; 32]> = leaves.iter().map(|l| l.hash()).collect();
+            // Odd level: last node is paired with itself
+            let n01 = merkle_node_hash(&h[0], &h[1]);
+            let n22 = merkle_node_hash(&h[2], &h[2]);
+            let root = merkle_node_hash(&n01, &n22);
+            let snap = snapshot(root, 3);
+
+            let proof = StatsLeafProof { leaf: leaves[1].clone(), index: 1, proof: vec![h[0], n22] };
+            assert!(snap.verify_inclusion(&proof).is_ok());
+
+            let proof = StatsLeafProof { leaf: leaves[2].clone(), index: 2, proof: vec![h[2], n01] };
+            assert!(snap.verify_inclusion(&proof).is_ok());
+        }
+
+        #[test]
+        fn test_changed_stats_rejected() {
+            let leaves = [leaf(1), leaf(2)];
+            let root = merkle_node_hash(&leaves[0].hash(), &leaves[1].hash());
+            let snap = snapshot(root, 2);
+
+            let mut tampered = leaves[0].clone();
+            tampered.total_burned += 1;
+            let proof = StatsLeafProof { leaf: tampered, index: 0, proof: vec![leaves[1].hash()] };
+            assert!(snap.verify_inclusion(&proof).is_err());
+        }
+
+        #[test]
+        fn test_index_beyond_token_count_rejected() {
+            let leaves = [leaf(1), leaf(2)];
+            let root = merkle_node_hash(&leaves[0].hash(), &leaves[1].hash());
+            let proof = StatsLeafProof { leaf: leaves[1].clone(), index: 1, proof: vec![leaves[0].hash()] };
+            assert!(snapshot(root, 1).verify_inclusion(&proof).is_err());
+        }
+    }
+
+    // ========================================================================
+    // 42. REBATE DRAW TESTS
+    // ========================================================================
+
+    mod rebate_draw_tests {
+        use crate::helpers::{draw_candidates_hash, draw_seed, slot_hash_at_or_after, weighted_index};
+        use anchor_lang::prelude::Pubkey;
+
+        /// Raw SlotHashes data, entries newest first
+        fn slot_hashes(slots: &[u64]) -> Vec<u8> {
+            let mut data = (slots.len() as u64).to_le_bytes().to_vec();
+            for slot in slots {
+                data.extend_from_slice(&slot.to_le_bytes());
+                data.extend_from_slice(&[*slot as u8; 32]);
+            }
+            data
+        }
+
+        #[test]
+        fn test_slot_hash_exact_and_skipped_slot() {
+            let data = slot_hashes(&[110, 108, 105, 100]);
+            assert_eq!(slot_hash_at_or_after(&data, 105), Some((105, [105u8; 32])));
+            // Slot 106 skipped: first produced slot after it
+            assert_eq!(slot_hash_at_or_after(&data, 106), Some((108, [108u8; 32])));
+            assert_eq!(slot_hash_at_or_after(&data, 111), None, "Not yet produced");
+        }
+
+        #[test]
+        fn test_slot_hash_possibly_evicted() {
+            // Oldest remaining entry is newer than the target: it may have been evicted
+            let data = slot_hashes(&[110, 108]);
+            assert_eq!(slot_hash_at_or_after(&data, 100), None);
+            assert_eq!(slot_hash_at_or_after(&data, 108), Some((108, [108u8; 32])));
+        }
+
+        #[test]
+        fn test_weighted_index_bounds() {
+            let mut seed = [0u8; 32];
+            assert_eq!(weighted_index(&[0, 0], &seed), None);
+            assert_eq!(weighted_index(&[0, 5, 3], &seed), Some(1), "Zero weights are never picked");
+            seed[..16].copy_from_slice(&7u128.to_le_bytes());
+            assert_eq!(weighted_index(&[0, 5, 3], &seed), Some(2), "7 % 8 falls in the last bucket");
+        }
+
+        #[test]
+        fn test_weighted_index_distribution() {
+            let weights = [1u64, 3];
+            let mut picks = [0u32; 2];
+            for nonce in 0..400u64 {
+                let seed = draw_seed(&[9u8; 32], &[1u8; 32], nonce);
+                picks[weighted_index(&weights, &seed).unwrap()] += 1;
+            }
+            assert!(picks[1] > picks[0] * 2, "Heavier candidate should win ~3x as often: {:?}", picks);
+        }
+
+        #[test]
+        fn test_candidates_hash_is_order_sensitive() {
+            let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+            assert_ne!(draw_candidates_hash(&[a, b]), draw_candidates_hash(&[b, a]));
+            assert_eq!(draw_candidates_hash(&[a, b]), draw_candidates_hash(&[a, b]));
+        }
+    }
+
+    // ========================================================================
+    // 43. REBATE EPOCH TESTS
+    // ========================================================================
+
+    mod rebate_epoch_tests {
+        use crate::state::{RebateEpoch, UserStats};
+        use anchor_lang::prelude::Pubkey;
+
+        fn epoch(total_contributions: u64, pool_amount: u64) -> RebateEpoch {
+            RebateEpoch {
+                epoch: 3,
+                total_contributions,
+                pool_amount,
+                claimed_amount: 0,
+                started_at: 0,
+                closed_at: 1,
+                bump: 255,
+            }
+        }
+
+        #[test]
+        fn test_rebate_epoch_size() {
+            assert_eq!(RebateEpoch::LEN, 49);
+        }
+
+        #[test]
+        fn test_share_is_pro_rata() {
+            let e = epoch(1_000_000, 5_520);
+            assert_eq!(e.share_of(500_000), 2_760, "Half the contributions, half the pool");
+            assert_eq!(e.share_of(1_000), 5, "Small contributors still receive their share");
+            assert_eq!(e.share_of(1_000_000), 5_520);
+        }
+
+        #[test]
+        fn test_shares_never_exceed_pool() {
+            let e = epoch(3, 100);
+            let total: u64 = [1u64, 1, 1].iter().map(|c| e.share_of(*c)).sum();
+            assert_eq!(total, 99, "Floored shares leave dust in the pool");
+            assert_eq!(epoch(0, 100).share_of(0), 0, "Empty epoch pays nothing");
+        }
+
+        #[test]
+        fn test_unclaimed_epoch_detection() {
+            let mut stats = UserStats {
+                bump: 255,
+                user: Pubkey::new_unique(),
+                pending_contribution: 0,
+                total_contributed: 0,
+                total_rebate: 0,
+                last_update_timestamp: 0,
+                last_update_slot: 0,
+                onboarding_bonus_claimed: true,
+                contribution_epoch: 3,
+                epoch_contribution: 0,
+                app_id: 0,
+                referrer: None,
+                version: 0,
+            };
+            assert!(!stats.has_unclaimed_epoch(4), "Nothing contributed");
+            stats.epoch_contribution = 10;
+            assert!(!stats.has_unclaimed_epoch(3), "Current epoch still open");
+            assert!(stats.has_unclaimed_epoch(4), "Closed epoch must be settled before rolling over");
+        }
+    }
+
+    // ========================================================================
+    // 44. PARTNER REVENUE SHARE TESTS
+    // ========================================================================
+
+    mod partner_share_tests {
+        use crate::state::{PartnerStats, TokenStats};
+        use crate::MAX_PARTNER_SHARE_BPS;
+        use anchor_lang::prelude::Pubkey;
+
+        fn stats(partner: Pubkey, partner_share_bps: u16) -> TokenStats {
+            TokenStats {
+                partner,
+                partner_share_bps,
+                ..super::token_stats_fixture()
+            }
+        }
+
+        #[test]
+        fn test_partner_stats_size() {
+            assert_eq!(PartnerStats::LEN, 57);
+        }
+
+        #[test]
+        fn test_partner_cut() {
+            let s = stats(Pubkey::new_unique(), 250);
+            assert_eq!(s.partner_cut(1_000_000_000), 25_000_000, "2.5% of 1 SOL");
+            assert_eq!(s.partner_cut(0), 0);
+        }
+
+        #[test]
+        fn test_no_partner_no_cut() {
+            assert_eq!(stats(Pubkey::default(), 250).partner_cut(1_000_000_000), 0);
+        }
+
+        #[test]
+        fn test_cap_bounds_cut() {
+            let s = stats(Pubkey::new_unique(), MAX_PARTNER_SHARE_BPS);
+            assert_eq!(s.partner_cut(u64::MAX), u64::MAX / 20, "No overflow at u64::MAX (5%)");
+            assert!(s.partner_cut(1_000_000) <= 1_000_000 / 20, "Never more than 5%");
+        }
+
+        #[test]
+        fn test_cut_taken_on_attributed_fees_not_shared_vault() {
+            // One creator vault holds both tokens' fees; only `partnered` has a partner
+            let mut partnered = TokenStats { pending_fees_lamports: 1_000_000_000, ..stats(Pubkey::new_unique(), 250) };
+            let plain = TokenStats { pending_fees_lamports: 3_000_000_000, ..stats(Pubkey::default(), 0) };
+
+            // partnered collects first and drains the whole 4 SOL vault
+            assert_eq!(partnered.partner_cut_due(), 25_000_000, "2.5% of its own 1 SOL, not of 4 SOL");
+            partnered.record_partner_payout(25_000_000);
+            assert_eq!(plain.partner_cut_due(), 0);
+            assert_eq!(partnered.total_partner_paid, 25_000_000);
+        }
+
+        #[test]
+        fn test_ecosystem_collect_pays_each_attributed_fee_once() {
+            let mut s = TokenStats { pending_fees_lamports: 1_000_000_000, ..stats(Pubkey::new_unique(), 250) };
+            s.record_partner_payout(s.partner_cut_due());
+
+            // Pending fees kept (ecosystem mode): a later collect owes nothing on them
+            assert_eq!(s.partner_cut_due(), 0);
+
+            // Newly attributed fees are owed on; settling the allocation keeps the rest paid
+            s.pending_fees_lamports += 400_000_000;
+            assert_eq!(s.partner_cut_due(), 10_000_000);
+            s.settle_pending_fees(1_000_000_000).unwrap();
+            assert_eq!(s.partner_cut_due(), 10_000_000, "Deferred fees still owed once");
+            s.record_partner_payout(s.partner_cut_due());
+            assert_eq!(s.partner_cut_due(), 0);
+
+            s.reset_pending_fees();
+            assert_eq!((s.pending_fees_lamports, s.partner_paid_pending), (0, 0));
+        }
+    }
+
+    // ========================================================================
+    // 45. ALERT THRESHOLD TESTS
+    // ========================================================================
+
+    mod alert_tests {
+        use crate::constants::*;
+        use crate::state::AlertConfig;
+
+        fn config(min_authority_balance: u64, max_consecutive_failures: u8, min_rebate_pool_balance: u64) -> AlertConfig {
+            AlertConfig {
+                min_authority_balance,
+                max_consecutive_failures,
+                min_rebate_pool_balance,
+                updated_at: 0,
+                bump: 255,
+            }
+        }
+
+        #[test]
+        fn test_alert_config_size() {
+            assert_eq!(AlertConfig::LEN, 26);
+        }
+
+        #[test]
+        fn test_all_breaches_reported() {
+            let alerts = config(1_000_000_000, 3, 500).breaches(10, 4, Some(499));
+            assert_eq!(alerts, vec![
+                (ALERT_LOW_AUTHORITY_BALANCE, 10, 1_000_000_000),
+                (ALERT_CONSECUTIVE_FAILURES, 4, 3),
+                (ALERT_LOW_REBATE_POOL, 499, 500),
+            ]);
+        }
+
+        #[test]
+        fn test_healthy_state_raises_nothing() {
+            assert!(config(1_000, 3, 500).breaches(1_000, 3, Some(500)).is_empty());
+        }
+
+        #[test]
+        fn test_zero_threshold_disables_alert() {
+            assert!(config(0, 0, 0).breaches(0, 255, Some(0)).is_empty());
+        }
+
+        #[test]
+        fn test_rebate_pool_alert_skipped_without_account() {
+            assert!(config(0, 0, 500).breaches(0, 0, None).is_empty());
+        }
+
+        #[test]
+        fn test_queued_alert_thresholds() {
+            let mut c = config(0, 0, 0);
+            assert!(c.apply_parameter(ADMIN_OP_ALERT_MAX_CONSECUTIVE_FAILURES, 256).is_err());
+            c.apply_parameter(ADMIN_OP_ALERT_MIN_AUTHORITY_BALANCE, 1_000).unwrap();
+            c.apply_parameter(ADMIN_OP_ALERT_MAX_CONSECUTIVE_FAILURES, 3).unwrap();
+            assert_eq!(c.apply_parameter(ADMIN_OP_ALERT_MIN_REBATE_POOL_BALANCE, 500).unwrap(), 0);
+            assert_eq!(c.breaches(999, 4, Some(499)).len(), 3);
+            assert!(c.apply_parameter(ADMIN_OP_REFERRAL_SHARE, 1).is_err(), "RebatePool parameter");
+        }
+    }
+
+    // ========================================================================
+    // 46. SOL DEPOSIT VALUATION TESTS
+    // ========================================================================
+
+    mod sol_deposit_tests {
+        use crate::{calculate_execution_price, sol_to_token_amount};
+
+        #[test]
+        fn test_sol_to_token_amount_basic() {
+            // 1 lamport per base unit
+            let price = calculate_execution_price(1_000_000, 1_000_000).unwrap();
+            assert_eq!(sol_to_token_amount(100_000_000, price).unwrap(), 100_000_000);
+        }
+
+        #[test]
+        fn test_sol_to_token_amount_inverts_execution_price() {
+            let price = calculate_execution_price(1_000_000_000, 250_000_000_000).unwrap();
+            assert_eq!(sol_to_token_amount(1_000_000_000, price).unwrap(), 250_000_000_000);
+        }
+
+        #[test]
+        fn test_sol_to_token_amount_requires_price() {
+            assert!(sol_to_token_amount(1_000_000_000, 0).is_err());
+        }
+
+        #[test]
+        fn test_sol_to_token_amount_overflow() {
+            assert!(sol_to_token_amount(u64::MAX, 1).is_err());
+        }
+    }
+
+    // ========================================================================
+    // 47. AMM ON-CHAIN QUOTE TESTS
+    // ========================================================================
+
+    mod amm_quote_tests {
+        use crate::{calculate_amm_buy_amount_and_slippage, calculate_tokens_out};
+
+        const BASE: u64 = 200_000_000_000_000;
+        const QUOTE: u64 = 100_000_000_000; // 100 SOL
+        const SUPPLY: u64 = 1_000_000_000_000_000;
+
+        #[test]
+        fn test_amm_quote_applies_slippage() {
+            let (sol, tokens, price) = calculate_amm_buy_amount_and_slippage(
+                500_000_000, BASE, QUOTE, SUPPLY, 10_000_000_000, 500, 0, 0,
+            ).unwrap();
+            let expected = calculate_tokens_out(500_000_000, QUOTE, BASE, SUPPLY).unwrap();
+            assert_eq!(sol, 500_000_000);
+            assert_eq!(tokens, expected * 95 / 100);
+            assert!(price > 0);
+        }
+
+        #[test]
+        fn test_amm_quote_caps_budget() {
+            // max_fees_per_cycle cap
+            let (sol, _, _) = calculate_amm_buy_amount_and_slippage(
+                800_000_000, BASE, QUOTE, SUPPLY, 300_000_000, 500, 0, 0,
+            ).unwrap();
+            assert_eq!(sol, 300_000_000);
+
+            // 1% of quote reserves cap
+            let (sol, _, _) = calculate_amm_buy_amount_and_slippage(
+                5_000_000_000, BASE, QUOTE, SUPPLY, 10_000_000_000, 500, 0, 0,
+            ).unwrap();
+            assert_eq!(sol, QUOTE / 100);
+        }
+
+        #[test]
+        fn test_amm_quote_rejects_thin_pool() {
+            assert!(calculate_amm_buy_amount_and_slippage(
+                100_000_000, BASE, 9_999_999, SUPPLY, 10_000_000_000, 500, 0, 0,
+            ).is_err());
+            assert!(calculate_amm_buy_amount_and_slippage(
+                100_000_000, 0, QUOTE, SUPPLY, 10_000_000_000, 500, 0, 0,
+            ).is_err());
+        }
+
+        #[test]
+        fn test_amm_quote_circuit_breaker() {
+            let (_, _, price) = calculate_amm_buy_amount_and_slippage(
+                500_000_000, BASE, QUOTE, SUPPLY, 10_000_000_000, 500, 0, 0,
+            ).unwrap();
+            assert!(calculate_amm_buy_amount_and_slippage(
+                500_000_000, BASE, QUOTE, SUPPLY, 10_000_000_000, 500, price / 2, 1000,
+            ).is_err());
+            assert!(calculate_amm_buy_amount_and_slippage(
+                500_000_000, BASE, QUOTE, SUPPLY, 10_000_000_000, 500, price, 1000,
+            ).is_ok());
+        }
+    }
+
+    // ========================================================================
+    // 48. TOKEN DEPOSIT ESCROW TESTS
+    // ========================================================================
+
+    mod token_deposit_tests {
+        use crate::constants::*;
+        use crate::state::TokenDeposit;
+        use anchor_lang::prelude::Pubkey;
+
+        fn deposit(amount: u64, deposited_at: i64) -> TokenDeposit {
+            TokenDeposit {
+                user: Pubkey::new_unique(),
+                mint: Pubkey::new_unique(),
+                amount,
+                min_asdf_out: 1_000,
+                deposited_at,
+                bump: 255,
+            }
+        }
+
+        #[test]
+        fn test_token_deposit_size() {
+            assert_eq!(TokenDeposit::LEN, 89);
+        }
+
+        #[test]
+        fn test_refund_unlocks_after_delay() {
+            let d = deposit(500, 1_000);
+            assert!(!d.refund_unlocked(1_000 + TOKEN_DEPOSIT_REFUND_DELAY - 1, TOKEN_DEPOSIT_REFUND_DELAY));
+            assert!(d.refund_unlocked(1_000 + TOKEN_DEPOSIT_REFUND_DELAY, TOKEN_DEPOSIT_REFUND_DELAY));
+        }
+
+        #[test]
+        fn test_clear_empties_escrow() {
+            let mut d = deposit(500, 1_000);
+            d.clear();
+            assert_eq!(d.amount, 0);
+            assert_eq!(d.min_asdf_out, 0);
+            assert_eq!(d.deposited_at, 1_000);
+        }
+    }
+
+    // ========================================================================
+    // 49. APP REGISTRY TESTS
+    // ========================================================================
+
+    mod app_registry_tests {
+        use crate::state::{AppRegistry, AppStats};
+        use anchor_lang::prelude::Pubkey;
+
+        fn app() -> AppStats {
+            AppStats {
+                app_id: 7,
+                authority: Pubkey::new_unique(),
+                fee_share_bps: 250,
+                total_deposited: 0,
+                deposits_count: 0,
+                unique_users: 0,
+                rebates_generated: 0,
+                registered_at: 0,
+                bump: 255,
+            }
+        }
+
+        #[test]
+        fn test_app_account_sizes() {
+            assert_eq!(AppRegistry::LEN, 5);
+            assert_eq!(AppStats::LEN, 79);
+        }
+
+        #[test]
+        fn test_record_deposit_counts_new_users_once() {
+            let mut stats = app();
+            stats.record_deposit(1_000_000, 5_520, true);
+            stats.record_deposit(2_000_000, 11_040, false);
+
+            assert_eq!(stats.total_deposited, 3_000_000);
+            assert_eq!(stats.deposits_count, 2);
+            assert_eq!(stats.rebates_generated, 16_560);
+            assert_eq!(stats.unique_users, 1);
+        }
+
+        #[test]
+        fn test_record_deposit_saturates() {
+            let mut stats = app();
+            stats.total_deposited = u64::MAX;
+            stats.record_deposit(1, 0, false);
+            assert_eq!(stats.total_deposited, u64::MAX);
+        }
+    }
+
+    // ========================================================================
+    // 50. PENDING FEES SETTLEMENT TESTS
+    // ========================================================================
+
+    mod settle_pending_fees_tests {
+        use crate::state::TokenStats;
+
+        fn stats(pending_fees_lamports: u64) -> TokenStats {
+            TokenStats {
+                pending_fees_lamports,
+                ..super::token_stats_fixture()
+            }
+        }
+
+        #[test]
+        fn test_settlement_deducts_exact_amount() {
+            let mut s = stats(1_000_000);
+            assert_eq!(s.settle_pending_fees(400_000).unwrap(), 600_000);
+            assert_eq!(s.settle_pending_fees(600_000).unwrap(), 0);
+        }
+
+        #[test]
+        fn test_fees_attributed_after_snapshot_survive() {
+            let mut s = stats(1_000_000);
+            // Daemon allocated 1 SOL, then 0.2 SOL more was attributed before settlement
+            s.pending_fees_lamports += 200_000;
+            assert_eq!(s.settle_pending_fees(1_000_000).unwrap(), 200_000);
+        }
+
+        #[test]
+        fn test_over_settlement_rejected() {
+            let mut s = stats(500);
+            assert!(s.settle_pending_fees(501).is_err());
+            assert_eq!(s.pending_fees_lamports, 500, "Failed settlement leaves pending untouched");
+        }
+
+        #[test]
+        fn test_targeted_deposit_credits_pending() {
+            let mut s = stats(1_000_000);
+            assert_eq!(s.credit_deposit(994_480).unwrap(), 1_994_480);
+            assert_eq!(s.pending_fees_lamports, 1_994_480);
+        }
+
+        #[test]
+        fn test_targeted_deposit_respects_pending_cap() {
+            let mut s = stats(crate::constants::MAX_PENDING_FEES - 10);
+            assert!(s.credit_deposit(11).is_err());
+            assert_eq!(s.pending_fees_lamports, crate::constants::MAX_PENDING_FEES - 10);
+            assert_eq!(s.credit_deposit(10).unwrap(), crate::constants::MAX_PENDING_FEES);
+        }
+    }
+
+    // ========================================================================
+    // 51. PORTABLE CONFIG TESTS
+    // ========================================================================
+
+    mod portable_config_tests {
+        use crate::state::{DATState, PortableConfig};
+        use anchor_lang::prelude::Pubkey;
+
+        fn state() -> DATState {
+            DATState {
+                admin: Pubkey::new_unique(),
+                asdf_mint: Pubkey::new_unique(),
+                wsol_mint: Pubkey::new_unique(),
+                pool_address: Pubkey::new_unique(),
+                pump_swap_program: Pubkey::new_unique(),
+                total_burned: 1_000,
+                total_sol_collected: 2_000,
+                total_buybacks: 3,
+                failed_cycles: 0,
+                consecutive_failures: 0,
+                is_active: 0,
+                emergency_pause: 1,
+                last_cycle_timestamp: 0,
+                initialized_at: 0,
+                last_am_execution: 0,
+                last_pm_execution: 0,
+                last_cycle_sol: 0,
+                last_cycle_burned: 0,
+                min_fees_threshold: 10_000_000,
+                max_fees_per_cycle: 1_000_000_000,
+                slippage_bps: 300,
+                min_cycle_interval: 120,
+                dat_authority_bump: 255,
+                current_fee_recipient_index: 0,
+                last_known_price: 42_000,
+                pending_burn_amount: 0,
+                root_token_mint: Pubkey::new_unique(),
+                fee_split_bps: 5520,
+                last_sol_sent_to_root: 0,
+                pending_admin: Pubkey::default(),
+                pending_fee_split: 0,
+                pending_fee_split_timestamp: 0,
+                admin_operation_cooldown: 7200,
+                last_direct_fee_split_timestamp: 0,
+                max_price_deviation_bps: 3000,
+                cycle_lock: Pubkey::default(),
+                governance_epoch: 0,
+                governance_epoch_started_at: 0,
+                last_upgrade_slot: 0,
+                event_sequence: 0,
+                auto_resume_after: 0,
+                auto_paused_at: 0,
+                failure_window_start: 0,
+                failing_tokens_in_window: 0,
+                auto_pause_failure_threshold: crate::DEFAULT_AUTO_PAUSE_FAILURE_THRESHOLD,
+                root_escrow_lamports: 0,
+                dev_fee_bps: crate::DEV_FEE_BPS,
+                pending_dev_fee_bps: 0,
+                pending_dev_fee_timestamp: 0,
+                dev_wallet: crate::DEV_WALLET,
+                pending_dev_wallet: Pubkey::default(),
+                reserve_bps: 0,
+                insurance_bps: 0,
+                randomized_timing: 0,
+                max_jito_tip_lamports: 0,
+                cycle_lut: Pubkey::default(),
+                version: 0,
+                pending_import_hash: [0; 32],
+                _reserved: [0; 11],
+            }
+        }
+
+        #[test]
+        fn test_blob_roundtrip() {
+            let config = PortableConfig::from_state(&state());
+            let blob = config.encode().unwrap();
+            assert_eq!(PortableConfig::decode(&blob).unwrap(), config);
+        }
+
+        #[test]
+        fn test_decode_rejects_bad_blobs() {
+            let mut config = PortableConfig::from_state(&state());
+            let blob = config.encode().unwrap();
+            assert!(PortableConfig::decode(&blob[..blob.len() - 1]).is_err(), "Truncated");
+
+            let mut trailing = blob.clone();
+            trailing.push(0);
+            assert!(PortableConfig::decode(&trailing).is_err(), "Trailing bytes");
+
+            config.version = PortableConfig::VERSION + 1;
+            assert!(PortableConfig::decode(&config.encode().unwrap()).is_err(), "Unknown version");
+        }
+
+        #[test]
+        fn test_apply_restores_parameters_only() {
+            let source = state();
+            let mut target = state();
+            target.slippage_bps = 500;
+            target.root_token_mint = Pubkey::default();
+
+            PortableConfig::from_state(&source).apply(&mut target);
+
+            assert_eq!(target.asdf_mint, source.asdf_mint);
+            assert_eq!(target.slippage_bps, 300);
+            assert_eq!(target.admin_operation_cooldown, 7200);
+            assert_eq!(target.last_known_price, 42_000);
+            assert_ne!(target.admin, source.admin, "Admin is not portable");
+            assert_eq!(target.root_token_mint(), None, "Root is re-assigned via set_root_token");
+        }
+
+        #[test]
+        fn test_validate_bounds() {
+            let config = PortableConfig::from_state(&state());
+            assert!(config.validate(3600).is_ok());
+            assert!(config.validate(7201).is_err(), "Import cannot shorten the timelock");
+
+            let mut bad = config.clone();
+            bad.slippage_bps = 501;
+            assert!(bad.validate(0).is_err());
+
+            let mut bad = config.clone();
+            bad.fee_split_bps = 9001;
+            assert!(bad.validate(0).is_err());
+
+            let mut bad = config;
+            bad.min_fees_threshold = bad.max_fees_per_cycle + 1;
+            assert!(bad.validate(0).is_err());
+        }
+    }
+
+    // ========================================================================
+    // 52. REFERRAL TESTS
+    // ========================================================================
+
+    mod referral_tests {
+        use crate::constants::*;
+        use crate::state::{RebatePool, ReferralStats};
+        use anchor_lang::prelude::Pubkey;
+
+        fn pool(referral_share_bps: u16, epoch_reserved: u64, referral_reserved: u64) -> RebatePool {
+            RebatePool {
+                bump: 255,
+                total_deposited: 0,
+                total_distributed: 0,
+                rebates_count: 0,
+                last_rebate_timestamp: 0,
+                last_rebate_slot: 0,
+                unique_recipients: 0,
+                bonus_bps: 0,
+                bonus_cap: 0,
+                bonus_budget: 0,
+                total_bonus_paid: 0,
+                _reserved: [0u8; 6],
+                rebate_share: 0,
+                rebate_threshold: 0,
+                pending_rebate_share: 0,
+                pending_rebate_threshold: 0,
+                pending_params_timestamp: 0,
+                draw_request_slot: 0,
+                draw_target_slot: 0,
+                draw_candidates_hash: [0u8; 32],
+                draw_randomness: [0u8; 32],
+                draw_winner: Pubkey::default(),
+                draws_count: 0,
+                current_epoch: 0,
+                epoch_started_at: 0,
+                epoch_reserved,
+                referral_share_bps,
+                referral_reserved,
+                version: 0,
+            }
+        }
+
+        #[test]
+        fn test_referral_stats_size() {
+            assert_eq!(ReferralStats::LEN, 65);
+        }
+
+        #[test]
+        fn test_referral_reward_is_share_of_rebate_portion() {
+            // 10% of the 0.552% rebate portion of a 1M deposit
+            assert_eq!(pool(1000, 0, 0).referral_reward(5_520), 552);
+            assert_eq!(pool(0, 0, 0).referral_reward(5_520), 0, "Disabled by default");
+            assert_eq!(pool(MAX_REFERRAL_SHARE_BPS, 0, 0).referral_reward(5_520), 2_760);
+        }
+
+        #[test]
+        fn test_referral_rewards_excluded_from_available() {
+            assert_eq!(pool(1000, 300, 200).available(1_000), 500);
+            assert_eq!(pool(1000, 800, 400).available(1_000), 0);
+        }
+
+        #[test]
+        fn test_queued_referral_share_bounded() {
+            let mut p = pool(0, 0, 0);
+            assert!(p.apply_parameter(ADMIN_OP_REFERRAL_SHARE, MAX_REFERRAL_SHARE_BPS as u64 + 1).is_err());
+            assert_eq!(p.apply_parameter(ADMIN_OP_REFERRAL_SHARE, 1000).unwrap(), 0);
+            assert_eq!(p.referral_share_bps, 1000);
+        }
+
+        #[test]
+        fn test_credit_accumulates() {
+            let mut stats = ReferralStats {
+                referrer: Pubkey::new_unique(),
+                referred_users: 1,
+                total_referred_volume: 0,
+                pending_rewards: 0,
+                total_claimed: 0,
+                bump: 255,
+            };
+            stats.credit(1_000_000, 552);
+            stats.credit(2_000_000, 1_104);
+            assert_eq!(stats.total_referred_volume, 3_000_000);
+            assert_eq!(stats.pending_rewards, 1_656);
+        }
+    }
+
+    // ========================================================================
+    // 53. CLIENT TESTS
+    // ========================================================================
+
+    mod client_tests {
+        use crate::client::*;
+        use crate::constants::*;
+        use anchor_lang::prelude::*;
+
+        #[test]
+        fn test_client_pdas_match_program_seeds() {
+            let mint = Pubkey::new_unique();
+            let user = Pubkey::new_unique();
+            assert_eq!(find_dat_state(), Pubkey::find_program_address(&[DAT_STATE_SEED], &crate::ID));
+            assert_eq!(
+                find_token_stats(&mint),
+                Pubkey::find_program_address(&[TOKEN_STATS_SEED, mint.as_ref()], &crate::ID)
+            );
+            assert_eq!(
+                find_root_treasury(&mint),
+                Pubkey::find_program_address(&[ROOT_TREASURY_SEED, mint.as_ref()], &crate::ID)
+            );
+            assert_eq!(
+                find_user_stats(&user),
+                Pubkey::find_program_address(&[USER_STATS_SEED, user.as_ref()], &crate::ID)
+            );
+            assert_eq!(
+                find_app_stats(7),
+                Pubkey::find_program_address(&[APP_STATS_SEED, &7u32.to_le_bytes()], &crate::ID)
+            );
+        }
+
+        #[test]
+        fn test_update_pending_fees_builder() {
+            let admin = Pubkey::new_unique();
+            let mint = Pubkey::new_unique();
+            let ix = update_pending_fees(admin, mint, 42);
+
+            assert_eq!(ix.program_id, crate::ID);
+            let keys: Vec<Pubkey> = ix.accounts.iter().map(|m| m.pubkey).collect();
+            assert_eq!(keys, vec![find_dat_state().0, find_token_stats(&mint).0, mint, admin]);
+            assert!(ix.accounts[1].is_writable);
+            assert!(ix.accounts[3].is_signer);
+            // 8-byte discriminator + u64 argument
+            assert_eq!(ix.data.len(), 16);
+            assert_eq!(&ix.data[8..], &42u64.to_le_bytes());
+        }
+
+        #[test]
+        fn test_build_instruction_appends_remaining_accounts() {
+            let admin = Pubkey::new_unique();
+            let extra = AccountMeta::new_readonly(Pubkey::new_unique(), false);
+            let ix = build_instruction_with_remaining(
+                crate::accounts::AdminControl {
+                    dat_state: find_dat_state().0,
+                    admin,
+                    admin_log: Some(find_admin_log().0),
+                },
+                crate::instruction::EmergencyPause {},
+                vec![extra.clone()],
+            );
+            assert_eq!(ix.accounts.len(), 4);
+            assert_eq!(ix.accounts[3], extra);
+            assert_eq!(ix.data, emergency_pause(admin).data);
+        }
+    }
+
+    // ========================================================================
+    // 54. AUTHORITY TOP-UP TESTS
+    // ========================================================================
+
+    mod authority_top_up_tests {
+        use crate::{authority_top_up_amount, AUTHORITY_TOP_UP_HEADROOM, RENT_EXEMPT_MINIMUM};
+
+        #[test]
+        fn test_top_up_restores_floor_plus_headroom() {
+            let balance = RENT_EXEMPT_MINIMUM - 1_234;
+            assert_eq!(
+                authority_top_up_amount(balance, RENT_EXEMPT_MINIMUM),
+                1_234 + AUTHORITY_TOP_UP_HEADROOM
+            );
+        }
+
+        #[test]
+        fn test_top_up_partial_headroom() {
+            let balance = RENT_EXEMPT_MINIMUM + 2_000;
+            assert_eq!(
+                authority_top_up_amount(balance, RENT_EXEMPT_MINIMUM),
+                AUTHORITY_TOP_UP_HEADROOM - 2_000
+            );
+        }
+
+        #[test]
+        fn test_no_top_up_when_funded() {
+            let balance = RENT_EXEMPT_MINIMUM + AUTHORITY_TOP_UP_HEADROOM;
+            assert_eq!(authority_top_up_amount(balance, RENT_EXEMPT_MINIMUM), 0);
+            assert_eq!(authority_top_up_amount(u64::MAX, RENT_EXEMPT_MINIMUM), 0);
+        }
+    }
+
+    // ========================================================================
+    // 55. COMPUTE BUDGET GUARD TESTS
+    // ========================================================================
+
+    mod compute_budget_tests {
+        use crate::{check_compute_budget, CU_PHASE_FEE_SPLIT, CU_PHASE_PUMPFUN_BUY, CU_PHASE_AMM_BUY};
+
+        #[test]
+        fn test_budget_exactly_met_passes() {
+            assert!(check_compute_budget(CU_PHASE_AMM_BUY, CU_PHASE_AMM_BUY).is_ok());
+        }
+
+        #[test]
+        fn test_budget_short_fails() {
+            assert!(check_compute_budget(CU_PHASE_PUMPFUN_BUY - 1, CU_PHASE_PUMPFUN_BUY).is_err());
+        }
+
+        #[test]
+        fn test_split_guard_covers_the_following_buy() {
+            // Enough for the split alone is not enough to start it
+            let remaining = CU_PHASE_FEE_SPLIT + CU_PHASE_PUMPFUN_BUY / 2;
+            assert!(check_compute_budget(remaining, CU_PHASE_FEE_SPLIT).is_ok());
+            assert!(check_compute_budget(remaining, CU_PHASE_FEE_SPLIT + CU_PHASE_PUMPFUN_BUY).is_err());
+        }
+    }
+
+    // ========================================================================
+    // 56. FEE RECIPIENT ROTATION TESTS
+    // ========================================================================
+
+    mod fee_recipient_tests {
+        use crate::{fee_recipient_rotation, FeeRecipients, MAX_FEE_RECIPIENTS};
+        use anchor_lang::prelude::*;
+
+        #[test]
+        fn test_rotation_cycles_through_entries() {
+            let list = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+            let mut index = 0u8;
+            for expected in list.iter().chain(list.iter()) {
+                let (recipient, next) = fee_recipient_rotation(&list, index);
+                assert_eq!(recipient, *expected);
+                index = next;
+            }
+            assert_eq!(index, 0);
+        }
+
+        #[test]
+        fn test_rotation_wraps_stale_index() {
+            // Index left over from a longer list (shrunk without reconfiguring)
+            let list = [Pubkey::new_unique(), Pubkey::new_unique()];
+            assert_eq!(fee_recipient_rotation(&list, 5), (list[1], 0));
+        }
+
+        #[test]
+        fn test_single_recipient_never_rotates() {
+            let list = [Pubkey::new_unique()];
+            assert_eq!(fee_recipient_rotation(&list, 0), (list[0], 0));
+        }
+
+        #[test]
+        fn test_active_bounded_by_count() {
+            let mut recipients = FeeRecipients {
+                recipients: [Pubkey::default(); MAX_FEE_RECIPIENTS],
+                count: 2,
+                updated_at: 0,
+                bump: 255,
+            };
+            assert_eq!(recipients.active().len(), 2);
+            recipients.count = u8::MAX;
+            assert_eq!(recipients.active().len(), MAX_FEE_RECIPIENTS);
+        }
+
+        #[test]
+        fn test_fee_recipients_size() {
+            assert_eq!(FeeRecipients::LEN, 266);
+        }
+    }
+
+    // ========================================================================
+    // 57. GOVERNANCE EPOCH TESTS
+    // ========================================================================
+
+    mod governance_epoch_tests {
+        use crate::state::DATState;
+        use anchor_lang::prelude::Pubkey;
+
+        fn zeroed_state() -> DATState {
+            bytemuck::Zeroable::zeroed()
+        }
+
+        #[test]
+        fn test_advance_drops_dat_state_proposals() {
+            let mut s = zeroed_state();
+            s.pending_admin = Pubkey::new_unique();
+            s.pending_fee_split = 6000;
+            s.pending_fee_split_timestamp = 1_000;
+
+            assert_eq!(s.advance_governance_epoch(500, 2_000).unwrap(), 1);
+            assert_eq!(s.governance_epoch_started_at, 2_000);
+            assert_eq!(s.last_upgrade_slot, 500);
+            assert_eq!(s.pending_admin(), None);
+            assert_eq!(s.pending_fee_split(), None);
+            assert_eq!(s.pending_fee_split_timestamp, 0);
+        }
+
+        #[test]
+        fn test_same_deployment_acknowledged_once() {
+            let mut s = zeroed_state();
+            s.advance_governance_epoch(500, 2_000).unwrap();
+            assert!(s.advance_governance_epoch(500, 3_000).is_err());
+            assert!(s.advance_governance_epoch(499, 3_000).is_err());
+            assert_eq!(s.advance_governance_epoch(900, 3_000).unwrap(), 2);
+        }
+
+        #[test]
+        fn test_proposals_from_previous_epoch_rejected() {
+            let mut s = zeroed_state();
+            assert!(s.require_current_epoch(1_000).is_ok(), "Epoch 0 accepts any proposal");
+
+            s.advance_governance_epoch(500, 2_000).unwrap();
+            assert!(s.require_current_epoch(1_000).is_err());
+            assert!(s.require_current_epoch(2_000).is_err(), "Same-second proposals predate the ack");
+            assert!(s.require_current_epoch(2_001).is_ok());
+        }
+    }
+
+    // ========================================================================
+    // 58. PUMP GLOBAL CONFIG TESTS
+    // ========================================================================
+
+    mod pump_global_tests {
+        use crate::parse_pump_global_fee_recipients;
+        use anchor_lang::prelude::Pubkey;
+
+        const DISCRIMINATOR: [u8; 8] = [167, 232, 232, 177, 200, 108, 114, 127];
+
+        fn global(fee_recipient: Pubkey, extra: &[Pubkey]) -> Vec<u8> {
+            let mut data = vec![0u8; 500];
+            data[..8].copy_from_slice(&DISCRIMINATOR);
+            data[41..73].copy_from_slice(fee_recipient.as_ref());
+            for (i, key) in extra.iter().enumerate() {
+                data[162 + 32 * i..194 + 32 * i].copy_from_slice(key.as_ref());
+            }
+            data
+        }
+
+        #[test]
+        fn test_parses_primary_and_extra_recipients() {
+            let primary = Pubkey::new_unique();
+            let extra = [Pubkey::new_unique(), Pubkey::new_unique()];
+            let recipients = parse_pump_global_fee_recipients(&global(primary, &extra)).unwrap();
+            assert_eq!(recipients, vec![primary, extra[0], extra[1]]);
+        }
+
+        #[test]
+        fn test_skips_empty_and_duplicate_slots() {
+            let primary = Pubkey::new_unique();
+            let other = Pubkey::new_unique();
+            let data = global(primary, &[primary, Pubkey::default(), other]);
+            assert_eq!(parse_pump_global_fee_recipients(&data).unwrap(), vec![primary, other]);
+        }
+
+        #[test]
+        fn test_rejects_other_accounts() {
+            let mut data = global(Pubkey::new_unique(), &[]);
+            data[0] ^= 1;
+            assert!(parse_pump_global_fee_recipients(&data).is_err(), "Wrong discriminator");
+            assert!(parse_pump_global_fee_recipients(&[0u8; 100]).is_err(), "Too short");
+        }
+    }
+
+    // ========================================================================
+    // 59. CREATOR VAULT TESTS
+    // ========================================================================
+
+    mod creator_vault_tests {
+        use crate::{pump_creator_vault, PUMP_PROGRAM, PUMPSWAP_CREATOR_VAULT_SEED};
+        use anchor_lang::prelude::Pubkey;
+
+        #[test]
+        fn test_creator_vault_uses_hyphenated_seed_under_pump() {
+            let creator = Pubkey::new_unique();
+            let (expected, _) = Pubkey::find_program_address(&[b"creator-vault", creator.as_ref()], &PUMP_PROGRAM);
+            assert_eq!(pump_creator_vault(&creator), expected);
+
+            // The PumpSwap (underscore) seed derives a different account
+            let (amm, _) = Pubkey::find_program_address(&[PUMPSWAP_CREATOR_VAULT_SEED, creator.as_ref()], &PUMP_PROGRAM);
+            assert_ne!(pump_creator_vault(&creator), amm);
+        }
+
+        #[test]
+        fn test_creator_vault_unique_per_creator() {
+            assert_ne!(pump_creator_vault(&Pubkey::new_unique()), pump_creator_vault(&Pubkey::new_unique()));
+        }
+    }
+
+    // ========================================================================
+    // 60. VENUE PROGRAM ACCOUNT TESTS
+    // ========================================================================
+
+    mod venue_program_account_tests {
+        use crate::{
+            PUMP_PROGRAM, PUMP_SWAP_PROGRAM, PUMP_EVENT_AUTHORITY, PUMP_GLOBAL_VOLUME_ACCUMULATOR,
+            PUMPSWAP_EVENT_AUTHORITY, PUMPSWAP_GLOBAL_VOLUME_ACCUMULATOR,
+        };
+        use anchor_lang::prelude::Pubkey;
+
+        #[test]
+        fn test_pump_accounts_derive_under_pump_program() {
+            let (event_authority, _) = Pubkey::find_program_address(&[b"__event_authority"], &PUMP_PROGRAM);
+            let (volume, _) = Pubkey::find_program_address(&[b"global_volume_accumulator"], &PUMP_PROGRAM);
+            assert_eq!(PUMP_EVENT_AUTHORITY, event_authority);
+            assert_eq!(PUMP_GLOBAL_VOLUME_ACCUMULATOR, volume);
+        }
+
+        #[test]
+        fn test_pumpswap_accounts_derive_under_amm_program() {
+            let (event_authority, _) = Pubkey::find_program_address(&[b"__event_authority"], &PUMP_SWAP_PROGRAM);
+            let (volume, _) = Pubkey::find_program_address(&[b"global_volume_accumulator"], &PUMP_SWAP_PROGRAM);
+            assert_eq!(PUMPSWAP_EVENT_AUTHORITY, event_authority);
+            assert_eq!(PUMPSWAP_GLOBAL_VOLUME_ACCUMULATOR, volume);
+            assert_ne!(PUMPSWAP_EVENT_AUTHORITY, PUMP_EVENT_AUTHORITY);
+        }
+    }
+
+    // ========================================================================
+    // 61. EMERGENCY WITHDRAWAL TESTS
+    // ========================================================================
+
+    mod emergency_withdrawal_tests {
+        use crate::state::EmergencyWithdrawal;
+        use crate::EMERGENCY_WITHDRAW_TIMELOCK;
+        use anchor_lang::prelude::Pubkey;
+
+        fn withdrawal() -> EmergencyWithdrawal {
+            EmergencyWithdrawal {
+                guardian: Pubkey::new_unique(),
+                pending_destination: Pubkey::default(),
+                pending_amount: 0,
+                pending_proposed_at: 0,
+                total_withdrawn: 0,
+                bump: 255,
+            }
+        }
+
+        #[test]
+        fn test_emergency_withdrawal_size() {
+            assert_eq!(EmergencyWithdrawal::LEN, 89, "EmergencyWithdrawal size mismatch");
+        }
+
+        #[test]
+        fn test_timelock_is_72_hours() {
+            assert_eq!(EMERGENCY_WITHDRAW_TIMELOCK, 259_200);
+        }
+
+        #[test]
+        fn test_pending_lifecycle() {
+            let mut w = withdrawal();
+            assert!(!w.has_pending());
+
+            w.pending_destination = Pubkey::new_unique();
+            w.pending_amount = 1_000_000;
+            w.pending_proposed_at = 1_700_000_000;
+            assert!(w.has_pending());
+            assert_eq!(w.executable_at(EMERGENCY_WITHDRAW_TIMELOCK), 1_700_259_200);
+
+            w.clear_pending();
+            assert!(!w.has_pending());
+            assert_eq!(w.pending_amount, 0);
+            assert_eq!(w.pending_proposed_at, 0);
+        }
+
+        #[test]
+        fn test_executable_at_saturates() {
+            let mut w = withdrawal();
+            w.pending_proposed_at = i64::MAX - 10;
+            assert_eq!(w.executable_at(EMERGENCY_WITHDRAW_TIMELOCK), i64::MAX);
+        }
+    }
+
+    // ========================================================================
+    // 62. DUST SWEEP TESTS
+    // ========================================================================
+
+    mod dust_sweep_tests {
+        use crate::state::TokenStats;
+
+        fn stats(dust_threshold: u64) -> TokenStats {
+            TokenStats {
+                dust_threshold,
+                ..super::token_stats_fixture()
+            }
+        }
+
+        #[test]
+        fn test_sweeps_whole_balance_within_threshold() {
+            let s = stats(1_000);
+            assert_eq!(s.dust_sweep_amount(1).unwrap(), 1);
+            assert_eq!(s.dust_sweep_amount(1_000).unwrap(), 1_000);
+        }
+
+        #[test]
+        fn test_rejects_balance_above_threshold() {
+            assert!(stats(1_000).dust_sweep_amount(1_001).is_err());
+        }
+
+        #[test]
+        fn test_rejects_empty_balance() {
+            assert!(stats(1_000).dust_sweep_amount(0).is_err());
+        }
+
+        #[test]
+        fn test_zero_threshold_disables_sweeping() {
+            assert!(stats(0).dust_sweep_amount(1).is_err());
+        }
+
+        #[test]
+        fn test_queued_threshold_applied() {
+            let mut s = stats(0);
+            assert_eq!(s.apply_parameter(crate::ADMIN_OP_DUST_THRESHOLD, 1_000).unwrap(), 0);
+            assert_eq!(s.dust_sweep_amount(1_000).unwrap(), 1_000);
+        }
+
+        #[test]
+        fn test_queued_threshold_bounded() {
+            use crate::{ADMIN_OP_DUST_THRESHOLD, MAX_DUST_THRESHOLD};
+            let mut s = stats(0);
+            assert!(s.apply_parameter(ADMIN_OP_DUST_THRESHOLD, MAX_DUST_THRESHOLD + 1).is_err());
+            assert_eq!(s.dust_threshold, 0);
+            s.apply_parameter(ADMIN_OP_DUST_THRESHOLD, MAX_DUST_THRESHOLD).unwrap();
+            assert_eq!(s.dust_threshold, MAX_DUST_THRESHOLD);
+        }
+    }
+
+    // ========================================================================
+    // 63. GLOBAL STATS ROLL-UP TESTS
+    // ========================================================================
+
+    mod global_stats_tests {
+        use crate::state::{GlobalStats, TokenStats};
+        use crate::{rollup_checksum, stats_leaf_hash};
+        use anchor_lang::prelude::Pubkey;
+
+        fn stats(mint: Pubkey, total_burned: u64, total_sol_collected: u64) -> TokenStats {
+            TokenStats {
+                mint,
+                total_burned,
+                total_sol_collected,
+                total_sol_used: total_sol_collected / 2,
+                total_buybacks: 3,
+                usd_value_burned: 7,
+                ..super::token_stats_fixture()
+            }
+        }
+
+        fn global() -> GlobalStats {
+            GlobalStats {
+                total_burned: 0,
+                total_sol_collected: 0,
+                total_sol_used: 0,
+                total_buybacks: 0,
+                usd_value_burned: 0,
+                token_count: 0,
+                checksum: [0u8; 32],
+                rolled_up_at: 0,
+                pending_total_burned: 0,
+                pending_total_sol_collected: 0,
+                pending_total_sol_used: 0,
+                pending_total_buybacks: 0,
+                pending_usd_value_burned: 0,
+                pending_token_count: 0,
+                pending_checksum: [0u8; 32],
+                cursor: Pubkey::default(),
+                in_progress: false,
+                bump: 255,
+            }
+        }
+
+        fn sorted_mints() -> (Pubkey, Pubkey) {
+            let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+            if a < b { (a, b) } else { (b, a) }
+        }
+
+        #[test]
+        fn test_global_stats_size() {
+            assert_eq!(GlobalStats::LEN, 194, "GlobalStats size mismatch");
+        }
+
+        #[test]
+        fn test_rollup_across_batches() {
+            let (a, b) = sorted_mints();
+            let (sa, sb) = (stats(a, 100, 1_000), stats(b, 50, 400));
+            let mut g = global();
+
+            g.begin();
+            g.accumulate(&sa).unwrap();
+            g.accumulate(&sb).unwrap();
+            g.commit(42).unwrap();
+
+            assert_eq!(g.total_burned, 150);
+            assert_eq!(g.total_sol_collected, 1_400);
+            assert_eq!(g.total_sol_used, 700);
+            assert_eq!(g.total_buybacks, 6);
+            assert_eq!(g.usd_value_burned, 14);
+            assert_eq!(g.token_count, 2);
+            assert_eq!(g.rolled_up_at, 42);
+            assert!(!g.in_progress);
+
+            let leaf = |s: &TokenStats| stats_leaf_hash(
+                &s.mint, s.total_burned, s.total_sol_collected, s.total_sol_used, s.total_buybacks, s.usd_value_burned,
+            );
+            let expected = rollup_checksum(&rollup_checksum(&[0u8; 32], &leaf(&sa)), &leaf(&sb));
+            assert_eq!(g.checksum, expected, "Indexers recompute the chained checksum");
+        }
+
+        #[test]
+        fn test_rejects_duplicate_or_descending_mints() {
+            let (a, b) = sorted_mints();
+            let mut g = global();
+            g.begin();
+            g.accumulate(&stats(b, 1, 1)).unwrap();
+            assert!(g.accumulate(&stats(b, 1, 1)).is_err(), "Duplicate mint");
+            assert!(g.accumulate(&stats(a, 1, 1)).is_err(), "Descending mint");
+        }
+
+        #[test]
+        fn test_requires_begin() {
+            let mut g = global();
+            assert!(g.accumulate(&stats(Pubkey::new_unique(), 1, 1)).is_err());
+            assert!(g.commit(0).is_err());
+        }
+
+        #[test]
+        fn test_begin_resets_accumulator_not_committed_totals() {
+            let mut g = global();
+            g.begin();
+            g.accumulate(&stats(Pubkey::new_unique(), 10, 10)).unwrap();
+            g.commit(1).unwrap();
+
+            g.begin();
+            assert_eq!(g.pending_total_burned, 0);
+            assert_eq!(g.pending_token_count, 0);
+            assert_eq!(g.total_burned, 10, "Committed totals survive until the next commit");
+        }
+    }
+
+    // ========================================================================
+    // 64. ACCOUNTING INVARIANT TESTS
+    // ========================================================================
+
+    mod invariant_tests {
+        use crate::state::TokenStats;
+
+        fn stats() -> TokenStats {
+            TokenStats {
+                total_burned: 1_000,
+                total_sol_collected: 5_000,
+                total_sol_used: 4_000,
+                last_cycle_sol: 400,
+                last_cycle_burned: 100,
+                total_partner_paid: 250,
+                dust_burned: 10,
+                ..super::token_stats_fixture()
+            }
+        }
+
+        #[test]
+        fn test_consistent_stats_pass() {
+            assert!(stats().check_invariants().is_ok());
+        }
+
+        #[test]
+        fn test_partner_paid_above_collected_fails() {
+            let mut s = stats();
+            s.total_partner_paid = s.total_sol_collected + 1;
+            assert!(s.check_invariants().is_err());
+        }
+
+        #[test]
+        fn test_dust_above_total_burned_fails() {
+            let mut s = stats();
+            s.dust_burned = s.total_burned + 1;
+            assert!(s.check_invariants().is_err());
+        }
+
+        #[test]
+        fn test_last_cycle_above_totals_fails() {
+            let mut s = stats();
+            s.last_cycle_burned = s.total_burned + 1;
+            assert!(s.check_invariants().is_err());
+
+            let mut s = stats();
+            s.last_cycle_sol = s.total_sol_used + 1;
+            assert!(s.check_invariants().is_err());
+        }
+
+        #[test]
+        fn test_secondary_spending_shared_pool_passes() {
+            // N+1 secondaries spend SOL collected by the first token
+            let mut s = stats();
+            s.total_sol_collected = 0;
+            s.total_partner_paid = 0;
+            assert!(s.check_invariants().is_ok());
+        }
+    }
+
+    // ========================================================================
+    // 65. CYCLE HISTORY RING BUFFER TESTS
+    // ========================================================================
+
+    mod cycle_history_tests {
+        use crate::state::{CycleHistory, CycleRecord};
+        use crate::CYCLE_HISTORY_LEN;
+        use anchor_lang::prelude::Pubkey;
+
+        fn history() -> CycleHistory {
+            CycleHistory {
+                mint: Pubkey::new_unique(),
+                total_recorded: 0,
+                head: 0,
+                records: [CycleRecord::default(); CYCLE_HISTORY_LEN],
+                bump: 255,
+            }
+        }
+
+        fn record(timestamp: i64) -> CycleRecord {
+            CycleRecord { timestamp, sol_used: 1_000, tokens_burned: 50, venue: 0 }
+        }
+
+        #[test]
+        fn test_cycle_history_size() {
+            assert_eq!(CycleRecord::LEN, 25);
+            assert_eq!(CycleHistory::LEN, 842);
+        }
+
+        #[test]
+        fn test_empty_history() {
+            let h = history();
+            assert!(h.latest().is_none());
+            assert!(h.chronological().is_empty());
+        }
+
+        #[test]
+        fn test_records_in_order_before_wrap() {
+            let mut h = history();
+            for t in 1..=3 {
+                h.record(record(t));
+            }
+            assert_eq!(h.latest().unwrap().timestamp, 3);
+            let ts: Vec<i64> = h.chronological().iter().map(|r| r.timestamp).collect();
+            assert_eq!(ts, vec![1, 2, 3]);
+        }
+
+        #[test]
+        fn test_wrap_overwrites_oldest() {
+            let mut h = history();
+            let n = CYCLE_HISTORY_LEN as i64 + 5;
+            for t in 1..=n {
+                h.record(record(t));
+            }
+            assert_eq!(h.total_recorded, n as u64);
+            assert_eq!(h.head, 5);
+            assert_eq!(h.latest().unwrap().timestamp, n);
+
+            let chrono = h.chronological();
+            assert_eq!(chrono.len(), CYCLE_HISTORY_LEN);
+            assert_eq!(chrono.first().unwrap().timestamp, 6, "Oldest 5 cycles overwritten");
+            assert_eq!(chrono.last().unwrap().timestamp, n);
+        }
+    }
+
+    // ========================================================================
+    // 66. DAILY STATS BUCKET TESTS
+    // ========================================================================
+
+    mod daily_stats_tests {
+        use crate::state::DailyStats;
+        use crate::SECONDS_PER_DAY;
+        use anchor_lang::prelude::Pubkey;
+
+        fn bucket(day: i64) -> DailyStats {
+            DailyStats {
+                mint: Pubkey::new_unique(),
+                day,
+                sol_collected: 0,
+                tokens_burned: 0,
+                bump: 255,
+            }
+        }
+
+        #[test]
+        fn test_daily_stats_size() {
+            assert_eq!(DailyStats::LEN, 57);
+        }
+
+        #[test]
+        fn test_day_index_boundaries() {
+            assert_eq!(DailyStats::day_index(0), 0);
+            assert_eq!(DailyStats::day_index(SECONDS_PER_DAY - 1), 0);
+            assert_eq!(DailyStats::day_index(SECONDS_PER_DAY), 1);
+            assert_eq!(DailyStats::day_index(-1), -1);
+        }
+
+        #[test]
+        fn test_record_accumulates_within_day() {
+            let mut b = bucket(20_000);
+            let start = 20_000 * SECONDS_PER_DAY;
+            b.record(start, 1_000, 0).unwrap();
+            b.record(start + SECONDS_PER_DAY - 1, 500, 42).unwrap();
+            assert_eq!((b.sol_collected, b.tokens_burned), (1_500, 42));
+        }
+
+        #[test]
+        fn test_record_rejects_other_day() {
+            let mut b = bucket(20_000);
+            assert!(b.record(20_001 * SECONDS_PER_DAY, 1, 1).is_err());
+            assert!(b.record(20_000 * SECONDS_PER_DAY - 1, 1, 1).is_err());
+        }
+    }
+
+    // ========================================================================
+    // 67. ROOT SET TESTS
+    // ========================================================================
+
+    mod root_set_tests {
+        use crate::state::RootSet;
+        use crate::MAX_ROOTS;
+        use anchor_lang::prelude::Pubkey;
+
+        fn empty() -> RootSet {
+            RootSet { roots: [Pubkey::default(); MAX_ROOTS], root_count: 0, bump: 255 }
+        }
+
+        #[test]
+        fn test_root_set_size() {
+            assert_eq!(RootSet::LEN, 258);
+        }
+
+        #[test]
+        fn test_add_remove_roots() {
+            let mut set = empty();
+            let roots: Vec<Pubkey> = (0..MAX_ROOTS).map(|_| Pubkey::new_unique()).collect();
+            for root in &roots {
+                set.add(*root).unwrap();
+            }
+            assert!(set.add(Pubkey::new_unique()).is_err(), "Set full");
+
+            set.remove(&roots[1]).unwrap();
+            assert_eq!(set.root_count as usize, MAX_ROOTS - 1);
+            assert!(!set.contains(&roots[1]));
+            assert_eq!(set.roots[1], roots[MAX_ROOTS - 1], "Last root moved into the freed slot");
+            assert!(set.remove(&roots[1]).is_err(), "Not listed");
+            assert!(set.add(roots[0]).is_err(), "Already listed");
+        }
+
+        #[test]
+        fn test_resolve_assigned_root() {
+            let (primary, regional) = (Pubkey::new_unique(), Pubkey::new_unique());
+            let mut set = empty();
+            set.add(regional).unwrap();
+
+            assert_eq!(RootSet::resolve(Pubkey::default(), Some(primary), None).unwrap(), primary);
+            assert!(RootSet::resolve(Pubkey::default(), None, Some(&set)).is_err(), "No primary root");
+            assert_eq!(RootSet::resolve(regional, Some(primary), Some(&set)).unwrap(), regional);
+            assert!(RootSet::resolve(regional, Some(primary), None).is_err(), "Root set required");
+
+            set.remove(&regional).unwrap();
+            assert!(RootSet::resolve(regional, Some(primary), Some(&set)).is_err(), "Unlisted root");
+        }
+    }
+
+    // ========================================================================
+    // 68. ROOT STREAM TESTS
+    // ========================================================================
+
+    mod root_stream_tests {
+        use crate::root_share;
+        use crate::state::RootStream;
+        use anchor_lang::prelude::Pubkey;
+
+        fn stream(flush_every_cycles: u16) -> RootStream {
+            RootStream {
+                mint: Pubkey::new_unique(),
+                accrued_lamports: 0,
+                cycles_accrued: 0,
+                flush_every_cycles,
+                total_flushed: 0,
+                last_flush_at: 0,
+                bump: 255,
+            }
+        }
+
+        #[test]
+        fn test_root_stream_size() {
+            assert_eq!(RootStream::LEN, 61);
+        }
+
+        #[test]
+        fn test_root_share_matches_fee_split() {
+            // 55.2% kept, 44.8% to root
+            assert_eq!(root_share(1_000_000_000, 5520), 448_000_000);
+            assert_eq!(root_share(1_000_000_000, 10000), 0);
+        }
+
+        #[test]
+        fn test_flush_schedule() {
+            let mut s = stream(3);
+            assert!(!s.flush_due(), "Nothing accrued");
+            s.accrue(100);
+            s.accrue(200);
+            assert!(!s.flush_due(), "Schedule not reached");
+            s.accrue(300);
+            assert!(s.flush_due());
+
+            assert_eq!(s.take_flush(1_000), 600);
+            assert_eq!((s.accrued_lamports, s.cycles_accrued, s.total_flushed, s.last_flush_at), (0, 0, 600, 1_000));
+        }
+
+        #[test]
+        fn test_leftover_flushable_after_streaming_off() {
+            let mut s = stream(10);
+            s.accrue(500);
+            assert!(!s.flush_due());
+            s.flush_every_cycles = 0;
+            assert!(s.flush_due(), "Lump mode flushes any leftover at once");
+        }
+    }
+
+    // ========================================================================
+    // 69. ADMIN LOG RING BUFFER TESTS
+    // ========================================================================
+
+    mod admin_log_tests {
+        use crate::state::{AdminLog, AdminLogEntry};
+        use crate::{ADMIN_LOG_LEN, ADMIN_OP_FEE_SPLIT};
+        use anchor_lang::prelude::Pubkey;
+
+        fn log() -> AdminLog {
+            AdminLog {
+                total_recorded: 0,
+                head: 0,
+                entries: [AdminLogEntry::default(); ADMIN_LOG_LEN],
+                bump: 255,
+            }
+        }
+
+        fn entry(timestamp: i64) -> AdminLogEntry {
+            AdminLogEntry {
+                op: ADMIN_OP_FEE_SPLIT,
+                subject: Pubkey::default(),
+                old_value: 5520,
+                new_value: 5600,
+                signer: Pubkey::new_unique(),
+                timestamp,
+            }
+        }
+
+        #[test]
+        fn test_admin_log_size() {
+            assert_eq!(AdminLogEntry::LEN, 89);
+            assert_eq!(AdminLog::LEN, 5706);
+        }
+
+        #[test]
+        fn test_empty_log() {
+            let l = log();
+            assert!(l.latest().is_none());
+            assert!(l.chronological().is_empty());
+        }
+
+        #[test]
+        fn test_wrap_keeps_last_64_operations() {
+            let mut l = log();
+            let n = ADMIN_LOG_LEN as i64 + 3;
+            for t in 1..=n {
+                l.record(entry(t));
+            }
+            assert_eq!((l.total_recorded, l.head), (n as u64, 3));
+            assert_eq!(l.latest().unwrap().timestamp, n);
+
+            let chrono = l.chronological();
+            assert_eq!(chrono.len(), ADMIN_LOG_LEN);
+            assert_eq!(chrono.first().unwrap().timestamp, 4, "Oldest 3 operations overwritten");
+            assert_eq!(chrono.last().unwrap().timestamp, n);
+        }
+    }
+
+    // ========================================================================
+    // 70. PARAMETER CHANGE QUEUE TESTS
+    // ========================================================================
+
+    mod change_queue_tests {
+        use crate::state::{ChangeQueue, ChangeTarget, PendingChange};
+        use crate::{
+            ADMIN_OP_BURN_RATIO, ADMIN_OP_DUST_THRESHOLD, ADMIN_OP_FEE_SPLIT, ADMIN_OP_MIN_INTERVAL,
+            ADMIN_OP_ONBOARDING_BONUS_CAP, ADMIN_OP_SLIPPAGE, ADMIN_OP_VALIDATOR_MAX_TX,
+            ADMIN_OP_ALERT_MIN_AUTHORITY_BALANCE, MAX_PENDING_CHANGES, MIN_BURN_BPS,
+        };
+        use anchor_lang::prelude::Pubkey;
+
+        fn queue() -> ChangeQueue {
+            ChangeQueue {
+                guardian: Pubkey::new_unique(),
+                next_id: 0,
+                changes: [PendingChange::default(); MAX_PENDING_CHANGES],
+                bump: 255,
+                version: 0,
+            }
+        }
+
+        #[test]
+        fn test_change_queue_size() {
+            assert_eq!(PendingChange::LEN, 65);
+            assert_eq!(ChangeQueue::LEN, 1082);
+        }
+
+        #[test]
+        fn test_change_executes_only_after_its_delay() {
+            let mut q = queue();
+            let fast = q.enqueue(ADMIN_OP_SLIPPAGE, Pubkey::default(), 300, 1_000, 3_600).unwrap();
+            let slow = q.enqueue(ADMIN_OP_MIN_INTERVAL, Pubkey::default(), 120, 1_000, 86_400).unwrap();
+            assert_eq!((fast, slow, q.pending_count()), (0, 1, 2));
+
+            assert!(q.take_due(fast, 4_599).is_err(), "Delay not elapsed");
+            let change = q.take_due(fast, 4_600).unwrap();
+            assert_eq!((change.param, change.value), (ADMIN_OP_SLIPPAGE, 300));
+            assert!(q.take_due(fast, 4_600).is_err(), "Executed once");
+            assert!(q.take_due(slow, 4_600).is_err(), "Per-change delay");
+            assert_eq!(q.pending_count(), 1);
+        }
+
+        #[test]
+        fn test_veto_frees_slot_and_full_queue_rejects() {
+            let mut q = queue();
+            for _ in 0..MAX_PENDING_CHANGES {
+                q.enqueue(ADMIN_OP_SLIPPAGE, Pubkey::default(), 300, 0, 3_600).unwrap();
+            }
+            assert!(q.enqueue(ADMIN_OP_SLIPPAGE, Pubkey::default(), 300, 0, 3_600).is_err(), "Queue full");
+
+            assert_eq!(q.remove(3).unwrap().id, 3);
+            assert!(q.remove(3).is_err(), "Already removed");
+            assert_eq!(q.enqueue(ADMIN_OP_SLIPPAGE, Pubkey::default(), 400, 0, 3_600).unwrap(), MAX_PENDING_CHANGES as u64);
+            assert!(q.enqueue(0, Pubkey::default(), 1, 0, 3_600).is_err(), "Empty-slot tag rejected");
+        }
+
+        #[test]
+        fn test_per_token_change_keeps_its_subject() {
+            let mut q = queue();
+            let mint = Pubkey::new_unique();
+            let id = q.enqueue(ADMIN_OP_BURN_RATIO, mint, 8000, 0, 3_600).unwrap();
+            let change = q.take_due(id, 3_600).unwrap();
+            assert_eq!((change.param, change.subject, change.value), (ADMIN_OP_BURN_RATIO, mint, 8000));
+        }
+
+        #[test]
+        fn test_change_targets() {
+            assert_eq!(ChangeTarget::of(ADMIN_OP_FEE_SPLIT), ChangeTarget::DatState);
+            assert_eq!(ChangeTarget::of(ADMIN_OP_DUST_THRESHOLD), ChangeTarget::TokenStats);
+            assert_eq!(ChangeTarget::of(ADMIN_OP_VALIDATOR_MAX_TX), ChangeTarget::ValidatorState);
+            assert_eq!(ChangeTarget::of(ADMIN_OP_ONBOARDING_BONUS_CAP), ChangeTarget::RebatePool);
+            assert_eq!(ChangeTarget::of(ADMIN_OP_ALERT_MIN_AUTHORITY_BALANCE), ChangeTarget::AlertConfig);
+        }
+
+        #[test]
+        fn test_queued_burn_ratio_bounded_by_reflection() {
+            let mut s = crate::state::TokenStats { reflection_bps: 6000, ..super::token_stats_fixture() };
+            assert!(s.apply_parameter(ADMIN_OP_BURN_RATIO, MIN_BURN_BPS as u64 - 1).is_err());
+            assert!(s.apply_parameter(ADMIN_OP_BURN_RATIO, 10_001).is_err());
+            assert!(s.apply_parameter(ADMIN_OP_BURN_RATIO, 5_999).is_err(), "Below the reflected share");
+            assert_eq!(s.apply_parameter(ADMIN_OP_BURN_RATIO, 8000).unwrap(), 10_000);
+            assert_eq!(s.burn_bps, 8000);
+        }
+    }
+
+    // ========================================================================
+    // 71. FEE SPLIT GOVERNANCE TESTS
+    // ========================================================================
+
+    mod governance_tests {
+        use crate::state::{FeeSplitProposal, Governance, VoteRecord};
+        use anchor_lang::prelude::Pubkey;
+
+        fn proposal(voting_ends_at: i64) -> FeeSplitProposal {
+            FeeSplitProposal {
+                id: 0,
+                proposer: Pubkey::new_unique(),
+                new_fee_split_bps: 6000,
+                created_at: 0,
+                voting_ends_at,
+                votes_for: 0,
+                votes_against: 0,
+                executed: false,
+                bump: 255,
+            }
+        }
+
+        #[test]
+        fn test_governance_sizes() {
+            assert_eq!(Governance::LEN, 33);
+            assert_eq!(FeeSplitProposal::LEN, 76);
+            assert_eq!(VoteRecord::LEN, 74);
+        }
+
+        #[test]
+        fn test_votes_only_while_open() {
+            let mut p = proposal(1_000);
+            p.record_vote(true, 500, 999).unwrap();
+            p.record_vote(false, 200, 999).unwrap();
+            assert_eq!((p.votes_for, p.votes_against), (500, 200));
+            assert!(p.record_vote(true, 1, 1_000).is_err(), "Voting closed");
+            assert!(p.record_vote(true, 0, 10).is_err(), "Zero weight");
+        }
+
+        #[test]
+        fn test_quorum_majority_and_timelock() {
+            let (quorum, delay) = (1_000, 3_600);
+            let mut p = proposal(1_000);
+            p.record_vote(true, 600, 10).unwrap();
+            assert!(!p.passed(quorum), "Below quorum");
+            p.record_vote(false, 600, 10).unwrap();
+            assert!(!p.passed(quorum), "Tie is not a majority");
+            p.record_vote(true, 1, 10).unwrap();
+            assert!(p.passed(quorum));
+
+            assert!(p.require_executable(quorum, delay, 1_000 + delay - 1).is_err(), "Timelock");
+            p.require_executable(quorum, delay, 1_000 + delay).unwrap();
+            p.executed = true;
+            assert!(p.require_executable(quorum, delay, 1_000 + delay).is_err(), "Executed once");
+        }
+    }
+
+    // ========================================================================
+    // 72. STAKING TESTS
+    // ========================================================================
+
+    mod staking_tests {
+        use crate::constants::{MAX_STAKE_BOOST_BPS, MAX_STAKE_LOCK, MIN_STAKE_LOCK};
+        use crate::state::StakeAccount;
+        use anchor_lang::prelude::Pubkey;
+
+        fn empty() -> StakeAccount {
+            StakeAccount {
+                owner: Pubkey::new_unique(),
+                amount: 0,
+                locked_until: 0,
+                lock_duration: 0,
+                bump: 255,
+            }
+        }
+
+        #[test]
+        fn test_stake_account_size() {
+            assert_eq!(StakeAccount::LEN, 57);
+        }
+
+        #[test]
+        fn test_deposit_bounds_and_never_shortens() {
+            let mut s = empty();
+            assert!(s.deposit(100, MIN_STAKE_LOCK - 1, 0).is_err(), "Lock too short");
+            assert!(s.deposit(100, MAX_STAKE_LOCK + 1, 0).is_err(), "Lock too long");
+            assert!(s.deposit(0, MIN_STAKE_LOCK, 0).is_err(), "Zero amount");
+
+            s.deposit(100, MAX_STAKE_LOCK, 0).unwrap();
+            s.deposit(50, MIN_STAKE_LOCK, 10).unwrap();
+            assert_eq!(s.amount, 150);
+            assert_eq!(s.locked_until, MAX_STAKE_LOCK, "Shorter deposit keeps the longer lock");
+            assert_eq!(s.lock_duration, MAX_STAKE_LOCK);
+        }
+
+        #[test]
+        fn test_boost_scales_with_lock_and_expires() {
+            let mut s = empty();
+            assert_eq!(s.boost_bps(0), 0, "No stake, no boost");
+
+            s.deposit(100, MAX_STAKE_LOCK, 0).unwrap();
+            assert_eq!(s.boost_bps(1), MAX_STAKE_BOOST_BPS);
+            assert_eq!(s.boost_bps(MAX_STAKE_LOCK), 0, "Unlocked stake earns no boost");
+
+            let mut short = empty();
+            short.deposit(100, MAX_STAKE_LOCK / 2, 0).unwrap();
+            assert_eq!(short.boost_bps(1), MAX_STAKE_BOOST_BPS / 2);
+        }
+
+        #[test]
+        fn test_extend_and_withdraw() {
+            let mut s = empty();
+            assert!(s.extend(MIN_STAKE_LOCK, 0).is_err(), "Nothing staked");
+            s.deposit(100, 30 * 24 * 3600, 0).unwrap();
+
+            assert!(s.extend(MIN_STAKE_LOCK, 0).is_err(), "Would shorten the lock");
+            s.extend(MAX_STAKE_LOCK, 100).unwrap();
+            assert_eq!(s.locked_until, MAX_STAKE_LOCK + 100);
+
+            assert!(s.withdraw(MAX_STAKE_LOCK).is_err(), "Still locked");
+            assert_eq!(s.withdraw(MAX_STAKE_LOCK + 100).unwrap(), 100);
+            assert_eq!(s.amount, 0);
+            assert!(s.withdraw(MAX_STAKE_LOCK + 100).is_err(), "Already withdrawn");
+        }
+
+        #[test]
+        fn test_vote_weight_requires_lock_through_vote() {
+            let mut s = empty();
+            s.deposit(100, MIN_STAKE_LOCK, 0).unwrap();
+            assert_eq!(s.vote_weight(MIN_STAKE_LOCK), 100);
+            assert_eq!(s.vote_weight(MIN_STAKE_LOCK + 1), 0, "Unlocks before voting ends");
+        }
+    }
+
+    // ========================================================================
+    // 73. HOLDER REFLECTION TESTS
+    // ========================================================================
+
+    mod reflection_tests {
+        use crate::helpers::{calculate_burn_split, calculate_reflection_split, merkle_node_hash, reflection_leaf_hash, verify_merkle_proof};
+        use crate::state::{ReflectionClaim, RewardsVault};
+        use anchor_lang::prelude::Pubkey;
+
+        fn vault() -> RewardsVault {
+            RewardsVault {
+                mint: Pubkey::new_unique(),
+                epoch: 0,
+                merkle_root: [0u8; 32],
+                epoch_amount: 0,
+                epoch_claimed: 0,
+                unallocated: 0,
+                total_reflected: 0,
+                total_claimed: 0,
+                bump: 255,
+            }
+        }
+
+        #[test]
+        fn test_reflection_sizes() {
+            assert_eq!(RewardsVault::LEN, 113);
+            assert_eq!(ReflectionClaim::LEN, 49);
+        }
+
+        #[test]
+        fn test_reflection_comes_out_of_burn_share() {
+            let (burn, treasury) = calculate_burn_split(1_000_000, 8000).unwrap();
+            let (burned, reflected) = calculate_reflection_split(1_000_000, burn, 3000).unwrap();
+            assert_eq!((burned, reflected, treasury), (500_000, 300_000, 200_000));
+
+            let (burned, reflected) = calculate_reflection_split(1_000_000, burn, 0).unwrap();
+            assert_eq!((burned, reflected), (burn, 0), "Reflection disabled burns everything");
+
+            let (burned, reflected) = calculate_reflection_split(1_000_000, burn, 10000).unwrap();
+            assert_eq!((burned, reflected), (0, burn), "Capped at the burn share");
+            assert!(calculate_reflection_split(1, 1, 10001).is_err());
+        }
+
+        #[test]
+        fn test_queued_reflection_ratio_bounded_by_burn() {
+            use crate::ADMIN_OP_REFLECTION_RATIO;
+            let mut s = crate::state::TokenStats { burn_bps: 8000, ..super::token_stats_fixture() };
+            assert!(s.apply_parameter(ADMIN_OP_REFLECTION_RATIO, 8001).is_err(), "Above the burned share");
+            assert_eq!(s.apply_parameter(ADMIN_OP_REFLECTION_RATIO, 3000).unwrap(), 0);
+            assert_eq!(s.reflection_bps, 3000);
+            assert_eq!(crate::state::ChangeTarget::of(ADMIN_OP_REFLECTION_RATIO), crate::state::ChangeTarget::TokenStats);
+        }
+
+        #[test]
+        fn test_snapshot_allocates_accrued_and_rolls_over() {
+            let mut v = vault();
+            v.accrue(1_000);
+            assert!(v.post_snapshot([1u8; 32], 1_001).is_err(), "More than accrued");
+            v.post_snapshot([1u8; 32], 800).unwrap();
+            assert_eq!((v.epoch, v.epoch_amount, v.unallocated), (1, 800, 200));
+
+            v.claim(500).unwrap();
+            assert!(v.claim(301).is_err(), "Exceeds snapshot");
+
+            // 300 unclaimed + 200 unallocated roll into the next snapshot
+            v.post_snapshot([2u8; 32], 500).unwrap();
+            assert_eq!((v.epoch, v.epoch_claimed, v.unallocated), (2, 0, 0));
+            assert_eq!((v.total_reflected, v.total_claimed), (1_000, 500));
+        }
+
+        #[test]
+        fn test_claim_requires_snapshot() {
+            let mut v = vault();
+            v.accrue(100);
+            assert!(v.claim(1).is_err(), "No snapshot posted");
+        }
+
+        #[test]
+        fn test_reflection_leaf_proof() {
+            let alice = Pubkey::new_unique();
+            let bob = Pubkey::new_unique();
+            let a = reflection_leaf_hash(1, &alice, 600);
+            let b = reflection_leaf_hash(1, &bob, 400);
+            let root = merkle_node_hash(&a, &b);
+
+            assert!(verify_merkle_proof(&root, a, 0, &[b]));
+            assert!(verify_merkle_proof(&root, b, 1, &[a]));
+            assert!(!verify_merkle_proof(&root, reflection_leaf_hash(1, &alice, 601), 0, &[b]), "Wrong amount");
+            assert!(!verify_merkle_proof(&root, reflection_leaf_hash(2, &alice, 600), 0, &[b]), "Stale epoch");
+        }
+    }
+
+    // ========================================================================
+    // 74. LIQUIDITY SEEDING TESTS
+    // ========================================================================
+
+    mod liquidity_tests {
+        use crate::state::{CyclePhase, CycleState, TokenStats};
+        use crate::MAX_LIQUIDITY_BPS;
+        use anchor_lang::prelude::Pubkey;
+
+        fn stats(liquidity_bps: u16) -> TokenStats {
+            TokenStats {
+                liquidity_bps,
+                ..super::token_stats_fixture()
+            }
+        }
+
+        fn cycle() -> CycleState {
+            CycleState {
+                mint: Pubkey::new_unique(),
+                phase: CyclePhase::Idle,
+                sequence: 0,
+                phase_updated_at: 0,
+                phase_slot: 0,
+                aborted_cycles: 0,
+                bump: 255,
+                rent_exempt_reserve: 0,
+                ata_rent_reserve: 0,
+                buy_venue: 0,
+                dev_fee_paid_sequence: 0,
+                liquidity_sequence: 0,
+            }
+        }
+
+        #[test]
+        fn test_liquidity_share() {
+            assert_eq!(stats(0).liquidity_share(1_000_000), 0, "Disabled");
+            assert_eq!(stats(MAX_LIQUIDITY_BPS).liquidity_share(1_000_000), 250_000);
+            assert_eq!(stats(MAX_LIQUIDITY_BPS).liquidity_share(u64::MAX), u64::MAX / 4, "No overflow");
+        }
+
+        #[test]
+        fn test_queued_liquidity_ratio_bounded() {
+            use crate::ADMIN_OP_LIQUIDITY_RATIO;
+            let mut s = stats(0);
+            assert!(s.apply_parameter(ADMIN_OP_LIQUIDITY_RATIO, MAX_LIQUIDITY_BPS as u64 + 1).is_err());
+            assert_eq!(s.apply_parameter(ADMIN_OP_LIQUIDITY_RATIO, MAX_LIQUIDITY_BPS as u64).unwrap(), 0);
+            assert_eq!(s.liquidity_bps, MAX_LIQUIDITY_BPS);
+        }
+
+        #[test]
+        fn test_liquidity_once_per_cycle_between_buy_and_burn() {
+            let mut c = cycle();
+            c.begin_collect(10, 1).unwrap();
+            assert!(c.mark_liquidity_provided().is_err(), "Nothing bought yet");
+            c.begin_buy(false, 1, 11, 2).unwrap();
+            c.mark_liquidity_provided().unwrap();
+            assert!(c.mark_liquidity_provided().is_err(), "Repeat within the same cycle");
+
+            c.complete_burn(12, 3).unwrap();
+            assert!(c.mark_liquidity_provided().is_err(), "Already burned");
+            c.begin_collect(13, 4).unwrap();
+            c.begin_buy(false, 1, 14, 5).unwrap();
+            c.mark_liquidity_provided().unwrap();
+            assert_eq!(c.liquidity_sequence, 2);
+        }
+    }
+
+    // ========================================================================
+    // 75. TREASURY RESERVE TESTS
+    // ========================================================================
+
+    mod reserve_vault_tests {
+        use crate::state::ReserveVault;
+        use crate::RESERVE_DEPLOY_TIMELOCK;
+        use anchor_lang::prelude::Pubkey;
+
+        fn vault() -> ReserveVault {
+            ReserveVault {
+                total_reserved: 5_000_000_000,
+                total_deployed: 0,
+                pending_recipient: Pubkey::default(),
+                pending_amount: 0,
+                pending_proposed_at: 0,
+                bump: 255,
+            }
+        }
+
+        #[test]
+        fn test_reserve_vault_size() {
+            assert_eq!(ReserveVault::LEN, 65, "ReserveVault size mismatch");
+        }
+
+        #[test]
+        fn test_no_pending_by_default() {
+            assert!(!vault().has_pending());
+        }
+
+        #[test]
+        fn test_executable_after_timelock() {
+            let mut v = vault();
+            v.pending_recipient = Pubkey::new_unique();
+            v.pending_amount = 1_000_000_000;
+            v.pending_proposed_at = 1_000;
+            assert!(v.has_pending());
+            assert_eq!(v.executable_at(RESERVE_DEPLOY_TIMELOCK), 1_000 + 48 * 3600);
+
+            v.pending_proposed_at = i64::MAX - 1;
+            assert_eq!(v.executable_at(RESERVE_DEPLOY_TIMELOCK), i64::MAX, "Saturates");
+        }
+
+        #[test]
+        fn test_clear_pending() {
+            let mut v = vault();
+            v.pending_recipient = Pubkey::new_unique();
+            v.pending_amount = 1;
+            v.pending_proposed_at = 100;
+            v.clear_pending();
+            assert!(!v.has_pending());
+            assert_eq!((v.pending_amount, v.pending_proposed_at), (0, 0));
+            assert_eq!(v.total_reserved, 5_000_000_000, "Totals untouched");
+        }
+    }
+
+    // ========================================================================
+    // 76. INSURANCE FUND TESTS
+    // ========================================================================
+
+    mod insurance_fund_tests {
+        use crate::state::InsuranceFund;
+        use crate::INSURANCE_PAYOUT_TIMELOCK;
+        use anchor_lang::prelude::Pubkey;
+
+        fn fund() -> InsuranceFund {
+            InsuranceFund {
+                guardian: Pubkey::new_unique(),
+                total_deposited: 0,
+                deposit_count: 0,
+                total_paid: 0,
+                payout_count: 0,
+                pending_recipient: Pubkey::default(),
+                pending_amount: 0,
+                pending_claim_hash: [0u8; 32],
+                pending_proposed_at: 0,
+                bump: 255,
+            }
+        }
+
+        #[test]
+        fn test_insurance_fund_size() {
+            assert_eq!(InsuranceFund::LEN, 141, "InsuranceFund size mismatch");
+        }
+
+        #[test]
+        fn test_deposit_accounting() {
+            let mut f = fund();
+            f.record_deposit(2_000_000);
+            f.record_deposit(3_000_000);
+            assert_eq!((f.total_deposited, f.deposit_count), (5_000_000, 2));
+
+            f.total_deposited = u64::MAX;
+            f.record_deposit(1);
+            assert_eq!(f.total_deposited, u64::MAX, "Saturates");
+        }
+
+        #[test]
+        fn test_pending_payout_lifecycle() {
+            let mut f = fund();
+            assert!(!f.has_pending());
+
+            f.pending_recipient = Pubkey::new_unique();
+            f.pending_amount = 1_000_000;
+            f.pending_claim_hash = [7u8; 32];
+            f.pending_proposed_at = 1_000;
+            assert!(f.has_pending());
+            assert_eq!(f.executable_at(INSURANCE_PAYOUT_TIMELOCK), 1_000 + 72 * 3600);
+
+            f.clear_pending();
+            assert!(!f.has_pending());
+            assert_eq!((f.pending_amount, f.pending_proposed_at), (0, 0));
+            assert_eq!(f.pending_claim_hash, [0u8; 32]);
+        }
+    }
+
+    // ========================================================================
+    // 77. COMMIT-REVEAL BUY TESTS
+    // ========================================================================
+
+    mod buy_commitment_tests {
+        use crate::state::BuyCommitment;
+        use crate::{BUY_REVEAL_MIN_DELAY_SLOTS, BUY_REVEAL_WINDOW_SLOTS};
+        use anchor_lang::prelude::Pubkey;
+
+        const SALT: [u8; 32] = [42u8; 32];
+
+        fn committed(amount: u64, slot: u64) -> BuyCommitment {
+            let mint = Pubkey::new_unique();
+            BuyCommitment {
+                mint,
+                commitment: BuyCommitment::hash(&mint, amount, &SALT),
+                committed_slot: slot,
+                bump: 255,
+            }
+        }
+
+        #[test]
+        fn test_buy_commitment_size() {
+            assert_eq!(BuyCommitment::LEN, 73, "BuyCommitment size mismatch");
+        }
+
+        #[test]
+        fn test_hash_binds_mint_amount_and_salt() {
+            let mint = Pubkey::new_unique();
+            let h = BuyCommitment::hash(&mint, 1_000_000, &SALT);
+            assert_ne!(h, BuyCommitment::hash(&Pubkey::new_unique(), 1_000_000, &SALT), "Other mint");
+            assert_ne!(h, BuyCommitment::hash(&mint, 1_000_001, &SALT), "Other amount");
+            assert_ne!(h, BuyCommitment::hash(&mint, 1_000_000, &[0u8; 32]), "Other salt");
+        }
+
+        #[test]
+        fn test_reveal_within_window_consumes_commitment() {
+            let mut c = committed(1_000_000, 100);
+            assert!(c.is_open());
+            c.reveal(1_000_000, &SALT, 100 + BUY_REVEAL_MIN_DELAY_SLOTS).unwrap();
+            assert!(!c.is_open());
+            assert!(c.reveal(1_000_000, &SALT, 102).is_err(), "Single use");
+        }
+
+        #[test]
+        fn test_reveal_slot_window() {
+            let mut c = committed(1_000_000, 100);
+            assert!(c.reveal(1_000_000, &SALT, 100).is_err(), "Same slot as commit");
+            assert!(c.reveal(1_000_000, &SALT, 100 + BUY_REVEAL_WINDOW_SLOTS + 1).is_err(), "Expired");
+            c.reveal(1_000_000, &SALT, 100 + BUY_REVEAL_WINDOW_SLOTS).unwrap();
+        }
+
+        #[test]
+        fn test_reveal_wrong_amount_or_salt() {
+            let mut c = committed(1_000_000, 100);
+            assert!(c.reveal(2_000_000, &SALT, 101).is_err(), "Other amount");
+            assert!(c.reveal(1_000_000, &[1u8; 32], 101).is_err(), "Other salt");
+            assert!(c.is_open(), "Failed reveals leave the commitment open");
+        }
+    }
+
+    // ========================================================================
+    // 78. VRF CYCLE WINDOW TESTS
+    // ========================================================================
+
+    mod cycle_window_tests {
+        use crate::state::CycleWindow;
+        use crate::{
+            parse_switchboard_randomness, CYCLE_RANDOMNESS_TIMEOUT, CYCLE_WINDOW_DURATION,
+            CYCLE_WINDOW_MIN_DELAY, CYCLE_WINDOW_SPREAD, SWITCHBOARD_RANDOMNESS_DISCRIMINATOR,
+        };
+        use anchor_lang::prelude::Pubkey;
+
+        const NOW: i64 = 1_700_000_000;
+
+        fn empty() -> CycleWindow {
+            CycleWindow {
+                mint: Pubkey::new_unique(),
+                randomness_account: Pubkey::default(),
+                seed_slot: 0,
+                requested_at: 0,
+                window_start: 0,
+                window_end: 0,
+                windows_settled: 0,
+                bump: 255,
+            }
+        }
+
+        fn randomness_data(seed_slot: u64, reveal_slot: u64, value: [u8; 32]) -> Vec<u8> {
+            let mut data = vec![0u8; 256];
+            data[..8].copy_from_slice(&SWITCHBOARD_RANDOMNESS_DISCRIMINATOR);
+            data[104..112].copy_from_slice(&seed_slot.to_le_bytes());
+            data[144..152].copy_from_slice(&reveal_slot.to_le_bytes());
+            data[152..184].copy_from_slice(&value);
+            data
+        }
+
+        #[test]
+        fn test_cycle_window_size() {
+            assert_eq!(CycleWindow::LEN, 105, "CycleWindow size mismatch");
+        }
+
+        #[test]
+        fn test_settle_places_window_within_spread() {
+            let mut w = empty();
+            w.request(Pubkey::new_unique(), 500, NOW).unwrap();
+            assert!(w.is_pending());
+
+            let mut value = [0u8; 32];
+            value[..8].copy_from_slice(&(CYCLE_WINDOW_SPREAD + 7).to_le_bytes());
+            w.settle(&value).unwrap();
+
+            assert_eq!(w.window_start, NOW + CYCLE_WINDOW_MIN_DELAY + 7);
+            assert_eq!(w.window_end, w.window_start + CYCLE_WINDOW_DURATION);
+            assert_eq!(w.windows_settled, 1);
+            assert!(!w.is_pending());
+            assert!(w.settle(&value).is_err(), "Settles once per request");
+        }
+
+        #[test]
+        fn test_contains_is_half_open() {
+            let mut w = empty();
+            assert!(!w.contains(NOW), "Unsettled window never contains");
+            w.request(Pubkey::new_unique(), 500, NOW).unwrap();
+            w.settle(&[0u8; 32]).unwrap();
+            assert!(!w.contains(w.window_start - 1));
+            assert!(w.contains(w.window_start));
+            assert!(!w.contains(w.window_end));
+        }
+
+        #[test]
+        fn test_window_cannot_be_rerolled() {
+            let mut w = empty();
+            w.request(Pubkey::new_unique(), 500, NOW).unwrap();
+            assert!(w.request(Pubkey::new_unique(), 501, NOW + 1).is_err(), "Pending request");
+            w.settle(&[0u8; 32]).unwrap();
+            assert!(w.request(Pubkey::new_unique(), 502, w.window_start).is_err(), "Open window");
+            w.request(Pubkey::new_unique(), 503, w.window_end).unwrap();
+        }
+
+        #[test]
+        fn test_unrevealed_request_times_out() {
+            let mut w = empty();
+            w.request(Pubkey::new_unique(), 500, NOW).unwrap();
+            assert!(!w.can_request(NOW + CYCLE_RANDOMNESS_TIMEOUT - 1));
+            w.request(Pubkey::new_unique(), 600, NOW + CYCLE_RANDOMNESS_TIMEOUT).unwrap();
+            assert_eq!(w.seed_slot, 600);
+        }
+
+        #[test]
+        fn test_parse_switchboard_randomness() {
+            let r = parse_switchboard_randomness(&randomness_data(500, 0, [0u8; 32])).unwrap();
+            assert_eq!(r.seed_slot, 500);
+            assert!(!r.is_revealed(), "Committed, not revealed");
+
+            let r = parse_switchboard_randomness(&randomness_data(500, 502, [9u8; 32])).unwrap();
+            assert!(r.is_revealed());
+            assert_eq!(r.value, [9u8; 32]);
+
+            let r = parse_switchboard_randomness(&randomness_data(600, 502, [9u8; 32])).unwrap();
+            assert!(!r.is_revealed(), "Stale reveal from an earlier commitment");
+        }
+
+        #[test]
+        fn test_parse_switchboard_randomness_rejects_bad_data() {
+            let mut data = randomness_data(500, 502, [9u8; 32]);
+            data[0] ^= 1;
+            assert!(parse_switchboard_randomness(&data).is_err(), "Wrong discriminator");
+            assert!(parse_switchboard_randomness(&randomness_data(500, 502, [9u8; 32])[..150]).is_err(), "Truncated");
+        }
+    }
+
+    // ========================================================================
+    // 79. KEEPER REGISTRY TESTS
+    // ========================================================================
+
+    mod keeper_tests {
+        use crate::state::KeeperInfo;
+        use anchor_lang::prelude::Pubkey;
+
+        fn keeper(successful_cycles: u64) -> KeeperInfo {
+            KeeperInfo {
+                keeper: Pubkey::new_unique(),
+                is_active: true,
+                registered_at: 1_700_000_000,
+                successful_cycles,
+                total_rewards: 0,
+                last_cycle_timestamp: 0,
+                bump: 255,
+            }
+        }
+
+        #[test]
+        fn test_keeper_info_size() {
+            assert_eq!(KeeperInfo::LEN, 66, "KeeperInfo size mismatch");
+        }
+
+        #[test]
+        fn test_reward_tiers() {
+            assert_eq!(keeper(0).reward_bps(), 10);
+            assert_eq!(keeper(99).reward_bps(), 10);
+            assert_eq!(keeper(100).reward_bps(), 20);
+            assert_eq!(keeper(999).reward_bps(), 20);
+            assert_eq!(keeper(1_000).reward_bps(), 30);
+            assert_eq!(keeper(u64::MAX).reward_bps(), 30);
+        }
+
+        #[test]
+        fn test_record_cycle_pays_tier_before_promotion() {
+            let mut k = keeper(99);
+            assert_eq!(k.record_cycle(1_000_000_000, 1_700_000_100), 1_000_000, "10 bps tier");
+            assert_eq!(k.successful_cycles, 100);
+            assert_eq!(k.last_cycle_timestamp, 1_700_000_100);
+            assert_eq!(k.record_cycle(1_000_000_000, 1_700_000_200), 2_000_000, "Promoted to 20 bps");
+        }
+
+        #[test]
+        fn test_record_cycle_no_overflow() {
+            let mut k = keeper(1_000);
+            assert_eq!(k.record_cycle(u64::MAX, 0), (u64::MAX as u128 * 30 / 10_000) as u64);
+            assert_eq!(k.record_cycle(0, 0), 0);
+        }
+    }
+
+    // ========================================================================
+    // 80. CYCLE LOOKUP TABLE TESTS
+    // ========================================================================
+
+    mod cycle_lut_tests {
+        use crate::constants::*;
+        use crate::helpers::{cycle_lut_static_addresses, derive_lookup_table_address, pump_creator_vault};
+        use anchor_lang::prelude::*;
+
+        #[test]
+        fn test_static_addresses_unique_and_complete() {
+            let (dat_state, dat_authority) = (Pubkey::new_unique(), Pubkey::new_unique());
+            let addresses = cycle_lut_static_addresses(&dat_state, &dat_authority);
+            let mut sorted = addresses.clone();
+            sorted.sort();
+            sorted.dedup();
+            assert_eq!(sorted.len(), addresses.len(), "No duplicate entries");
+            for key in [dat_state, dat_authority, pump_creator_vault(&dat_authority), PUMP_PROGRAM, PUMP_FEE_PROGRAM] {
+                assert!(addresses.contains(&key));
+            }
+            assert!(!addresses.contains(&crate::ID), "Invoked program stays in the message");
+        }
+
+        #[test]
+        fn test_lookup_table_address_depends_on_slot() {
+            let authority = Pubkey::new_unique();
+            let (a, _) = derive_lookup_table_address(&authority, 100);
+            let (b, _) = derive_lookup_table_address(&authority, 101);
+            assert_ne!(a, b);
+            assert_eq!(derive_lookup_table_address(&authority, 100).0, a);
+        }
+    }
+
+    // ========================================================================
+    // 81. ACCOUNT MIGRATION TESTS
+    // ========================================================================
+
+    mod migration_tests {
+        use crate::constants::*;
+        use crate::migrations::{apply_steps, AccountKind, MigrationEnv, MIGRATIONS};
+        use crate::state::{ChangeQueue, PendingChange, TokenStats, UserStats};
+        use anchor_lang::prelude::*;
+        use anchor_lang::Discriminator;
+
+        const KINDS: [AccountKind; 6] = [
+            AccountKind::DatState,
+            AccountKind::TokenStats,
+            AccountKind::TokenConfig,
+            AccountKind::RebatePool,
+            AccountKind::UserStats,
+            AccountKind::ChangeQueue,
+        ];
+
+        /// Oldest layout of `kind`, zero-extended to the current size
+        fn legacy(kind: AccountKind, size: usize) -> Vec<u8> {
+            let mut data = vec![0u8; kind.current_size()];
+            data[..8].copy_from_slice(kind.discriminator());
+            data.truncate(size);
+            data.resize(kind.current_size(), 0);
+            data
+        }
+
+        #[test]
+        fn test_steps_chain_to_current_layout() {
+            for kind in KINDS {
+                let oldest = MIGRATIONS.iter().find(|s| s.kind == kind).unwrap().from_size;
+                let steps = kind.steps_from(oldest).unwrap();
+                assert_eq!(steps.last().unwrap().to_size, kind.current_size(), "{:?} ends at 8 + LEN", kind);
+                assert!(steps.windows(2).all(|w| w[0].to_size == w[1].from_size));
+                assert!(steps.iter().all(|s| s.from_size < s.to_size));
+                assert_eq!(steps.len() + 1, kind.current_version() as usize, "{:?} version = layouts", kind);
+                assert!(kind.steps_from(kind.current_size()).unwrap().is_empty());
+            }
+        }
+
+        #[test]
+        fn test_unknown_size_rejected() {
+            assert!(AccountKind::TokenStats.steps_from(115).is_err());
+            assert!(AccountKind::UserStats.steps_from(8 + UserStats::LEN + 1).is_err());
+        }
+
+        #[test]
+        fn test_token_stats_v1_upgrade() {
+            let mut data = legacy(AccountKind::TokenStats, 114);
+            let mint = Pubkey::new_unique();
+            data[8..40].copy_from_slice(mint.as_ref());
+            data[80..88].copy_from_slice(&7u64.to_le_bytes()); // total_buybacks
+
+            let env = MigrationEnv { now: 1_000, mint_risk_flags: Some(MINT_RISK_FREEZE_AUTHORITY) };
+            let steps = apply_steps(AccountKind::TokenStats, &mut data, 114, &env).unwrap();
+            assert_eq!(steps + 1, TOKEN_STATS_VERSION as usize);
+
+            let stats: TokenStats = bytemuck::pod_read_unaligned(&data[8..]);
+            assert_eq!(&data[..8], TokenStats::DISCRIMINATOR);
+            assert_eq!(stats.mint, mint);
+            assert_eq!(stats.total_buybacks, 7);
+            assert_eq!(stats.last_fee_update_timestamp, 1_000);
+            assert_eq!(stats.cycles_participated, 7);
+            assert_eq!(stats.burn_bps, DEFAULT_BURN_BPS);
+            assert_eq!(stats.risk_flags, MINT_RISK_FREEZE_AUTHORITY);
+            assert_eq!(stats.category, UNCATEGORIZED);
+            assert_eq!((stats.registry_page, stats.in_registry), (0, 0), "Unregistered");
+            assert_eq!(stats.version, TOKEN_STATS_VERSION);
+        }
+
+        #[test]
+        fn test_token_stats_v12_relaid_out_for_zero_copy() {
+            // V12 Borsh offsets (after the discriminator)
+            let mut data = legacy(AccountKind::TokenStats, 358);
+            let (mint, partner) = (Pubkey::new_unique(), Pubkey::new_unique());
+            data[8..40].copy_from_slice(mint.as_ref());
+            data[40..48].copy_from_slice(&900u64.to_le_bytes()); // total_burned
+            data[112] = 1; // is_root_token
+            data[113] = 253; // bump
+            data[114..122].copy_from_slice(&5_000u64.to_le_bytes()); // pending_fees_lamports
+            data[138..140].copy_from_slice(&7500u16.to_le_bytes()); // burn_bps
+            data[148] = 0b101; // risk_flags
+            data[191] = 1; // is_paused
+            data[224] = 1; // payer_refunded
+            data[225..257].copy_from_slice(partner.as_ref());
+            data[257..259].copy_from_slice(&250u16.to_le_bytes()); // partner_share_bps
+            data[346] = 12; // version
+            data[347..349].copy_from_slice(&4u16.to_le_bytes()); // registry_page
+            data[349] = 1; // in_registry
+            data[350..358].copy_from_slice(&1_000u64.to_le_bytes()); // partner_paid_pending
+
+            assert_eq!(apply_steps(AccountKind::TokenStats, &mut data, 358, &MigrationEnv::default()).unwrap(), 1);
+            let stats: TokenStats = bytemuck::pod_read_unaligned(&data[8..]);
+            assert_eq!((stats.mint, stats.partner), (mint, partner));
+            assert_eq!((stats.total_burned, stats.pending_fees_lamports), (900, 5_000));
+            assert_eq!((stats.is_root_token, stats.bump, stats.is_paused, stats.payer_refunded), (1, 253, 1, 1));
+            assert_eq!((stats.burn_bps, stats.partner_share_bps, stats.risk_flags), (7500, 250, 0b101));
+            assert_eq!((stats.registry_page, stats.in_registry, stats.partner_paid_pending), (4, 1, 1_000));
+            assert_eq!(stats.version, TOKEN_STATS_VERSION);
+        }
+
+        #[test]
+        fn test_token_stats_v2_needs_mint() {
+            let mut data = legacy(AccountKind::TokenStats, 138);
+            assert!(apply_steps(AccountKind::TokenStats, &mut data, 138, &MigrationEnv::default()).is_err());
+
+            // Past V3 the mint is not needed
+            let mut data = legacy(AccountKind::TokenStats, 192);
+            assert!(apply_steps(AccountKind::TokenStats, &mut data, 192, &MigrationEnv::default()).is_ok());
+        }
+
+        #[test]
+        fn test_user_stats_v1_upgrade() {
+            let mut data = legacy(AccountKind::UserStats, 81);
+            apply_steps(AccountKind::UserStats, &mut data, 81, &MigrationEnv::default()).unwrap();
+
+            let stats = UserStats::try_deserialize(&mut &data[..]).unwrap();
+            assert!(stats.onboarding_bonus_claimed, "V1 users already deposited");
+            assert_eq!(stats.referrer, None);
+            assert_eq!(stats.version, USER_STATS_VERSION);
+        }
+
+        #[test]
+        fn test_change_queue_v1_slots_respaced() {
+            let mut data = legacy(AccountKind::ChangeQueue, 577);
+            let guardian = Pubkey::new_unique();
+            data[8..40].copy_from_slice(guardian.as_ref());
+            data[40..48].copy_from_slice(&3u64.to_le_bytes()); // next_id
+            // V1 slot 2: id 2, param 7, value 300, proposed_at 10, executable_at 3_610
+            let slot = 48 + 2 * PendingChange::V1_LEN;
+            data[slot..slot + 8].copy_from_slice(&2u64.to_le_bytes());
+            data[slot + 8] = 7;
+            data[slot + 9..slot + 17].copy_from_slice(&300u64.to_le_bytes());
+            data[slot + 17..slot + 25].copy_from_slice(&10i64.to_le_bytes());
+            data[slot + 25..slot + 33].copy_from_slice(&3_610i64.to_le_bytes());
+            data[576] = 254; // bump
+
+            assert_eq!(apply_steps(AccountKind::ChangeQueue, &mut data, 577, &MigrationEnv::default()).unwrap(), 1);
+            let q = ChangeQueue::try_deserialize(&mut &data[..]).unwrap();
+            assert_eq!((q.guardian, q.next_id, q.bump, q.version), (guardian, 3, 254, CHANGE_QUEUE_VERSION));
+            let c = q.changes[2];
+            assert_eq!((c.id, c.param, c.value, c.proposed_at, c.executable_at), (2, 7, 300, 10, 3_610));
+            assert_eq!(c.subject, Pubkey::default());
+            assert_eq!(q.changes.iter().filter(|c| c.param != 0).count(), 1);
+        }
+
+        #[test]
+        fn test_current_layout_only_stamped() {
+            let mut data = legacy(AccountKind::UserStats, 8 + UserStats::LEN);
+            assert_eq!(apply_steps(AccountKind::UserStats, &mut data, 8 + UserStats::LEN, &MigrationEnv::default()).unwrap(), 0);
+            assert_eq!(&data[..8], UserStats::DISCRIMINATOR);
+            assert_eq!(UserStats::try_deserialize(&mut &data[..]).unwrap().version, USER_STATS_VERSION);
+        }
+    }
+
+    // ========================================================================
+    // 82. TOKEN2022 TRANSFER FEE TESTS
+    // ========================================================================
+
+    mod transfer_fee_tests {
+        use crate::helpers::{calculate_transfer_fee, transfer_fee_for, MINT_BASE_LEN};
+
+        /// Token2022 mint with a TransferFeeConfig: older fee until `newer_epoch`, then newer
+        fn fee_mint(older: (u64, u16), newer_epoch: u64, newer: (u64, u16)) -> Vec<u8> {
+            let mut data = vec![0u8; 165];
+            data.push(1); // AccountType::Mint
+            data.extend_from_slice(&1u16.to_le_bytes());
+            data.extend_from_slice(&108u16.to_le_bytes());
+            data.extend_from_slice(&[0u8; 72]); // authorities + withheld_amount
+            for (epoch, (maximum_fee, bps)) in [(0, older), (newer_epoch, newer)] {
+                data.extend_from_slice(&u64::to_le_bytes(epoch));
+                data.extend_from_slice(&maximum_fee.to_le_bytes());
+                data.extend_from_slice(&bps.to_le_bytes());
+            }
+            data
+        }
+
+        #[test]
+        fn test_fee_rounds_up_and_caps() {
+            assert_eq!(calculate_transfer_fee(10_000, 100, u64::MAX), 100);
+            assert_eq!(calculate_transfer_fee(10_001, 100, u64::MAX), 101, "Rounded up");
+            assert_eq!(calculate_transfer_fee(1_000_000, 100, 500), 500, "Capped at maximum_fee");
+            assert_eq!(calculate_transfer_fee(1_000, 0, 500), 0);
+            assert_eq!(calculate_transfer_fee(0, 100, 500), 0);
+            assert_eq!(calculate_transfer_fee(u64::MAX, 10_000, u64::MAX), u64::MAX, "No overflow");
+        }
+
+        #[test]
+        fn test_fee_follows_epoch() {
+            let data = fee_mint((u64::MAX, 50), 10, (u64::MAX, 200));
+            assert_eq!(transfer_fee_for(&data, true, 9, 10_000).unwrap(), 50);
+            assert_eq!(transfer_fee_for(&data, true, 10, 10_000).unwrap(), 200);
+        }
+
+        #[test]
+        fn test_no_fee_without_extension() {
+            let data = fee_mint((u64::MAX, 50), 0, (u64::MAX, 50));
+            assert_eq!(transfer_fee_for(&data, false, 5, 10_000).unwrap(), 0, "SPL Token has no extensions");
+            assert_eq!(transfer_fee_for(&[0u8; MINT_BASE_LEN], true, 5, 10_000).unwrap(), 0);
+
+            // Other extension only (MintCloseAuthority)
+            let mut data = vec![0u8; 165];
+            data.push(1);
+            data.extend_from_slice(&3u16.to_le_bytes());
+            data.extend_from_slice(&32u16.to_le_bytes());
+            data.extend_from_slice(&[7u8; 32]);
+            assert_eq!(transfer_fee_for(&data, true, 5, 10_000).unwrap(), 0);
+        }
+
+        #[test]
+        fn test_truncated_fee_config_rejected() {
+            let mut data = fee_mint((u64::MAX, 50), 0, (u64::MAX, 50));
+            data.truncate(data.len() - 1);
+            assert!(transfer_fee_for(&data, true, 5, 10_000).is_err());
+        }
+
+        #[test]
+        fn test_deposit_split_net_of_fee() {
+            // 1% fee mint: each leg loses its own fee, the split is applied to what arrives
+            let amount = 1_000_000_000u64;
+            let burn_sent = amount * crate::BURN_SHARE as u64 / crate::SHARE_DENOMINATOR;
+            let rebate_sent = amount - burn_sent;
+            let burn_received = burn_sent - calculate_transfer_fee(burn_sent, 100, u64::MAX);
+            let rebate_received = rebate_sent - calculate_transfer_fee(rebate_sent, 100, u64::MAX);
+            assert_eq!(burn_received + rebate_received, 990_000_000);
+            assert!(burn_received + rebate_received <= amount - calculate_transfer_fee(amount, 100, u64::MAX));
+        }
+    }
+
+    // ========================================================================
+    // 83. TOKEN REGISTRY TESTS
+    // ========================================================================
+
+    mod token_registry_tests {
+        use crate::constants::TOKEN_REGISTRY_PAGE_SIZE;
+        use crate::state::{TokenRegistry, TokenRegistryPage};
+        use anchor_lang::prelude::Pubkey;
+
+        fn page() -> TokenRegistryPage {
+            TokenRegistryPage {
+                page: 0,
+                count: 0,
+                mints: [Pubkey::default(); TOKEN_REGISTRY_PAGE_SIZE],
+                bump: 255,
+            }
+        }
+
+        #[test]
+        fn test_registry_sizes() {
+            assert_eq!(TokenRegistry::LEN, 7);
+            assert_eq!(TokenRegistryPage::LEN, 2053);
+        }
+
+        #[test]
+        fn test_page_add_remove() {
+            let (a, b, c) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+            let mut p = page();
+            for mint in [a, b, c] {
+                p.add(mint).unwrap();
+            }
+            p.remove(&a).unwrap();
+            assert_eq!(p.active(), &[c, b], "Last entry moves into the freed slot");
+            assert_eq!(p.mints[2], Pubkey::default());
+            assert!(p.remove(&a).is_err(), "Mint not on the page");
+        }
+
+        #[test]
+        fn test_page_full() {
+            let mut p = page();
+            for _ in 0..TOKEN_REGISTRY_PAGE_SIZE {
+                p.add(Pubkey::new_unique()).unwrap();
+            }
+            assert!(p.add(Pubkey::new_unique()).is_err());
+        }
+
+        #[test]
+        fn test_new_page_only_when_all_full() {
+            let mut registry = TokenRegistry { page_count: 0, token_count: 0, bump: 255 };
+            assert!(registry.can_add_page(), "First page");
+            registry.page_count = 1;
+            assert!(!registry.can_add_page());
+            registry.token_count = TOKEN_REGISTRY_PAGE_SIZE as u32;
+            assert!(registry.can_add_page());
+        }
+    }
+
+    // ========================================================================
+    // 84. METAPLEX METADATA TESTS
+    // ========================================================================
+
+    mod metaplex_metadata_tests {
+        use crate::constants::MPL_UPDATE_METADATA_ACCOUNT_V2_DISCRIMINATOR;
+        use crate::helpers::{parse_metaplex_metadata, update_metadata_v2_data};
+        use anchor_lang::prelude::Pubkey;
+
+        fn string(data: &mut Vec<u8>, s: &str) {
+            data.extend_from_slice(&(s.len() as u32).to_le_bytes());
+            data.extend_from_slice(s.as_bytes());
+        }
+
+        /// Metadata with one creator, a collection and no uses (zero padded like on-chain)
+        fn metadata(update_authority: Pubkey, mint: Pubkey, is_mutable: bool) -> Vec<u8> {
+            let mut data = vec![4u8];
+            data.extend_from_slice(update_authority.as_ref());
+            data.extend_from_slice(mint.as_ref());
+            string(&mut data, "Old");
+            string(&mut data, "OLD");
+            string(&mut data, "https://old.example/meta.json");
+            data.extend_from_slice(&500u16.to_le_bytes());
+            data.extend_from_slice(&[1, 1, 0, 0, 0]);
+            data.extend_from_slice(&[9u8; 32]);
+            data.extend_from_slice(&[1, 100]);
+            data.push(0); // primary_sale_happened
+            data.push(is_mutable as u8);
+            data.extend_from_slice(&[1, 254]); // edition_nonce
+            data.push(0); // token_standard
+            data.extend_from_slice(&[1, 0]);
+            data.extend_from_slice(&[7u8; 32]);
+            data.push(0); // uses
+            data.resize(679, 0);
+            data
+        }
+
+        #[test]
+        fn test_parse_metadata() {
+            let (authority, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+            let data = metadata(authority, mint, true);
+            let parsed = parse_metaplex_metadata(&data).unwrap();
+            assert_eq!(parsed.update_authority, authority);
+            assert_eq!(parsed.mint, mint);
+            assert!(parsed.is_mutable);
+            assert_eq!(parsed.fee_and_creators.len(), 2 + 1 + 4 + 34);
+            assert_eq!(parsed.collection_and_uses.len(), 1 + 33 + 1);
+            assert!(!parse_metaplex_metadata(&metadata(authority, mint, false)).unwrap().is_mutable);
+        }
+
+        #[test]
+        fn test_invalid_metadata_rejected() {
+            let mut data = metadata(Pubkey::new_unique(), Pubkey::new_unique(), true);
+            assert!(parse_metaplex_metadata(&data[..120]).is_err(), "Truncated");
+            data[0] = 6;
+            assert!(parse_metaplex_metadata(&data).is_err(), "Not a MetadataV1 account");
+        }
+
+        #[test]
+        fn test_update_data_keeps_creators_and_collection() {
+            let data = metadata(Pubkey::new_unique(), Pubkey::new_unique(), true);
+            let parsed = parse_metaplex_metadata(&data).unwrap();
+            let ix = update_metadata_v2_data(&parsed, "New", "NEW", "ipfs://new");
+
+            let mut expected = vec![MPL_UPDATE_METADATA_ACCOUNT_V2_DISCRIMINATOR, 1];
+            string(&mut expected, "New");
+            string(&mut expected, "NEW");
+            string(&mut expected, "ipfs://new");
+            expected.extend_from_slice(parsed.fee_and_creators);
+            expected.extend_from_slice(parsed.collection_and_uses);
+            expected.extend_from_slice(&[0, 0, 0]);
+            assert_eq!(ix, expected);
+            assert_eq!(&ix[ix.len() - 38..ix.len() - 3], parsed.collection_and_uses);
+        }
+    }
+
+    // ========================================================================
+    // 85. BUY EVENT TESTS
+    // ========================================================================
+
+    mod buy_event_tests {
+        use crate::constants::VENUE_BONDING_CURVE;
+        use crate::events::BuyExecuted;
+        use anchor_lang::prelude::Pubkey;
+
+        #[test]
+        fn test_buy_event_reports_observed_spend() {
+            let mint = Pubkey::new_unique();
+            // max_sol_cost was 1 SOL; the curve only took 0.97 SOL for 31k tokens
+            let event = BuyExecuted::observed(
+                mint,
+                VENUE_BONDING_CURVE,
+                (5_000_000_000, 4_030_000_000),
+                (1_000, 32_000),
+                7,
+                1_700_000_000,
+            );
+            assert_eq!(event.sol_spent, 970_000_000, "Lamport delta, not the slippage cap");
+            assert_eq!(event.tokens_received, 31_000);
+            assert_eq!((event.mint, event.venue, event.sequence), (mint, VENUE_BONDING_CURVE, 7));
+        }
+
+        #[test]
+        fn test_buy_event_never_underflows() {
+            // Payer topped up mid-instruction: nothing reported as spent
+            let event = BuyExecuted::observed(Pubkey::default(), VENUE_BONDING_CURVE, (100, 150), (10, 10), 1, 0);
+            assert_eq!((event.sol_spent, event.tokens_received), (0, 0));
+        }
+    }
+
+    // ========================================================================
+    // 86. BATCH COLLECT ATTRIBUTION TESTS
+    // ========================================================================
+
+    mod collect_batch_tests {
+        use crate::attribute_collected;
+
+        #[test]
+        fn test_single_collect_split_by_pending_fees() {
+            // 4 SOL drained once from the shared vault, tokens attributed 1 and 3 SOL
+            let shares = attribute_collected(4_000_000_000, &[1_000_000_000, 3_000_000_000]);
+            assert_eq!(shares, vec![1_000_000_000, 3_000_000_000]);
+        }
+
+        #[test]
+        fn test_rounding_remainder_to_last_pending_token() {
+            let shares = attribute_collected(100, &[1, 1, 1, 0]);
+            assert_eq!(shares, vec![33, 33, 34, 0]);
+            assert_eq!(shares.iter().sum::<u64>(), 100);
+        }
+
+        #[test]
+        fn test_no_pending_fees_attributes_nothing() {
+            assert_eq!(attribute_collected(1_000, &[0, 0]), vec![0, 0]);
+            assert_eq!(attribute_collected(0, &[5, 7]), vec![0, 0]);
+        }
+
+        #[test]
+        fn test_no_overflow_at_extremes() {
+            let shares = attribute_collected(u64::MAX, &[u64::MAX, u64::MAX]);
+            assert_eq!(shares.iter().sum::<u64>(), u64::MAX);
+        }
+    }
 }
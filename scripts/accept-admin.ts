@@ -54,9 +54,10 @@ async function main() {
   // Fetch current state to verify pending_admin
   const state = await (program.account as any).datState.fetch(datState);
   console.log("\nCurrent admin:", state.admin.toString());
-  console.log("Pending admin:", state.pendingAdmin ? state.pendingAdmin.toString() : "None");
+  const hasPendingAdmin = !state.pendingAdmin.equals(PublicKey.default);
+  console.log("Pending admin:", hasPendingAdmin ? state.pendingAdmin.toString() : "None");
 
-  if (!state.pendingAdmin) {
+  if (!hasPendingAdmin) {
     console.log("\nNo pending admin transfer!");
     process.exit(1);
   }
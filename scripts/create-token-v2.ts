@@ -302,7 +302,7 @@ ${colors.magenta}Vanity Pool Integration:${colors.reset}
 
   try {
     const tx = await program.methods
-      .createPumpfunTokenV2(name, symbol, uri)
+      .createPumpfunTokenV2(name, symbol, uri, null) // no initial dev-buy
       .accounts({
         datState,
         datAuthority,
@@ -80,8 +80,10 @@ const DAT_STATE_SEED = Buffer.from('dat_v3');
 const DAT_AUTHORITY_SEED = Buffer.from('auth_v3');
 const TOKEN_STATS_SEED = Buffer.from('token_stats_v1');
 const ROOT_TREASURY_SEED = Buffer.from('root_treasury');
+const CYCLE_STATE_SEED = Buffer.from('cycle_v1');
 const USER_STATS_SEED = Buffer.from('user_stats_v1');
 const REBATE_POOL_SEED = Buffer.from('rebate_pool');
+const STAKE_SEED = Buffer.from('stake_v1');
 
 // Pump.fun / PumpSwap
 const PUMP_PROGRAM = new PublicKey('6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P');
@@ -94,7 +96,8 @@ const WSOL_MINT = new PublicKey('So11111111111111111111111111111111111111112');
 const GLOBAL_VOLUME_ACCUMULATOR = new PublicKey('Hq2wp8uJ9jCPsYgNHex8RtqdvMPfVGoYwjvF1ATiwn2Y');
 const ASSOCIATED_TOKEN_PROGRAM = new PublicKey('ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL');
 const PUMPSWAP_GLOBAL_CONFIG = new PublicKey('4wTV1YmiEkRvAtNtsSGPtUrqRYQMe5SKy2uB4Jjaxnjf');
-const PUMPSWAP_EVENT_AUTHORITY = new PublicKey('Ce6TQqeHC9p8KetsN6JsjHK7UTZk7nasjjnr7XxXp9F1');
+const PUMPSWAP_EVENT_AUTHORITY = new PublicKey('GS4CU59F31iL7aR2Q8zVS8DRrcRnXX1yjQ66TqNVQnaR');
+const PUMPSWAP_GLOBAL_VOLUME_ACCUMULATOR = new PublicKey('C2aFPdENg4A2HQsmrd5rTw5TaYBX5Ku887cWjbFKtZpw');
 
 // Dev sustainability wallet - receives 1% of secondary burns (99% burned)
 const DEV_WALLET = new PublicKey('dcW5uy7wKdKFxkhyBfPv3MyvrCkDcv1rWucoat13KH4');
@@ -232,7 +235,7 @@ async function executeTokenCycle(
     const state = await getTypedAccounts(program).datState.fetch(datState);
     const rootMint = state.rootTokenMint;
 
-    if (!rootMint) {
+    if (rootMint.equals(PublicKey.default)) {
       throw new Error('Root token not configured in DAT state');
     }
 
@@ -397,19 +400,18 @@ async function executeTokenCycle(
           datAuthority,
           datWsolAccount,
           wsolMint: WSOL_MINT,
+          payer: adminKeypair.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM,
           systemProgram: SystemProgram.programId,
         })
         .instruction();
 
       instructions.push(wrapIx);
 
-      // Step 4: Buy tokens with AMM
-      const desiredTokens = new BN(1_000_000); // 1M tokens minimum
-      const maxSolCost = new BN(solAllocation);
-
+      // Step 4: Buy tokens with AMM (quote derived on-chain from pool reserves)
       const buyIx = await program.methods
-        .executeBuyAmm(desiredTokens, maxSolCost)
+        .executeBuyAmm(new BN(solAllocation))
         .accounts({
           datState,
           datAuthority,
@@ -431,7 +433,7 @@ async function executeTokenCycle(
           pumpSwapProgram: PUMP_SWAP_PROGRAM,
           coinCreatorVaultAta,
           coinCreatorVaultAuthority,
-          globalVolumeAccumulator: GLOBAL_VOLUME_ACCUMULATOR,
+          globalVolumeAccumulator: PUMPSWAP_GLOBAL_VOLUME_ACCUMULATOR,
           userVolumeAccumulator: userVolumeAccumulatorAmm,
           feeConfig,
           feeProgram: FEE_PROGRAM,
@@ -479,11 +481,12 @@ async function executeTokenCycle(
       );
 
       const buyIx = await program.methods
-        .executeBuySecondary(new BN(solAllocation))
+        .executeBuySecondary(new BN(solAllocation), null)
         .accounts({
           datState,
           datAuthority,
           datAsdfAccount,
+          tokenStats,
           pool: token.bondingCurve,
           asdfMint: token.mint,
           poolAsdfAccount,
@@ -541,6 +544,10 @@ async function executeTokenCycle(
       .transferDevFee(new BN(secondaryShareLamports))
       .accounts({
         datState,
+        cycleState: PublicKey.findProgramAddressSync(
+          [CYCLE_STATE_SEED, token.mint.toBuffer()],
+          program.programId
+        )[0],
         datAuthority,
         devWallet: DEV_WALLET,
         systemProgram: SystemProgram.programId,
@@ -771,7 +778,9 @@ async function executeRootCycle(
             datAuthority,
             datWsolAccount,
             wsolMint: WSOL_MINT,
+            payer: adminKeypair.publicKey,
             tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM,
             systemProgram: SystemProgram.programId,
           })
           .instruction();
@@ -780,12 +789,9 @@ async function executeRootCycle(
         log('  📦', `Wrap instruction added: ${formatSOL(availableForWrap)} SOL → WSOL`, colors.cyan);
       }
 
-      // Step 2: Execute buy via AMM
-      const desiredTokens = new BN(1_000_000);
-      const maxSolCost = new BN(10_000_000_000); // 10 SOL max
-
+      // Step 2: Execute buy via AMM (budget capped on-chain by the DAT WSOL balance)
       const buyIx = await program.methods
-        .executeBuyAmm(desiredTokens, maxSolCost)
+        .executeBuyAmm(new BN(10_000_000_000))
         .accounts({
           datState,
           datAuthority,
@@ -807,7 +813,7 @@ async function executeRootCycle(
           pumpSwapProgram: PUMP_SWAP_PROGRAM,
           coinCreatorVaultAta,
           coinCreatorVaultAuthority,
-          globalVolumeAccumulator: GLOBAL_VOLUME_ACCUMULATOR,
+          globalVolumeAccumulator: PUMPSWAP_GLOBAL_VOLUME_ACCUMULATOR,
           userVolumeAccumulator,
           feeConfig,
           feeProgram: FEE_PROGRAM,
@@ -839,11 +845,12 @@ async function executeRootCycle(
       // Execute buy instruction
       log('  📦', 'Building buy instruction (100% buyback)...', colors.cyan);
       const buyIx = await program.methods
-        .executeBuy(null) // No allocated_lamports = use full balance
+        .executeBuy(null, null, null) // No allocated_lamports = use full balance, no price bound, no tip
         .accounts({
           datState,
           datAuthority,
           datAsdfAccount,
+          tokenStats,
           pool: poolAddress,
           asdfMint: rootToken.mint,
           poolAsdfAccount,
@@ -1019,7 +1026,14 @@ async function executeUserRebate(
       false
     );
 
-    // Calculate rebate amount (0.552% of pending)
+    // User's stake (boosts the rebate while locked) - optional account
+    const [stakePda] = PublicKey.findProgramAddressSync(
+      [STAKE_SEED, selectedUser.pubkey.toBuffer()],
+      program.programId
+    );
+    const stakeAccount = (await connection.getAccountInfo(stakePda)) ? stakePda : null;
+
+    // Calculate rebate amount (0.552% of pending, before any staking boost)
     const rebateAmount = calculateRebateAmount(selectedUser.pendingContribution);
     log('🎁', `Rebate amount: ${rebateAmount.toNumber() / 1e9} $ASDF`, colors.cyan);
 
@@ -1034,8 +1048,10 @@ async function executeUserRebate(
         rebatePoolAta,
         userStats: selectedUser.statsPda,
         user: selectedUser.pubkey,
+        stakeAccount,
         userAta,
         admin: adminKeypair.publicKey,
+        asdfMint,
         tokenProgram,
       })
       .signers([adminKeypair])
@@ -90,8 +90,8 @@ async function main() {
     log("📊", "Current state:", colors.cyan);
     const state = await getTypedAccounts(program).datState.fetch(datState);
     log("  ", `Admin: ${state.admin.toString()}`, colors.reset);
-    log("  ", `Active: ${state.isActive}`, colors.reset);
-    log("  ", `Emergency Pause: ${state.emergencyPause}`, colors.reset);
+    log("  ", `Active: ${state.isActive !== 0}`, colors.reset);
+    log("  ", `Emergency Pause: ${state.emergencyPause !== 0}`, colors.reset);
     process.exit(0);
   } catch {
     log("✅", "DAT State not initialized yet, proceeding...", colors.green);
@@ -123,15 +123,15 @@ async function main() {
     console.log(`${"=".repeat(70)}\n`);
 
     log("👤", `Admin: ${state.admin.toString()}`, colors.cyan);
-    log("🔧", `Active: ${state.isActive}`, colors.green);
-    log("🚨", `Emergency Pause: ${state.emergencyPause}`, colors.green);
+    log("🔧", `Active: ${state.isActive !== 0}`, colors.green);
+    log("🚨", `Emergency Pause: ${state.emergencyPause !== 0}`, colors.green);
     log("📊", `Fee Split BPS: ${state.feeSplitBps} (${state.feeSplitBps / 100}%)`, colors.cyan);
     log("⏱️ ", `Min Cycle Interval: ${state.minCycleInterval}s`, colors.cyan);
     log("💰", `Min Fees Threshold: ${state.minFeesThreshold.toNumber() / 1e9} SOL`, colors.cyan);
     log("💎", `Max Fees Per Cycle: ${state.maxFeesPerCycle.toNumber() / 1e9} SOL`, colors.cyan);
     log("📉", `Slippage BPS: ${state.slippageBps} (${state.slippageBps / 100}%)`, colors.cyan);
 
-    if (state.rootTokenMint) {
+    if (!state.rootTokenMint.equals(PublicKey.default)) {
       log("🏆", `Root Token: ${state.rootTokenMint.toString()}`, colors.magenta);
     } else {
       log("⚠️", "No root token set yet", colors.yellow);
@@ -27,6 +27,15 @@ import { getNetworkConfig, printNetworkBanner } from "../src/network/config";
 const PROGRAM_ID = new PublicKey("ASDFc5hkEM2MF8mrAAtCPieV6x6h1B5BwjgztFt7Xbui");
 const TOKEN_STATS_SEED = Buffer.from("token_stats_v1");
 const DAT_AUTHORITY_SEED = Buffer.from("auth_v3");
+const TOKEN_REGISTRY_SEED = Buffer.from("token_registry_v1");
+const TOKEN_REGISTRY_PAGE_SEED = Buffer.from("token_registry_page_v1");
+const TOKEN_REGISTRY_PAGE_SIZE = 64;
+
+function tokenRegistryPage(page: number): PublicKey {
+  const index = Buffer.alloc(2);
+  index.writeUInt16LE(page);
+  return PublicKey.findProgramAddressSync([TOKEN_REGISTRY_PAGE_SEED, index], PROGRAM_ID)[0];
+}
 
 async function main() {
   // Parse arguments
@@ -105,10 +114,33 @@ async function main() {
   } else {
     console.log("\n🔨 Creating TokenStats account...");
 
+    // The mint is listed on the first registry page with room; append a page when every page is full
+    const [tokenRegistry] = PublicKey.findProgramAddressSync([TOKEN_REGISTRY_SEED], PROGRAM_ID);
+    const registry = await (program.account as any).tokenRegistry.fetchNullable(tokenRegistry);
+    const pageCount: number = registry ? registry.pageCount : 0;
+    const pages = await (program.account as any).tokenRegistryPage.fetchMultiple(
+      Array.from({ length: pageCount }, (_, i) => tokenRegistryPage(i))
+    );
+    let page = pages.findIndex((p: any) => p && p.count < TOKEN_REGISTRY_PAGE_SIZE);
+    if (page < 0) {
+      page = pageCount;
+      console.log(`🔨 Creating token registry page ${page}...`);
+      await program.methods
+        .createTokenRegistryPage(page)
+        .accounts({
+          tokenRegistry,
+          tokenRegistryPage: tokenRegistryPage(page),
+          payer: payer.publicKey,
+        })
+        .rpc();
+    }
+
     const tx = await program.methods
       .initializeTokenStats()
       .accounts({
         tokenStats,
+        tokenRegistry,
+        tokenRegistryPage: tokenRegistryPage(page),
         mint: tokenMint,
         payer: payer.publicKey,
         systemProgram: PublicKey.default,
@@ -10,6 +10,10 @@ import path from "path";
 
 const PROGRAM_ID = new PublicKey("ASDFc5hkEM2MF8mrAAtCPieV6x6h1B5BwjgztFt7Xbui");
 
+// Must match DAT_STATE_VERSION / 8 + DATState::LEN of the deployed build
+const DAT_STATE_VERSION = 17;
+const DAT_STATE_SIZE = 641;
+
 function loadIdl(): any {
   const idlPath = path.join(__dirname, "../target/idl/asdf_burn_engine.json");
   const idl = JSON.parse(fs.readFileSync(idlPath, "utf-8"));
@@ -20,7 +24,7 @@ function loadIdl(): any {
 
 async function main() {
   console.log("\n" + "=".repeat(70));
-  console.log("MIGRATION DU DAT STATE (382 -> 390 bytes)");
+  console.log("MIGRATION DU DAT STATE (-> 641 bytes, version 18)");
   console.log("=".repeat(70) + "\n");
 
   const connection = new Connection("https://api.devnet.solana.com", "confirmed");
@@ -61,9 +65,9 @@ async function main() {
   }
 
   console.log("\nCurrent account size:", accountInfo.data.length, "bytes");
-  console.log("Target size: 390 bytes");
+  console.log("Target size:", DAT_STATE_SIZE, "bytes");
 
-  if (accountInfo.data.length >= 390) {
+  if (accountInfo.data.length >= DAT_STATE_SIZE) {
     console.log("\nAccount already migrated!");
     return;
   }
@@ -72,7 +76,7 @@ async function main() {
 
   try {
     const tx = await program.methods
-      .migrateDatState()
+      .migrateDatState(DAT_STATE_VERSION)
       .accounts({
         datState,
         admin: admin.publicKey,
@@ -101,10 +101,19 @@ async function main() {
 
   // Check current state
   log("\n📋", "État actuel:", colors.yellow);
+  // The replaced root's TokenStats is passed so set_root_token clears its root flag
+  let oldRootTokenStats: PublicKey | null = null;
   try {
     const state = await getTypedAccounts(program).datState.fetch(datState);
-    if (state.rootTokenMint) {
-      log("⚠️", `Root token déjà défini: ${state.rootTokenMint.toString()}`, colors.yellow);
+    const currentRoot = state.rootTokenMint.equals(PublicKey.default) ? null : state.rootTokenMint;
+    if (currentRoot && !currentRoot.equals(rootMint)) {
+      [oldRootTokenStats] = PublicKey.findProgramAddressSync(
+        [Buffer.from("token_stats_v1"), currentRoot.toBuffer()],
+        PROGRAM_ID
+      );
+    }
+    if (currentRoot) {
+      log("⚠️", `Root token déjà défini: ${currentRoot.toString()}`, colors.yellow);
       log("💡", `Fee split: ${state.feeSplitBps / 100}% keep, ${(10000 - state.feeSplitBps) / 100}% to root`, colors.yellow);
     } else {
       log("ℹ️", "Aucun root token défini", colors.cyan);
@@ -136,6 +145,7 @@ async function main() {
         datState,
         rootTokenStats,
         admin: admin.publicKey,
+        oldRootTokenStats,
       })
       .rpc();
 
@@ -150,7 +160,7 @@ async function main() {
     console.log(`${colors.bright}${colors.green}📊 CONFIGURATION ROOT TOKEN${colors.reset}`);
     console.log(`${"=".repeat(70)}\n`);
 
-    log("🏆", `Root Token: ${updatedState.rootTokenMint?.toString() ?? 'Not set'}`, colors.green);
+    log("🏆", `Root Token: ${updatedState.rootTokenMint.equals(PublicKey.default) ? 'Not set' : updatedState.rootTokenMint.toString()}`, colors.green);
     log("📊", `Fee Split: ${updatedState.feeSplitBps / 100}% keep, ${(10000 - updatedState.feeSplitBps) / 100}% to root`, colors.green);
     log("", "", colors.reset);
     log("💡", "Les tokens secondaires enverront maintenant 44.8% de leurs fees au root treasury", colors.cyan);
@@ -48,6 +48,9 @@ export const TOKEN_STATS_SEED = Buffer.from('token_stats_v1');
 /** Root Treasury PDA seed */
 export const ROOT_TREASURY_SEED = Buffer.from('root_treasury');
 
+/** Cycle State PDA seed */
+export const CYCLE_STATE_SEED = Buffer.from('cycle_v1');
+
 /** Validator State PDA seed */
 export const VALIDATOR_STATE_SEED = Buffer.from('validator_v1');
 
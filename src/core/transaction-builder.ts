@@ -24,6 +24,7 @@ import {
   DAT_AUTHORITY_SEED,
   TOKEN_STATS_SEED,
   ROOT_TREASURY_SEED,
+  CYCLE_STATE_SEED,
   PUMP_PROGRAM,
   PUMPSWAP_PROGRAM,
   WSOL_MINT,
@@ -54,6 +55,9 @@ const PUMP_GLOBAL_CONFIG = new PublicKey(
 const PUMP_EVENT_AUTHORITY = new PublicKey(
   "Ce6TQqeHC9p8KetsN6JsjHK7UTZk7nasjjnr7XxXp9F1"
 );
+const PUMPSWAP_EVENT_AUTHORITY = new PublicKey(
+  "GS4CU59F31iL7aR2Q8zVS8DRrcRnXX1yjQ66TqNVQnaR"
+);
 const FEE_PROGRAM = new PublicKey(
   "pfeeUxB6jkeY1Hxd7CsFCAjcbHA9rWtchMGdZ6VojVZ"
 );
@@ -178,6 +182,7 @@ export class TransactionBuilder {
           tokenStats,
           datTokenAccount,
           tokenProgram,
+          adminPubkey,
         }))
       );
     } else {
@@ -222,6 +227,7 @@ export class TransactionBuilder {
       await this.buildDevFeeInstruction({
         datState,
         datAuthority,
+        tokenMint: token.mint,
         allocation,
       })
     );
@@ -296,6 +302,7 @@ export class TransactionBuilder {
           datTokenAccount,
           tokenProgram,
           rootTreasury,
+          adminPubkey,
         }))
       );
     } else {
@@ -443,11 +450,12 @@ export class TransactionBuilder {
 
     // Buy instruction
     const buyIx = await this.program.methods
-      .executeBuySecondary(new BN(allocation.toString()))
+      .executeBuySecondary(new BN(allocation.toString()), null)
       .accounts({
         datState,
         datAuthority,
         datAsdfAccount: datTokenAccount,
+        tokenStats,
         pool: token.bondingCurve,
         asdfMint: token.mint,
         poolAsdfAccount: poolTokenAccount,
@@ -483,9 +491,10 @@ export class TransactionBuilder {
     tokenStats: PublicKey;
     datTokenAccount: PublicKey;
     tokenProgram: PublicKey;
+    adminPubkey: PublicKey;
   }): Promise<TransactionInstruction[]> {
     const instructions: TransactionInstruction[] = [];
-    const { token, allocation, datState, datAuthority, tokenStats, datTokenAccount, tokenProgram } = params;
+    const { token, allocation, datState, datAuthority, tokenStats, datTokenAccount, tokenProgram, adminPubkey } = params;
 
     // DAT's WSOL account
     const [datWsolAccount] = PublicKey.findProgramAddressSync(
@@ -570,19 +579,18 @@ export class TransactionBuilder {
         datAuthority,
         datWsolAccount,
         wsolMint: WSOL_MINT,
+        payer: adminPubkey,
         tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM,
         systemProgram: SystemProgram.programId,
       })
       .instruction();
 
     instructions.push(wrapIx);
 
-    // Step 4: Buy tokens via AMM
-    const desiredTokens = new BN(1_000_000); // Minimum, actual determined by CPI
-    const maxSolCost = new BN(allocation.toString());
-
+    // Step 4: Buy tokens via AMM (desired tokens and max cost derived on-chain from pool reserves)
     const buyIx = await this.program.methods
-      .executeBuyAmm(desiredTokens, maxSolCost)
+      .executeBuyAmm(new BN(allocation.toString()))
       .accounts({
         datState,
         datAuthority,
@@ -600,7 +608,7 @@ export class TransactionBuilder {
         quoteTokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM,
-        eventAuthority: PUMP_EVENT_AUTHORITY,
+        eventAuthority: PUMPSWAP_EVENT_AUTHORITY,
         pumpSwapProgram: PUMPSWAP_PROGRAM,
         coinCreatorVaultAta: creatorVaultAta,
         coinCreatorVaultAuthority: creatorVaultAuthority,
@@ -691,11 +699,12 @@ export class TransactionBuilder {
     // ROOT TOKEN: Use executeBuy (not executeBuySecondary) - 100% burn, no split
     // Note: execute_buy does NOT take rootTreasury (that's for secondaries only)
     const buyIx = await this.program.methods
-      .executeBuy(new BN(allocation.toString()))
+      .executeBuy(new BN(allocation.toString()), null, null)
       .accounts({
         datState,
         datAuthority,
         datAsdfAccount: datTokenAccount,
+        tokenStats,
         pool: token.bondingCurve,
         asdfMint: token.mint,
         poolAsdfAccount: poolTokenAccount,
@@ -727,6 +736,7 @@ export class TransactionBuilder {
     datTokenAccount: PublicKey;
     tokenProgram: PublicKey;
     rootTreasury: PublicKey;
+    adminPubkey: PublicKey;
   }): Promise<TransactionInstruction[]> {
     // Similar to secondary AMM but for root token
     // For now, use same pattern - can be specialized if needed
@@ -738,6 +748,7 @@ export class TransactionBuilder {
       tokenStats: params.tokenStats,
       datTokenAccount: params.datTokenAccount,
       tokenProgram: params.tokenProgram,
+      adminPubkey: params.adminPubkey,
     });
   }
 
@@ -785,6 +796,7 @@ export class TransactionBuilder {
   private async buildDevFeeInstruction(params: {
     datState: PublicKey;
     datAuthority: PublicKey;
+    tokenMint: PublicKey;
     allocation: bigint;
   }): Promise<TransactionInstruction> {
     // 1% dev fee from secondary share (55.2% × 1% = 0.552% of total)
@@ -797,6 +809,10 @@ export class TransactionBuilder {
       .transferDevFee(new BN(secondaryShareLamports))
       .accounts({
         datState: params.datState,
+        cycleState: PublicKey.findProgramAddressSync(
+          [CYCLE_STATE_SEED, params.tokenMint.toBuffer()],
+          this.program.programId
+        )[0],
         datAuthority: params.datAuthority,
         devWallet: DEV_WALLET,
         systemProgram: SystemProgram.programId,
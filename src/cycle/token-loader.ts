@@ -354,8 +354,8 @@ export class TokenLoader {
       let rootTokenMint: PublicKey | null = null;
       try {
         const datState = await getTypedAccounts(program).datState.fetch(datStatePda);
-        rootTokenMint = datState.rootTokenMint;
-        if (rootTokenMint) {
+        if (!datState.rootTokenMint.equals(PublicKey.default)) {
+          rootTokenMint = datState.rootTokenMint;
           log(
             '✓',
             `Root token from DATState: ${rootTokenMint.toBase58().slice(0, 8)}...`,
@@ -320,7 +320,7 @@ export class Daemon {
       // Fetch DATState using Anchor
       const datState = await (program.account as any).datState.fetch(datStatePda);
 
-      if (datState && datState.rootTokenMint) {
+      if (datState && !datState.rootTokenMint.equals(PublicKey.default)) {
         const rootMint = datState.rootTokenMint as PublicKey;
 
         log.info("Root token loaded from DATState", {
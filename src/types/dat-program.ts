@@ -25,8 +25,8 @@ export interface DATState {
   totalBuybacks: number;
   failedCycles: number;
   consecutiveFailures: number;
-  isActive: boolean;
-  emergencyPause: boolean;
+  isActive: number;        // 0 / 1 (zero-copy account flag)
+  emergencyPause: number;  // 0 / 1
   lastCycleTimestamp: BN;
   initializedAt: BN;
   lastAmExecution: BN;
@@ -41,12 +41,12 @@ export interface DATState {
   currentFeeRecipientIndex: number;
   lastKnownPrice: BN;
   pendingBurnAmount: BN;
-  rootTokenMint: PublicKey | null;
+  rootTokenMint: PublicKey;  // PublicKey.default when unset
   feeSplitBps: number;
   lastSolSentToRoot: BN;
   // Security audit additions (v2)
-  pendingAdmin: PublicKey | null;
-  pendingFeeSplit: number | null;
+  pendingAdmin: PublicKey;   // PublicKey.default when none
+  pendingFeeSplit: number;   // 0 when none
   pendingFeeSplitTimestamp: BN;
   adminOperationCooldown: BN;
 }
@@ -66,7 +66,7 @@ export interface TokenStats {
   lastCycleTimestamp: BN;
   lastCycleSol: BN;
   lastCycleBurned: BN;
-  isRootToken: boolean;
+  isRootToken: number;  // 0 / 1 (zero-copy account flag)
   bump: number;
   pendingFeesLamports: BN;
   lastFeeUpdateTimestamp: BN;
@@ -269,7 +269,7 @@ export async function captureState(
 
   // Get root treasury balance
   let rootTreasuryBalance = 0;
-  if (datState.rootTokenMint) {
+  if (!datState.rootTokenMint.equals(PublicKey.default)) {
     const [treasury] = PublicKey.findProgramAddressSync(
       [Buffer.from('root_treasury'), datState.rootTokenMint.toBuffer()],
       PROGRAM_ID